@@ -0,0 +1,124 @@
+//! Generated datasets shared by the benchmarks.
+//!
+//! The number of rows can be set with the `POLARS_BENCH_SIZE` env var.
+#![allow(dead_code)]
+use polars::prelude::*;
+use polars::rand::{Distribution, RandomGenerator};
+
+const DEFAULT_SIZE: usize = 100_000;
+
+/// Number of rows of the generated datasets.
+pub fn bench_size() -> usize {
+    std::env::var("POLARS_BENCH_SIZE")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_SIZE)
+}
+
+/// Draw `n` keys `id000`, `id001`, .. with equal probability.
+fn str_keys(n: usize) -> Distribution {
+    Distribution::Categorical {
+        categories: (0..n).map(|i| format!("id{:03}", i)).collect(),
+        weights: vec![1.0; n],
+    }
+}
+
+fn int_keys(n: usize) -> Distribution {
+    Distribution::UniformInt {
+        low: 0,
+        high: n as i64,
+    }
+}
+
+/// Create a DataFrame with the same layout as the h2oai groupby benchmark.
+///
+/// * id1, id2, id3 - Utf8 keys with low, medium and high cardinality
+/// * id4, id5, id6 - Int32 keys with low, medium and high cardinality
+/// * v1, v2 - Int32 values
+/// * v3 - Float64 values
+pub fn groupby_df(size: usize, null_percentage: f64) -> DataFrame {
+    let mut gen = RandomGenerator::new(Some(0));
+    let k = 100;
+    let n_high = std::cmp::max(size / k, 1);
+
+    let values = Distribution::UniformInt { low: 1, high: 6 };
+    let v3 = Distribution::Uniform {
+        low: 0.0,
+        high: 100.0,
+    };
+    let mut df = gen
+        .frame(
+            size,
+            &[
+                ("id1", str_keys(k)),
+                ("id2", str_keys(k)),
+                ("id3", str_keys(n_high)),
+                ("id4", int_keys(k)),
+                ("id5", int_keys(k)),
+                ("id6", int_keys(n_high)),
+                ("v1", values.clone()),
+                ("v2", values),
+                ("v3", v3),
+            ],
+        )
+        .unwrap();
+
+    for name in &["id1", "id2", "id3"] {
+        df.may_apply(name, |s| s.cast::<Utf8Type>()).unwrap();
+    }
+    for name in &["id4", "id5", "id6"] {
+        df.may_apply(name, |s| s.cast::<Int32Type>()).unwrap();
+    }
+    for name in &["v1", "v2", "v3"] {
+        df.may_apply(name, |s| {
+            let s = gen.with_nulls(s, null_percentage)?;
+            match s.dtype() {
+                DataType::Int64 => s.cast::<Int32Type>(),
+                _ => Ok(s),
+            }
+        })
+        .unwrap();
+    }
+    df
+}
+
+/// Create a left and a right table that share the `key` column.
+///
+/// The right table has `size / 10` rows with unique keys, so the join is a
+/// many to one join with a selectivity of roughly `match_fraction`.
+pub fn join_dfs(size: usize, match_fraction: f64) -> (DataFrame, DataFrame) {
+    let mut gen = RandomGenerator::new(Some(0));
+    let n_right = std::cmp::max(size / 10, 1);
+    // draw left keys from a range that only overlaps the right keys for `match_fraction`
+    let upper = (n_right as f64 / match_fraction.max(f64::EPSILON)) as u32;
+    let val = Distribution::Uniform {
+        low: 0.0,
+        high: 1.0,
+    };
+
+    let mut left = gen
+        .frame(
+            size,
+            &[("key", int_keys(upper as usize)), ("left_val", val.clone())],
+        )
+        .unwrap();
+    left.may_apply("key", |s| s.cast::<UInt32Type>()).unwrap();
+
+    let right_key: NoNull<UInt32Chunked> = (0..n_right as u32).collect();
+    let mut right_key = right_key.into_inner().into_series();
+    right_key.rename("key");
+    let right_val = gen.series("right_val", n_right, &val).unwrap();
+
+    (left, DataFrame::new(vec![right_key, right_val]).unwrap())
+}
+
+/// Serialize a DataFrame to an in memory csv file.
+pub fn to_csv_bytes(df: &DataFrame) -> Vec<u8> {
+    let mut buf = Vec::new();
+    let mut df = df.clone();
+    CsvWriter::new(&mut buf)
+        .has_headers(true)
+        .finish(&mut df)
+        .expect("could not write csv");
+    buf
+}
@@ -0,0 +1,35 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use polars::prelude::*;
+
+mod data;
+
+fn bench_filter(c: &mut Criterion) {
+    let df = data::groupby_df(data::bench_size(), 0.05);
+
+    c.bench_function("filter eager numeric", |b| {
+        b.iter(|| {
+            let mask = df.column("v3").unwrap().gt(50.0);
+            df.filter(&mask).unwrap()
+        })
+    });
+
+    c.bench_function("filter eager utf8", |b| {
+        b.iter(|| {
+            let mask = df.column("id1").unwrap().eq("id001");
+            df.filter(&mask).unwrap()
+        })
+    });
+
+    c.bench_function("filter lazy", |b| {
+        b.iter(|| {
+            df.clone()
+                .lazy()
+                .filter(col("v1").gt(lit(2)).and(col("v3").lt(lit(50.0))))
+                .collect()
+                .unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, bench_filter);
+criterion_main!(benches);
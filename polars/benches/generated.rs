@@ -0,0 +1,58 @@
+//! Groupby and csv parsing benchmarks that don't depend on an external file.
+use criterion::{criterion_group, criterion_main, Criterion};
+use polars::prelude::*;
+use std::io::Cursor;
+
+mod data;
+
+fn bench_groupby(c: &mut Criterion) {
+    let df = data::groupby_df(data::bench_size(), 0.05);
+
+    c.bench_function("groupby generated utf8 key", |b| {
+        b.iter(|| {
+            df.clone()
+                .lazy()
+                .groupby(vec![col("id1")])
+                .agg(vec![col("v1").sum()])
+                .collect()
+                .unwrap()
+        })
+    });
+    c.bench_function("groupby generated int high cardinality", |b| {
+        b.iter(|| {
+            df.clone()
+                .lazy()
+                .groupby(vec![col("id6")])
+                .agg(vec![col("v1").sum(), col("v3").mean()])
+                .collect()
+                .unwrap()
+        })
+    });
+    c.bench_function("groupby generated 2 keys", |b| {
+        b.iter(|| {
+            df.clone()
+                .lazy()
+                .groupby(vec![col("id1"), col("id4")])
+                .agg(vec![col("v3").median()])
+                .collect()
+                .unwrap()
+        })
+    });
+}
+
+fn bench_csv_parse(c: &mut Criterion) {
+    let df = data::groupby_df(data::bench_size(), 0.05);
+    let bytes = data::to_csv_bytes(&df);
+
+    c.bench_function("parse generated csv", |b| {
+        b.iter(|| {
+            CsvReader::new(Cursor::new(bytes.clone()))
+                .has_header(true)
+                .finish()
+                .unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, bench_groupby, bench_csv_parse);
+criterion_main!(benches);
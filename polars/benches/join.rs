@@ -0,0 +1,66 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use polars::prelude::*;
+
+mod data;
+
+fn bench_join(c: &mut Criterion) {
+    let size = data::bench_size();
+    let mut group = c.benchmark_group("join");
+
+    for &fraction in &[0.1, 0.5, 1.0] {
+        let (left, right) = data::join_dfs(size, fraction);
+
+        group.bench_with_input(BenchmarkId::new("inner", fraction), &fraction, |b, _| {
+            b.iter(|| left.inner_join(&right, "key", "key").unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("left", fraction), &fraction, |b, _| {
+            b.iter(|| left.left_join(&right, "key", "key").unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("outer", fraction), &fraction, |b, _| {
+            b.iter(|| left.outer_join(&right, "key", "key").unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_join_multiple_keys(c: &mut Criterion) {
+    let df = data::groupby_df(data::bench_size(), 0.0);
+    let right = df
+        .drop_duplicates(true, Some(&["id1".to_string(), "id4".to_string()]))
+        .unwrap()
+        .select(&["id1", "id4", "v3"])
+        .unwrap();
+
+    c.bench_function("join inner 2 keys", |b| {
+        b.iter(|| {
+            df.join(&right, &["id1", "id4"], &["id1", "id4"], JoinType::Inner)
+                .unwrap()
+        })
+    });
+}
+
+fn bench_join_utf8_keys(c: &mut Criterion) {
+    let df = data::groupby_df(data::bench_size(), 0.0);
+    let mut group = c.benchmark_group("join utf8 keys");
+
+    // id1 has few distinct keys and is interned before hashing, id3 has many and is not
+    for key in &["id1", "id3"] {
+        let right = df
+            .drop_duplicates(true, Some(&[key.to_string()]))
+            .unwrap()
+            .select(&[*key, "v3"])
+            .unwrap();
+        group.bench_with_input(BenchmarkId::new("inner", key), key, |b, key| {
+            b.iter(|| df.inner_join(&right, key, key).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_join,
+    bench_join_multiple_keys,
+    bench_join_utf8_keys
+);
+criterion_main!(benches);
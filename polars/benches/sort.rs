@@ -0,0 +1,20 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use polars::prelude::*;
+
+mod data;
+
+fn bench_sort(c: &mut Criterion) {
+    let df = data::groupby_df(data::bench_size(), 0.05);
+
+    c.bench_function("sort int", |b| b.iter(|| df.sort("id6", false).unwrap()));
+    c.bench_function("sort float with nulls", |b| {
+        b.iter(|| df.sort("v3", true).unwrap())
+    });
+    c.bench_function("sort utf8", |b| b.iter(|| df.sort("id3", false).unwrap()));
+    c.bench_function("sort lazy", |b| {
+        b.iter(|| df.clone().lazy().sort("v3", false).collect().unwrap())
+    });
+}
+
+criterion_group!(benches, bench_sort);
+criterion_main!(benches);
@@ -56,6 +56,7 @@ impl ChunkedBuilder<bool, BooleanType> for BooleanChunkedBuilder {
             chunk_id: vec![len],
             phantom: PhantomData,
             categorical_map: None,
+            sorted: Default::default(),
         }
     }
 }
@@ -103,6 +104,7 @@ where
             chunk_id: vec![len],
             phantom: PhantomData,
             categorical_map: None,
+            sorted: Default::default(),
         }
     }
 }
@@ -133,7 +135,7 @@ impl CategoricalChunkedBuilder {
 
         CategoricalChunkedBuilder {
             array_builder: PrimitiveArrayBuilder::<UInt32Type>::new(capacity),
-            field: Field::new(name, DataType::Categorical),
+            field: Field::new(name, DataType::Categorical(CategoricalOrdering::Physical)),
             mapping,
             reverse_mapping,
         }
@@ -234,6 +236,7 @@ impl ChunkedBuilder<&str, CategoricalType> for CategoricalChunkedBuilder {
             chunk_id: vec![len],
             phantom: PhantomData,
             categorical_map: Some(Arc::new(self.reverse_mapping)),
+            sorted: Default::default(),
         }
     }
 }
@@ -285,6 +288,7 @@ impl Utf8ChunkedBuilder {
             chunk_id: vec![len],
             phantom: PhantomData,
             categorical_map: None,
+            sorted: Default::default(),
         }
     }
 }
@@ -501,6 +505,7 @@ where
             chunk_id: vec![v.len()],
             phantom: PhantomData,
             categorical_map: None,
+            sorted: Default::default(),
         }
     }
 
@@ -559,6 +564,7 @@ macro_rules! finish_list_builder {
             chunk_id: vec![len],
             phantom: PhantomData,
             categorical_map: None,
+            sorted: Default::default(),
         }
     }};
 }
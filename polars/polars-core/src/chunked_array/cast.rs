@@ -3,9 +3,13 @@ use crate::chunked_array::builder::CategoricalChunkedBuilder;
 use crate::chunked_array::kernels::{cast_numeric_from_dtype, transmute_array_from_dtype};
 use crate::prelude::*;
 use crate::use_string_cache;
+use ahash::AHashMap;
 use arrow::compute::cast;
 use num::{NumCast, ToPrimitive};
 
+/// Number of milliseconds in a day, used to convert between Date32 and Date64.
+const MILLISECONDS_IN_DAY: i64 = 86_400_000;
+
 fn cast_ca<N, T>(ca: &ChunkedArray<T>) -> Result<ChunkedArray<N>>
 where
     N: PolarsDataType,
@@ -93,7 +97,7 @@ where
     {
         let ca = match T::get_dtype() {
             DataType::UInt32 => match N::get_dtype() {
-                DataType::Categorical => {
+                DataType::Categorical(_) => {
                     let ca: ChunkedArray<N> = unsafe { std::mem::transmute(self.clone()) };
                     Ok(ca)
                 }
@@ -117,6 +121,14 @@ where
                     DataType::Float64 => {
                         cast_from_dtype!(self, cast_numeric_from_dtype, Float64)
                     }
+                    // a millisecond duration is an offset from the unix epoch
+                    DataType::Date64
+                        if T::get_dtype() == DataType::Duration(TimeUnit::Millisecond) =>
+                    {
+                        let ca: Int64Chunked =
+                            unsafe { cast_from_dtype!(self, transmute_array_from_dtype, Int64) }?;
+                        cast_ca(&ca)
+                    }
                     _ => cast_ca(self),
                 }
             }
@@ -145,6 +157,14 @@ where
             }
             DataType::Date64 => {
                 match N::get_dtype() {
+                    // round down to the day the timestamp falls in, also before the epoch
+                    DataType::Date32 => {
+                        let ca: Date32Chunked = self
+                            .cast::<Date64Type>()
+                            .unwrap()
+                            .apply_cast_numeric(|v| v.div_euclid(MILLISECONDS_IN_DAY) as i32);
+                        Ok(unsafe { std::mem::transmute(ca) })
+                    }
                     // underlying type: i32
                     DataType::Int32 => {
                         cast_from_dtype!(self, cast_numeric_from_dtype, Int32)
@@ -159,7 +179,12 @@ where
                     #[cfg(feature = "temporal")]
                     DataType::Utf8 => {
                         let ca: ChunkedArray<N> = unsafe {
-                            std::mem::transmute(self.cast::<Date64Type>().unwrap().str_fmt("%+"))
+                            // the same format as the Display of Date64 values
+                            std::mem::transmute(
+                                self.cast::<Date64Type>()
+                                    .unwrap()
+                                    .str_fmt("%Y-%m-%d %H:%M:%S%.f"),
+                            )
                         };
                         Ok(ca)
                     }
@@ -188,13 +213,64 @@ macro_rules! impl_chunkcast {
     };
 }
 
+impl Utf8Chunked {
+    /// Cast to a Categorical with a closed set of categories.
+    ///
+    /// The u32 indexes of the categories follow the order of `categories`, so sorting and comparing
+    /// the result follows the declared order. The categories are part of the data type, see
+    /// [CategoricalOrdering::Enum]. Values that are not in `categories` return an error if
+    /// `strict`, otherwise they become null.
+    pub fn cast_to_enum(&self, categories: &[&str], strict: bool) -> Result<CategoricalChunked> {
+        let mut mapping = AHashMap::with_capacity(categories.len());
+        for (idx, cat) in categories.iter().enumerate() {
+            if mapping.insert(*cat, idx as u32).is_some() {
+                return Err(PolarsError::Duplicate(
+                    format!("category {:?} is defined more than once", cat).into(),
+                ));
+            }
+        }
+
+        let mut builder = PrimitiveChunkedBuilder::<UInt32Type>::new(self.name(), self.len());
+        for opt_v in self {
+            match opt_v {
+                Some(v) => match mapping.get(v) {
+                    Some(idx) => builder.append_value(*idx),
+                    None if strict => {
+                        return Err(PolarsError::ValueError(
+                            format!(
+                                "value {:?} in column {:?} is not one of the categories {:?}",
+                                v,
+                                self.name(),
+                                categories
+                            )
+                            .into(),
+                        ))
+                    }
+                    None => builder.append_null(),
+                },
+                None => builder.append_null(),
+            }
+        }
+
+        let reverse_mapping = categories
+            .iter()
+            .enumerate()
+            .map(|(idx, cat)| (idx as u32, cat.to_string()))
+            .collect::<AHashMap<_, _>>();
+        let mut ca: CategoricalChunked = builder.finish().cast()?;
+        ca.categorical_map = Some(Arc::new(reverse_mapping));
+        let categories = categories.iter().map(|cat| cat.to_string()).collect();
+        Ok(ca.set_ordering(CategoricalOrdering::Enum(Arc::new(categories))))
+    }
+}
+
 impl ChunkCast for Utf8Chunked {
     fn cast<N>(&self) -> Result<ChunkedArray<N>>
     where
         N: PolarsDataType,
     {
         match N::get_dtype() {
-            DataType::Categorical => {
+            DataType::Categorical(_) => {
                 let mut builder = CategoricalChunkedBuilder::new(self.name(), self.len());
 
                 if use_string_cache() || self.null_count() != 0 {
@@ -211,6 +287,17 @@ impl ChunkCast for Utf8Chunked {
                 let ca = unsafe { std::mem::transmute(ca) };
                 Ok(ca)
             }
+            // parse with a sniffed format, values that don't match the format become null
+            #[cfg(feature = "temporal")]
+            DataType::Date32 if self.null_count() != self.len() => {
+                let ca = self.as_date32(None)?;
+                Ok(unsafe { std::mem::transmute(ca) })
+            }
+            #[cfg(feature = "temporal")]
+            DataType::Date64 if self.null_count() != self.len() => {
+                let ca = self.as_date64(None)?;
+                Ok(unsafe { std::mem::transmute(ca) })
+            }
             _ => cast_ca(self),
         }
     }
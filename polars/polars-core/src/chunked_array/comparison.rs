@@ -6,7 +6,7 @@ use arrow::{
     compute::kernels::comparison,
 };
 use num::{Num, NumCast, ToPrimitive};
-use std::ops::{BitAnd, BitOr, Not};
+use std::ops::{BitAnd, BitOr, Deref, Not};
 use std::sync::Arc;
 
 impl<T> ChunkedArray<T>
@@ -416,6 +416,87 @@ impl ChunkCompare<&Utf8Chunked> for Utf8Chunked {
     }
 }
 
+impl CategoricalChunked {
+    /// The u32 indexes can only be compared if both arrays use the same mapping.
+    /// Arrays cast with the same enum categories have equal mappings.
+    fn same_categorical_map(&self, rhs: &CategoricalChunked) -> bool {
+        match (&self.categorical_map, &rhs.categorical_map) {
+            (Some(l), Some(r)) => Arc::ptr_eq(l, r) || l == r,
+            _ => false,
+        }
+    }
+
+    /// Arrays with a different ordering can't be compared, as `a < b` and `b > a` could
+    /// disagree. A Series is first cast to the supertype of both orderings, see
+    /// [get_supertype](crate::utils::get_supertype).
+    fn check_comparable(&self, rhs: &CategoricalChunked) -> Result<()> {
+        let ordering = self.ordering();
+        if ordering != rhs.ordering() {
+            return Err(PolarsError::DataTypeMisMatch(
+                format!(
+                    "cannot compare categoricals with orderings {:?} and {:?}",
+                    ordering,
+                    rhs.ordering()
+                )
+                .into(),
+            ));
+        }
+        if matches!(ordering, CategoricalOrdering::Enum(_)) && !self.same_categorical_map(rhs) {
+            return Err(PolarsError::InvalidOperation(
+                "cannot compare enums with different category maps".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Compare the u32 indexes if possible and otherwise fall back to comparing the String values.
+macro_rules! impl_cmp_categorical {
+    ($self:ident, $rhs:ident, $method:ident, $ordered:expr) => {{
+        $self
+            .check_comparable($rhs)
+            .expect("cannot compare categoricals");
+        let lexical = $ordered && $self.ordering() == CategoricalOrdering::Lexical;
+        if lexical || !$self.same_categorical_map($rhs) {
+            let lhs = $self.cast::<Utf8Type>().unwrap();
+            let rhs = $rhs.cast::<Utf8Type>().unwrap();
+            lhs.$method(&rhs)
+        } else {
+            $self.deref().$method($rhs.deref())
+        }
+    }};
+}
+
+impl ChunkCompare<&CategoricalChunked> for CategoricalChunked {
+    fn eq_missing(&self, rhs: &CategoricalChunked) -> BooleanChunked {
+        impl_cmp_categorical!(self, rhs, eq_missing, false)
+    }
+
+    fn eq(&self, rhs: &CategoricalChunked) -> BooleanChunked {
+        impl_cmp_categorical!(self, rhs, eq, false)
+    }
+
+    fn neq(&self, rhs: &CategoricalChunked) -> BooleanChunked {
+        impl_cmp_categorical!(self, rhs, neq, false)
+    }
+
+    fn gt(&self, rhs: &CategoricalChunked) -> BooleanChunked {
+        impl_cmp_categorical!(self, rhs, gt, true)
+    }
+
+    fn gt_eq(&self, rhs: &CategoricalChunked) -> BooleanChunked {
+        impl_cmp_categorical!(self, rhs, gt_eq, true)
+    }
+
+    fn lt(&self, rhs: &CategoricalChunked) -> BooleanChunked {
+        impl_cmp_categorical!(self, rhs, lt, true)
+    }
+
+    fn lt_eq(&self, rhs: &CategoricalChunked) -> BooleanChunked {
+        impl_cmp_categorical!(self, rhs, lt_eq, true)
+    }
+}
+
 pub trait NumComp: Num + NumCast + PartialOrd {}
 
 impl NumComp for f32 {}
@@ -955,4 +1036,22 @@ mod test {
         assert_eq!(a1.lt_eq(&a2).sum(), a2.lt_eq(&a1).sum());
         assert_eq!(a1.gt_eq(&a2).sum(), a2.gt_eq(&a1).sum());
     }
+
+    #[test]
+    fn test_categorical_comparable() {
+        let ca = Utf8Chunked::new_from_slice("", &["a", "b"]);
+        let physical = ca.cast::<CategoricalType>().unwrap();
+        let lexical = physical.clone().set_ordering(CategoricalOrdering::Lexical);
+        assert!(physical.check_comparable(&lexical).is_err());
+        assert!(lexical.check_comparable(&physical).is_err());
+        assert!(lexical.check_comparable(&lexical).is_ok());
+
+        let enum_ab = ca.cast_to_enum(&["a", "b"], true).unwrap();
+        let enum_ba = ca.cast_to_enum(&["b", "a"], true).unwrap();
+        assert!(enum_ab.check_comparable(&enum_ba).is_err());
+        let rhs = Utf8Chunked::new_from_slice("", &["b", "b"])
+            .cast_to_enum(&["a", "b"], true)
+            .unwrap();
+        assert!(enum_ab.check_comparable(&rhs).is_ok());
+    }
 }
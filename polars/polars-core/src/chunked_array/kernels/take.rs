@@ -1,7 +1,6 @@
 use crate::prelude::*;
 use arrow::array::{
     Array, ArrayData, BooleanArray, LargeStringArray, LargeStringBuilder, PrimitiveArray,
-    UInt32Array,
 };
 use arrow::buffer::MutableBuffer;
 use std::mem;
@@ -10,7 +9,7 @@ use std::sync::Arc;
 /// Take kernel for single chunk without nulls and arrow array as index.
 pub(crate) unsafe fn take_no_null_primitive<T: PolarsNumericType>(
     arr: &PrimitiveArray<T>,
-    indices: &UInt32Array,
+    indices: &IdxArr,
 ) -> Arc<PrimitiveArray<T>> {
     assert_eq!(arr.null_count(), 0);
 
@@ -346,10 +345,7 @@ pub(crate) fn take_utf8_iter<I: IntoIterator<Item = usize>>(
     Arc::new(iter.collect())
 }
 
-pub(crate) unsafe fn take_utf8(
-    arr: &LargeStringArray,
-    indices: &UInt32Array,
-) -> Arc<LargeStringArray> {
+pub(crate) unsafe fn take_utf8(arr: &LargeStringArray, indices: &IdxArr) -> Arc<LargeStringArray> {
     let data_len = indices.len();
 
     let offset_len_in_bytes = (data_len + 1) * mem::size_of::<i64>();
@@ -467,13 +463,13 @@ mod test {
     fn test_utf8_kernel() {
         let s = LargeStringArray::from(vec![Some("foo"), None, Some("bar")]);
         unsafe {
-            let out = take_utf8(&s, &UInt32Array::from(vec![1, 2]));
+            let out = take_utf8(&s, &IdxArr::from(vec![1, 2]));
             assert!(out.is_null(0));
             assert!(out.is_valid(1));
-            let out = take_utf8(&s, &UInt32Array::from(vec![None, Some(2)]));
+            let out = take_utf8(&s, &IdxArr::from(vec![None, Some(2)]));
             assert!(out.is_null(0));
             assert!(out.is_valid(1));
-            let out = take_utf8(&s, &UInt32Array::from(vec![None, None]));
+            let out = take_utf8(&s, &IdxArr::from(vec![None, None]));
             assert!(out.is_null(0));
             assert!(out.is_null(1));
         }
@@ -163,6 +163,8 @@ pub struct ChunkedArray<T> {
     phantom: PhantomData<T>,
     /// maps categorical u32 indexes to String values
     pub(crate) categorical_map: Option<Arc<AHashMap<u32, String>>>,
+    /// set if the values are known to be sorted
+    pub(crate) sorted: IsSorted,
 }
 
 impl<T> ChunkedArray<T> {
@@ -275,7 +277,7 @@ impl<T> ChunkedArray<T> {
     /// assert_eq!(Vec::from(&array), [Some(1), Some(2), Some(3)])
     /// ```
     pub fn append_array(&mut self, other: ArrayRef) -> Result<()> {
-        if matches!(self.dtype(), DataType::Categorical) {
+        if matches!(self.dtype(), DataType::Categorical(_)) {
             return Err(PolarsError::InvalidOperation(
                 "append_array not supported for categorical type".into(),
             ));
@@ -283,6 +285,7 @@ impl<T> ChunkedArray<T> {
         if self.field.data_type() == other.data_type() {
             self.chunks.push(other);
             self.chunk_id = create_chunk_id(&self.chunks);
+            self.sorted = IsSorted::Not;
             Ok(())
         } else {
             Err(PolarsError::DataTypeMisMatch(
@@ -305,6 +308,8 @@ impl<T> ChunkedArray<T> {
             chunk_id,
             phantom: PhantomData,
             categorical_map: self.categorical_map.clone(),
+            // the chunks may have a different order
+            sorted: IsSorted::Not,
         }
     }
 
@@ -337,7 +342,9 @@ impl<T> ChunkedArray<T> {
                 break;
             }
         }
-        Ok(self.copy_with_chunks(new_chunks))
+        let mut out = self.copy_with_chunks(new_chunks);
+        out.sorted = self.sorted;
+        Ok(out)
     }
 
     /// Get a mask of the null values.
@@ -415,11 +422,13 @@ impl<T> ChunkedArray<T> {
     where
         Self: std::marker::Sized,
     {
-        if matches!(self.dtype(), DataType::Categorical) && !self.is_empty() {
-            assert!(Arc::ptr_eq(
+        if matches!(self.dtype(), DataType::Categorical(_)) && !self.is_empty() {
+            let (l, r) = (
                 self.categorical_map.as_ref().unwrap(),
-                other.categorical_map.as_ref().unwrap()
-            ));
+                other.categorical_map.as_ref().unwrap(),
+            );
+            // arrays cast with the same enum categories don't share the map, but are equal
+            assert!(Arc::ptr_eq(l, r) || l == r);
         }
 
         // replace an empty array
@@ -429,6 +438,19 @@ impl<T> ChunkedArray<T> {
             self.chunks.extend_from_slice(&other.chunks)
         }
         self.chunk_id = create_chunk_id(&self.chunks);
+        self.sorted = IsSorted::Not;
+    }
+
+    /// Get the sortedness flag of this array. This is only set by operations that are known
+    /// to produce sorted output, so [IsSorted::Not](IsSorted::Not) means the order is unknown.
+    pub fn sorted_flag(&self) -> IsSorted {
+        self.sorted
+    }
+
+    /// Flag the values of this array as sorted. This is not checked, setting a wrong flag
+    /// leads to wrong results in operations that rely on it, like joins.
+    pub fn set_sorted_flag(&mut self, sorted: IsSorted) {
+        self.sorted = sorted
     }
 
     /// Name of the ChunkedArray.
@@ -443,7 +465,15 @@ impl<T> ChunkedArray<T> {
 
     /// Rename this ChunkedArray.
     pub fn rename(&mut self, name: &str) {
-        self.field = Arc::new(Field::new(name, self.field.data_type().clone()))
+        self.field = Arc::new(
+            Field::new(name, self.field.data_type().clone())
+                .with_metadata(self.field.metadata().clone()),
+        )
+    }
+
+    /// Set the key-value metadata of this array, e.g. a description or the unit of the values.
+    pub fn set_metadata(&mut self, metadata: FieldMetadata) {
+        self.field = Arc::new(self.field.as_ref().clone().with_metadata(metadata))
     }
 }
 
@@ -503,6 +533,7 @@ where
             chunk_id,
             phantom: PhantomData,
             categorical_map: None,
+            sorted: Default::default(),
         }
     }
 
@@ -560,7 +591,7 @@ where
             }
             #[cfg(feature = "object")]
             DataType::Object => AnyValue::Object(&"object"),
-            DataType::Categorical => {
+            DataType::Categorical(_) => {
                 let v = downcast!(UInt32Array);
                 AnyValue::Utf8(
                     &self
@@ -625,6 +656,7 @@ where
             chunk_id: vec![len],
             phantom: PhantomData,
             categorical_map: None,
+            sorted: Default::default(),
         }
     }
 
@@ -642,6 +674,7 @@ where
             chunk_id: vec![len],
             phantom: PhantomData,
             categorical_map: None,
+            sorted: Default::default(),
         }
     }
 }
@@ -688,6 +721,20 @@ where
         }
     }
 
+    /// Contiguous slice that borrows the values without copying. If the array has more than one
+    /// chunk it is rechunked first. Returns an error if there are null values.
+    pub fn rechunked_slice(&mut self) -> Result<&[T::Native]> {
+        if self.null_count() != 0 {
+            return Err(PolarsError::HasNullValues(
+                "cannot get a slice of an array with null values".into(),
+            ));
+        }
+        if self.chunks.len() > 1 {
+            *self = self.rechunk();
+        }
+        self.cont_slice()
+    }
+
     /// Get slices of the underlying arrow data.
     /// NOTE: null values should be taken into account by the user of these slices as they are handled
     /// separately
@@ -801,6 +848,7 @@ impl<T> Clone for ChunkedArray<T> {
             chunk_id: self.chunk_id.clone(),
             phantom: PhantomData,
             categorical_map: self.categorical_map.clone(),
+            sorted: self.sorted,
         }
     }
 }
@@ -909,9 +957,40 @@ impl From<UInt32Chunked> for CategoricalChunked {
 }
 
 impl CategoricalChunked {
+    /// Get the ordering that is used when sorting and comparing the values of this array.
+    pub fn ordering(&self) -> CategoricalOrdering {
+        match self.dtype() {
+            DataType::Categorical(ordering) => ordering.clone(),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Set the ordering that is used when sorting and comparing the values of this array. The
+    /// ordering is part of the data type.
+    ///
+    /// This does not check the values, use [cast_to_enum](Utf8Chunked::cast_to_enum) to get a
+    /// [CategoricalOrdering::Enum].
+    pub fn set_ordering(mut self, ordering: CategoricalOrdering) -> Self {
+        self.field = Arc::new(
+            Field::new(self.name(), DataType::Categorical(ordering))
+                .with_metadata(self.field.metadata().clone()),
+        );
+        self
+    }
+
+    /// Iterate over the String values of the categories.
+    pub(crate) fn iter_str(&self) -> impl Iterator<Item = Option<&str>> {
+        let mapping = &**self.categorical_map.as_ref().expect("should be set");
+        self.into_iter()
+            .map(move |opt_idx| opt_idx.map(|idx| mapping.get(&idx).unwrap().as_str()))
+    }
+
     fn set_state<T>(mut self, other: &ChunkedArray<T>) -> Self {
         self.categorical_map = other.categorical_map.clone();
-        self
+        match other.dtype() {
+            DataType::Categorical(ordering) => self.set_ordering(ordering.clone()),
+            _ => self,
+        }
     }
 }
 
@@ -1111,4 +1190,111 @@ pub(crate) mod test {
         let v: Vec<_> = ca.into_iter().collect();
         assert_eq!(v, &[Some(0), None, Some(1), Some(2)]);
     }
+
+    #[test]
+    fn test_enum() {
+        let categories = ["low", "mid", "high"];
+        let ca = Utf8Chunked::new_from_opt_slice("", &[Some("high"), None, Some("low"), Some("x")]);
+        assert!(ca.cast_to_enum(&categories, true).is_err());
+        assert!(ca.cast_to_enum(&["low", "low"], false).is_err());
+
+        // casting to the data type validates the values
+        let dtype = DataType::Categorical(CategoricalOrdering::Enum(Arc::new(
+            categories.iter().map(|cat| cat.to_string()).collect(),
+        )));
+        assert!(ca.clone().into_series().cast_with_datatype(&dtype).is_err());
+
+        let ca = ca.cast_to_enum(&categories, false).unwrap();
+        assert_eq!(ca.dtype(), &dtype);
+        let v: Vec<_> = ca.into_iter().collect();
+        assert_eq!(v, &[Some(2), None, Some(0), None]);
+
+        // sorting follows the declared order
+        let sorted = ca.sort(true);
+        let v: Vec<_> = sorted.cast::<Utf8Type>().unwrap().into_iter().collect();
+        assert_eq!(v, &[Some("high"), Some("low"), None, None]);
+
+        // arrays cast with the same categories can be compared by index
+        let rhs = Utf8Chunked::new_from_slice("", &["mid", "mid", "mid", "mid"])
+            .cast_to_enum(&categories, true)
+            .unwrap();
+        let mask: Vec<_> = ca.gt(&rhs).into_iter().collect();
+        assert_eq!(mask, &[Some(true), None, Some(false), None]);
+    }
+
+    #[test]
+    fn test_categorical_ordering() {
+        let ca = Utf8Chunked::new_from_opt_slice(
+            "",
+            &[Some("foo"), None, Some("bar"), Some("ham"), Some("bar")],
+        );
+        let ca = ca.cast::<CategoricalType>().unwrap();
+
+        // physical ordering follows the order of insertion
+        let sorted = ca.sort(false);
+        let v: Vec<_> = sorted.cast::<Utf8Type>().unwrap().into_iter().collect();
+        assert_eq!(
+            v,
+            &[None, Some("foo"), Some("bar"), Some("bar"), Some("ham")]
+        );
+        assert_eq!(ca.max_as_series().get(0), AnyValue::Utf8("ham"));
+
+        let ca = ca.set_ordering(CategoricalOrdering::Lexical);
+        assert_eq!(
+            ca.dtype(),
+            &DataType::Categorical(CategoricalOrdering::Lexical)
+        );
+        let sorted = ca.sort(false);
+        assert_eq!(sorted.ordering(), CategoricalOrdering::Lexical);
+        let v: Vec<_> = sorted.cast::<Utf8Type>().unwrap().into_iter().collect();
+        assert_eq!(
+            v,
+            &[None, Some("bar"), Some("bar"), Some("foo"), Some("ham")]
+        );
+        assert_eq!(ca.min_as_series().get(0), AnyValue::Utf8("bar"));
+        assert_eq!(ca.max_as_series().get(0), AnyValue::Utf8("ham"));
+
+        // "foo" > "bar" lexically, but not physically
+        let rhs = ca.slice(2, 1).unwrap();
+        let rhs = rhs.expand_at_index(0, ca.len());
+        let mask: Vec<_> = ca.gt(&rhs).into_iter().collect();
+        assert_eq!(
+            mask,
+            &[Some(true), None, Some(false), Some(true), Some(false)]
+        );
+        let physical = ca.clone().set_ordering(CategoricalOrdering::Physical);
+        let mask: Vec<_> = physical
+            .gt(&rhs.clone().set_ordering(CategoricalOrdering::Physical))
+            .into_iter()
+            .collect();
+        assert_eq!(
+            mask,
+            &[Some(false), None, Some(false), Some(true), Some(false)]
+        );
+        // series with a different ordering are compared in their supertype, the lexical ordering
+        let lhs = physical.clone().into_series();
+        let rhs = rhs.into_series();
+        let mask: Vec<_> = lhs.gt(&rhs).into_iter().collect();
+        assert_eq!(
+            mask,
+            &[Some(true), None, Some(false), Some(true), Some(false)]
+        );
+        let mask: Vec<_> = rhs.lt(&lhs).into_iter().collect();
+        assert_eq!(
+            mask,
+            &[Some(true), None, Some(false), Some(true), Some(false)]
+        );
+        let ca = physical;
+
+        // the ordering is set by a cast to the data type
+        let s = ca
+            .into_series()
+            .cast_with_datatype(&DataType::Categorical(CategoricalOrdering::Lexical))
+            .unwrap();
+        assert_eq!(
+            s.categorical().unwrap().ordering(),
+            CategoricalOrdering::Lexical
+        );
+        assert_eq!(s.min_as_series().get(0), AnyValue::Utf8("bar"));
+    }
 }
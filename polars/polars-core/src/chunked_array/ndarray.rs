@@ -41,7 +41,7 @@ impl ListChunked {
                 let mut row = ndarray.slice_mut(s![0, ..]);
                 row.assign(&a);
 
-                for series in iter {
+                for (row_idx, series) in iter.enumerate() {
                     if series.len() != width {
                         return Err(PolarsError::ShapeMisMatch(
                             "Could not create a 2D array. Series have different lengths".into(),
@@ -50,7 +50,7 @@ impl ListChunked {
                     let series = series.cast::<N>()?;
                     let ca = series.unpack::<N>()?;
                     let a = ca.to_ndarray()?;
-                    let mut row = ndarray.slice_mut(s![0, ..]);
+                    let mut row = ndarray.slice_mut(s![row_idx + 1, ..]);
                     row.assign(&a)
                 }
                 Ok(ndarray)
@@ -63,6 +63,22 @@ impl ListChunked {
     }
 }
 
+/// The memory layout of a 2D `ndarray::Array`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IndexOrder {
+    /// Row-major, the values of a row are contiguous.
+    C,
+    /// Column-major, the values of a column are contiguous. This is the cheapest layout to
+    /// create from, or convert to, the columns of a DataFrame.
+    Fortran,
+}
+
+impl Default for IndexOrder {
+    fn default() -> Self {
+        IndexOrder::C
+    }
+}
+
 impl DataFrame {
     /// Create a 2D `ndarray::Array` from this `DataFrame`. This requires all columns in the
     /// `DataFrame` to be non-null and numeric. They will be casted to the same data type
@@ -88,7 +104,27 @@ impl DataFrame {
         N: PolarsNumericType,
         N::Native: num::Zero + Copy,
     {
-        let mut ndarr = Array2::zeros(self.shape());
+        self.to_ndarray_with_order::<N>(IndexOrder::C)
+    }
+
+    /// Create a 2D `ndarray::Array` with the memory layout of `order` from this `DataFrame`.
+    /// With [IndexOrder::Fortran](IndexOrder::Fortran) every column is copied as a whole.
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// let df = df!["a" => &[1, 2, 3], "b" => &[10., 8., 6.]].unwrap();
+    /// let ndarray = df.to_ndarray_with_order::<Float64Type>(IndexOrder::Fortran).unwrap();
+    /// assert_eq!(ndarray.column(1).as_slice(), Some(&[10., 8., 6.][..]));
+    /// ```
+    pub fn to_ndarray_with_order<N>(&self, order: IndexOrder) -> Result<Array2<N::Native>>
+    where
+        N: PolarsNumericType,
+        N::Native: num::Zero + Copy,
+    {
+        let mut ndarr = match order {
+            IndexOrder::C => Array2::zeros(self.shape()),
+            IndexOrder::Fortran => Array2::zeros(self.shape().f()),
+        };
         for (col_idx, series) in self.get_columns().iter().enumerate() {
             if series.null_count() != 0 {
                 return Err(PolarsError::HasNullValues(
@@ -99,12 +135,101 @@ impl DataFrame {
             let series = series.cast::<N>()?;
             let ca = series.unpack::<N>()?;
 
-            ca.into_no_null_iter()
-                .enumerate()
-                .for_each(|(row_idx, val)| {
-                    ndarr[[row_idx, col_idx]] = val;
-                })
+            let mut column = ndarr.column_mut(col_idx);
+            match ca.cont_slice() {
+                Ok(src) if column.is_standard_layout() => {
+                    column.as_slice_mut().unwrap().copy_from_slice(src)
+                }
+                _ => column
+                    .iter_mut()
+                    .zip(ca.into_no_null_iter())
+                    .for_each(|(dst, val)| *dst = val),
+            }
         }
         Ok(ndarr)
     }
+
+    /// Create a `DataFrame` from a 2D `ndarray::Array` of numeric values. Every column of the
+    /// array becomes a column named after `names`, or `"column_{i}"` if `None`. Columns of a
+    /// column-major array are copied as a whole.
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use ndarray::array;
+    ///
+    /// let arr = array![[1.0, 10.0], [2.0, 8.0]];
+    /// let df = DataFrame::from_ndarray::<Float64Type>(arr.view(), Some(&["a", "b"])).unwrap();
+    /// assert_eq!(df.shape(), (2, 2));
+    /// assert_eq!(df.get_column_names(), &["a", "b"]);
+    /// ```
+    pub fn from_ndarray<N>(arr: ArrayView2<N::Native>, names: Option<&[&str]>) -> Result<Self>
+    where
+        N: PolarsNumericType,
+        ChunkedArray<N>: IntoSeries,
+    {
+        if let Some(names) = names {
+            if names.len() != arr.ncols() {
+                return Err(PolarsError::ShapeMisMatch(
+                    format!(
+                        "got {} names for an array of {} columns",
+                        names.len(),
+                        arr.ncols()
+                    )
+                    .into(),
+                ));
+            }
+        }
+        let columns = arr
+            .gencolumns()
+            .into_iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let name = match names {
+                    Some(names) => names[i].to_string(),
+                    None => format!("column_{}", i),
+                };
+                let ca: ChunkedArray<N> = match column.as_slice() {
+                    Some(slice) => ChunkedArray::new_from_slice(&name, slice),
+                    None => ChunkedArray::new_from_iter(&name, column.iter().copied()),
+                };
+                ca.into_series()
+            })
+            .collect();
+        DataFrame::new(columns)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_ndarray_roundtrip() -> Result<()> {
+        let df = df![
+            "a" => &[1u32, 2, 3],
+            "b" => &[10.0, 8.0, 6.0]
+        ]?;
+        for order in [IndexOrder::C, IndexOrder::Fortran].iter() {
+            let arr = df.to_ndarray_with_order::<Float64Type>(*order)?;
+            assert_eq!(arr, array![[1.0, 10.0], [2.0, 8.0], [3.0, 6.0]]);
+            assert_eq!(arr.is_standard_layout(), *order == IndexOrder::C);
+
+            let out = DataFrame::from_ndarray::<Float64Type>(arr.view(), Some(&["a", "b"]))?;
+            assert!(out
+                .column("a")?
+                .series_equal(&df.column("a")?.cast::<Float64Type>()?));
+            assert!(out.column("b")?.series_equal(df.column("b")?));
+        }
+
+        let out = DataFrame::from_ndarray::<Int32Type>(array![[1, 2]].view(), None)?;
+        assert_eq!(out.get_column_names(), &["column_0", "column_1"]);
+        assert!(DataFrame::from_ndarray::<Int32Type>(array![[1, 2]].view(), Some(&["a"])).is_err());
+
+        let list: ListChunked = vec![Series::new("", &[1, 2]), Series::new("", &[3, 4])]
+            .into_iter()
+            .collect();
+        assert_eq!(list.to_ndarray::<Int32Type>()?, array![[1, 2], [3, 4]]);
+        Ok(())
+    }
 }
@@ -79,6 +79,7 @@ where
             chunk_id: vec![len],
             phantom: PhantomData,
             categorical_map: None,
+            sorted: Default::default(),
         }
     }
 }
@@ -145,6 +146,7 @@ where
             chunk_id: vec![len],
             phantom: PhantomData,
             categorical_map: None,
+            sorted: Default::default(),
         }
     }
 }
@@ -65,16 +65,19 @@ macro_rules! agg_float_with_nans {
 macro_rules! impl_quantile {
     ($self:expr, $quantile:expr) => {{
         let null_count = $self.null_count();
-        let opt = ChunkSort::sort($self, false)
-            .slice(
-                ((($self.len() - null_count) as f64) * $quantile + null_count as f64) as usize,
-                1,
-            )
-            .unwrap()
-            .into_iter()
-            .next()
-            .unwrap();
-        opt
+        let valid_count = $self.len() - null_count;
+        if valid_count == 0 {
+            None
+        } else {
+            // the nulls are sorted first; clamp so that the 1.0 quantile is the last value
+            let idx = std::cmp::min(((valid_count as f64) * $quantile) as usize, valid_count - 1);
+            ChunkSort::sort($self, false)
+                .slice(idx + null_count, 1)
+                .unwrap()
+                .into_iter()
+                .next()
+                .unwrap()
+        }
     }};
 }
 
@@ -443,15 +446,22 @@ macro_rules! one_null_utf8 {
     }};
 }
 
+/// Strings are compared lexically.
+fn utf8_min_max(ca: &Utf8Chunked, max: bool) -> Series {
+    let iter = ca.into_iter().flatten();
+    let v = if max { iter.max() } else { iter.min() };
+    Utf8Chunked::new_from_opt_slice(ca.name(), &[v]).into_series()
+}
+
 impl ChunkAggSeries for Utf8Chunked {
     fn sum_as_series(&self) -> Series {
         one_null_utf8!(self)
     }
     fn max_as_series(&self) -> Series {
-        one_null_utf8!(self)
+        utf8_min_max(self, true)
     }
     fn min_as_series(&self) -> Series {
-        one_null_utf8!(self)
+        utf8_min_max(self, false)
     }
     fn mean_as_series(&self) -> Series {
         one_null_utf8!(self)
@@ -464,7 +474,48 @@ impl ChunkAggSeries for Utf8Chunked {
     }
 }
 
-impl ChunkAggSeries for CategoricalChunked {}
+fn categorical_min_max(ca: &CategoricalChunked, max: bool) -> Series {
+    let opt_idx = match ca.ordering() {
+        CategoricalOrdering::Physical | CategoricalOrdering::Enum(_) => {
+            let iter = ca
+                .into_iter()
+                .enumerate()
+                .filter_map(|(idx, opt_v)| opt_v.map(|v| (idx, v)));
+            if max {
+                iter.max_by_key(|(_idx, v)| *v).map(|(idx, _v)| idx)
+            } else {
+                iter.min_by_key(|(_idx, v)| *v).map(|(idx, _v)| idx)
+            }
+        }
+        CategoricalOrdering::Lexical => {
+            let iter = ca
+                .iter_str()
+                .enumerate()
+                .filter_map(|(idx, opt_v)| opt_v.map(|v| (idx, v)));
+            if max {
+                iter.max_by_key(|(_idx, v)| *v).map(|(idx, _v)| idx)
+            } else {
+                iter.min_by_key(|(_idx, v)| *v).map(|(idx, _v)| idx)
+            }
+        }
+    };
+    match opt_idx {
+        Some(idx) => ca.slice(idx, 1).unwrap().into_series(),
+        None => {
+            let out: CategoricalChunked = UInt32Chunked::full_null(ca.name(), 1).cast().unwrap();
+            out.set_state(ca).into_series()
+        }
+    }
+}
+
+impl ChunkAggSeries for CategoricalChunked {
+    fn max_as_series(&self) -> Series {
+        categorical_min_max(self, true)
+    }
+    fn min_as_series(&self) -> Series {
+        categorical_min_max(self, false)
+    }
+}
 
 macro_rules! one_null_list {
     ($self:ident) => {{
@@ -0,0 +1,263 @@
+//! Cheap estimation of the number of distinct values of a column.
+//!
+//! The estimate is computed from an evenly spaced sample of the values, so that it costs the same
+//! for every column length. Joins use it to build their hash table on the side with the fewest
+//! distinct keys.
+//!
+//! [HyperLogLog] sketches estimate the number of distinct values of a whole column in constant
+//! memory. They back `approx_n_unique`.
+use crate::prelude::*;
+use crate::utils::split_ca;
+use crate::POOL;
+use ahash::{AHashMap, RandomState};
+use rayon::prelude::*;
+use std::hash::{BuildHasher, Hash, Hasher};
+
+/// Number of values that are sampled to estimate the number of distinct values.
+const CARDINALITY_SAMPLE_SIZE: usize = 1024;
+
+/// Indexes of an evenly spaced sample of a column of length `len`.
+fn sample_indexes(len: usize) -> impl Iterator<Item = usize> {
+    let sample_len = std::cmp::min(len, CARDINALITY_SAMPLE_SIZE);
+    (0..sample_len).map(move |i| i * len / sample_len)
+}
+
+/// Estimate the number of distinct values of a column of length `len` from a sample of its
+/// values with the Duj1 estimator of Haas et al. It scales the distinct values of the sample by
+/// how many of them are only seen once, so a sample of unique values estimates a unique column
+/// and a sample of repeated values estimates the values of the sample.
+fn estimate_n_unique_from_sample<K, I>(sample: I, len: usize) -> usize
+where
+    K: Hash + Eq,
+    I: Iterator<Item = K>,
+{
+    let mut counts = AHashMap::with_capacity(CARDINALITY_SAMPLE_SIZE);
+    let mut sample_len = 0;
+    for k in sample {
+        *counts.entry(k).or_insert(0u32) += 1;
+        sample_len += 1;
+    }
+    if sample_len == 0 {
+        return 0;
+    }
+    let n_distinct = counts.len() as f64;
+    let seen_once = counts.values().filter(|&&count| count == 1).count() as f64;
+    let fraction = sample_len as f64 / len as f64;
+    let estimate = n_distinct / (1.0 - (1.0 - fraction) * seen_once / sample_len as f64);
+    std::cmp::min(estimate.round() as usize, len)
+}
+
+pub(crate) trait EstimateNUnique {
+    /// Estimate the number of distinct values, where null counts as a value.
+    fn estimate_n_unique(&self) -> usize;
+}
+
+impl<T> EstimateNUnique for ChunkedArray<T>
+where
+    T: PolarsIntegerType,
+    T::Native: Hash + Eq,
+{
+    fn estimate_n_unique(&self) -> usize {
+        let sample = sample_indexes(self.len()).map(|idx| self.get(idx));
+        estimate_n_unique_from_sample(sample, self.len())
+    }
+}
+
+macro_rules! impl_float_estimate_n_unique {
+    ($ca: ty) => {
+        impl EstimateNUnique for $ca {
+            fn estimate_n_unique(&self) -> usize {
+                let sample =
+                    sample_indexes(self.len()).map(|idx| self.get(idx).map(|v| v.to_bits()));
+                estimate_n_unique_from_sample(sample, self.len())
+            }
+        }
+    };
+}
+
+impl_float_estimate_n_unique!(Float32Chunked);
+impl_float_estimate_n_unique!(Float64Chunked);
+
+impl EstimateNUnique for BooleanChunked {
+    fn estimate_n_unique(&self) -> usize {
+        let sample = sample_indexes(self.len()).map(|idx| self.get(idx));
+        estimate_n_unique_from_sample(sample, self.len())
+    }
+}
+
+impl EstimateNUnique for Utf8Chunked {
+    fn estimate_n_unique(&self) -> usize {
+        let sample = sample_indexes(self.len()).map(|idx| self.get(idx));
+        estimate_n_unique_from_sample(sample, self.len())
+    }
+}
+
+/// Number of bits of a hash that select the register of a [HyperLogLog] sketch.
+const HLL_PRECISION: u32 = 12;
+const HLL_REGISTERS: usize = 1 << HLL_PRECISION;
+
+/// HyperLogLog sketch of Flajolet et al. Every hash updates one register with the position of its
+/// first set bit, the registers estimate the number of distinct hashes with a standard error of
+/// about 1.6%. Sketches of parts of a column merge into the sketch of the whole column.
+pub(crate) struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    pub(crate) fn new() -> Self {
+        HyperLogLog {
+            registers: vec![0; HLL_REGISTERS],
+        }
+    }
+
+    #[inline]
+    pub(crate) fn add(&mut self, h: u64) {
+        let idx = (h >> (64 - HLL_PRECISION)) as usize;
+        // the sentinel bit bounds the rank if the remaining bits are all zero
+        let rank = ((h << HLL_PRECISION) | (1 << (HLL_PRECISION - 1))).leading_zeros() as u8 + 1;
+        // Safety:
+        // the index has HLL_PRECISION bits
+        let register = unsafe { self.registers.get_unchecked_mut(idx) };
+        *register = std::cmp::max(*register, rank);
+    }
+
+    pub(crate) fn merge(mut self, other: &HyperLogLog) -> Self {
+        self.registers
+            .iter_mut()
+            .zip(&other.registers)
+            .for_each(|(a, b)| *a = std::cmp::max(*a, *b));
+        self
+    }
+
+    pub(crate) fn count(&self) -> usize {
+        let m = HLL_REGISTERS as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum: f64 = self.registers.iter().map(|&r| 2f64.powi(-(r as i32))).sum();
+        let estimate = alpha * m * m / sum;
+        let empty = self.registers.iter().filter(|&&r| r == 0).count();
+        // small cardinalities leave registers empty, linear counting is more accurate there
+        if estimate <= 2.5 * m && empty > 0 {
+            (m * (m / empty as f64).ln()).round() as usize
+        } else {
+            estimate.round() as usize
+        }
+    }
+}
+
+/// Estimate the number of distinct values of the iterators with a [HyperLogLog] sketch per
+/// iterator, that are built in parallel and merged.
+pub(crate) fn approx_n_unique_threaded<I, T>(iters: Vec<I>) -> usize
+where
+    I: Iterator<Item = T> + Send,
+    T: Hash,
+{
+    let random_state = RandomState::new();
+    POOL.install(|| {
+        iters
+            .into_par_iter()
+            .map(|iter| {
+                let mut hll = HyperLogLog::new();
+                for val in iter {
+                    let mut hasher = random_state.build_hasher();
+                    val.hash(&mut hasher);
+                    hll.add(hasher.finish());
+                }
+                hll
+            })
+            .reduce(HyperLogLog::new, |a, b| a.merge(&b))
+            .count()
+    })
+}
+
+/// Estimate the number of distinct values of a ChunkedArray with one [HyperLogLog] sketch per
+/// thread. `$map` maps a value to a hashable key.
+macro_rules! approx_n_unique {
+    ($ca:expr) => {{
+        approx_n_unique!($ca, |v| v)
+    }};
+    ($ca:expr, $map:expr) => {{
+        let splitted = split_ca($ca, POOL.current_num_threads()).unwrap();
+        match $ca.null_count() {
+            0 => approx_n_unique_threaded(
+                splitted
+                    .iter()
+                    .map(|ca| ca.into_no_null_iter().map($map))
+                    .collect(),
+            ),
+            _ => approx_n_unique_threaded(
+                splitted
+                    .iter()
+                    .map(|ca| ca.into_iter().map(|opt_v| opt_v.map($map)))
+                    .collect(),
+            ),
+        }
+    }};
+}
+
+pub(crate) trait ApproxNUnique {
+    /// Estimate the number of distinct values of the whole array, where null counts as a value.
+    fn approx_n_unique(&self) -> usize;
+}
+
+impl<T> ApproxNUnique for ChunkedArray<T>
+where
+    T: PolarsIntegerType + Sync,
+    T::Native: Hash,
+{
+    fn approx_n_unique(&self) -> usize {
+        approx_n_unique!(self)
+    }
+}
+
+impl ApproxNUnique for Float32Chunked {
+    fn approx_n_unique(&self) -> usize {
+        approx_n_unique!(self, |v: f32| v.to_bits())
+    }
+}
+
+impl ApproxNUnique for Float64Chunked {
+    fn approx_n_unique(&self) -> usize {
+        approx_n_unique!(self, |v: f64| v.to_bits())
+    }
+}
+
+impl ApproxNUnique for Utf8Chunked {
+    fn approx_n_unique(&self) -> usize {
+        approx_n_unique!(self)
+    }
+}
+
+impl ApproxNUnique for CategoricalChunked {
+    fn approx_n_unique(&self) -> usize {
+        approx_n_unique!(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_estimate_n_unique() {
+        let ca = UInt32Chunked::new_from_iter("a", 0..100_000);
+        let estimate = ca.estimate_n_unique();
+        assert_eq!(estimate, 100_000);
+
+        let ca = UInt32Chunked::new_from_iter("a", (0..100_000).map(|v| v % 10));
+        assert_eq!(ca.estimate_n_unique(), 10);
+
+        let ca = Utf8Chunked::new_from_opt_slice("a", &[Some("x"), None, Some("x"), None]);
+        assert_eq!(ca.estimate_n_unique(), 2);
+    }
+
+    #[test]
+    fn test_approx_n_unique() {
+        let ca = UInt32Chunked::new_from_iter("a", 0..100_000);
+        let estimate = ApproxNUnique::approx_n_unique(&ca) as f64;
+        assert!((estimate - 100_000.0).abs() < 5_000.0, "{}", estimate);
+
+        let ca = Utf8Chunked::new_from_opt_slice("a", &[Some("x"), None, Some("y"), None]);
+        // the values can share a register
+        assert!((2..=3).contains(&ApproxNUnique::approx_n_unique(&ca)));
+    }
+}
@@ -63,10 +63,8 @@ impl ChunkOps for CategoricalChunked {
     where
         Self: std::marker::Sized,
     {
-        let cat_map = self.categorical_map.clone();
-        let mut ca = self.cast::<UInt32Type>().unwrap().rechunk().cast().unwrap();
-        ca.categorical_map = cat_map;
-        ca
+        let ca: Self = self.cast::<UInt32Type>().unwrap().rechunk().cast().unwrap();
+        ca.set_state(self)
     }
 }
 
@@ -0,0 +1,101 @@
+use crate::prelude::*;
+use num::{Num, NumCast, Zero};
+
+impl<T> ChunkClip<T> for ChunkedArray<T>
+where
+    T: PolarsNumericType,
+    T::Native: PartialOrd + Num + NumCast + Zero,
+{
+    fn clip_quantile(&self, low_q: f64, high_q: f64) -> Result<ChunkedArray<T>> {
+        if low_q > high_q {
+            return Err(PolarsError::ValueError(
+                format!(
+                    "lower quantile {} should not be larger than upper quantile {}",
+                    low_q, high_q
+                )
+                .into(),
+            ));
+        }
+        match (self.quantile(low_q)?, self.quantile(high_q)?) {
+            (Some(low), Some(high)) => Ok(self.apply(|v| {
+                if v < low {
+                    low
+                } else if v > high {
+                    high
+                } else {
+                    v
+                }
+            })),
+            // only null values
+            _ => Ok(self.clone()),
+        }
+    }
+}
+
+impl ChunkClip<BooleanType> for BooleanChunked {}
+impl ChunkClip<Utf8Type> for Utf8Chunked {}
+impl ChunkClip<CategoricalType> for CategoricalChunked {}
+
+impl ChunkClip<ListType> for ListChunked {
+    /// Clip the values of every list to the quantiles of that list.
+    fn clip_quantile(&self, low_q: f64, high_q: f64) -> Result<ListChunked> {
+        let lists = self
+            .into_iter()
+            .map(|opt_s| opt_s.map(|s| s.clip_quantile(low_q, high_q)).transpose())
+            .collect::<Result<Vec<_>>>()?;
+        if lists.iter().all(|opt_s| opt_s.is_none()) {
+            return Ok(self.clone());
+        }
+        let mut ca: ListChunked = lists.into_iter().collect();
+        ca.rename(self.name());
+        Ok(ca)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_clip_quantile() -> Result<()> {
+        let ca = Int32Chunked::new_from_opt_slice(
+            "a",
+            &[
+                Some(-100),
+                Some(1),
+                None,
+                Some(2),
+                Some(3),
+                Some(4),
+                Some(100),
+            ],
+        );
+        let out = ca.clip_quantile(0.2, 0.8)?;
+        assert_eq!(
+            Vec::from(&out),
+            &[Some(1), Some(1), None, Some(2), Some(3), Some(4), Some(4)]
+        );
+        // the full range doesn't clip anything
+        let out = ca.clip_quantile(0.0, 1.0)?;
+        assert_eq!(Vec::from(&out), Vec::from(&ca));
+
+        assert!(ca.clip_quantile(0.8, 0.2).is_err());
+        assert!(ca.clip_quantile(0.2, 1.5).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_clip_quantile_list() -> Result<()> {
+        let a = Series::new("", &[1, 2, 3, 4, 100]);
+        let b = Series::new("", &[-10, 5, 6]);
+        let ca: ListChunked = [a, b].iter().collect();
+        let out = ca.clip_quantile(0.0, 0.6)?;
+        let out: Vec<_> = out
+            .into_iter()
+            .map(|s| Vec::from(s.unwrap().i32().unwrap()))
+            .collect();
+        assert_eq!(out[0], &[Some(1), Some(2), Some(3), Some(4), Some(4)]);
+        assert_eq!(out[1], &[Some(-10), Some(5), Some(5)]);
+        Ok(())
+    }
+}
@@ -47,7 +47,7 @@ where
             .zip(filter.downcast_chunks())
             .map(|(&left, mask)| filter_fn(left, mask).unwrap())
             .collect::<Vec<_>>();
-        Ok(ChunkedArray::new_from_chunks(self.name(), chunks))
+        Ok(self.copy_with_chunks(chunks))
     }
 }
 
@@ -69,7 +69,7 @@ impl ChunkFilter<BooleanType> for BooleanChunked {
             .zip(filter.downcast_chunks())
             .map(|(&left, mask)| filter_fn(left, mask).unwrap())
             .collect::<Vec<_>>();
-        Ok(ChunkedArray::new_from_chunks(self.name(), chunks))
+        Ok(self.copy_with_chunks(chunks))
     }
 }
 
@@ -91,7 +91,7 @@ impl ChunkFilter<Utf8Type> for Utf8Chunked {
             .zip(filter.downcast_chunks())
             .map(|(&left, mask)| filter_fn(left, mask).unwrap())
             .collect::<Vec<_>>();
-        Ok(ChunkedArray::new_from_chunks(self.name(), chunks))
+        Ok(self.copy_with_chunks(chunks))
     }
 }
 
@@ -122,7 +122,7 @@ impl ChunkFilter<ListType> for ListChunked {
             .zip(filter.downcast_chunks())
             .map(|(&left, mask)| filter_fn(left, mask).unwrap())
             .collect::<Vec<_>>();
-        Ok(ChunkedArray::new_from_chunks(self.name(), chunks))
+        Ok(self.copy_with_chunks(chunks))
     }
 }
 
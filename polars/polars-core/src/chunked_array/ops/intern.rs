@@ -0,0 +1,97 @@
+//! Per-query string interning.
+//!
+//! Utf8 keys are mapped to u32 codes with a dictionary that only lives for a single groupby or
+//! join. The hash tables of those operations then hash and compare integers instead of full
+//! strings, without needing the global string cache.
+use crate::chunked_array::ops::cardinality::EstimateNUnique;
+use crate::prelude::*;
+use crate::utils::NoNull;
+use ahash::AHashMap;
+
+/// Columns shorter than this are not interned, the extra pass costs more than it saves.
+pub(crate) const INTERN_MIN_ROWS: usize = 1 << 14;
+/// Intern when at most this fraction of the values is estimated to be distinct.
+const INTERN_MAX_DISTINCT_FRACTION: f64 = 0.5;
+
+/// Local dictionary that maps strings to consecutive u32 codes.
+#[derive(Default)]
+pub(crate) struct StringInterner<'a> {
+    map: AHashMap<&'a str, u32>,
+}
+
+impl<'a> StringInterner<'a> {
+    fn code(&mut self, v: &'a str) -> u32 {
+        let next = self.map.len() as u32;
+        *self.map.entry(v).or_insert(next)
+    }
+
+    /// Map every string of `ca` to its code. Nulls stay null.
+    pub(crate) fn intern(&mut self, ca: &'a Utf8Chunked) -> UInt32Chunked {
+        let mut out: UInt32Chunked = if ca.null_count() == 0 {
+            let ca: NoNull<UInt32Chunked> = ca.into_no_null_iter().map(|v| self.code(v)).collect();
+            ca.into_inner()
+        } else {
+            ca.into_iter()
+                .map(|opt_v| opt_v.map(|v| self.code(v)))
+                .collect()
+        };
+        out.rename(ca.name());
+        out
+    }
+}
+
+impl Utf8Chunked {
+    /// Estimate from an evenly spaced sample whether interning this column speeds up hashing.
+    /// This is the case for long columns with many repeated values.
+    pub(crate) fn should_intern(&self) -> bool {
+        if self.len() < INTERN_MIN_ROWS {
+            return false;
+        }
+        (self.estimate_n_unique() as f64) <= self.len() as f64 * INTERN_MAX_DISTINCT_FRACTION
+    }
+
+    /// Map the strings to u32 codes that are only valid within this column.
+    pub(crate) fn intern_local(&self) -> UInt32Chunked {
+        StringInterner::default().intern(self)
+    }
+
+    /// Map the strings of both columns to u32 codes with a shared dictionary, so that equal
+    /// strings in `self` and `other` get equal codes.
+    pub(crate) fn intern_pair(&self, other: &Utf8Chunked) -> (UInt32Chunked, UInt32Chunked) {
+        let mut interner = StringInterner::default();
+        let a = interner.intern(self);
+        let b = interner.intern(other);
+        (a, b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_intern() {
+        let a = Utf8Chunked::new_from_opt_slice("a", &[Some("x"), None, Some("y"), Some("x")]);
+        let b = Utf8Chunked::new_from_opt_slice("b", &[Some("y"), Some("z"), None]);
+        let (codes_a, codes_b) = a.intern_pair(&b);
+        assert_eq!(Vec::from(&codes_a), &[Some(0), None, Some(1), Some(0)]);
+        assert_eq!(Vec::from(&codes_b), &[Some(1), Some(2), None]);
+        assert_eq!(codes_a.name(), "a");
+
+        let ca = Utf8Chunked::new_from_slice("a", &vec!["foo"; 1 << 14]);
+        assert!(ca.should_intern());
+        assert!(!a.should_intern());
+
+        // the distinct values are only at the end of the column
+        let ca: Utf8Chunked = (0..1 << 16)
+            .map(|i| {
+                if i < 1024 {
+                    "foo".to_string()
+                } else {
+                    i.to_string()
+                }
+            })
+            .collect();
+        assert!(!ca.should_intern());
+    }
+}
@@ -5,17 +5,21 @@ use crate::chunked_array::object::ObjectType;
 use crate::prelude::*;
 use crate::series::implementations::Wrap;
 use crate::utils::NoNull;
-use arrow::array::{ArrayRef, UInt32Array};
+use arrow::array::ArrayRef;
 use std::marker::Sized;
 
 pub(crate) mod aggregate;
 pub(crate) mod apply;
+pub(crate) mod cardinality;
 pub(crate) mod chunkops;
+pub(crate) mod clip;
 pub(crate) mod cum_agg;
 pub(crate) mod explode;
 pub(crate) mod fill_none;
 pub(crate) mod filter;
+pub(crate) mod intern;
 pub(crate) mod peaks;
+pub(crate) mod scale;
 pub(crate) mod set;
 pub(crate) mod shift;
 pub(crate) mod sort;
@@ -238,7 +242,7 @@ where
     I: Iterator<Item = usize>,
     INulls: Iterator<Item = Option<usize>>,
 {
-    Array(&'a UInt32Array),
+    Array(&'a IdxArr),
     Iter(I),
     // will return a null where None
     IterNulls(INulls),
@@ -248,8 +252,8 @@ pub type Dummy<T> = std::iter::Once<T>;
 pub type TakeIdxIter<'a, I> = TakeIdx<'a, I, Dummy<Option<usize>>>;
 pub type TakeIdxIterNull<'a, INull> = TakeIdx<'a, Dummy<usize>, INull>;
 
-impl<'a> From<&'a UInt32Chunked> for TakeIdx<'a, Dummy<usize>, Dummy<Option<usize>>> {
-    fn from(ca: &'a UInt32Chunked) -> Self {
+impl<'a> From<&'a IdxCa> for TakeIdx<'a, Dummy<usize>, Dummy<Option<usize>>> {
+    fn from(ca: &'a IdxCa) -> Self {
         if ca.chunks.len() == 1 {
             TakeIdx::Array(ca.downcast_chunks()[0])
         } else {
@@ -507,13 +511,21 @@ pub trait ChunkUnique<T> {
 
     /// Get first index of the unique values in a `ChunkedArray`.
     /// This Vec is sorted.
-    fn arg_unique(&self) -> Result<Vec<u32>>;
+    fn arg_unique(&self) -> Result<Vec<IdxSize>>;
 
     /// Number of unique values in the `ChunkedArray`
     fn n_unique(&self) -> Result<usize> {
         self.arg_unique().map(|v| v.len())
     }
 
+    /// Estimate the number of unique values in the `ChunkedArray` with a HyperLogLog sketch. Uses
+    /// constant memory, where [n_unique](ChunkUnique::n_unique) needs memory for every unique value.
+    fn approx_n_unique(&self) -> Result<usize> {
+        Err(PolarsError::InvalidOperation(
+            "approx_n_unique is not implemented for this dtype".into(),
+        ))
+    }
+
     /// Get a mask of all the unique values.
     fn is_unique(&self) -> Result<BooleanChunked> {
         Err(PolarsError::InvalidOperation(
@@ -539,7 +551,7 @@ pub trait ChunkUnique<T> {
 pub trait ToDummies<T>: ChunkUnique<T> {
     fn to_dummies(&self) -> Result<DataFrame> {
         Err(PolarsError::InvalidOperation(
-            "is_duplicated is not implemented for this dtype".into(),
+            "to_dummies is not implemented for this dtype".into(),
         ))
     }
 }
@@ -553,7 +565,7 @@ pub trait ChunkSort<T> {
     fn sort_in_place(&mut self, reverse: bool);
 
     /// Retrieve the indexes needed to sort this array.
-    fn argsort(&self, reverse: bool) -> UInt32Chunked;
+    fn argsort(&self, reverse: bool) -> IdxCa;
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -791,17 +803,19 @@ impl ChunkExpandAtIndex<Utf8Type> for Utf8Chunked {
 
 impl ChunkExpandAtIndex<CategoricalType> for CategoricalChunked {
     fn expand_at_index(&self, index: usize, length: usize) -> CategoricalChunked {
-        self.cast::<UInt32Type>()
+        let ca: CategoricalChunked = self
+            .cast::<UInt32Type>()
             .unwrap()
             .expand_at_index(index, length)
             .cast()
-            .unwrap()
+            .unwrap();
+        ca.set_state(self)
     }
 }
 
 impl ChunkExpandAtIndex<ListType> for ListChunked {
-    fn expand_at_index(&self, _index: usize, _length: usize) -> ListChunked {
-        unimplemented!()
+    fn expand_at_index(&self, index: usize, length: usize) -> ListChunked {
+        self.take(std::iter::repeat(index).take(length).into())
     }
 }
 
@@ -855,3 +869,35 @@ pub trait ChunkPeaks {
         unimplemented!()
     }
 }
+
+/// Clip the values of a ChunkedArray.
+pub trait ChunkClip<T> {
+    /// Clip the values to the `low_q` and `high_q` quantiles of the array, also known as
+    /// winsorizing. Values below the lower quantile are set to the lower quantile and values
+    /// above the upper quantile are set to the upper quantile. Null values stay null.
+    fn clip_quantile(&self, _low_q: f64, _high_q: f64) -> Result<ChunkedArray<T>> {
+        Err(PolarsError::InvalidOperation(
+            "clip_quantile not supported for this dtype".into(),
+        ))
+    }
+}
+
+/// Feature scaling of numeric values. The output has the `Float64` data type and null values
+/// stay null.
+pub trait ChunkScale {
+    /// Subtract the mean and divide by the standard deviation, i.e. compute the z-score.
+    /// All values are 0.0 if the standard deviation is zero.
+    fn standardize(&self) -> Result<Series> {
+        Err(PolarsError::InvalidOperation(
+            "standardize not supported for this dtype".into(),
+        ))
+    }
+
+    /// Scale the values to the range [0.0, 1.0] by subtracting the minimum and dividing by the
+    /// difference between the maximum and the minimum. All values are 0.0 if that difference is zero.
+    fn min_max_scale(&self) -> Result<Series> {
+        Err(PolarsError::InvalidOperation(
+            "min_max_scale not supported for this dtype".into(),
+        ))
+    }
+}
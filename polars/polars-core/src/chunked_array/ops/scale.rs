@@ -0,0 +1,136 @@
+use crate::prelude::*;
+use num::ToPrimitive;
+
+/// Get the mean and the sample standard deviation of the values in a single pass with
+/// Welford's algorithm.
+fn mean_std<I>(values: I) -> (f64, f64)
+where
+    I: Iterator<Item = f64>,
+{
+    let mut count = 0usize;
+    let mut mean = 0.0;
+    let mut m2 = 0.0;
+    for v in values {
+        count += 1;
+        let delta = v - mean;
+        mean += delta / count as f64;
+        m2 += delta * (v - mean);
+    }
+    let std = if count > 1 {
+        (m2 / (count - 1) as f64).sqrt()
+    } else {
+        0.0
+    };
+    (mean, std)
+}
+
+/// Get the minimum and the maximum of the values in a single pass.
+fn min_max<I>(values: I) -> (f64, f64)
+where
+    I: Iterator<Item = f64>,
+{
+    values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+        (min.min(v), max.max(v))
+    })
+}
+
+/// Compute `(v - offset) / scale` for every value. A zero scale maps all values to 0.0.
+fn shift_and_scale<T>(ca: &ChunkedArray<T>, offset: f64, scale: f64) -> Series
+where
+    T: PolarsNumericType,
+    T::Native: ToPrimitive,
+{
+    let mut out: Float64Chunked = ca
+        .into_iter()
+        .map(|opt_v| {
+            opt_v.map(|v| {
+                if scale == 0.0 {
+                    0.0
+                } else {
+                    (v.to_f64().unwrap() - offset) / scale
+                }
+            })
+        })
+        .collect();
+    out.rename(ca.name());
+    out.into_series()
+}
+
+impl<T> ChunkScale for ChunkedArray<T>
+where
+    T: PolarsNumericType,
+    T::Native: ToPrimitive,
+{
+    fn standardize(&self) -> Result<Series> {
+        let (mean, std) = mean_std(self.into_iter().flatten().map(|v| v.to_f64().unwrap()));
+        Ok(shift_and_scale(self, mean, std))
+    }
+
+    fn min_max_scale(&self) -> Result<Series> {
+        let (min, max) = min_max(self.into_iter().flatten().map(|v| v.to_f64().unwrap()));
+        Ok(shift_and_scale(self, min, max - min))
+    }
+}
+
+impl ListChunked {
+    fn scale_lists<F>(&self, f: F) -> Result<Series>
+    where
+        F: Fn(&Series) -> Result<Series>,
+    {
+        let lists = self
+            .into_iter()
+            .map(|opt_s| opt_s.map(|s| f(&s)).transpose())
+            .collect::<Result<Vec<_>>>()?;
+        if lists.iter().all(|opt_s| opt_s.is_none()) {
+            return Ok(self.clone().into_series());
+        }
+        let mut ca: ListChunked = lists.into_iter().collect();
+        ca.rename(self.name());
+        Ok(ca.into_series())
+    }
+}
+
+/// Every list is scaled on its own values.
+impl ChunkScale for ListChunked {
+    fn standardize(&self) -> Result<Series> {
+        self.scale_lists(|s| s.standardize())
+    }
+
+    fn min_max_scale(&self) -> Result<Series> {
+        self.scale_lists(|s| s.min_max_scale())
+    }
+}
+
+impl ChunkScale for BooleanChunked {}
+impl ChunkScale for Utf8Chunked {}
+impl ChunkScale for CategoricalChunked {}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_scale() -> Result<()> {
+        let ca = Int32Chunked::new_from_opt_slice("a", &[Some(1), None, Some(2), Some(3)]);
+        let out = ca.standardize()?;
+        assert_eq!(out.name(), "a");
+        assert_eq!(
+            Vec::from(out.f64()?),
+            &[Some(-1.0), None, Some(0.0), Some(1.0)]
+        );
+        let out = ca.min_max_scale()?;
+        assert_eq!(
+            Vec::from(out.f64()?),
+            &[Some(0.0), None, Some(0.5), Some(1.0)]
+        );
+
+        // no spread
+        let ca = Float32Chunked::new_from_slice("a", &[2.0, 2.0]);
+        assert_eq!(Vec::from(ca.standardize()?.f64()?), &[Some(0.0), Some(0.0)]);
+        assert_eq!(
+            Vec::from(ca.min_max_scale()?.f64()?),
+            &[Some(0.0), Some(0.0)]
+        );
+        Ok(())
+    }
+}
@@ -14,73 +14,89 @@ fn sort_partial<T: PartialOrd>(a: &Option<T>, b: &Option<T>) -> Ordering {
     }
 }
 
-impl<T> ChunkSort<T> for ChunkedArray<T>
+fn sort_numeric<T>(ca: &ChunkedArray<T>, reverse: bool) -> ChunkedArray<T>
 where
     T: PolarsNumericType,
     T::Native: std::cmp::PartialOrd,
 {
-    fn sort(&self, reverse: bool) -> ChunkedArray<T> {
-        if self.is_optimal_aligned()
-            && self.len()
-                > std::env::var("POLARS_PAR_SORT_BOUND")
-                    .map(|v| v.parse::<usize>().expect("could not parse"))
-                    .unwrap_or(1000000)
-        {
-            let n = self.len();
-            let vals = self.cont_slice().unwrap();
-            let vals_ptr = vals.as_ptr();
-            // allocate aligned
-            let mut new = AlignedVec::<T::Native>::with_capacity_aligned(n);
-            let new_ptr = new.as_mut_ptr();
+    if ca.is_optimal_aligned()
+        && ca.len()
+            > std::env::var("POLARS_PAR_SORT_BOUND")
+                .map(|v| v.parse::<usize>().expect("could not parse"))
+                .unwrap_or(1000000)
+    {
+        let n = ca.len();
+        let vals = ca.cont_slice().unwrap();
+        let vals_ptr = vals.as_ptr();
+        // allocate aligned
+        let mut new = AlignedVec::<T::Native>::with_capacity_aligned(n);
+        let new_ptr = new.as_mut_ptr();
 
-            // memcopy
-            unsafe { std::ptr::copy_nonoverlapping(vals_ptr, new_ptr, n) };
-            // set len to copied bytes
-            unsafe { new.set_len(n) };
+        // memcopy
+        unsafe { std::ptr::copy_nonoverlapping(vals_ptr, new_ptr, n) };
+        // set len to copied bytes
+        unsafe { new.set_len(n) };
 
-            if reverse {
-                new.as_mut_slice()
-                    .par_sort_by(|a, b| b.partial_cmp(a).unwrap())
-            } else {
-                new.as_mut_slice()
-                    .par_sort_by(|a, b| a.partial_cmp(b).unwrap())
-            }
-            ChunkedArray::new_from_aligned_vec(self.name(), new)
-        } else if self.null_count() == 0 {
-            if reverse {
-                let ca: NoNull<ChunkedArray<T>> = self
-                    .into_no_null_iter()
-                    .sorted_by(|a, b| b.partial_cmp(a).unwrap())
-                    .collect();
-                let mut ca = ca.into_inner();
-                ca.rename(self.name());
-                ca
-            } else {
-                let ca: NoNull<ChunkedArray<T>> = self
-                    .into_no_null_iter()
-                    .sorted_by(|a, b| a.partial_cmp(b).unwrap())
-                    .collect();
-                let mut ca = ca.into_inner();
-                ca.rename(self.name());
-                ca
-            }
-        } else if reverse {
-            self.into_iter()
-                .sorted_by(|a, b| sort_partial(b, a))
-                .collect()
+        if reverse {
+            new.as_mut_slice()
+                .par_sort_by(|a, b| b.partial_cmp(a).unwrap())
         } else {
-            self.into_iter()
-                .sorted_by(|a, b| sort_partial(a, b))
-                .collect()
+            new.as_mut_slice()
+                .par_sort_by(|a, b| a.partial_cmp(b).unwrap())
         }
+        ChunkedArray::new_from_aligned_vec(ca.name(), new)
+    } else if ca.null_count() == 0 {
+        if reverse {
+            let out: NoNull<ChunkedArray<T>> = ca
+                .into_no_null_iter()
+                .sorted_by(|a, b| b.partial_cmp(a).unwrap())
+                .collect();
+            let mut out = out.into_inner();
+            out.rename(ca.name());
+            out
+        } else {
+            let out: NoNull<ChunkedArray<T>> = ca
+                .into_no_null_iter()
+                .sorted_by(|a, b| a.partial_cmp(b).unwrap())
+                .collect();
+            let mut out = out.into_inner();
+            out.rename(ca.name());
+            out
+        }
+    } else if reverse {
+        ca.into_iter()
+            .sorted_by(|a, b| sort_partial(b, a))
+            .collect()
+    } else {
+        ca.into_iter()
+            .sorted_by(|a, b| sort_partial(a, b))
+            .collect()
+    }
+}
+
+impl<T> ChunkSort<T> for ChunkedArray<T>
+where
+    T: PolarsNumericType,
+    T::Native: std::cmp::PartialOrd,
+{
+    fn sort(&self, reverse: bool) -> ChunkedArray<T> {
+        let mut out = sort_numeric(self, reverse);
+        out.sorted = if reverse {
+            IsSorted::Descending
+        } else {
+            IsSorted::Ascending
+        };
+        out
     }
 
     fn sort_in_place(&mut self, reverse: bool) {
         let sorted = self.sort(reverse);
         self.chunks = sorted.chunks;
+        self.chunk_id = sorted.chunk_id;
+        self.sorted = sorted.sorted;
     }
 
-    fn argsort(&self, reverse: bool) -> UInt32Chunked {
+    fn argsort(&self, reverse: bool) -> IdxCa {
         // if len larger than 1M we sort in paralllel
         if self.is_optimal_aligned()
             && self.len()
@@ -100,37 +116,37 @@ where
                     .par_sort_by(|(_idx, a), (_idx_b, b)| a.partial_cmp(b).unwrap());
             }
             vals.into_par_iter()
-                .map(|(idx, _v)| Some(idx as u32))
+                .map(|(idx, _v)| Some(idx as IdxSize))
                 .collect()
         } else if self.null_count() == 0 {
             if reverse {
                 self.into_no_null_iter()
                     .enumerate()
                     .sorted_by(|(_idx_a, a), (_idx_b, b)| b.partial_cmp(a).unwrap())
-                    .map(|(idx, _v)| idx as u32)
-                    .collect::<NoNull<UInt32Chunked>>()
+                    .map(|(idx, _v)| idx as IdxSize)
+                    .collect::<NoNull<IdxCa>>()
                     .into_inner()
             } else {
                 self.into_no_null_iter()
                     .enumerate()
                     .sorted_by(|(_idx_a, a), (_idx_b, b)| a.partial_cmp(b).unwrap())
-                    .map(|(idx, _v)| idx as u32)
-                    .collect::<NoNull<UInt32Chunked>>()
+                    .map(|(idx, _v)| idx as IdxSize)
+                    .collect::<NoNull<IdxCa>>()
                     .into_inner()
             }
         } else if reverse {
             self.into_iter()
                 .enumerate()
                 .sorted_by(|(_idx_a, a), (_idx_b, b)| sort_partial(b, a))
-                .map(|(idx, _v)| idx as u32)
-                .collect::<NoNull<UInt32Chunked>>()
+                .map(|(idx, _v)| idx as IdxSize)
+                .collect::<NoNull<IdxCa>>()
                 .into_inner()
         } else {
             self.into_iter()
                 .enumerate()
                 .sorted_by(|(_idx_a, a), (_idx_b, b)| sort_partial(a, b))
-                .map(|(idx, _v)| idx as u32)
-                .collect::<NoNull<UInt32Chunked>>()
+                .map(|(idx, _v)| idx as IdxSize)
+                .collect::<NoNull<IdxCa>>()
                 .into_inner()
         }
     }
@@ -142,8 +158,8 @@ macro_rules! argsort {
             .into_iter()
             .enumerate()
             .sorted_by($closure)
-            .map(|(idx, _v)| idx as u32)
-            .collect::<NoNull<UInt32Chunked>>()
+            .map(|(idx, _v)| idx as IdxSize)
+            .collect::<NoNull<IdxCa>>()
             .into_inner()
     }};
 }
@@ -168,7 +184,7 @@ impl ChunkSort<Utf8Type> for Utf8Chunked {
         self.chunks = sorted.chunks;
     }
 
-    fn argsort(&self, reverse: bool) -> UInt32Chunked {
+    fn argsort(&self, reverse: bool) -> IdxCa {
         if reverse {
             argsort!(self, |(_idx_a, a), (_idx_b, b)| b.cmp(a))
         } else {
@@ -179,15 +195,53 @@ impl ChunkSort<Utf8Type> for Utf8Chunked {
 
 impl ChunkSort<CategoricalType> for CategoricalChunked {
     fn sort(&self, reverse: bool) -> Self {
-        self.as_ref().sort(reverse).cast().unwrap()
+        match self.ordering() {
+            CategoricalOrdering::Physical | CategoricalOrdering::Enum(_) => {
+                let ca: CategoricalChunked = self.deref().sort(reverse).cast().unwrap();
+                ca.set_state(self)
+            }
+            CategoricalOrdering::Lexical => {
+                let idx = self.argsort(reverse);
+                self.take((&idx).into())
+            }
+        }
     }
 
     fn sort_in_place(&mut self, reverse: bool) {
-        self.deref_mut().sort_in_place(reverse)
+        match self.ordering() {
+            CategoricalOrdering::Physical | CategoricalOrdering::Enum(_) => {
+                self.deref_mut().sort_in_place(reverse)
+            }
+            CategoricalOrdering::Lexical => {
+                let sorted = self.sort(reverse);
+                self.chunks = sorted.chunks;
+                self.chunk_id = sorted.chunk_id;
+                // the physical values are not in order
+                self.sorted = IsSorted::Not;
+            }
+        }
     }
 
-    fn argsort(&self, reverse: bool) -> UInt32Chunked {
-        self.deref().argsort(reverse)
+    fn argsort(&self, reverse: bool) -> IdxCa {
+        match self.ordering() {
+            CategoricalOrdering::Physical | CategoricalOrdering::Enum(_) => {
+                self.deref().argsort(reverse)
+            }
+            CategoricalOrdering::Lexical => {
+                let iter = self.iter_str().enumerate();
+                if reverse {
+                    iter.sorted_by(|(_idx_a, a), (_idx_b, b)| b.cmp(a))
+                        .map(|(idx, _v)| idx as IdxSize)
+                        .collect::<NoNull<IdxCa>>()
+                        .into_inner()
+                } else {
+                    iter.sorted_by(|(_idx_a, a), (_idx_b, b)| a.cmp(b))
+                        .map(|(idx, _v)| idx as IdxSize)
+                        .collect::<NoNull<IdxCa>>()
+                        .into_inner()
+                }
+            }
+        }
     }
 }
 
@@ -200,7 +254,7 @@ impl ChunkSort<ListType> for ListChunked {
         unimplemented!()
     }
 
-    fn argsort(&self, _reverse: bool) -> UInt32Chunked {
+    fn argsort(&self, _reverse: bool) -> IdxCa {
         unimplemented!()
     }
 }
@@ -215,7 +269,7 @@ impl<T> ChunkSort<ObjectType<T>> for ObjectChunked<T> {
         unimplemented!()
     }
 
-    fn argsort(&self, _reverse: bool) -> UInt32Chunked {
+    fn argsort(&self, _reverse: bool) -> IdxCa {
         unimplemented!()
     }
 }
@@ -230,7 +284,7 @@ impl ChunkSort<BooleanType> for BooleanChunked {
         self.chunks = sorted.chunks;
     }
 
-    fn argsort(&self, reverse: bool) -> UInt32Chunked {
+    fn argsort(&self, reverse: bool) -> IdxCa {
         if reverse {
             argsort!(self, |(_idx_a, a), (_idx_b, b)| b.cmp(a))
         } else {
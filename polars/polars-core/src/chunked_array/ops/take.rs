@@ -419,8 +419,8 @@ impl ChunkTake for ListChunked {
                     return Self::full_null(self.name(), iter.size_hint().0);
                 }
                 let idx_ca = iter
-                    .map(|idx| idx as u32)
-                    .collect::<NoNull<UInt32Chunked>>()
+                    .map(|idx| idx as IdxSize)
+                    .collect::<NoNull<IdxCa>>()
                     .into_inner();
                 self.take((&idx_ca).into())
             }
@@ -429,8 +429,8 @@ impl ChunkTake for ListChunked {
                     return Self::full_null(self.name(), iter.size_hint().0);
                 }
                 let idx_ca = iter
-                    .map(|opt_idx| opt_idx.map(|idx| idx as u32))
-                    .collect::<UInt32Chunked>();
+                    .map(|opt_idx| opt_idx.map(|idx| idx as IdxSize))
+                    .collect::<IdxCa>();
                 self.take((&idx_ca).into())
             }
         }
@@ -849,14 +849,13 @@ impl ChunkTakeEvery<ListType> for ListChunked {
 
 impl ChunkTakeEvery<CategoricalType> for CategoricalChunked {
     fn take_every(&self, n: usize) -> CategoricalChunked {
-        let mut ca = if self.null_count() == 0 {
+        let ca = if self.null_count() == 0 {
             let ca: NoNull<UInt32Chunked> = self.into_no_null_iter().step_by(n).collect();
             ca.into_inner()
         } else {
             self.into_iter().step_by(n).collect()
         };
-        ca.categorical_map = self.categorical_map.clone();
-        ca.cast().unwrap()
+        ca.cast::<CategoricalType>().unwrap().set_state(self)
     }
 }
 #[cfg(feature = "object")]
@@ -1,10 +1,16 @@
 #[cfg(feature = "object")]
 use crate::chunked_array::object::ObjectType;
+use crate::chunked_array::ops::cardinality::ApproxNUnique;
 use crate::frame::group_by::GroupTuples;
 use crate::prelude::*;
-use crate::utils::{floating_encode_f64, integer_decode_f64, NoNull};
+use crate::utils::{floating_encode_f64, integer_decode_f64, split_ca, NoNull};
+use crate::vector_hasher::{
+    create_hash_and_keys_threaded_vectorized, n_hash_partitions, partition_hashed_keys,
+};
+use crate::POOL;
 use crate::{chunked_array::float::IntegerDecode, frame::group_by::IntoGroupTuples};
 use ahash::RandomState;
+use hashbrown::{hash_map::RawEntryMut, HashMap};
 use itertools::Itertools;
 use num::{NumCast, ToPrimitive};
 use rayon::prelude::*;
@@ -14,7 +20,7 @@ use std::hash::Hash;
 
 pub(crate) fn is_unique_helper(
     mut groups: GroupTuples,
-    len: u32,
+    len: IdxSize,
     unique_val: bool,
     duplicated_val: bool,
 ) -> BooleanChunked {
@@ -49,7 +55,7 @@ where
     ChunkedArray<T>: IntoGroupTuples,
 {
     let groups = ca.group_tuples(true);
-    let mut out = is_unique_helper(groups, ca.len() as u32, true, false);
+    let mut out = is_unique_helper(groups, ca.len() as IdxSize, true, false);
     out.rename(ca.name());
     out
 }
@@ -60,7 +66,7 @@ where
     ChunkedArray<T>: IntoGroupTuples,
 {
     let groups = ca.group_tuples(true);
-    let mut out = is_unique_helper(groups, ca.len() as u32, false, true);
+    let mut out = is_unique_helper(groups, ca.len() as IdxSize, false, true);
     out.rename(ca.name());
     out
 }
@@ -72,7 +78,7 @@ impl ChunkUnique<ListType> for ListChunked {
         ))
     }
 
-    fn arg_unique(&self) -> Result<Vec<u32>> {
+    fn arg_unique(&self) -> Result<Vec<IdxSize>> {
         Err(PolarsError::InvalidOperation(
             "unique not supported for list".into(),
         ))
@@ -86,7 +92,7 @@ impl<T> ChunkUnique<ObjectType<T>> for ObjectChunked<T> {
         ))
     }
 
-    fn arg_unique(&self) -> Result<Vec<u32>> {
+    fn arg_unique(&self) -> Result<Vec<IdxSize>> {
         Err(PolarsError::InvalidOperation(
             "unique not supported for object".into(),
         ))
@@ -106,7 +112,7 @@ where
     set
 }
 
-fn arg_unique<T>(a: impl Iterator<Item = T>, capacity: usize) -> Vec<u32>
+fn arg_unique<T>(a: impl Iterator<Item = T>, capacity: usize) -> Vec<IdxSize>
 where
     T: Hash + Eq,
 {
@@ -114,13 +120,13 @@ where
     let mut unique = Vec::with_capacity(capacity);
     a.enumerate().for_each(|(idx, val)| {
         if set.insert(val) {
-            unique.push(idx as u32)
+            unique.push(idx as IdxSize)
         }
     });
     unique
 }
 
-fn arg_unique_ca<'a, T>(ca: &'a ChunkedArray<T>) -> Vec<u32>
+fn arg_unique_ca<'a, T>(ca: &'a ChunkedArray<T>) -> Vec<IdxSize>
 where
     &'a ChunkedArray<T>: IntoIterator + IntoNoNullIterator,
     T: 'a,
@@ -133,14 +139,90 @@ where
     }
 }
 
+/// Arrays of at least this length are deduplicated in parallel by hash partition.
+const PARTITIONED_UNIQUE_MIN_LEN: usize = 1 << 16;
+
+/// First row index of every distinct value, grouped by hash partition. The values are hashed in
+/// parallel and scattered to partitions by hash, so equal values end up in the same partition and
+/// every partition is deduplicated independently with a small hash table.
+fn unique_partitions<I, T>(iters: Vec<I>) -> Vec<Vec<IdxSize>>
+where
+    I: Iterator<Item = T> + Send,
+    T: Send + Sync + Hash + Eq + Copy,
+{
+    let n_partitions = n_hash_partitions(iters.len());
+    let (hashes, random_state) = create_hash_and_keys_threaded_vectorized(iters, None);
+    let partitioned = partition_hashed_keys(hashes, n_partitions);
+
+    POOL.install(|| {
+        (0..n_partitions)
+            .into_par_iter()
+            .map(|partition| {
+                let size = partitioned.iter().map(|p| p[partition].len()).sum();
+                let mut first: HashMap<T, IdxSize, RandomState> =
+                    HashMap::with_capacity_and_hasher(size, random_state.clone());
+                // the keys of a partition are in row order, so the first index of a value wins
+                for (idx, h, k) in partitioned.iter().flat_map(|p| &p[partition]) {
+                    if let RawEntryMut::Vacant(entry) =
+                        first.raw_entry_mut().from_key_hashed_nocheck(*h, k)
+                    {
+                        entry.insert_hashed_nocheck(*h, *k, *idx);
+                    }
+                }
+                first.values().copied().collect()
+            })
+            .collect()
+    })
+}
+
+/// Merge the partitions of [unique_partitions] into sorted first indexes.
+fn merge_unique_partitions(partitions: Vec<Vec<IdxSize>>) -> Vec<IdxSize> {
+    let mut idx = partitions.into_iter().flatten().collect_vec();
+    POOL.install(|| idx.par_sort_unstable());
+    idx
+}
+
+/// [unique_partitions] of a ChunkedArray with one part per thread. `$map` maps a value to a
+/// hashable key.
+macro_rules! unique_partitions {
+    ($ca:expr) => {{
+        unique_partitions!($ca, |v| v)
+    }};
+    ($ca:expr, $map:expr) => {{
+        let splitted = split_ca($ca, POOL.current_num_threads()).unwrap();
+        match $ca.null_count() {
+            0 => unique_partitions(
+                splitted
+                    .iter()
+                    .map(|ca| ca.into_no_null_iter().map($map))
+                    .collect(),
+            ),
+            _ => unique_partitions(
+                splitted
+                    .iter()
+                    .map(|ca| ca.into_iter().map(|opt_v| opt_v.map($map)))
+                    .collect(),
+            ),
+        }
+    }};
+}
+
+/// Unique values in order of first occurrence, from the partitioned first indexes.
+macro_rules! partitioned_unique {
+    ($ca:expr, $partitions:expr) => {{
+        let idx = merge_unique_partitions($partitions);
+        unsafe { $ca.take_unchecked(idx.into_iter().map(|i| i as usize).into()) }
+    }};
+}
+
 macro_rules! impl_value_counts {
     ($self:expr) => {{
         let group_tuples = $self.group_tuples(true);
         let values =
             unsafe { $self.take_unchecked(group_tuples.iter().map(|t| t.0 as usize).into()) };
-        let mut counts: NoNull<UInt32Chunked> = group_tuples
+        let mut counts: NoNull<IdxCa> = group_tuples
             .into_iter()
-            .map(|(_, groups)| groups.len() as u32)
+            .map(|(_, groups)| groups.len() as IdxSize)
             .collect();
         counts.rename("counts");
         let cols = vec![values.into_series(), counts.into_inner().into_series()];
@@ -151,19 +233,36 @@ macro_rules! impl_value_counts {
 
 impl<T> ChunkUnique<T> for ChunkedArray<T>
 where
-    T: PolarsIntegerType,
+    T: PolarsIntegerType + Sync,
     T::Native: Hash + Eq,
     ChunkedArray<T>: ChunkOps + IntoSeries,
 {
     fn unique(&self) -> Result<Self> {
+        if self.len() >= PARTITIONED_UNIQUE_MIN_LEN {
+            return Ok(partitioned_unique!(self, unique_partitions!(self)));
+        }
         let set = fill_set(self.into_iter(), self.len());
         Ok(Self::new_from_opt_iter(self.name(), set.iter().copied()))
     }
 
-    fn arg_unique(&self) -> Result<Vec<u32>> {
+    fn arg_unique(&self) -> Result<Vec<IdxSize>> {
+        if self.len() >= PARTITIONED_UNIQUE_MIN_LEN {
+            return Ok(merge_unique_partitions(unique_partitions!(self)));
+        }
         Ok(arg_unique_ca(self))
     }
 
+    fn n_unique(&self) -> Result<usize> {
+        if self.len() >= PARTITIONED_UNIQUE_MIN_LEN {
+            return Ok(unique_partitions!(self).iter().map(|p| p.len()).sum());
+        }
+        Ok(arg_unique_ca(self).len())
+    }
+
+    fn approx_n_unique(&self) -> Result<usize> {
+        Ok(ApproxNUnique::approx_n_unique(self))
+    }
+
     fn is_unique(&self) -> Result<BooleanChunked> {
         Ok(is_unique(self))
     }
@@ -179,6 +278,9 @@ where
 
 impl ChunkUnique<Utf8Type> for Utf8Chunked {
     fn unique(&self) -> Result<Self> {
+        if self.len() >= PARTITIONED_UNIQUE_MIN_LEN {
+            return Ok(partitioned_unique!(self, unique_partitions!(self)));
+        }
         let set = fill_set(self.into_iter(), self.len());
         Ok(Utf8Chunked::new_from_opt_iter(
             self.name(),
@@ -186,10 +288,24 @@ impl ChunkUnique<Utf8Type> for Utf8Chunked {
         ))
     }
 
-    fn arg_unique(&self) -> Result<Vec<u32>> {
+    fn arg_unique(&self) -> Result<Vec<IdxSize>> {
+        if self.len() >= PARTITIONED_UNIQUE_MIN_LEN {
+            return Ok(merge_unique_partitions(unique_partitions!(self)));
+        }
         Ok(arg_unique_ca(self))
     }
 
+    fn n_unique(&self) -> Result<usize> {
+        if self.len() >= PARTITIONED_UNIQUE_MIN_LEN {
+            return Ok(unique_partitions!(self).iter().map(|p| p.len()).sum());
+        }
+        Ok(arg_unique_ca(self).len())
+    }
+
+    fn approx_n_unique(&self) -> Result<usize> {
+        Ok(ApproxNUnique::approx_n_unique(self))
+    }
+
     fn is_unique(&self) -> Result<BooleanChunked> {
         Ok(is_unique(self))
     }
@@ -205,15 +321,28 @@ impl ChunkUnique<Utf8Type> for Utf8Chunked {
 impl ChunkUnique<CategoricalType> for CategoricalChunked {
     fn unique(&self) -> Result<Self> {
         let set = fill_set(self.into_iter(), self.len());
-        let mut ca = UInt32Chunked::new_from_opt_iter(self.name(), set.iter().copied());
-        ca.categorical_map = self.categorical_map.clone();
-        ca.cast()
+        let ca = UInt32Chunked::new_from_opt_iter(self.name(), set.iter().copied());
+        Ok(ca.cast::<CategoricalType>()?.set_state(self))
     }
 
-    fn arg_unique(&self) -> Result<Vec<u32>> {
+    fn arg_unique(&self) -> Result<Vec<IdxSize>> {
+        if self.len() >= PARTITIONED_UNIQUE_MIN_LEN {
+            return Ok(merge_unique_partitions(unique_partitions!(self)));
+        }
         Ok(arg_unique_ca(self))
     }
 
+    fn n_unique(&self) -> Result<usize> {
+        if self.len() >= PARTITIONED_UNIQUE_MIN_LEN {
+            return Ok(unique_partitions!(self).iter().map(|p| p.len()).sum());
+        }
+        Ok(arg_unique_ca(self).len())
+    }
+
+    fn approx_n_unique(&self) -> Result<usize> {
+        Ok(ApproxNUnique::approx_n_unique(self))
+    }
+
     fn is_unique(&self) -> Result<BooleanChunked> {
         Ok(is_unique(self))
     }
@@ -226,7 +355,7 @@ impl ChunkUnique<CategoricalType> for CategoricalChunked {
     }
 }
 
-fn dummies_helper(mut groups: Vec<u32>, len: usize, name: &str) -> UInt8Chunked {
+fn dummies_helper(mut groups: Vec<IdxSize>, len: usize, name: &str) -> UInt8Chunked {
     groups.sort_unstable();
 
     // let mut group_member_iter = groups.into_iter();
@@ -243,52 +372,55 @@ fn dummies_helper(mut groups: Vec<u32>, len: usize, name: &str) -> UInt8Chunked
     ChunkedArray::new_from_aligned_vec(name, av)
 }
 
-fn sort_columns(columns: Vec<Series>) -> Vec<Series> {
-    columns
-        .into_iter()
-        .sorted_by_key(|s| s.name().to_string())
-        .collect()
+/// Name of a dummy column, e.g. `{name}_{value}` or `{name}_null`.
+fn dummy_name<V: Display>(col_name: &str, val: Option<V>) -> String {
+    match val {
+        Some(val) => format!("{}_{}", col_name, val),
+        None => format!("{}_null", col_name),
+    }
 }
 
 impl ToDummies<Utf8Type> for Utf8Chunked {
     fn to_dummies(&self) -> Result<DataFrame> {
-        let groups = self.group_tuples(true);
+        let mut groups = self.group_tuples(true);
+        // order the dummy columns by value
+        groups.sort_unstable_by_key(|(first, _)| self.get(*first as usize));
         let col_name = self.name();
 
         let columns = groups
             .into_par_iter()
             .map(|(first, groups)| {
-                let val = unsafe { self.get_unchecked(first as usize) };
-                let name = format!("{}_{}", col_name, val);
+                let name = dummy_name(col_name, self.get(first as usize));
                 let ca = dummies_helper(groups, self.len(), &name);
                 ca.into_series()
             })
             .collect();
 
-        Ok(DataFrame::new_no_checks(sort_columns(columns)))
+        Ok(DataFrame::new_no_checks(columns))
     }
 }
 impl<T> ToDummies<T> for ChunkedArray<T>
 where
     T: PolarsIntegerType + Sync,
-    T::Native: Hash + Eq + Display,
+    T::Native: Hash + Eq + Ord + Display,
     ChunkedArray<T>: ChunkOps + ChunkCompare<T::Native> + ChunkUnique<T>,
 {
     fn to_dummies(&self) -> Result<DataFrame> {
-        let groups = self.group_tuples(true);
+        let mut groups = self.group_tuples(true);
+        // order the dummy columns by value
+        groups.sort_unstable_by_key(|(first, _)| self.get(*first as usize));
         let col_name = self.name();
 
         let columns = groups
             .into_par_iter()
             .map(|(first, groups)| {
-                let val = unsafe { self.get_unchecked(first as usize) };
-                let name = format!("{}_{}", col_name, val);
+                let name = dummy_name(col_name, self.get(first as usize));
                 let ca = dummies_helper(groups, self.len(), &name);
                 ca.into_series()
             })
             .collect();
 
-        Ok(DataFrame::new_no_checks(sort_columns(columns)))
+        Ok(DataFrame::new_no_checks(columns))
     }
 }
 
@@ -315,10 +447,15 @@ impl ChunkUnique<BooleanType> for BooleanChunked {
         Ok(ChunkedArray::new_from_opt_slice(self.name(), &unique))
     }
 
-    fn arg_unique(&self) -> Result<Vec<u32>> {
+    fn arg_unique(&self) -> Result<Vec<IdxSize>> {
         Ok(arg_unique_ca(self))
     }
 
+    fn approx_n_unique(&self) -> Result<usize> {
+        // at most three values, counting them exactly is as cheap
+        self.unique().map(|ca| ca.len())
+    }
+
     fn is_unique(&self) -> Result<BooleanChunked> {
         Ok(is_unique(self))
     }
@@ -357,11 +494,14 @@ where
     )
 }
 
-fn float_arg_unique<T>(ca: &ChunkedArray<T>) -> Vec<u32>
+fn float_arg_unique<T>(ca: &ChunkedArray<T>) -> Vec<IdxSize>
 where
-    T: PolarsFloatType,
+    T: PolarsFloatType + Sync,
     T::Native: IntegerDecode,
 {
+    if ca.len() >= PARTITIONED_UNIQUE_MIN_LEN {
+        return merge_unique_partitions(float_unique_partitions(ca));
+    }
     match ca.null_count() {
         0 => arg_unique(ca.into_no_null_iter().map(|v| v.integer_decode()), ca.len()),
         _ => arg_unique(
@@ -372,15 +512,46 @@ where
     }
 }
 
+fn float_unique_partitions<T>(ca: &ChunkedArray<T>) -> Vec<Vec<IdxSize>>
+where
+    T: PolarsFloatType + Sync,
+    T::Native: IntegerDecode,
+{
+    unique_partitions!(ca, |v: T::Native| v.integer_decode())
+}
+
+fn float_n_unique<T>(ca: &ChunkedArray<T>) -> usize
+where
+    T: PolarsFloatType + Sync,
+    T::Native: IntegerDecode,
+{
+    if ca.len() >= PARTITIONED_UNIQUE_MIN_LEN {
+        float_unique_partitions(ca).iter().map(|p| p.len()).sum()
+    } else {
+        float_arg_unique(ca).len()
+    }
+}
+
 impl ChunkUnique<Float32Type> for Float32Chunked {
     fn unique(&self) -> Result<ChunkedArray<Float32Type>> {
+        if self.len() >= PARTITIONED_UNIQUE_MIN_LEN {
+            return Ok(partitioned_unique!(self, float_unique_partitions(self)));
+        }
         Ok(float_unique(self))
     }
 
-    fn arg_unique(&self) -> Result<Vec<u32>> {
+    fn arg_unique(&self) -> Result<Vec<IdxSize>> {
         Ok(float_arg_unique(self))
     }
 
+    fn n_unique(&self) -> Result<usize> {
+        Ok(float_n_unique(self))
+    }
+
+    fn approx_n_unique(&self) -> Result<usize> {
+        Ok(ApproxNUnique::approx_n_unique(self))
+    }
+
     fn is_unique(&self) -> Result<BooleanChunked> {
         Ok(is_unique(self))
     }
@@ -394,13 +565,24 @@ impl ChunkUnique<Float32Type> for Float32Chunked {
 
 impl ChunkUnique<Float64Type> for Float64Chunked {
     fn unique(&self) -> Result<ChunkedArray<Float64Type>> {
+        if self.len() >= PARTITIONED_UNIQUE_MIN_LEN {
+            return Ok(partitioned_unique!(self, float_unique_partitions(self)));
+        }
         Ok(float_unique(self))
     }
 
-    fn arg_unique(&self) -> Result<Vec<u32>> {
+    fn arg_unique(&self) -> Result<Vec<IdxSize>> {
         Ok(float_arg_unique(self))
     }
 
+    fn n_unique(&self) -> Result<usize> {
+        Ok(float_n_unique(self))
+    }
+
+    fn approx_n_unique(&self) -> Result<usize> {
+        Ok(ApproxNUnique::approx_n_unique(self))
+    }
+
     fn is_unique(&self) -> Result<BooleanChunked> {
         Ok(is_unique(self))
     }
@@ -414,6 +596,7 @@ impl ChunkUnique<Float64Type> for Float64Chunked {
 
 #[cfg(test)]
 mod test {
+    use super::PARTITIONED_UNIQUE_MIN_LEN;
     use crate::prelude::*;
     use itertools::Itertools;
 
@@ -438,6 +621,39 @@ mod test {
         );
     }
 
+    #[test]
+    fn unique_partitioned() -> Result<()> {
+        let n = 3 * PARTITIONED_UNIQUE_MIN_LEN;
+        let ca = Utf8Chunked::new_from_opt_iter(
+            "a",
+            (0..n).map(|i| {
+                if i % 7 == 0 {
+                    None
+                } else {
+                    Some(format!("{}", i % 1000))
+                }
+            }),
+        );
+        assert_eq!(ca.n_unique()?, 1000);
+        let idx = ca.arg_unique()?;
+        assert_eq!(idx.len(), 1000);
+        assert_eq!(&idx[..3], &[0, 1, 2]);
+        assert_eq!(ca.unique()?.get(1), Some("1"));
+
+        let ca = Float64Chunked::new_from_iter("a", (0..n).map(|i| (i % 10) as f64));
+        assert_eq!(ca.n_unique()?, 10);
+        assert_eq!(
+            Vec::from(&ca.unique()?),
+            (0..10).map(|i| Some(i as f64)).collect_vec()
+        );
+
+        let ca = UInt32Chunked::new_from_iter("a", (0..n as u32).rev());
+        assert_eq!(ca.n_unique()?, n);
+        let estimate = ca.approx_n_unique()? as f64;
+        assert!((estimate / n as f64 - 1.0).abs() < 0.05);
+        Ok(())
+    }
+
     #[test]
     fn arg_unique() {
         let ca = ChunkedArray::<Int32Type>::new_from_slice("a", &[1, 2, 1, 1, 3]);
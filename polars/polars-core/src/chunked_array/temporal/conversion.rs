@@ -191,15 +191,21 @@ impl Utf8Chunked {
 
     fn sniff_fmt_date64(&self) -> Result<&'static str> {
         let val = self.get_first_val()?;
-        let pat = r"^\d{4}-\d{1,2}-\d{1,2} \d{2}:\d{2}:\d{2}\s*$";
+        // `%.f` also parses timestamps without fractional seconds
+        let pat = r"^\d{4}-\d{1,2}-\d{1,2} \d{2}:\d{2}:\d{2}(\.\d+)?\s*$";
         let reg = Regex::new(pat).expect("wrong regex");
         if reg.is_match(val) {
-            return Ok("%Y-%m-%d %H:%M:%S");
+            return Ok("%Y-%m-%d %H:%M:%S%.f");
         }
-        let pat = r"^\d{4}/\d{1,2}/\d{1,2} \d{2}:\d{2}:\d{2}\s*$";
+        let pat = r"^\d{4}-\d{1,2}-\d{1,2}T\d{2}:\d{2}:\d{2}(\.\d+)?\s*$";
         let reg = Regex::new(pat).expect("wrong regex");
         if reg.is_match(val) {
-            return Ok("%Y/%m/%d %H:%M:%S");
+            return Ok("%Y-%m-%dT%H:%M:%S%.f");
+        }
+        let pat = r"^\d{4}/\d{1,2}/\d{1,2} \d{2}:\d{2}:\d{2}(\.\d+)?\s*$";
+        let reg = Regex::new(pat).expect("wrong regex");
+        if reg.is_match(val) {
+            return Ok("%Y/%m/%d %H:%M:%S%.f");
         }
         Err(PolarsError::Other(
             "Could not find an appropriate format to parse dates, please define a fmt".into(),
@@ -223,7 +229,7 @@ impl Utf8Chunked {
         let pat = r"^\d{4}/\d{1,2}/\d{1,2}\s*$";
         let reg = Regex::new(pat).expect("wrong regex");
         if reg.is_match(val) {
-            return Ok("%Y/%m/%d %H:%M:%S");
+            return Ok("%Y/%m/%d");
         }
         Err(PolarsError::Other(
             "Could not find an appropriate format to parse dates, please define a fmt".into(),
@@ -295,7 +301,7 @@ impl Date64Chunked {
     /// Extract month from underlying NaiveDateTime representation.
     /// Returns the year number in the calendar date.
     pub fn year(&self) -> Int32Chunked {
-        self.apply_kernel_cast::<_, Int32Type>(date64_to_month)
+        self.apply_kernel_cast::<_, Int32Type>(date64_to_year)
     }
 
     /// Extract month from underlying NaiveDateTime representation.
@@ -18,10 +18,10 @@ pub(crate) fn date32_as_datetime(v: i32) -> NaiveDateTime {
 
 pub(crate) fn date64_as_datetime(v: i64) -> NaiveDateTime {
     NaiveDateTime::from_timestamp(
-        // extract seconds from milliseconds
-        v / MILLISECONDS_IN_SECOND,
+        // extract seconds from milliseconds, rounding down for timestamps before the epoch
+        v.div_euclid(MILLISECONDS_IN_SECOND),
         // discard extracted seconds and convert milliseconds to nanoseconds
-        (v % MILLISECONDS_IN_SECOND * MICROSECONDS_IN_SECOND) as u32,
+        (v.rem_euclid(MILLISECONDS_IN_SECOND) * MICROSECONDS_IN_SECOND) as u32,
     )
 }
 
@@ -31,7 +31,7 @@ pub fn naive_datetime_to_date64(v: &NaiveDateTime) -> i64 {
 }
 
 pub fn naive_datetime_to_date32(v: &NaiveDateTime) -> i32 {
-    (naive_datetime_to_date64(v) / (MILLISECONDS_IN_SECOND * SECONDS_IN_DAY)) as i32
+    naive_datetime_to_date64(v).div_euclid(MILLISECONDS_IN_SECOND * SECONDS_IN_DAY) as i32
 }
 
 pub(crate) fn naive_time_to_time64_nanoseconds(v: &NaiveTime) -> i64 {
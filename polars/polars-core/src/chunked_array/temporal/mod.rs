@@ -6,14 +6,65 @@ pub use self::conversion::{
     FromNaiveTime,
 };
 pub(crate) use self::conversions_utils::*;
+use crate::prelude::*;
 use chrono::NaiveDateTime;
 
 pub fn unix_time() -> NaiveDateTime {
     NaiveDateTime::from_timestamp(0, 0)
 }
 
+/// Parse a duration string like `"3d12h30m"` to milliseconds. A duration is a sequence of
+/// integers followed by a unit, optionally preceded by a `-` to negate it. The units are:
+///
+/// * `w`: weeks
+/// * `d`: days
+/// * `h`: hours
+/// * `m`: minutes
+/// * `s`: seconds
+/// * `ms`: milliseconds
+pub fn parse_duration(duration: &str) -> Result<i64> {
+    let err = || PolarsError::ValueError(format!("invalid duration {:?}", duration).into());
+    let (negative, mut rest) = match duration.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, duration),
+    };
+    if rest.is_empty() {
+        return Err(err());
+    }
+
+    let mut total: i64 = 0;
+    while !rest.is_empty() {
+        let n_digits = rest.find(|c: char| !c.is_ascii_digit()).ok_or_else(err)?;
+        if n_digits == 0 {
+            return Err(err());
+        }
+        let value = rest[..n_digits].parse::<i64>().map_err(|_| err())?;
+        rest = &rest[n_digits..];
+
+        let n_unit = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or_else(|| rest.len());
+        let ms_per_unit = match &rest[..n_unit] {
+            "w" => 7 * 86_400_000,
+            "d" => 86_400_000,
+            "h" => 3_600_000,
+            "m" => 60_000,
+            "s" => 1_000,
+            "ms" => 1,
+            _ => return Err(err()),
+        };
+        rest = &rest[n_unit..];
+        total = value
+            .checked_mul(ms_per_unit)
+            .and_then(|ms| total.checked_add(ms))
+            .ok_or_else(err)?;
+    }
+    Ok(if negative { -total } else { total })
+}
+
 #[cfg(all(test, feature = "temporal"))]
 mod test {
+    use super::parse_duration;
     use crate::prelude::*;
     use chrono::{NaiveDateTime, NaiveTime};
 
@@ -63,4 +114,44 @@ mod test {
             ca.cont_slice().unwrap()
         );
     }
+
+    #[test]
+    fn test_date32_date64_cast() {
+        // 1969-12-31 12:00:00, 1970-01-02 00:00:00
+        let ts = Date64Chunked::new_from_slice("ts", &[-43_200_000, 86_400_000]);
+        let days = ts.cast::<Date32Type>().unwrap();
+        assert_eq!(days.cont_slice().unwrap(), &[-1, 1]);
+        assert_eq!(
+            Vec::from(&days.str_fmt("%F")),
+            &[Some("1969-12-31"), Some("1970-01-02")]
+        );
+        assert_eq!(Vec::from(&ts.year()), &[Some(1969), Some(1970)]);
+
+        let back = days.cast::<Date64Type>().unwrap();
+        assert_eq!(back.cont_slice().unwrap(), &[-86_400_000, 86_400_000]);
+
+        // a string cast round trips and matches the Display of the values
+        let s = ts.cast::<Utf8Type>().unwrap();
+        assert_eq!(
+            Vec::from(&s),
+            &[Some("1969-12-31 12:00:00"), Some("1970-01-02 00:00:00")]
+        );
+        assert_eq!(
+            format!("{}", ts.clone().into_series().get(0)),
+            "1969-12-31 12:00:00"
+        );
+        let parsed = s.cast::<Date64Type>().unwrap();
+        assert_eq!(parsed.cont_slice().unwrap(), ts.cont_slice().unwrap());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("3d12h30m").unwrap(), 304_200_000);
+        assert_eq!(parse_duration("1w").unwrap(), 604_800_000);
+        assert_eq!(parse_duration("1m30s250ms").unwrap(), 90_250);
+        assert_eq!(parse_duration("-2h").unwrap(), -7_200_000);
+        for invalid in &["", "-", "3", "d", "3x", "1h-2m", "1.5h"] {
+            assert!(parse_duration(invalid).is_err(), "{}", invalid);
+        }
+    }
 }
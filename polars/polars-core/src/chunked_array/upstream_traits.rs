@@ -21,6 +21,7 @@ impl<T> Default for ChunkedArray<T> {
             chunk_id: Default::default(),
             phantom: PhantomData,
             categorical_map: None,
+            sorted: Default::default(),
         }
     }
 }
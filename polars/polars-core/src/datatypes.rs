@@ -17,7 +17,9 @@ pub use arrow::datatypes::{
     TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
     TimestampSecondType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
 };
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
 
 pub struct Utf8Type {}
 
@@ -86,7 +88,7 @@ impl PolarsDataType for ListType {
 
 impl PolarsDataType for CategoricalType {
     fn get_dtype() -> DataType {
-        DataType::Categorical
+        DataType::Categorical(CategoricalOrdering::Physical)
     }
 }
 
@@ -131,6 +133,19 @@ pub type DurationMillisecondChunked = ChunkedArray<DurationMillisecondType>;
 pub type Time64NanosecondChunked = ChunkedArray<Time64NanosecondType>;
 pub type CategoricalChunked = ChunkedArray<CategoricalType>;
 
+/// The type used for row indices, e.g. in takes, sorts, joins and groupbys.
+/// This is `u32` unless the `bigidx` feature is activated.
+#[cfg(not(feature = "bigidx"))]
+pub type IdxSize = u32;
+#[cfg(feature = "bigidx")]
+pub type IdxSize = u64;
+#[cfg(not(feature = "bigidx"))]
+pub type IdxType = UInt32Type;
+#[cfg(feature = "bigidx")]
+pub type IdxType = UInt64Type;
+pub type IdxCa = ChunkedArray<IdxType>;
+pub type IdxArr = arrow::array::PrimitiveArray<IdxType>;
+
 pub trait PolarsPrimitiveType: ArrowPrimitiveType + Send + Sync + PolarsDataType {}
 // impl PolarsPrimitiveType for BooleanType {}
 impl PolarsPrimitiveType for UInt8Type {}
@@ -254,7 +269,7 @@ impl Display for DataType {
             DataType::List(tp) => return write!(f, "list [{}]", DataType::from(tp)),
             #[cfg(feature = "object")]
             DataType::Object => "object",
-            DataType::Categorical => "cat",
+            DataType::Categorical(_) => "cat",
             _ => panic!("{:?} not implemented", self),
         };
         f.write_str(s)
@@ -314,7 +329,42 @@ pub enum DataType {
     #[cfg(feature = "object")]
     Object,
     Null,
-    Categorical,
+    /// The ordering determines how the values are sorted and compared.
+    Categorical(CategoricalOrdering),
+}
+
+/// Determines how the values of a Categorical are ordered when they are sorted or compared.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub enum CategoricalOrdering {
+    /// Order by the u32 index of the categories. This is the order in which they were inserted
+    /// in the mapping (or the global string cache).
+    Physical,
+    /// Order by the String values of the categories.
+    Lexical,
+    /// A closed set of categories, ordered as they are declared. The u32 index of a category is
+    /// its position in the set. Casting to this type fails on values outside the set.
+    Enum(Arc<Vec<String>>),
+}
+
+impl Default for CategoricalOrdering {
+    fn default() -> Self {
+        CategoricalOrdering::Physical
+    }
+}
+
+/// Whether the values of an array are known to be sorted.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum IsSorted {
+    Ascending,
+    Descending,
+    /// Not sorted, or unknown.
+    Not,
+}
+
+impl Default for IsSorted {
+    fn default() -> Self {
+        IsSorted::Not
+    }
 }
 
 impl DataType {
@@ -343,7 +393,7 @@ impl DataType {
             Null => ArrowDataType::Null,
             #[cfg(feature = "object")]
             Object => unimplemented!(),
-            Categorical => ArrowDataType::UInt16,
+            Categorical(_) => ArrowDataType::UInt16,
         }
     }
 }
@@ -355,10 +405,14 @@ impl PartialEq<ArrowDataType> for DataType {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Hash)]
+/// Key-value metadata of a column, e.g. a description or the unit of the values.
+pub type FieldMetadata = BTreeMap<String, String>;
+
+#[derive(Clone, Debug)]
 pub struct Field {
     name: String,
     data_type: DataType,
+    metadata: FieldMetadata,
 }
 
 impl Field {
@@ -366,6 +420,7 @@ impl Field {
         Field {
             name: name.to_string(),
             data_type,
+            metadata: FieldMetadata::new(),
         }
     }
     pub fn name(&self) -> &String {
@@ -376,8 +431,37 @@ impl Field {
         &self.data_type
     }
 
+    /// Get the key-value metadata of this field.
+    pub fn metadata(&self) -> &FieldMetadata {
+        &self.metadata
+    }
+
+    /// Set the key-value metadata of this field. The metadata is only descriptive, fields that
+    /// only differ in metadata are equal.
+    pub fn with_metadata(mut self, metadata: FieldMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
     pub fn to_arrow(&self) -> ArrowField {
-        ArrowField::new(&self.name, self.data_type.to_arrow(), true)
+        let mut field = ArrowField::new(&self.name, self.data_type.to_arrow(), true);
+        if !self.metadata.is_empty() {
+            field.set_metadata(Some(self.metadata.clone()))
+        }
+        field
+    }
+}
+
+impl PartialEq for Field {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.data_type == other.data_type
+    }
+}
+
+impl Hash for Field {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.data_type.hash(state);
     }
 }
 
@@ -407,8 +491,7 @@ impl Schema {
         let mut new_fields = self.fields.clone();
 
         for (i, name) in idx.into_iter().zip(new_names) {
-            let dt = new_fields[i].data_type.clone();
-            new_fields[i] = Field::new(name.as_ref(), dt)
+            new_fields[i].name = name.as_ref().to_string();
         }
         Ok(Self::new(new_fields))
     }
@@ -520,6 +603,11 @@ impl From<&ArrowDataType> for DataType {
                 DataType::Duration(TimeUnit::Millisecond)
             }
             ArrowDataType::Utf8 => DataType::Utf8,
+            ArrowDataType::Dictionary(_, values)
+                if matches!(**values, ArrowDataType::Utf8 | ArrowDataType::LargeUtf8) =>
+            {
+                DataType::Categorical(CategoricalOrdering::Physical)
+            }
             dt => panic!("Arrow datatype {:?} not supported by Polars", dt),
         }
     }
@@ -527,7 +615,11 @@ impl From<&ArrowDataType> for DataType {
 
 impl From<&ArrowField> for Field {
     fn from(f: &ArrowField) -> Self {
-        Field::new(f.name(), f.data_type().into())
+        let field = Field::new(f.name(), f.data_type().into());
+        match f.metadata() {
+            Some(metadata) => field.with_metadata(metadata.clone()),
+            None => field,
+        }
     }
 }
 impl From<&ArrowSchema> for Schema {
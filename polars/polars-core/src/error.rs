@@ -48,6 +48,8 @@ pub enum PolarsError {
     Regex(#[from] regex::Error),
     #[error("DuplicateError: {0}")]
     Duplicate(ErrString),
+    #[error("The query was cancelled")]
+    Cancelled,
 }
 
 pub type Result<T> = std::result::Result<T, PolarsError>;
@@ -197,7 +197,7 @@ impl Debug for ListChunked {
 impl Debug for CategoricalChunked {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let limit = set_limit!(self);
-        let dtype = format!("{:?}", DataType::Categorical);
+        let dtype = format!("{:?}", self.dtype());
         format_array!(limit, f, self, dtype, self.name(), "ChunkedArray")
     }
 }
@@ -304,7 +304,7 @@ impl Debug for Series {
             }
             #[cfg(feature = "object")]
             DataType::Object => format_object_array(limit, f, self.as_ref(), self.name(), "Series"),
-            DataType::Categorical => format_array!(
+            DataType::Categorical(_) => format_array!(
                 limit,
                 f,
                 self.categorical().unwrap(),
@@ -312,6 +312,13 @@ impl Debug for Series {
                 self.name(),
                 "Series"
             ),
+            DataType::Null => {
+                write![f, "Series: '{}' [null]\n[\n", self.name()]?;
+                for _ in 0..limit {
+                    writeln!(f, "\tnull")?;
+                }
+                write![f, "]"]
+            }
             _ => unimplemented!(),
         }
     }
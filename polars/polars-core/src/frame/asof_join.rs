@@ -0,0 +1,336 @@
+use crate::frame::hash_join::{
+    prepare_multiple_keys, prepare_multiple_keys_relation, probe_multiple_keys, KeysEq,
+};
+use crate::prelude::*;
+use crate::POOL;
+use ahash::AHashMap;
+
+/// Find the last index of `right_idx` whose value is smaller than or equal to `v`.
+/// The values of `right` taken at `right_idx` should be valid and sorted ascending.
+fn asof_search<T: PartialOrd + Copy>(
+    right: &[Option<T>],
+    right_idx: &[IdxSize],
+    v: T,
+) -> Option<IdxSize> {
+    // binary search the number of values that are <= v
+    let mut low = 0;
+    let mut high = right_idx.len();
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if right[right_idx[mid] as usize] <= Some(v) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    if low == 0 {
+        None
+    } else {
+        Some(right_idx[low - 1])
+    }
+}
+
+/// For every value of `left`, find the row of `right` within the group of that row that has the
+/// last value smaller than or equal to it. `groups_left` maps a row of `left` to its group in
+/// `groups_right`. The groups are sorted by the values of `right`, unless `right` is `sorted`.
+fn asof_join_tuples<T, F>(
+    left: &ChunkedArray<T>,
+    right: &ChunkedArray<T>,
+    groups_right: &[Vec<IdxSize>],
+    groups_left: F,
+    sorted: bool,
+) -> Vec<Option<IdxSize>>
+where
+    T: PolarsNumericType,
+    F: Fn(IdxSize) -> Option<usize>,
+{
+    let right_values: Vec<Option<T::Native>> = right.into_iter().collect();
+    let value = |idx: &IdxSize| right_values[*idx as usize];
+    // null values are never matched
+    let groups_right: Vec<Vec<IdxSize>> = groups_right
+        .iter()
+        .map(|group| {
+            let mut group: Vec<IdxSize> = group
+                .iter()
+                .copied()
+                .filter(|idx| value(idx).is_some())
+                .collect();
+            // a stable sort keeps the last of equal values last
+            if !sorted && !group.windows(2).all(|w| value(&w[0]) <= value(&w[1])) {
+                group.sort_by(|a, b| {
+                    value(a)
+                        .partial_cmp(&value(b))
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                });
+            }
+            group
+        })
+        .collect();
+
+    left.into_iter()
+        .enumerate()
+        .map(|(idx, opt_v)| {
+            let v = opt_v?;
+            let group = groups_left(idx as IdxSize)?;
+            asof_search(&right_values, &groups_right[group], v)
+        })
+        .collect()
+}
+
+/// Cast the `on` columns to a common type that can be binary searched.
+fn asof_keys(left: &Series, right: &Series) -> Result<(Series, Series)> {
+    fn is_float(s: &Series) -> Result<bool> {
+        match s.dtype() {
+            DataType::Float32 | DataType::Float64 => Ok(true),
+            DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::Date32
+            | DataType::Date64
+            | DataType::Time64(_)
+            | DataType::Duration(_) => Ok(false),
+            dt => Err(PolarsError::DataTypeMisMatch(
+                format!(
+                    "asof join on column {} of type {:?} is not supported, expected a numeric or temporal column",
+                    s.name(),
+                    dt
+                )
+                .into(),
+            )),
+        }
+    }
+    if is_float(left)? || is_float(right)? {
+        Ok((left.cast::<Float64Type>()?, right.cast::<Float64Type>()?))
+    } else {
+        Ok((left.cast::<Int64Type>()?, right.cast::<Int64Type>()?))
+    }
+}
+
+/// Compute the asof join tuples on the physical type of the `on` columns.
+fn asof_join_tuples_dispatch<F>(
+    s_left: &Series,
+    s_right: &Series,
+    groups_right: &[Vec<IdxSize>],
+    groups_left: F,
+    sorted: bool,
+) -> Vec<Option<IdxSize>>
+where
+    F: Fn(IdxSize) -> Option<usize>,
+{
+    match s_left.dtype() {
+        DataType::Float64 => asof_join_tuples(
+            s_left.f64().unwrap(),
+            s_right.f64().unwrap(),
+            groups_right,
+            groups_left,
+            sorted,
+        ),
+        _ => asof_join_tuples(
+            s_left.i64().unwrap(),
+            s_right.i64().unwrap(),
+            groups_right,
+            groups_left,
+            sorted,
+        ),
+    }
+}
+
+impl DataFrame {
+    /// Perform an asof join on two DataFrames. Every row of `self` is matched with the last row
+    /// of `other` whose `right_on` value is smaller than or equal to the `left_on` value. Rows
+    /// that have no such match get null values.
+    ///
+    /// The rows of `other` are searched in ascending order of `right_on`. Unless that column is
+    /// flagged as sorted, e.g. by a scan that indexed it, its order is checked and it's sorted
+    /// when needed. All columns of `other` are kept, duplicate column names get the `suffix`, or
+    /// `"_right"` if `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    /// fn last_known_price(trades: &DataFrame, quotes: &DataFrame) -> Result<DataFrame> {
+    ///     trades.join_asof(quotes, "time", "time", None)
+    /// }
+    /// ```
+    pub fn join_asof(
+        &self,
+        other: &DataFrame,
+        left_on: &str,
+        right_on: &str,
+        suffix: Option<String>,
+    ) -> Result<DataFrame> {
+        self.join_asof_by(other, left_on, right_on, &[], &[], suffix)
+    }
+
+    /// Perform an [asof join](DataFrame::join_asof) within groups. A row of `self` is only
+    /// matched with rows of `other` that have equal values in the `by_left` and `by_right`
+    /// columns, e.g. the last quote of the same ticker. The `by_right` columns are not kept.
+    ///
+    /// The rows of `other` are searched in ascending order of `right_on` within every group, the
+    /// rows of `self` may be in any order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    /// fn last_known_price(trades: &DataFrame, quotes: &DataFrame) -> Result<DataFrame> {
+    ///     trades.join_asof_by(quotes, "time", "time", &["ticker"], &["ticker"], None)
+    /// }
+    /// ```
+    pub fn join_asof_by(
+        &self,
+        other: &DataFrame,
+        left_on: &str,
+        right_on: &str,
+        by_left: &[&str],
+        by_right: &[&str],
+        suffix: Option<String>,
+    ) -> Result<DataFrame> {
+        if by_left.len() != by_right.len() {
+            return Err(PolarsError::ShapeMisMatch(
+                format!(
+                    "asof join got {} by_left columns and {} by_right columns",
+                    by_left.len(),
+                    by_right.len()
+                )
+                .into(),
+            ));
+        }
+        let sorted = other.column(right_on)?.sorted_flag() == IsSorted::Ascending;
+        let (s_left, s_right) = asof_keys(self.column(left_on)?, other.column(right_on)?)?;
+
+        let opt_join_tuples = if by_left.is_empty() {
+            let all_rows = (0..other.height() as IdxSize).collect();
+            asof_join_tuples_dispatch(&s_left, &s_right, &[all_rows], |_| Some(0), sorted)
+        } else {
+            let selected_left = self.select_series(by_left)?;
+            let selected_right = other
+                .select_series(by_right)?
+                .into_iter()
+                .zip(&selected_left)
+                .map(|(r, l)| {
+                    if r.dtype() == l.dtype() {
+                        Ok(r)
+                    } else {
+                        r.cast_with_datatype(l.dtype())
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let (keys_left, keys_right) = prepare_multiple_keys(&selected_left, &selected_right)?;
+            let keys_eq = KeysEq::new(&keys_left, &keys_right);
+
+            // the rows of every group are in ascending order, and thus sorted by `right_on` if
+            // the column is sorted
+            let (hashes_left, hash_tbl) = prepare_multiple_keys_relation(&keys_left, &keys_right);
+            let mut groups_right = Vec::with_capacity(hash_tbl.len());
+            let mut first_to_group = AHashMap::with_capacity(hash_tbl.len());
+            for (first, group) in hash_tbl.values() {
+                first_to_group.insert(*first, groups_right.len());
+                groups_right.push(group.clone());
+            }
+            let hashes_left: Vec<u64> = hashes_left.into_no_null_iter().collect();
+
+            asof_join_tuples_dispatch(
+                &s_left,
+                &s_right,
+                &groups_right,
+                |idx| {
+                    probe_multiple_keys(&hash_tbl, &keys_eq, idx, hashes_left[idx as usize])
+                        .map(|(first, _)| first_to_group[first])
+                },
+                sorted,
+            )
+        };
+
+        let mut df_right = other.clone();
+        for name in by_right {
+            df_right = df_right.drop(name)?;
+        }
+        let (df_left, df_right) = POOL.join(
+            || self.clone(),
+            || unsafe {
+                df_right.take_opt_iter_unchecked(
+                    opt_join_tuples
+                        .iter()
+                        .map(|opt_idx| opt_idx.map(|i| i as usize)),
+                )
+            },
+        );
+        self.finish_join(df_left, df_right, suffix)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_join_asof() -> Result<()> {
+        let trades = df![
+            "time" => &[1, 3, 5, 10],
+            "qty" => &[100, 200, 300, 400]
+        ]?;
+        let quotes = df![
+            "time" => &[2, 3, 7],
+            "price" => &[1.0, 1.5, 2.0]
+        ]?;
+
+        let out = trades.join_asof(&quotes, "time", "time", None)?;
+        assert_eq!(out.shape(), (4, 4));
+        assert_eq!(
+            Vec::from(out.column("price")?.f64()?),
+            &[None, Some(1.5), Some(1.5), Some(2.0)]
+        );
+        assert_eq!(
+            Vec::from(out.column("time_right")?.i32()?),
+            &[None, Some(3), Some(3), Some(7)]
+        );
+
+        // unsorted quotes are searched in order of time
+        let quotes = df![
+            "time" => &[7, 2, 3],
+            "price" => &[2.0, 1.0, 1.5]
+        ]?;
+        let out = trades.join_asof(&quotes, "time", "time", None)?;
+        assert_eq!(
+            Vec::from(out.column("price")?.f64()?),
+            &[None, Some(1.5), Some(1.5), Some(2.0)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_join_asof_by() -> Result<()> {
+        let trades = df![
+            "time" => &[3, 4, 5, 6, 1],
+            "ticker" => &["a", "b", "a", "c", "b"]
+        ]?;
+        let quotes = df![
+            "time" => &[1, 2, 2, 4, 5],
+            "symbol" => &["a", "b", "a", "b", "a"],
+            "price" => &[10, 20, 11, 21, 12]
+        ]?;
+
+        let out = trades.join_asof_by(&quotes, "time", "time", &["ticker"], &["symbol"], None)?;
+        assert_eq!(
+            out.get_column_names(),
+            &["time", "ticker", "time_right", "price"]
+        );
+        assert_eq!(
+            Vec::from(out.column("price")?.i32()?),
+            &[Some(11), Some(21), Some(12), None, None]
+        );
+
+        assert!(trades
+            .join_asof_by(&quotes, "time", "time", &["ticker"], &[], None)
+            .is_err());
+        assert!(trades.join_asof(&quotes, "ticker", "symbol", None).is_err());
+        Ok(())
+    }
+}
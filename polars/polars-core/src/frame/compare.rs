@@ -0,0 +1,145 @@
+use crate::frame::hash_join::JoinType;
+use crate::prelude::*;
+
+const IN_LEFT: &str = "__POLARS_IN_LEFT";
+const IN_RIGHT: &str = "__POLARS_IN_RIGHT";
+
+/// Per row: is the cell in `old` different from the cell in `new`. Nulls are equal to each other.
+fn cells_changed(old: &Series, new: &Series) -> Result<Vec<bool>> {
+    let new = if new.dtype() != old.dtype() {
+        new.cast_with_datatype(old.dtype())?
+    } else {
+        new.clone()
+    };
+    let neq = old.neq(&new);
+    let changed = neq
+        .into_iter()
+        .zip(old.is_null().into_iter())
+        .zip(new.is_null().into_iter())
+        .map(|((opt_neq, old_null), new_null)| match opt_neq {
+            Some(neq) => neq,
+            None => old_null != new_null,
+        })
+        .collect();
+    Ok(changed)
+}
+
+impl DataFrame {
+    /// Compare this DataFrame (the old version) with `other` (the new version). Rows are matched
+    /// on the `keys` columns with an outer join.
+    ///
+    /// Returns a DataFrame with the key columns, a `status` column that is one of `"added"`,
+    /// `"removed"` or `"changed"`, and for every other column that both DataFrames have, an
+    /// `{name}_old` and `{name}_new` column. Unchanged rows are not part of the output.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    ///
+    /// fn reconcile(yesterday: &DataFrame, today: &DataFrame) -> Result<DataFrame> {
+    ///     let diff = yesterday.compare(today, &["id"])?;
+    ///     // only the rows that were updated
+    ///     let mask = diff.column("status")?.eq("changed");
+    ///     diff.filter(&mask)
+    /// }
+    /// ```
+    pub fn compare(&self, other: &DataFrame, keys: &[&str]) -> Result<DataFrame> {
+        if keys.is_empty() {
+            return Err(PolarsError::InvalidOperation(
+                "compare needs at least one key column".into(),
+            ));
+        }
+        let value_names = self
+            .get_column_names()
+            .into_iter()
+            .filter(|name| !keys.contains(name) && other.column(name).is_ok())
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+
+        let mut left = self.clone();
+        left.add_column(BooleanChunked::full(IN_LEFT, true, self.height()))?;
+        let mut right = other.select(keys.to_vec())?;
+        for name in &value_names {
+            right.add_column(other.column(name)?.clone())?;
+        }
+        right.add_column(BooleanChunked::full(IN_RIGHT, true, other.height()))?;
+
+        let joined = left.join(&right, keys.to_vec(), keys.to_vec(), JoinType::Outer)?;
+        let in_left = joined.column(IN_LEFT)?.is_not_null();
+        let in_right = joined.column(IN_RIGHT)?.is_not_null();
+
+        let mut row_changed = vec![false; joined.height()];
+        let mut value_columns = Vec::with_capacity(value_names.len() * 2);
+        for name in &value_names {
+            let mut old = joined.column(name)?.clone();
+            let mut new = joined.column(&format!("{}_right", name))?.clone();
+            for (row, changed) in row_changed
+                .iter_mut()
+                .zip(cells_changed(&old, &new)?.into_iter())
+            {
+                *row |= changed;
+            }
+            old.rename(&format!("{}_old", name));
+            new.rename(&format!("{}_new", name));
+            value_columns.push(old);
+            value_columns.push(new);
+        }
+
+        let status: Utf8Chunked = in_left
+            .into_no_null_iter()
+            .zip(in_right.into_no_null_iter())
+            .zip(row_changed.into_iter())
+            .map(|((in_left, in_right), changed)| match (in_left, in_right) {
+                (true, false) => Some("removed"),
+                (false, true) => Some("added"),
+                _ if changed => Some("changed"),
+                _ => None,
+            })
+            .collect();
+        let mask = status.is_not_null();
+
+        let mut columns = joined.select_series(keys.to_vec())?;
+        let mut status = status.into_series();
+        status.rename("status");
+        columns.push(status);
+        columns.extend(value_columns);
+        DataFrame::new(columns)?.filter(&mask)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_compare() -> Result<()> {
+        let old = df![
+            "id" => &[1, 2, 3],
+            "value" => &["a", "b", "c"]
+        ]?;
+        let new = df![
+            "id" => &[2, 3, 4],
+            "value" => &["b", "z", "d"]
+        ]?;
+        let diff = old.compare(&new, &["id"])?.sort("id", false)?;
+
+        assert_eq!(
+            Vec::from(diff.column("id")?.i32()?),
+            &[Some(1), Some(3), Some(4)]
+        );
+        assert_eq!(
+            Vec::from(diff.column("status")?.utf8()?),
+            &[Some("removed"), Some("changed"), Some("added")]
+        );
+        assert_eq!(
+            Vec::from(diff.column("value_old")?.utf8()?),
+            &[Some("a"), Some("c"), None]
+        );
+        assert_eq!(
+            Vec::from(diff.column("value_new")?.utf8()?),
+            &[None, Some("z"), Some("d")]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use crate::prelude::*;
+use crate::POOL;
+
+impl DataFrame {
+    /// Create the cartesian product of this DataFrame and `other`. Every row of `self` is
+    /// repeated `other.height()` times. Duplicate column names of `other` get a `_right` suffix.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    /// fn all_combinations(sizes: &DataFrame, colors: &DataFrame) -> Result<DataFrame> {
+    ///     sizes.cross_join(colors)
+    /// }
+    /// ```
+    pub fn cross_join(&self, other: &DataFrame) -> Result<DataFrame> {
+        self.cross_join_impl(other, None)
+    }
+
+    pub(crate) fn cross_join_impl(
+        &self,
+        other: &DataFrame,
+        suffix: Option<String>,
+    ) -> Result<DataFrame> {
+        let n_left = self.height();
+        let n_right = other.height();
+        let n_rows = n_left.checked_mul(n_right).ok_or_else(|| {
+            PolarsError::ValueError(
+                format!(
+                    "cross join of {} and {} rows would overflow the number of rows",
+                    n_left, n_right
+                )
+                .into(),
+            )
+        })?;
+
+        // Safety:
+        // for every i < n_left * n_right, i / n_right < n_left and i % n_right < n_right.
+        let (df_left, df_right) = POOL.join(
+            || unsafe { self.take_iter_unchecked((0..n_rows).map(|i| i / n_right)) },
+            || unsafe { other.take_iter_unchecked((0..n_rows).map(|i| i % n_right)) },
+        );
+        self.finish_join(df_left, df_right, suffix)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::frame::hash_join::JoinType;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_cross_join() -> Result<()> {
+        let df_a = df![
+            "a" => &[1, 2],
+            "b" => &["x", "y"]
+        ]?;
+        let df_b = df![
+            "a" => &[10, 20, 30]
+        ]?;
+
+        let out = df_a.join(
+            &df_b,
+            Vec::<&str>::new(),
+            Vec::<&str>::new(),
+            JoinType::Cross,
+        )?;
+        assert_eq!(out.shape(), (6, 3));
+        assert_eq!(
+            Vec::from(out.column("a")?.i32()?),
+            &[Some(1), Some(1), Some(1), Some(2), Some(2), Some(2)]
+        );
+        assert_eq!(
+            Vec::from(out.column("a_right")?.i32()?),
+            &[Some(10), Some(20), Some(30), Some(10), Some(20), Some(30)]
+        );
+
+        let empty = df_b.head(Some(0));
+        assert_eq!(df_a.cross_join(&empty)?.height(), 0);
+        Ok(())
+    }
+}
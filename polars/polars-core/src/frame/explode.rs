@@ -14,6 +14,9 @@ fn get_exploded(series: &Series) -> Result<(Series, &[i64])> {
 impl DataFrame {
     /// Explode `DataFrame` to long format by exploding a column with Lists.
     ///
+    /// The order of the rows is always maintained, the values of an exploded row directly follow
+    /// each other in the order of their list.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -113,6 +116,9 @@ impl DataFrame {
     ///
     /// * `id_vars` - String slice that represent the columns to use as id variables.
     /// * `value_vars` - String slice that represent the columns to use as value variables.
+    /// * `maintain_order` - Keep the order of the rows: every row is followed by the values of the
+    ///                      next value variable. Otherwise the rows of the first value variable
+    ///                      are followed by the rows of the next, which is cheaper to compute.
     ///
     /// ```rust
     ///
@@ -125,7 +131,7 @@ impl DataFrame {
     ///     )
     /// .unwrap();
     ///
-    /// let melted = df.melt(&["A", "B"], &["C", "D"]).unwrap();
+    /// let melted = df.melt(&["A", "B"], &["C", "D"], false).unwrap();
     /// println!("{:?}", df);
     /// println!("{:?}", melted);
     /// ```
@@ -165,16 +171,17 @@ impl DataFrame {
         &self,
         id_vars: SelId,
         value_vars: SelValue,
+        maintain_order: bool,
     ) -> Result<Self> {
         let ids = self.select(id_vars)?;
-        let value_vars = value_vars.to_selection_vec();
+        let value_vars = self.select_series(value_vars)?;
         let len = self.height();
+        let n_vars = value_vars.len();
 
         let mut dataframe_chunks = VecDeque::with_capacity(value_vars.len());
 
-        for value_column_name in value_vars {
-            let variable_col = Utf8Chunked::full("variable", value_column_name, len).into_series();
-            let mut value_col = self.column(value_column_name)?.clone();
+        for mut value_col in value_vars {
+            let variable_col = Utf8Chunked::full("variable", value_col.name(), len).into_series();
             value_col.rename("value");
 
             let mut df_chunk = ids.clone();
@@ -189,6 +196,11 @@ impl DataFrame {
         while let Some(df) = dataframe_chunks.pop_front() {
             main_df.vstack_mut(&df)?;
         }
+        if maintain_order {
+            // row i of value variable j is at j * len + i
+            let idx = (0..len).flat_map(|i| (0..n_vars).map(move |j| j * len + i));
+            main_df = unsafe { main_df.take_iter_unchecked(idx) };
+        }
         Ok(main_df)
     }
 }
@@ -240,10 +252,31 @@ mod test {
         )
         .unwrap();
 
-        let melted = df.melt(&["A", "B"], &["C", "D"]).unwrap();
+        let melted = df.melt(&["A", "B"], &["C", "D"], false).unwrap();
         assert_eq!(
             Vec::from(melted.column("value").unwrap().i32().unwrap()),
             &[Some(10), Some(11), Some(12), Some(2), Some(4), Some(6)]
+        );
+
+        let melted = df.melt(&["A", "B"], &["C", "D"], true).unwrap();
+        assert_eq!(
+            Vec::from(melted.column("B").unwrap().i32().unwrap()),
+            &[Some(1), Some(1), Some(3), Some(3), Some(5), Some(5)]
+        );
+        assert_eq!(
+            Vec::from(melted.column("variable").unwrap().utf8().unwrap()),
+            &[
+                Some("C"),
+                Some("D"),
+                Some("C"),
+                Some("D"),
+                Some("C"),
+                Some("D")
+            ]
+        );
+        assert_eq!(
+            Vec::from(melted.column("value").unwrap().i32().unwrap()),
+            &[Some(10), Some(2), Some(11), Some(4), Some(12), Some(6)]
         )
     }
 }
@@ -2,7 +2,7 @@ use crate::chunked_array::kernels::take_agg::{
     take_agg_no_null_primitive_iter_unchecked, take_agg_primitive_iter_unchecked,
 };
 use crate::chunked_array::{builder::PrimitiveChunkedBuilder, float::IntegerDecode};
-use crate::frame::select::Selection;
+use crate::frame::select::{NameSelection, Selection};
 use crate::prelude::*;
 use crate::utils::{accumulate_dataframes_vertical, split_ca, split_df, NoNull};
 use crate::vector_hasher::{
@@ -16,14 +16,27 @@ use itertools::Itertools;
 use num::{Bounded, Num, NumCast, ToPrimitive, Zero};
 use polars_arrow::prelude::*;
 use rayon::prelude::*;
-use std::collections::HashSet;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
 use std::hash::{BuildHasher, Hash, Hasher};
 use std::{
     fmt::{Debug, Formatter},
     ops::Add,
 };
 
-pub type GroupTuples = Vec<(u32, Vec<u32>)>;
+pub type GroupTuples = Vec<(IdxSize, Vec<IdxSize>)>;
+
+/// Hash a value that may be null. A value is hashed like in the branches without nulls, so that
+/// its hash doesn't depend on the presence of nulls in the array.
+#[inline]
+fn hash_opt_value<T: Hash>(opt_v: Option<T>, random_state: &RandomState) -> u64 {
+    let mut hasher = random_state.build_hasher();
+    match &opt_v {
+        Some(v) => v.hash(&mut hasher),
+        None => opt_v.hash(&mut hasher),
+    }
+    hasher.finish()
+}
 
 pub trait VecHash {
     /// Compute the hase for all values in the array.
@@ -47,11 +60,7 @@ where
                 hasher.finish()
             })
         } else {
-            self.branch_apply_cast_numeric_no_null(|opt_v| {
-                let mut hasher = random_state.build_hasher();
-                opt_v.hash(&mut hasher);
-                hasher.finish()
-            })
+            self.branch_apply_cast_numeric_no_null(|opt_v| hash_opt_value(opt_v, &random_state))
         }
     }
 }
@@ -65,11 +74,7 @@ impl VecHash for Utf8Chunked {
                 hasher.finish()
             })
         } else {
-            self.branch_apply_cast_numeric_no_null(|opt_v| {
-                let mut hasher = random_state.build_hasher();
-                opt_v.hash(&mut hasher);
-                hasher.finish()
-            })
+            self.branch_apply_cast_numeric_no_null(|opt_v| hash_opt_value(opt_v, &random_state))
         }
     }
 }
@@ -83,11 +88,7 @@ impl VecHash for BooleanChunked {
                 hasher.finish()
             })
         } else {
-            self.branch_apply_cast_numeric_no_null(|opt_v| {
-                let mut hasher = random_state.build_hasher();
-                opt_v.hash(&mut hasher);
-                hasher.finish()
-            })
+            self.branch_apply_cast_numeric_no_null(|opt_v| hash_opt_value(opt_v, &random_state))
         }
     }
 }
@@ -103,10 +104,7 @@ impl VecHash for Float32Chunked {
             })
         } else {
             self.branch_apply_cast_numeric_no_null(|opt_v| {
-                let opt_v = opt_v.map(|v| v.to_bits());
-                let mut hasher = random_state.build_hasher();
-                opt_v.hash(&mut hasher);
-                hasher.finish()
+                hash_opt_value(opt_v.map(|v| v.to_bits()), &random_state)
             })
         }
     }
@@ -122,10 +120,7 @@ impl VecHash for Float64Chunked {
             })
         } else {
             self.branch_apply_cast_numeric_no_null(|opt_v| {
-                let opt_v = opt_v.map(|v| v.to_bits());
-                let mut hasher = random_state.build_hasher();
-                opt_v.hash(&mut hasher);
-                hasher.finish()
+                hash_opt_value(opt_v.map(|v| v.to_bits()), &random_state)
             })
         }
     }
@@ -179,18 +174,18 @@ where
             let hashes_and_keys = &hashes_and_keys;
             let thread_no = thread_no as u64;
 
-            let mut hash_tbl: HashMap<T, (u32, Vec<u32>), RandomState> =
+            let mut hash_tbl: HashMap<T, (IdxSize, Vec<IdxSize>), RandomState> =
                 HashMap::with_capacity_and_hasher(size / n_threads, random_state);
 
             let n_threads = n_threads as u64;
             let mut offset = 0;
             for hashes_and_keys in hashes_and_keys {
-                let len = hashes_and_keys.len() as u32;
+                let len = hashes_and_keys.len() as IdxSize;
                 hashes_and_keys
                     .iter()
                     .enumerate()
                     .for_each(|(idx, (h, k))| {
-                        let idx = idx as u32;
+                        let idx = idx as IdxSize;
                         // partition hashes by thread no.
                         // So only a part of the hashes go to this hashmap
                         if (h + thread_no) % n_threads == 0 {
@@ -230,7 +225,7 @@ where
 ///
 /// # Safety
 /// Doesn't check any bounds
-unsafe fn compare_fn(keys: &DataFrame, idx_a: u32, idx_b: u32) -> bool {
+unsafe fn compare_fn(keys: &DataFrame, idx_a: IdxSize, idx_b: IdxSize) -> bool {
     for s in keys.get_columns() {
         if !(s.get_unchecked(idx_a as usize) == s.get_unchecked(idx_b as usize)) {
             return false;
@@ -239,22 +234,21 @@ unsafe fn compare_fn(keys: &DataFrame, idx_a: u32, idx_b: u32) -> bool {
     true
 }
 
-fn populate_multiple_key_hashmap(
-    hash_tbl: &mut HashMap<IdxHash, (u32, Vec<u32>), IdBuildHasher>,
-    idx: u32,
+/// Insert row `idx` with hash `h` in the hash table. `eq_rows` compares the row of an entry with
+/// row `idx` of the keys.
+pub(crate) fn populate_multiple_key_hashmap<F>(
+    hash_tbl: &mut HashMap<IdxHash, (IdxSize, Vec<IdxSize>), IdBuildHasher>,
+    idx: IdxSize,
     h: u64,
-    keys: &DataFrame,
-) {
+    eq_rows: F,
+) where
+    F: Fn(IdxSize, IdxSize) -> bool,
+{
     let entry = hash_tbl
         .raw_entry_mut()
         // uses the idx to probe rows in the original DataFrame with keys
         // to check equality to find an entry
-        .from_hash(h, |idx_hash| {
-            let key_idx = idx_hash.idx;
-            // Safety:
-            // indices in a join operation are always in bounds.
-            unsafe { compare_fn(keys, key_idx, idx) }
-        });
+        .from_hash(h, |idx_hash| eq_rows(idx_hash.idx, idx));
     match entry {
         RawEntryMut::Vacant(entry) => {
             entry.insert_hashed_nocheck(h, IdxHash::new(idx, h), (idx, vec![idx]));
@@ -270,14 +264,18 @@ fn groupby_multiple_keys(keys: DataFrame) -> GroupTuples {
     let (hashes, _) = df_rows_to_hashes(&keys, None);
     let size = hashes.len();
     // rather over allocate because rehashing is expensive
-    let mut hash_tbl: HashMap<IdxHash, (u32, Vec<u32>), IdBuildHasher> =
+    let mut hash_tbl: HashMap<IdxHash, (IdxSize, Vec<IdxSize>), IdBuildHasher> =
         HashMap::with_capacity_and_hasher(size, IdBuildHasher::default());
 
     // hashes has no nulls
     let mut idx = 0;
     for hashes_chunk in hashes.data_views() {
         for &h in hashes_chunk {
-            populate_multiple_key_hashmap(&mut hash_tbl, idx, h, &keys);
+            // Safety:
+            // indices in a groupby operation are always in bounds.
+            populate_multiple_key_hashmap(&mut hash_tbl, idx, h, |a, b| unsafe {
+                compare_fn(&keys, a, b)
+            });
             idx += 1;
         }
     }
@@ -303,13 +301,13 @@ fn groupby_threaded_multiple_keys_flat(keys: DataFrame, n_threads: usize) -> Gro
             let keys = &keys;
 
             // rather over allocate because rehashing is expensive
-            let mut hash_tbl: HashMap<IdxHash, (u32, Vec<u32>), IdBuildHasher> =
+            let mut hash_tbl: HashMap<IdxHash, (IdxSize, Vec<IdxSize>), IdBuildHasher> =
                 HashMap::with_capacity_and_hasher(size / n_threads, IdBuildHasher::default());
 
             let n_threads = n_threads as u64;
             let mut offset = 0;
             for hashes in hashes {
-                let len = hashes.len() as u32;
+                let len = hashes.len() as IdxSize;
 
                 let mut idx = 0;
                 for hashes_chunk in hashes.data_views() {
@@ -318,7 +316,11 @@ fn groupby_threaded_multiple_keys_flat(keys: DataFrame, n_threads: usize) -> Gro
                         // So only a part of the hashes go to this hashmap
                         if (h + thread_no) % n_threads == 0 {
                             let idx = idx + offset;
-                            populate_multiple_key_hashmap(&mut hash_tbl, idx, h, &keys);
+                            // Safety:
+                            // indices in a groupby operation are always in bounds.
+                            populate_multiple_key_hashmap(&mut hash_tbl, idx, h, |a, b| unsafe {
+                                compare_fn(keys, a, b)
+                            });
                         }
                         idx += 1;
                     }
@@ -433,6 +435,10 @@ impl IntoGroupTuples for BooleanChunked {
 
 impl IntoGroupTuples for Utf8Chunked {
     fn group_tuples(&self, multithreaded: bool) -> GroupTuples {
+        // group on integer codes if the strings repeat a lot
+        if self.should_intern() {
+            return self.intern_local().group_tuples(multithreaded);
+        }
         group_tuples!(self, multithreaded)
     }
 }
@@ -590,7 +596,7 @@ impl<'b> (dyn SeriesTrait + 'b) {
             DataType::Utf8 => as_groupable_iter!(self.utf8().unwrap(), Utf8),
             DataType::Float32 => Ok(float_to_groupable_iter(self.f32().unwrap())),
             DataType::Float64 => Ok(float_to_groupable_iter(self.f64().unwrap())),
-            DataType::Categorical => as_groupable_iter!(self.categorical().unwrap(), UInt32),
+            DataType::Categorical(_) => as_groupable_iter!(self.categorical().unwrap(), UInt32),
             dt => Err(PolarsError::Other(
                 format!("Column with dtype {:?} is not groupable", dt).into(),
             )),
@@ -610,7 +616,7 @@ impl DataFrame {
         let keys_df = DataFrame::new(
             by.iter()
                 .map(|s| match s.dtype() {
-                    DataType::Categorical => s.cast::<UInt32Type>().unwrap(),
+                    DataType::Categorical(_) => s.cast::<UInt32Type>().unwrap(),
                     _ => s.clone(),
                 })
                 .collect(),
@@ -725,30 +731,152 @@ pub struct GroupBy<'df, 'selection_str> {
 }
 
 pub(crate) trait NumericAggSync {
-    fn agg_mean(&self, _groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+    fn agg_mean(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
         None
     }
-    fn agg_min(&self, _groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+    fn agg_min(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
         None
     }
-    fn agg_max(&self, _groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+    fn agg_max(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
         None
     }
-    fn agg_sum(&self, _groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+    fn agg_sum(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
         None
     }
-    fn agg_std(&self, _groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+    fn agg_std(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
         None
     }
-    fn agg_var(&self, _groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+    fn agg_var(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
         None
     }
+    fn agg_min_max(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<(Series, Series)> {
+        Some((self.agg_min(groups)?, self.agg_max(groups)?))
+    }
+    fn agg_sum_count(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<(Series, Series)> {
+        Some((self.agg_sum(groups)?, agg_count(groups)))
+    }
+    fn agg_mean_count(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<(Series, Series)> {
+        Some((self.agg_mean(groups)?, agg_count(groups)))
+    }
+}
+
+/// The length of every group, which is the `count` aggregation.
+pub(crate) fn agg_count(groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
+    let ca: NoNull<IdxCa> = groups.iter().map(|(_, g)| g.len() as IdxSize).collect();
+    ca.into_inner().into_series()
+}
+
+/// The sum of the values of a group, `None` if they are all null.
+fn group_sum<T>(ca: &ChunkedArray<T>, first: IdxSize, idx: &[IdxSize]) -> Option<T::Native>
+where
+    T: PolarsNumericType,
+    T::Native: Num + NumCast + PartialOrd,
+{
+    if idx.len() == 1 {
+        return ca.get(first as usize);
+    }
+    match (ca.null_count(), ca.chunks.len()) {
+        (0, 1) => Some(unsafe {
+            take_agg_no_null_primitive_iter_unchecked(
+                ca.downcast_chunks()[0],
+                idx.iter().map(|i| *i as usize),
+                |a, b| a + b,
+                T::Native::zero(),
+            )
+        }),
+        (_, 1) => unsafe {
+            take_agg_primitive_iter_unchecked(
+                ca.downcast_chunks()[0],
+                idx.iter().map(|i| *i as usize),
+                |a, b| a + b,
+                T::Native::zero(),
+            )
+        },
+        _ => {
+            let take = unsafe { ca.take_unchecked(idx.iter().map(|i| *i as usize).into()) };
+            take.sum()
+        }
+    }
+}
+
+/// The row index of the minimum or maximum value per group.
+fn min_max_idx<V, F>(groups: &[(IdxSize, Vec<IdxSize>)], get: F, max: bool) -> Vec<Option<usize>>
+where
+    V: Ord,
+    F: Fn(usize) -> Option<V>,
+{
+    groups
+        .iter()
+        .map(|(_first, idx)| {
+            let iter = idx
+                .iter()
+                .filter_map(|i| get(*i as usize).map(|v| (*i as usize, v)));
+            let opt = if max {
+                iter.max_by(|a, b| a.1.cmp(&b.1))
+            } else {
+                iter.min_by(|a, b| a.1.cmp(&b.1))
+            };
+            opt.map(|(i, _v)| i)
+        })
+        .collect()
+}
+
+/// Booleans are aggregated as 1 or 0.
+impl NumericAggSync for BooleanChunked {
+    fn agg_mean(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+        self.cast::<UInt32Type>().ok()?.agg_mean(groups)
+    }
+    fn agg_min(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+        self.cast::<UInt32Type>().ok()?.agg_min(groups)
+    }
+    fn agg_max(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+        self.cast::<UInt32Type>().ok()?.agg_max(groups)
+    }
+    fn agg_sum(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+        self.cast::<UInt32Type>().ok()?.agg_sum(groups)
+    }
+}
+
+/// Strings are compared lexically.
+impl NumericAggSync for Utf8Chunked {
+    fn agg_min(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+        let idx = min_max_idx(groups, |i| self.get(i), false);
+        Some(self.take(Wrap(idx.into_iter()).into()).into_series())
+    }
+    fn agg_max(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+        let idx = min_max_idx(groups, |i| self.get(i), true);
+        Some(self.take(Wrap(idx.into_iter()).into()).into_series())
+    }
 }
 
-impl NumericAggSync for BooleanChunked {}
-impl NumericAggSync for Utf8Chunked {}
 impl NumericAggSync for ListChunked {}
-impl NumericAggSync for CategoricalChunked {}
+
+fn categorical_agg_min_max(
+    ca: &CategoricalChunked,
+    groups: &[(IdxSize, Vec<IdxSize>)],
+    max: bool,
+) -> Series {
+    let idx = match ca.ordering() {
+        CategoricalOrdering::Physical | CategoricalOrdering::Enum(_) => {
+            min_max_idx(groups, |i| ca.get(i), max)
+        }
+        CategoricalOrdering::Lexical => {
+            let values = ca.iter_str().collect::<Vec<_>>();
+            min_max_idx(groups, |i| values[i], max)
+        }
+    };
+    ca.take(Wrap(idx.into_iter()).into()).into_series()
+}
+
+/// Categoricals are compared by their ordering.
+impl NumericAggSync for CategoricalChunked {
+    fn agg_min(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+        Some(categorical_agg_min_max(self, groups, false))
+    }
+    fn agg_max(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+        Some(categorical_agg_min_max(self, groups, true))
+    }
+}
 #[cfg(feature = "object")]
 impl<T> NumericAggSync for ObjectChunked<T> {}
 
@@ -758,7 +886,7 @@ where
     T::Native: std::ops::Add<Output = T::Native> + Num + NumCast + Bounded,
     ChunkedArray<T>: IntoSeries,
 {
-    fn agg_mean(&self, groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+    fn agg_mean(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
         let ca: Float64Chunked = groups
             .par_iter()
             .map(|(first, idx)| {
@@ -799,7 +927,7 @@ where
         Some(ca.into_series())
     }
 
-    fn agg_min(&self, groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+    fn agg_min(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
         Some(
             groups
                 .par_iter()
@@ -838,7 +966,7 @@ where
         )
     }
 
-    fn agg_max(&self, groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+    fn agg_max(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
         Some(
             groups
                 .par_iter()
@@ -877,82 +1005,113 @@ where
         )
     }
 
-    fn agg_sum(&self, groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+    fn agg_sum(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
         Some(
             groups
                 .par_iter()
-                .map(|(first, idx)| {
-                    if idx.len() == 1 {
-                        self.get(*first as usize)
-                    } else {
-                        match (self.null_count(), self.chunks.len()) {
-                            (0, 1) => Some(unsafe {
-                                take_agg_no_null_primitive_iter_unchecked(
-                                    self.downcast_chunks()[0],
-                                    idx.iter().map(|i| *i as usize),
-                                    |a, b| a + b,
-                                    T::Native::zero(),
-                                )
-                            }),
-                            (_, 1) => unsafe {
-                                take_agg_primitive_iter_unchecked(
-                                    self.downcast_chunks()[0],
-                                    idx.iter().map(|i| *i as usize),
-                                    |a, b| a + b,
-                                    T::Native::zero(),
-                                )
-                            },
-                            _ => {
-                                let take = unsafe {
-                                    self.take_unchecked(idx.iter().map(|i| *i as usize).into())
-                                };
-                                take.sum()
-                            }
-                        }
-                    }
-                })
+                .map(|(first, idx)| group_sum(self, *first, idx))
                 .collect::<ChunkedArray<T>>()
                 .into_series(),
         )
     }
-    fn agg_var(&self, groups: &[(u32, Vec<u32>)]) -> Option<Series> {
-        Some(
-            groups
-                .par_iter()
-                .map(|(_first, idx)| {
+    fn agg_var(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+        let ca: Float64Chunked = groups
+            .par_iter()
+            .map(|(_first, idx)| {
+                let take = unsafe { self.take_unchecked(idx.iter().map(|i| *i as usize).into()) };
+                // the variance of integers is a Float64
+                take.into_series()
+                    .var_as_series()
+                    .cast::<Float64Type>()
+                    .unwrap()
+                    .f64()
+                    .unwrap()
+                    .get(0)
+            })
+            .collect();
+        let dtype = groupby_output_dtype(self.dtype(), GroupByMethod::Var).ok()?;
+        ca.into_series().cast_with_datatype(&dtype).ok()
+    }
+    fn agg_std(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+        let ca: Float64Chunked = groups
+            .par_iter()
+            .map(|(_first, idx)| {
+                let take = unsafe { self.take_unchecked(idx.iter().map(|i| *i as usize).into()) };
+                take.into_series()
+                    .std_as_series()
+                    .cast::<Float64Type>()
+                    .unwrap()
+                    .f64()
+                    .unwrap()
+                    .get(0)
+            })
+            .collect();
+        let dtype = groupby_output_dtype(self.dtype(), GroupByMethod::Std).ok()?;
+        ca.into_series().cast_with_datatype(&dtype).ok()
+    }
+    fn agg_min_max(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<(Series, Series)> {
+        let (min, max): (Vec<_>, Vec<_>) = groups
+            .par_iter()
+            .map(|(first, idx)| {
+                if idx.len() == 1 {
+                    let v = self.get(*first as usize);
+                    (v, v)
+                } else if let (0, 1) = (self.null_count(), self.chunks.len()) {
+                    let values = self.downcast_chunks()[0].values();
+                    let init = (T::Native::max_value(), T::Native::min_value());
+                    let (min, max) = idx.iter().fold(init, |(min, max), i| {
+                        let v = unsafe { *values.get_unchecked(*i as usize) };
+                        (if min < v { min } else { v }, if max > v { max } else { v })
+                    });
+                    (Some(min), Some(max))
+                } else {
                     let take =
                         unsafe { self.take_unchecked(idx.iter().map(|i| *i as usize).into()) };
-                    take.into_series()
-                        .var_as_series()
-                        .unpack::<T>()
-                        .unwrap()
-                        .get(0)
-                })
-                .collect::<ChunkedArray<T>>()
+                    (take.min(), take.max())
+                }
+            })
+            .unzip();
+        Some((
+            min.into_iter().collect::<ChunkedArray<T>>().into_series(),
+            max.into_iter().collect::<ChunkedArray<T>>().into_series(),
+        ))
+    }
+    fn agg_sum_count(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<(Series, Series)> {
+        let (sum, count): (Vec<_>, Vec<_>) = groups
+            .par_iter()
+            .map(|(first, idx)| (group_sum(self, *first, idx), idx.len() as IdxSize))
+            .unzip();
+        Some((
+            sum.into_iter().collect::<ChunkedArray<T>>().into_series(),
+            count
+                .into_iter()
+                .collect::<NoNull<IdxCa>>()
+                .into_inner()
                 .into_series(),
-        )
+        ))
     }
-    fn agg_std(&self, groups: &[(u32, Vec<u32>)]) -> Option<Series> {
-        Some(
-            groups
-                .par_iter()
-                .map(|(_first, idx)| {
-                    let take =
-                        unsafe { self.take_unchecked(idx.iter().map(|i| *i as usize).into()) };
-                    take.into_series()
-                        .std_as_series()
-                        .unpack::<T>()
-                        .unwrap()
-                        .get(0)
-                })
-                .collect::<ChunkedArray<T>>()
+    fn agg_mean_count(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<(Series, Series)> {
+        let (mean, count): (Vec<_>, Vec<_>) = groups
+            .par_iter()
+            .map(|(first, idx)| {
+                let mean = group_sum(self, *first, idx)
+                    .map(|sum| sum.to_f64().unwrap() / idx.len() as f64);
+                (mean, idx.len() as IdxSize)
+            })
+            .unzip();
+        Some((
+            mean.into_iter().collect::<Float64Chunked>().into_series(),
+            count
+                .into_iter()
+                .collect::<NoNull<IdxCa>>()
+                .into_inner()
                 .into_series(),
-        )
+        ))
     }
 }
 
 pub(crate) trait AggFirst {
-    fn agg_first(&self, _groups: &[(u32, Vec<u32>)]) -> Series;
+    fn agg_first(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Series;
 }
 
 macro_rules! impl_agg_first {
@@ -970,31 +1129,31 @@ where
     T: PolarsPrimitiveType + Send,
     ChunkedArray<T>: IntoSeries,
 {
-    fn agg_first(&self, groups: &[(u32, Vec<u32>)]) -> Series {
+    fn agg_first(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
         impl_agg_first!(self, groups, ChunkedArray<T>)
     }
 }
 
 impl AggFirst for BooleanChunked {
-    fn agg_first(&self, groups: &[(u32, Vec<u32>)]) -> Series {
+    fn agg_first(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
         impl_agg_first!(self, groups, BooleanChunked)
     }
 }
 
 impl AggFirst for Utf8Chunked {
-    fn agg_first(&self, groups: &[(u32, Vec<u32>)]) -> Series {
+    fn agg_first(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
         impl_agg_first!(self, groups, Utf8Chunked)
     }
 }
 
 impl AggFirst for ListChunked {
-    fn agg_first(&self, groups: &[(u32, Vec<u32>)]) -> Series {
+    fn agg_first(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
         impl_agg_first!(self, groups, ListChunked)
     }
 }
 
 impl AggFirst for CategoricalChunked {
-    fn agg_first(&self, groups: &[(u32, Vec<u32>)]) -> Series {
+    fn agg_first(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
         self.cast::<UInt32Type>()
             .unwrap()
             .agg_first(groups)
@@ -1005,13 +1164,13 @@ impl AggFirst for CategoricalChunked {
 
 #[cfg(feature = "object")]
 impl<T> AggFirst for ObjectChunked<T> {
-    fn agg_first(&self, _groups: &[(u32, Vec<u32>)]) -> Series {
+    fn agg_first(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
         todo!()
     }
 }
 
 pub(crate) trait AggLast {
-    fn agg_last(&self, _groups: &[(u32, Vec<u32>)]) -> Series;
+    fn agg_last(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Series;
 }
 
 macro_rules! impl_agg_last {
@@ -1029,25 +1188,25 @@ where
     T: PolarsPrimitiveType + Send,
     ChunkedArray<T>: IntoSeries,
 {
-    fn agg_last(&self, groups: &[(u32, Vec<u32>)]) -> Series {
+    fn agg_last(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
         impl_agg_last!(self, groups, ChunkedArray<T>)
     }
 }
 
 impl AggLast for BooleanChunked {
-    fn agg_last(&self, groups: &[(u32, Vec<u32>)]) -> Series {
+    fn agg_last(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
         impl_agg_last!(self, groups, BooleanChunked)
     }
 }
 
 impl AggLast for Utf8Chunked {
-    fn agg_last(&self, groups: &[(u32, Vec<u32>)]) -> Series {
+    fn agg_last(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
         impl_agg_last!(self, groups, Utf8Chunked)
     }
 }
 
 impl AggLast for CategoricalChunked {
-    fn agg_last(&self, groups: &[(u32, Vec<u32>)]) -> Series {
+    fn agg_last(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
         self.cast::<UInt32Type>()
             .unwrap()
             .agg_last(groups)
@@ -1057,20 +1216,20 @@ impl AggLast for CategoricalChunked {
 }
 
 impl AggLast for ListChunked {
-    fn agg_last(&self, groups: &[(u32, Vec<u32>)]) -> Series {
+    fn agg_last(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
         impl_agg_last!(self, groups, ListChunked)
     }
 }
 
 #[cfg(feature = "object")]
 impl<T> AggLast for ObjectChunked<T> {
-    fn agg_last(&self, _groups: &[(u32, Vec<u32>)]) -> Series {
+    fn agg_last(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
         todo!()
     }
 }
 
 pub(crate) trait AggNUnique {
-    fn agg_n_unique(&self, _groups: &[(u32, Vec<u32>)]) -> Option<UInt32Chunked> {
+    fn agg_n_unique(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<IdxCa> {
         None
     }
 }
@@ -1086,14 +1245,14 @@ macro_rules! impl_agg_n_unique {
                         let v = unsafe { $self.get_unchecked(*i as usize) };
                         set.insert(v);
                     }
-                    set.len() as u32
+                    set.len() as IdxSize
                 } else {
                     let mut set = HashSet::with_hasher(RandomState::new());
                     for i in idx {
                         let opt_v = $self.get(*i as usize);
                         set.insert(opt_v);
                     }
-                    set.len() as u32
+                    set.len() as IdxSize
                 }
             })
             .collect::<$ca_type>()
@@ -1106,8 +1265,8 @@ where
     T: PolarsIntegerType + Sync,
     T::Native: Hash + Eq,
 {
-    fn agg_n_unique(&self, groups: &[(u32, Vec<u32>)]) -> Option<UInt32Chunked> {
-        Some(impl_agg_n_unique!(self, groups, NoNull<UInt32Chunked>))
+    fn agg_n_unique(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<IdxCa> {
+        Some(impl_agg_n_unique!(self, groups, NoNull<IdxCa>))
     }
 }
 
@@ -1116,7 +1275,7 @@ impl AggNUnique for Float32Chunked {}
 impl AggNUnique for Float64Chunked {}
 impl AggNUnique for ListChunked {}
 impl AggNUnique for CategoricalChunked {
-    fn agg_n_unique(&self, groups: &[(u32, Vec<u32>)]) -> Option<UInt32Chunked> {
+    fn agg_n_unique(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<IdxCa> {
         self.cast::<UInt32Type>().unwrap().agg_n_unique(groups)
     }
 }
@@ -1125,19 +1284,19 @@ impl<T> AggNUnique for ObjectChunked<T> {}
 
 // TODO: could be faster as it can only be null, true, or false
 impl AggNUnique for BooleanChunked {
-    fn agg_n_unique(&self, groups: &[(u32, Vec<u32>)]) -> Option<UInt32Chunked> {
-        Some(impl_agg_n_unique!(self, groups, NoNull<UInt32Chunked>))
+    fn agg_n_unique(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<IdxCa> {
+        Some(impl_agg_n_unique!(self, groups, NoNull<IdxCa>))
     }
 }
 
 impl AggNUnique for Utf8Chunked {
-    fn agg_n_unique(&self, groups: &[(u32, Vec<u32>)]) -> Option<UInt32Chunked> {
-        Some(impl_agg_n_unique!(self, groups, NoNull<UInt32Chunked>))
+    fn agg_n_unique(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<IdxCa> {
+        Some(impl_agg_n_unique!(self, groups, NoNull<IdxCa>))
     }
 }
 
 pub(crate) trait AggList {
-    fn agg_list(&self, _groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+    fn agg_list(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
         None
     }
 }
@@ -1146,7 +1305,7 @@ where
     T: PolarsDataType,
     ChunkedArray<T>: IntoSeries,
 {
-    fn agg_list(&self, groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+    fn agg_list(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
         // needed capacity for the list
         let values_cap = groups.iter().fold(0, |acc, g| acc + g.1.len());
 
@@ -1205,11 +1364,11 @@ where
 }
 
 pub(crate) trait AggQuantile {
-    fn agg_quantile(&self, _groups: &[(u32, Vec<u32>)], _quantile: f64) -> Option<Series> {
+    fn agg_quantile(&self, _groups: &[(IdxSize, Vec<IdxSize>)], _quantile: f64) -> Option<Series> {
         None
     }
 
-    fn agg_median(&self, groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+    fn agg_median(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
         self.agg_quantile(groups, 0.5)
     }
 }
@@ -1220,7 +1379,7 @@ where
     T::Native: PartialEq,
     ChunkedArray<T>: IntoSeries,
 {
-    fn agg_quantile(&self, groups: &[(u32, Vec<u32>)], quantile: f64) -> Option<Series> {
+    fn agg_quantile(&self, groups: &[(IdxSize, Vec<IdxSize>)], quantile: f64) -> Option<Series> {
         Some(
             groups
                 .into_par_iter()
@@ -1240,12 +1399,121 @@ where
 }
 
 impl AggQuantile for Utf8Chunked {}
-impl AggQuantile for BooleanChunked {}
+impl AggQuantile for BooleanChunked {
+    fn agg_quantile(&self, groups: &[(IdxSize, Vec<IdxSize>)], quantile: f64) -> Option<Series> {
+        self.cast::<UInt32Type>()
+            .ok()?
+            .agg_quantile(groups, quantile)
+    }
+}
 impl AggQuantile for ListChunked {}
 impl AggQuantile for CategoricalChunked {}
 #[cfg(feature = "object")]
 impl<T> AggQuantile for ObjectChunked<T> {}
 
+/// A value and its row index in the bounded heap of [top_k_idx]. The heap has the worst of the
+/// rows it keeps on top: the smallest value, or of equal values the later row.
+struct TopKItem<V> {
+    value: V,
+    idx: IdxSize,
+}
+
+impl<V: PartialOrd> Ord for TopKItem<V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .value
+            .partial_cmp(&self.value)
+            .unwrap_or(Ordering::Equal)
+            .then(self.idx.cmp(&other.idx))
+    }
+}
+
+impl<V: PartialOrd> PartialOrd for TopKItem<V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<V: PartialOrd> PartialEq for TopKItem<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<V: PartialOrd> Eq for TopKItem<V> {}
+
+/// Indexes of the rows with the `k` largest values of every group, largest first. Every group
+/// keeps a heap of at most `k` rows, so a group of `n` rows costs `O(n log k)` instead of a sort.
+fn top_k_idx<V, F>(groups: &[(IdxSize, Vec<IdxSize>)], k: usize, get: F) -> Vec<Vec<IdxSize>>
+where
+    V: PartialOrd,
+    F: Fn(usize) -> Option<V> + Sync,
+{
+    groups
+        .into_par_iter()
+        .map(|(_first, idx)| {
+            let mut heap = BinaryHeap::with_capacity(std::cmp::min(k, idx.len()));
+            for &i in idx {
+                if let Some(value) = get(i as usize) {
+                    let item = TopKItem { value, idx: i };
+                    if heap.len() < k {
+                        heap.push(item);
+                    } else if let Some(mut worst) = heap.peek_mut() {
+                        if item < *worst {
+                            *worst = item;
+                        }
+                    }
+                }
+            }
+            heap.into_sorted_vec()
+                .into_iter()
+                .map(|item| item.idx)
+                .collect()
+        })
+        .collect()
+}
+
+pub(crate) trait AggTopK {
+    /// Indexes of the rows with the `k` largest values of every group, largest first. Null values
+    /// are skipped.
+    fn agg_top_k_idx(
+        &self,
+        _groups: &[(IdxSize, Vec<IdxSize>)],
+        _k: usize,
+    ) -> Option<Vec<Vec<IdxSize>>> {
+        None
+    }
+}
+
+impl<T> AggTopK for ChunkedArray<T>
+where
+    T: PolarsNumericType + Sync,
+{
+    fn agg_top_k_idx(
+        &self,
+        groups: &[(IdxSize, Vec<IdxSize>)],
+        k: usize,
+    ) -> Option<Vec<Vec<IdxSize>>> {
+        Some(top_k_idx(groups, k, |i| self.get(i)))
+    }
+}
+
+impl AggTopK for Utf8Chunked {
+    fn agg_top_k_idx(
+        &self,
+        groups: &[(IdxSize, Vec<IdxSize>)],
+        k: usize,
+    ) -> Option<Vec<Vec<IdxSize>>> {
+        Some(top_k_idx(groups, k, |i| self.get(i)))
+    }
+}
+
+impl AggTopK for BooleanChunked {}
+impl AggTopK for ListChunked {}
+impl AggTopK for CategoricalChunked {}
+#[cfg(feature = "object")]
+impl<T> AggTopK for ObjectChunked<T> {}
+
 impl<'df, 'selection_str> GroupBy<'df, 'selection_str> {
     /// Select the column(s) that should be aggregated.
     /// You can select a single column or a slice of columns.
@@ -1254,7 +1522,7 @@ impl<'df, 'selection_str> GroupBy<'df, 'selection_str> {
     /// skip it all columns (except for the keys) will be selected for aggregation.
     pub fn select<S, J>(mut self, selection: S) -> Self
     where
-        S: Selection<'selection_str, J>,
+        S: NameSelection<'selection_str, J>,
     {
         self.selected_agg = Some(selection.to_selection_vec());
         self
@@ -1569,6 +1837,80 @@ impl<'df, 'selection_str> GroupBy<'df, 'selection_str> {
         DataFrame::new(cols)
     }
 
+    /// Aggregate grouped `Series` by counting the null values.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use polars_core::prelude::*;
+    /// fn example(df: DataFrame) -> Result<DataFrame> {
+    ///     df.groupby("date")?.select("temp").null_count()
+    /// }
+    /// ```
+    pub fn null_count(&self) -> Result<DataFrame> {
+        let (mut cols, agg_cols) = self.prepare_agg()?;
+        for agg_col in agg_cols {
+            let new_name = fmt_groupby_column(agg_col.name(), GroupByMethod::NullCount);
+            let mut agg = agg_col.agg_null_count(&self.groups);
+            agg.rename(&new_name);
+            cols.push(agg.into_series());
+        }
+        DataFrame::new(cols)
+    }
+
+    /// Count the distinct combinations of values in `columns` per group. Rows are compared
+    /// by hashing the values of `columns`, so this doesn't materialize the combinations.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use polars_core::prelude::*;
+    /// fn example(df: DataFrame) -> Result<DataFrame> {
+    ///     // distinct (user, session) pairs per day
+    ///     df.groupby("date")?.n_unique_multi(&["user", "session"])
+    /// }
+    /// ```
+    pub fn n_unique_multi<S: AsRef<str>>(&self, columns: &[S]) -> Result<DataFrame> {
+        if columns.is_empty() {
+            return Err(PolarsError::InvalidOperation(
+                "n_unique_multi needs at least one column".into(),
+            ));
+        }
+        let names = columns.iter().map(|s| s.as_ref()).collect::<Vec<_>>();
+        let keys = self.df.select(names.clone())?;
+        let (hashes, _) = df_rows_to_hashes(&keys, None);
+        let hashes = hashes.rechunk();
+        // the row hashes don't have nulls
+        let hashes = hashes.cont_slice()?;
+
+        let counts: Vec<IdxSize> = POOL.install(|| {
+            self.groups
+                .par_iter()
+                .map(|(_first, idx)| {
+                    let mut hash_tbl: HashMap<IdxHash, (), IdBuildHasher> =
+                        HashMap::with_capacity_and_hasher(idx.len(), IdBuildHasher::default());
+                    for &row in idx {
+                        let h = hashes[row as usize];
+                        let entry = hash_tbl.raw_entry_mut().from_hash(h, |idx_hash| {
+                            // Safety:
+                            // indices of the groups are always in bounds.
+                            unsafe { compare_fn(&keys, idx_hash.idx, row) }
+                        });
+                        if let RawEntryMut::Vacant(entry) = entry {
+                            entry.insert_hashed_nocheck(h, IdxHash::new(row, h), ());
+                        }
+                    }
+                    hash_tbl.len() as IdxSize
+                })
+                .collect()
+        });
+
+        let mut cols = self.keys();
+        let name = fmt_groupby_column(&names.join("_"), GroupByMethod::NUnique);
+        cols.push(IdxCa::new_from_slice(&name, &counts).into_series());
+        DataFrame::new(cols)
+    }
+
     /// Aggregate grouped `Series` and determine the quantile per group.
     ///
     /// # Example
@@ -1677,10 +2019,9 @@ impl<'df, 'selection_str> GroupBy<'df, 'selection_str> {
         let (mut cols, agg_cols) = self.prepare_agg()?;
         for agg_col in agg_cols {
             let new_name = fmt_groupby_column(agg_col.name(), GroupByMethod::Count);
-            let mut builder =
-                PrimitiveChunkedBuilder::<UInt32Type>::new(&new_name, self.groups.len());
+            let mut builder = PrimitiveChunkedBuilder::<IdxType>::new(&new_name, self.groups.len());
             for (_first, idx) in &self.groups {
-                builder.append_value(idx.len() as u32);
+                builder.append_value(idx.len() as IdxSize);
             }
             let ca = builder.finish();
             cols.push(ca.into_series())
@@ -1688,6 +2029,41 @@ impl<'df, 'selection_str> GroupBy<'df, 'selection_str> {
         DataFrame::new(cols)
     }
 
+    /// Get the rows with the `k` largest values of `by_column` of every group, largest first.
+    /// The rows have the keys and the selected columns. A group keeps fewer rows if it has fewer
+    /// than `k` non-null values in `by_column`.
+    ///
+    /// Every group keeps a bounded heap of `k` rows, which is much cheaper than sorting the groups
+    /// when `k` is small.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use polars_core::prelude::*;
+    /// // the 3 warmest days of every month
+    /// fn warmest_days(df: DataFrame) -> Result<DataFrame> {
+    ///     df.groupby("month")?.select(&["date", "temp"]).top_k(3, "temp")
+    /// }
+    /// ```
+    pub fn top_k(&self, k: usize, by_column: &str) -> Result<DataFrame> {
+        let by = self.df.column(by_column)?;
+        let idx = by.agg_top_k_idx(&self.groups, k).ok_or_else(|| {
+            PolarsError::InvalidOperation(
+                format!("top_k is not supported for dtype {:?}", by.dtype()).into(),
+            )
+        })?;
+        let (_, agg_cols) = self.prepare_agg()?;
+        let columns = self
+            .selected_keys
+            .iter()
+            .chain(&agg_cols)
+            .map(|s| unsafe {
+                s.take_iter_unchecked(&mut idx.iter().flatten().map(|i| *i as usize))
+            })
+            .collect();
+        DataFrame::new(columns)
+    }
+
     /// Get the groupby group indexes.
     ///
     /// # Example
@@ -1720,7 +2096,7 @@ impl<'df, 'selection_str> GroupBy<'df, 'selection_str> {
             .groups
             .iter()
             .map(|(_first, idx)| {
-                let ca: NoNull<UInt32Chunked> = idx.iter().map(|&v| v as u32).collect();
+                let ca: NoNull<IdxCa> = idx.iter().copied().collect();
                 ca.into_inner().into_series()
             })
             .collect();
@@ -1743,6 +2119,17 @@ impl<'df, 'selection_str> GroupBy<'df, 'selection_str> {
     /// * max
     /// * mean
     /// * median
+    /// * std
+    /// * var
+    /// * n_unique
+    /// * null_count
+    /// * list
+    /// * quantile(q), e.g. `"quantile(0.25)"`
+    ///
+    /// Outputs are named `{column}_{aggregation}`, in the order of the specification. A column
+    /// may be listed more than once. To aggregate with a `HashMap<&str, Vec<&str>>`, collect it into
+    /// a `Vec` first. A column that isn't aggregated by this groupby returns
+    /// [PolarsError::NotFound], an unknown aggregation [PolarsError::InvalidOperation].
     ///
     /// # Example
     ///
@@ -1775,12 +2162,6 @@ impl<'df, 'selection_str> GroupBy<'df, 'selection_str> {
         Slice: AsRef<[S]>,
         Column: AsRef<str>,
     {
-        // create a mapping from columns to aggregations on that column
-        let mut map = HashMap::with_capacity_and_hasher(column_to_agg.len(), RandomState::new());
-        column_to_agg.iter().for_each(|(column, aggregations)| {
-            map.insert(column.as_ref(), aggregations.as_ref());
-        });
-
         macro_rules! finish_agg_opt {
             ($self:ident, $name_fmt:expr, $agg_fn:ident, $agg_col:ident, $cols:ident) => {{
                 let new_name = format![$name_fmt, $agg_col.name()];
@@ -1801,35 +2182,65 @@ impl<'df, 'selection_str> GroupBy<'df, 'selection_str> {
         }
 
         let (mut cols, agg_cols) = self.prepare_agg()?;
-        for agg_col in &agg_cols {
-            if let Some(&aggregations) = map.get(agg_col.name()) {
-                for aggregation_f in aggregations {
-                    match aggregation_f.as_ref() {
-                        "min" => finish_agg_opt!(self, "{}_min", agg_min, agg_col, cols),
-                        "max" => finish_agg_opt!(self, "{}_max", agg_max, agg_col, cols),
-                        "mean" => finish_agg_opt!(self, "{}_mean", agg_mean, agg_col, cols),
-                        "sum" => finish_agg_opt!(self, "{}_sum", agg_sum, agg_col, cols),
-                        "first" => finish_agg!(self, "{}_first", agg_first, agg_col, cols),
-                        "last" => finish_agg!(self, "{}_last", agg_last, agg_col, cols),
-                        "n_unique" => {
-                            finish_agg_opt!(self, "{}_n_unique", agg_n_unique, agg_col, cols)
+        for (column, aggregations) in column_to_agg {
+            let agg_col = agg_cols
+                .iter()
+                .find(|s| s.name() == column.as_ref())
+                .ok_or_else(|| PolarsError::NotFound(column.as_ref().to_string()))?;
+            for aggregation_f in aggregations.as_ref() {
+                match aggregation_f.as_ref() {
+                    "min" => finish_agg_opt!(self, "{}_min", agg_min, agg_col, cols),
+                    "max" => finish_agg_opt!(self, "{}_max", agg_max, agg_col, cols),
+                    "mean" => finish_agg_opt!(self, "{}_mean", agg_mean, agg_col, cols),
+                    "sum" => finish_agg_opt!(self, "{}_sum", agg_sum, agg_col, cols),
+                    "first" => finish_agg!(self, "{}_first", agg_first, agg_col, cols),
+                    "last" => finish_agg!(self, "{}_last", agg_last, agg_col, cols),
+                    "n_unique" => {
+                        finish_agg_opt!(self, "{}_n_unique", agg_n_unique, agg_col, cols)
+                    }
+                    "median" => finish_agg_opt!(self, "{}_median", agg_median, agg_col, cols),
+                    "std" => finish_agg_opt!(self, "{}_std", agg_std, agg_col, cols),
+                    "var" => finish_agg_opt!(self, "{}_var", agg_var, agg_col, cols),
+                    "list" => finish_agg_opt!(self, "{}_list", agg_list, agg_col, cols),
+                    "null_count" => {
+                        finish_agg!(self, "{}_null_count", agg_null_count, agg_col, cols)
+                    }
+                    "count" => {
+                        let new_name = format!["{}_count", agg_col.name()];
+                        let mut builder =
+                            PrimitiveChunkedBuilder::<IdxType>::new(&new_name, self.groups.len());
+                        for (_first, idx) in &self.groups {
+                            builder.append_value(idx.len() as IdxSize);
                         }
-                        "median" => finish_agg_opt!(self, "{}_median", agg_median, agg_col, cols),
-                        "std" => finish_agg_opt!(self, "{}_std", agg_std, agg_col, cols),
-                        "var" => finish_agg_opt!(self, "{}_var", agg_var, agg_col, cols),
-                        "count" => {
-                            let new_name = format!["{}_count", agg_col.name()];
-                            let mut builder = PrimitiveChunkedBuilder::<UInt32Type>::new(
-                                &new_name,
-                                self.groups.len(),
-                            );
-                            for (_first, idx) in &self.groups {
-                                builder.append_value(idx.len() as u32);
-                            }
-                            let ca = builder.finish();
-                            cols.push(ca.into_series());
+                        let ca = builder.finish();
+                        cols.push(ca.into_series());
+                    }
+                    a if a.starts_with("quantile(") && a.ends_with(')') => {
+                        let quantile = a["quantile(".len()..a.len() - 1]
+                            .trim()
+                            .parse::<f64>()
+                            .ok()
+                            .filter(|q| (0.0..=1.0).contains(q))
+                            .ok_or_else(|| {
+                                PolarsError::ValueError(
+                                    format!(
+                                        "aggregation {} should have a quantile within 0.0 and 1.0",
+                                        a
+                                    )
+                                    .into(),
+                                )
+                            })?;
+                        let new_name =
+                            fmt_groupby_column(agg_col.name(), GroupByMethod::Quantile(quantile));
+                        if let Some(mut agg) = agg_col.agg_quantile(&self.groups, quantile) {
+                            agg.rename(&new_name);
+                            cols.push(agg);
                         }
-                        a => panic!("aggregation: {:?} is not supported", a),
+                    }
+                    a => {
+                        return Err(PolarsError::InvalidOperation(
+                            format!("aggregation {} is not supported", a).into(),
+                        ))
                     }
                 }
             }
@@ -1989,7 +2400,7 @@ impl<'df, 'selection_str> GroupBy<'df, 'selection_str> {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub enum GroupByMethod {
     Min,
     Max,
@@ -2005,6 +2416,8 @@ pub enum GroupByMethod {
     List,
     Std,
     Var,
+    NullCount,
+    TopK(usize),
 }
 
 // Formatting functions used in eager and lazy code for renaming grouped columns
@@ -2025,9 +2438,123 @@ pub fn fmt_groupby_column(name: &str, method: GroupByMethod) -> String {
         Quantile(quantile) => format!["{}_quantile_{:.2}", name, quantile],
         Std => format!["{}_agg_std", name],
         Var => format!["{}_agg_var", name],
+        NullCount => format!["{}_null_count", name],
+        TopK(k) => format!["{}_top_{}", name, k],
     }
 }
 
+// Data type of the aggregated columns, used in eager and lazy code so that the schema of an
+// aggregation matches the output of the kernels. Unsupported aggregations return an error.
+pub fn groupby_output_dtype(dtype: &DataType, method: GroupByMethod) -> Result<DataType> {
+    use DataType::*;
+    use GroupByMethod::*;
+    let out = match (dtype, method) {
+        (_, First) | (_, Last) => Some(dtype.clone()),
+        (_, NUnique) | (_, Count) | (_, NullCount) => Some(IdxType::get_dtype()),
+        (_, Groups) => Some(List(IdxType::get_dtype().to_arrow())),
+        (_, List) => Some(List(dtype.to_arrow())),
+        // booleans are aggregated as 1 or 0
+        (Boolean, Mean) => Some(Float64),
+        (Boolean, Min) | (Boolean, Max) | (Boolean, Sum) | (Boolean, Median) => Some(UInt32),
+        (Boolean, Quantile(_)) => Some(UInt32),
+        // strings are compared lexically, categoricals by their ordering
+        (Utf8, Min) | (Utf8, Max) | (Categorical(_), Min) | (Categorical(_), Max) => {
+            Some(dtype.clone())
+        }
+        // the median of dates is a date, the sum or mean of dates is not defined
+        (Date32, Min) | (Date32, Max) | (Date32, Median) | (Date32, Quantile(_)) => {
+            Some(dtype.clone())
+        }
+        (Date64, Min) | (Date64, Max) | (Date64, Median) | (Date64, Quantile(_)) => {
+            Some(dtype.clone())
+        }
+        (Time64(_), Min) | (Time64(_), Max) | (Time64(_), Median) | (Time64(_), Quantile(_)) => {
+            Some(dtype.clone())
+        }
+        (Date32, TopK(_)) | (Date64, TopK(_)) | (Time64(_), TopK(_)) => {
+            Some(List(dtype.to_arrow()))
+        }
+        (UInt8, _)
+        | (UInt16, _)
+        | (UInt32, _)
+        | (UInt64, _)
+        | (Int8, _)
+        | (Int16, _)
+        | (Int32, _)
+        | (Int64, _)
+        | (Float32, _)
+        | (Float64, _)
+        | (Duration(_), _) => match method {
+            Mean => Some(Float64),
+            Std | Var => match dtype {
+                Float32 => Some(Float32),
+                _ => Some(Float64),
+            },
+            TopK(_) => Some(List(dtype.to_arrow())),
+            _ => Some(dtype.clone()),
+        },
+        _ => None,
+    };
+    out.ok_or_else(|| {
+        PolarsError::InvalidOperation(
+            format!(
+                "cannot aggregate a column of type {:?} with {:?}",
+                dtype, method
+            )
+            .into(),
+        )
+    })
+}
+
+/// Whether the aggregations `a` and `b` of the same column can be computed together by
+/// [agg_fused].
+pub fn can_fuse_aggs(a: GroupByMethod, b: GroupByMethod) -> bool {
+    use GroupByMethod::*;
+    matches!(
+        (a, b),
+        (Min, Max) | (Max, Min) | (Sum, Count) | (Count, Sum) | (Mean, Count) | (Count, Mean)
+    )
+}
+
+/// Compute the aggregations `methods` of every group in a single pass over `s`. The count is
+/// the group length, like the unfused `count`.
+/// Returns `None` for pairs that are not accepted by [can_fuse_aggs].
+pub fn agg_fused(
+    s: &Series,
+    groups: &[(IdxSize, Vec<IdxSize>)],
+    methods: [GroupByMethod; 2],
+) -> Option<[Series; 2]> {
+    use GroupByMethod::*;
+    let out = match methods {
+        [Min, Max] => {
+            let (min, max) = s.agg_min_max(groups)?;
+            [min, max]
+        }
+        [Max, Min] => {
+            let (min, max) = s.agg_min_max(groups)?;
+            [max, min]
+        }
+        [Sum, Count] => {
+            let (sum, count) = s.agg_sum_count(groups)?;
+            [sum, count]
+        }
+        [Count, Sum] => {
+            let (sum, count) = s.agg_sum_count(groups)?;
+            [count, sum]
+        }
+        [Mean, Count] => {
+            let (mean, count) = s.agg_mean_count(groups)?;
+            [mean, count]
+        }
+        [Count, Mean] => {
+            let (mean, count) = s.agg_mean_count(groups)?;
+            [count, mean]
+        }
+        _ => return None,
+    };
+    Some(out)
+}
+
 /// Intermediate structure when a `pivot` operation is applied.
 /// See [the pivot method for more information.](../group_by/struct.GroupBy.html#method.pivot)
 pub struct Pivot<'df, 'selection_str> {
@@ -2041,7 +2568,7 @@ pub(crate) trait ChunkPivot {
         &self,
         _pivot_series: &'a (dyn SeriesTrait + 'a),
         _keys: Vec<Series>,
-        _groups: &[(u32, Vec<u32>)],
+        _groups: &[(IdxSize, Vec<IdxSize>)],
         _agg_type: PivotAgg,
     ) -> Result<DataFrame> {
         Err(PolarsError::InvalidOperation(
@@ -2053,7 +2580,7 @@ pub(crate) trait ChunkPivot {
         &self,
         _pivot_series: &'a (dyn SeriesTrait + 'a),
         _keys: Vec<Series>,
-        _groups: &[(u32, Vec<u32>)],
+        _groups: &[(IdxSize, Vec<IdxSize>)],
     ) -> Result<DataFrame> {
         Err(PolarsError::InvalidOperation(
             "Pivot count operation not implemented for this type".into(),
@@ -2078,7 +2605,7 @@ fn create_column_values_map<'a, T>(
 /// Create a hashmap that maps columns/keys to the result of the aggregation.
 fn create_new_column_builder_map<'a, T>(
     pivot_vec: &'a [Option<Groupable>],
-    groups: &[(u32, Vec<u32>)],
+    groups: &[(IdxSize, Vec<IdxSize>)],
 ) -> HashMap<&'a Groupable<'a>, PrimitiveChunkedBuilder<T>, RandomState>
 where
     T: PolarsNumericType,
@@ -2104,7 +2631,7 @@ where
         &self,
         pivot_series: &'a (dyn SeriesTrait + 'a),
         keys: Vec<Series>,
-        groups: &[(u32, Vec<u32>)],
+        groups: &[(IdxSize, Vec<IdxSize>)],
         agg_type: PivotAgg,
     ) -> Result<DataFrame> {
         // TODO: save an allocation by creating a random access struct for the Groupable utility type.
@@ -2170,7 +2697,7 @@ where
         &self,
         pivot_series: &'a (dyn SeriesTrait + 'a),
         keys: Vec<Series>,
-        groups: &[(u32, Vec<u32>)],
+        groups: &[(IdxSize, Vec<IdxSize>)],
     ) -> Result<DataFrame> {
         pivot_count_impl(self, pivot_series, keys, groups)
     }
@@ -2180,11 +2707,11 @@ fn pivot_count_impl<'a, CA: TakeRandom>(
     ca: &CA,
     pivot_series: &'a (dyn SeriesTrait + 'a),
     keys: Vec<Series>,
-    groups: &[(u32, Vec<u32>)],
+    groups: &[(IdxSize, Vec<IdxSize>)],
 ) -> Result<DataFrame> {
     let pivot_vec: Vec<_> = pivot_series.as_groupable_iter()?.collect();
     // create a hash map that will be filled with the results of the aggregation.
-    let mut columns_agg_map_main = create_new_column_builder_map::<UInt32Type>(&pivot_vec, groups);
+    let mut columns_agg_map_main = create_new_column_builder_map::<IdxType>(&pivot_vec, groups);
 
     // iterate over the groups that need to be aggregated
     // idxes are the indexes of the groups in the keys, pivot, and values columns
@@ -2208,7 +2735,7 @@ fn pivot_count_impl<'a, CA: TakeRandom>(
         // hash map, mapping pivot values as column to aggregate result.
         for (k, v) in &mut columns_agg_map_group {
             let main_builder = columns_agg_map_main.get_mut(k).unwrap();
-            main_builder.append_value(v.len() as u32)
+            main_builder.append_value(v.len() as IdxSize)
         }
     }
     // Finalize the pivot by creating a vec of all the columns and creating a DataFrame
@@ -2228,7 +2755,7 @@ impl ChunkPivot for BooleanChunked {
         &self,
         pivot_series: &'a (dyn SeriesTrait + 'a),
         keys: Vec<Series>,
-        groups: &[(u32, Vec<u32>)],
+        groups: &[(IdxSize, Vec<IdxSize>)],
     ) -> Result<DataFrame> {
         pivot_count_impl(self, pivot_series, keys, groups)
     }
@@ -2238,7 +2765,7 @@ impl ChunkPivot for Utf8Chunked {
         &self,
         pivot_series: &'a (dyn SeriesTrait + 'a),
         keys: Vec<Series>,
-        groups: &[(u32, Vec<u32>)],
+        groups: &[(IdxSize, Vec<IdxSize>)],
     ) -> Result<DataFrame> {
         pivot_count_impl(&self, pivot_series, keys, groups)
     }
@@ -2249,7 +2776,7 @@ impl ChunkPivot for CategoricalChunked {
         &self,
         pivot_series: &'a (dyn SeriesTrait + 'a),
         keys: Vec<Series>,
-        groups: &[(u32, Vec<u32>)],
+        groups: &[(IdxSize, Vec<IdxSize>)],
     ) -> Result<DataFrame> {
         self.cast::<UInt32Type>()
             .unwrap()
@@ -2261,6 +2788,7 @@ impl ChunkPivot for ListChunked {}
 #[cfg(feature = "object")]
 impl<T> ChunkPivot for ObjectChunked<T> {}
 
+#[derive(Copy, Clone, Debug)]
 pub enum PivotAgg {
     First,
     Sum,
@@ -2425,13 +2953,145 @@ impl<'df, 'sel_str> Pivot<'df, 'sel_str> {
             PivotAgg::Median,
         )
     }
+
+    /// Aggregate the pivot results with one or multiple aggregations at once.
+    ///
+    /// Every distinct value of the pivot column becomes a column, in ascending order of the pivot
+    /// values. Null pivot values are ignored. With a single aggregation the columns are named
+    /// after the pivot values, with multiple aggregations they are named like `"{pivot value}_sum"`.
+    /// The groups are split by pivot value and aggregated in parallel, so the values column may
+    /// have any data type that supports the aggregations.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_core::frame::group_by::PivotAgg;
+    ///
+    /// fn example(df: DataFrame) -> Result<DataFrame> {
+    ///     df.groupby("foo")?
+    ///     .pivot("bar", "N")
+    ///     .agg(&[PivotAgg::Sum, PivotAgg::Mean])
+    /// }
+    /// ```
+    pub fn agg(&self, aggs: &[PivotAgg]) -> Result<DataFrame> {
+        if aggs.is_empty() {
+            return Err(PolarsError::NoData(
+                "a pivot needs at least one aggregation".into(),
+            ));
+        }
+        let pivot_series = self.gb.df.column(self.pivot_column)?;
+        let values_series = self.gb.df.column(self.values_column)?;
+
+        // determine the pivot column of every row
+        let pivot_groups = pivot_series.group_tuples(true);
+        let pivot_values = unsafe {
+            pivot_series
+                .take_iter_unchecked(&mut pivot_groups.iter().map(|(first, _)| *first as usize))
+        };
+        let pivot_names = pivot_values.cast_with_datatype(&DataType::Utf8)?;
+        let pivot_names = pivot_names.utf8()?;
+        let mut row_to_column = vec![None; pivot_series.len()];
+        let mut column_names = Vec::with_capacity(pivot_groups.len());
+        for group_idx in pivot_values.argsort(false).into_no_null_iter() {
+            if let Some(name) = pivot_names.get(group_idx as usize) {
+                let column = column_names.len();
+                for &row in &pivot_groups[group_idx as usize].1 {
+                    row_to_column[row as usize] = Some(column);
+                }
+                column_names.push(name.to_string());
+            }
+        }
+        let n_columns = column_names.len();
+
+        // split every group by the pivot columns
+        let split_groups: Vec<Vec<Option<(IdxSize, Vec<IdxSize>)>>> = POOL.install(|| {
+            self.gb
+                .groups
+                .par_iter()
+                .map(|(_first, idx)| {
+                    let mut sub_groups = vec![None; n_columns];
+                    for &row in idx {
+                        if let Some(column) = row_to_column[row as usize] {
+                            match &mut sub_groups[column] {
+                                Some((_first, sub_idx)) => sub_idx.push(row),
+                                empty => *empty = Some((row, vec![row])),
+                            }
+                        }
+                    }
+                    sub_groups
+                })
+                .collect()
+        });
+
+        // the sub groups are aggregated at once and gathered into the pivot columns
+        let mut sub_groups = Vec::with_capacity(self.gb.groups.len());
+        let mut take_idx = vec![Vec::with_capacity(self.gb.groups.len()); n_columns];
+        for split in split_groups {
+            for (column, opt_group) in split.into_iter().enumerate() {
+                take_idx[column].push(opt_group.map(|group| {
+                    sub_groups.push(group);
+                    sub_groups.len() - 1
+                }));
+            }
+        }
+
+        let mut cols = self.gb.keys();
+        for &agg in aggs {
+            let (opt_agg, method) = match agg {
+                PivotAgg::First => (
+                    Some(values_series.agg_first(&sub_groups)),
+                    GroupByMethod::First,
+                ),
+                PivotAgg::Sum => (values_series.agg_sum(&sub_groups), GroupByMethod::Sum),
+                PivotAgg::Min => (values_series.agg_min(&sub_groups), GroupByMethod::Min),
+                PivotAgg::Max => (values_series.agg_max(&sub_groups), GroupByMethod::Max),
+                PivotAgg::Mean => (values_series.agg_mean(&sub_groups), GroupByMethod::Mean),
+                PivotAgg::Median => (values_series.agg_median(&sub_groups), GroupByMethod::Median),
+            };
+            let aggregated = opt_agg.ok_or_else(|| {
+                PolarsError::InvalidOperation(
+                    format!(
+                        "cannot pivot values of type {:?} with {:?}",
+                        values_series.dtype(),
+                        agg
+                    )
+                    .into(),
+                )
+            })?;
+
+            let pivot_cols: Vec<_> = POOL.install(|| {
+                take_idx
+                    .par_iter()
+                    .zip(column_names.par_iter())
+                    .map(|(idx, name)| {
+                        // Safety:
+                        // the indexes point to the sub groups, which have all been aggregated.
+                        let mut s =
+                            unsafe { aggregated.take_opt_iter_unchecked(&mut idx.iter().copied()) };
+                        if aggs.len() == 1 {
+                            s.rename(name);
+                        } else {
+                            s.rename(&fmt_groupby_column(name, method));
+                        }
+                        s
+                    })
+                    .collect()
+            });
+            cols.extend(pivot_cols);
+        }
+        DataFrame::new(cols)
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::frame::group_by::{groupby, groupby_threaded_flat};
+    use crate::frame::group_by::{
+        agg_fused, groupby, groupby_threaded_flat, GroupByMethod, PivotAgg,
+    };
     use crate::prelude::*;
     use crate::utils::split_ca;
+    use ahash::RandomState;
     use itertools::Itertools;
 
     #[test]
@@ -2538,6 +3198,37 @@ mod test {
         println!("{:?}", df.groupby("date").unwrap().groups().unwrap());
     }
 
+    #[test]
+    fn test_vec_hash_with_nulls() {
+        let random_state = RandomState::with_seeds(1, 2, 3, 4);
+        macro_rules! check {
+            ($with_nulls:expr, $no_nulls:expr) => {{
+                let with_nulls = $with_nulls.vec_hash(random_state.clone());
+                let no_nulls = $no_nulls.vec_hash(random_state.clone());
+                assert_eq!(with_nulls.get(0), no_nulls.get(0));
+                assert_eq!(with_nulls.get(2), no_nulls.get(1));
+                assert_ne!(with_nulls.get(1), with_nulls.get(0));
+            }};
+        }
+        // the hash of a value doesn't depend on the presence of nulls in the array
+        check!(
+            Int32Chunked::new_from_opt_slice("a", &[Some(1), None, Some(2)]),
+            Int32Chunked::new_from_slice("a", &[1, 2])
+        );
+        check!(
+            Float64Chunked::new_from_opt_slice("a", &[Some(1.0), None, Some(2.0)]),
+            Float64Chunked::new_from_slice("a", &[1.0, 2.0])
+        );
+        check!(
+            Utf8Chunked::new_from_opt_slice("a", &[Some("x"), None, Some("y")]),
+            Utf8Chunked::new_from_slice("a", &["x", "y"])
+        );
+        check!(
+            BooleanChunked::new_from_opt_slice("a", &[Some(true), None, Some(false)]),
+            BooleanChunked::new_from_slice("a", &[true, false])
+        );
+    }
+
     #[test]
     fn test_pivot() {
         let s0 = Series::new("foo", ["A", "A", "B", "B", "C"].as_ref());
@@ -2573,11 +3264,48 @@ mod test {
             .count()
             .unwrap();
         assert_eq!(
-            Vec::from(&pvt.column("m").unwrap().u32().unwrap().sort(false)),
+            Vec::from(&pvt.column("m").unwrap().idx().unwrap().sort(false)),
             &[Some(0), Some(0), Some(2)]
         );
     }
 
+    #[test]
+    fn test_pivot_agg() -> Result<()> {
+        let df = df![
+            "foo" => &["A", "A", "B", "B", "C", "A"],
+            "N" => &[1, 2, 2, 4, 2, 5],
+            "bar" => &[Some("m"), Some("k"), Some("m"), Some("m"), Some("k"), None]
+        ]?;
+
+        let pvt = df
+            .groupby_stable("foo")?
+            .pivot("bar", "N")
+            .agg(&[PivotAgg::Sum])?;
+        assert_eq!(pvt.get_column_names(), &["foo", "k", "m"]);
+        assert_eq!(
+            Vec::from(pvt.column("k")?.i32()?),
+            &[Some(2), None, Some(2)]
+        );
+        assert_eq!(
+            Vec::from(pvt.column("m")?.i32()?),
+            &[Some(1), Some(6), None]
+        );
+
+        let pvt = df
+            .groupby_stable("foo")?
+            .pivot("bar", "N")
+            .agg(&[PivotAgg::Min, PivotAgg::Max])?;
+        assert_eq!(
+            pvt.get_column_names(),
+            &["foo", "k_min", "m_min", "k_max", "m_max"]
+        );
+        assert_eq!(
+            Vec::from(pvt.column("m_max")?.i32()?),
+            &[Some(1), Some(4), None]
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_static_groupby_by_12_columns() {
         // Build GroupBy DataFrame.
@@ -2694,6 +3422,106 @@ mod test {
         assert!(out.sort("b", false).unwrap().frame_equal(&df));
     }
 
+    #[test]
+    fn test_groupby_agg_spec() {
+        let df = df! {
+            "a" => [1, 1, 2, 2, 2],
+            "b" => [1., 2., 3., 4., 5.]
+        }
+        .unwrap();
+
+        let out = df
+            .groupby("a")
+            .unwrap()
+            .agg(&[("b", vec!["quantile(0.5)", "max"]), ("b", vec!["count"])])
+            .unwrap()
+            .sort("a", false)
+            .unwrap();
+        assert_eq!(
+            out.get_column_names(),
+            &["a", "b_quantile_0.50", "b_max", "b_count"]
+        );
+        assert_eq!(
+            Vec::from(out.column("b_max").unwrap().f64().unwrap()),
+            &[Some(2.), Some(5.)]
+        );
+
+        let gb = df.groupby("a").unwrap();
+        assert!(gb.agg(&[("b", &["quantile(1.5)"])]).is_err());
+        assert!(matches!(
+            gb.agg(&[("b", &["mode"])]),
+            Err(PolarsError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            gb.agg(&[("missing", &["sum"])]),
+            Err(PolarsError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_groupby_n_unique_multi() {
+        let df = df! {
+            "g" => [1, 1, 1, 2, 2],
+            "a" => [1, 1, 2, 1, 1],
+            "b" => [Some("x"), Some("x"), Some("x"), None, None]
+        }
+        .unwrap();
+
+        let out = df
+            .groupby("g")
+            .unwrap()
+            .n_unique_multi(&["a", "b"])
+            .unwrap()
+            .sort("g", false)
+            .unwrap();
+        assert_eq!(
+            Vec::from(out.column("a_b_n_unique").unwrap().idx().unwrap()),
+            &[Some(2), Some(1)]
+        );
+    }
+
+    #[test]
+    fn test_groupby_top_k() {
+        let df = df! {
+            "g" => [1, 2, 1, 1, 2, 1, 3],
+            "v" => [Some(3), Some(7), None, Some(5), Some(1), Some(4), None],
+            "s" => ["a", "b", "c", "d", "e", "f", "g"]
+        }
+        .unwrap();
+
+        let out = df
+            .groupby("g")
+            .unwrap()
+            .select("s")
+            .top_k(2, "v")
+            .unwrap()
+            .sort("s", false)
+            .unwrap();
+        assert_eq!(out.get_column_names(), &["g", "s"]);
+        assert_eq!(
+            Vec::from(out.column("s").unwrap().utf8().unwrap()),
+            &[Some("b"), Some("d"), Some("e"), Some("f")]
+        );
+
+        let out = df.groupby("g").unwrap().select("v").top_k(5, "s").unwrap();
+        assert_eq!(out.height(), 7);
+
+        let gb = df.groupby("g").unwrap();
+        let groups = gb.get_groups();
+        let top = df.column("v").unwrap().agg_top_k(groups, 1).unwrap();
+        let lengths = top
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|s| s.unwrap().len())
+            .sorted()
+            .collect_vec();
+        assert_eq!(lengths, &[0, 1, 1]);
+
+        let top = df.column("v").unwrap().top_k(3).unwrap();
+        assert_eq!(Vec::from(top.i32().unwrap()), &[Some(7), Some(5), Some(4)]);
+    }
+
     #[test]
     fn test_groupby_threaded() {
         for slice in &[
@@ -2713,4 +3541,62 @@ mod test {
             assert_eq!(a, b);
         }
     }
+
+    #[test]
+    fn test_groupby_var_std_dtype() {
+        let df = df! {
+            "g" => &[1, 1, 2, 2],
+            "int" => &[1, 2, 3, 5],
+            "f32" => &[1.0f32, 2.0, 3.0, 5.0]
+        }
+        .unwrap();
+        let gb = df.groupby("g").unwrap();
+        for out in &[gb.var().unwrap(), gb.std().unwrap()] {
+            for (s, dtype) in out.get_columns()[1..]
+                .iter()
+                .zip(&[DataType::Float64, DataType::Float32])
+            {
+                assert_eq!(s.dtype(), dtype);
+            }
+        }
+        let var = gb.var().unwrap().sort("g", false).unwrap();
+        assert_eq!(
+            Vec::from(var.column("int_agg_var").unwrap().f64().unwrap()),
+            &[Some(0.5), Some(2.0)]
+        );
+    }
+
+    #[test]
+    fn test_agg_fused() {
+        use GroupByMethod::*;
+        let df = df! {
+            "g" => &[1, 1, 2, 2, 2, 3],
+            "int" => &[4, -1, 3, 5, 0, 7],
+            "f64" => &[1.5, 2.0, -3.0, 5.0, 0.5, 1.0]
+        }
+        .unwrap();
+        let gb = df.groupby("g").unwrap();
+        let groups = gb.get_groups();
+        let count: NoNull<IdxCa> = groups.iter().map(|(_, g)| g.len() as IdxSize).collect();
+        let count = count.into_inner().into_series();
+        for name in &["int", "f64"] {
+            let s = df.column(name).unwrap();
+            for (methods, expected) in vec![
+                ([Min, Max], [s.agg_min(groups), s.agg_max(groups)]),
+                ([Max, Min], [s.agg_max(groups), s.agg_min(groups)]),
+                ([Sum, Count], [s.agg_sum(groups), Some(count.clone())]),
+                ([Count, Sum], [Some(count.clone()), s.agg_sum(groups)]),
+                ([Mean, Count], [s.agg_mean(groups), Some(count.clone())]),
+                ([Count, Mean], [Some(count.clone()), s.agg_mean(groups)]),
+            ] {
+                let out = agg_fused(s, groups, methods).unwrap();
+                for (out, expected) in out.iter().zip(&expected) {
+                    let expected = expected.as_ref().unwrap();
+                    assert_eq!(out.dtype(), expected.dtype());
+                    assert!(out.series_equal(expected));
+                }
+            }
+        }
+        assert!(agg_fused(df.column("int").unwrap(), groups, [Mean, Sum]).is_none());
+    }
 }
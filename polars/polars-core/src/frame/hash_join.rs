@@ -1,25 +1,64 @@
+use crate::chunked_array::ops::cardinality::EstimateNUnique;
+use crate::frame::group_by::populate_multiple_key_hashmap;
 use crate::frame::select::Selection;
 use crate::prelude::*;
 use crate::utils::{split_ca, NoNull};
 use crate::vector_hasher::{
-    create_hash_and_keys_threaded_vectorized, prepare_hashed_relation,
-    prepare_hashed_relation_threaded,
+    create_hash_and_keys_threaded_vectorized, df_rows_to_hashes, n_hash_partitions,
+    partition_hashed_keys, prepare_hashed_partition, prepare_hashed_relation, IdBuildHasher,
+    IdxHash, PartitionedKeys,
 };
 use crate::POOL;
-use ahash::RandomState;
+use ahash::{AHashMap, RandomState};
+use arrow::array::{Array, BooleanArray, LargeStringArray, PrimitiveArray};
 use hashbrown::HashMap;
 use itertools::Itertools;
 use rayon::prelude::*;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::fmt::Debug;
-use std::hash::Hash;
+use std::hash::{BuildHasher, Hash, Hasher};
 use std::ops::Deref;
 use unsafe_unwrap::UnsafeUnwrap;
 
+/// Relations with fewer rows are not worth estimating the number of distinct keys for.
+const BUILD_SIDE_MIN_ROWS: usize = 1 << 14;
+/// The number of distinct keys only decides the build side if the longer relation has at most
+/// this many times the rows of the shorter one. Otherwise probing the longer one is cheaper.
+const BUILD_SIDE_MAX_LEN_RATIO: usize = 4;
+
+/// Check if the hash table of a join should be built on the `right` relation. This is the
+/// shortest relation, unless the lengths are comparable and the longer relation has fewer
+/// distinct keys, which gives a smaller hash table with longer runs of row indexes.
+fn build_on_right<F>(len_left: usize, len_right: usize, estimate_n_unique: F) -> bool
+where
+    F: FnOnce() -> (usize, usize),
+{
+    let (shortest, longest) = if len_left > len_right {
+        (len_right, len_left)
+    } else {
+        (len_left, len_right)
+    };
+    if shortest < BUILD_SIDE_MIN_ROWS || longest > shortest * BUILD_SIDE_MAX_LEN_RATIO {
+        return len_left > len_right;
+    }
+    let (n_unique_left, n_unique_right) = estimate_n_unique();
+    if n_unique_left == n_unique_right {
+        len_left > len_right
+    } else {
+        n_unique_left > n_unique_right
+    }
+}
+
 macro_rules! det_hash_prone_order {
     ($self:expr, $other:expr) => {{
-        // The shortest relation will be used to create a hash table.
-        let left_first = $self.len() > $other.len();
+        // The hash table is created on the relation with the fewest distinct keys, see
+        // `build_on_right`.
+        let left_first = build_on_right($self.len(), $other.len(), || {
+            ($self.estimate_n_unique(), $other.estimate_n_unique())
+        });
         let a;
         let b;
         if left_first {
@@ -39,162 +78,522 @@ pub enum JoinType {
     Left,
     Inner,
     Outer,
+    /// Cartesian product. The join columns are ignored.
+    Cross,
 }
 
-unsafe fn get_hash_tbl<T>(
-    h: u64,
-    hash_tables: &[HashMap<T, Vec<u32>, RandomState>],
-    len: u64,
-) -> &HashMap<T, Vec<u32>, RandomState>
+/// The relationship between the join keys of the left and right DataFrame that
+/// [DataFrame::join](DataFrame::join) should check before joining.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JoinValidation {
+    /// No check, the keys may have duplicates on both sides.
+    ManyToMany,
+    /// The keys of the left DataFrame must be unique.
+    OneToMany,
+    /// The keys of the right DataFrame must be unique.
+    ManyToOne,
+    /// The keys of both DataFrames must be unique.
+    OneToOne,
+}
+
+impl Default for JoinValidation {
+    fn default() -> Self {
+        JoinValidation::ManyToMany
+    }
+}
+
+impl JoinValidation {
+    fn check(self, left_keys: &[Series], right_keys: &[Series]) -> Result<()> {
+        use JoinValidation::*;
+        let (unique_left, unique_right) = match self {
+            ManyToMany => (false, false),
+            OneToMany => (true, false),
+            ManyToOne => (false, true),
+            OneToOne => (true, true),
+        };
+        let check_side = |keys: &[Series], side: &str| -> Result<()> {
+            let df = DataFrame::new_no_checks(keys.to_vec());
+            let n_unique = df.groupby(df.get_column_names())?.get_groups().len();
+            if n_unique != df.height() {
+                return Err(PolarsError::Duplicate(
+                    format!(
+                        "join keys are not unique in the {} DataFrame; {} of its {} rows have a duplicate key, so this is not a {} join",
+                        side,
+                        df.height() - n_unique,
+                        df.height(),
+                        self
+                    )
+                    .into(),
+                ));
+            }
+            Ok(())
+        };
+        if unique_left {
+            check_side(left_keys, "left")?;
+        }
+        if unique_right {
+            check_side(right_keys, "right")?;
+        }
+        Ok(())
+    }
+}
+
+/// How the hash table of a single key join is built and probed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JoinStrategy {
+    /// Broadcast when the build side has at most [BROADCAST_JOIN_MAX_ROWS] rows, otherwise
+    /// use the threaded join.
+    Auto,
+    /// Partition both relations by the bits of their hashes and join the partitions on the
+    /// threads, each with its own small hash table.
+    Threaded,
+    /// Build one hash table of the build side and probe it on a single thread. This saves the
+    /// partitioning when the build side is tiny.
+    Broadcast,
+    /// Left joins probe the left relation in chunks of [LOW_MEMORY_JOIN_CHUNK_SIZE] rows and
+    /// gather the output rows of a chunk before probing the next one, so that the row indexes of
+    /// the whole join are never in memory at once. Other joins use the threaded join.
+    LowMemory,
+}
+
+impl Default for JoinStrategy {
+    fn default() -> Self {
+        JoinStrategy::Auto
+    }
+}
+
+/// Name of the column that records whether an output row of a join was matched on the
+/// `"left_only"`, `"right_only"` or `"both"` sides.
+pub const JOIN_INDICATOR_NAME: &str = "_merge";
+const LEFT_MARKER_NAME: &str = "__POLARS_JOIN_LEFT_MARKER";
+const RIGHT_MARKER_NAME: &str = "__POLARS_JOIN_RIGHT_MARKER";
+
+/// The maximum number of rows of the build side for which [JoinStrategy::Auto] broadcasts.
+pub const BROADCAST_JOIN_MAX_ROWS: usize = 1024;
+
+/// The number of left rows that a [JoinStrategy::LowMemory] left join probes at once.
+pub const LOW_MEMORY_JOIN_CHUNK_SIZE: usize = 1 << 16;
+
+impl JoinStrategy {
+    fn broadcast(self, build_len: usize) -> bool {
+        match self {
+            JoinStrategy::Auto => build_len <= BROADCAST_JOIN_MAX_ROWS,
+            JoinStrategy::Threaded | JoinStrategy::LowMemory => false,
+            JoinStrategy::Broadcast => true,
+        }
+    }
+}
+
+/// The arguments of a [join](DataFrame::join). Every argument but the [JoinType] has a default,
+/// which the `with_*` methods override. A [JoinType] converts into the default arguments.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JoinArgs {
+    pub how: JoinType,
+    /// Suffix for the columns of `other` that are also in the left DataFrame.
+    /// Defaults to `"_right"`.
+    pub suffix: Option<String>,
+    /// Check that the join keys are unique on the left and/or right side.
+    /// Defaults to [JoinValidation::ManyToMany], which checks nothing.
+    pub validate: JoinValidation,
+    /// Append a Categorical [`"_merge"`](JOIN_INDICATOR_NAME) column that records if an output
+    /// row was matched on the `"left_only"`, `"right_only"` or `"both"` sides. Defaults to `false`.
+    pub indicator: bool,
+    /// How the hash table of inner and left joins on a single key column is built and probed.
+    /// Defaults to [JoinStrategy::Auto].
+    pub strategy: JoinStrategy,
+    /// Order the rows of an outer join by the left rows, followed by the unmatched right rows.
+    /// Defaults to `false`, in which case the order of an outer join is not defined. Inner and
+    /// left joins always keep the order of the left rows.
+    pub maintain_order: bool,
+}
+
+impl JoinArgs {
+    pub fn new(how: JoinType) -> Self {
+        JoinArgs {
+            how,
+            suffix: None,
+            validate: JoinValidation::default(),
+            indicator: false,
+            strategy: JoinStrategy::default(),
+            maintain_order: false,
+        }
+    }
+
+    pub fn with_suffix(mut self, suffix: Option<String>) -> Self {
+        self.suffix = suffix;
+        self
+    }
+
+    pub fn with_validation(mut self, validate: JoinValidation) -> Self {
+        self.validate = validate;
+        self
+    }
+
+    pub fn with_indicator(mut self, indicator: bool) -> Self {
+        self.indicator = indicator;
+        self
+    }
+
+    pub fn with_strategy(mut self, strategy: JoinStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    pub fn with_maintain_order(mut self, maintain_order: bool) -> Self {
+        self.maintain_order = maintain_order;
+        self
+    }
+}
+
+impl From<JoinType> for JoinArgs {
+    fn from(how: JoinType) -> Self {
+        JoinArgs::new(how)
+    }
+}
+
+impl std::fmt::Display for JoinValidation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            JoinValidation::ManyToMany => "many-to-many",
+            JoinValidation::OneToMany => "one-to-many",
+            JoinValidation::ManyToOne => "many-to-one",
+            JoinValidation::OneToOne => "one-to-one",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// A relation of a join.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JoinSide {
+    Left,
+    Right,
+}
+
+/// Diagnostics of a join, see [DataFrame::join_with_statistics](DataFrame::join_with_statistics).
+#[derive(Clone, Debug, PartialEq)]
+pub struct JoinStatistics {
+    pub left_rows: usize,
+    pub right_rows: usize,
+    /// The relation the hash table was built on, which is picked by its length and its estimated
+    /// number of distinct keys. `None` if no hash table was built, like in a merge join of sorted
+    /// keys or a cross join.
+    pub build_side: Option<JoinSide>,
+    pub build_side_rows: usize,
+    /// The number of distinct keys in the hash table. The other rows of the build side have the
+    /// key of an earlier row, end up in the same bucket and multiply the output rows.
+    pub build_side_keys: usize,
+    /// The number of keys in the hash table that have the hash of another key, and are only told
+    /// apart by comparing the keys.
+    pub hash_collisions: usize,
+    /// The number of rows of which the key is found in the other relation.
+    pub left_matched_rows: usize,
+    pub right_matched_rows: usize,
+    pub output_rows: usize,
+}
+
+impl JoinStatistics {
+    /// The fraction of the left rows that is matched.
+    pub fn left_match_rate(&self) -> f64 {
+        match_rate(self.left_matched_rows, self.left_rows)
+    }
+
+    /// The fraction of the right rows that is matched.
+    pub fn right_match_rate(&self) -> f64 {
+        match_rate(self.right_matched_rows, self.right_rows)
+    }
+}
+
+fn match_rate(matched: usize, rows: usize) -> f64 {
+    if rows == 0 {
+        0.0
+    } else {
+        matched as f64 / rows as f64
+    }
+}
+
+impl std::fmt::Display for JoinStatistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "rows: {} left, {} right; ",
+            self.left_rows, self.right_rows
+        )?;
+        match self.build_side {
+            Some(side) => write!(
+                f,
+                "build side: {:?} ({} rows, {} distinct keys, {} hash collisions); ",
+                side, self.build_side_rows, self.build_side_keys, self.hash_collisions
+            )?,
+            None => write!(f, "build side: none; ")?,
+        }
+        write!(
+            f,
+            "matched rows: {} left ({:.1}%), {} right ({:.1}%); output rows: {}",
+            self.left_matched_rows,
+            self.left_match_rate() * 100.0,
+            self.right_matched_rows,
+            self.right_match_rate() * 100.0,
+            self.output_rows
+        )
+    }
+}
+
+/// The counters of a join that runs in [DataFrame::join_with_statistics]. They are recorded by
+/// the join kernels of the same thread.
+#[derive(Default)]
+struct JoinCounters {
+    build_side: Option<JoinSide>,
+    build_side_keys: usize,
+    hash_collisions: usize,
+    left_matched: Vec<bool>,
+    right_matched: Vec<bool>,
+}
+
+thread_local! {static JOIN_COUNTERS: RefCell<Option<JoinCounters>> = RefCell::new(None)}
+
+/// Record the hash tables a join kernel built, if join statistics are collected. The tables are
+/// the partitions of the keys of a single relation, so keys with the same hash are in the same
+/// table.
+fn record_hash_tables<K, V, S>(hash_tbls: &[HashMap<K, V, S>], build_left: bool)
 where
-    T: Send + Hash + Eq + Sync + Copy,
+    K: Hash,
+    S: BuildHasher,
 {
-    let mut idx = 0;
-    for i in 0..len {
-        if (h + i) % len == 0 {
-            idx = i as usize;
+    JOIN_COUNTERS.with(|counters| {
+        if let Some(counters) = counters.borrow_mut().as_mut() {
+            counters.build_side = Some(if build_left {
+                JoinSide::Left
+            } else {
+                JoinSide::Right
+            });
+            counters.build_side_keys = hash_tbls.iter().map(|tbl| tbl.len()).sum();
+            counters.hash_collisions = hash_tbls
+                .iter()
+                .map(|tbl| {
+                    let mut hashes =
+                        HashSet::with_capacity_and_hasher(tbl.len(), IdBuildHasher::default());
+                    tbl.keys()
+                        .filter(|k| {
+                            let mut hasher = tbl.hasher().build_hasher();
+                            k.hash(&mut hasher);
+                            !hashes.insert(hasher.finish())
+                        })
+                        .count()
+                })
+                .sum();
         }
-    }
-    hash_tables.get_unchecked(idx)
+    })
 }
 
-#[allow(clippy::needless_collect)]
-fn hash_join_tuples_inner_threaded<T, I, J>(
+/// Check that all join tuples point into the left and right tables, and record the matched rows
+/// if join statistics are collected.
+/// The tuples are used to gather without bound checks, so in debug builds we validate them first.
+fn check_join_tuples<I>(tuples: I, left_len: usize, right_len: usize)
+where
+    I: Iterator<Item = (Option<IdxSize>, Option<IdxSize>)>,
+{
+    JOIN_COUNTERS.with(|counters| {
+        let mut counters = counters.borrow_mut();
+        if !cfg!(debug_assertions) && counters.is_none() {
+            return;
+        }
+        if let Some(counters) = counters.as_mut() {
+            counters.left_matched.resize(left_len, false);
+            counters.right_matched.resize(right_len, false);
+        }
+        for (opt_left, opt_right) in tuples {
+            if cfg!(debug_assertions) {
+                assert!(
+                    opt_left.map_or(true, |idx| (idx as usize) < left_len),
+                    "left join index {:?} out of bounds for length {}",
+                    opt_left,
+                    left_len
+                );
+                assert!(
+                    opt_right.map_or(true, |idx| (idx as usize) < right_len),
+                    "right join index {:?} out of bounds for length {}",
+                    opt_right,
+                    right_len
+                );
+            }
+            if let (Some(counters), Some(left), Some(right)) =
+                (counters.as_mut(), opt_left, opt_right)
+            {
+                counters.left_matched[left as usize] = true;
+                counters.right_matched[right as usize] = true;
+            }
+        }
+    })
+}
+
+/// Hash both relations and build a hash table for every partition of the keys of `b`. Equal keys
+/// end up in the same partition, so the tables can be built independently. The hashed keys of `a`
+/// are partitioned in the same way, so that every partition of `a` only probes its own table.
+#[allow(clippy::type_complexity)]
+fn hash_relations_and_build_partitions<T, I, J>(
     a: Vec<I>,
     b: Vec<J>,
-    // Because b should be the shorter relation we could need to swap to keep left left and right right.
-    swap: bool,
-) -> Vec<(u32, u32)>
+) -> (
+    Vec<PartitionedKeys<T>>,
+    Vec<HashMap<T, Vec<IdxSize>, RandomState>>,
+)
 where
     I: Iterator<Item = T> + Send,
     J: Iterator<Item = T> + Send,
-    T: Send + Hash + Eq + Sync + Copy + Debug,
+    T: Send + Hash + Eq + Sync + Copy,
 {
-    // first we hash one relation
-    let hash_tbls = prepare_hashed_relation_threaded(b);
-    let random_state = hash_tbls[0].hasher().clone();
-    let (probe_hashes, _) = create_hash_and_keys_threaded_vectorized(a, Some(random_state));
+    let n_partitions = n_hash_partitions(a.len());
+    let (hashes_b, random_state) = create_hash_and_keys_threaded_vectorized(b, None);
+    let (hashes_a, _) = create_hash_and_keys_threaded_vectorized(a, Some(random_state.clone()));
+    let partitioned_b = partition_hashed_keys(hashes_b, n_partitions);
+    let partitioned_a = partition_hashed_keys(hashes_a, n_partitions);
+    let hash_tbls = POOL.install(|| {
+        (0..n_partitions)
+            .into_par_iter()
+            .map(|partition| {
+                prepare_hashed_partition(&partitioned_b, partition, random_state.clone())
+            })
+            .collect()
+    });
+    (partitioned_a, hash_tbls)
+}
 
-    let n_tables = hash_tbls.len() as u64;
-    let offsets = probe_hashes
-        .iter()
-        .map(|ph| ph.len())
-        .scan(0, |state, val| {
-            let out = *state;
-            *state += val;
-            Some(out)
-        })
-        .collect::<Vec<_>>();
-    // next we probe the other relation
-    // code duplication is because we want to only do the swap check once
-    POOL.install(|| {
-        probe_hashes
+/// Pointer to the output of a parallel scatter, in which every thread writes to its own
+/// positions.
+#[derive(Clone, Copy)]
+struct ScatterPtr<R>(*mut R);
+
+unsafe impl<R: Send> Send for ScatterPtr<R> {}
+unsafe impl<R: Send> Sync for ScatterPtr<R> {}
+
+impl<R> ScatterPtr<R> {
+    fn get(self) -> *mut R {
+        self.0
+    }
+}
+
+/// Probe every partition of `a` with its own hash table in parallel. `probe` is called with the
+/// row index in `a` and the matching rows of `b`, if any. The number of results of every row is
+/// recorded, so that the results of the partitions can be scattered back in the order of `a`
+/// without sorting.
+fn probe_partitioned_tables<T, R, F>(
+    partitioned_a: Vec<PartitionedKeys<T>>,
+    hash_tbls: &[HashMap<T, Vec<IdxSize>, RandomState>],
+    probe: F,
+) -> Vec<R>
+where
+    T: Send + Hash + Eq + Sync,
+    R: Send,
+    F: Fn(IdxSize, Option<&Vec<IdxSize>>, &mut Vec<R>) + Send + Sync,
+{
+    let len_a = partitioned_a.iter().flatten().map(|keys| keys.len()).sum();
+    let probed: Vec<(Vec<R>, Vec<(IdxSize, usize)>)> = POOL.install(|| {
+        (0..hash_tbls.len())
             .into_par_iter()
-            .zip(offsets)
-            .map(|(probe_hashes, offset)| {
-                // local reference
-                let hash_tbls = &hash_tbls;
-                let mut results =
-                    Vec::with_capacity(probe_hashes.len() / POOL.current_num_threads());
-                let local_offset = offset;
-                // code duplication is to hoist swap out of the inner loop.
-                if swap {
-                    probe_hashes.iter().enumerate().for_each(|(idx_a, (h, k))| {
-                        let idx_a = (idx_a + local_offset) as u32;
-                        // probe table that contains the hashed value
-                        let current_probe_table = unsafe { get_hash_tbl(*h, hash_tbls, n_tables) };
-
-                        let entry = current_probe_table
-                            .raw_entry()
-                            .from_key_hashed_nocheck(*h, k);
-
-                        if let Some((_, indexes_b)) = entry {
-                            let tuples = indexes_b.iter().map(|&idx_b| (idx_b, idx_a));
-                            results.extend(tuples);
-                        }
-                    });
-                } else {
-                    probe_hashes.iter().enumerate().for_each(|(idx_a, (h, k))| {
-                        let idx_a = (idx_a + local_offset) as u32;
-                        // probe table that contains the hashed value
-                        let current_probe_table = unsafe { get_hash_tbl(*h, hash_tbls, n_tables) };
-
-                        let entry = current_probe_table
-                            .raw_entry()
-                            .from_key_hashed_nocheck(*h, k);
-
-                        if let Some((_, indexes_b)) = entry {
-                            let tuples = indexes_b.iter().map(|&idx_b| (idx_a, idx_b));
-                            results.extend(tuples);
-                        }
-                    });
+            .map(|partition| {
+                let hash_tbl = &hash_tbls[partition];
+                let mut results = Vec::new();
+                let mut n_results = Vec::new();
+                for (idx_a, h, k) in partitioned_a.iter().flat_map(|p| &p[partition]) {
+                    let len = results.len();
+                    let entry = hash_tbl.raw_entry().from_key_hashed_nocheck(*h, k);
+                    probe(*idx_a, entry.map(|(_, indexes_b)| indexes_b), &mut results);
+                    n_results.push((*idx_a, results.len() - len));
                 }
-
-                results
+                (results, n_results)
             })
-            .flatten()
             .collect()
-    })
+    });
+
+    // the position of the first result of every row of `a` in the output
+    let mut offsets = vec![0usize; len_a];
+    for (_, n_results) in &probed {
+        for &(idx_a, n) in n_results {
+            offsets[idx_a as usize] = n;
+        }
+    }
+    let mut total = 0;
+    for offset in offsets.iter_mut() {
+        let n = *offset;
+        *offset = total;
+        total += n;
+    }
+
+    let mut out = Vec::with_capacity(total);
+    let out_ptr = ScatterPtr(out.as_mut_ptr());
+    POOL.install(|| {
+        probed.into_par_iter().for_each(|(results, n_results)| {
+            let out_ptr = out_ptr.get();
+            let mut results = results.into_iter();
+            for (idx_a, n) in n_results {
+                let offset = offsets[idx_a as usize];
+                for (i, result) in (&mut results).take(n).enumerate() {
+                    // Safety:
+                    // every row of `a` is in a single partition and has its own `n` positions
+                    // in the output, which are all smaller than `total`.
+                    unsafe { out_ptr.add(offset + i).write(result) }
+                }
+            }
+        })
+    });
+    // Safety:
+    // every position smaller than `total` is written above.
+    unsafe { out.set_len(total) };
+    out
 }
 
-fn hash_join_tuples_left_threaded<T, I, J>(a: Vec<I>, b: Vec<J>) -> Vec<(u32, Option<u32>)>
+/// Radix partitioned inner join of a and b.
+fn hash_join_tuples_inner_threaded<T, I, J>(
+    a: Vec<I>,
+    b: Vec<J>,
+    // Because b should be the shorter relation we could need to swap to keep left left and right right.
+    swap: bool,
+) -> Vec<(IdxSize, IdxSize)>
 where
     I: Iterator<Item = T> + Send,
     J: Iterator<Item = T> + Send,
     T: Send + Hash + Eq + Sync + Copy + Debug,
 {
-    // first we hash one relation
-    let hash_tbls = prepare_hashed_relation_threaded(b);
-    let random_state = hash_tbls[0].hasher().clone();
-    let (probe_hashes, _) = create_hash_and_keys_threaded_vectorized(a, Some(random_state));
+    let (partitioned_a, hash_tbls) = hash_relations_and_build_partitions(a, b);
+    record_hash_tables(&hash_tbls, swap);
 
-    let offsets = probe_hashes
-        .iter()
-        .map(|ph| ph.len())
-        .scan(0, |state, val| {
-            let out = *state;
-            *state += val;
-            Some(out)
+    // code duplication is to hoist swap out of the inner loop.
+    if swap {
+        probe_partitioned_tables(partitioned_a, &hash_tbls, |idx_a, entry, results| {
+            if let Some(indexes_b) = entry {
+                results.extend(indexes_b.iter().map(|&idx_b| (idx_b, idx_a)))
+            }
+        })
+    } else {
+        probe_partitioned_tables(partitioned_a, &hash_tbls, |idx_a, entry, results| {
+            if let Some(indexes_b) = entry {
+                results.extend(indexes_b.iter().map(|&idx_b| (idx_a, idx_b)))
+            }
         })
-        .collect::<Vec<_>>();
+    }
+}
 
-    let n_tables = hash_tbls.len() as u64;
+/// Radix partitioned left join of a and b.
+fn hash_join_tuples_left_threaded<T, I, J>(a: Vec<I>, b: Vec<J>) -> Vec<(IdxSize, Option<IdxSize>)>
+where
+    I: Iterator<Item = T> + Send,
+    J: Iterator<Item = T> + Send,
+    T: Send + Hash + Eq + Sync + Copy + Debug,
+{
+    let (partitioned_a, hash_tbls) = hash_relations_and_build_partitions(a, b);
+    record_hash_tables(&hash_tbls, false);
 
-    // next we probe the other relation
-    // code duplication is because we want to only do the swap check once
-    POOL.install(|| {
-        probe_hashes
-            .into_par_iter()
-            .zip(offsets)
-            .map(|(probe_hashes, offset)| {
-                // local reference
-                let hash_tbls = &hash_tbls;
-                let mut results =
-                    Vec::with_capacity(probe_hashes.len() / POOL.current_num_threads());
-
-                probe_hashes.iter().enumerate().for_each(|(idx_a, (h, k))| {
-                    let idx_a = (idx_a + offset) as u32;
-                    // probe table that contains the hashed value
-                    let current_probe_table = unsafe { get_hash_tbl(*h, hash_tbls, n_tables) };
-
-                    let entry = current_probe_table
-                        .raw_entry()
-                        .from_key_hashed_nocheck(*h, k);
-
-                    match entry {
-                        // left and right matches
-                        Some((_, indexes_b)) => {
-                            results.extend(indexes_b.iter().map(|&idx_b| (idx_a, Some(idx_b))))
-                        }
-                        // only left values, right = null
-                        None => results.push((idx_a, None)),
-                    }
-                });
-                results
-            })
-            .flatten()
-            .collect()
-    })
+    probe_partitioned_tables(
+        partitioned_a,
+        &hash_tbls,
+        |idx_a, entry, results| match entry {
+            // left and right matches
+            Some(indexes_b) => results.extend(indexes_b.iter().map(|&idx_b| (idx_a, Some(idx_b)))),
+            // only left values, right = null
+            None => results.push((idx_a, None)),
+        },
+    )
 }
 
 /// Hash join a and b.
@@ -205,19 +604,20 @@ fn hash_join_tuples_inner<T>(
     b: impl Iterator<Item = T>,
     // Because b should be the shorter relation we could need to swap to keep left left and right right.
     swap: bool,
-) -> Vec<(u32, u32)>
+) -> Vec<(IdxSize, IdxSize)>
 where
     T: Hash + Eq + Copy,
 {
     let mut results = Vec::new();
     // First we hash one relation
     let hash_tbl = prepare_hashed_relation(b);
+    record_hash_tables(std::slice::from_ref(&hash_tbl), swap);
 
     // Next we probe the other relation in the hash table
     // code duplication is because we want to only do the swap check once
     if swap {
         a.enumerate().for_each(|(idx_a, key)| {
-            let idx_a = idx_a as u32;
+            let idx_a = idx_a as IdxSize;
             if let Some(indexes_b) = hash_tbl.get(&key) {
                 let tuples = indexes_b.iter().map(|&idx_b| (idx_b, idx_a));
                 results.extend(tuples)
@@ -225,7 +625,7 @@ where
         });
     } else {
         a.enumerate().for_each(|(idx_a, key)| {
-            let idx_a = idx_a as u32;
+            let idx_a = idx_a as IdxSize;
             if let Some(indexes_b) = hash_tbl.get(&key) {
                 let tuples = indexes_b.iter().map(|&idx_b| (idx_a, idx_b));
                 results.extend(tuples)
@@ -240,17 +640,30 @@ where
 fn hash_join_tuples_left<T>(
     a: impl Iterator<Item = T>,
     b: impl Iterator<Item = T>,
-) -> Vec<(u32, Option<u32>)>
+) -> Vec<(IdxSize, Option<IdxSize>)>
 where
     T: Hash + Eq + Copy,
 {
-    let mut results = Vec::new();
     // First we hash one relation
     let hash_tbl = prepare_hashed_relation(b);
+    record_hash_tables(std::slice::from_ref(&hash_tbl), false);
 
     // Next we probe the other relation in the hash table
+    probe_left(&hash_tbl, a)
+}
+
+/// Probe the hash table of the right relation with every key of `a`, in row order. The row
+/// indexes of `a` start at 0.
+fn probe_left<T>(
+    hash_tbl: &HashMap<T, Vec<IdxSize>, RandomState>,
+    a: impl Iterator<Item = T>,
+) -> Vec<(IdxSize, Option<IdxSize>)>
+where
+    T: Hash + Eq,
+{
+    let mut results = Vec::with_capacity(a.size_hint().0);
     a.enumerate().for_each(|(idx_a, key)| {
-        let idx_a = idx_a as u32;
+        let idx_a = idx_a as IdxSize;
         match hash_tbl.get(&key) {
             // left and right matches
             Some(indexes_b) => results.extend(indexes_b.iter().map(|&idx_b| (idx_a, Some(idx_b)))),
@@ -261,9 +674,98 @@ where
     results
 }
 
+/// Merge join a and b. Both should be sorted in the same direction and may not contain nulls.
+fn merge_join_tuples_inner<T>(a: &[T], b: &[T], descending: bool) -> Vec<(IdxSize, IdxSize)>
+where
+    T: Ord + Copy,
+{
+    let mut results = Vec::with_capacity(std::cmp::min(a.len(), b.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        let ord = a[i].cmp(&b[j]);
+        let ord = if descending { ord.reverse() } else { ord };
+        match ord {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                let key = b[j];
+                let run_end = j + b[j..].iter().take_while(|&&v| v == key).count();
+                // every left value in this run matches the whole run on the right
+                while i < a.len() && a[i] == key {
+                    results.extend((j..run_end).map(|idx_b| (i as IdxSize, idx_b as IdxSize)));
+                    i += 1;
+                }
+                j = run_end;
+            }
+        }
+    }
+    results
+}
+
+/// Merge join left. Both should be sorted in the same direction and may not contain nulls.
+fn merge_join_tuples_left<T>(a: &[T], b: &[T], descending: bool) -> Vec<(IdxSize, Option<IdxSize>)>
+where
+    T: Ord + Copy,
+{
+    let mut results = Vec::with_capacity(a.len());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        let ord = a[i].cmp(&b[j]);
+        let ord = if descending { ord.reverse() } else { ord };
+        match ord {
+            Ordering::Less => {
+                results.push((i as IdxSize, None));
+                i += 1
+            }
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                let key = b[j];
+                let run_end = j + b[j..].iter().take_while(|&&v| v == key).count();
+                while i < a.len() && a[i] == key {
+                    results
+                        .extend((j..run_end).map(|idx_b| (i as IdxSize, Some(idx_b as IdxSize))));
+                    i += 1;
+                }
+                j = run_end;
+            }
+        }
+    }
+    // the right relation is exhausted
+    results.extend((i..a.len()).map(|idx_a| (idx_a as IdxSize, None)));
+    results
+}
+
+/// A merge join can be used if both keys are flagged as sorted in the same direction and
+/// don't have nulls. Returns if the keys are sorted in descending order.
+fn merge_join_order<T>(left: &ChunkedArray<T>, right: &ChunkedArray<T>) -> Option<bool> {
+    if left.null_count() > 0 || right.null_count() > 0 {
+        return None;
+    }
+    match (left.sorted_flag(), right.sorted_flag()) {
+        (IsSorted::Ascending, IsSorted::Ascending) => Some(false),
+        (IsSorted::Descending, IsSorted::Descending) => Some(true),
+        _ => None,
+    }
+}
+
+/// Get the values of an array without nulls as a slice, only copying if it has multiple chunks.
+fn no_null_values<T>(ca: &ChunkedArray<T>) -> Cow<[T::Native]>
+where
+    T: PolarsNumericType,
+{
+    match ca.cont_slice() {
+        Ok(slice) => Cow::Borrowed(slice),
+        Err(_) => Cow::Owned(ca.into_no_null_iter().collect()),
+    }
+}
+
 /// Hash join outer. Both left and right can have no match so Options
 /// We accept a closure as we need to do two passes over the same iterators.
-fn hash_join_tuples_outer<T, I, J>(a: I, b: J, swap: bool) -> Vec<(Option<u32>, Option<u32>)>
+fn hash_join_tuples_outer<T, I, J>(
+    a: I,
+    b: J,
+    swap: bool,
+) -> Vec<(Option<IdxSize>, Option<IdxSize>)>
 where
     I: Iterator<Item = T>,
     J: Iterator<Item = T>,
@@ -273,6 +775,7 @@ where
 
     // prepare hash table
     let mut hash_tbl = prepare_hashed_relation(b);
+    record_hash_tables(std::slice::from_ref(&hash_tbl), swap);
 
     // probe the hash table.
     // Note: indexes from b that are not matched will be None, Some(idx_b)
@@ -281,7 +784,7 @@ where
     // code duplication is because we want to only do the swap check once
     if swap {
         a.enumerate().for_each(|(idx_a, key)| {
-            let idx_a = idx_a as u32;
+            let idx_a = idx_a as IdxSize;
             match hash_tbl.remove(&key) {
                 // left and right matches
                 Some(indexes_b) => {
@@ -299,7 +802,7 @@ where
         });
     } else {
         a.enumerate().for_each(|(idx_a, key)| {
-            let idx_a = idx_a as u32;
+            let idx_a = idx_a as IdxSize;
             match hash_tbl.remove(&key) {
                 // left and right matches
                 Some(indexes_b) => {
@@ -320,14 +823,410 @@ where
     results
 }
 
+/// Order the tuples of an outer join by their left row, followed by the unmatched right rows in
+/// their right order, so that the output doesn't depend on the hash tables.
+fn sort_outer_join_tuples(tuples: &mut [(Option<IdxSize>, Option<IdxSize>)]) {
+    POOL.install(|| tuples.par_sort_unstable_by_key(|&(left, right)| (left.is_none(), left, right)))
+}
+
+/// Integer data type that a temporal data type is stored as.
+fn temporal_to_physical(dtype: &DataType) -> Option<DataType> {
+    match dtype {
+        DataType::Date32 => Some(DataType::Int32),
+        DataType::Date64 | DataType::Time64(_) | DataType::Duration(_) => Some(DataType::Int64),
+        _ => None,
+    }
+}
+
+/// Cast the join keys to a single data type. A key of the Null type is cast to the data type of
+/// the other key, so that both keys are joined with the typed hash join of the other key. If one of
+/// the keys is temporal, the `right` key is cast to the data type of the `left` key, so that
+/// temporal keys are always joined on the integers of a single data type. Dates are converted to
+/// the other date type and integers are reinterpreted as the temporal type they are stored as.
+fn coerce_join_keys(left: &Series, right: &Series) -> Result<(Series, Series)> {
+    let (dtype_left, dtype_right) = (left.dtype(), right.dtype());
+    if dtype_left == dtype_right {
+        return Ok((left.clone(), right.clone()));
+    }
+    match (dtype_left, dtype_right) {
+        (DataType::Null, _) => return Ok((left.cast_with_datatype(dtype_right)?, right.clone())),
+        (_, DataType::Null) => return Ok((left.clone(), right.cast_with_datatype(dtype_left)?)),
+        _ => {}
+    }
+    let right = match (
+        temporal_to_physical(dtype_left),
+        temporal_to_physical(dtype_right),
+    ) {
+        (None, None) => right.clone(),
+        (Some(_), Some(_))
+            if matches!(dtype_left, DataType::Date32 | DataType::Date64)
+                && matches!(dtype_right, DataType::Date32 | DataType::Date64) =>
+        {
+            right.cast_with_datatype(dtype_left)?
+        }
+        (Some(physical), None) if &physical == dtype_right => {
+            right.cast_with_datatype(dtype_left)?
+        }
+        (None, Some(physical)) if &physical == dtype_left => {
+            right.cast_with_datatype(dtype_left)?
+        }
+        _ => {
+            return Err(PolarsError::DataTypeMisMatch(
+                format!(
+                    "cannot join key {} of type {:?} with key {} of type {:?}",
+                    left.name(),
+                    dtype_left,
+                    right.name(),
+                    dtype_right
+                )
+                .into(),
+            ))
+        }
+    };
+    Ok((left.clone(), right))
+}
+
+/// [coerce_join_keys] for every pair of left and right keys.
+fn coerce_join_keys_multiple(
+    left: &[Series],
+    right: &[Series],
+) -> Result<(Vec<Series>, Vec<Series>)> {
+    let pairs = left
+        .iter()
+        .zip(right)
+        .map(|(s_left, s_right)| coerce_join_keys(s_left, s_right))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(pairs.into_iter().unzip())
+}
+
+/// Whether the u32 indexes of two categorical arrays mean the same categories, e.g. because both
+/// were created under the global string cache. Only then can they be compared without their
+/// strings.
+pub(crate) fn same_categories(left: &CategoricalChunked, right: &CategoricalChunked) -> bool {
+    match (left.get_categorical_map(), right.get_categorical_map()) {
+        (Some(l), Some(r)) if Arc::ptr_eq(l, r) || l == r => true,
+        (Some(l), Some(r)) => {
+            let l_idx = l
+                .iter()
+                .map(|(idx, cat)| (cat.as_str(), *idx))
+                .collect::<AHashMap<_, _>>();
+            r.iter().all(|(idx, cat)| {
+                l.get(idx).map_or(true, |l_cat| l_cat == cat)
+                    && l_idx.get(cat.as_str()).map_or(true, |l_idx| l_idx == idx)
+            })
+        }
+        _ => false,
+    }
+}
+
+/// Prepare the join keys of both relations to be hashed and compared row by row. Temporal keys
+/// are compared by their integers, categorical keys by their u32 indexes if both sides have the
+/// same categories, otherwise by their strings. Every key is a single chunk, so that the rows of
+/// all the keys of a relation are aligned.
+pub(crate) fn prepare_multiple_keys(
+    left: &[Series],
+    right: &[Series],
+) -> Result<(DataFrame, DataFrame)> {
+    if left.len() != right.len() {
+        return Err(PolarsError::ShapeMisMatch(
+            "the number of left and right join keys should be equal".into(),
+        ));
+    }
+    let (columns_left, columns_right) = left
+        .iter()
+        .zip(right)
+        .map(|(l, r)| {
+            let dtype = match l.dtype() {
+                DataType::Categorical(_) if same_categories(l.categorical()?, r.categorical()?) => {
+                    DataType::UInt32
+                }
+                DataType::Categorical(_) => DataType::Utf8,
+                dtype => temporal_to_physical(dtype).unwrap_or_else(|| dtype.clone()),
+            };
+            let l = l.cast_with_datatype(&dtype)?.rechunk();
+            let r = r.cast_with_datatype(&dtype)?.rechunk();
+            Ok((l, r))
+        })
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+    Ok((
+        DataFrame::new_no_checks(columns_left),
+        DataFrame::new_no_checks(columns_right),
+    ))
+}
+
+/// An arrow array of a key column that compares its values without going through `AnyValue`.
+trait KeyArray: Array {
+    /// # Safety
+    /// Doesn't check any bounds
+    unsafe fn value_eq(&self, idx: usize, other: &Self, other_idx: usize) -> bool;
+}
+
+impl<T: PolarsPrimitiveType> KeyArray for PrimitiveArray<T> {
+    unsafe fn value_eq(&self, idx: usize, other: &Self, other_idx: usize) -> bool {
+        self.value_unchecked(idx) == other.value_unchecked(other_idx)
+    }
+}
+
+impl KeyArray for BooleanArray {
+    unsafe fn value_eq(&self, idx: usize, other: &Self, other_idx: usize) -> bool {
+        self.value_unchecked(idx) == other.value_unchecked(other_idx)
+    }
+}
+
+impl KeyArray for LargeStringArray {
+    unsafe fn value_eq(&self, idx: usize, other: &Self, other_idx: usize) -> bool {
+        self.value_unchecked(idx) == other.value_unchecked(other_idx)
+    }
+}
+
+/// Compares a row of a key column of one relation with a row of the key column of the other.
+/// Nulls are seen as equal.
+trait KeyColumnEq {
+    /// # Safety
+    /// Doesn't check any bounds
+    unsafe fn eq_rows(&self, idx_a: usize, idx_b: usize) -> bool;
+}
+
+struct ArrayKeyEq<'a, A> {
+    a: &'a A,
+    b: &'a A,
+}
+
+impl<A: KeyArray> KeyColumnEq for ArrayKeyEq<'_, A> {
+    #[inline]
+    unsafe fn eq_rows(&self, idx_a: usize, idx_b: usize) -> bool {
+        match (self.a.is_valid(idx_a), self.b.is_valid(idx_b)) {
+            (true, true) => self.a.value_eq(idx_a, self.b, idx_b),
+            (valid_a, valid_b) => valid_a == valid_b,
+        }
+    }
+}
+
+/// The key types without a typed comparison, e.g. lists, are compared as `AnyValue`s.
+struct AnyValueKeyEq<'a> {
+    a: &'a Series,
+    b: &'a Series,
+}
+
+impl KeyColumnEq for AnyValueKeyEq<'_> {
+    unsafe fn eq_rows(&self, idx_a: usize, idx_b: usize) -> bool {
+        self.a.get_unchecked(idx_a) == self.b.get_unchecked(idx_b)
+    }
+}
+
+/// Compares the rows of the prepared join keys of two relations, see [prepare_multiple_keys].
+pub(crate) struct KeysEq<'a> {
+    columns: Vec<Box<dyn KeyColumnEq + 'a>>,
+}
+
+impl<'a> KeysEq<'a> {
+    pub(crate) fn new(keys_a: &'a DataFrame, keys_b: &'a DataFrame) -> Self {
+        macro_rules! array_eq {
+            ($a:expr, $b:expr) => {
+                Box::new(ArrayKeyEq {
+                    a: $a.unwrap().downcast_chunks()[0],
+                    b: $b.unwrap().downcast_chunks()[0],
+                }) as Box<dyn KeyColumnEq + 'a>
+            };
+        }
+        let columns = keys_a
+            .get_columns()
+            .iter()
+            .zip(keys_b.get_columns())
+            .map(|(a, b)| match a.dtype() {
+                DataType::Boolean => array_eq!(a.bool(), b.bool()),
+                DataType::Utf8 => array_eq!(a.utf8(), b.utf8()),
+                DataType::UInt8 => array_eq!(a.u8(), b.u8()),
+                DataType::UInt16 => array_eq!(a.u16(), b.u16()),
+                DataType::UInt32 => array_eq!(a.u32(), b.u32()),
+                DataType::UInt64 => array_eq!(a.u64(), b.u64()),
+                DataType::Int8 => array_eq!(a.i8(), b.i8()),
+                DataType::Int16 => array_eq!(a.i16(), b.i16()),
+                DataType::Int32 => array_eq!(a.i32(), b.i32()),
+                DataType::Int64 => array_eq!(a.i64(), b.i64()),
+                DataType::Float32 => array_eq!(a.f32(), b.f32()),
+                DataType::Float64 => array_eq!(a.f64(), b.f64()),
+                _ => Box::new(AnyValueKeyEq { a, b }) as Box<dyn KeyColumnEq + 'a>,
+            })
+            .collect();
+        KeysEq { columns }
+    }
+
+    /// Compare a row of the keys `a` with a row of the keys `b`, column by column so that the
+    /// first unequal key stops the comparison.
+    ///
+    /// # Safety
+    /// Doesn't check any bounds
+    #[inline]
+    pub(crate) unsafe fn eq_rows(&self, idx_a: IdxSize, idx_b: IdxSize) -> bool {
+        self.columns
+            .iter()
+            .all(|c| c.eq_rows(idx_a as usize, idx_b as usize))
+    }
+}
+
+/// Hash the rows of the join keys of both relations with the same hasher and create a hash table
+/// of the build relation `b`.
+pub(crate) fn prepare_multiple_keys_relation(
+    a: &DataFrame,
+    b: &DataFrame,
+) -> (
+    UInt64Chunked,
+    HashMap<IdxHash, (IdxSize, Vec<IdxSize>), IdBuildHasher>,
+) {
+    let (hash_tbl, random_state) = build_multiple_keys_table(b);
+    let (hashes_a, _) = df_rows_to_hashes(a, Some(random_state));
+    (hashes_a, hash_tbl)
+}
+
+/// Hash the rows of the join keys of the build relation `b` and create its hash table. The
+/// returned hasher should be used to hash the probe keys.
+fn build_multiple_keys_table(
+    b: &DataFrame,
+) -> (
+    HashMap<IdxHash, (IdxSize, Vec<IdxSize>), IdBuildHasher>,
+    RandomState,
+) {
+    let (hashes_b, random_state) = df_rows_to_hashes(b, None);
+    let keys_eq = KeysEq::new(b, b);
+
+    let mut hash_tbl: HashMap<IdxHash, (IdxSize, Vec<IdxSize>), IdBuildHasher> =
+        HashMap::with_capacity_and_hasher(hashes_b.len(), IdBuildHasher::default());
+    // hashes have no nulls
+    for (idx, h) in hashes_b.into_no_null_iter().enumerate() {
+        // Safety:
+        // the rows of the hash table are indexes of b.
+        populate_multiple_key_hashmap(&mut hash_tbl, idx as IdxSize, h, |a, b| unsafe {
+            keys_eq.eq_rows(a, b)
+        });
+    }
+    (hash_tbl, random_state)
+}
+
+/// Find the rows of the build keys that match row `idx_a` of the probe keys.
+pub(crate) fn probe_multiple_keys<'a>(
+    hash_tbl: &'a HashMap<IdxHash, (IdxSize, Vec<IdxSize>), IdBuildHasher>,
+    keys_eq: &KeysEq,
+    idx_a: IdxSize,
+    h: u64,
+) -> Option<&'a (IdxSize, Vec<IdxSize>)> {
+    hash_tbl
+        .raw_entry()
+        .from_hash(h, |idx_hash| {
+            // Safety:
+            // indices in a join operation are always in bounds.
+            unsafe { keys_eq.eq_rows(idx_a, idx_hash.idx) }
+        })
+        .map(|(_k, v)| v)
+}
+
+/// Inner join on any number of key columns. `b` should be the shorter relation.
+fn hash_join_tuples_multiple_keys_inner(
+    a: &DataFrame,
+    b: &DataFrame,
+    swap: bool,
+) -> Vec<(IdxSize, IdxSize)> {
+    let (hashes_a, hash_tbl) = prepare_multiple_keys_relation(a, b);
+    record_hash_tables(std::slice::from_ref(&hash_tbl), swap);
+    let keys_eq = KeysEq::new(a, b);
+    let mut results = Vec::new();
+    for (idx_a, h) in hashes_a.into_no_null_iter().enumerate() {
+        let idx_a = idx_a as IdxSize;
+        if let Some((_, indexes_b)) = probe_multiple_keys(&hash_tbl, &keys_eq, idx_a, h) {
+            if swap {
+                results.extend(indexes_b.iter().map(|&idx_b| (idx_b, idx_a)))
+            } else {
+                results.extend(indexes_b.iter().map(|&idx_b| (idx_a, idx_b)))
+            }
+        }
+    }
+    results
+}
+
+/// Left join on any number of key columns.
+fn hash_join_tuples_multiple_keys_left(
+    a: &DataFrame,
+    b: &DataFrame,
+) -> Vec<(IdxSize, Option<IdxSize>)> {
+    let (hashes_a, hash_tbl) = prepare_multiple_keys_relation(a, b);
+    record_hash_tables(std::slice::from_ref(&hash_tbl), false);
+    let keys_eq = KeysEq::new(a, b);
+    let mut results = Vec::with_capacity(a.height());
+    for (idx_a, h) in hashes_a.into_no_null_iter().enumerate() {
+        let idx_a = idx_a as IdxSize;
+        match probe_multiple_keys(&hash_tbl, &keys_eq, idx_a, h) {
+            // left and right matches
+            Some((_, indexes_b)) => {
+                results.extend(indexes_b.iter().map(|&idx_b| (idx_a, Some(idx_b))))
+            }
+            // only left values, right = null
+            None => results.push((idx_a, None)),
+        }
+    }
+    results
+}
+
+/// Outer join on any number of key columns. `b` should be the shorter relation.
+fn hash_join_tuples_multiple_keys_outer(
+    a: &DataFrame,
+    b: &DataFrame,
+    swap: bool,
+) -> Vec<(Option<IdxSize>, Option<IdxSize>)> {
+    let (hashes_a, hash_tbl) = prepare_multiple_keys_relation(a, b);
+    record_hash_tables(std::slice::from_ref(&hash_tbl), swap);
+    let keys_eq = KeysEq::new(a, b);
+    let mut results = Vec::with_capacity(a.height() + b.height());
+    // the groups of b that are matched; the remaining groups are joined from the right
+    let mut matched = HashSet::with_capacity_and_hasher(hash_tbl.len(), RandomState::default());
+
+    let tuple = |idx_a: Option<IdxSize>, idx_b: Option<IdxSize>| {
+        if swap {
+            (idx_b, idx_a)
+        } else {
+            (idx_a, idx_b)
+        }
+    };
+    for (idx_a, h) in hashes_a.into_no_null_iter().enumerate() {
+        let idx_a = idx_a as IdxSize;
+        match probe_multiple_keys(&hash_tbl, &keys_eq, idx_a, h) {
+            Some((first, indexes_b)) => {
+                matched.insert(*first);
+                results.extend(
+                    indexes_b
+                        .iter()
+                        .map(|&idx_b| tuple(Some(idx_a), Some(idx_b))),
+                )
+            }
+            None => results.push(tuple(Some(idx_a), None)),
+        }
+    }
+    hash_tbl
+        .values()
+        .filter(|(first, _)| !matched.contains(first))
+        .for_each(|(_, indexes_b)| {
+            results.extend(indexes_b.iter().map(|&idx_b| tuple(None, Some(idx_b))))
+        });
+    results
+}
+
 pub(crate) trait HashJoin<T> {
-    fn hash_join_inner(&self, _other: &ChunkedArray<T>) -> Vec<(u32, u32)> {
+    fn hash_join_inner(
+        &self,
+        _other: &ChunkedArray<T>,
+        _strategy: JoinStrategy,
+    ) -> Vec<(IdxSize, IdxSize)> {
         unimplemented!()
     }
-    fn hash_join_left(&self, _other: &ChunkedArray<T>) -> Vec<(u32, Option<u32>)> {
+    fn hash_join_left(
+        &self,
+        _other: &ChunkedArray<T>,
+        _strategy: JoinStrategy,
+    ) -> Vec<(IdxSize, Option<IdxSize>)> {
         unimplemented!()
     }
-    fn hash_join_outer(&self, _other: &ChunkedArray<T>) -> Vec<(Option<u32>, Option<u32>)> {
+    fn hash_join_outer(&self, _other: &ChunkedArray<T>) -> Vec<(Option<IdxSize>, Option<IdxSize>)> {
         unimplemented!()
     }
 }
@@ -335,8 +1234,26 @@ pub(crate) trait HashJoin<T> {
 macro_rules! impl_float_hash_join {
     ($type: ty, $ca: ty) => {
         impl HashJoin<$type> for $ca {
-            fn hash_join_inner(&self, other: &$ca) -> Vec<(u32, u32)> {
+            fn hash_join_inner(
+                &self,
+                other: &$ca,
+                strategy: JoinStrategy,
+            ) -> Vec<(IdxSize, IdxSize)> {
                 let (a, b, swap) = det_hash_prone_order!(self, other);
+                if strategy.broadcast(b.len()) {
+                    return match (a.null_count(), b.null_count()) {
+                        (0, 0) => hash_join_tuples_inner(
+                            a.into_no_null_iter().map(|v| v.to_bits()),
+                            b.into_no_null_iter().map(|v| v.to_bits()),
+                            swap,
+                        ),
+                        _ => hash_join_tuples_inner(
+                            a.into_iter().map(|opt_v| opt_v.map(|v| v.to_bits())),
+                            b.into_iter().map(|opt_v| opt_v.map(|v| v.to_bits())),
+                            swap,
+                        ),
+                    };
+                }
 
                 let n_threads = n_join_threads();
                 let splitted_a = split_ca(a, n_threads).unwrap();
@@ -367,11 +1284,26 @@ macro_rules! impl_float_hash_join {
                     }
                 }
             }
-            fn hash_join_left(&self, other: &$ca) -> Vec<(u32, Option<u32>)> {
-                let n_threads = n_join_threads();
-
+            fn hash_join_left(
+                &self,
+                other: &$ca,
+                strategy: JoinStrategy,
+            ) -> Vec<(IdxSize, Option<IdxSize>)> {
                 let a = self;
                 let b = other;
+                if strategy.broadcast(b.len()) {
+                    return match (a.null_count(), b.null_count()) {
+                        (0, 0) => hash_join_tuples_left(
+                            a.into_no_null_iter().map(|v| v.to_bits()),
+                            b.into_no_null_iter().map(|v| v.to_bits()),
+                        ),
+                        _ => hash_join_tuples_left(
+                            a.into_iter().map(|opt_v| opt_v.map(|v| v.to_bits())),
+                            b.into_iter().map(|opt_v| opt_v.map(|v| v.to_bits())),
+                        ),
+                    };
+                }
+                let n_threads = n_join_threads();
                 let splitted_a = split_ca(a, n_threads).unwrap();
                 let splitted_b = split_ca(b, n_threads).unwrap();
 
@@ -400,7 +1332,7 @@ macro_rules! impl_float_hash_join {
                     }
                 }
             }
-            fn hash_join_outer(&self, other: &$ca) -> Vec<(Option<u32>, Option<u32>)> {
+            fn hash_join_outer(&self, other: &$ca) -> Vec<(Option<IdxSize>, Option<IdxSize>)> {
                 let (a, b, swap) = det_hash_prone_order!(self, other);
 
                 match (a.null_count() == 0, b.null_count() == 0) {
@@ -424,15 +1356,45 @@ impl_float_hash_join!(Float32Type, Float32Chunked);
 impl_float_hash_join!(Float64Type, Float64Chunked);
 
 impl HashJoin<ListType> for ListChunked {}
+/// Categorical keys are joined on their u32 indexes if both sides have the same categories,
+/// otherwise on their strings.
 impl HashJoin<CategoricalType> for CategoricalChunked {
-    fn hash_join_inner(&self, other: &CategoricalChunked) -> Vec<(u32, u32)> {
-        self.deref().hash_join_inner(&other.cast().unwrap())
+    fn hash_join_inner(
+        &self,
+        other: &CategoricalChunked,
+        strategy: JoinStrategy,
+    ) -> Vec<(IdxSize, IdxSize)> {
+        if same_categories(self, other) {
+            self.deref()
+                .hash_join_inner(&other.cast().unwrap(), strategy)
+        } else {
+            let (a, b): (Utf8Chunked, Utf8Chunked) = (self.cast().unwrap(), other.cast().unwrap());
+            a.hash_join_inner(&b, strategy)
+        }
     }
-    fn hash_join_left(&self, other: &CategoricalChunked) -> Vec<(u32, Option<u32>)> {
-        self.deref().hash_join_left(&other.cast().unwrap())
+    fn hash_join_left(
+        &self,
+        other: &CategoricalChunked,
+        strategy: JoinStrategy,
+    ) -> Vec<(IdxSize, Option<IdxSize>)> {
+        if same_categories(self, other) {
+            self.deref()
+                .hash_join_left(&other.cast().unwrap(), strategy)
+        } else {
+            let (a, b): (Utf8Chunked, Utf8Chunked) = (self.cast().unwrap(), other.cast().unwrap());
+            a.hash_join_left(&b, strategy)
+        }
     }
-    fn hash_join_outer(&self, other: &CategoricalChunked) -> Vec<(Option<u32>, Option<u32>)> {
-        self.deref().hash_join_outer(&other.cast().unwrap())
+    fn hash_join_outer(
+        &self,
+        other: &CategoricalChunked,
+    ) -> Vec<(Option<IdxSize>, Option<IdxSize>)> {
+        if same_categories(self, other) {
+            self.deref().hash_join_outer(&other.cast().unwrap())
+        } else {
+            let (a, b): (Utf8Chunked, Utf8Chunked) = (self.cast().unwrap(), other.cast().unwrap());
+            a.hash_join_outer(&b)
+        }
     }
 }
 
@@ -446,10 +1408,29 @@ fn n_join_threads() -> usize {
 impl<T> HashJoin<T> for ChunkedArray<T>
 where
     T: PolarsIntegerType + Sync,
-    T::Native: Eq + Hash,
+    T::Native: Eq + Hash + Ord,
 {
-    fn hash_join_inner(&self, other: &ChunkedArray<T>) -> Vec<(u32, u32)> {
+    fn hash_join_inner(
+        &self,
+        other: &ChunkedArray<T>,
+        strategy: JoinStrategy,
+    ) -> Vec<(IdxSize, IdxSize)> {
+        if let Some(descending) = merge_join_order(self, other) {
+            return merge_join_tuples_inner(
+                &no_null_values(self),
+                &no_null_values(other),
+                descending,
+            );
+        }
         let (a, b, swap) = det_hash_prone_order!(self, other);
+        if strategy.broadcast(b.len()) {
+            return match (a.null_count(), b.null_count()) {
+                (0, 0) => {
+                    hash_join_tuples_inner(a.into_no_null_iter(), b.into_no_null_iter(), swap)
+                }
+                _ => hash_join_tuples_inner(a.into_iter(), b.into_iter(), swap),
+            };
+        }
 
         let n_threads = n_join_threads();
         let splitted_a = split_ca(a, n_threads).unwrap();
@@ -475,11 +1456,27 @@ where
         }
     }
 
-    fn hash_join_left(&self, other: &ChunkedArray<T>) -> Vec<(u32, Option<u32>)> {
-        let n_threads = n_join_threads();
-
+    fn hash_join_left(
+        &self,
+        other: &ChunkedArray<T>,
+        strategy: JoinStrategy,
+    ) -> Vec<(IdxSize, Option<IdxSize>)> {
+        if let Some(descending) = merge_join_order(self, other) {
+            return merge_join_tuples_left(
+                &no_null_values(self),
+                &no_null_values(other),
+                descending,
+            );
+        }
         let a = self;
         let b = other;
+        if strategy.broadcast(b.len()) {
+            return match (a.null_count(), b.null_count()) {
+                (0, 0) => hash_join_tuples_left(a.into_no_null_iter(), b.into_no_null_iter()),
+                _ => hash_join_tuples_left(a.into_iter(), b.into_iter()),
+            };
+        }
+        let n_threads = n_join_threads();
         let splitted_a = split_ca(a, n_threads).unwrap();
         let splitted_b = split_ca(b, n_threads).unwrap();
 
@@ -503,7 +1500,7 @@ where
         }
     }
 
-    fn hash_join_outer(&self, other: &ChunkedArray<T>) -> Vec<(Option<u32>, Option<u32>)> {
+    fn hash_join_outer(&self, other: &ChunkedArray<T>) -> Vec<(Option<IdxSize>, Option<IdxSize>)> {
         let (a, b, swap) = det_hash_prone_order!(self, other);
 
         match (a.null_count() == 0, b.null_count() == 0) {
@@ -516,7 +1513,12 @@ where
 }
 
 impl HashJoin<BooleanType> for BooleanChunked {
-    fn hash_join_inner(&self, other: &BooleanChunked) -> Vec<(u32, u32)> {
+    // there are at most three distinct keys, so the join is always single threaded
+    fn hash_join_inner(
+        &self,
+        other: &BooleanChunked,
+        _strategy: JoinStrategy,
+    ) -> Vec<(IdxSize, IdxSize)> {
         let (a, b, swap) = det_hash_prone_order!(self, other);
 
         // Create the join tuples
@@ -528,7 +1530,11 @@ impl HashJoin<BooleanType> for BooleanChunked {
         }
     }
 
-    fn hash_join_left(&self, other: &BooleanChunked) -> Vec<(u32, Option<u32>)> {
+    fn hash_join_left(
+        &self,
+        other: &BooleanChunked,
+        _strategy: JoinStrategy,
+    ) -> Vec<(IdxSize, Option<IdxSize>)> {
         match (self.null_count() == 0, other.null_count() == 0) {
             (true, true) => {
                 hash_join_tuples_left(self.into_no_null_iter(), other.into_no_null_iter())
@@ -537,7 +1543,7 @@ impl HashJoin<BooleanType> for BooleanChunked {
         }
     }
 
-    fn hash_join_outer(&self, other: &BooleanChunked) -> Vec<(Option<u32>, Option<u32>)> {
+    fn hash_join_outer(&self, other: &BooleanChunked) -> Vec<(Option<IdxSize>, Option<IdxSize>)> {
         let (a, b, swap) = det_hash_prone_order!(self, other);
         match (a.null_count() == 0, b.null_count() == 0) {
             (true, true) => {
@@ -548,9 +1554,33 @@ impl HashJoin<BooleanType> for BooleanChunked {
     }
 }
 
+/// Intern the join keys if the strings repeat a lot, so that the hash tables work on u32 codes.
+fn intern_join_keys(a: &Utf8Chunked, b: &Utf8Chunked) -> Option<(UInt32Chunked, UInt32Chunked)> {
+    if a.should_intern() || b.should_intern() {
+        Some(a.intern_pair(b))
+    } else {
+        None
+    }
+}
+
 impl HashJoin<Utf8Type> for Utf8Chunked {
-    fn hash_join_inner(&self, other: &Utf8Chunked) -> Vec<(u32, u32)> {
+    fn hash_join_inner(
+        &self,
+        other: &Utf8Chunked,
+        strategy: JoinStrategy,
+    ) -> Vec<(IdxSize, IdxSize)> {
+        if let Some((a, b)) = intern_join_keys(self, other) {
+            return a.hash_join_inner(&b, strategy);
+        }
         let (a, b, swap) = det_hash_prone_order!(self, other);
+        if strategy.broadcast(b.len()) {
+            return match (a.null_count(), b.null_count()) {
+                (0, 0) => {
+                    hash_join_tuples_inner(a.into_no_null_iter(), b.into_no_null_iter(), swap)
+                }
+                _ => hash_join_tuples_inner(a.into_iter(), b.into_iter(), swap),
+            };
+        }
 
         let n_threads = n_join_threads();
         let splitted_a = split_ca(a, n_threads).unwrap();
@@ -576,11 +1606,23 @@ impl HashJoin<Utf8Type> for Utf8Chunked {
         }
     }
 
-    fn hash_join_left(&self, other: &Utf8Chunked) -> Vec<(u32, Option<u32>)> {
-        let n_threads = n_join_threads();
-
+    fn hash_join_left(
+        &self,
+        other: &Utf8Chunked,
+        strategy: JoinStrategy,
+    ) -> Vec<(IdxSize, Option<IdxSize>)> {
+        if let Some((a, b)) = intern_join_keys(self, other) {
+            return a.hash_join_left(&b, strategy);
+        }
         let a = self;
         let b = other;
+        if strategy.broadcast(b.len()) {
+            return match (a.null_count(), b.null_count()) {
+                (0, 0) => hash_join_tuples_left(a.into_no_null_iter(), b.into_no_null_iter()),
+                _ => hash_join_tuples_left(a.into_iter(), b.into_iter()),
+            };
+        }
+        let n_threads = n_join_threads();
         let splitted_a = split_ca(a, n_threads).unwrap();
         let splitted_b = split_ca(b, n_threads).unwrap();
 
@@ -604,7 +1646,10 @@ impl HashJoin<Utf8Type> for Utf8Chunked {
         }
     }
 
-    fn hash_join_outer(&self, other: &Utf8Chunked) -> Vec<(Option<u32>, Option<u32>)> {
+    fn hash_join_outer(&self, other: &Utf8Chunked) -> Vec<(Option<IdxSize>, Option<IdxSize>)> {
+        if let Some((a, b)) = intern_join_keys(self, other) {
+            return a.hash_join_outer(&b);
+        }
         let (a, b, swap) = det_hash_prone_order!(self, other);
         match (a.null_count() == 0, b.null_count() == 0) {
             (true, true) => {
@@ -619,7 +1664,7 @@ pub trait ZipOuterJoinColumn {
     fn zip_outer_join_column(
         &self,
         _right_column: &Series,
-        _opt_join_tuples: &[(Option<u32>, Option<u32>)],
+        _opt_join_tuples: &[(Option<IdxSize>, Option<IdxSize>)],
     ) -> Series {
         unimplemented!()
     }
@@ -627,13 +1672,13 @@ pub trait ZipOuterJoinColumn {
 
 impl<T> ZipOuterJoinColumn for ChunkedArray<T>
 where
-    T: PolarsIntegerType,
+    T: PolarsNumericType,
     ChunkedArray<T>: IntoSeries,
 {
     fn zip_outer_join_column(
         &self,
         right_column: &Series,
-        opt_join_tuples: &[(Option<u32>, Option<u32>)],
+        opt_join_tuples: &[(Option<IdxSize>, Option<IdxSize>)],
     ) -> Series {
         let right_ca = self.unpack_series_matching_type(right_column).unwrap();
 
@@ -658,10 +1703,93 @@ where
     }
 }
 
-impl ZipOuterJoinColumn for Float32Chunked {}
-impl ZipOuterJoinColumn for Float64Chunked {}
-impl ZipOuterJoinColumn for ListChunked {}
-impl ZipOuterJoinColumn for CategoricalChunked {}
+impl ZipOuterJoinColumn for ListChunked {
+    fn zip_outer_join_column(
+        &self,
+        right_column: &Series,
+        opt_join_tuples: &[(Option<IdxSize>, Option<IdxSize>)],
+    ) -> Series {
+        let right_ca = self.unpack_series_matching_type(right_column).unwrap();
+
+        // take from both columns appended, the rows of the right column are offset by the
+        // length of the left column
+        let offset = self.len();
+        let mut values = self.clone();
+        values.append(right_ca);
+        let idx = opt_join_tuples
+            .iter()
+            .map(|(opt_left_idx, opt_right_idx)| match opt_left_idx {
+                Some(left_idx) => *left_idx as usize,
+                None => unsafe { offset + opt_right_idx.unsafe_unwrap() as usize },
+            });
+        // Safety:
+        // join tuples are in bounds of their relation
+        unsafe { values.take_unchecked(idx.into()) }.into_series()
+    }
+}
+
+impl ZipOuterJoinColumn for CategoricalChunked {
+    fn zip_outer_join_column(
+        &self,
+        right_column: &Series,
+        opt_join_tuples: &[(Option<IdxSize>, Option<IdxSize>)],
+    ) -> Series {
+        let right_ca = right_column.categorical().unwrap();
+        let (right_physical, mapping) = match (&self.categorical_map, &right_ca.categorical_map) {
+            // the u32 indexes mean the same categories on both sides, the keys that are only in
+            // the right column need the categories of the right mapping
+            (Some(l), Some(r)) if same_categories(self, right_ca) => {
+                let mapping = if Arc::ptr_eq(l, r) {
+                    l.clone()
+                } else {
+                    let mut mapping = (**l).clone();
+                    mapping.extend(r.iter().map(|(k, v)| (*k, v.clone())));
+                    Arc::new(mapping)
+                };
+                (right_ca.deref().clone(), Some(mapping))
+            }
+            // re-encode the right indexes in the left mapping, the categories that are only in
+            // the right mapping get new indexes
+            (Some(l), Some(r)) => {
+                let mut mapping = (**l).clone();
+                let left_idx = l
+                    .iter()
+                    .map(|(idx, cat)| (cat.as_str(), *idx))
+                    .collect::<AHashMap<_, _>>();
+                let mut next_idx = l.keys().max().map_or(0, |idx| idx + 1);
+                let right_to_left = r
+                    .iter()
+                    .map(|(r_idx, cat)| {
+                        let idx = match left_idx.get(cat.as_str()) {
+                            Some(idx) => *idx,
+                            None => {
+                                let idx = next_idx;
+                                next_idx += 1;
+                                mapping.insert(idx, cat.clone());
+                                idx
+                            }
+                        };
+                        (*r_idx, idx)
+                    })
+                    .collect::<AHashMap<_, _>>();
+                let right_physical: UInt32Chunked = right_ca
+                    .deref()
+                    .into_iter()
+                    .map(|opt_idx| opt_idx.map(|idx| right_to_left[&idx]))
+                    .collect();
+                (right_physical, Some(Arc::new(mapping)))
+            }
+            (None, r) => (right_ca.deref().clone(), r.clone()),
+            (l, None) => (right_ca.deref().clone(), l.clone()),
+        };
+        let physical = self
+            .deref()
+            .zip_outer_join_column(&right_physical.into_series(), opt_join_tuples);
+        let mut out: CategoricalChunked = physical.u32().unwrap().cast().unwrap();
+        out.categorical_map = mapping;
+        out.set_ordering(self.ordering()).into_series()
+    }
+}
 #[cfg(feature = "object")]
 impl<T> ZipOuterJoinColumn for ObjectChunked<T> {}
 
@@ -671,7 +1799,7 @@ macro_rules! impl_zip_outer_join {
             fn zip_outer_join_column(
                 &self,
                 right_column: &Series,
-                opt_join_tuples: &[(Option<u32>, Option<u32>)],
+                opt_join_tuples: &[(Option<IdxSize>, Option<IdxSize>)],
             ) -> Series {
                 let right_ca = self.unpack_series_matching_type(right_column).unwrap();
 
@@ -701,7 +1829,14 @@ impl_zip_outer_join!(Utf8Chunked);
 
 impl DataFrame {
     /// Utility method to finish a join.
-    fn finish_join(&self, mut df_left: DataFrame, mut df_right: DataFrame) -> Result<DataFrame> {
+    /// Columns of the right DataFrame that are also in the left DataFrame get the `suffix`,
+    /// or `"_right"` if `None`.
+    pub(crate) fn finish_join(
+        &self,
+        mut df_left: DataFrame,
+        mut df_right: DataFrame,
+        suffix: Option<String>,
+    ) -> Result<DataFrame> {
         let mut left_names = HashSet::with_capacity_and_hasher(df_left.width(), RandomState::new());
 
         df_left.columns.iter().for_each(|series| {
@@ -716,62 +1851,117 @@ impl DataFrame {
             }
         });
 
+        let suffix = suffix.as_deref().unwrap_or("_right");
         for name in rename_strs {
-            df_right.rename(&name, &format!("{}_right", name))?;
+            df_right.rename(&name, &format!("{}{}", name, suffix))?;
         }
 
         df_left.hstack_mut(&df_right.columns)?;
         Ok(df_left)
     }
 
-    fn create_left_df<B: Sync>(&self, join_tuples: &[(u32, B)]) -> DataFrame {
+    fn create_left_df<B: Sync>(&self, join_tuples: &[(IdxSize, B)]) -> DataFrame {
         unsafe { self.take_iter_unchecked(join_tuples.iter().map(|(left, _right)| *left as usize)) }
     }
 
     /// Generic join method. Can be used to join on multiple columns.
+    /// `args` is a [JoinType], or [JoinArgs] to also set the suffix of duplicate column names of
+    /// `other`, the validation of the keys, the merge indicator column, the strategy of the hash
+    /// table and the order of an outer join.
+    ///
+    /// With [validate](JoinArgs::validate) the join first checks that the keys are unique on the
+    /// left and/or right side, and returns an error instead of duplicating rows if they are not.
+    ///
+    /// The [strategy](JoinArgs::strategy) is used by inner and left joins on a single key column.
+    /// Other joins ignore it, except for [JoinStrategy::LowMemory] left joins, which are chunked
+    /// for any number of key columns.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    ///
+    /// // every order should match at most one customer
+    /// fn add_customers(orders: &DataFrame, customers: &DataFrame) -> Result<DataFrame> {
+    ///     let args = JoinArgs::new(JoinType::Left).with_validation(JoinValidation::ManyToOne);
+    ///     orders.join(customers, "customer_id", "id", args)
+    /// }
+    ///
+    /// fn add_currency_names(payments: &DataFrame, currencies: &DataFrame) -> Result<DataFrame> {
+    ///     let args = JoinArgs::new(JoinType::Left).with_strategy(JoinStrategy::Broadcast);
+    ///     payments.join(currencies, "currency", "code", args)
+    /// }
+    /// ```
     pub fn join<'a, J, S1: Selection<'a, J>, S2: Selection<'a, J>>(
         &self,
         other: &DataFrame,
         left_on: S1,
         right_on: S2,
-        how: JoinType,
+        args: impl Into<JoinArgs>,
     ) -> Result<DataFrame> {
+        let args = args.into();
+        if args.indicator {
+            return self.join_with_indicator(other, left_on, right_on, args);
+        }
+        let JoinArgs {
+            how,
+            suffix,
+            validate,
+            strategy,
+            maintain_order,
+            ..
+        } = args;
+        if how == JoinType::Cross {
+            return self.cross_join_impl(other, suffix);
+        }
         let selected_left = self.select_series(left_on)?;
         let selected_right = other.select_series(right_on)?;
-        assert_eq!(selected_right.len(), selected_left.len());
+        if selected_left.len() != selected_right.len() {
+            return Err(PolarsError::ShapeMisMatch(
+                "the number of left and right join keys should be equal".into(),
+            ));
+        }
+        let (selected_left, selected_right) =
+            coerce_join_keys_multiple(&selected_left, &selected_right)?;
+        validate.check(&selected_left, &selected_right)?;
+
+        if how == JoinType::Left && strategy == JoinStrategy::LowMemory {
+            return self.left_join_low_memory(
+                other,
+                &selected_left,
+                &selected_right,
+                suffix,
+                LOW_MEMORY_JOIN_CHUNK_SIZE,
+            );
+        }
 
         if selected_left.len() == 1 {
             return match how {
-                JoinType::Inner => {
-                    self.inner_join(other, selected_left[0].name(), selected_right[0].name())
-                }
-                JoinType::Left => {
-                    self.left_join(other, selected_left[0].name(), selected_right[0].name())
-                }
-                JoinType::Outer => {
-                    self.outer_join(other, selected_left[0].name(), selected_right[0].name())
-                }
+                JoinType::Inner => self.inner_join_from_series(
+                    other,
+                    &selected_left[0],
+                    &selected_right[0],
+                    suffix,
+                    strategy,
+                ),
+                JoinType::Left => self.left_join_from_series(
+                    other,
+                    &selected_left[0],
+                    &selected_right[0],
+                    suffix,
+                    strategy,
+                ),
+                JoinType::Outer => self.outer_join_from_series(
+                    other,
+                    &selected_left[0],
+                    &selected_right[0],
+                    suffix,
+                    maintain_order,
+                ),
+                JoinType::Cross => unreachable!(),
             };
         }
 
-        macro_rules! det_hash_prone_order2 {
-            ($self:expr, $other:expr) => {{
-                // The shortest relation will be used to create a hash table.
-                let left_first = $self.size_hint().0 > $other.size_hint().0;
-                let a;
-                let b;
-                if left_first {
-                    a = $self;
-                    b = $other;
-                } else {
-                    b = $self;
-                    a = $other;
-                }
-
-                (a, b, !left_first)
-            }};
-        }
-
         fn remove_selected(df: &DataFrame, selected: &[Series]) -> DataFrame {
             let mut new = None;
             for s in selected {
@@ -783,41 +1973,28 @@ impl DataFrame {
             new.unwrap()
         }
 
+        // The shortest relation will be used to create a hash table.
+        fn det_build_side<'a>(
+            left: &'a DataFrame,
+            right: &'a DataFrame,
+        ) -> (&'a DataFrame, &'a DataFrame, bool) {
+            if left.height() > right.height() {
+                (left, right, false)
+            } else {
+                (right, left, true)
+            }
+        }
+
+        let (keys_left, keys_right) = prepare_multiple_keys(&selected_left, &selected_right)?;
         match how {
             JoinType::Inner => {
-                let join_tuples = match selected_left.len() {
-                    2 => {
-                        let a = static_zip!(selected_left, 1);
-                        let b = static_zip!(selected_right, 1);
-                        let (a, b, swap) = det_hash_prone_order2!(a, b);
-                        hash_join_tuples_inner(a, b, swap)
-                    }
-                    3 => {
-                        let a = static_zip!(selected_left, 2);
-                        let b = static_zip!(selected_right, 2);
-                        let (a, b, swap) = det_hash_prone_order2!(a, b);
-                        hash_join_tuples_inner(a, b, swap)
-                    }
-                    4 => {
-                        let a = static_zip!(selected_left, 3);
-                        let b = static_zip!(selected_right, 3);
-                        let (a, b, swap) = det_hash_prone_order2!(a, b);
-                        hash_join_tuples_inner(a, b, swap)
-                    }
-                    5 => {
-                        let a = static_zip!(selected_left, 4);
-                        let b = static_zip!(selected_right, 4);
-                        let (a, b, swap) = det_hash_prone_order2!(a, b);
-                        hash_join_tuples_inner(a, b, swap)
-                    }
-                    6 => {
-                        let a = static_zip!(selected_left, 5);
-                        let b = static_zip!(selected_right, 5);
-                        let (a, b, swap) = det_hash_prone_order2!(a, b);
-                        hash_join_tuples_inner(a, b, swap)
-                    }
-                    _ => todo!(),
-                };
+                let (keys_a, keys_b, swap) = det_build_side(&keys_left, &keys_right);
+                let join_tuples = hash_join_tuples_multiple_keys_inner(keys_a, keys_b, swap);
+                check_join_tuples(
+                    join_tuples.iter().map(|(l, r)| (Some(*l), Some(*r))),
+                    self.height(),
+                    other.height(),
+                );
 
                 let (df_left, df_right) = POOL.join(
                     || self.create_left_df(&join_tuples),
@@ -828,37 +2005,15 @@ impl DataFrame {
                         )
                     },
                 );
-                self.finish_join(df_left, df_right)
+                self.finish_join(df_left, df_right, suffix)
             }
             JoinType::Left => {
-                let join_tuples = match selected_left.len() {
-                    2 => {
-                        let a = static_zip!(selected_left, 1);
-                        let b = static_zip!(selected_right, 1);
-                        hash_join_tuples_left(a, b)
-                    }
-                    3 => {
-                        let a = static_zip!(selected_left, 2);
-                        let b = static_zip!(selected_right, 2);
-                        hash_join_tuples_left(a, b)
-                    }
-                    4 => {
-                        let a = static_zip!(selected_left, 3);
-                        let b = static_zip!(selected_right, 3);
-                        hash_join_tuples_left(a, b)
-                    }
-                    5 => {
-                        let a = static_zip!(selected_left, 4);
-                        let b = static_zip!(selected_right, 4);
-                        hash_join_tuples_left(a, b)
-                    }
-                    6 => {
-                        let a = static_zip!(selected_left, 5);
-                        let b = static_zip!(selected_right, 5);
-                        hash_join_tuples_left(a, b)
-                    }
-                    _ => todo!(),
-                };
+                let join_tuples = hash_join_tuples_multiple_keys_left(&keys_left, &keys_right);
+                check_join_tuples(
+                    join_tuples.iter().map(|(l, r)| (Some(*l), *r)),
+                    self.height(),
+                    other.height(),
+                );
 
                 let (df_left, df_right) = POOL.join(
                     || self.create_left_df(&join_tuples),
@@ -871,42 +2026,20 @@ impl DataFrame {
                         )
                     },
                 );
-                self.finish_join(df_left, df_right)
+                self.finish_join(df_left, df_right, suffix)
             }
             JoinType::Outer => {
-                let opt_join_tuples = match selected_left.len() {
-                    2 => {
-                        let a = static_zip!(selected_left, 1);
-                        let b = static_zip!(selected_right, 1);
-                        let (a, b, swap) = det_hash_prone_order2!(a, b);
-                        hash_join_tuples_outer(a, b, swap)
-                    }
-                    3 => {
-                        let a = static_zip!(selected_left, 2);
-                        let b = static_zip!(selected_right, 2);
-                        let (a, b, swap) = det_hash_prone_order2!(a, b);
-                        hash_join_tuples_outer(a, b, swap)
-                    }
-                    4 => {
-                        let a = static_zip!(selected_left, 3);
-                        let b = static_zip!(selected_right, 3);
-                        let (a, b, swap) = det_hash_prone_order2!(a, b);
-                        hash_join_tuples_outer(a, b, swap)
-                    }
-                    5 => {
-                        let a = static_zip!(selected_left, 4);
-                        let b = static_zip!(selected_right, 4);
-                        let (a, b, swap) = det_hash_prone_order2!(a, b);
-                        hash_join_tuples_outer(a, b, swap)
-                    }
-                    6 => {
-                        let a = static_zip!(selected_left, 5);
-                        let b = static_zip!(selected_right, 5);
-                        let (a, b, swap) = det_hash_prone_order2!(a, b);
-                        hash_join_tuples_outer(a, b, swap)
-                    }
-                    _ => todo!(),
-                };
+                let (keys_a, keys_b, swap) = det_build_side(&keys_left, &keys_right);
+                let mut opt_join_tuples =
+                    hash_join_tuples_multiple_keys_outer(keys_a, keys_b, swap);
+                if maintain_order {
+                    sort_outer_join_tuples(&mut opt_join_tuples);
+                }
+                check_join_tuples(
+                    opt_join_tuples.iter().copied(),
+                    self.height(),
+                    other.height(),
+                );
 
                 // Take the left and right dataframes by join tuples
                 let (mut df_left, df_right) = POOL.join(
@@ -930,11 +2063,301 @@ impl DataFrame {
                     s.rename(s_left.name());
                     df_left.hstack_mut(&[s])?;
                 }
-                self.finish_join(df_left, df_right)
+                self.finish_join(df_left, df_right, suffix)
+            }
+            JoinType::Cross => unreachable!(),
+        }
+    }
+
+    /// Join with marker columns on both sides. A marker is null in the output if its side was
+    /// not matched, from which the indicator column is computed.
+    fn join_with_indicator<'a, J, S1: Selection<'a, J>, S2: Selection<'a, J>>(
+        &self,
+        other: &DataFrame,
+        left_on: S1,
+        right_on: S2,
+        args: JoinArgs,
+    ) -> Result<DataFrame> {
+        let mut left = self.clone();
+        left.add_column(BooleanChunked::full(LEFT_MARKER_NAME, true, self.height()))?;
+        let mut right = other.clone();
+        right.add_column(BooleanChunked::full(
+            RIGHT_MARKER_NAME,
+            true,
+            other.height(),
+        ))?;
+
+        let mut out = left.join(&right, left_on, right_on, args.with_indicator(false))?;
+        let left_marker = out.drop_in_place(LEFT_MARKER_NAME)?.is_null();
+        let right_marker = out.drop_in_place(RIGHT_MARKER_NAME)?.is_null();
+
+        let indicator: Utf8Chunked = left_marker
+            .into_no_null_iter()
+            .zip(right_marker.into_no_null_iter())
+            .map(|(left_null, right_null)| match (left_null, right_null) {
+                (false, true) => "left_only",
+                (true, false) => "right_only",
+                _ => "both",
+            })
+            .collect();
+        let mut indicator = indicator
+            .into_series()
+            .cast_with_datatype(&DataType::Categorical(CategoricalOrdering::Physical))?;
+        indicator.rename(JOIN_INDICATOR_NAME);
+        out.add_column(indicator)?;
+        Ok(out)
+    }
+
+    /// Left join that probes `chunk_size` rows of `self` at a time. The output of every chunk is
+    /// gathered and appended before the next chunk is probed, so only the join tuples of a single
+    /// chunk are materialized. The hash table of `other` is built once.
+    ///
+    /// A single key of a primitive, utf8 or boolean type is probed in a typed hash table. Other
+    /// keys are compared row by row.
+    fn left_join_low_memory(
+        &self,
+        other: &DataFrame,
+        selected_left: &[Series],
+        selected_right: &[Series],
+        suffix: Option<String>,
+        chunk_size: usize,
+    ) -> Result<DataFrame> {
+        let mut right = other.clone();
+        for s in selected_right {
+            right = right.drop(s.name())?;
+        }
+
+        if selected_left.len() == 1 && selected_left[0].dtype() == selected_right[0].dtype() {
+            let (a, b) = (&selected_left[0], &selected_right[0]);
+            let physical = match a.dtype() {
+                DataType::Categorical(_) if same_categories(a.categorical()?, b.categorical()?) => {
+                    Some(DataType::UInt32)
+                }
+                DataType::Categorical(_) => Some(DataType::Utf8),
+                dtype => temporal_to_physical(dtype),
+            };
+            let (a, b) = match physical {
+                Some(dtype) => (a.cast_with_datatype(&dtype)?, b.cast_with_datatype(&dtype)?),
+                None => (a.clone(), b.clone()),
+            };
+
+            macro_rules! probe_chunks {
+                ($a:expr, $b:expr) => {
+                    probe_chunks!($a, $b, |opt_v| opt_v)
+                };
+                ($a:expr, $b:expr, $to_key:expr) => {{
+                    let (a, b) = ($a, $b);
+                    let hash_tbl = prepare_hashed_relation(b.into_iter().map($to_key));
+                    record_hash_tables(std::slice::from_ref(&hash_tbl), false);
+                    return self.left_join_in_chunks(&right, suffix, chunk_size, |offset, len| {
+                        let chunk = a.slice(offset, len)?;
+                        Ok(probe_left(&hash_tbl, chunk.into_iter().map($to_key)))
+                    });
+                }};
+            }
+
+            match a.dtype() {
+                DataType::Utf8 => probe_chunks!(a.utf8()?, b.utf8()?),
+                DataType::Boolean => probe_chunks!(a.bool()?, b.bool()?),
+                DataType::UInt8 => probe_chunks!(a.u8()?, b.u8()?),
+                DataType::UInt16 => probe_chunks!(a.u16()?, b.u16()?),
+                DataType::UInt32 => probe_chunks!(a.u32()?, b.u32()?),
+                DataType::UInt64 => probe_chunks!(a.u64()?, b.u64()?),
+                DataType::Int32 => probe_chunks!(a.i32()?, b.i32()?),
+                DataType::Int64 => probe_chunks!(a.i64()?, b.i64()?),
+                DataType::Float32 => probe_chunks!(a.f32()?, b.f32()?, |opt_v: Option<f32>| {
+                    opt_v.map(|v| v.to_bits())
+                }),
+                DataType::Float64 => probe_chunks!(a.f64()?, b.f64()?, |opt_v: Option<f64>| {
+                    opt_v.map(|v| v.to_bits())
+                }),
+                _ => {}
+            }
+        }
+
+        let (keys_left, keys_right) = prepare_multiple_keys(selected_left, selected_right)?;
+        let (hash_tbl, random_state) = build_multiple_keys_table(&keys_right);
+        record_hash_tables(std::slice::from_ref(&hash_tbl), false);
+        self.left_join_in_chunks(&right, suffix, chunk_size, |offset, len| {
+            let keys_chunk = keys_left.slice(offset, len)?;
+            let keys_eq = KeysEq::new(&keys_chunk, &keys_right);
+            let (hashes, _) = df_rows_to_hashes(&keys_chunk, Some(random_state.clone()));
+            let mut join_tuples = Vec::with_capacity(len);
+            for (idx_a, h) in hashes.into_no_null_iter().enumerate() {
+                let idx_a = idx_a as IdxSize;
+                match probe_multiple_keys(&hash_tbl, &keys_eq, idx_a, h) {
+                    Some((_, indexes_b)) => {
+                        join_tuples.extend(indexes_b.iter().map(|&idx_b| (idx_a, Some(idx_b))))
+                    }
+                    None => join_tuples.push((idx_a, None)),
+                }
+            }
+            Ok(join_tuples)
+        })
+    }
+
+    /// Left join `self` with the columns of `right` in chunks of `chunk_size` rows.
+    /// `probe_chunk` is called with the offset and length of a chunk and returns its join tuples,
+    /// with the left indexes relative to the chunk.
+    fn left_join_in_chunks<F>(
+        &self,
+        right: &DataFrame,
+        suffix: Option<String>,
+        chunk_size: usize,
+        mut probe_chunk: F,
+    ) -> Result<DataFrame>
+    where
+        F: FnMut(usize, usize) -> Result<Vec<(IdxSize, Option<IdxSize>)>>,
+    {
+        let height = self.height();
+        let mut out: Option<DataFrame> = None;
+        let mut offset = 0;
+        // always join one chunk, so that an empty left relation still has the joined schema
+        loop {
+            let len = std::cmp::min(chunk_size, height - offset);
+            let join_tuples = probe_chunk(offset, len)?;
+            check_join_tuples(
+                join_tuples
+                    .iter()
+                    .map(|(l, r)| (Some(*l + offset as IdxSize), *r)),
+                height,
+                right.height(),
+            );
+
+            let chunk = self.slice(offset, len)?;
+            let (df_left, df_right) = POOL.join(
+                || chunk.create_left_df(&join_tuples),
+                || unsafe {
+                    right.take_opt_iter_unchecked(
+                        join_tuples
+                            .iter()
+                            .map(|(_left, right)| right.map(|i| i as usize)),
+                    )
+                },
+            );
+            let joined = chunk.finish_join(df_left, df_right, suffix.clone())?;
+            match out.as_mut() {
+                Some(out) => {
+                    out.vstack_mut(&joined)?;
+                }
+                None => out = Some(joined),
+            }
+
+            offset += len;
+            if offset >= height {
+                return Ok(out.unwrap());
             }
         }
     }
 
+    /// Get the rows of this DataFrame whose keys have no match in `other`, in their original
+    /// order. Like in the other joins, null keys match each other.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    ///
+    /// fn unpaid_orders(orders: &DataFrame, payments: &DataFrame) -> Result<DataFrame> {
+    ///     orders.anti_join(payments, "order_id", "order_id")
+    /// }
+    /// ```
+    pub fn anti_join<'a, J, S1: Selection<'a, J>, S2: Selection<'a, J>>(
+        &self,
+        other: &DataFrame,
+        left_on: S1,
+        right_on: S2,
+    ) -> Result<DataFrame> {
+        let selected_left = self.select_series(left_on)?;
+        let selected_right = other.select_series(right_on)?;
+        if selected_left.len() != selected_right.len() {
+            return Err(PolarsError::ShapeMisMatch(
+                "the number of left and right join keys should be equal".into(),
+            ));
+        }
+        let (selected_left, selected_right) =
+            coerce_join_keys_multiple(&selected_left, &selected_right)?;
+
+        let (keys_left, keys_right) = prepare_multiple_keys(&selected_left, &selected_right)?;
+        let (hash_tbl, random_state) = build_multiple_keys_table(&keys_right);
+        let keys_eq = KeysEq::new(&keys_left, &keys_right);
+        let (hashes, _) = df_rows_to_hashes(&keys_left, Some(random_state));
+        let idx = hashes
+            .into_no_null_iter()
+            .enumerate()
+            .filter(|(idx_a, h)| {
+                probe_multiple_keys(&hash_tbl, &keys_eq, *idx_a as IdxSize, *h).is_none()
+            })
+            .map(|(idx_a, _)| idx_a)
+            .collect::<Vec<_>>();
+        // Safety:
+        // the indexes are in bounds of self.
+        Ok(unsafe { self.take_iter_unchecked(idx.into_iter()) })
+    }
+
+    /// [Join](DataFrame::join) and return the [statistics](JoinStatistics) of the join: the sizes
+    /// of the relations, the hash table, the match rates and the number of output rows. They are
+    /// counted by the join kernels while joining. Useful to find out why a join returns many more
+    /// or fewer rows than expected, or why it is slow.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    ///
+    /// fn add_customers(orders: &DataFrame, customers: &DataFrame) -> Result<DataFrame> {
+    ///     let (out, stats) =
+    ///         orders.join_with_statistics(customers, "customer_id", "id", JoinType::Left)?;
+    ///     println!("{}", stats);
+    ///     Ok(out)
+    /// }
+    /// ```
+    pub fn join_with_statistics<'a, J, S1: Selection<'a, J>, S2: Selection<'a, J>>(
+        &self,
+        other: &DataFrame,
+        left_on: S1,
+        right_on: S2,
+        args: impl Into<JoinArgs>,
+    ) -> Result<(DataFrame, JoinStatistics)> {
+        let args = args.into();
+        let how = args.how;
+        let outer_counters = JOIN_COUNTERS.with(|c| c.replace(Some(JoinCounters::default())));
+        let out = self.join(other, left_on, right_on, args);
+        let counters = JOIN_COUNTERS
+            .with(|c| c.replace(outer_counters))
+            .unwrap_or_default();
+        let out = out?;
+
+        let (left_rows, right_rows) = (self.height(), other.height());
+        let (left_matched_rows, right_matched_rows) = if how == JoinType::Cross {
+            (
+                if right_rows > 0 { left_rows } else { 0 },
+                if left_rows > 0 { right_rows } else { 0 },
+            )
+        } else {
+            (
+                counters.left_matched.iter().filter(|m| **m).count(),
+                counters.right_matched.iter().filter(|m| **m).count(),
+            )
+        };
+        let stats = JoinStatistics {
+            left_rows,
+            right_rows,
+            build_side: counters.build_side,
+            build_side_rows: match counters.build_side {
+                Some(JoinSide::Left) => left_rows,
+                Some(JoinSide::Right) => right_rows,
+                None => 0,
+            },
+            build_side_keys: counters.build_side_keys,
+            hash_collisions: counters.hash_collisions,
+            left_matched_rows,
+            right_matched_rows,
+            output_rows: out.height(),
+        };
+        Ok((out, stats))
+    }
+
     /// Perform an inner join on two DataFrames.
     ///
     /// # Example
@@ -951,9 +2374,8 @@ impl DataFrame {
         left_on: &str,
         right_on: &str,
     ) -> Result<DataFrame> {
-        let s_left = self.column(left_on)?;
-        let s_right = other.column(right_on)?;
-        self.inner_join_from_series(other, s_left, s_right)
+        let (s_left, s_right) = coerce_join_keys(self.column(left_on)?, other.column(right_on)?)?;
+        self.inner_join_from_series(other, &s_left, &s_right, None, JoinStrategy::Auto)
     }
 
     pub(crate) fn inner_join_from_series(
@@ -961,8 +2383,16 @@ impl DataFrame {
         other: &DataFrame,
         s_left: &Series,
         s_right: &Series,
+        suffix: Option<String>,
+        strategy: JoinStrategy,
     ) -> Result<DataFrame> {
-        let join_tuples = s_left.hash_join_inner(s_right);
+        let join_tuples = s_left.hash_join_inner(s_right, strategy);
+
+        check_join_tuples(
+            join_tuples.iter().map(|(l, r)| (Some(*l), Some(*r))),
+            self.height(),
+            other.height(),
+        );
 
         let (df_left, df_right) = POOL.join(
             || self.create_left_df(&join_tuples),
@@ -973,7 +2403,7 @@ impl DataFrame {
                     .take_iter_unchecked(join_tuples.iter().map(|(_left, right)| *right as usize))
             },
         );
-        self.finish_join(df_left, df_right)
+        self.finish_join(df_left, df_right, suffix)
     }
 
     /// Perform a left join on two DataFrames
@@ -986,9 +2416,8 @@ impl DataFrame {
     /// }
     /// ```
     pub fn left_join(&self, other: &DataFrame, left_on: &str, right_on: &str) -> Result<DataFrame> {
-        let s_left = self.column(left_on)?;
-        let s_right = other.column(right_on)?;
-        self.left_join_from_series(other, s_left, s_right)
+        let (s_left, s_right) = coerce_join_keys(self.column(left_on)?, other.column(right_on)?)?;
+        self.left_join_from_series(other, &s_left, &s_right, None, JoinStrategy::Auto)
     }
 
     pub(crate) fn left_join_from_series(
@@ -996,8 +2425,16 @@ impl DataFrame {
         other: &DataFrame,
         s_left: &Series,
         s_right: &Series,
+        suffix: Option<String>,
+        strategy: JoinStrategy,
     ) -> Result<DataFrame> {
-        let opt_join_tuples = s_left.hash_join_left(s_right);
+        let opt_join_tuples = s_left.hash_join_left(s_right, strategy);
+
+        check_join_tuples(
+            opt_join_tuples.iter().map(|(l, r)| (Some(*l), *r)),
+            self.height(),
+            other.height(),
+        );
 
         let (df_left, df_right) = POOL.join(
             || self.create_left_df(&opt_join_tuples),
@@ -1009,7 +2446,7 @@ impl DataFrame {
                 )
             },
         );
-        self.finish_join(df_left, df_right)
+        self.finish_join(df_left, df_right, suffix)
     }
 
     /// Perform an outer join on two DataFrames
@@ -1027,18 +2464,28 @@ impl DataFrame {
         left_on: &str,
         right_on: &str,
     ) -> Result<DataFrame> {
-        let s_left = self.column(left_on)?;
-        let s_right = other.column(right_on)?;
-        self.outer_join_from_series(other, s_left, s_right)
+        let (s_left, s_right) = coerce_join_keys(self.column(left_on)?, other.column(right_on)?)?;
+        self.outer_join_from_series(other, &s_left, &s_right, None, false)
     }
     pub(crate) fn outer_join_from_series(
         &self,
         other: &DataFrame,
         s_left: &Series,
         s_right: &Series,
+        suffix: Option<String>,
+        maintain_order: bool,
     ) -> Result<DataFrame> {
         // Get the indexes of the joined relations
-        let opt_join_tuples = s_left.hash_join_outer(s_right);
+        let mut opt_join_tuples = s_left.hash_join_outer(s_right);
+        if maintain_order {
+            sort_outer_join_tuples(&mut opt_join_tuples);
+        }
+
+        check_join_tuples(
+            opt_join_tuples.iter().copied(),
+            self.height(),
+            other.height(),
+        );
 
         // Take the left and right dataframes by join tuples
         let (mut df_left, df_right) = POOL.join(
@@ -1060,12 +2507,13 @@ impl DataFrame {
         let mut s = s_left.zip_outer_join_column(s_right, &opt_join_tuples);
         s.rename(s_left.name());
         df_left.hstack_mut(&[s])?;
-        self.finish_join(df_left, df_right)
+        self.finish_join(df_left, df_right, suffix)
     }
 }
 
 #[cfg(test)]
 mod test {
+    use super::{build_on_right, HashJoin, ZipOuterJoinColumn};
     use crate::prelude::*;
     use crate::toggle_string_cache;
 
@@ -1153,6 +2601,94 @@ mod test {
         assert_eq!(joined.column("days").unwrap().sum::<i32>(), Some(7));
     }
 
+    #[test]
+    fn test_radix_partitioned_join() {
+        let a = UInt32Chunked::new_from_iter("a", (0..5000u32).map(|i| (i * 7919) % 1500));
+        let b = UInt32Chunked::new_from_iter("b", (0..3000u32).map(|i| (i * 104729) % 2000));
+
+        for (a, b) in [(&a, &b), (&b, &a)].iter() {
+            // the threaded join keeps the order of the probe relation
+            let threaded = a.hash_join_inner(b, JoinStrategy::Threaded);
+            assert!(!threaded.is_empty());
+            assert_eq!(threaded, a.hash_join_inner(b, JoinStrategy::Broadcast));
+
+            let threaded = a.hash_join_left(b, JoinStrategy::Threaded);
+            let broadcast = a.hash_join_left(b, JoinStrategy::Broadcast);
+            assert_eq!(threaded, broadcast);
+        }
+    }
+
+    #[test]
+    fn test_cardinality_build_side() {
+        // short relations and relations of very different lengths build on the shortest
+        assert!(build_on_right(10, 5, || unreachable!()));
+        assert!(build_on_right(1 << 20, 1 << 15, || unreachable!()));
+        assert!(!build_on_right(1 << 16, 1 << 15, || (10, 1 << 15)));
+        assert!(build_on_right(1 << 15, 1 << 16, || (1 << 15, 10)));
+
+        // the longer relation has far fewer distinct keys
+        let a = UInt32Chunked::new_from_iter("a", (0..40_000u32).map(|i| (i * 7919) % 10));
+        let b = UInt32Chunked::new_from_iter("b", (0..20_000u32).map(|i| (i * 104729) % 20_000));
+        for (a, b) in [(&a, &b), (&b, &a)].iter() {
+            let threaded = a.hash_join_inner(b, JoinStrategy::Threaded);
+            assert_eq!(threaded.len(), 40_000);
+            assert_eq!(threaded, a.hash_join_inner(b, JoinStrategy::Broadcast));
+        }
+    }
+
+    #[test]
+    fn test_outer_join_zip_keys() -> Result<()> {
+        let df_a = df! {
+            "key" => &[1.0, 2.5],
+            "a" => &[1, 2]
+        }?;
+        let df_b = df! {
+            "key" => &[2.5, 4.0],
+            "b" => &[3, 4]
+        }?;
+        let out = df_a.outer_join(&df_b, "key", "key")?;
+        let mut keys = Vec::from(out.column("key")?.f64()?);
+        keys.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(keys, &[Some(1.0), Some(2.5), Some(4.0)]);
+
+        toggle_string_cache(true);
+        let mut df_a = df_a;
+        df_a.replace("key", Series::new("key", &["x", "y"]))?;
+        df_a.may_apply("key", |s| {
+            s.cast_with_datatype(&DataType::Categorical(CategoricalOrdering::Physical))
+        })?;
+        let mut df_b = df_b;
+        df_b.replace("key", Series::new("key", &["y", "z"]))?;
+        df_b.may_apply("key", |s| {
+            s.cast_with_datatype(&DataType::Categorical(CategoricalOrdering::Physical))
+        })?;
+        let out = df_a.outer_join(&df_b, "key", "key")?;
+        let out = out.column("key")?.cast::<Utf8Type>()?;
+        let mut keys = Vec::from(out.utf8()?);
+        keys.sort();
+        assert_eq!(keys, &[Some("x"), Some("y"), Some("z")]);
+
+        // without the string cache the index 0 means "x" on the left and "z" on the right
+        let cat = DataType::Categorical(CategoricalOrdering::Physical);
+        let left = Series::new("a", &["x", "y"]).cast_with_datatype(&cat)?;
+        let right = Series::new("b", &["z", "y"]).cast_with_datatype(&cat)?;
+        let out = left.categorical()?.zip_outer_join_column(
+            &right,
+            &[(Some(0), None), (Some(1), Some(1)), (None, Some(0))],
+        );
+        let out = out.cast::<Utf8Type>()?;
+        assert_eq!(Vec::from(out.utf8()?), &[Some("x"), Some("y"), Some("z")]);
+
+        let left = Series::new("a", &[Series::new("", &[1, 2]), Series::new("", &[3])]);
+        let right = Series::new("b", &[Series::new("", &[4])]);
+        let out = left
+            .list()?
+            .zip_outer_join_column(&right, &[(Some(1), None), (None, Some(0))]);
+        let out = out.explode()?;
+        assert_eq!(Vec::from(out.i32()?), &[Some(3), Some(4)]);
+        Ok(())
+    }
+
     #[test]
     fn test_join_with_nulls() {
         let dts = &[20, 21, 22, 23, 24, 25, 27, 28];
@@ -1178,6 +2714,32 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_join_null_key() -> Result<()> {
+        let df = df! {
+            "key" => &[1, 2, 3],
+            "val" => &["a", "b", "c"]
+        }?;
+        let df_null = DataFrame::new(vec![
+            Series::full_null("key", 2, &DataType::Null)?,
+            Series::new("val2", &[1.0, 2.0]),
+        ])?;
+
+        let out = df.inner_join(&df_null, "key", "key")?;
+        assert_eq!(out.height(), 0);
+
+        let out = df.left_join(&df_null, "key", "key")?;
+        assert_eq!(out.height(), 3);
+        assert_eq!(out.column("val2")?.null_count(), 3);
+
+        let out = df_null.outer_join(&df, "key", "key")?;
+        assert_eq!(out.height(), 5);
+        assert_eq!(out.column("key")?.dtype(), &DataType::Int32);
+        assert_eq!(out.column("key")?.null_count(), 2);
+        assert_eq!(out.column("key")?.sum::<i32>(), Some(6));
+        Ok(())
+    }
+
     fn get_dfs() -> (DataFrame, DataFrame) {
         let df_a = df! {
             "a" => &[1, 2, 1, 1],
@@ -1262,16 +2824,420 @@ mod test {
             .series_equal_missing(joined_outer.column("ham").unwrap()));
     }
 
+    #[test]
+    fn test_join_many_keys() -> Result<()> {
+        let keys = ["k0", "k1", "k2", "k3", "k4", "k5", "k6", "k7"];
+        let key_columns = |values: &[Option<i32>]| {
+            keys.iter()
+                .map(|name| Series::new(name, values))
+                .collect::<Vec<_>>()
+        };
+
+        let mut columns = key_columns(&[Some(1), Some(2), None, Some(4)]);
+        columns.push(Series::new("left", &["a", "b", "c", "d"]));
+        let df_a = DataFrame::new(columns)?;
+        let mut columns = key_columns(&[None, Some(1), Some(1)]);
+        // the last row only differs from the first row of `df_a` in the last key
+        columns[7] = Series::new("k7", &[None, Some(1), Some(3)]);
+        columns.push(Series::new("right", &["x", "y", "z"]));
+        let df_b = DataFrame::new(columns)?;
+
+        let out = df_a.join(&df_b, &keys, &keys, JoinType::Inner)?;
+        assert_eq!(out.shape(), (2, 10));
+        assert_eq!(
+            Vec::from(out.column("left")?.utf8()?),
+            &[Some("a"), Some("c")]
+        );
+        assert_eq!(
+            Vec::from(out.column("right")?.utf8()?),
+            &[Some("y"), Some("x")]
+        );
+
+        let out = df_a.join(&df_b, &keys, &keys, JoinType::Left)?;
+        assert_eq!(
+            Vec::from(out.column("right")?.utf8()?),
+            &[Some("y"), None, Some("x"), None]
+        );
+
+        let out = df_a.join(&df_b, &keys, &keys, JoinType::Outer)?;
+        assert_eq!(out.height(), 5);
+        assert_eq!(out.column("left")?.null_count(), 1);
+        assert_eq!(out.column("right")?.null_count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_join() -> Result<()> {
+        let df_a = df![
+            "key" => &[5, 1, 2, 4, 1],
+            "a" => &[4, 0, 2, 3, 1]
+        ]?;
+        let df_b = df![
+            "key" => &[2, 5, 3, 1, 2],
+            "b" => &[1, 4, 3, 0, 2]
+        ]?;
+        let sorted_a = df_a.sort("key", false)?;
+        let sorted_b = df_b.sort("key", false)?;
+        assert_eq!(sorted_a.column("key")?.sorted_flag(), IsSorted::Ascending);
+
+        let out = sorted_a.inner_join(&sorted_b, "key", "key")?;
+        assert_eq!(
+            Vec::from(out.column("a")?.i32()?),
+            &[Some(0), Some(1), Some(2), Some(2), Some(4)]
+        );
+        assert_eq!(
+            Vec::from(out.column("b")?.i32()?),
+            &[Some(0), Some(0), Some(1), Some(2), Some(4)]
+        );
+        // same rows as the hash join
+        let hashed = df_a.inner_join(&df_b, "key", "key")?.sort("b", false)?;
+        assert_eq!(
+            Vec::from(out.sort("b", false)?.column("key")?.i32()?),
+            Vec::from(hashed.column("key")?.i32()?)
+        );
+
+        let out = sorted_a.left_join(&sorted_b, "key", "key")?;
+        assert_eq!(
+            Vec::from(out.column("b")?.i32()?),
+            &[Some(0), Some(0), Some(1), Some(2), None, Some(4)]
+        );
+
+        let sorted_a = df_a.sort("key", true)?;
+        let sorted_b = df_b.sort("key", true)?;
+        let out = sorted_a.inner_join(&sorted_b, "key", "key")?;
+        assert_eq!(
+            Vec::from(out.column("a")?.i32()?),
+            &[Some(4), Some(2), Some(2), Some(0), Some(1)]
+        );
+        assert_eq!(
+            Vec::from(out.column("b")?.i32()?),
+            &[Some(4), Some(1), Some(2), Some(0), Some(0)]
+        );
+
+        // different directions fall back to the hash join
+        let sorted_b = df_b.sort("key", false)?;
+        let out = sorted_a.inner_join(&sorted_b, "key", "key")?;
+        assert_eq!(out.height(), 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_join_strategy() -> Result<()> {
+        let df_a = df![
+            "int" => [Some(1), Some(2), None, Some(3), Some(2)],
+            "float" => [Some(1.0), Some(2.0), None, Some(3.0), Some(2.0)],
+            "str" => [Some("a"), Some("b"), None, Some("c"), Some("b")],
+            "a" => [0, 1, 2, 3, 4]
+        ]?;
+        let df_b = df![
+            "int" => [Some(2), None, Some(1)],
+            "float" => [Some(2.0), None, Some(1.0)],
+            "str" => [Some("b"), None, Some("a")],
+            "b" => [0, 1, 2]
+        ]?;
+
+        for key in &["int", "float", "str"] {
+            for how in &[JoinType::Inner, JoinType::Left] {
+                let join = |strategy| {
+                    df_a.select(&[*key, "a"])?.join(
+                        &df_b.select(&[*key, "b"])?,
+                        *key,
+                        *key,
+                        JoinArgs::new(*how).with_strategy(strategy),
+                    )
+                };
+                let threaded = join(JoinStrategy::Threaded)?;
+                let broadcast = join(JoinStrategy::Broadcast)?;
+                assert!(broadcast.frame_equal_missing(&threaded));
+                assert!(join(JoinStrategy::Auto)?.frame_equal_missing(&threaded));
+                assert!(join(JoinStrategy::LowMemory)?.frame_equal_missing(&threaded));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_left_join_low_memory() -> Result<()> {
+        let df_a = df![
+            "k1" => [Some(1), Some(2), None, Some(3), Some(2), Some(4), Some(1)],
+            "k2" => ["a", "b", "c", "c", "b", "d", "a"],
+            "v" => [0, 1, 2, 3, 4, 5, 6]
+        ]?;
+        let df_b = df![
+            "k1" => [Some(2), None, Some(1), Some(2)],
+            "k2" => ["b", "c", "a", "b"],
+            "v" => [10, 11, 12, 13]
+        ]?;
+        let selected_left = df_a.select_series(&["k1", "k2"])?;
+        let selected_right = df_b.select_series(&["k1", "k2"])?;
+        let expected =
+            df_a.left_join_low_memory(&df_b, &selected_left, &selected_right, None, df_a.height())?;
+        let expected_multiple = df_a.join(&df_b, &["k1", "k2"], &["k1", "k2"], JoinType::Left)?;
+        assert!(expected.frame_equal_missing(&expected_multiple));
+        assert_eq!(expected.height(), 9);
+
+        for chunk_size in 1..5 {
+            let out = df_a.left_join_low_memory(
+                &df_b,
+                &selected_left,
+                &selected_right,
+                None,
+                chunk_size,
+            )?;
+            assert!(out.frame_equal_missing(&expected));
+        }
+
+        let empty = df_a.slice(0, 0)?;
+        let selected_empty = empty.select_series(&["k1", "k2"])?;
+        let out = empty.left_join_low_memory(&df_b, &selected_empty, &selected_right, None, 2)?;
+        assert_eq!(out.shape(), (0, expected.width()));
+
+        // a single key is probed in a typed hash table
+        let with_float_key = |df: &DataFrame| -> Result<DataFrame> {
+            let mut key = df.column("k1")?.cast::<Float64Type>()?;
+            key.rename("k3");
+            let mut df = df.clone();
+            df.add_column(key)?;
+            Ok(df)
+        };
+        let (df_a, df_b) = (with_float_key(&df_a)?, with_float_key(&df_b)?);
+        for key in &["k1", "k2", "k3"] {
+            let expected = df_a.left_join(&df_b, key, key)?;
+            let selected_left = df_a.select_series(*key)?;
+            let selected_right = df_b.select_series(*key)?;
+            for chunk_size in 1..5 {
+                let out = df_a.left_join_low_memory(
+                    &df_b,
+                    &selected_left,
+                    &selected_right,
+                    None,
+                    chunk_size,
+                )?;
+                assert!(out.frame_equal_missing(&expected));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_anti_join() -> Result<()> {
+        let df_a = df![
+            "k" => [Some(1), Some(2), None, Some(3), Some(1)],
+            "v" => [0, 1, 2, 3, 4]
+        ]?;
+        let df_b = df!["key" => [Some(1), None, Some(5), Some(1)]]?;
+        let out = df_a.anti_join(&df_b, "k", "key")?;
+        assert_eq!(Vec::from(out.column("v")?.i32()?), &[Some(1), Some(3)]);
+
+        let empty = df_b.slice(0, 0)?;
+        assert!(df_a
+            .anti_join(&empty, "k", "key")?
+            .frame_equal_missing(&df_a));
+        assert!(df_a.anti_join(&df_b, &["k", "v"], &["key"]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_outer_join_maintain_order() -> Result<()> {
+        let df_a = df![
+            "k" => [3, 1, 2, 5, 1],
+            "k2" => ["a", "a", "a", "a", "a"],
+            "a" => [0, 1, 2, 3, 4]
+        ]?;
+        let df_b = df![
+            "k" => [2, 4, 3, 6, 1],
+            "k2" => ["a", "a", "a", "a", "a"],
+            "b" => [0, 1, 2, 3, 4]
+        ]?;
+        for keys in &[vec!["k"], vec!["k", "k2"]] {
+            let out = df_a.join(
+                &df_b,
+                keys,
+                keys,
+                JoinArgs::new(JoinType::Outer).with_maintain_order(true),
+            )?;
+            assert_eq!(
+                Vec::from(out.column("k")?.i32()?),
+                &[
+                    Some(3),
+                    Some(1),
+                    Some(2),
+                    Some(5),
+                    Some(1),
+                    Some(4),
+                    Some(6)
+                ]
+            );
+            assert_eq!(
+                Vec::from(out.column("b")?.i32()?),
+                &[Some(2), Some(4), Some(0), None, Some(4), Some(1), Some(3)]
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_join_validation() -> Result<()> {
+        let orders = df![
+            "customer" => &[1, 2, 1],
+            "amount" => &[10, 20, 30]
+        ]?;
+        let customers = df![
+            "id" => &[1, 2, 3],
+            "name" => &["a", "b", "c"]
+        ]?;
+        let out = orders.join(
+            &customers,
+            "customer",
+            "id",
+            JoinArgs::new(JoinType::Left).with_validation(JoinValidation::ManyToOne),
+        )?;
+        assert_eq!(out.height(), 3);
+
+        // the customers of the orders are not unique
+        let err = orders
+            .join(
+                &customers,
+                "customer",
+                "id",
+                JoinArgs::new(JoinType::Inner).with_validation(JoinValidation::OneToOne),
+            )
+            .unwrap_err();
+        assert!(matches!(err, PolarsError::Duplicate(_)));
+        assert!(err.to_string().contains("left DataFrame"));
+        assert!(err.to_string().contains("one-to-one"));
+        assert!(customers
+            .join(
+                &orders,
+                "id",
+                "customer",
+                JoinArgs::new(JoinType::Left).with_validation(JoinValidation::ManyToOne)
+            )
+            .is_err());
+
+        // the combination of the keys is unique
+        let customers = df![
+            "id" => &[1, 1, 2],
+            "region" => &["x", "y", "x"]
+        ]?;
+        let orders = orders.with_column(Series::new("region", &["x", "x", "y"]))?;
+        let out = orders.join(
+            &customers,
+            &["customer", "region"],
+            &["id", "region"],
+            JoinArgs::new(JoinType::Left).with_validation(JoinValidation::ManyToOne),
+        )?;
+        assert_eq!(out.height(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_join_suffix() {
+        let (df_a, df_b) = get_dfs();
+        let df_b = df_b.with_column(Series::new("c", &[4, 5, 6])).unwrap();
+        let out = df_a
+            .join(
+                &df_b,
+                "b",
+                "bar",
+                JoinArgs::new(JoinType::Inner).with_suffix(Some("_other".into())),
+            )
+            .unwrap();
+        assert!(out.column("c_other").is_ok());
+        assert!(out.column("c_right").is_err());
+    }
+
+    #[test]
+    fn test_join_indicator() -> Result<()> {
+        let left = df! {
+            "key" => &[1, 2, 3],
+            "a" => &["x", "y", "z"]
+        }?;
+        let right = df! {
+            "key" => &[2, 3, 4],
+            "b" => &[true, false, true]
+        }?;
+        let indicator = |how| -> Result<Vec<Option<String>>> {
+            let mut out = left.join(
+                &right,
+                "key",
+                "key",
+                JoinArgs::new(how).with_indicator(true),
+            )?;
+            out.sort_in_place("key", false)?;
+            let s = out.column(JOIN_INDICATOR_NAME)?;
+            assert_eq!(
+                s.dtype(),
+                &DataType::Categorical(CategoricalOrdering::Physical)
+            );
+            Ok(s.cast::<Utf8Type>()?
+                .utf8()?
+                .into_iter()
+                .map(|opt_v| opt_v.map(|v| v.to_string()))
+                .collect())
+        };
+        let strs = |v: &[&str]| v.iter().map(|v| Some(v.to_string())).collect::<Vec<_>>();
+
+        assert_eq!(indicator(JoinType::Inner)?, strs(&["both", "both"]));
+        assert_eq!(
+            indicator(JoinType::Left)?,
+            strs(&["left_only", "both", "both"])
+        );
+        assert_eq!(
+            indicator(JoinType::Outer)?,
+            strs(&["left_only", "both", "both", "right_only"])
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(all(feature = "dtype-date32", feature = "dtype-date64"))]
+    fn test_join_temporal() -> Result<()> {
+        let ts = Series::new("ts", &[0i64, 86_400_000, 172_800_000]).cast::<Date64Type>()?;
+        let left = DataFrame::new(vec![ts, Series::new("a", &[1, 2, 3])])?;
+        let days = Series::new("day", &[2, 0, 5]).cast::<Date32Type>()?;
+        let right = DataFrame::new(vec![days, Series::new("b", &["c", "a", "x"])])?;
+
+        let out = left.inner_join(&right, "ts", "day")?.sort("a", false)?;
+        assert_eq!(out.column("ts")?.dtype(), &DataType::Date64);
+        assert_eq!(Vec::from(out.column("a")?.i32()?), &[Some(1), Some(3)]);
+        assert_eq!(Vec::from(out.column("b")?.utf8()?), &[Some("a"), Some("c")]);
+
+        let out = left.join(
+            &left,
+            "ts",
+            "ts",
+            JoinArgs::new(JoinType::Outer).with_validation(JoinValidation::OneToOne),
+        )?;
+        assert_eq!(out.height(), 3);
+
+        // integers are joined with the temporal type they are stored as
+        let ms = Series::new("ms", &[86_400_000i64]);
+        let right = DataFrame::new(vec![ms])?;
+        let out = left.left_join(&right, "ts", "ms")?;
+        assert_eq!(out.shape(), (3, 2));
+        let out = left.inner_join(&right, "ts", "ms")?;
+        assert_eq!(Vec::from(out.column("a")?.i32()?), &[Some(2)]);
+
+        let wrong = DataFrame::new(vec![Series::new("ts", &["1970-01-01"])])?;
+        assert!(left.inner_join(&wrong, "ts", "ts").is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_join_categorical() {
         toggle_string_cache(true);
 
         let (mut df_a, mut df_b) = get_dfs();
 
-        df_a.may_apply("b", |s| s.cast_with_datatype(&DataType::Categorical))
-            .unwrap();
-        df_b.may_apply("bar", |s| s.cast_with_datatype(&DataType::Categorical))
-            .unwrap();
+        df_a.may_apply("b", |s| {
+            s.cast_with_datatype(&DataType::Categorical(CategoricalOrdering::Physical))
+        })
+        .unwrap();
+        df_b.may_apply("bar", |s| {
+            s.cast_with_datatype(&DataType::Categorical(CategoricalOrdering::Physical))
+        })
+        .unwrap();
 
         let out = df_a.join(&df_b, "b", "bar", JoinType::Left).unwrap();
         assert_eq!(out.shape(), (6, 5));
@@ -1289,6 +3255,42 @@ mod test {
         assert_eq!(Vec::from(ca), correct_ham);
     }
 
+    #[test]
+    fn test_join_categorical_different_maps() -> Result<()> {
+        // without the string cache both mappings start at 0, so the u32 indexes of "x" and "y"
+        // are swapped between the frames
+        let cat = DataType::Categorical(CategoricalOrdering::Physical);
+        let df_a = DataFrame::new(vec![
+            Series::new("k", &["x", "y", "x"]).cast_with_datatype(&cat)?,
+            Series::new("n", &[1, 1, 2]),
+        ])?;
+        let df_b = DataFrame::new(vec![
+            Series::new("k", &["y", "x"]).cast_with_datatype(&cat)?,
+            Series::new("n", &[1, 1]),
+            Series::new("val", &["b_y", "b_x"]),
+        ])?;
+
+        let out = df_a.join(&df_b, &["k", "n"], &["k", "n"], JoinType::Left)?;
+        assert_eq!(
+            Vec::from(out.column("val")?.utf8()?),
+            &[Some("b_x"), Some("b_y"), None]
+        );
+        let out = df_a.join(&df_b, "k", "k", JoinType::Left)?;
+        assert_eq!(
+            Vec::from(out.column("val")?.utf8()?),
+            &[Some("b_x"), Some("b_y"), Some("b_x")]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_join_key_count_mismatch() -> Result<()> {
+        let (temp, rain) = create_frames();
+        let out = temp.join(&rain, &["days", "rain"], &["days"], JoinType::Inner);
+        assert!(matches!(out, Err(PolarsError::ShapeMisMatch(_))));
+        Ok(())
+    }
+
     #[test]
     fn empty_df_join() {
         let empty: Vec<String> = vec![];
@@ -1312,4 +3314,83 @@ mod test {
         right.inner_join(&left, "key", "key").unwrap();
         right.outer_join(&left, "key", "key").unwrap();
     }
+
+    #[test]
+    fn test_join_statistics() -> Result<()> {
+        let left = df! {
+            "key" => &[1, 1, 2, 3],
+            "lval" => &[1, 2, 3, 4]
+        }?;
+        let right = df! {
+            "key" => &[1, 1, 2, 4, 5],
+            "rval" => &[1, 2, 3, 4, 5]
+        }?;
+
+        let (out, stats) = left.join_with_statistics(&right, "key", "key", JoinType::Inner)?;
+        assert_eq!(out.height(), 5);
+        assert_eq!(
+            stats,
+            JoinStatistics {
+                left_rows: 4,
+                right_rows: 5,
+                build_side: Some(JoinSide::Left),
+                build_side_rows: 4,
+                build_side_keys: 3,
+                hash_collisions: 0,
+                left_matched_rows: 3,
+                right_matched_rows: 3,
+                output_rows: 5,
+            }
+        );
+        assert_eq!(stats.left_match_rate(), 0.75);
+        assert_eq!(stats.right_match_rate(), 0.6);
+
+        for (how, build_side) in [
+            (JoinType::Inner, Some(JoinSide::Left)),
+            (JoinType::Left, Some(JoinSide::Right)),
+            (JoinType::Outer, Some(JoinSide::Left)),
+            (JoinType::Cross, None),
+        ]
+        .iter()
+        {
+            let (out, stats) = left.join_with_statistics(&right, "key", "key", *how)?;
+            assert!(out.frame_equal_missing(&left.join(&right, "key", "key", *how)?));
+            assert_eq!(stats.output_rows, out.height());
+            assert_eq!(stats.build_side, *build_side);
+
+            let (left_on, right_on) = (["key", "lval"], ["key", "rval"]);
+            let (out, stats) = left.join_with_statistics(&right, &left_on, &right_on, *how)?;
+            assert_eq!(
+                out.height(),
+                left.join(&right, &left_on, &right_on, *how)?.height()
+            );
+            assert_eq!(stats.output_rows, out.height());
+            assert_eq!(stats.build_side, *build_side);
+        }
+
+        let args = JoinArgs::new(JoinType::Left).with_strategy(JoinStrategy::LowMemory);
+        let (_, stats) = left.join_with_statistics(&right, "key", "key", args)?;
+        assert_eq!(stats.build_side, Some(JoinSide::Right));
+        assert_eq!((stats.build_side_keys, stats.left_matched_rows), (4, 3));
+        Ok(())
+    }
+
+    #[test]
+    fn test_join_statistics_build_side() -> Result<()> {
+        // the longer relation has far fewer distinct keys, so the hash table is built on it
+        let left = df! {
+            "key" => (0..40_000u32).map(|i| (i * 7919) % 10).collect::<Vec<_>>()
+        }?;
+        let right = df! {
+            "key" => (0..20_000u32).map(|i| (i * 104729) % 20_000).collect::<Vec<_>>()
+        }?;
+        let (_, stats) = left.join_with_statistics(&right, "key", "key", JoinType::Inner)?;
+        assert_eq!(stats.build_side, Some(JoinSide::Left));
+        assert_eq!(stats.build_side_rows, 40_000);
+        assert_eq!(stats.build_side_keys, 10);
+        assert_eq!(stats.left_matched_rows, 40_000);
+        assert_eq!(stats.right_matched_rows, 10);
+        assert_eq!(stats.output_rows, 40_000);
+        Ok(())
+    }
 }
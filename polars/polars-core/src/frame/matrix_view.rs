@@ -0,0 +1,120 @@
+use crate::prelude::*;
+
+/// A column-major view of the values of a numeric DataFrame that borrows the columns without
+/// copying them. Column `j` is the slice of values of the `j`th column of the DataFrame.
+#[derive(Debug, Clone)]
+pub struct MatrixView<'a, T> {
+    columns: Vec<&'a [T]>,
+    height: usize,
+}
+
+impl<'a, T: Copy> MatrixView<'a, T> {
+    /// Number of rows and columns.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.height, self.columns.len())
+    }
+
+    /// Get the values of column `idx`.
+    pub fn column(&self, idx: usize) -> Option<&'a [T]> {
+        self.columns.get(idx).copied()
+    }
+
+    /// Get the slices of all columns.
+    pub fn columns(&self) -> &[&'a [T]] {
+        &self.columns
+    }
+
+    /// Get the value at `row` of column `column`.
+    pub fn get(&self, row: usize, column: usize) -> Option<T> {
+        self.columns.get(column)?.get(row).copied()
+    }
+
+    /// Iterate over the values of row `idx`. Every value is in a different column, prefer the
+    /// column slices for hot loops.
+    pub fn row(&self, idx: usize) -> impl Iterator<Item = T> + '_ {
+        self.columns.iter().map(move |column| column[idx])
+    }
+}
+
+impl DataFrame {
+    /// Borrow the values of all columns as a [MatrixView] without copying.
+    ///
+    /// Returns an error instead of copying if a column is not of data type `N`, has null values
+    /// or has more than one chunk. Call [as_single_chunk](DataFrame::as_single_chunk) first to
+    /// make sure the columns are contiguous.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    ///
+    /// let df = df!["x" => &[1.0, 2.0], "y" => &[3.0, 4.0]].unwrap();
+    /// let view = df.as_matrix_view::<Float64Type>().unwrap();
+    /// assert_eq!(view.shape(), (2, 2));
+    /// assert_eq!(view.column(1), Some(&[3.0, 4.0][..]));
+    /// assert_eq!(view.get(1, 0), Some(2.0));
+    /// ```
+    pub fn as_matrix_view<N>(&self) -> Result<MatrixView<'_, N::Native>>
+    where
+        N: PolarsNumericType,
+    {
+        let columns = self
+            .columns
+            .iter()
+            .map(|s| {
+                let ca = s.unpack::<N>().map_err(|_| {
+                    PolarsError::DataTypeMisMatch(
+                        format!(
+                            "cannot view column {} of type {:?} as a matrix of {:?}",
+                            s.name(),
+                            s.dtype(),
+                            N::get_dtype()
+                        )
+                        .into(),
+                    )
+                })?;
+                if ca.null_count() != 0 {
+                    return Err(PolarsError::HasNullValues(
+                        format!("cannot view column {} with null values", s.name()).into(),
+                    ));
+                }
+                ca.cont_slice()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(MatrixView {
+            columns,
+            height: self.height(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_matrix_view() -> Result<()> {
+        let mut df = df![
+            "a" => &[1, 2],
+            "b" => &[3, 4]
+        ]?;
+        let other = df.clone();
+        df.vstack_mut(&other)?;
+        // the stacked columns have two chunks
+        assert!(df.as_matrix_view::<Int32Type>().is_err());
+        df.as_single_chunk();
+
+        let view = df.as_matrix_view::<Int32Type>()?;
+        assert_eq!(view.shape(), (4, 2));
+        assert_eq!(view.column(0), Some(&[1, 2, 1, 2][..]));
+        assert_eq!(view.row(1).collect::<Vec<_>>(), &[2, 4]);
+        assert_eq!(view.get(4, 0), None);
+        assert!(df.as_matrix_view::<Float64Type>().is_err());
+
+        let mut ca = Int32Chunked::new_from_slice("a", &[1, 2]);
+        ca.append(&Int32Chunked::new_from_slice("a", &[3]));
+        assert!(ca.cont_slice().is_err());
+        assert_eq!(ca.rechunked_slice()?, &[1, 2, 3]);
+        Ok(())
+    }
+}
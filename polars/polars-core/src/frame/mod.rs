@@ -2,7 +2,10 @@
 use crate::chunked_array::ops::unique::is_unique_helper;
 use crate::frame::select::Selection;
 use crate::prelude::*;
-use crate::utils::{accumulate_dataframes_horizontal, accumulate_dataframes_vertical, NoNull};
+use crate::utils::{
+    accumulate_dataframes_horizontal, accumulate_dataframes_vertical, check_bounds,
+    check_bounds_ca, get_supertype, to_snake_case, NoNull,
+};
 use ahash::RandomState;
 use arrow::record_batch::RecordBatch;
 use itertools::Itertools;
@@ -14,13 +17,21 @@ use std::mem;
 use std::sync::Arc;
 
 mod arithmetic;
+mod asof_join;
+pub mod compare;
+mod cross_join;
 pub mod explode;
 pub mod group_by;
 pub mod hash_join;
+pub mod matrix_view;
 pub mod resample;
 pub mod row;
 pub mod select;
+mod session;
+pub mod set_ops;
+pub mod update;
 mod upstream_traits;
+pub mod validate;
 
 #[derive(Clone)]
 pub struct DataFrame {
@@ -83,7 +94,15 @@ impl DataFrame {
             match first_len {
                 Some(len) => {
                     if series.len() != len {
-                        return Err(PolarsError::ShapeMisMatch("Could not create a new DataFrame from Series. The Series have different lengths".into()));
+                        return Err(PolarsError::ShapeMisMatch(
+                            format!(
+                                "Could not create a new DataFrame from Series. The Series have different lengths: column '{}' has length {}, expected length {}",
+                                series.name(),
+                                series.len(),
+                                len
+                            )
+                            .into(),
+                        ));
                     }
                 }
                 None => first_len = Some(series.len()),
@@ -106,6 +125,37 @@ impl DataFrame {
         Ok(df)
     }
 
+    /// Create a DataFrame without rows that has the columns of `schema`. The columns keep the
+    /// data types and metadata of the fields, so the result can be stacked onto or joined with
+    /// DataFrames of the same schema.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    /// let schema = Schema::new(vec![
+    ///     Field::new("id", DataType::UInt32),
+    ///     Field::new("tags", DataType::List(ArrowDataType::Int64)),
+    /// ]);
+    /// let df = DataFrame::empty_with_schema(&schema).unwrap();
+    /// assert_eq!(df.shape(), (0, 2));
+    /// assert_eq!(df.schema(), schema);
+    /// ```
+    pub fn empty_with_schema(schema: &Schema) -> Result<Self> {
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|fld| {
+                let mut s = Series::new_empty(fld.name(), fld.data_type())?;
+                if !fld.metadata().is_empty() {
+                    s.set_metadata(fld.metadata().clone());
+                }
+                Ok(s)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        DataFrame::new(columns)
+    }
+
     // doesn't check Series sizes.
     // todo! make private
     pub fn new_no_checks(columns: Vec<Series>) -> DataFrame {
@@ -251,7 +301,8 @@ impl DataFrame {
     }
 
     /// Add multiple Series to a DataFrame
-    /// The added Series are required to have the same length.
+    /// The added Series are required to have the same length. A Series of length 1 is broadcast
+    /// to the height of the DataFrame, like a literal in an expression.
     ///
     /// # Example
     ///
@@ -267,7 +318,7 @@ impl DataFrame {
         // first loop check validity. We don't do this in a single pass otherwise
         // this DataFrame is already modified when an error occurs.
         for col in columns {
-            if col.len() != height {
+            if col.len() != height && !self.broadcasts(col) {
                 return Err(PolarsError::ShapeMisMatch(
                     format!("Could not horizontally stack Series. The Series length {} differs from the DataFrame height: {}", col.len(), height).into()));
             }
@@ -284,17 +335,89 @@ impl DataFrame {
             }
             names.insert(name.to_string());
         }
-        Ok(self.hstack_mut_no_checks(columns))
+        let columns = columns
+            .iter()
+            .map(|s| self.broadcast(s))
+            .collect::<Vec<_>>();
+        Ok(self.hstack_mut_no_checks(&columns))
     }
 
     /// Add multiple Series to a DataFrame
-    /// The added Series are required to have the same length.
+    /// The added Series are required to have the same length. A Series of length 1 is broadcast
+    /// to the height of the DataFrame.
     pub fn hstack(&self, columns: &[Series]) -> Result<Self> {
         let mut new_cols = self.columns.clone();
-        new_cols.extend_from_slice(columns);
+        new_cols.extend(columns.iter().map(|s| self.broadcast(s)));
         DataFrame::new(new_cols)
     }
 
+    /// A Series of length 1 is broadcast to the height of a DataFrame with columns.
+    fn broadcasts(&self, s: &Series) -> bool {
+        s.len() == 1 && self.width() > 0
+    }
+
+    fn broadcast(&self, s: &Series) -> Series {
+        let height = self.height();
+        if self.broadcasts(s) && height != 1 {
+            s.broadcast(height)
+        } else {
+            s.clone()
+        }
+    }
+
+    /// Return a DataFrame with the columns in the order and with the data types of `schema`.
+    ///
+    /// Columns are cast to the data type of the schema and columns that are missing are
+    /// added as columns of nulls. Columns that are not in the schema are dropped.
+    ///
+    /// If `strict`, missing columns, extra columns and casts that turn values into nulls
+    /// return an error instead.
+    pub fn match_schema(&self, schema: &Schema, strict: bool) -> Result<Self> {
+        if strict {
+            if let Some(s) = self
+                .columns
+                .iter()
+                .find(|s| schema.column_with_name(s.name()).is_none())
+            {
+                return Err(PolarsError::ValueError(
+                    format!("column {} is not in the schema", s.name()).into(),
+                ));
+            }
+        }
+        let height = self.height();
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let dtype = field.data_type();
+                match self.column(field.name()) {
+                    Ok(s) if s.dtype() == dtype => Ok(s.clone()),
+                    Ok(s) => {
+                        let out = s.cast_with_datatype(dtype)?;
+                        if strict && out.null_count() != s.null_count() {
+                            return Err(PolarsError::ValueError(
+                                format!(
+                                    "column {} could not be cast from {:?} to {:?} without loss",
+                                    s.name(),
+                                    s.dtype(),
+                                    dtype
+                                )
+                                .into(),
+                            ));
+                        }
+                        Ok(out)
+                    }
+                    Err(_) if strict => Err(PolarsError::NotFound(format!(
+                        "column {} of the schema is missing",
+                        field.name()
+                    ))),
+                    Err(_) => Series::full_null(field.name(), height, dtype),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(DataFrame::new_no_checks(columns))
+    }
+
     /// Concatenate a DataFrame to this DataFrame and return as newly allocated DataFrame
     pub fn vstack(&self, columns: &DataFrame) -> Result<Self> {
         let mut df = self.clone();
@@ -310,7 +433,17 @@ impl DataFrame {
             ));
         }
 
-        if self.dtypes() != df.dtypes() {
+        // a column of nulls can be stacked on any type and categoricals with a different ordering
+        // are stacked in their supertype
+        let compatible = self.columns.iter().zip(df.columns.iter()).all(|(l, r)| {
+            l.dtype() == r.dtype()
+                || l.dtype() == &DataType::Null
+                || r.dtype() == &DataType::Null
+                || (matches!(l.dtype(), DataType::Categorical(_))
+                    && matches!(r.dtype(), DataType::Categorical(_))
+                    && get_supertype(l.dtype(), r.dtype()).is_ok())
+        });
+        if !compatible {
             return Err(PolarsError::DataTypeMisMatch(
                 format!(
                     "cannot vstack: data types don't match of {:?} {:?}",
@@ -320,13 +453,28 @@ impl DataFrame {
                 .into(),
             ));
         }
-        self.columns
-            .iter_mut()
-            .zip(df.columns.iter())
-            .for_each(|(left, right)| {
+        let mut casted = false;
+        for (left, right) in self.columns.iter_mut().zip(df.columns.iter()) {
+            if left.dtype() == right.dtype() {
                 left.append(right).expect("should not fail");
-            });
-        // don't rechunk here. Chunks in columns always match.
+            } else if left.dtype() == &DataType::Null {
+                *left = left.cast_with_datatype(right.dtype())?;
+                left.append(right)?;
+                casted = true;
+            } else if right.dtype() != &DataType::Null {
+                let dtype = get_supertype(left.dtype(), right.dtype())?;
+                *left = left.cast_with_datatype(&dtype)?;
+                left.append(&right.cast_with_datatype(&dtype)?)?;
+                casted = true;
+            } else {
+                left.append(&right.cast_with_datatype(left.dtype())?)?;
+                casted = true;
+            }
+        }
+        // Chunks in columns always match, unless a null column was cast.
+        if casted {
+            self.rechunk();
+        }
         Ok(self)
     }
 
@@ -387,6 +535,16 @@ impl DataFrame {
     }
 
     fn insert_at_idx_no_name_check(&mut self, index: usize, series: Series) -> Result<&mut Self> {
+        if index > self.width() {
+            return Err(PolarsError::OutOfBounds(
+                format!(
+                    "cannot insert column at index {} of a DataFrame with {} columns",
+                    index,
+                    self.width()
+                )
+                .into(),
+            ));
+        }
         if series.len() == self.height() {
             self.columns.insert(index, series);
             self.rechunk();
@@ -403,13 +561,26 @@ impl DataFrame {
         }
     }
 
-    /// Insert a new column at a given index
-    pub fn insert_at_idx<S: IntoSeries>(&mut self, index: usize, column: S) -> Result<&mut Self> {
+    /// Insert a new column at a given index. The index may be equal to the width of the
+    /// `DataFrame` to add the column at the end.
+    ///
+    /// ```
+    /// # use polars_core::prelude::*;
+    /// let mut df = df!("a" => &[1, 2], "c" => &[5, 6]).unwrap();
+    /// df.insert_column(1, Series::new("b", &[3, 4])).unwrap();
+    /// assert_eq!(df.get_column_names(), &["a", "b", "c"]);
+    /// ```
+    pub fn insert_column<S: IntoSeries>(&mut self, index: usize, column: S) -> Result<&mut Self> {
         let series = column.into_series();
         self.has_column(series.name())?;
         self.insert_at_idx_no_name_check(index, series)
     }
 
+    /// Insert a new column at a given index, see [insert_column](DataFrame::insert_column).
+    pub fn insert_at_idx<S: IntoSeries>(&mut self, index: usize, column: S) -> Result<&mut Self> {
+        self.insert_column(index, column)
+    }
+
     /// Add a new column to this `DataFrame`.
     pub fn add_column<S: IntoSeries>(&mut self, column: S) -> Result<&mut Self> {
         let series = column.into_series();
@@ -502,6 +673,8 @@ impl DataFrame {
     ///         "by_str" => df.select("my-column"),
     ///         "by_tuple" => df.select(("col_1", "col_2")),
     ///         "by_vec" => df.select(vec!["col_a", "col_b"]),
+    ///         "by_range" => df.select(1..3),
+    ///         "by_dtype" => df.select(DataType::Float64),
     ///          _ => unimplemented!()
     ///     }
     /// }
@@ -520,12 +693,65 @@ impl DataFrame {
     where
         S: Selection<'a, J>,
     {
-        let cols = selection.to_selection_vec();
-        let selected = cols
+        selection.select_from(self)
+    }
+
+    /// Select columns by name in the given order. Unlike [select](DataFrame::select) it returns an
+    /// error if a column is selected more than once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    /// fn example(df: &DataFrame) -> Result<DataFrame> {
+    ///     df.select_in_order(&["year", "month", "day"])
+    /// }
+    /// ```
+    pub fn select_in_order<S: AsRef<str>>(&self, names: &[S]) -> Result<Self> {
+        let mut seen = HashSet::with_capacity_and_hasher(names.len(), RandomState::default());
+        let columns = names
             .iter()
-            .map(|c| self.column(c).map(|s| s.clone()))
+            .map(|name| {
+                let name = name.as_ref();
+                if !seen.insert(name) {
+                    return Err(PolarsError::Duplicate(
+                        format!("column '{}' is selected more than once", name).into(),
+                    ));
+                }
+                self.column(name).map(|s| s.clone())
+            })
             .collect::<Result<Vec<_>>>()?;
-        Ok(selected)
+        Ok(DataFrame::new_no_checks(columns))
+    }
+
+    /// Move the column `name` to index `idx`, shifting the columns in between.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    /// fn example(df: &mut DataFrame) -> Result<&mut DataFrame> {
+    ///     // make "id" the first column
+    ///     df.move_column("id", 0)
+    /// }
+    /// ```
+    pub fn move_column(&mut self, name: &str, idx: usize) -> Result<&mut Self> {
+        let current = self
+            .find_idx_by_name(name)
+            .ok_or_else(|| PolarsError::NotFound(name.to_string()))?;
+        if idx >= self.width() {
+            return Err(PolarsError::OutOfBounds(
+                format!(
+                    "cannot move column to index {} of a DataFrame with {} columns",
+                    idx,
+                    self.width()
+                )
+                .into(),
+            ));
+        }
+        let column = self.columns.remove(current);
+        self.columns.insert(idx, column);
+        Ok(self)
     }
 
     /// Select a mutable series by name.
@@ -580,6 +806,26 @@ impl DataFrame {
         DataFrame::new_no_checks(new_col)
     }
 
+    /// Take DataFrame values by indexes from an iterator.
+    /// Returns an error if any of the indices is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    /// fn example(df: &DataFrame) -> Result<DataFrame> {
+    ///     let iterator = (0..9).into_iter();
+    ///     df.take_iter_checked(iterator)
+    /// }
+    /// ```
+    pub fn take_iter_checked<I>(&self, iter: I) -> Result<Self>
+    where
+        I: Iterator<Item = usize> + Clone + Sync,
+    {
+        check_bounds(iter.clone(), self.height())?;
+        Ok(self.take_iter(iter))
+    }
+
     /// Take DataFrame values by indexes from an iterator.
     ///
     /// # Safety
@@ -594,8 +840,9 @@ impl DataFrame {
             Ok(n) => n,
         };
 
-        if n_chunks == 1 {
-            let idx_ca: NoNull<UInt32Chunked> = iter.into_iter().map(|idx| idx as u32).collect();
+        // the single chunk fast path gathers with an index array
+        if n_chunks == 1 && self.height() <= IdxSize::MAX as usize {
+            let idx_ca: NoNull<IdxCa> = iter.into_iter().map(|idx| idx as IdxSize).collect();
             let idx_ca = idx_ca.into_inner();
             let cols = self
                 .columns
@@ -638,8 +885,12 @@ impl DataFrame {
             Ok(n) => n,
         };
 
-        if n_chunks == 1 {
-            let idx_ca: UInt32Chunked = iter.into_iter().map(|opt| opt.map(|v| v as u32)).collect();
+        // the single chunk fast path gathers with an index array
+        if n_chunks == 1 && self.height() <= IdxSize::MAX as usize {
+            let idx_ca: IdxCa = iter
+                .into_iter()
+                .map(|opt| opt.map(|v| v as IdxSize))
+                .collect();
             let cols = self
                 .columns
                 .par_iter()
@@ -673,14 +924,14 @@ impl DataFrame {
     /// ```
     /// use polars_core::prelude::*;
     /// fn example(df: &DataFrame) -> DataFrame {
-    ///     let idx = UInt32Chunked::new_from_slice("idx", &[0, 1, 9]);
+    ///     let idx = IdxCa::new_from_slice("idx", &[0, 1, 9]);
     ///     df.take(&idx)
     /// }
     /// ```
     /// # Safety
     ///
     /// Out of bounds access doesn't Error but will return a Null value
-    pub fn take(&self, indices: &UInt32Chunked) -> Self {
+    pub fn take(&self, indices: &IdxCa) -> Self {
         let indices = if indices.chunks.len() > 1 {
             Cow::Owned(indices.rechunk())
         } else {
@@ -691,6 +942,23 @@ impl DataFrame {
         DataFrame::new_no_checks(new_col)
     }
 
+    /// Take DataFrame rows by index values.
+    /// Returns an error if any of the indices is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    /// fn example(df: &DataFrame) -> Result<DataFrame> {
+    ///     let idx = IdxCa::new_from_slice("idx", &[0, 1, 9]);
+    ///     df.take_checked(&idx)
+    /// }
+    /// ```
+    pub fn take_checked(&self, indices: &IdxCa) -> Result<Self> {
+        check_bounds_ca(indices, self.height())?;
+        Ok(self.take(indices))
+    }
+
     /// Rename a column in the DataFrame
     ///
     /// # Example
@@ -710,6 +978,55 @@ impl DataFrame {
         Ok(self)
     }
 
+    /// Rename all columns with `f`, that maps the current name of a column to its new name.
+    /// Returns an error if two columns would get the same name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    /// fn example(df: &mut DataFrame) -> Result<&mut DataFrame> {
+    ///     df.rename_with(|name| name.to_uppercase())
+    /// }
+    /// ```
+    pub fn rename_with<F: Fn(&str) -> String>(&mut self, f: F) -> Result<&mut Self> {
+        let names: Vec<String> = self.columns.iter().map(|s| f(s.name())).collect();
+        let mut unique = HashSet::with_capacity_and_hasher(names.len(), RandomState::default());
+        for name in &names {
+            if !unique.insert(name.as_str()) {
+                return Err(PolarsError::Duplicate(
+                    format!(
+                        "renaming would create more than one column named '{}'",
+                        name
+                    )
+                    .into(),
+                ));
+            }
+        }
+        self.set_column_names(&names)?;
+        Ok(self)
+    }
+
+    /// Normalize all column names to snake_case, e.g. `"Sepal Width"` becomes `"sepal_width"`.
+    /// See [to_snake_case](crate::utils::to_snake_case).
+    pub fn rename_snake_case(&mut self) -> Result<&mut Self> {
+        self.rename_with(to_snake_case)
+    }
+
+    /// Add a prefix to all column names.
+    pub fn add_prefix(&mut self, prefix: &str) -> &mut Self {
+        // prefixing keeps the names unique
+        self.rename_with(|name| format!("{}{}", prefix, name))
+            .unwrap()
+    }
+
+    /// Add a suffix to all column names.
+    pub fn add_suffix(&mut self, suffix: &str) -> &mut Self {
+        // suffixing keeps the names unique
+        self.rename_with(|name| format!("{}{}", name, suffix))
+            .unwrap()
+    }
+
     /// Sort DataFrame in place by a column.
     pub fn sort_in_place(&mut self, by_column: &str, reverse: bool) -> Result<&mut Self> {
         let s = self.column(by_column)?;
@@ -717,6 +1034,7 @@ impl DataFrame {
         let take = s.argsort(reverse);
 
         self.columns = self.columns.par_iter().map(|s| s.take(&take)).collect();
+        self.flag_sorted_column(by_column, reverse);
         Ok(self)
     }
 
@@ -725,7 +1043,24 @@ impl DataFrame {
         let s = self.column(by_column)?;
 
         let take = s.argsort(reverse);
-        Ok(self.take(&take))
+        let mut df = self.take(&take);
+        df.flag_sorted_column(by_column, reverse);
+        Ok(df)
+    }
+
+    /// Set the sortedness flag of the column the DataFrame was just sorted by.
+    fn flag_sorted_column(&mut self, by_column: &str, reverse: bool) {
+        if let Some(s) = self.columns.iter_mut().find(|s| s.name() == by_column) {
+            // lexical categoricals are not sorted by their physical values
+            if s.dtype() != &DataType::Categorical(CategoricalOrdering::Lexical) {
+                let sorted = if reverse {
+                    IsSorted::Descending
+                } else {
+                    IsSorted::Ascending
+                };
+                s.set_sorted_flag(sorted);
+            }
+        }
     }
 
     /// Replace a column with a series.
@@ -1186,6 +1521,28 @@ impl DataFrame {
         Ok(DataFrame::new_no_checks(columns))
     }
 
+    /// Count the null values of every column. Returns a single row with an `IdxSize` count per
+    /// column. The counts are read from the validity bitmaps.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    ///
+    /// let df = df!("a" => &[Some(1), None, None], "b" => &[Some("x"), Some("y"), None]).unwrap();
+    /// let nulls = df.null_count();
+    /// assert_eq!(nulls.column("a").unwrap().idx().unwrap().get(0), Some(2));
+    /// assert_eq!(nulls.column("b").unwrap().idx().unwrap().get(0), Some(1));
+    /// ```
+    pub fn null_count(&self) -> Self {
+        let columns = self
+            .columns
+            .iter()
+            .map(|s| IdxCa::new_from_slice(s.name(), &[s.null_count() as IdxSize]).into_series())
+            .collect();
+        DataFrame::new_no_checks(columns)
+    }
+
     /// Pipe different functions/ closure operations that work on a DataFrame together.
     pub fn pipe<F, B>(self, f: F) -> Result<B>
     where
@@ -1210,7 +1567,13 @@ impl DataFrame {
         f(self, args)
     }
 
-    /// Create dummy variables.
+    /// Create dummy variables, i.e. one-hot encode the `columns`, or all columns if `None`.
+    ///
+    /// Every value of a column gets a `UInt8` indicator column named `{name}{separator}{value}`.
+    /// The indicator columns are ordered by value and take the place of the original column;
+    /// columns that are not selected are kept as is. Missing values get an indicator column
+    /// named `{name}{separator}null`; if that name is not unique, e.g. because the column also
+    /// has the String value `"null"`, a `Duplicate` error is returned.
     ///
     /// # Example
     ///
@@ -1227,39 +1590,75 @@ impl DataFrame {
     ///       "code" => &["X1", "X2", "X3", "X3", "X2", "X2", "X1", "X1"]
     ///   }.unwrap();
     ///
-    ///   let dummies = df.to_dummies().unwrap();
+    ///   let dummies = df.to_dummies(None, "_").unwrap();
     ///   dbg!(dummies);
     /// # }
     /// ```
     /// Outputs:
     /// ```text
     ///  +------+------+------+--------+--------+--------+---------+---------+---------+
-    ///  | id_1 | id_3 | id_2 | type_A | type_B | type_C | code_X1 | code_X2 | code_X3 |
+    ///  | id_1 | id_2 | id_3 | type_A | type_B | type_C | code_X1 | code_X2 | code_X3 |
     ///  | ---  | ---  | ---  | ---    | ---    | ---    | ---     | ---     | ---     |
     ///  | u8   | u8   | u8   | u8     | u8     | u8     | u8      | u8      | u8      |
     ///  +======+======+======+========+========+========+=========+=========+=========+
     ///  | 1    | 0    | 0    | 1      | 0      | 0      | 1       | 0       | 0       |
     ///  +------+------+------+--------+--------+--------+---------+---------+---------+
-    ///  | 0    | 0    | 1    | 0      | 1      | 0      | 0       | 1       | 0       |
+    ///  | 0    | 1    | 0    | 0      | 1      | 0      | 0       | 1       | 0       |
     ///  +------+------+------+--------+--------+--------+---------+---------+---------+
-    ///  | 0    | 1    | 0    | 0      | 1      | 0      | 0       | 0       | 1       |
+    ///  | 0    | 0    | 1    | 0      | 1      | 0      | 0       | 0       | 1       |
     ///  +------+------+------+--------+--------+--------+---------+---------+---------+
     ///  | 1    | 0    | 0    | 0      | 1      | 0      | 0       | 0       | 1       |
     ///  +------+------+------+--------+--------+--------+---------+---------+---------+
-    ///  | 0    | 0    | 1    | 0      | 0      | 1      | 0       | 1       | 0       |
-    ///  +------+------+------+--------+--------+--------+---------+---------+---------+
     ///  | 0    | 1    | 0    | 0      | 0      | 1      | 0       | 1       | 0       |
     ///  +------+------+------+--------+--------+--------+---------+---------+---------+
+    ///  | 0    | 0    | 1    | 0      | 0      | 1      | 0       | 1       | 0       |
+    ///  +------+------+------+--------+--------+--------+---------+---------+---------+
     ///  | 1    | 0    | 0    | 0      | 0      | 1      | 1       | 0       | 0       |
     ///  +------+------+------+--------+--------+--------+---------+---------+---------+
     ///  | 1    | 0    | 0    | 0      | 1      | 0      | 1       | 0       | 0       |
     ///  +------+------+------+--------+--------+--------+---------+---------+---------+
     /// ```
-    pub fn to_dummies(&self) -> Result<Self> {
+    pub fn to_dummies(&self, columns: Option<&[&str]>, separator: &str) -> Result<Self> {
+        if let Some(columns) = columns {
+            for name in columns {
+                self.column(name)?;
+            }
+        }
         let cols = self
             .columns
             .par_iter()
-            .map(|s| s.to_dummies())
+            .map(|s| {
+                if let Some(columns) = columns {
+                    if !columns.contains(&s.name()) {
+                        return Ok(DataFrame::new_no_checks(vec![s.clone()]));
+                    }
+                }
+                let mut dummies = s.to_dummies()?;
+                if separator != "_" {
+                    // the dummies are named `{name}_{value}`
+                    let prefix_len = s.name().len() + 1;
+                    for dummy in &mut dummies.columns {
+                        let new_name =
+                            format!("{}{}{}", s.name(), separator, &dummy.name()[prefix_len..]);
+                        dummy.rename(&new_name);
+                    }
+                }
+                let mut names =
+                    HashSet::with_capacity_and_hasher(dummies.width(), RandomState::default());
+                for dummy in dummies.get_columns() {
+                    if !names.insert(dummy.name()) {
+                        return Err(PolarsError::Duplicate(
+                            format!(
+                                "dummy column {:?} of column {:?} is not unique",
+                                dummy.name(),
+                                s.name()
+                            )
+                            .into(),
+                        ));
+                    }
+                }
+                Ok(dummies)
+            })
             .collect::<Result<Vec<_>>>()?;
 
         accumulate_dataframes_horizontal(cols)
@@ -1268,6 +1667,9 @@ impl DataFrame {
     /// Drop duplicate rows from a DataFrame.
     /// *This fails when there is a column of type List in DataFrame*
     ///
+    /// The first occurrence of every row is kept. With `maintain_order` these rows are in the
+    /// order of the original DataFrame, otherwise their order is arbitrary.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -1306,7 +1708,33 @@ impl DataFrame {
             Some(s) => s.iter().map(|s| &**s).collect(),
             None => self.get_column_names(),
         };
-        let gb = self.groupby(names)?;
+        let keys = self.select_series(names)?;
+        self.drop_duplicates_by(maintain_order, keys)
+    }
+
+    /// Drop the rows that have duplicate values in `keys`, e.g. keys that are computed from the
+    /// columns. The keys should have the same length as the DataFrame and are not added to it.
+    /// See [drop_duplicates](DataFrame::drop_duplicates).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    ///
+    /// // keep the first row of every whole price
+    /// fn unique_whole_prices(df: &DataFrame) -> Result<DataFrame> {
+    ///     let whole = df.column("price")?.cast::<Int64Type>()?;
+    ///     df.drop_duplicates_by(true, vec![whole])
+    /// }
+    /// ```
+    pub fn drop_duplicates_by(&self, maintain_order: bool, keys: Vec<Series>) -> Result<Self> {
+        // the first indexes of a single key are deduplicated in parallel and come out sorted,
+        // so that they maintain the order for free
+        if let [key] = keys.as_slice() {
+            let idx = key.arg_unique()?;
+            return Ok(unsafe { self.take_iter_unchecked(idx.into_iter().map(|i| i as usize)) });
+        }
+        let gb = self.groupby_with_series(keys, true)?;
         let groups = gb.get_groups().iter().map(|v| v.0);
 
         let df = if maintain_order {
@@ -1324,24 +1752,30 @@ impl DataFrame {
     pub fn is_unique(&self) -> Result<BooleanChunked> {
         let mut gb = self.groupby(self.get_column_names())?;
         let groups = std::mem::take(&mut gb.groups);
-        Ok(is_unique_helper(groups, self.height() as u32, true, false))
+        Ok(is_unique_helper(
+            groups,
+            self.height() as IdxSize,
+            true,
+            false,
+        ))
     }
 
     /// Get a mask of all the duplicated rows in the DataFrame.
     pub fn is_duplicated(&self) -> Result<BooleanChunked> {
         let mut gb = self.groupby(self.get_column_names())?;
         let groups = std::mem::take(&mut gb.groups);
-        Ok(is_unique_helper(groups, self.height() as u32, false, true))
+        Ok(is_unique_helper(
+            groups,
+            self.height() as IdxSize,
+            false,
+            true,
+        ))
     }
 
-    /// Create a new DataFrame that shows the null counts per column.
-    pub fn null_count(&self) -> Self {
-        let cols = self
-            .columns
-            .iter()
-            .map(|s| Series::new(s.name(), &[s.null_count() as u32]))
-            .collect();
-        Self::new_no_checks(cols)
+    /// Hash every row with a fixed `seed`. See [Series::hash](crate::series::Series::hash) for
+    /// when the hashes can be compared.
+    pub fn hash_rows(&self, seed: u64) -> Result<UInt64Chunked> {
+        crate::functions::hash_rows(&self.columns, seed)
     }
 }
 
@@ -1397,7 +1831,13 @@ impl std::convert::TryFrom<RecordBatch> for DataFrame {
             .columns()
             .iter()
             .zip(batch.schema().fields())
-            .map(|(arr, field)| Series::try_from((field.name().as_ref(), arr.clone())))
+            .map(|(arr, field)| {
+                let mut s = Series::try_from((field.name().as_ref(), arr.clone()))?;
+                if let Some(metadata) = field.metadata() {
+                    s.set_metadata(metadata.clone());
+                }
+                Ok(s)
+            })
             .collect();
 
         DataFrame::new(columns?)
@@ -1520,6 +1960,66 @@ mod test {
         assert_eq!(df.column("days").unwrap().eq(1).sum(), Some(1));
     }
 
+    #[test]
+    fn test_column_order() -> Result<()> {
+        let mut df = create_frame();
+        df.insert_column(1, Series::new("id", &[1, 2, 3]))?;
+        assert_eq!(df.get_column_names(), &["days", "id", "temp"]);
+        assert!(matches!(
+            df.insert_column(5, Series::new("x", &[1, 2, 3])),
+            Err(PolarsError::OutOfBounds(_))
+        ));
+        assert!(matches!(
+            df.insert_column(0, Series::new("id", &[1, 2, 3])),
+            Err(PolarsError::Duplicate(_))
+        ));
+        df.insert_at_idx(3, Series::new("x", &[1, 2, 3]))?;
+        assert_eq!(df.get_column_names(), &["days", "id", "temp", "x"]);
+        df.drop_in_place("x")?;
+
+        df.move_column("id", 0)?;
+        assert_eq!(df.get_column_names(), &["id", "days", "temp"]);
+        df.move_column("id", 2)?;
+        assert_eq!(df.get_column_names(), &["days", "temp", "id"]);
+        assert!(matches!(
+            df.move_column("id", 3),
+            Err(PolarsError::OutOfBounds(_))
+        ));
+        assert!(matches!(
+            df.move_column("foo", 0),
+            Err(PolarsError::NotFound(_))
+        ));
+
+        df.replace("temp", Series::new("other", &[1.0, 2.0, 3.0]))?;
+        assert_eq!(df.get_column_names(), &["days", "temp", "id"]);
+
+        let ordered = df.select_in_order(&["id", "temp", "days"])?;
+        assert_eq!(ordered.get_column_names(), &["id", "temp", "days"]);
+        assert!(matches!(
+            df.select_in_order(&["id", "id"]),
+            Err(PolarsError::Duplicate(_))
+        ));
+        assert!(matches!(
+            df.select_in_order(&["foo"]),
+            Err(PolarsError::NotFound(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_select_range_and_dtype() -> Result<()> {
+        let df = create_frame();
+        assert_eq!(df.select(1..2)?.get_column_names(), &["temp"]);
+        assert_eq!(df.select(0..=1)?.get_column_names(), &["days", "temp"]);
+        assert_eq!(df.select(1..)?.get_column_names(), &["temp"]);
+        assert_eq!(df.select(..1)?.get_column_names(), &["days"]);
+        assert!(matches!(df.select(1..3), Err(PolarsError::OutOfBounds(_))));
+
+        let selected = df.select(crate::datatypes::DataType::Float64)?;
+        assert_eq!(selected.get_column_names(), &["temp"]);
+        Ok(())
+    }
+
     #[test]
     fn test_filter() {
         let df = create_frame();
@@ -1561,6 +2061,42 @@ mod test {
         println!("{:?}", df);
     }
 
+    #[test]
+    fn test_take_checked() {
+        let df = create_frame();
+        let idx = IdxCa::new_from_slice("idx", &[2, 0]);
+        let out = df.take_checked(&idx).unwrap();
+        assert_eq!(
+            Vec::from(out.column("days").unwrap().i32().unwrap()),
+            &[Some(2), Some(0)]
+        );
+        let idx = IdxCa::new_from_slice("idx", &[0, 3]);
+        assert!(df.take_checked(&idx).is_err());
+        assert!(df.take_iter_checked(vec![1, 2].into_iter()).is_ok());
+        assert!(df.take_iter_checked(vec![1, 5].into_iter()).is_err());
+        assert!(df.column("temp").unwrap().take_checked(&idx).is_err());
+    }
+
+    #[test]
+    fn test_match_schema() {
+        let df = create_frame();
+        // DataType, Field and Schema are the arrow types in this module
+        use crate::datatypes;
+        let schema = datatypes::Schema::new(vec![
+            datatypes::Field::new("temp", datatypes::DataType::Float64),
+            datatypes::Field::new("days", datatypes::DataType::Int64),
+            datatypes::Field::new("city", datatypes::DataType::Utf8),
+        ]);
+        let out = df.match_schema(&schema, false).unwrap();
+        assert_eq!(out.get_column_names(), &["temp", "days", "city"]);
+        assert_eq!(
+            out.column("days").unwrap().dtype(),
+            &datatypes::DataType::Int64
+        );
+        assert_eq!(out.column("city").unwrap().null_count(), 3);
+        assert!(df.match_schema(&schema, true).is_err());
+    }
+
     #[test]
     fn slice() {
         let df = create_frame();
@@ -1577,8 +2113,30 @@ mod test {
             "code" => &["X1", "X2", "X3", "X3", "X2", "X2", "X1", "X1"]
         }
         .unwrap();
-        let dummies = df.to_dummies().unwrap();
+        let dummies = df.to_dummies(None, "_").unwrap();
         dbg!(&dummies);
+        assert_eq!(
+            dummies.get_column_names(),
+            &[
+                "id_1", "id_2", "id_3", "type_A", "type_B", "type_C", "code_X1", "code_X2",
+                "code_X3"
+            ]
+        );
+        let partial = df.to_dummies(Some(&["type"]), ":").unwrap();
+        assert_eq!(
+            partial.get_column_names(),
+            &["id", "type:A", "type:B", "type:C", "code"]
+        );
+
+        // the missing values and the value "null" would get the same dummy column
+        let df = df! {
+            "type" => &[Some("A"), None, Some("null")]
+        }
+        .unwrap();
+        assert!(matches!(
+            df.to_dummies(None, "_"),
+            Err(PolarsError::Duplicate(_))
+        ));
         assert_eq!(
             Vec::from(dummies.column("id_1").unwrap().u8().unwrap()),
             &[
@@ -1629,6 +2187,41 @@ mod test {
         assert!(df.frame_equal(&valid));
     }
 
+    #[test]
+    fn drop_duplicates_maintain_order() -> Result<()> {
+        let df = df! {
+            "a" => [3, 1, 3, 2, 1],
+            "b" => [0, 1, 2, 3, 4]
+        }?;
+        // the first occurrences are kept in their original order
+        let out = df.drop_duplicates(true, Some(&["a".to_string()]))?;
+        assert_eq!(
+            Vec::from(out.column("a")?.i32()?),
+            &[Some(3), Some(1), Some(2)]
+        );
+        assert_eq!(
+            Vec::from(out.column("b")?.i32()?),
+            &[Some(0), Some(1), Some(3)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn drop_duplicates_by_keys() -> Result<()> {
+        let df = df! {
+            "a" => [1.2, 3.5, 1.7, 2.0],
+            "b" => [0, 1, 2, 3]
+        }?;
+        let keys = df.column("a")?.cast::<Int64Type>()?;
+        let out = df.drop_duplicates_by(true, vec![keys])?;
+        assert_eq!(out.get_column_names(), &["a", "b"]);
+        assert_eq!(
+            Vec::from(out.column("b")?.i32()?),
+            &[Some(0), Some(1), Some(3)]
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_vstack() {
         // check that it does not accidentally rechunks
@@ -1642,4 +2235,196 @@ mod test {
         df.vstack_mut(&df.slice(0, 3).unwrap()).unwrap();
         assert_eq!(df.n_chunks().unwrap(), 2)
     }
+
+    #[test]
+    fn test_vstack_null() {
+        let mut df = df! {
+            "int" => [1, 2],
+            "str" => ["a", "b"]
+        }
+        .unwrap();
+        let nulls = DataFrame::new(vec![
+            Series::full_null("int", 2, &DataType::Null).unwrap(),
+            Series::new("str", &["c", "d"]),
+        ])
+        .unwrap();
+
+        df.vstack_mut(&nulls).unwrap();
+        assert_eq!(df.column("int").unwrap().dtype(), &DataType::Int32);
+        assert_eq!(df.column("int").unwrap().null_count(), 2);
+
+        let mut nulls = nulls;
+        nulls.vstack_mut(&df).unwrap();
+        assert_eq!(nulls.column("int").unwrap().dtype(), &DataType::Int32);
+        assert_eq!(nulls.shape(), (6, 2));
+    }
+
+    #[test]
+    fn test_vstack_categorical() -> Result<()> {
+        let s = Series::new("cat", &["a", "b"]).cast::<CategoricalType>()?;
+        let lexical = s.cast_with_datatype(&DataType::Categorical(CategoricalOrdering::Lexical))?;
+        let mut df = DataFrame::new(vec![s.clone()])?;
+        df.vstack_mut(&DataFrame::new(vec![lexical])?)?;
+        assert_eq!(
+            df.column("cat")?.dtype(),
+            &DataType::Categorical(CategoricalOrdering::Lexical)
+        );
+        assert_eq!(df.height(), 4);
+
+        // an enum keeps its categories and enums with different categories can't be stacked
+        let enum_ab = s.cast_to_enum(&["a", "b"], true)?;
+        let mut df = DataFrame::new(vec![s.clone()])?;
+        df.vstack_mut(&DataFrame::new(vec![enum_ab.clone()])?)?;
+        assert_eq!(df.column("cat")?.dtype(), enum_ab.dtype());
+        let enum_ba = DataFrame::new(vec![s.cast_to_enum(&["b", "a"], true)?])?;
+        assert!(DataFrame::new(vec![enum_ab])?.vstack(&enum_ba).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_null_count() -> Result<()> {
+        let mut df = df! {
+            "g" => [1, 1, 2, 2],
+            "a" => [Some(1), None, None, None],
+            "b" => [Some("x"), Some("y"), Some("z"), None]
+        }?;
+        // nulls spread over multiple chunks
+        let other = df.clone();
+        df.vstack_mut(&other)?;
+        df.add_column(Series::full_null("c", 8, &DataType::Null)?)?;
+
+        let nulls = df.null_count();
+        assert_eq!(nulls.shape(), (1, 4));
+        assert_eq!(Vec::from(nulls.column("a")?.idx()?), &[Some(6)]);
+        assert_eq!(Vec::from(nulls.column("b")?.idx()?), &[Some(2)]);
+        assert_eq!(Vec::from(nulls.column("c")?.idx()?), &[Some(8)]);
+
+        let out = df.groupby("g")?.null_count()?.sort("g", false)?;
+        assert_eq!(
+            Vec::from(out.column("a_null_count")?.idx()?),
+            &[Some(2), Some(4)]
+        );
+        assert_eq!(
+            Vec::from(out.column("b_null_count")?.idx()?),
+            &[Some(0), Some(2)]
+        );
+        // a Null column has no validity bitmap, all its values are null
+        assert_eq!(
+            Vec::from(out.column("c_null_count")?.idx()?),
+            &[Some(4), Some(4)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_rename_with() -> Result<()> {
+        let mut df = df! {
+            "Sepal Width" => [1.0, 2.0],
+            "sepalLength" => [3.0, 4.0],
+            "HTTPStatus" => [200, 404],
+            "__id." => [1, 2]
+        }?;
+        df.rename_snake_case()?;
+        assert_eq!(
+            df.get_column_names(),
+            &["sepal_width", "sepal_length", "http_status", "id"]
+        );
+
+        df.add_prefix("iris_").add_suffix("_v1");
+        assert_eq!(df.get_column_names()[0], "iris_sepal_width_v1");
+
+        assert!(df.rename_with(|_| "a".to_string()).is_err());
+        assert_eq!(df.get_column_names()[0], "iris_sepal_width_v1");
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty_with_schema() -> Result<()> {
+        use crate::prelude::{DataType, Field, Schema};
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32),
+            Field::new("name", DataType::Utf8),
+            Field::new("values", DataType::List(ArrowDataType::Int64)),
+            Field::new("cat", DataType::Categorical(CategoricalOrdering::Physical)),
+        ]);
+        let empty = DataFrame::empty_with_schema(&schema)?;
+        assert_eq!(empty.shape(), (0, 4));
+        assert_eq!(empty.schema(), schema);
+
+        let values = Series::new("values", &[1i64, 2]);
+        let df = DataFrame::new(vec![
+            Series::new("id", &[1, 2]),
+            Series::new("name", &["a", "b"]),
+            Series::new("values", &[values.clone(), values]),
+        ])?;
+        let empty = empty.drop("cat")?;
+        let stacked = empty.vstack(&df)?;
+        assert_eq!(stacked.shape(), (2, 3));
+        assert_eq!(stacked.schema(), df.schema());
+
+        let joined = df.inner_join(&empty, "id", "id")?;
+        assert_eq!(joined.height(), 0);
+        assert_eq!(joined.width(), 5);
+
+        assert_eq!(
+            Series::full_null("a", 2, &DataType::List(ArrowDataType::Utf8))?.null_count(),
+            2
+        );
+        assert!(Series::new_empty("a", &DataType::List(ArrowDataType::Null)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_df_macro() -> Result<()> {
+        use crate::prelude::DataType;
+        let df = df! {
+            "opt" => [Some(1), None, Some(3)],
+            "str" => [Some("a"), Some("b"), None],
+            "cast" => [1, 2, 3] => DataType::Float64,
+            "list" => [vec![1, 2], vec![], vec![3]],
+            "opt_list" => [vec![Some("a"), None], vec![], vec![None]],
+        }?;
+        assert_eq!(df.shape(), (3, 5));
+        assert_eq!(df.column("opt")?.null_count(), 1);
+        assert_eq!(df.column("str")?.null_count(), 1);
+        assert_eq!(df.column("cast")?.dtype(), &DataType::Float64);
+        assert_eq!(
+            df.column("list")?.dtype(),
+            &DataType::List(ArrowDataType::Int32)
+        );
+        assert_eq!(
+            df.column("opt_list")?.dtype(),
+            &DataType::List(ArrowDataType::Utf8)
+        );
+        let list = df.column("list")?.list()?;
+        assert_eq!(
+            list.into_iter()
+                .map(|s| s.unwrap().len())
+                .collect::<Vec<_>>(),
+            &[2, 0, 1]
+        );
+
+        let err = df! {"a" => [1, 2], "b" => [1]}.unwrap_err();
+        assert!(format!("{:?}", err).contains("'b'"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_hstack_broadcast() -> Result<()> {
+        let mut df = create_frame();
+        let list = Series::new("list", &[Series::new("", &[1, 2])]);
+        let out = df.hstack(&[Series::new("unit", &["celsius"]), list])?;
+        assert_eq!(out.shape(), (3, 4));
+        assert_eq!(
+            Vec::from(out.column("unit")?.utf8()?),
+            &[Some("celsius"); 3]
+        );
+        assert_eq!(out.column("list")?.list()?.into_iter().count(), 3);
+
+        df.hstack_mut(&[Series::new("offset", &[10])])?;
+        assert_eq!(Vec::from(df.column("offset")?.i32()?), &[Some(10); 3]);
+        // only Series of length 1 are broadcast
+        assert!(df.hstack(&[Series::new("two", &[1, 2])]).is_err());
+        Ok(())
+    }
 }
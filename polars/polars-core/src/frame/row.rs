@@ -2,7 +2,13 @@ use crate::prelude::*;
 use itertools::Itertools;
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct Row<'a>(Vec<AnyValue<'a>>);
+pub struct Row<'a>(pub Vec<AnyValue<'a>>);
+
+impl<'a> Row<'a> {
+    pub fn new(values: Vec<AnyValue<'a>>) -> Self {
+        Row(values)
+    }
+}
 
 impl DataFrame {
     /// Get a row from a DataFrame. Use of this is discouraged as it will likely be slow.
@@ -38,4 +44,352 @@ impl DataFrame {
                 *any_val = s.get_unchecked(idx);
             });
     }
+
+    /// Create a DataFrame from rows of values. Every row should have a value of the data type of
+    /// the field, or a null, for every field of `schema`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    /// use polars_core::frame::row::Row;
+    ///
+    /// let schema = Schema::new(vec![
+    ///     Field::new("id", DataType::UInt32),
+    ///     Field::new("event", DataType::Utf8),
+    /// ]);
+    /// let rows = [
+    ///     Row::new(vec![AnyValue::UInt32(1), AnyValue::Utf8("click")]),
+    ///     Row::new(vec![AnyValue::UInt32(2), AnyValue::Null]),
+    /// ];
+    /// let df = DataFrame::from_rows_and_schema(&rows, &schema).unwrap();
+    /// assert_eq!(df.shape(), (2, 2));
+    /// ```
+    pub fn from_rows_and_schema(rows: &[Row], schema: &Schema) -> Result<Self> {
+        let mut buffer = RowBuffer::new(schema.clone(), rows.len())?;
+        buffer.extend_from_slice_of_rows(rows)?;
+        Ok(buffer.flush())
+    }
+
+    /// Append a row to the DataFrame. The schema of the DataFrame doesn't change, the values of
+    /// the row should have the data types of the columns.
+    ///
+    /// Every call appends a chunk, to accumulate many rows use a [RowBuffer] and append its
+    /// flushed DataFrames instead.
+    pub fn push_row(&mut self, row: &Row) -> Result<()> {
+        self.extend_from_slice_of_rows(std::slice::from_ref(row))
+    }
+
+    /// Append rows to the DataFrame. The schema of the DataFrame doesn't change, the values of
+    /// the rows should have the data types of the columns.
+    pub fn extend_from_slice_of_rows(&mut self, rows: &[Row]) -> Result<()> {
+        let other = DataFrame::from_rows_and_schema(rows, &self.schema())?;
+        self.vstack_mut(&other)?;
+        Ok(())
+    }
+}
+
+/// Builder of a single column of a [RowBuffer]. Values are appended as their physical type,
+/// and cast to the data type of the field when the buffer is flushed.
+enum AnyValueBuffer {
+    Boolean(BooleanChunkedBuilder),
+    Int32(PrimitiveChunkedBuilder<Int32Type>),
+    Int64(PrimitiveChunkedBuilder<Int64Type>),
+    UInt32(PrimitiveChunkedBuilder<UInt32Type>),
+    UInt64(PrimitiveChunkedBuilder<UInt64Type>),
+    Float32(PrimitiveChunkedBuilder<Float32Type>),
+    Float64(PrimitiveChunkedBuilder<Float64Type>),
+    Utf8(Utf8ChunkedBuilder),
+}
+
+impl AnyValueBuffer {
+    fn new(field: &Field, capacity: usize) -> Result<Self> {
+        use DataType::*;
+        let name = field.name();
+        let buf = match field.data_type() {
+            Boolean => AnyValueBuffer::Boolean(BooleanChunkedBuilder::new(name, capacity)),
+            Int8 | Int16 | Int32 | Date32 => {
+                AnyValueBuffer::Int32(PrimitiveChunkedBuilder::new(name, capacity))
+            }
+            Int64 | Date64 | Time64(_) | Duration(_) => {
+                AnyValueBuffer::Int64(PrimitiveChunkedBuilder::new(name, capacity))
+            }
+            UInt8 | UInt16 | UInt32 => {
+                AnyValueBuffer::UInt32(PrimitiveChunkedBuilder::new(name, capacity))
+            }
+            UInt64 => AnyValueBuffer::UInt64(PrimitiveChunkedBuilder::new(name, capacity)),
+            Float32 => AnyValueBuffer::Float32(PrimitiveChunkedBuilder::new(name, capacity)),
+            Float64 => AnyValueBuffer::Float64(PrimitiveChunkedBuilder::new(name, capacity)),
+            Utf8 | Categorical(_) => {
+                AnyValueBuffer::Utf8(Utf8ChunkedBuilder::new(name, capacity, capacity * 5))
+            }
+            dt => {
+                return Err(PolarsError::InvalidOperation(
+                    format!(
+                        "cannot buffer rows of column {} of type {:?}",
+                        field.name(),
+                        dt
+                    )
+                    .into(),
+                ))
+            }
+        };
+        Ok(buf)
+    }
+
+    /// Check if `val` can be appended without a cast.
+    fn accepts(&self, val: &AnyValue) -> bool {
+        use AnyValueBuffer::*;
+        matches!(
+            (self, val),
+            (_, AnyValue::Null)
+                | (Boolean(_), AnyValue::Boolean(_))
+                | (Int32(_), AnyValue::Int8(_))
+                | (Int32(_), AnyValue::Int16(_))
+                | (Int32(_), AnyValue::Int32(_))
+                | (Int32(_), AnyValue::Date32(_))
+                | (Int64(_), AnyValue::Int64(_))
+                | (Int64(_), AnyValue::Date64(_))
+                | (Int64(_), AnyValue::Time64(_, _))
+                | (Int64(_), AnyValue::Duration(_, _))
+                | (UInt32(_), AnyValue::UInt8(_))
+                | (UInt32(_), AnyValue::UInt16(_))
+                | (UInt32(_), AnyValue::UInt32(_))
+                | (UInt64(_), AnyValue::UInt64(_))
+                | (Float32(_), AnyValue::Float32(_))
+                | (Float64(_), AnyValue::Float64(_))
+                | (Utf8(_), AnyValue::Utf8(_))
+        )
+    }
+
+    /// Append a value that is [accepted](AnyValueBuffer::accepts).
+    fn add(&mut self, val: &AnyValue) {
+        use AnyValueBuffer::*;
+        match (self, val) {
+            (Boolean(builder), AnyValue::Null) => builder.append_null(),
+            (Int32(builder), AnyValue::Null) => builder.append_null(),
+            (Int64(builder), AnyValue::Null) => builder.append_null(),
+            (UInt32(builder), AnyValue::Null) => builder.append_null(),
+            (UInt64(builder), AnyValue::Null) => builder.append_null(),
+            (Float32(builder), AnyValue::Null) => builder.append_null(),
+            (Float64(builder), AnyValue::Null) => builder.append_null(),
+            (Utf8(builder), AnyValue::Null) => builder.append_null(),
+            (Boolean(builder), AnyValue::Boolean(v)) => builder.append_value(*v),
+            (Int32(builder), AnyValue::Int8(v)) => builder.append_value(*v as i32),
+            (Int32(builder), AnyValue::Int16(v)) => builder.append_value(*v as i32),
+            (Int32(builder), AnyValue::Int32(v)) => builder.append_value(*v),
+            (Int32(builder), AnyValue::Date32(v)) => builder.append_value(*v),
+            (Int64(builder), AnyValue::Int64(v)) => builder.append_value(*v),
+            (Int64(builder), AnyValue::Date64(v)) => builder.append_value(*v),
+            (Int64(builder), AnyValue::Time64(v, _)) => builder.append_value(*v),
+            (Int64(builder), AnyValue::Duration(v, _)) => builder.append_value(*v),
+            (UInt32(builder), AnyValue::UInt8(v)) => builder.append_value(*v as u32),
+            (UInt32(builder), AnyValue::UInt16(v)) => builder.append_value(*v as u32),
+            (UInt32(builder), AnyValue::UInt32(v)) => builder.append_value(*v),
+            (UInt64(builder), AnyValue::UInt64(v)) => builder.append_value(*v),
+            (Float32(builder), AnyValue::Float32(v)) => builder.append_value(*v),
+            (Float64(builder), AnyValue::Float64(v)) => builder.append_value(*v),
+            (Utf8(builder), AnyValue::Utf8(v)) => builder.append_value(v),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Finish the buffered values and start a new buffer with room for `capacity` values.
+    fn reset(&mut self, field: &Field, capacity: usize) -> Series {
+        let new = AnyValueBuffer::new(field, capacity).unwrap();
+        match std::mem::replace(self, new) {
+            AnyValueBuffer::Boolean(builder) => builder.finish().into_series(),
+            AnyValueBuffer::Int32(builder) => builder.finish().into_series(),
+            AnyValueBuffer::Int64(builder) => builder.finish().into_series(),
+            AnyValueBuffer::UInt32(builder) => builder.finish().into_series(),
+            AnyValueBuffer::UInt64(builder) => builder.finish().into_series(),
+            AnyValueBuffer::Float32(builder) => builder.finish().into_series(),
+            AnyValueBuffer::Float64(builder) => builder.finish().into_series(),
+            AnyValueBuffer::Utf8(builder) => builder.finish().into_series(),
+        }
+    }
+}
+
+/// Accumulates rows in column builders with a fixed schema, e.g. to collect events before they
+/// are written periodically. This is much cheaper than stacking single row DataFrames.
+///
+/// A [flush](RowBuffer::flush) returns the buffered rows as a DataFrame, after which the builders
+/// are reused with a capacity for as many rows as the last flush.
+///
+/// # Example
+///
+/// ```
+/// use polars_core::prelude::*;
+/// use polars_core::frame::row::{Row, RowBuffer};
+///
+/// let schema = Schema::new(vec![
+///     Field::new("ts", DataType::Int64),
+///     Field::new("value", DataType::Float64),
+/// ]);
+/// let mut buffer = RowBuffer::new(schema, 1024).unwrap();
+/// buffer.push_row(&Row::new(vec![AnyValue::Int64(1), AnyValue::Float64(0.5)])).unwrap();
+/// buffer.push_row(&Row::new(vec![AnyValue::Int64(2), AnyValue::Null])).unwrap();
+/// let df = buffer.flush();
+/// assert_eq!(df.shape(), (2, 2));
+/// assert!(buffer.is_empty());
+/// ```
+pub struct RowBuffer {
+    schema: Schema,
+    buffers: Vec<AnyValueBuffer>,
+    capacity: usize,
+    len: usize,
+}
+
+impl RowBuffer {
+    /// Create a buffer for rows of `schema` with room for `capacity` rows.
+    /// Returns an error if a data type of the schema cannot be buffered.
+    pub fn new(schema: Schema, capacity: usize) -> Result<Self> {
+        let buffers = schema
+            .fields()
+            .iter()
+            .map(|fld| AnyValueBuffer::new(fld, capacity))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(RowBuffer {
+            schema,
+            buffers,
+            capacity,
+            len: 0,
+        })
+    }
+
+    /// The locked schema of the buffered rows.
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    /// Number of buffered rows.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Append a row. Returns an error, and appends nothing, if the row doesn't have a value of
+    /// the data type of the field, or a null, for every field of the schema.
+    pub fn push_row(&mut self, row: &Row) -> Result<()> {
+        if row.0.len() != self.buffers.len() {
+            return Err(PolarsError::ShapeMisMatch(
+                format!(
+                    "row has {} values, but the schema has {} fields",
+                    row.0.len(),
+                    self.buffers.len()
+                )
+                .into(),
+            ));
+        }
+        for ((buf, val), fld) in self.buffers.iter().zip(&row.0).zip(self.schema.fields()) {
+            if !buf.accepts(val) {
+                return Err(PolarsError::DataTypeMisMatch(
+                    format!(
+                        "cannot append {:?} to column {} of type {:?}",
+                        val,
+                        fld.name(),
+                        fld.data_type()
+                    )
+                    .into(),
+                ));
+            }
+        }
+        self.buffers
+            .iter_mut()
+            .zip(&row.0)
+            .for_each(|(buf, val)| buf.add(val));
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Append rows. The rows before the first invalid row are appended.
+    pub fn extend_from_slice_of_rows(&mut self, rows: &[Row]) -> Result<()> {
+        rows.iter().try_for_each(|row| self.push_row(row))
+    }
+
+    /// Take the buffered rows as a DataFrame with the locked schema.
+    pub fn flush(&mut self) -> DataFrame {
+        self.capacity = std::cmp::max(self.capacity, self.len);
+        let capacity = self.capacity;
+        let columns = self
+            .buffers
+            .iter_mut()
+            .zip(self.schema.fields())
+            .map(|(buf, fld)| {
+                let s = buf.reset(fld, capacity);
+                if s.dtype() == fld.data_type() {
+                    s
+                } else {
+                    s.cast_with_datatype(fld.data_type()).unwrap()
+                }
+            })
+            .collect();
+        self.len = 0;
+        DataFrame::new_no_checks(columns)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_row_buffer() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::UInt16),
+            Field::new("event", DataType::Utf8),
+            Field::new("value", DataType::Float64),
+        ]);
+        let mut buffer = RowBuffer::new(schema.clone(), 2)?;
+        buffer.extend_from_slice_of_rows(&[
+            Row::new(vec![
+                AnyValue::UInt16(1),
+                AnyValue::Utf8("a"),
+                AnyValue::Float64(1.0),
+            ]),
+            Row::new(vec![AnyValue::UInt16(2), AnyValue::Null, AnyValue::Null]),
+        ])?;
+        // rows with a wrong width or data type are not appended
+        assert!(buffer
+            .push_row(&Row::new(vec![AnyValue::UInt16(3), AnyValue::Utf8("c")]))
+            .is_err());
+        assert!(buffer
+            .push_row(&Row::new(vec![
+                AnyValue::UInt16(3),
+                AnyValue::Utf8("c"),
+                AnyValue::Int32(1)
+            ]))
+            .is_err());
+        assert_eq!(buffer.len(), 2);
+
+        let mut df = buffer.flush();
+        assert_eq!(df.schema(), schema);
+        assert_eq!(Vec::from(df.column("event")?.utf8()?), &[Some("a"), None]);
+        assert!(buffer.is_empty());
+
+        buffer.push_row(&Row::new(vec![
+            AnyValue::UInt16(3),
+            AnyValue::Utf8("c"),
+            AnyValue::Float64(3.0),
+        ]))?;
+        df.vstack_mut(&buffer.flush())?;
+        df.push_row(&Row::new(vec![
+            AnyValue::UInt16(4),
+            AnyValue::Utf8("d"),
+            AnyValue::Null,
+        ]))?;
+        assert_eq!(df.schema(), schema);
+        assert_eq!(
+            Vec::from(df.column("value")?.f64()?),
+            &[Some(1.0), None, Some(3.0), None]
+        );
+        assert_eq!(
+            Vec::from(df.column("id")?.u16()?),
+            &[Some(1), Some(2), Some(3), Some(4)]
+        );
+        Ok(())
+    }
 }
@@ -1,21 +1,43 @@
-#[allow(clippy::wrong_self_convention)]
+use crate::prelude::*;
+use std::ops::{Range, RangeFrom, RangeInclusive, RangeTo};
+
+/// A selection of the columns of a DataFrame: by name, by index range or by data type.
 pub trait Selection<'a, S> {
+    /// Select the columns of `df`.
+    fn select_from(self, df: &DataFrame) -> Result<Vec<Series>>;
+}
+
+/// A selection of columns by name.
+#[allow(clippy::wrong_self_convention)]
+pub trait NameSelection<'a, S> {
     fn to_selection_vec(self) -> Vec<&'a str>;
 }
 
-impl<'a> Selection<'a, &str> for &'a str {
+impl<'a, S, T> Selection<'a, S> for T
+where
+    T: NameSelection<'a, S>,
+{
+    fn select_from(self, df: &DataFrame) -> Result<Vec<Series>> {
+        self.to_selection_vec()
+            .iter()
+            .map(|name| df.column(name).map(|s| s.clone()))
+            .collect()
+    }
+}
+
+impl<'a> NameSelection<'a, &str> for &'a str {
     fn to_selection_vec(self) -> Vec<&'a str> {
         vec![self]
     }
 }
 
-impl<'a> Selection<'a, &str> for Vec<&'a str> {
+impl<'a> NameSelection<'a, &str> for Vec<&'a str> {
     fn to_selection_vec(self) -> Vec<&'a str> {
         self
     }
 }
 
-impl<'a, T, S: 'a> Selection<'a, S> for &'a T
+impl<'a, T, S: 'a> NameSelection<'a, S> for &'a T
 where
     T: AsRef<[S]>,
     S: AsRef<str>,
@@ -25,31 +47,84 @@ where
     }
 }
 
-impl<'a> Selection<'a, &str> for (&'a str, &'a str) {
+impl<'a> NameSelection<'a, &str> for (&'a str, &'a str) {
     fn to_selection_vec(self) -> Vec<&'a str> {
         vec![self.0, self.1]
     }
 }
-impl<'a> Selection<'a, &str> for (&'a str, &'a str, &'a str) {
+impl<'a> NameSelection<'a, &str> for (&'a str, &'a str, &'a str) {
     fn to_selection_vec(self) -> Vec<&'a str> {
         vec![self.0, self.1, self.2]
     }
 }
 
-impl<'a> Selection<'a, &str> for (&'a str, &'a str, &'a str, &'a str) {
+impl<'a> NameSelection<'a, &str> for (&'a str, &'a str, &'a str, &'a str) {
     fn to_selection_vec(self) -> Vec<&'a str> {
         vec![self.0, self.1, self.2, self.3]
     }
 }
 
-impl<'a> Selection<'a, &str> for (&'a str, &'a str, &'a str, &'a str, &'a str) {
+impl<'a> NameSelection<'a, &str> for (&'a str, &'a str, &'a str, &'a str, &'a str) {
     fn to_selection_vec(self) -> Vec<&'a str> {
         vec![self.0, self.1, self.2, self.3, self.4]
     }
 }
 
-impl<'a> Selection<'a, &str> for (&'a str, &'a str, &'a str, &'a str, &'a str, &'a str) {
+impl<'a> NameSelection<'a, &str> for (&'a str, &'a str, &'a str, &'a str, &'a str, &'a str) {
     fn to_selection_vec(self) -> Vec<&'a str> {
         vec![self.0, self.1, self.2, self.3, self.4, self.5]
     }
 }
+
+fn select_index_range(df: &DataFrame, start: usize, end: usize) -> Result<Vec<Series>> {
+    df.get_columns()
+        .get(start..end)
+        .map(|columns| columns.to_vec())
+        .ok_or_else(|| {
+            PolarsError::OutOfBounds(
+                format!(
+                    "cannot select columns {}..{} of a DataFrame with {} columns",
+                    start,
+                    end,
+                    df.width()
+                )
+                .into(),
+            )
+        })
+}
+
+impl<'a> Selection<'a, usize> for Range<usize> {
+    fn select_from(self, df: &DataFrame) -> Result<Vec<Series>> {
+        select_index_range(df, self.start, self.end)
+    }
+}
+
+impl<'a> Selection<'a, usize> for RangeInclusive<usize> {
+    fn select_from(self, df: &DataFrame) -> Result<Vec<Series>> {
+        select_index_range(df, *self.start(), self.end().saturating_add(1))
+    }
+}
+
+impl<'a> Selection<'a, usize> for RangeFrom<usize> {
+    fn select_from(self, df: &DataFrame) -> Result<Vec<Series>> {
+        select_index_range(df, self.start, df.width())
+    }
+}
+
+impl<'a> Selection<'a, usize> for RangeTo<usize> {
+    fn select_from(self, df: &DataFrame) -> Result<Vec<Series>> {
+        select_index_range(df, 0, self.end)
+    }
+}
+
+/// Select all columns of this data type.
+impl<'a> Selection<'a, DataType> for DataType {
+    fn select_from(self, df: &DataFrame) -> Result<Vec<Series>> {
+        Ok(df
+            .get_columns()
+            .iter()
+            .filter(|s| s.dtype() == &self)
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,102 @@
+use crate::frame::group_by::GroupTuples;
+use crate::prelude::*;
+
+impl DataFrame {
+    /// Assign session ids to the rows, e.g. to group the clicks of a user into visits.
+    ///
+    /// Within every partition of `partition_by` the rows are visited in row order and a new
+    /// session starts when the time between two consecutive rows is larger than `gap`. Session ids
+    /// start at 0 in every partition. The `time_col` must be sorted within every partition;
+    /// rows with a null time get a null session id.
+    ///
+    /// The `gap` is in the units of `time_col`, i.e. days for `Date32` and milliseconds for `Date64`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    ///
+    /// fn sessionize(clicks: &DataFrame) -> Result<DataFrame> {
+    ///     // a new session after 30 minutes of inactivity
+    ///     let sessions = clicks.session_id("time", 30 * 60 * 1000, &["user"])?;
+    ///     clicks.with_column(sessions)
+    /// }
+    /// ```
+    pub fn session_id(&self, time_col: &str, gap: i64, partition_by: &[&str]) -> Result<Series> {
+        if gap < 0 {
+            return Err(PolarsError::ValueError(
+                format!("session gap should not be negative, got {}", gap).into(),
+            ));
+        }
+        let height = self.height();
+        let time = self
+            .column(time_col)?
+            .cast_with_datatype(&DataType::Int64)?;
+        let time: Vec<Option<i64>> = time.i64()?.into_iter().collect();
+
+        let groups: GroupTuples = if partition_by.is_empty() || height == 0 {
+            vec![(0, (0..height as IdxSize).collect())]
+        } else {
+            let mut gb = self.groupby(partition_by.to_vec())?;
+            std::mem::take(&mut gb.groups)
+        };
+
+        let mut ids = vec![None; height];
+        for (_first, idx) in &groups {
+            let mut session = 0u32;
+            let mut prev = None;
+            // the indexes of a group are in row order
+            for &row in idx {
+                if let Some(t) = time[row as usize] {
+                    if let Some(prev) = prev {
+                        if t < prev {
+                            return Err(PolarsError::InvalidOperation(
+                                format!(
+                                    "column {} should be sorted within every partition to determine sessions",
+                                    time_col
+                                )
+                                .into(),
+                            ));
+                        }
+                        if t.saturating_sub(prev) > gap {
+                            session += 1;
+                        }
+                    }
+                    prev = Some(t);
+                    ids[row as usize] = Some(session);
+                }
+            }
+        }
+        let mut ca: UInt32Chunked = ids.into_iter().collect();
+        ca.rename("session_id");
+        Ok(ca.into_series())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_session_id() -> Result<()> {
+        let df = df![
+            "user" => &["a", "b", "a", "a", "b", "a"],
+            "time" => &[Some(0), Some(5), Some(10), Some(100), Some(200), None]
+        ]?;
+
+        let sessions = df.session_id("time", 30, &["user"])?;
+        assert_eq!(
+            Vec::from(sessions.u32()?),
+            &[Some(0), Some(0), Some(0), Some(1), Some(1), None]
+        );
+        let sessions = df.session_id("time", 30, &[])?;
+        assert_eq!(
+            Vec::from(sessions.u32()?),
+            &[Some(0), Some(0), Some(0), Some(1), Some(2), None]
+        );
+
+        let unsorted = df.sort("time", true)?;
+        assert!(unsorted.session_id("time", 30, &["user"]).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+use crate::prelude::*;
+
+/// Set operations on the rows of two DataFrames. All of them have distinct semantics,
+/// like `UNION`, `INTERSECT` and `EXCEPT` in SQL.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SetOperation {
+    /// Rows that are in either DataFrame.
+    Union,
+    /// Rows that are in both DataFrames.
+    Intersect,
+    /// Rows of the left DataFrame that are not in the right DataFrame.
+    Difference,
+    /// Rows that are in exactly one of the DataFrames.
+    SymmetricDifference,
+}
+
+impl DataFrame {
+    /// Apply a set operation on the rows of this DataFrame and `other`.
+    ///
+    /// The columns are matched by name, so both DataFrames should have the same column names
+    /// and data types, but the columns of `other` may be in a different order. Rows are compared
+    /// on all columns and null values are considered equal to each other. The output has no
+    /// duplicate rows and the rows are in order of first occurrence, where the rows of `self`
+    /// come before the rows of `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    ///
+    /// fn new_customers(today: &DataFrame, yesterday: &DataFrame) -> Result<DataFrame> {
+    ///     today.set_operation(yesterday, SetOperation::Difference)
+    /// }
+    /// ```
+    pub fn set_operation(&self, other: &DataFrame, op: SetOperation) -> Result<DataFrame> {
+        let names = self.get_column_names();
+        if names.is_empty() {
+            return Err(PolarsError::NoData(
+                "cannot apply a set operation on a DataFrame without columns".into(),
+            ));
+        }
+        if self.width() != other.width() {
+            return Err(PolarsError::ShapeMisMatch(
+                format!(
+                    "cannot apply a set operation on DataFrames with {} and {} columns",
+                    self.width(),
+                    other.width()
+                )
+                .into(),
+            ));
+        }
+        // line up the columns of the right DataFrame by name
+        let other = other.select(&names)?;
+        let n_left = self.height() as IdxSize;
+        let stacked = self.vstack(&other)?;
+
+        let mut gb = stacked.groupby(names)?;
+        let groups = std::mem::take(&mut gb.groups);
+        let mut idx = groups
+            .into_iter()
+            .filter_map(|(first, rows)| {
+                let in_left = rows.iter().any(|&i| i < n_left);
+                let in_right = rows.iter().any(|&i| i >= n_left);
+                let keep = match op {
+                    SetOperation::Union => true,
+                    SetOperation::Intersect => in_left && in_right,
+                    SetOperation::Difference => !in_right,
+                    SetOperation::SymmetricDifference => in_left != in_right,
+                };
+                if keep {
+                    Some(first)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        idx.sort_unstable();
+
+        // Safety:
+        // the group indexes are in bounds of the stacked DataFrame.
+        Ok(unsafe { stacked.take_iter_unchecked(idx.into_iter().map(|i| i as usize)) })
+    }
+
+    /// Get the distinct rows that are in this DataFrame or in `other`.
+    /// See [set_operation](DataFrame::set_operation).
+    pub fn union(&self, other: &DataFrame) -> Result<DataFrame> {
+        self.set_operation(other, SetOperation::Union)
+    }
+
+    /// Get the distinct rows that are both in this DataFrame and in `other`.
+    /// See [set_operation](DataFrame::set_operation).
+    pub fn intersect(&self, other: &DataFrame) -> Result<DataFrame> {
+        self.set_operation(other, SetOperation::Intersect)
+    }
+
+    /// Get the distinct rows of this DataFrame that are not in `other`.
+    /// See [set_operation](DataFrame::set_operation).
+    pub fn difference(&self, other: &DataFrame) -> Result<DataFrame> {
+        self.set_operation(other, SetOperation::Difference)
+    }
+
+    /// Get the distinct rows of this DataFrame that are not in `other`, like `EXCEPT` in SQL.
+    ///
+    /// With `on`, a row is removed if `other` has a row with equal values in the `on` columns,
+    /// and `other` only needs to have these columns. Otherwise rows are compared on all columns.
+    /// The rows keep their order and null values are considered equal to each other.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    ///
+    /// fn not_shipped(orders: &DataFrame, shipments: &DataFrame) -> Result<DataFrame> {
+    ///     orders.except(shipments, Some(&["order_id"]))
+    /// }
+    /// ```
+    pub fn except(&self, other: &DataFrame, on: Option<&[&str]>) -> Result<DataFrame> {
+        let on = match on {
+            Some(on) => on.to_vec(),
+            None => self.get_column_names(),
+        };
+        self.anti_join(other, &on, &on)?.drop_duplicates(true, None)
+    }
+
+    /// Get the distinct rows that are in only one of this DataFrame and `other`.
+    /// See [set_operation](DataFrame::set_operation).
+    pub fn symmetric_difference(&self, other: &DataFrame) -> Result<DataFrame> {
+        self.set_operation(other, SetOperation::SymmetricDifference)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_set_operations() -> Result<()> {
+        let df_a = df![
+            "a" => &[Some(1), Some(2), None, Some(2)],
+            "b" => &["x", "y", "z", "y"]
+        ]?;
+        // same schema, different column order
+        let df_b = df![
+            "b" => &["z", "y", "w"],
+            "a" => &[None, Some(2), Some(3)]
+        ]?;
+
+        let out = df_a.union(&df_b)?;
+        assert_eq!(
+            Vec::from(out.column("a")?.i32()?),
+            &[Some(1), Some(2), None, Some(3)]
+        );
+        let out = df_a.intersect(&df_b)?;
+        assert_eq!(Vec::from(out.column("a")?.i32()?), &[Some(2), None]);
+        let out = df_a.difference(&df_b)?;
+        assert_eq!(Vec::from(out.column("b")?.utf8()?), &[Some("x")]);
+        let out = df_a.symmetric_difference(&df_b)?;
+        assert_eq!(Vec::from(out.column("b")?.utf8()?), &[Some("x"), Some("w")]);
+
+        let out = df_a.except(&df_b, None)?;
+        assert!(out.frame_equal(&df_a.difference(&df_b)?));
+        let df_d = df!["a" => &[Some(1), None]]?;
+        let out = df_a.except(&df_d, Some(&["a"]))?;
+        assert_eq!(Vec::from(out.column("b")?.utf8()?), &[Some("y")]);
+
+        let df_c = df!["a" => &[1], "c" => &["x"]]?;
+        assert!(df_a.union(&df_c).is_err());
+        assert!(df_a.except(&df_c, None).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+use crate::prelude::*;
+
+/// Suffix of the joined columns of the other DataFrame in [DataFrame::update].
+const UPDATE_SUFFIX: &str = "__POLARS_UPDATE_RIGHT";
+
+/// Which value [DataFrame::update] keeps when both DataFrames have a value for a row.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UpdateStrategy {
+    /// Keep the values of the left DataFrame. The other DataFrame only fills its nulls.
+    PreferLeft,
+    /// Overwrite with the values of the other DataFrame. Its nulls don't overwrite.
+    PreferRight,
+}
+
+impl Default for UpdateStrategy {
+    fn default() -> Self {
+        UpdateStrategy::PreferRight
+    }
+}
+
+impl UpdateStrategy {
+    /// Combine the `left` column with the joined `right` column of equal data type.
+    pub fn coalesce(self, left: &Series, right: &Series) -> Result<Series> {
+        let mut out = match self {
+            UpdateStrategy::PreferLeft => left.zip_with(&left.is_not_null(), right)?,
+            UpdateStrategy::PreferRight => right.zip_with(&right.is_not_null(), left)?,
+        };
+        out.rename(left.name());
+        Ok(out)
+    }
+}
+
+impl DataFrame {
+    /// Update the values of `self` with the values of the rows of `other` that have equal
+    /// values in the `on` columns. The columns of `other` that are not in `self` are ignored,
+    /// and so are the rows of `other` without a matching row in `self`. The rows of `self` keep
+    /// their order.
+    ///
+    /// This is a left join that coalesces every common column according to `strategy`. The keys
+    /// of `other` must be unique, otherwise an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    /// fn apply_corrections(prices: &DataFrame, corrections: &DataFrame) -> Result<DataFrame> {
+    ///     prices.update(corrections, &["date", "ticker"], UpdateStrategy::PreferRight)
+    /// }
+    /// ```
+    pub fn update(
+        &self,
+        other: &DataFrame,
+        on: &[&str],
+        strategy: UpdateStrategy,
+    ) -> Result<DataFrame> {
+        let update_names: Vec<&str> = other
+            .get_column_names()
+            .into_iter()
+            .filter(|name| !on.contains(name) && self.column(name).is_ok())
+            .collect();
+        let right_names: Vec<&str> = on.iter().chain(&update_names).copied().collect();
+        let right = other.select(right_names)?;
+
+        let joined = self.join(
+            &right,
+            on.to_vec(),
+            on.to_vec(),
+            JoinArgs::new(JoinType::Left)
+                .with_suffix(Some(UPDATE_SUFFIX.to_string()))
+                .with_validation(JoinValidation::ManyToOne),
+        )?;
+
+        let columns = self
+            .get_columns()
+            .iter()
+            .map(|s| {
+                let name = s.name();
+                if update_names.contains(&name) {
+                    let left = joined.column(name)?;
+                    let right = joined.column(&format!("{}{}", name, UPDATE_SUFFIX))?;
+                    let right = if right.dtype() == left.dtype() {
+                        right.clone()
+                    } else {
+                        right.cast_with_datatype(left.dtype())?
+                    };
+                    strategy.coalesce(left, &right)
+                } else {
+                    Ok(joined.column(name)?.clone())
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(DataFrame::new_no_checks(columns))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_update() -> Result<()> {
+        let df = df![
+            "id" => &[1, 2, 3, 4],
+            "price" => &[Some(1.0), None, Some(3.0), None],
+            "name" => &["a", "b", "c", "d"]
+        ]?;
+        let other = df![
+            "price" => &[Some(30.0), None, Some(40.0), Some(50.0)],
+            "id" => &[3, 2, 4, 5],
+            "extra" => &[true, true, false, false]
+        ]?;
+
+        let out = df.update(&other, &["id"], UpdateStrategy::PreferRight)?;
+        assert_eq!(out.get_column_names(), &["id", "price", "name"]);
+        assert_eq!(
+            Vec::from(out.column("price")?.f64()?),
+            &[Some(1.0), None, Some(30.0), Some(40.0)]
+        );
+
+        let out = df.update(&other, &["id"], UpdateStrategy::PreferLeft)?;
+        assert_eq!(
+            Vec::from(out.column("price")?.f64()?),
+            &[Some(1.0), None, Some(3.0), Some(40.0)]
+        );
+
+        let dup = df!["id" => &[1, 1], "price" => &[1.0, 2.0]]?;
+        assert!(df
+            .update(&dup, &["id"], UpdateStrategy::PreferRight)
+            .is_err());
+        Ok(())
+    }
+}
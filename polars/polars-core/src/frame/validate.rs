@@ -0,0 +1,240 @@
+use crate::prelude::*;
+use std::fmt;
+
+/// Constraints on a single column, checked by [DataFrame::validate](DataFrame::validate).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnConstraints {
+    name: String,
+    dtype: Option<DataType>,
+    not_null: bool,
+    unique: bool,
+    sorted: Option<bool>,
+}
+
+impl ColumnConstraints {
+    /// The column must exist.
+    pub fn new(name: &str) -> Self {
+        ColumnConstraints {
+            name: name.to_string(),
+            dtype: None,
+            not_null: false,
+            unique: false,
+            sorted: None,
+        }
+    }
+
+    /// The column must have this data type.
+    pub fn dtype(mut self, dtype: DataType) -> Self {
+        self.dtype = Some(dtype);
+        self
+    }
+
+    /// The column may not contain null values.
+    pub fn not_null(mut self) -> Self {
+        self.not_null = true;
+        self
+    }
+
+    /// All values of the column must be unique.
+    pub fn unique(mut self) -> Self {
+        self.unique = true;
+        self
+    }
+
+    /// The column must be sorted ascending, or descending if `reverse`.
+    pub fn sorted(mut self, reverse: bool) -> Self {
+        self.sorted = Some(reverse);
+        self
+    }
+}
+
+/// Constraints on a DataFrame, checked by [DataFrame::validate](DataFrame::validate).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Constraints {
+    columns: Vec<ColumnConstraints>,
+    unique_key: Option<Vec<String>>,
+}
+
+impl Constraints {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the constraints of a column.
+    pub fn column(mut self, column: ColumnConstraints) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    /// The combination of these columns must be unique for every row.
+    pub fn unique_key(mut self, columns: &[&str]) -> Self {
+        self.unique_key = Some(columns.iter().map(|s| s.to_string()).collect());
+        self
+    }
+}
+
+/// A violated constraint, returned by [DataFrame::validate](DataFrame::validate).
+#[derive(Clone, Debug, PartialEq)]
+pub enum Violation {
+    MissingColumn(String),
+    WrongDataType {
+        column: String,
+        expected: DataType,
+        found: DataType,
+    },
+    HasNulls {
+        column: String,
+        null_count: usize,
+    },
+    NotUnique(String),
+    NotSorted(String),
+    DuplicateKey(Vec<String>),
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use Violation::*;
+        match self {
+            MissingColumn(column) => write!(f, "column {} is missing", column),
+            WrongDataType {
+                column,
+                expected,
+                found,
+            } => write!(
+                f,
+                "column {} has data type {:?}, expected {:?}",
+                column, found, expected
+            ),
+            HasNulls { column, null_count } => {
+                write!(f, "column {} has {} null values", column, null_count)
+            }
+            NotUnique(column) => write!(f, "column {} has duplicate values", column),
+            NotSorted(column) => write!(f, "column {} is not sorted", column),
+            DuplicateKey(columns) => write!(f, "key {:?} has duplicate values", columns),
+        }
+    }
+}
+
+impl DataFrame {
+    /// Check if every row has a unique combination of values in the `keys` columns.
+    pub fn is_unique_key(&self, keys: &[&str]) -> Result<bool> {
+        let gb = self.groupby(keys.to_vec())?;
+        Ok(gb.get_groups().len() == self.height())
+    }
+
+    /// Check the DataFrame against `constraints` and return all violations.
+    /// An empty `Vec` means that the DataFrame is valid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    /// use polars_core::frame::validate::{ColumnConstraints, Constraints};
+    ///
+    /// fn check_output(df: &DataFrame) -> Result<()> {
+    ///     let constraints = Constraints::new()
+    ///         .column(ColumnConstraints::new("id").dtype(DataType::Int32).not_null().unique())
+    ///         .column(ColumnConstraints::new("date").sorted(false));
+    ///     let violations = df.validate(&constraints)?;
+    ///     if !violations.is_empty() {
+    ///         return Err(PolarsError::ValueError(format!("{:?}", violations).into()));
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn validate(&self, constraints: &Constraints) -> Result<Vec<Violation>> {
+        let mut violations = vec![];
+        for c in &constraints.columns {
+            let s = match self.column(&c.name) {
+                Ok(s) => s,
+                Err(_) => {
+                    violations.push(Violation::MissingColumn(c.name.clone()));
+                    continue;
+                }
+            };
+            if let Some(dtype) = &c.dtype {
+                if s.dtype() != dtype {
+                    violations.push(Violation::WrongDataType {
+                        column: c.name.clone(),
+                        expected: dtype.clone(),
+                        found: s.dtype().clone(),
+                    });
+                }
+            }
+            if c.not_null && s.null_count() > 0 {
+                violations.push(Violation::HasNulls {
+                    column: c.name.clone(),
+                    null_count: s.null_count(),
+                });
+            }
+            if c.unique && s.n_unique()? != s.len() {
+                violations.push(Violation::NotUnique(c.name.clone()));
+            }
+            if let Some(reverse) = c.sorted {
+                if !s.is_sorted(reverse)? {
+                    violations.push(Violation::NotSorted(c.name.clone()));
+                }
+            }
+        }
+        if let Some(keys) = &constraints.unique_key {
+            let keys = keys.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+            let missing = keys
+                .iter()
+                .filter(|name| self.column(name).is_err())
+                .map(|name| Violation::MissingColumn(name.to_string()))
+                .collect::<Vec<_>>();
+            if !missing.is_empty() {
+                violations.extend(missing);
+            } else if !self.is_unique_key(&keys)? {
+                violations.push(Violation::DuplicateKey(
+                    keys.iter().map(|s| s.to_string()).collect(),
+                ));
+            }
+        }
+        Ok(violations)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate() -> Result<()> {
+        let df = df![
+            "id" => &[1, 2, 2],
+            "group" => &["a", "a", "b"],
+            "value" => &[Some(3.0), None, Some(1.0)]
+        ]?;
+        assert!(df.is_unique_key(&["id", "group"])?);
+        assert!(!df.is_unique_key(&["id"])?);
+
+        let constraints = Constraints::new()
+            .column(ColumnConstraints::new("id").sorted(false).unique())
+            .column(
+                ColumnConstraints::new("value")
+                    .dtype(DataType::Int32)
+                    .not_null(),
+            )
+            .column(ColumnConstraints::new("missing"))
+            .unique_key(&["id", "group"]);
+        let violations = df.validate(&constraints)?;
+        assert_eq!(
+            violations,
+            vec![
+                Violation::NotUnique("id".into()),
+                Violation::WrongDataType {
+                    column: "value".into(),
+                    expected: DataType::Int32,
+                    found: DataType::Float64
+                },
+                Violation::HasNulls {
+                    column: "value".into(),
+                    null_count: 1
+                },
+                Violation::MissingColumn("missing".into()),
+            ]
+        );
+        Ok(())
+    }
+}
@@ -1,5 +1,8 @@
 use crate::prelude::*;
+use crate::vector_hasher::combine_hashes;
 use num::{Float, NumCast};
+use rayon::prelude::*;
+use std::cmp::Ordering;
 use std::ops::Div;
 
 // todo! make numerical stable from catastrophic cancellation
@@ -23,6 +26,109 @@ where
     Some(cov(a, b)? / (a.std()? * b.std()?))
 }
 
+/// Combine two columns of hashes, e.g. from [Series::hash](crate::series::Series::hash), into one
+/// column of hashes.
+pub fn hash_combine(a: &UInt64Chunked, b: &UInt64Chunked) -> Result<UInt64Chunked> {
+    if a.len() != b.len() {
+        return Err(PolarsError::ShapeMisMatch(
+            "cannot combine hashes of arrays with different lengths".into(),
+        ));
+    }
+    let ca: NoNull<UInt64Chunked> = a
+        .into_iter()
+        .zip(b.into_iter())
+        .map(|(a, b)| combine_hashes(a.unwrap_or(0), b.unwrap_or(0)))
+        .collect();
+    let mut ca = ca.into_inner();
+    ca.rename(a.name());
+    Ok(ca)
+}
+
+/// Hash the rows of `columns` with a fixed `seed`. See [Series::hash](crate::series::Series::hash).
+pub fn hash_rows(columns: &[Series], seed: u64) -> Result<UInt64Chunked> {
+    let mut iter = columns.iter();
+    let first = iter
+        .next()
+        .ok_or_else(|| PolarsError::NoData("cannot hash the rows of zero columns".into()))?;
+    iter.try_fold(first.hash(seed)?, |acc, s| {
+        hash_combine(&acc, &s.hash(seed)?)
+    })
+}
+
+/// Dense rank of every row of `s` in the sort order of `s`: equal values get equal ranks.
+fn sort_ranks(s: &Series, reverse: bool) -> Vec<IdxSize> {
+    let idx = s.argsort(reverse);
+    let sorted = s.take(&idx);
+    let same_as_previous = sorted.eq_missing(&sorted.shift(1));
+    let mut ranks = vec![0; s.len()];
+    let mut rank = 0;
+    for (i, (row, same)) in idx
+        .into_no_null_iter()
+        .zip(same_as_previous.into_iter())
+        .enumerate()
+    {
+        if i > 0 && same != Some(true) {
+            rank += 1;
+        }
+        ranks[row as usize] = rank;
+    }
+    ranks
+}
+
+/// Get the indexes that sort the rows lexicographically by the columns in `by`. The rows are
+/// sorted by the first column, rows with equal values by the second column, and so on.
+/// `reverse` has a sort direction for every column.
+///
+/// # Example
+///
+/// ```
+/// # use polars_core::prelude::*;
+/// use polars_core::functions::argsort_by;
+///
+/// let a = Series::new("a", &[2, 1, 2]);
+/// let b = Series::new("b", &["x", "y", "z"]);
+/// let idx = argsort_by(&[a, b], &[false, true]).unwrap();
+/// assert_eq!(Vec::from(&idx), &[Some(1), Some(2), Some(0)]);
+/// ```
+pub fn argsort_by(by: &[Series], reverse: &[bool]) -> Result<IdxCa> {
+    let first = by
+        .first()
+        .ok_or_else(|| PolarsError::NoData("cannot sort by zero columns".into()))?;
+    if by.len() != reverse.len() {
+        return Err(PolarsError::ValueError(
+            format!(
+                "got {} sort directions for {} columns",
+                reverse.len(),
+                by.len()
+            )
+            .into(),
+        ));
+    }
+    if by.iter().any(|s| s.len() != first.len()) {
+        return Err(PolarsError::ShapeMisMatch(
+            "cannot sort by columns with different lengths".into(),
+        ));
+    }
+    if by.len() == 1 {
+        return Ok(first.argsort(reverse[0]));
+    }
+
+    let ranks: Vec<_> = by
+        .par_iter()
+        .zip(reverse)
+        .map(|(s, reverse)| sort_ranks(s, *reverse))
+        .collect();
+    let mut idx: Vec<IdxSize> = (0..first.len() as IdxSize).collect();
+    idx.par_sort_by(|&a, &b| {
+        ranks
+            .iter()
+            .map(|ranks| ranks[a as usize].cmp(&ranks[b as usize]))
+            .find(|ord| *ord != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    });
+    Ok(IdxCa::new_from_slice(first.name(), &idx))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -34,4 +140,47 @@ mod test {
         assert!((cov(&a.f32().unwrap(), &b.f32().unwrap()).unwrap() - 0.5).abs() < 0.001);
         assert!((pearson_corr(&a.f32().unwrap(), &b.f32().unwrap()).unwrap() - 1.0).abs() < 0.001);
     }
+
+    #[test]
+    fn test_hash() -> Result<()> {
+        let a = Series::new("a", &[Some(1), None, Some(1)]);
+        let b = Series::new("b", &[1, 2, 1]);
+        let ha = a.hash(0)?;
+        // equal values have equal hashes, with or without nulls in the Series
+        assert_eq!(ha.get(0), ha.get(2));
+        assert_eq!(ha.get(0), b.hash(0)?.get(0));
+        assert_ne!(ha.get(0), a.hash(1)?.get(0));
+        assert_eq!(b.hash(0)?.get(0), b.hash(0)?.get(2));
+
+        let rows = hash_rows(&[a, Series::new("c", &["x", "y", "x"])], 0)?;
+        assert_eq!(rows.get(0), rows.get(2));
+        assert_ne!(rows.get(0), rows.get(1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_argsort_by() -> Result<()> {
+        let a = Series::new("a", &[Some(2), None, Some(1), Some(2), None]);
+        let b = Series::new("b", &[1.0, 2.0, 3.0, 4.0, 1.0]);
+        let c = Series::new("c", &["x", "y", "z", "v", "w"]);
+
+        let idx = argsort_by(&[a.clone(), b.clone()], &[false, true])?;
+        let sorted_b = b.take(&idx);
+        // nulls sort first, equal values of `a` by descending `b`
+        assert_eq!(
+            Vec::from(sorted_b.f64()?),
+            &[Some(2.0), Some(1.0), Some(3.0), Some(4.0), Some(1.0)]
+        );
+
+        let idx = argsort_by(&[a.clone(), c], &[true, false])?;
+        assert_eq!(
+            Vec::from(&idx),
+            &[Some(3), Some(0), Some(2), Some(4), Some(1)]
+        );
+
+        assert!(argsort_by(&[a.clone(), b.clone()], &[false]).is_err());
+        assert!(argsort_by(&[a, b.head(Some(2))], &[false, false]).is_err());
+        assert!(argsort_by(&[], &[]).is_err());
+        Ok(())
+    }
 }
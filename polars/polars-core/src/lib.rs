@@ -10,6 +10,9 @@ mod fmt;
 pub mod frame;
 pub mod functions;
 pub mod prelude;
+#[cfg(feature = "random")]
+#[cfg_attr(docsrs, doc(cfg(feature = "random")))]
+pub mod rand;
 pub mod series;
 pub mod testing;
 pub(crate) mod vector_hasher;
@@ -18,7 +21,7 @@ use ahash::AHashMap;
 use lazy_static::lazy_static;
 use rayon::{ThreadPool, ThreadPoolBuilder};
 use std::cell::Cell;
-use std::sync::{Mutex, MutexGuard};
+use std::sync::{Mutex, MutexGuard, RwLock};
 
 // this is re-exported in utils for polars child crates
 lazy_static! {
@@ -67,3 +70,40 @@ pub fn toggle_string_cache(toggle: bool) {
 pub(crate) fn use_string_cache() -> bool {
     USE_STRING_CACHE.with(|val| val.get())
 }
+
+/// The target size of the chunks in which the readers and parallel operations split their data.
+/// Smaller chunks spread the work better over the threads, larger chunks have less overhead
+/// per chunk.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChunkSize {
+    /// A number of rows.
+    Rows(usize),
+    /// A number of bytes. It is converted to rows with the estimated size of a row, so that
+    /// very wide frames get chunks with less rows than long narrow frames.
+    Bytes(usize),
+}
+
+impl ChunkSize {
+    /// The number of rows of a chunk of rows that are `row_bytes` large.
+    pub fn rows(self, row_bytes: usize) -> usize {
+        match self {
+            ChunkSize::Rows(n) => std::cmp::max(n, 1),
+            ChunkSize::Bytes(n) => std::cmp::max(n / std::cmp::max(row_bytes, 1), 1),
+        }
+    }
+}
+
+lazy_static! {
+    static ref CHUNK_SIZE: RwLock<Option<ChunkSize>> = RwLock::new(None);
+}
+
+/// Set the target size of the chunks that the readers produce and that the parallel operations
+/// split their input in, or `None` to split the data in a chunk per thread.
+pub fn set_chunk_size(size: Option<ChunkSize>) {
+    *CHUNK_SIZE.write().unwrap() = size
+}
+
+/// The target size of the chunks, see [set_chunk_size].
+pub fn chunk_size() -> Option<ChunkSize> {
+    *CHUNK_SIZE.read().unwrap()
+}
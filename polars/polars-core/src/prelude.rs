@@ -20,7 +20,17 @@ pub use crate::{
     datatypes,
     datatypes::*,
     error::{PolarsError, Result},
-    frame::{group_by::VecHash, hash_join::JoinType, DataFrame},
+    frame::{
+        group_by::VecHash,
+        hash_join::{
+            JoinArgs, JoinSide, JoinStatistics, JoinStrategy, JoinType, JoinValidation,
+            JOIN_INDICATOR_NAME,
+        },
+        matrix_view::MatrixView,
+        set_ops::SetOperation,
+        update::UpdateStrategy,
+        DataFrame,
+    },
     series::{
         arithmetic::{LhsNumOps, NumOpsDispatch},
         IntoSeries, NamedFrom, Series, SeriesTrait,
@@ -32,6 +42,8 @@ pub(crate) use polars_arrow::array::*;
 pub use polars_arrow::vec::AlignedVec;
 pub use std::sync::Arc;
 
+#[cfg(feature = "ndarray")]
+pub use crate::chunked_array::ndarray::IndexOrder;
 #[cfg(feature = "temporal")]
 pub use crate::chunked_array::temporal::conversion::*;
 
@@ -0,0 +1,224 @@
+//! Generate Series and DataFrames with random data, for tests, benchmarks and demos.
+//!
+//! A [RandomGenerator] created with a seed always generates the same data, so that a failing
+//! test or a benchmark run can be reproduced.
+//!
+//! # Example
+//!
+//! ```
+//! use polars_core::prelude::*;
+//! use polars_core::rand::{Distribution, RandomGenerator};
+//!
+//! let mut gen = RandomGenerator::new(Some(0));
+//! let df = gen
+//!     .frame(
+//!         100,
+//!         &[
+//!             ("id", Distribution::UniformInt { low: 0, high: 10 }),
+//!             ("value", Distribution::Normal { mean: 0.0, std_dev: 1.0 }),
+//!             (
+//!                 "side",
+//!                 Distribution::Categorical {
+//!                     categories: vec!["buy".into(), "sell".into()],
+//!                     weights: vec![0.7, 0.3],
+//!                 },
+//!             ),
+//!         ],
+//!     )
+//!     .unwrap();
+//! assert_eq!(df.shape(), (100, 3));
+//! ```
+use crate::prelude::*;
+#[cfg(all(feature = "temporal", feature = "dtype-date64"))]
+use chrono::NaiveDateTime;
+use rand::distributions::{Bernoulli, WeightedIndex};
+use rand::prelude::*;
+use rand_distr::{Distribution as _, Normal, Uniform};
+
+/// Distribution of the values of a generated column.
+#[derive(Clone, Debug)]
+pub enum Distribution {
+    /// Float64 values in `[low, high)`.
+    Uniform { low: f64, high: f64 },
+    /// Int64 values in `[low, high)`.
+    UniformInt { low: i64, high: i64 },
+    /// Float64 values from a normal distribution.
+    Normal { mean: f64, std_dev: f64 },
+    /// Boolean values that are true with probability `p`.
+    Bernoulli { p: f64 },
+    /// Categorical values, where a category is drawn with a probability proportional to its
+    /// weight.
+    Categorical {
+        categories: Vec<String>,
+        weights: Vec<f64>,
+    },
+    /// Date64 values in `[start, end)` with millisecond precision.
+    #[cfg(all(feature = "temporal", feature = "dtype-date64"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "temporal", feature = "dtype-date64"))))]
+    Datetime {
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    },
+}
+
+fn rand_error<E: std::fmt::Debug>(e: E) -> PolarsError {
+    PolarsError::RandError(format!("{:?}", e))
+}
+
+fn check_range<T: PartialOrd + std::fmt::Debug>(low: T, high: T) -> Result<()> {
+    if low < high {
+        Ok(())
+    } else {
+        Err(PolarsError::RandError(format!(
+            "low {:?} should be smaller than high {:?}",
+            low, high
+        )))
+    }
+}
+
+/// Generates random Series and DataFrames from a seedable random number generator.
+pub struct RandomGenerator {
+    rng: StdRng,
+}
+
+impl RandomGenerator {
+    /// Create a generator. With a `seed` the generated data is reproducible, without one the
+    /// generator is seeded from the operating system.
+    pub fn new(seed: Option<u64>) -> Self {
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        RandomGenerator { rng }
+    }
+
+    /// Generate a Series of `length` values drawn from `distribution`.
+    pub fn series(
+        &mut self,
+        name: &str,
+        length: usize,
+        distribution: &Distribution,
+    ) -> Result<Series> {
+        let rng = &mut self.rng;
+        let mut s = match distribution {
+            Distribution::Uniform { low, high } => {
+                check_range(*low, *high)?;
+                let dist = Uniform::new(*low, *high);
+                Float64Chunked::new_from_iter(name, (0..length).map(|_| dist.sample(rng)))
+                    .into_series()
+            }
+            Distribution::UniformInt { low, high } => {
+                check_range(*low, *high)?;
+                let dist = Uniform::new(*low, *high);
+                Int64Chunked::new_from_iter(name, (0..length).map(|_| dist.sample(rng)))
+                    .into_series()
+            }
+            Distribution::Normal { mean, std_dev } => {
+                let dist = Normal::new(*mean, *std_dev).map_err(rand_error)?;
+                Float64Chunked::new_from_iter(name, (0..length).map(|_| dist.sample(rng)))
+                    .into_series()
+            }
+            Distribution::Bernoulli { p } => {
+                let dist = Bernoulli::new(*p).map_err(rand_error)?;
+                let ca: BooleanChunked = (0..length).map(|_| dist.sample(rng)).collect();
+                ca.into_series()
+            }
+            Distribution::Categorical {
+                categories,
+                weights,
+            } => {
+                if categories.len() != weights.len() {
+                    return Err(PolarsError::ShapeMisMatch(
+                        "every category should have a weight".into(),
+                    ));
+                }
+                let dist = WeightedIndex::new(weights).map_err(rand_error)?;
+                let ca: Utf8Chunked = (0..length)
+                    .map(|_| categories[dist.sample(rng)].as_str())
+                    .collect();
+                ca.into_series()
+                    .cast_with_datatype(&DataType::Categorical(CategoricalOrdering::Physical))?
+            }
+            #[cfg(all(feature = "temporal", feature = "dtype-date64"))]
+            Distribution::Datetime { start, end } => {
+                let (low, high) = (start.timestamp_millis(), end.timestamp_millis());
+                check_range(low, high)?;
+                let dist = Uniform::new(low, high);
+                Date64Chunked::new_from_iter(name, (0..length).map(|_| dist.sample(rng)))
+                    .into_series()
+            }
+        };
+        s.rename(name);
+        Ok(s)
+    }
+
+    /// Generate a DataFrame of `height` rows with a column for every name and distribution in
+    /// `columns`. The columns are generated in order.
+    pub fn frame(&mut self, height: usize, columns: &[(&str, Distribution)]) -> Result<DataFrame> {
+        let columns = columns
+            .iter()
+            .map(|(name, distribution)| self.series(name, height, distribution))
+            .collect::<Result<Vec<_>>>()?;
+        DataFrame::new(columns)
+    }
+
+    /// Replace a random selection of the values of `s` with nulls. Every value becomes null with
+    /// probability `null_fraction`.
+    pub fn with_nulls(&mut self, s: &Series, null_fraction: f64) -> Result<Series> {
+        let dist = Bernoulli::new(null_fraction).map_err(rand_error)?;
+        let rng = &mut self.rng;
+        let is_valid: BooleanChunked = (0..s.len()).map(|_| !dist.sample(rng)).collect();
+        let nulls = Series::full_null(s.name(), s.len(), s.dtype())?;
+        s.zip_with(&is_valid, &nulls)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_random_generator() -> Result<()> {
+        let columns = [
+            ("a", Distribution::UniformInt { low: -5, high: 5 }),
+            (
+                "b",
+                Distribution::Uniform {
+                    low: 0.0,
+                    high: 1.0,
+                },
+            ),
+            (
+                "c",
+                Distribution::Categorical {
+                    categories: vec!["x".into(), "y".into()],
+                    weights: vec![1.0, 0.0],
+                },
+            ),
+        ];
+        let df = RandomGenerator::new(Some(1)).frame(50, &columns)?;
+        let same = RandomGenerator::new(Some(1)).frame(50, &columns)?;
+        assert!(df.frame_equal(&same));
+        assert_eq!(
+            df.column("c")?.dtype(),
+            &DataType::Categorical(CategoricalOrdering::Physical)
+        );
+
+        let a = df.column("a")?.i64()?;
+        assert!(a.into_no_null_iter().all(|v| (-5..5).contains(&v)));
+        let c = df.column("c")?.cast::<Utf8Type>()?;
+        assert!(c.utf8()?.into_no_null_iter().all(|v| v == "x"));
+
+        let mut gen = RandomGenerator::new(Some(2));
+        let s = gen.series("d", 100, &Distribution::Bernoulli { p: 0.5 })?;
+        assert_eq!(gen.with_nulls(&s, 1.0)?.null_count(), 100);
+        assert_eq!(gen.with_nulls(&s, 0.0)?.null_count(), 0);
+
+        let empty = Distribution::Uniform {
+            low: 1.0,
+            high: 1.0,
+        };
+        assert!(gen.series("e", 10, &empty).is_err());
+        Ok(())
+    }
+}
@@ -35,6 +35,10 @@ macro_rules! impl_compare {
                 .unwrap()
                 .$method($rhs.duration_millisecond().unwrap()),
             DataType::List(_) => $self.list().unwrap().$method($rhs.list().unwrap()),
+            DataType::Categorical(_) => $self
+                .categorical()
+                .unwrap()
+                .$method($rhs.categorical().unwrap()),
             _ => unimplemented!(),
         }
     }};
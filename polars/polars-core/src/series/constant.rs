@@ -0,0 +1,418 @@
+//! A Series of which all values are equal, e.g. a broadcast literal. It only stores the value
+//! and a length. The values are materialized once, when an operation needs them.
+use super::forward::Materialized;
+use super::private::{self, PrivateSeries};
+use super::IntoSeries;
+use super::SeriesTrait;
+use crate::frame::group_by::{GroupTuples, PivotAgg};
+use crate::prelude::*;
+use ahash::RandomState;
+use arrow::array::{ArrayDataRef, ArrayRef};
+use arrow::buffer::Buffer;
+use std::any::Any;
+
+pub struct ConstantChunked {
+    /// A Series of length 1.
+    value: Series,
+    length: usize,
+    chunk_id: Vec<usize>,
+    materialized: Materialized,
+}
+
+impl Clone for ConstantChunked {
+    fn clone(&self) -> Self {
+        let out = self.with_len(self.length);
+        if self.materialized.is_completed() {
+            out.materialized
+                .get_or_init(|| self.materialized_series().clone());
+        }
+        out
+    }
+}
+
+impl ConstantChunked {
+    /// Repeat the value at `index` of `s` `length` times.
+    pub fn new(s: &Series, index: usize, length: usize) -> Self {
+        let value = match s.0.constant_value() {
+            Some(value) => value.clone(),
+            None => s.take_iter(&mut std::iter::once(index)),
+        };
+        ConstantChunked {
+            value,
+            length,
+            chunk_id: vec![length],
+            materialized: Materialized::new(),
+        }
+    }
+
+    fn with_len(&self, length: usize) -> ConstantChunked {
+        ConstantChunked::new(&self.value, 0, length)
+    }
+
+    fn materialized_series(&self) -> &Series {
+        self.materialized
+            .get_or_init(|| self.value.expand_at_index(0, self.length).rechunk())
+    }
+
+    fn inner(&self) -> &dyn SeriesTrait {
+        self.materialized_series().0.as_ref()
+    }
+}
+
+impl IntoSeries for ConstantChunked {
+    fn into_series(self) -> Series {
+        Series(Arc::new(self))
+    }
+}
+
+impl private::PrivateSeries for ConstantChunked {
+    fn constant_value(&self) -> Option<&Series> {
+        Some(&self.value)
+    }
+    fn materialized(&self) -> Option<&Series> {
+        Some(self.materialized_series())
+    }
+    fn agg_first(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
+        self.with_len(groups.len()).into_series()
+    }
+    fn agg_last(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
+        self.with_len(groups.len()).into_series()
+    }
+    fn agg_n_unique(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<IdxCa> {
+        Some(IdxCa::full(self.name(), 1, groups.len()))
+    }
+    fn group_tuples(&self, _multithreaded: bool) -> GroupTuples {
+        if self.length == 0 {
+            vec![]
+        } else {
+            vec![(0, (0..self.length as IdxSize).collect())]
+        }
+    }
+
+    forward_series_methods!(
+        inner;
+        vec_hash,
+        agg_mean,
+        agg_min,
+        agg_max,
+        agg_sum,
+        agg_std,
+        agg_var,
+        agg_list,
+        agg_quantile,
+        agg_median,
+        agg_top_k_idx,
+        agg_top_k,
+        pivot,
+        pivot_count,
+        hash_join_inner,
+        hash_join_left,
+        hash_join_outer,
+        zip_outer_join_column,
+        subtract,
+        add_to,
+        multiply,
+        divide,
+        remainder,
+    );
+}
+
+impl SeriesTrait for ConstantChunked {
+    fn rename(&mut self, name: &str) {
+        self.value.rename(name);
+        if let Some(s) = self.materialized.get_mut() {
+            s.rename(name);
+        }
+    }
+
+    fn set_metadata(&mut self, metadata: FieldMetadata) {
+        self.value.set_metadata(metadata.clone());
+        if let Some(s) = self.materialized.get_mut() {
+            s.set_metadata(metadata);
+        }
+    }
+
+    fn chunk_lengths(&self) -> &Vec<usize> {
+        &self.chunk_id
+    }
+
+    fn name(&self) -> &str {
+        self.value.name()
+    }
+
+    fn field(&self) -> &Field {
+        self.value.field()
+    }
+
+    fn n_chunks(&self) -> usize {
+        1
+    }
+
+    fn slice(&self, offset: usize, length: usize) -> Result<Series> {
+        if offset + length > self.length {
+            return Err(PolarsError::OutOfBounds(
+                "offset and length was larger than the size of the Series during slice operation"
+                    .into(),
+            ));
+        }
+        Ok(self.with_len(length).into_series())
+    }
+
+    fn filter(&self, filter: &BooleanChunked) -> Result<Series> {
+        if filter.len() != self.length {
+            return Err(PolarsError::ShapeMisMatch(
+                "filter's length differs from that of the Series".into(),
+            ));
+        }
+        let len = filter
+            .into_iter()
+            .filter(|opt_v| matches!(opt_v, Some(true)))
+            .count();
+        Ok(self.with_len(len).into_series())
+    }
+
+    unsafe fn take_unchecked(&self, idx: &IdxCa) -> Result<Series> {
+        if idx.null_count() == 0 {
+            Ok(self.with_len(idx.len()).into_series())
+        } else {
+            self.inner().take_unchecked(idx)
+        }
+    }
+
+    fn take(&self, indices: &IdxCa) -> Series {
+        // out of bounds and null indices produce nulls
+        let in_bounds = indices
+            .max()
+            .map_or(true, |max| (max as usize) < self.length);
+        if indices.null_count() == 0 && in_bounds {
+            self.with_len(indices.len()).into_series()
+        } else {
+            self.inner().take(indices)
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn rechunk(&self) -> Series {
+        self.clone().into_series()
+    }
+
+    fn head(&self, length: Option<usize>) -> Series {
+        self.with_len(std::cmp::min(length.unwrap_or(10), self.length))
+            .into_series()
+    }
+
+    fn tail(&self, length: Option<usize>) -> Series {
+        self.with_len(std::cmp::min(length.unwrap_or(10), self.length))
+            .into_series()
+    }
+
+    fn take_every(&self, n: usize) -> Series {
+        // step_by panics on a step of 0, like the ChunkedArray implementation
+        self.with_len((0..self.length).step_by(n).len())
+            .into_series()
+    }
+
+    fn drop_nulls(&self) -> Series {
+        if self.value.null_count() == 0 {
+            self.clone().into_series()
+        } else {
+            self.with_len(0).into_series()
+        }
+    }
+
+    fn expand_at_index(&self, _index: usize, length: usize) -> Series {
+        self.with_len(length).into_series()
+    }
+
+    fn cast_with_datatype(&self, data_type: &DataType) -> Result<Series> {
+        let value = self.value.cast_with_datatype(data_type)?;
+        Ok(ConstantChunked::new(&value, 0, self.length).into_series())
+    }
+
+    fn get(&self, index: usize) -> AnyValue {
+        assert!(index < self.length, "index out of bounds");
+        self.value.get(0)
+    }
+
+    unsafe fn get_unchecked(&self, _index: usize) -> AnyValue {
+        self.value.get_unchecked(0)
+    }
+
+    fn sort_in_place(&mut self, _reverse: bool) {}
+
+    fn sort(&self, _reverse: bool) -> Series {
+        self.clone().into_series()
+    }
+
+    fn argsort(&self, _reverse: bool) -> IdxCa {
+        (0..self.length as IdxSize)
+            .collect::<NoNull<_>>()
+            .into_inner()
+    }
+
+    fn null_count(&self) -> usize {
+        self.value.null_count() * self.length
+    }
+
+    fn sorted_flag(&self) -> IsSorted {
+        IsSorted::Ascending
+    }
+
+    fn set_sorted_flag(&mut self, _sorted: IsSorted) {}
+
+    fn unique(&self) -> Result<Series> {
+        Ok(self.with_len(std::cmp::min(1, self.length)).into_series())
+    }
+
+    fn n_unique(&self) -> Result<usize> {
+        Ok(std::cmp::min(1, self.length))
+    }
+
+    fn approx_n_unique(&self) -> Result<usize> {
+        self.n_unique()
+    }
+
+    fn is_null(&self) -> BooleanChunked {
+        BooleanChunked::full(self.name(), self.value.null_count() == 1, self.length)
+    }
+
+    fn is_not_null(&self) -> BooleanChunked {
+        BooleanChunked::full(self.name(), self.value.null_count() == 0, self.length)
+    }
+
+    fn reverse(&self) -> Series {
+        self.clone().into_series()
+    }
+
+    fn min_as_series(&self) -> Series {
+        if self.length == 0 {
+            self.inner().min_as_series()
+        } else {
+            self.with_len(1).into_series()
+        }
+    }
+
+    fn max_as_series(&self) -> Series {
+        if self.length == 0 {
+            self.inner().max_as_series()
+        } else {
+            self.with_len(1).into_series()
+        }
+    }
+
+    fn fmt_list(&self) -> String {
+        format!("[{}; {}]", self.value.get(0), self.length)
+    }
+
+    fn clone_inner(&self) -> Arc<dyn SeriesTrait> {
+        Arc::new(self.clone())
+    }
+
+    #[cfg(feature = "random")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "random")))]
+    fn sample_n(&self, n: usize, with_replacement: bool) -> Result<Series> {
+        if !with_replacement && n > self.length {
+            return Err(PolarsError::ShapeMisMatch(
+                "n is larger than the number of elements in this array".into(),
+            ));
+        }
+        Ok(self.with_len(n).into_series())
+    }
+
+    #[cfg(feature = "random")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "random")))]
+    fn sample_frac(&self, frac: f64, with_replacement: bool) -> Result<Series> {
+        let n = (self.length as f64 * frac) as usize;
+        self.sample_n(n, with_replacement)
+    }
+
+    fn arg_unique(&self) -> Result<Vec<IdxSize>> {
+        Ok(if self.length == 0 { vec![] } else { vec![0] })
+    }
+
+    fn is_unique(&self) -> Result<BooleanChunked> {
+        Ok(BooleanChunked::full(
+            self.name(),
+            self.length == 1,
+            self.length,
+        ))
+    }
+
+    fn is_duplicated(&self) -> Result<BooleanChunked> {
+        Ok(BooleanChunked::full(
+            self.name(),
+            self.length > 1,
+            self.length,
+        ))
+    }
+
+    impl_unreachable_mutations!("a constant Series");
+
+    forward_series_methods!(
+        inner;
+        cum_max,
+        cum_min,
+        cum_sum,
+        array_data,
+        chunks,
+        i8,
+        i16,
+        i32,
+        i64,
+        f32,
+        f64,
+        u8,
+        u16,
+        u32,
+        u64,
+        bool,
+        utf8,
+        date32,
+        date64,
+        time64_nanosecond,
+        duration_nanosecond,
+        duration_millisecond,
+        list,
+        categorical,
+        take_iter,
+        take_iter_unchecked,
+        take_opt_iter_unchecked,
+        take_opt_iter,
+        to_dummies,
+        value_counts,
+        arg_true,
+        null_bits,
+        shift,
+        fill_none,
+        zip_with,
+        sum_as_series,
+        mean_as_series,
+        median_as_series,
+        var_as_series,
+        std_as_series,
+        quantile_as_series,
+        rolling_mean,
+        rolling_sum,
+        rolling_min,
+        rolling_max,
+        hour,
+        minute,
+        second,
+        nanosecond,
+        day,
+        ordinal_day,
+        month,
+        year,
+        datetime_str_fmt,
+        get_as_any,
+        pow,
+        peak_max,
+        peak_min,
+        clip_quantile,
+        standardize,
+        min_max_scale,
+    );
+}
@@ -0,0 +1,400 @@
+//! A Utf8 Series that stores every distinct string once and an index per row, e.g. for the
+//! messages of a log. Operations that only select rows, like `filter`, `take` and `slice`,
+//! work on the indexes, and sorting works on the rank of every distinct string. The other
+//! operations decode the strings for the duration of the operation; only the accessors that
+//! return a borrow of the strings, like `utf8` and `chunks`, cache them.
+use super::forward::Materialized;
+use super::private::{self, PrivateSeries};
+use super::IntoSeries;
+use super::SeriesTrait;
+use crate::frame::group_by::{GroupTuples, PivotAgg};
+use crate::prelude::*;
+use ahash::{AHashMap, RandomState};
+use arrow::array::{ArrayDataRef, ArrayRef};
+use arrow::buffer::Buffer;
+
+pub struct DedupUtf8Chunked {
+    /// The distinct strings.
+    values: Utf8Chunked,
+    /// The index in `values` of the string of every row, null for the null rows.
+    indices: IdxCa,
+    materialized: Materialized,
+}
+
+impl Clone for DedupUtf8Chunked {
+    fn clone(&self) -> Self {
+        let out = self.with_indices(self.indices.clone());
+        if self.materialized.is_completed() {
+            out.materialized
+                .get_or_init(|| self.materialized_series().clone());
+        }
+        out
+    }
+}
+
+impl DedupUtf8Chunked {
+    pub fn new(ca: &Utf8Chunked) -> Self {
+        let mut positions = AHashMap::new();
+        let mut values = Utf8ChunkedBuilder::new(ca.name(), 16, 16 * 16);
+        let indices = ca
+            .into_iter()
+            .map(|opt_s| {
+                opt_s.map(|s| {
+                    let n_values = positions.len() as IdxSize;
+                    *positions.entry(s).or_insert_with(|| {
+                        values.append_value(s);
+                        n_values
+                    })
+                })
+            })
+            .collect::<IdxCa>();
+        let mut out = DedupUtf8Chunked {
+            values: values.finish(),
+            indices,
+            materialized: Materialized::new(),
+        };
+        out.indices.rename(ca.name());
+        out
+    }
+
+    /// The number of distinct strings.
+    pub fn n_values(&self) -> usize {
+        self.values.len()
+    }
+
+    fn with_indices(&self, indices: IdxCa) -> Self {
+        DedupUtf8Chunked {
+            values: self.values.clone(),
+            indices,
+            materialized: Materialized::new(),
+        }
+    }
+
+    /// Select the rows with an operation on the indices.
+    fn map_indices<F: FnOnce(Series) -> Series>(&self, f: F) -> Series {
+        let indices = f(self.indices.clone().into_series());
+        self.with_indices(indices.idx().unwrap().clone())
+            .into_series()
+    }
+
+    /// Decode the strings of every row. The result is dropped after the operation that needed it.
+    fn decoded(&self) -> Arc<dyn SeriesTrait> {
+        let mut s = self.values.clone().into_series().take(&self.indices);
+        s.rename(self.name());
+        s.0
+    }
+
+    /// The decoded strings, cached for the accessors that return a borrow of them.
+    fn materialized_series(&self) -> &Series {
+        self.materialized.get_or_init(|| Series(self.decoded()))
+    }
+
+    fn inner(&self) -> &dyn SeriesTrait {
+        self.materialized_series().0.as_ref()
+    }
+
+    /// The rank of the string of every row among the distinct strings. Sorting the ranks sorts
+    /// the strings, without decoding them.
+    fn ranks(&self) -> IdxCa {
+        let mut rank = vec![0 as IdxSize; self.values.len()];
+        for (r, idx) in self.values.argsort(false).into_no_null_iter().enumerate() {
+            rank[idx as usize] = r as IdxSize;
+        }
+        let mut ranks: IdxCa = self
+            .indices
+            .into_iter()
+            .map(|opt_idx| opt_idx.map(|idx| rank[idx as usize]))
+            .collect();
+        ranks.rename(self.name());
+        ranks
+    }
+}
+
+impl IntoSeries for DedupUtf8Chunked {
+    fn into_series(self) -> Series {
+        Series(Arc::new(self))
+    }
+}
+
+impl private::PrivateSeries for DedupUtf8Chunked {
+    fn materialized(&self) -> Option<&Series> {
+        Some(self.materialized_series())
+    }
+    fn agg_first(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
+        self.map_indices(|s| s.agg_first(groups))
+    }
+    fn agg_last(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
+        self.map_indices(|s| s.agg_last(groups))
+    }
+    fn agg_n_unique(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<IdxCa> {
+        self.indices.clone().into_series().agg_n_unique(groups)
+    }
+    fn group_tuples(&self, multithreaded: bool) -> GroupTuples {
+        // equal strings have equal indices
+        self.indices
+            .clone()
+            .into_series()
+            .group_tuples(multithreaded)
+    }
+
+    forward_series_methods!(
+        decoded;
+        vec_hash,
+        agg_mean,
+        agg_min,
+        agg_max,
+        agg_sum,
+        agg_std,
+        agg_var,
+        agg_list,
+        agg_quantile,
+        agg_median,
+        agg_top_k_idx,
+        agg_top_k,
+        pivot,
+        pivot_count,
+        hash_join_inner,
+        hash_join_left,
+        hash_join_outer,
+        zip_outer_join_column,
+        subtract,
+        add_to,
+        multiply,
+        divide,
+        remainder,
+    );
+}
+
+impl SeriesTrait for DedupUtf8Chunked {
+    fn rename(&mut self, name: &str) {
+        self.values.rename(name);
+        self.indices.rename(name);
+        if let Some(s) = self.materialized.get_mut() {
+            s.rename(name);
+        }
+    }
+
+    fn set_metadata(&mut self, metadata: FieldMetadata) {
+        self.values.set_metadata(metadata.clone());
+        if let Some(s) = self.materialized.get_mut() {
+            s.set_metadata(metadata);
+        }
+    }
+
+    fn chunk_lengths(&self) -> &Vec<usize> {
+        self.indices.chunk_id()
+    }
+
+    fn name(&self) -> &str {
+        self.indices.name()
+    }
+
+    fn field(&self) -> &Field {
+        self.values.ref_field()
+    }
+
+    fn n_chunks(&self) -> usize {
+        self.indices.chunks().len()
+    }
+
+    fn slice(&self, offset: usize, length: usize) -> Result<Series> {
+        Ok(self
+            .with_indices(self.indices.slice(offset, length)?)
+            .into_series())
+    }
+
+    fn filter(&self, filter: &BooleanChunked) -> Result<Series> {
+        Ok(self
+            .with_indices(self.indices.filter(filter)?)
+            .into_series())
+    }
+
+    fn take_iter(&self, iter: &mut dyn Iterator<Item = usize>) -> Series {
+        self.map_indices(|s| s.take_iter(iter))
+    }
+
+    unsafe fn take_iter_unchecked(&self, iter: &mut dyn Iterator<Item = usize>) -> Series {
+        self.map_indices(|s| s.take_iter_unchecked(iter))
+    }
+
+    unsafe fn take_unchecked(&self, idx: &IdxCa) -> Result<Series> {
+        let indices = self.indices.clone().into_series().take_unchecked(idx)?;
+        Ok(self.with_indices(indices.idx()?.clone()).into_series())
+    }
+
+    unsafe fn take_opt_iter_unchecked(
+        &self,
+        iter: &mut dyn Iterator<Item = Option<usize>>,
+    ) -> Series {
+        self.map_indices(|s| s.take_opt_iter_unchecked(iter))
+    }
+
+    fn take_opt_iter(&self, iter: &mut dyn Iterator<Item = Option<usize>>) -> Series {
+        self.map_indices(|s| s.take_opt_iter(iter))
+    }
+
+    fn take(&self, indices: &IdxCa) -> Series {
+        self.map_indices(|s| s.take(indices))
+    }
+
+    fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    fn rechunk(&self) -> Series {
+        self.map_indices(|s| s.rechunk())
+    }
+
+    fn head(&self, length: Option<usize>) -> Series {
+        self.map_indices(|s| s.head(length))
+    }
+
+    fn tail(&self, length: Option<usize>) -> Series {
+        self.map_indices(|s| s.tail(length))
+    }
+
+    fn take_every(&self, n: usize) -> Series {
+        self.map_indices(|s| s.take_every(n))
+    }
+
+    fn drop_nulls(&self) -> Series {
+        self.map_indices(|s| s.drop_nulls())
+    }
+
+    fn expand_at_index(&self, index: usize, length: usize) -> Series {
+        self.map_indices(|s| s.expand_at_index(index, length))
+    }
+
+    fn get(&self, index: usize) -> AnyValue {
+        match self.indices.get(index) {
+            Some(idx) => self.values.get_any_value(idx as usize),
+            None => AnyValue::Null,
+        }
+    }
+
+    unsafe fn get_unchecked(&self, index: usize) -> AnyValue {
+        match self.indices.get_unchecked(index) {
+            Some(idx) => self.values.get_any_value_unchecked(idx as usize),
+            None => AnyValue::Null,
+        }
+    }
+
+    fn null_count(&self) -> usize {
+        self.indices.null_count()
+    }
+
+    fn unique(&self) -> Result<Series> {
+        let indices = self.indices.clone().into_series().unique()?;
+        Ok(self.with_indices(indices.idx()?.clone()).into_series())
+    }
+
+    fn n_unique(&self) -> Result<usize> {
+        self.indices.n_unique()
+    }
+
+    fn approx_n_unique(&self) -> Result<usize> {
+        self.n_unique()
+    }
+
+    fn arg_unique(&self) -> Result<Vec<IdxSize>> {
+        self.indices.arg_unique()
+    }
+
+    fn is_null(&self) -> BooleanChunked {
+        self.indices.is_null()
+    }
+
+    fn is_not_null(&self) -> BooleanChunked {
+        self.indices.is_not_null()
+    }
+
+    fn is_unique(&self) -> Result<BooleanChunked> {
+        self.indices.is_unique()
+    }
+
+    fn is_duplicated(&self) -> Result<BooleanChunked> {
+        self.indices.is_duplicated()
+    }
+
+    fn reverse(&self) -> Series {
+        self.map_indices(|s| s.reverse())
+    }
+
+    fn shift(&self, periods: i64) -> Series {
+        self.map_indices(|s| s.shift(periods))
+    }
+
+    fn clone_inner(&self) -> Arc<dyn SeriesTrait> {
+        Arc::new(self.clone())
+    }
+
+    fn sorted_flag(&self) -> IsSorted {
+        IsSorted::Not
+    }
+
+    fn sort_in_place(&mut self, reverse: bool) {
+        let idx = self.argsort(reverse);
+        let indices = self.indices.clone().into_series().take(&idx);
+        self.indices = indices.idx().unwrap().clone();
+        self.materialized = Materialized::new();
+    }
+
+    fn sort(&self, reverse: bool) -> Series {
+        let idx = self.argsort(reverse);
+        self.map_indices(|s| s.take(&idx))
+    }
+
+    fn argsort(&self, reverse: bool) -> IdxCa {
+        self.ranks().argsort(reverse)
+    }
+
+    impl_unreachable_mutations!("a deduplicated Series");
+
+    // borrowed from the cached strings
+    forward_series_methods!(inner; chunks, utf8);
+
+    forward_series_methods!(
+        decoded;
+        cum_max,
+        cum_min,
+        cum_sum,
+        array_data,
+        cast_with_datatype,
+        to_dummies,
+        value_counts,
+        arg_true,
+        null_bits,
+        fill_none,
+        zip_with,
+        sum_as_series,
+        max_as_series,
+        min_as_series,
+        mean_as_series,
+        median_as_series,
+        var_as_series,
+        std_as_series,
+        quantile_as_series,
+        rolling_mean,
+        rolling_sum,
+        rolling_min,
+        rolling_max,
+        fmt_list,
+        hour,
+        minute,
+        second,
+        nanosecond,
+        day,
+        ordinal_day,
+        month,
+        year,
+        datetime_str_fmt,
+        sample_n,
+        sample_frac,
+        pow,
+        peak_max,
+        peak_min,
+        clip_quantile,
+        standardize,
+        min_max_scale,
+    );
+}
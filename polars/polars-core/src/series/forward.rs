@@ -0,0 +1,607 @@
+//! Shared parts of the Series that don't store their values as a ChunkedArray, e.g. a constant
+//! or a deduplicated Series. Most of their methods run on the values, which are materialized by
+//! the implementation.
+use crate::prelude::*;
+use std::cell::UnsafeCell;
+use std::sync::Once;
+
+/// The values of a Series that doesn't store them, computed on first use.
+pub(crate) struct Materialized {
+    once: Once,
+    series: UnsafeCell<Option<Series>>,
+}
+
+// Safety: `series` is only written in `call_once`, which blocks the readers until it's written.
+unsafe impl Sync for Materialized {}
+
+impl Materialized {
+    pub(crate) fn new() -> Self {
+        Materialized {
+            once: Once::new(),
+            series: UnsafeCell::new(None),
+        }
+    }
+
+    pub(crate) fn get_or_init<F: FnOnce() -> Series>(&self, f: F) -> &Series {
+        self.once
+            .call_once(|| unsafe { *self.series.get() = Some(f()) });
+        unsafe { (*self.series.get()).as_ref().unwrap() }
+    }
+
+    pub(crate) fn get_mut(&mut self) -> Option<&mut Series> {
+        self.series.get_mut().as_mut()
+    }
+
+    pub(crate) fn is_completed(&self) -> bool {
+        self.once.is_completed()
+    }
+}
+
+/// Implement the given Series methods by calling them on `self.$values()`, which returns the
+/// values as a `&dyn SeriesTrait` or an `Arc<dyn SeriesTrait>`. Methods that return a reference
+/// need `$values` to return a reference that lives as long as `self`.
+macro_rules! forward_series_methods {
+    ($values:ident; $($method:ident),* $(,)?) => {
+        $(forward_series_method!($values, $method);)*
+    };
+}
+
+macro_rules! forward_series_method {
+    ($values:ident, vec_hash) => {
+        fn vec_hash(&self, random_state: RandomState) -> UInt64Chunked {
+            self.$values().vec_hash(random_state)
+        }
+    };
+    ($values:ident, agg_mean) => {
+        fn agg_mean(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+            self.$values().agg_mean(groups)
+        }
+    };
+    ($values:ident, agg_min) => {
+        fn agg_min(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+            self.$values().agg_min(groups)
+        }
+    };
+    ($values:ident, agg_max) => {
+        fn agg_max(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+            self.$values().agg_max(groups)
+        }
+    };
+    ($values:ident, agg_sum) => {
+        fn agg_sum(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+            self.$values().agg_sum(groups)
+        }
+    };
+    ($values:ident, agg_std) => {
+        fn agg_std(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+            self.$values().agg_std(groups)
+        }
+    };
+    ($values:ident, agg_var) => {
+        fn agg_var(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+            self.$values().agg_var(groups)
+        }
+    };
+    ($values:ident, agg_list) => {
+        fn agg_list(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+            self.$values().agg_list(groups)
+        }
+    };
+    ($values:ident, agg_quantile) => {
+        fn agg_quantile(
+            &self,
+            groups: &[(IdxSize, Vec<IdxSize>)],
+            quantile: f64,
+        ) -> Option<Series> {
+            self.$values().agg_quantile(groups, quantile)
+        }
+    };
+    ($values:ident, agg_median) => {
+        fn agg_median(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+            self.$values().agg_median(groups)
+        }
+    };
+    ($values:ident, agg_top_k_idx) => {
+        fn agg_top_k_idx(
+            &self,
+            groups: &[(IdxSize, Vec<IdxSize>)],
+            k: usize,
+        ) -> Option<Vec<Vec<IdxSize>>> {
+            self.$values().agg_top_k_idx(groups, k)
+        }
+    };
+    ($values:ident, agg_top_k) => {
+        fn agg_top_k(&self, groups: &[(IdxSize, Vec<IdxSize>)], k: usize) -> Option<Series> {
+            self.$values().agg_top_k(groups, k)
+        }
+    };
+    ($values:ident, pivot) => {
+        fn pivot<'a>(
+            &self,
+            pivot_series: &'a (dyn SeriesTrait + 'a),
+            keys: Vec<Series>,
+            groups: &[(IdxSize, Vec<IdxSize>)],
+            agg_type: PivotAgg,
+        ) -> Result<DataFrame> {
+            self.$values().pivot(pivot_series, keys, groups, agg_type)
+        }
+    };
+    ($values:ident, pivot_count) => {
+        fn pivot_count<'a>(
+            &self,
+            pivot_series: &'a (dyn SeriesTrait + 'a),
+            keys: Vec<Series>,
+            groups: &[(IdxSize, Vec<IdxSize>)],
+        ) -> Result<DataFrame> {
+            self.$values().pivot_count(pivot_series, keys, groups)
+        }
+    };
+    ($values:ident, hash_join_inner) => {
+        fn hash_join_inner(
+            &self,
+            other: &Series,
+            strategy: JoinStrategy,
+        ) -> Vec<(IdxSize, IdxSize)> {
+            self.$values().hash_join_inner(other, strategy)
+        }
+    };
+    ($values:ident, hash_join_left) => {
+        fn hash_join_left(
+            &self,
+            other: &Series,
+            strategy: JoinStrategy,
+        ) -> Vec<(IdxSize, Option<IdxSize>)> {
+            self.$values().hash_join_left(other, strategy)
+        }
+    };
+    ($values:ident, hash_join_outer) => {
+        fn hash_join_outer(&self, other: &Series) -> Vec<(Option<IdxSize>, Option<IdxSize>)> {
+            self.$values().hash_join_outer(other)
+        }
+    };
+    ($values:ident, zip_outer_join_column) => {
+        fn zip_outer_join_column(
+            &self,
+            right_column: &Series,
+            opt_join_tuples: &[(Option<IdxSize>, Option<IdxSize>)],
+        ) -> Series {
+            self.$values()
+                .zip_outer_join_column(right_column, opt_join_tuples)
+        }
+    };
+    ($values:ident, subtract) => {
+        fn subtract(&self, rhs: &Series) -> Result<Series> {
+            self.$values().subtract(rhs)
+        }
+    };
+    ($values:ident, add_to) => {
+        fn add_to(&self, rhs: &Series) -> Result<Series> {
+            self.$values().add_to(rhs)
+        }
+    };
+    ($values:ident, multiply) => {
+        fn multiply(&self, rhs: &Series) -> Result<Series> {
+            self.$values().multiply(rhs)
+        }
+    };
+    ($values:ident, divide) => {
+        fn divide(&self, rhs: &Series) -> Result<Series> {
+            self.$values().divide(rhs)
+        }
+    };
+    ($values:ident, remainder) => {
+        fn remainder(&self, rhs: &Series) -> Result<Series> {
+            self.$values().remainder(rhs)
+        }
+    };
+    ($values:ident, take_iter) => {
+        fn take_iter(&self, iter: &mut dyn Iterator<Item = usize>) -> Series {
+            self.$values().take_iter(iter)
+        }
+    };
+    ($values:ident, take_iter_unchecked) => {
+        unsafe fn take_iter_unchecked(&self, iter: &mut dyn Iterator<Item = usize>) -> Series {
+            self.$values().take_iter_unchecked(iter)
+        }
+    };
+    ($values:ident, take_opt_iter_unchecked) => {
+        unsafe fn take_opt_iter_unchecked(
+            &self,
+            iter: &mut dyn Iterator<Item = Option<usize>>,
+        ) -> Series {
+            self.$values().take_opt_iter_unchecked(iter)
+        }
+    };
+    ($values:ident, take_opt_iter) => {
+        fn take_opt_iter(&self, iter: &mut dyn Iterator<Item = Option<usize>>) -> Series {
+            self.$values().take_opt_iter(iter)
+        }
+    };
+    ($values:ident, shift) => {
+        fn shift(&self, periods: i64) -> Series {
+            self.$values().shift(periods)
+        }
+    };
+    ($values:ident, cum_max) => {
+        fn cum_max(&self, reverse: bool) -> Series {
+            self.$values().cum_max(reverse)
+        }
+    };
+    ($values:ident, cum_min) => {
+        fn cum_min(&self, reverse: bool) -> Series {
+            self.$values().cum_min(reverse)
+        }
+    };
+    ($values:ident, cum_sum) => {
+        fn cum_sum(&self, reverse: bool) -> Series {
+            self.$values().cum_sum(reverse)
+        }
+    };
+    ($values:ident, array_data) => {
+        fn array_data(&self) -> Vec<ArrayDataRef> {
+            self.$values().array_data()
+        }
+    };
+    ($values:ident, chunks) => {
+        fn chunks(&self) -> &Vec<ArrayRef> {
+            self.$values().chunks()
+        }
+    };
+    ($values:ident, i8) => {
+        fn i8(&self) -> Result<&Int8Chunked> {
+            self.$values().i8()
+        }
+    };
+    ($values:ident, i16) => {
+        fn i16(&self) -> Result<&Int16Chunked> {
+            self.$values().i16()
+        }
+    };
+    ($values:ident, i32) => {
+        fn i32(&self) -> Result<&Int32Chunked> {
+            self.$values().i32()
+        }
+    };
+    ($values:ident, i64) => {
+        fn i64(&self) -> Result<&Int64Chunked> {
+            self.$values().i64()
+        }
+    };
+    ($values:ident, f32) => {
+        fn f32(&self) -> Result<&Float32Chunked> {
+            self.$values().f32()
+        }
+    };
+    ($values:ident, f64) => {
+        fn f64(&self) -> Result<&Float64Chunked> {
+            self.$values().f64()
+        }
+    };
+    ($values:ident, u8) => {
+        fn u8(&self) -> Result<&UInt8Chunked> {
+            self.$values().u8()
+        }
+    };
+    ($values:ident, u16) => {
+        fn u16(&self) -> Result<&UInt16Chunked> {
+            self.$values().u16()
+        }
+    };
+    ($values:ident, u32) => {
+        fn u32(&self) -> Result<&UInt32Chunked> {
+            self.$values().u32()
+        }
+    };
+    ($values:ident, u64) => {
+        fn u64(&self) -> Result<&UInt64Chunked> {
+            self.$values().u64()
+        }
+    };
+    ($values:ident, bool) => {
+        fn bool(&self) -> Result<&BooleanChunked> {
+            self.$values().bool()
+        }
+    };
+    ($values:ident, utf8) => {
+        fn utf8(&self) -> Result<&Utf8Chunked> {
+            self.$values().utf8()
+        }
+    };
+    ($values:ident, date32) => {
+        fn date32(&self) -> Result<&Date32Chunked> {
+            self.$values().date32()
+        }
+    };
+    ($values:ident, date64) => {
+        fn date64(&self) -> Result<&Date64Chunked> {
+            self.$values().date64()
+        }
+    };
+    ($values:ident, time64_nanosecond) => {
+        fn time64_nanosecond(&self) -> Result<&Time64NanosecondChunked> {
+            self.$values().time64_nanosecond()
+        }
+    };
+    ($values:ident, duration_nanosecond) => {
+        fn duration_nanosecond(&self) -> Result<&DurationNanosecondChunked> {
+            self.$values().duration_nanosecond()
+        }
+    };
+    ($values:ident, duration_millisecond) => {
+        fn duration_millisecond(&self) -> Result<&DurationMillisecondChunked> {
+            self.$values().duration_millisecond()
+        }
+    };
+    ($values:ident, list) => {
+        fn list(&self) -> Result<&ListChunked> {
+            self.$values().list()
+        }
+    };
+    ($values:ident, categorical) => {
+        fn categorical(&self) -> Result<&CategoricalChunked> {
+            self.$values().categorical()
+        }
+    };
+    ($values:ident, cast_with_datatype) => {
+        fn cast_with_datatype(&self, data_type: &DataType) -> Result<Series> {
+            self.$values().cast_with_datatype(data_type)
+        }
+    };
+    ($values:ident, to_dummies) => {
+        fn to_dummies(&self) -> Result<DataFrame> {
+            self.$values().to_dummies()
+        }
+    };
+    ($values:ident, value_counts) => {
+        fn value_counts(&self) -> Result<DataFrame> {
+            self.$values().value_counts()
+        }
+    };
+    ($values:ident, sort) => {
+        fn sort(&self, reverse: bool) -> Series {
+            self.$values().sort(reverse)
+        }
+    };
+    ($values:ident, argsort) => {
+        fn argsort(&self, reverse: bool) -> IdxCa {
+            self.$values().argsort(reverse)
+        }
+    };
+    ($values:ident, arg_true) => {
+        fn arg_true(&self) -> Result<IdxCa> {
+            self.$values().arg_true()
+        }
+    };
+    ($values:ident, null_bits) => {
+        fn null_bits(&self) -> Vec<(usize, Option<Buffer>)> {
+            self.$values().null_bits()
+        }
+    };
+    ($values:ident, fill_none) => {
+        fn fill_none(&self, strategy: FillNoneStrategy) -> Result<Series> {
+            self.$values().fill_none(strategy)
+        }
+    };
+    ($values:ident, zip_with) => {
+        fn zip_with(&self, mask: &BooleanChunked, other: &Series) -> Result<Series> {
+            self.$values().zip_with(mask, other)
+        }
+    };
+    ($values:ident, sum_as_series) => {
+        fn sum_as_series(&self) -> Series {
+            self.$values().sum_as_series()
+        }
+    };
+    ($values:ident, max_as_series) => {
+        fn max_as_series(&self) -> Series {
+            self.$values().max_as_series()
+        }
+    };
+    ($values:ident, min_as_series) => {
+        fn min_as_series(&self) -> Series {
+            self.$values().min_as_series()
+        }
+    };
+    ($values:ident, mean_as_series) => {
+        fn mean_as_series(&self) -> Series {
+            self.$values().mean_as_series()
+        }
+    };
+    ($values:ident, median_as_series) => {
+        fn median_as_series(&self) -> Series {
+            self.$values().median_as_series()
+        }
+    };
+    ($values:ident, var_as_series) => {
+        fn var_as_series(&self) -> Series {
+            self.$values().var_as_series()
+        }
+    };
+    ($values:ident, std_as_series) => {
+        fn std_as_series(&self) -> Series {
+            self.$values().std_as_series()
+        }
+    };
+    ($values:ident, quantile_as_series) => {
+        fn quantile_as_series(&self, quantile: f64) -> Result<Series> {
+            self.$values().quantile_as_series(quantile)
+        }
+    };
+    ($values:ident, rolling_mean) => {
+        fn rolling_mean(
+            &self,
+            window_size: usize,
+            weight: Option<&[f64]>,
+            ignore_null: bool,
+        ) -> Result<Series> {
+            self.$values()
+                .rolling_mean(window_size, weight, ignore_null)
+        }
+    };
+    ($values:ident, rolling_sum) => {
+        fn rolling_sum(
+            &self,
+            window_size: usize,
+            weight: Option<&[f64]>,
+            ignore_null: bool,
+        ) -> Result<Series> {
+            self.$values().rolling_sum(window_size, weight, ignore_null)
+        }
+    };
+    ($values:ident, rolling_min) => {
+        fn rolling_min(
+            &self,
+            window_size: usize,
+            weight: Option<&[f64]>,
+            ignore_null: bool,
+        ) -> Result<Series> {
+            self.$values().rolling_min(window_size, weight, ignore_null)
+        }
+    };
+    ($values:ident, rolling_max) => {
+        fn rolling_max(
+            &self,
+            window_size: usize,
+            weight: Option<&[f64]>,
+            ignore_null: bool,
+        ) -> Result<Series> {
+            self.$values().rolling_max(window_size, weight, ignore_null)
+        }
+    };
+    ($values:ident, fmt_list) => {
+        fn fmt_list(&self) -> String {
+            self.$values().fmt_list()
+        }
+    };
+    ($values:ident, hour) => {
+        #[cfg(feature = "temporal")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "temporal")))]
+        fn hour(&self) -> Result<Series> {
+            self.$values().hour()
+        }
+    };
+    ($values:ident, minute) => {
+        #[cfg(feature = "temporal")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "temporal")))]
+        fn minute(&self) -> Result<Series> {
+            self.$values().minute()
+        }
+    };
+    ($values:ident, second) => {
+        #[cfg(feature = "temporal")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "temporal")))]
+        fn second(&self) -> Result<Series> {
+            self.$values().second()
+        }
+    };
+    ($values:ident, nanosecond) => {
+        #[cfg(feature = "temporal")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "temporal")))]
+        fn nanosecond(&self) -> Result<Series> {
+            self.$values().nanosecond()
+        }
+    };
+    ($values:ident, day) => {
+        #[cfg(feature = "temporal")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "temporal")))]
+        fn day(&self) -> Result<Series> {
+            self.$values().day()
+        }
+    };
+    ($values:ident, ordinal_day) => {
+        #[cfg(feature = "temporal")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "temporal")))]
+        fn ordinal_day(&self) -> Result<Series> {
+            self.$values().ordinal_day()
+        }
+    };
+    ($values:ident, month) => {
+        #[cfg(feature = "temporal")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "temporal")))]
+        fn month(&self) -> Result<Series> {
+            self.$values().month()
+        }
+    };
+    ($values:ident, year) => {
+        #[cfg(feature = "temporal")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "temporal")))]
+        fn year(&self) -> Result<Series> {
+            self.$values().year()
+        }
+    };
+    ($values:ident, datetime_str_fmt) => {
+        #[cfg(feature = "temporal")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "temporal")))]
+        fn datetime_str_fmt(&self, fmt: &str) -> Result<Series> {
+            self.$values().datetime_str_fmt(fmt)
+        }
+    };
+    ($values:ident, sample_n) => {
+        #[cfg(feature = "random")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "random")))]
+        fn sample_n(&self, n: usize, with_replacement: bool) -> Result<Series> {
+            self.$values().sample_n(n, with_replacement)
+        }
+    };
+    ($values:ident, sample_frac) => {
+        #[cfg(feature = "random")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "random")))]
+        fn sample_frac(&self, frac: f64, with_replacement: bool) -> Result<Series> {
+            self.$values().sample_frac(frac, with_replacement)
+        }
+    };
+    ($values:ident, get_as_any) => {
+        fn get_as_any(&self, index: usize) -> &dyn Any {
+            self.$values().get_as_any(index)
+        }
+    };
+    ($values:ident, pow) => {
+        fn pow(&self, exponent: f64) -> Result<Series> {
+            self.$values().pow(exponent)
+        }
+    };
+    ($values:ident, peak_max) => {
+        fn peak_max(&self) -> BooleanChunked {
+            self.$values().peak_max()
+        }
+    };
+    ($values:ident, peak_min) => {
+        fn peak_min(&self) -> BooleanChunked {
+            self.$values().peak_min()
+        }
+    };
+    ($values:ident, clip_quantile) => {
+        fn clip_quantile(&self, low_q: f64, high_q: f64) -> Result<Series> {
+            self.$values().clip_quantile(low_q, high_q)
+        }
+    };
+    ($values:ident, standardize) => {
+        fn standardize(&self) -> Result<Series> {
+            self.$values().standardize()
+        }
+    };
+    ($values:ident, min_max_scale) => {
+        fn min_max_scale(&self) -> Result<Series> {
+            self.$values().min_max_scale()
+        }
+    };
+}
+
+/// Implement the methods that mutate a Series in place. They are unreachable, because
+/// [Series](crate::series::Series) replaces a Series that doesn't store its values by the
+/// materialized values before it is mutated.
+macro_rules! impl_unreachable_mutations {
+    ($what:literal) => {
+        fn append_array(&mut self, _other: ArrayRef) -> Result<()> {
+            unreachable!(concat!($what, " is materialized before it is mutated"))
+        }
+
+        fn append(&mut self, _other: &Series) -> Result<()> {
+            unreachable!(concat!($what, " is materialized before it is mutated"))
+        }
+
+        fn as_single_ptr(&mut self) -> Result<usize> {
+            unreachable!(concat!($what, " is materialized before it is mutated"))
+        }
+    };
+}
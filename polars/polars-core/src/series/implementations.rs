@@ -9,7 +9,6 @@ use crate::fmt::FmtList;
 use crate::frame::group_by::*;
 use crate::frame::hash_join::{HashJoin, ZipOuterJoinColumn};
 use crate::prelude::*;
-#[cfg(feature = "object")]
 use crate::series::private::PrivateSeries;
 use ahash::RandomState;
 use arrow::array::{ArrayDataRef, ArrayRef};
@@ -54,9 +53,15 @@ where
     T: 'static + PolarsDataType,
 {
     fn as_ref(&self) -> &ChunkedArray<T> {
+        // a constant Series doesn't store a ChunkedArray
+        if let Some(s) = self.materialized() {
+            return s.as_ref().as_ref();
+        }
         if &T::get_dtype() == self.dtype() ||
             // needed because we want to get ref of List no matter what the inner type is.
-            (matches!(T::get_dtype(), DataType::List(_)) && matches!(self.dtype(), DataType::List(_)) )
+            (matches!(T::get_dtype(), DataType::List(_)) && matches!(self.dtype(), DataType::List(_)) ) ||
+            // and of a Categorical no matter what the ordering is.
+            (matches!(T::get_dtype(), DataType::Categorical(_)) && matches!(self.dtype(), DataType::Categorical(_)))
         {
             unsafe { &*(self as *const dyn SeriesTrait as *const ChunkedArray<T>) }
         } else {
@@ -82,59 +87,99 @@ macro_rules! impl_dyn_series {
                 self.0.vec_hash(random_state)
             }
 
-            fn agg_mean(&self, groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+            fn agg_mean(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
                 self.0.agg_mean(groups)
             }
 
-            fn agg_min(&self, groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+            fn agg_min(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
                 self.0.agg_min(groups)
             }
 
-            fn agg_max(&self, groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+            fn agg_max(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
                 self.0.agg_max(groups)
             }
 
-            fn agg_sum(&self, groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+            fn agg_sum(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
                 self.0.agg_sum(groups)
             }
 
-            fn agg_first(&self, groups: &[(u32, Vec<u32>)]) -> Series {
+            fn agg_first(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
                 self.0.agg_first(groups)
             }
 
-            fn agg_last(&self, groups: &[(u32, Vec<u32>)]) -> Series {
+            fn agg_last(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
                 self.0.agg_last(groups)
             }
 
-            fn agg_std(&self, groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+            fn agg_std(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
                 self.0.agg_std(groups)
             }
 
-            fn agg_var(&self, groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+            fn agg_var(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
                 self.0.agg_var(groups)
             }
 
-            fn agg_n_unique(&self, groups: &[(u32, Vec<u32>)]) -> Option<UInt32Chunked> {
+            fn agg_min_max(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<(Series, Series)> {
+                self.0.agg_min_max(groups)
+            }
+
+            fn agg_sum_count(
+                &self,
+                groups: &[(IdxSize, Vec<IdxSize>)],
+            ) -> Option<(Series, Series)> {
+                self.0.agg_sum_count(groups)
+            }
+
+            fn agg_mean_count(
+                &self,
+                groups: &[(IdxSize, Vec<IdxSize>)],
+            ) -> Option<(Series, Series)> {
+                self.0.agg_mean_count(groups)
+            }
+
+            fn agg_n_unique(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<IdxCa> {
                 self.0.agg_n_unique(groups)
             }
 
-            fn agg_list(&self, groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+            fn agg_list(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
                 self.0.agg_list(groups)
             }
 
-            fn agg_quantile(&self, groups: &[(u32, Vec<u32>)], quantile: f64) -> Option<Series> {
+            fn agg_quantile(
+                &self,
+                groups: &[(IdxSize, Vec<IdxSize>)],
+                quantile: f64,
+            ) -> Option<Series> {
                 self.0.agg_quantile(groups, quantile)
             }
 
-            fn agg_median(&self, groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+            fn agg_median(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
                 self.0.agg_median(groups)
             }
 
+            fn agg_top_k_idx(
+                &self,
+                groups: &[(IdxSize, Vec<IdxSize>)],
+                k: usize,
+            ) -> Option<Vec<Vec<IdxSize>>> {
+                self.0.agg_top_k_idx(groups, k)
+            }
+
+            fn agg_top_k(&self, groups: &[(IdxSize, Vec<IdxSize>)], k: usize) -> Option<Series> {
+                let groups: Vec<_> = self
+                    .0
+                    .agg_top_k_idx(groups, k)?
+                    .into_iter()
+                    .map(|idx| (idx.first().copied().unwrap_or(0), idx))
+                    .collect();
+                self.0.agg_list(&groups)
+            }
+
             fn pivot<'a>(
                 &self,
                 pivot_series: &'a (dyn SeriesTrait + 'a),
                 keys: Vec<Series>,
-                groups: &[(u32, Vec<u32>)],
+                groups: &[(IdxSize, Vec<IdxSize>)],
                 agg_type: PivotAgg,
             ) -> Result<DataFrame> {
                 self.0.pivot(pivot_series, keys, groups, agg_type)
@@ -144,23 +189,31 @@ macro_rules! impl_dyn_series {
                 &self,
                 pivot_series: &'a (dyn SeriesTrait + 'a),
                 keys: Vec<Series>,
-                groups: &[(u32, Vec<u32>)],
+                groups: &[(IdxSize, Vec<IdxSize>)],
             ) -> Result<DataFrame> {
                 self.0.pivot_count(pivot_series, keys, groups)
             }
-            fn hash_join_inner(&self, other: &Series) -> Vec<(u32, u32)> {
-                HashJoin::hash_join_inner(&self.0, other.as_ref().as_ref())
+            fn hash_join_inner(
+                &self,
+                other: &Series,
+                strategy: JoinStrategy,
+            ) -> Vec<(IdxSize, IdxSize)> {
+                HashJoin::hash_join_inner(&self.0, other.as_ref().as_ref(), strategy)
             }
-            fn hash_join_left(&self, other: &Series) -> Vec<(u32, Option<u32>)> {
-                HashJoin::hash_join_left(&self.0, other.as_ref().as_ref())
+            fn hash_join_left(
+                &self,
+                other: &Series,
+                strategy: JoinStrategy,
+            ) -> Vec<(IdxSize, Option<IdxSize>)> {
+                HashJoin::hash_join_left(&self.0, other.as_ref().as_ref(), strategy)
             }
-            fn hash_join_outer(&self, other: &Series) -> Vec<(Option<u32>, Option<u32>)> {
+            fn hash_join_outer(&self, other: &Series) -> Vec<(Option<IdxSize>, Option<IdxSize>)> {
                 HashJoin::hash_join_outer(&self.0, other.as_ref().as_ref())
             }
             fn zip_outer_join_column(
                 &self,
                 right_column: &Series,
-                opt_join_tuples: &[(Option<u32>, Option<u32>)],
+                opt_join_tuples: &[(Option<IdxSize>, Option<IdxSize>)],
             ) -> Series {
                 ZipOuterJoinColumn::zip_outer_join_column(&self.0, right_column, opt_join_tuples)
             }
@@ -201,6 +254,10 @@ macro_rules! impl_dyn_series {
                 self.0.rename(name);
             }
 
+            fn set_metadata(&mut self, metadata: FieldMetadata) {
+                self.0.set_metadata(metadata);
+            }
+
             fn array_data(&self) -> Vec<ArrayDataRef> {
                 self.0.array_data()
             }
@@ -498,7 +555,7 @@ macro_rules! impl_dyn_series {
             }
 
             fn categorical(&self) -> Result<&CategoricalChunked> {
-                if matches!(self.0.dtype(), DataType::Categorical) {
+                if matches!(self.0.dtype(), DataType::Categorical(_)) {
                     unsafe { Ok(&*(self as *const dyn SeriesTrait as *const CategoricalChunked)) }
                 } else {
                     Err(PolarsError::DataTypeMisMatch(
@@ -536,7 +593,7 @@ macro_rules! impl_dyn_series {
                 ChunkFilter::filter(&self.0, filter).map(|ca| ca.into_series())
             }
 
-            fn take(&self, indices: &UInt32Chunked) -> Series {
+            fn take(&self, indices: &IdxCa) -> Series {
                 let indices = if indices.chunks.len() > 1 {
                     Cow::Owned(indices.rechunk())
                 } else {
@@ -557,7 +614,7 @@ macro_rules! impl_dyn_series {
                 ChunkTake::take_unchecked(&self.0, iter.into()).into_series()
             }
 
-            unsafe fn take_unchecked(&self, idx: &UInt32Chunked) -> Result<Series> {
+            unsafe fn take_unchecked(&self, idx: &IdxCa) -> Result<Series> {
                 let idx = if idx.chunks.len() > 1 {
                     Cow::Owned(idx.rechunk())
                 } else {
@@ -633,9 +690,13 @@ macro_rules! impl_dyn_series {
                             .map(|ca| ca.into_series())
                     }
                     List(_) => ChunkCast::cast::<ListType>(&self.0).map(|ca| ca.into_series()),
-                    Categorical => {
-                        ChunkCast::cast::<CategoricalType>(&self.0).map(|ca| ca.into_series())
+                    // validate the values against the closed set of categories
+                    Categorical(CategoricalOrdering::Enum(categories)) => {
+                        let categories: Vec<&str> = categories.iter().map(|s| s.as_str()).collect();
+                        self.0.clone().into_series().cast_to_enum(&categories, true)
                     }
+                    Categorical(ordering) => ChunkCast::cast::<CategoricalType>(&self.0)
+                        .map(|ca| ca.set_ordering(ordering.clone()).into_series()),
                     dt => Err(PolarsError::Other(
                         format!("Casting to {:?} is not supported", dt).into(),
                     )),
@@ -667,7 +728,7 @@ macro_rules! impl_dyn_series {
                 ChunkSort::sort(&self.0, reverse).into_series()
             }
 
-            fn argsort(&self, reverse: bool) -> UInt32Chunked {
+            fn argsort(&self, reverse: bool) -> IdxCa {
                 ChunkSort::argsort(&self.0, reverse)
             }
 
@@ -675,6 +736,14 @@ macro_rules! impl_dyn_series {
                 self.0.null_count()
             }
 
+            fn sorted_flag(&self) -> IsSorted {
+                self.0.sorted_flag()
+            }
+
+            fn set_sorted_flag(&mut self, sorted: IsSorted) {
+                self.0.set_sorted_flag(sorted)
+            }
+
             fn unique(&self) -> Result<Series> {
                 ChunkUnique::unique(&self.0).map(|ca| ca.into_series())
             }
@@ -683,11 +752,15 @@ macro_rules! impl_dyn_series {
                 ChunkUnique::n_unique(&self.0)
             }
 
-            fn arg_unique(&self) -> Result<Vec<u32>> {
+            fn approx_n_unique(&self) -> Result<usize> {
+                ChunkUnique::approx_n_unique(&self.0)
+            }
+
+            fn arg_unique(&self) -> Result<Vec<IdxSize>> {
                 ChunkUnique::arg_unique(&self.0)
             }
 
-            fn arg_true(&self) -> Result<UInt32Chunked> {
+            fn arg_true(&self) -> Result<IdxCa> {
                 let ca: &BooleanChunked = self.bool()?;
                 Ok(ca.arg_true())
             }
@@ -910,6 +983,20 @@ macro_rules! impl_dyn_series {
             fn peak_min(&self) -> BooleanChunked {
                 self.0.peak_min()
             }
+
+            fn clip_quantile(&self, low_q: f64, high_q: f64) -> Result<Series> {
+                self.0
+                    .clip_quantile(low_q, high_q)
+                    .map(|ca| ca.into_series())
+            }
+
+            fn standardize(&self) -> Result<Series> {
+                self.0.standardize()
+            }
+
+            fn min_max_scale(&self) -> Result<Series> {
+                self.0.min_max_scale()
+            }
         }
     };
 }
@@ -967,6 +1054,10 @@ where
         ObjectChunked::rename(&mut self.0, name)
     }
 
+    fn set_metadata(&mut self, metadata: FieldMetadata) {
+        ObjectChunked::set_metadata(&mut self.0, metadata)
+    }
+
     fn array_data(&self) -> Vec<ArrayDataRef> {
         ObjectChunked::array_data(&self.0)
     }
@@ -1022,7 +1113,7 @@ where
         todo!()
     }
 
-    unsafe fn take_unchecked(&self, _idx: &UInt32Chunked) -> Result<Series> {
+    unsafe fn take_unchecked(&self, _idx: &IdxCa) -> Result<Series> {
         todo!()
     }
 
@@ -1087,7 +1178,7 @@ where
         ChunkSort::sort(&self.0, reverse).into_series()
     }
 
-    fn argsort(&self, reverse: bool) -> UInt32Chunked {
+    fn argsort(&self, reverse: bool) -> IdxCa {
         ChunkSort::argsort(&self.0, reverse)
     }
 
@@ -1103,7 +1194,11 @@ where
         ChunkUnique::n_unique(&self.0)
     }
 
-    fn arg_unique(&self) -> Result<Vec<u32>> {
+    fn approx_n_unique(&self) -> Result<usize> {
+        ChunkUnique::approx_n_unique(&self.0)
+    }
+
+    fn arg_unique(&self) -> Result<Vec<IdxSize>> {
         ChunkUnique::arg_unique(&self.0)
     }
 
@@ -1,14 +1,27 @@
 //! Type agnostic columnar data structure.
 pub use crate::prelude::ChunkCompare;
 use crate::prelude::*;
-use arrow::{array::ArrayRef, buffer::Buffer};
+use arrow::{
+    array::{Array, ArrayRef},
+    buffer::Buffer,
+};
 pub(crate) mod arithmetic;
 mod comparison;
+#[macro_use]
+mod forward;
+pub mod constant;
+pub mod dedup;
 pub mod implementations;
 pub(crate) mod iterator;
+pub mod null;
 
 use crate::chunked_array::builder::get_list_builder;
 use crate::chunked_array::float::IsNan;
+use crate::series::constant::ConstantChunked;
+use crate::series::dedup::DedupUtf8Chunked;
+use crate::series::null::NullChunked;
+use crate::utils::{check_bounds, check_bounds_ca};
+use ahash::AHashMap;
 use arrow::array::ArrayDataRef;
 use arrow::compute::cast;
 use itertools::Itertools;
@@ -26,54 +39,82 @@ pub trait IntoSeries {
 
 pub(crate) mod private {
     use super::*;
-    use crate::frame::group_by::{GroupTuples, PivotAgg};
+    use crate::frame::group_by::{agg_count, GroupTuples, PivotAgg};
     use ahash::RandomState;
 
     pub trait PrivateSeries {
         fn vec_hash(&self, _random_state: RandomState) -> UInt64Chunked {
             unimplemented!()
         }
-        fn agg_mean(&self, _groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+        fn agg_mean(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
             unimplemented!()
         }
-        fn agg_min(&self, _groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+        fn agg_min(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
             unimplemented!()
         }
-        fn agg_max(&self, _groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+        fn agg_max(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
             unimplemented!()
         }
-        fn agg_sum(&self, _groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+        fn agg_sum(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
             unimplemented!()
         }
-        fn agg_std(&self, _groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+        fn agg_std(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
             unimplemented!()
         }
-        fn agg_var(&self, _groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+        fn agg_var(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
             unimplemented!()
         }
-        fn agg_first(&self, _groups: &[(u32, Vec<u32>)]) -> Series {
+        fn agg_min_max(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<(Series, Series)> {
+            Some((self.agg_min(groups)?, self.agg_max(groups)?))
+        }
+        fn agg_sum_count(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<(Series, Series)> {
+            Some((self.agg_sum(groups)?, agg_count(groups)))
+        }
+        fn agg_mean_count(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<(Series, Series)> {
+            Some((self.agg_mean(groups)?, agg_count(groups)))
+        }
+        fn agg_first(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
+            unimplemented!()
+        }
+        fn agg_last(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
+            unimplemented!()
+        }
+        fn agg_n_unique(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<IdxCa> {
             unimplemented!()
         }
-        fn agg_last(&self, _groups: &[(u32, Vec<u32>)]) -> Series {
+        fn agg_list(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
             unimplemented!()
         }
-        fn agg_n_unique(&self, _groups: &[(u32, Vec<u32>)]) -> Option<UInt32Chunked> {
+        fn agg_quantile(
+            &self,
+            _groups: &[(IdxSize, Vec<IdxSize>)],
+            _quantile: f64,
+        ) -> Option<Series> {
             unimplemented!()
         }
-        fn agg_list(&self, _groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+        fn agg_median(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
             unimplemented!()
         }
-        fn agg_quantile(&self, _groups: &[(u32, Vec<u32>)], _quantile: f64) -> Option<Series> {
+        fn agg_top_k_idx(
+            &self,
+            _groups: &[(IdxSize, Vec<IdxSize>)],
+            _k: usize,
+        ) -> Option<Vec<Vec<IdxSize>>> {
             unimplemented!()
         }
-        fn agg_median(&self, _groups: &[(u32, Vec<u32>)]) -> Option<Series> {
+        fn agg_top_k(&self, _groups: &[(IdxSize, Vec<IdxSize>)], _k: usize) -> Option<Series> {
             unimplemented!()
         }
+        /// The nulls per group of a Series without a validity bitmap. Otherwise `None`, and the
+        /// nulls are read from the bitmap.
+        fn agg_null_count(&self, _groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<IdxCa> {
+            None
+        }
         fn pivot<'a>(
             &self,
             _pivot_series: &'a (dyn SeriesTrait + 'a),
             _keys: Vec<Series>,
-            _groups: &[(u32, Vec<u32>)],
+            _groups: &[(IdxSize, Vec<IdxSize>)],
             _agg_type: PivotAgg,
         ) -> Result<DataFrame> {
             unimplemented!()
@@ -83,24 +124,32 @@ pub(crate) mod private {
             &self,
             _pivot_series: &'a (dyn SeriesTrait + 'a),
             _keys: Vec<Series>,
-            _groups: &[(u32, Vec<u32>)],
+            _groups: &[(IdxSize, Vec<IdxSize>)],
         ) -> Result<DataFrame> {
             unimplemented!()
         }
 
-        fn hash_join_inner(&self, _other: &Series) -> Vec<(u32, u32)> {
+        fn hash_join_inner(
+            &self,
+            _other: &Series,
+            _strategy: JoinStrategy,
+        ) -> Vec<(IdxSize, IdxSize)> {
             unimplemented!()
         }
-        fn hash_join_left(&self, _other: &Series) -> Vec<(u32, Option<u32>)> {
+        fn hash_join_left(
+            &self,
+            _other: &Series,
+            _strategy: JoinStrategy,
+        ) -> Vec<(IdxSize, Option<IdxSize>)> {
             unimplemented!()
         }
-        fn hash_join_outer(&self, _other: &Series) -> Vec<(Option<u32>, Option<u32>)> {
+        fn hash_join_outer(&self, _other: &Series) -> Vec<(Option<IdxSize>, Option<IdxSize>)> {
             unimplemented!()
         }
         fn zip_outer_join_column(
             &self,
             _right_column: &Series,
-            _opt_join_tuples: &[(Option<u32>, Option<u32>)],
+            _opt_join_tuples: &[(Option<IdxSize>, Option<IdxSize>)],
         ) -> Series {
             unimplemented!()
         }
@@ -123,6 +172,14 @@ pub(crate) mod private {
         fn group_tuples(&self, _multithreaded: bool) -> GroupTuples {
             unimplemented!()
         }
+        /// The value of a constant Series.
+        fn constant_value(&self) -> Option<&Series> {
+            None
+        }
+        /// The values of a Series that doesn't store them, e.g. a constant Series.
+        fn materialized(&self) -> Option<&Series> {
+            None
+        }
     }
 }
 
@@ -145,6 +202,11 @@ pub trait SeriesTrait: Send + Sync + private::PrivateSeries {
     /// Rename the Series.
     fn rename(&mut self, name: &str);
 
+    /// Set the key-value metadata of the Series.
+    fn set_metadata(&mut self, _metadata: FieldMetadata) {
+        unimplemented!()
+    }
+
     /// Get Arrow ArrayData
     fn array_data(&self) -> Vec<ArrayDataRef> {
         unimplemented!()
@@ -262,6 +324,18 @@ pub trait SeriesTrait: Send + Sync + private::PrivateSeries {
         ))
     }
 
+    /// Unpack to ChunkedArray of the index type, see [IdxSize](crate::datatypes::IdxSize).
+    fn idx(&self) -> Result<&IdxCa> {
+        #[cfg(feature = "bigidx")]
+        {
+            self.u64()
+        }
+        #[cfg(not(feature = "bigidx"))]
+        {
+            self.u32()
+        }
+    }
+
     /// Unpack to ChunkedArray of dtype bool
     fn bool(&self) -> Result<&BooleanChunked> {
         Err(PolarsError::DataTypeMisMatch(
@@ -372,7 +446,7 @@ pub trait SeriesTrait: Send + Sync + private::PrivateSeries {
     ///
     /// # Safety
     /// This doesn't check any bounds. Null validity is checked.
-    unsafe fn take_unchecked(&self, _idx: &UInt32Chunked) -> Result<Series> {
+    unsafe fn take_unchecked(&self, _idx: &IdxCa) -> Result<Series> {
         unimplemented!()
     }
 
@@ -402,7 +476,7 @@ pub trait SeriesTrait: Send + Sync + private::PrivateSeries {
     /// # Safety
     ///
     /// Out of bounds access doesn't Error but will return a Null value
-    fn take(&self, _indices: &UInt32Chunked) -> Series {
+    fn take(&self, _indices: &IdxCa) -> Series {
         unimplemented!()
     }
 
@@ -495,7 +569,7 @@ pub trait SeriesTrait: Send + Sync + private::PrivateSeries {
     }
 
     /// Retrieve the indexes needed for a sort.
-    fn argsort(&self, _reverse: bool) -> UInt32Chunked {
+    fn argsort(&self, _reverse: bool) -> IdxCa {
         unimplemented!()
     }
 
@@ -504,23 +578,37 @@ pub trait SeriesTrait: Send + Sync + private::PrivateSeries {
         unimplemented!()
     }
 
+    /// Get the sortedness flag. See [ChunkedArray::sorted_flag](crate::chunked_array::ChunkedArray::sorted_flag).
+    fn sorted_flag(&self) -> IsSorted {
+        IsSorted::Not
+    }
+
+    /// Flag the Series as sorted. This is not checked.
+    fn set_sorted_flag(&mut self, _sorted: IsSorted) {}
+
     /// Get unique values in the Series.
     fn unique(&self) -> Result<Series> {
         unimplemented!()
     }
 
-    /// Get unique values in the Series.
+    /// Get the number of unique values in the Series.
     fn n_unique(&self) -> Result<usize> {
         unimplemented!()
     }
 
+    /// Estimate the number of unique values in the Series in constant memory.
+    /// See [ChunkUnique::approx_n_unique](crate::chunked_array::ops::ChunkUnique::approx_n_unique).
+    fn approx_n_unique(&self) -> Result<usize> {
+        unimplemented!()
+    }
+
     /// Get first indexes of unique values.
-    fn arg_unique(&self) -> Result<Vec<u32>> {
+    fn arg_unique(&self) -> Result<Vec<IdxSize>> {
         unimplemented!()
     }
 
     /// Get indexes that evaluate true
-    fn arg_true(&self) -> Result<UInt32Chunked> {
+    fn arg_true(&self) -> Result<IdxCa> {
         Err(PolarsError::InvalidOperation(
             "arg_true can only be called for boolean dtype".into(),
         ))
@@ -836,6 +924,44 @@ pub trait SeriesTrait: Send + Sync + private::PrivateSeries {
     fn peak_min(&self) -> BooleanChunked {
         unimplemented!()
     }
+
+    /// Clip a numeric Series to its `low_q` and `high_q` quantiles (winsorize).
+    /// Null values stay null.
+    ///
+    /// ```
+    /// # use polars_core::prelude::*;
+    /// let s = Series::new("a", &[-100, 1, 2, 3, 4, 100]);
+    /// let clipped = s.clip_quantile(0.2, 0.8).unwrap();
+    /// assert_eq!(Vec::from(clipped.i32().unwrap()), &[Some(1), Some(1), Some(2), Some(3), Some(4), Some(4)]);
+    /// ```
+    fn clip_quantile(&self, _low_q: f64, _high_q: f64) -> Result<Series> {
+        Err(PolarsError::InvalidOperation(
+            format!("clip_quantile not supported on dtype {:?}", self.dtype()).into(),
+        ))
+    }
+
+    /// Standardize a numeric Series to a `Float64` Series with mean 0 and standard deviation 1
+    /// (z-score). See [ChunkScale](crate::prelude::ChunkScale).
+    ///
+    /// ```
+    /// # use polars_core::prelude::*;
+    /// let s = Series::new("a", &[1, 2, 3]);
+    /// let z = s.standardize().unwrap();
+    /// assert_eq!(Vec::from(z.f64().unwrap()), &[Some(-1.0), Some(0.0), Some(1.0)]);
+    /// ```
+    fn standardize(&self) -> Result<Series> {
+        Err(PolarsError::InvalidOperation(
+            format!("standardize not supported on dtype {:?}", self.dtype()).into(),
+        ))
+    }
+
+    /// Scale a numeric Series to a `Float64` Series in the range [0.0, 1.0].
+    /// See [ChunkScale](crate::prelude::ChunkScale).
+    fn min_max_scale(&self) -> Result<Series> {
+        Err(PolarsError::InvalidOperation(
+            format!("min_max_scale not supported on dtype {:?}", self.dtype()).into(),
+        ))
+    }
 }
 
 impl<'a> (dyn SeriesTrait + 'a) {
@@ -843,7 +969,11 @@ impl<'a> (dyn SeriesTrait + 'a) {
     where
         N: PolarsDataType,
     {
-        if &N::get_dtype() == self.dtype() {
+        let matches = match (N::get_dtype(), self.dtype()) {
+            (DataType::Categorical(_), DataType::Categorical(_)) => true,
+            (dtype, self_dtype) => &dtype == self_dtype,
+        };
+        if matches {
             Ok(self.as_ref())
         } else {
             Err(PolarsError::DataTypeMisMatch(
@@ -963,18 +1093,176 @@ impl Series {
         self
     }
 
+    /// Get the key-value metadata of the Series, e.g. a description or the unit of the values.
+    ///
+    /// The metadata is kept by renames, projections, filters, takes and slices, and is written
+    /// to and read from IPC and parquet files. Operations that compute new values don't keep it.
+    pub fn metadata(&self) -> &FieldMetadata {
+        self.field().metadata()
+    }
+
+    /// Set the key-value metadata of the Series.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use polars_core::prelude::*;
+    ///
+    /// let mut s = Series::new("distance", &[1.5, 2.0]);
+    /// let mut metadata = FieldMetadata::new();
+    /// metadata.insert("unit".to_string(), "km".to_string());
+    /// s.set_metadata(metadata);
+    /// assert_eq!(s.metadata()["unit"], "km");
+    /// ```
+    pub fn set_metadata(&mut self, metadata: FieldMetadata) -> &mut Series {
+        self.get_inner_mut().set_metadata(metadata);
+        self
+    }
+
+    /// Replace a Series that doesn't store its values, e.g. a constant Series, by its values.
+    fn materialize_in_place(&mut self) {
+        if let Some(inner) = self.0.materialized().map(|s| s.0.clone()) {
+            self.0 = inner
+        }
+    }
+
     /// Append arrow array of same datatype.
     pub fn append_array(&mut self, other: ArrayRef) -> Result<&mut Self> {
+        self.materialize_in_place();
         self.get_inner_mut().append_array(other)?;
         Ok(self)
     }
 
     /// Append a Series of the same type in place.
     pub fn append(&mut self, other: &Series) -> Result<&mut Self> {
+        self.materialize_in_place();
         self.get_inner_mut().append(other)?;
         Ok(self)
     }
 
+    /// Take by index. Returns an error if any of the indices is out of bounds.
+    pub fn take_checked(&self, indices: &IdxCa) -> Result<Series> {
+        check_bounds_ca(indices, self.len())?;
+        Ok(self.take(indices))
+    }
+
+    /// Take by index from an iterator. Returns an error if any of the indices is out of bounds.
+    pub fn take_iter_checked(&self, iter: &mut dyn Iterator<Item = usize>) -> Result<Series> {
+        let indices = iter.collect::<Vec<_>>();
+        check_bounds(indices.iter().copied(), self.len())?;
+        Ok(self.take_iter(&mut indices.into_iter()))
+    }
+
+    /// Count the null values per group. The nulls are read from the validity bitmap, so a
+    /// Series without nulls doesn't look at the groups at all.
+    pub fn agg_null_count(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> IdxCa {
+        if let Some(ca) = self.0.agg_null_count(groups) {
+            return ca;
+        }
+        let ca: NoNull<IdxCa> = if self.null_count() == 0 {
+            groups.iter().map(|_| 0).collect()
+        } else {
+            let s = self.rechunk();
+            let arr = &s.chunks()[0];
+            groups
+                .iter()
+                .map(|(_, idx)| idx.iter().filter(|&&i| arr.is_null(i as usize)).count() as IdxSize)
+                .collect()
+        };
+        let mut ca = ca.into_inner();
+        ca.rename(self.name());
+        ca
+    }
+
+    /// Hash the values with a fixed `seed`. Equal values, including nulls, get equal hashes,
+    /// regardless of the other values in the Series, so the hashes can be used for feature
+    /// hashing. Categorical values are hashed by their string
+    /// value.
+    ///
+    /// The values are hashed with `ahash`, whose output depends on the platform, the enabled
+    /// cpu features and the version of the crate. The hashes are only comparable within a
+    /// process, so don't persist them.
+    pub fn hash(&self, seed: u64) -> Result<UInt64Chunked> {
+        let s = match self.dtype() {
+            DataType::Categorical(_) => self.cast::<Utf8Type>()?,
+            DataType::List(_) => {
+                return Err(PolarsError::InvalidOperation(
+                    "cannot hash a list Series".into(),
+                ))
+            }
+            #[cfg(feature = "object")]
+            DataType::Object => {
+                return Err(PolarsError::InvalidOperation(
+                    "cannot hash an object Series".into(),
+                ))
+            }
+            _ => self.clone(),
+        };
+        let random_state = ahash::RandomState::with_seeds(seed, seed, seed, seed);
+        let mut hashes = s.vec_hash(random_state);
+        hashes.rename(self.name());
+        Ok(hashes)
+    }
+
+    /// Check if the Series is sorted in ascending order, or descending if `reverse`.
+    /// Null values are ignored. Returns an error for data types that have no order, like lists
+    /// and objects.
+    ///
+    /// ```
+    /// # use polars_core::prelude::*;
+    /// let s = Series::new("a", &[Some(1), None, Some(2), Some(2)]);
+    /// assert!(s.is_sorted(false).unwrap());
+    /// assert!(!s.is_sorted(true).unwrap());
+    /// ```
+    pub fn is_sorted(&self, reverse: bool) -> Result<bool> {
+        match self.dtype() {
+            DataType::Boolean
+            | DataType::Utf8
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Date32
+            | DataType::Date64
+            | DataType::Time64(TimeUnit::Nanosecond)
+            | DataType::Duration(TimeUnit::Nanosecond)
+            | DataType::Duration(TimeUnit::Millisecond)
+            | DataType::Categorical(_) => {}
+            dt => {
+                return Err(PolarsError::InvalidOperation(
+                    format!("cannot check if a Series of dtype {:?} is sorted", dt).into(),
+                ))
+            }
+        }
+        let s = if self.null_count() > 0 {
+            self.drop_nulls()
+        } else {
+            self.clone()
+        };
+        let len = s.len();
+        if len < 2 {
+            return Ok(true);
+        }
+        // compare every value with its successor
+        let a = s.slice(0, len - 1)?;
+        let b = s.slice(1, len - 1)?;
+        let in_order = if reverse { a.gt_eq(&b) } else { a.lt_eq(&b) };
+        Ok(in_order.all_true())
+    }
+
+    /// Flag the Series as sorted, so that operations like joins can use a faster path.
+    /// This is not checked, see [is_sorted](Series::is_sorted) to verify the order.
+    pub fn set_sorted_flag(&mut self, sorted: IsSorted) -> &mut Self {
+        self.get_inner_mut().set_sorted_flag(sorted);
+        self
+    }
+
     /// Sort in place.
     pub fn sort_in_place(&mut self, reverse: bool) -> &mut Self {
         self.get_inner_mut().sort_in_place(reverse);
@@ -984,9 +1272,42 @@ impl Series {
     /// Rechunk and return a pointer to the start of the Series.
     /// Only implemented for numeric types
     pub fn as_single_ptr(&mut self) -> Result<usize> {
+        self.materialize_in_place();
         self.get_inner_mut().as_single_ptr()
     }
 
+    /// Repeat the first value `length` times, like a literal in an expression. The values are
+    /// not materialized until an operation needs them, so broadcasting a value to a large
+    /// DataFrame is cheap.
+    ///
+    /// ```
+    /// # use polars_core::prelude::*;
+    /// let s = Series::new("flag", &[1]).broadcast(3);
+    /// assert_eq!(Vec::from(s.i32().unwrap()), &[Some(1), Some(1), Some(1)]);
+    /// ```
+    pub fn broadcast(&self, length: usize) -> Series {
+        match self.dtype() {
+            #[cfg(feature = "object")]
+            DataType::Object => self.expand_at_index(0, length),
+            _ => ConstantChunked::new(self, 0, length).into_series(),
+        }
+    }
+
+    /// Store every distinct string of a Utf8 Series once, with an index per row. This cuts the
+    /// memory of columns with many repetitions of long strings, e.g. the messages of a log.
+    /// The Series stays Utf8: operations that select rows or sort keep the deduplicated storage,
+    /// other operations decode the strings for the duration of the operation.
+    ///
+    /// ```
+    /// # use polars_core::prelude::*;
+    /// let s = Series::new("msg", &["disk full", "disk full", "ok"]).dedup_strings().unwrap();
+    /// assert_eq!(s.dtype(), &DataType::Utf8);
+    /// assert_eq!(s.get(1), AnyValue::Utf8("disk full"));
+    /// ```
+    pub fn dedup_strings(&self) -> Result<Series> {
+        Ok(DedupUtf8Chunked::new(self.utf8()?).into_series())
+    }
+
     /// Cast to some primitive type.
     pub fn cast<N>(&self) -> Result<Self>
     where
@@ -994,6 +1315,129 @@ impl Series {
     {
         self.0.cast_with_datatype(&N::get_dtype())
     }
+
+    /// Create a new Series of length `size` where all values are null.
+    ///
+    /// ```
+    /// # use polars_core::prelude::*;
+    /// let s = Series::full_null("a", 3, &DataType::Float64).unwrap();
+    /// assert_eq!(s.null_count(), 3);
+    /// let s = Series::full_null("a", 3, &DataType::Null).unwrap();
+    /// assert_eq!(s.dtype(), &DataType::Null);
+    /// ```
+    pub fn full_null(name: &str, size: usize, dtype: &DataType) -> Result<Self> {
+        use DataType::*;
+        let s = match dtype {
+            Null => NullChunked::new(name, size).into_series(),
+            Boolean => BooleanChunked::full_null(name, size).into_series(),
+            Utf8 => Utf8Chunked::full_null(name, size).into_series(),
+            UInt8 => UInt8Chunked::full_null(name, size).into_series(),
+            UInt16 => UInt16Chunked::full_null(name, size).into_series(),
+            UInt32 => UInt32Chunked::full_null(name, size).into_series(),
+            UInt64 => UInt64Chunked::full_null(name, size).into_series(),
+            #[cfg(feature = "dtype-i8")]
+            Int8 => Int8Chunked::full_null(name, size).into_series(),
+            #[cfg(feature = "dtype-i16")]
+            Int16 => Int16Chunked::full_null(name, size).into_series(),
+            Int32 => Int32Chunked::full_null(name, size).into_series(),
+            Int64 => Int64Chunked::full_null(name, size).into_series(),
+            Float32 => Float32Chunked::full_null(name, size).into_series(),
+            Float64 => Float64Chunked::full_null(name, size).into_series(),
+            #[cfg(feature = "dtype-date32")]
+            Date32 => Date32Chunked::full_null(name, size).into_series(),
+            #[cfg(feature = "dtype-date64")]
+            Date64 => Date64Chunked::full_null(name, size).into_series(),
+            #[cfg(feature = "dtype-time64-ns")]
+            Time64(TimeUnit::Nanosecond) => {
+                Time64NanosecondChunked::full_null(name, size).into_series()
+            }
+            #[cfg(feature = "dtype-duration-ns")]
+            Duration(TimeUnit::Nanosecond) => {
+                DurationNanosecondChunked::full_null(name, size).into_series()
+            }
+            #[cfg(feature = "dtype-duration-ms")]
+            Duration(TimeUnit::Millisecond) => {
+                DurationMillisecondChunked::full_null(name, size).into_series()
+            }
+            List(inner) => {
+                let inner = DataType::from(inner);
+                if matches!(inner, List(_) | Categorical(_) | Null | Object) {
+                    return Err(PolarsError::InvalidOperation(
+                        format!(
+                            "cannot create a list Series with values of type {:?}",
+                            inner
+                        )
+                        .into(),
+                    ));
+                }
+                let mut builder = get_list_builder(&inner, 0, size, name);
+                for _ in 0..size {
+                    builder.append_opt_series(None);
+                }
+                builder.finish().into_series()
+            }
+            Categorical(ordering) => {
+                let mut ca: CategoricalChunked = UInt32Chunked::full_null(name, size).cast()?;
+                let mapping = match ordering {
+                    CategoricalOrdering::Enum(categories) => categories
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, cat)| (idx as u32, cat.clone()))
+                        .collect(),
+                    _ => AHashMap::new(),
+                };
+                ca.categorical_map = Some(Arc::new(mapping));
+                ca.set_ordering(ordering.clone()).into_series()
+            }
+            dt => {
+                return Err(PolarsError::InvalidOperation(
+                    format!("cannot create a null Series of type {:?}", dt).into(),
+                ))
+            }
+        };
+        Ok(s)
+    }
+
+    /// Create a new Series of length 0 with the given data type.
+    ///
+    /// ```
+    /// # use polars_core::prelude::*;
+    /// let s = Series::new_empty("a", &DataType::List(ArrowDataType::Float64)).unwrap();
+    /// assert_eq!(s.len(), 0);
+    /// assert_eq!(s.dtype(), &DataType::List(ArrowDataType::Float64));
+    /// ```
+    pub fn new_empty(name: &str, dtype: &DataType) -> Result<Self> {
+        Self::full_null(name, 0, dtype)
+    }
+
+    /// Cast a Utf8 or Categorical Series to a Categorical with a closed set of categories.
+    ///
+    /// Values that are not in `categories` return an error if `strict`, otherwise they become null.
+    /// ```
+    /// # use polars_core::prelude::*;
+    /// let s = Series::new("size", &["small", "large", "small"]);
+    /// let s = s.cast_to_enum(&["small", "medium", "large"], true).unwrap();
+    /// assert_eq!(s.max_as_series().get(0), AnyValue::Utf8("large"));
+    ///
+    /// let s = Series::new("size", &["small", "huge"]);
+    /// assert!(s.cast_to_enum(&["small", "medium", "large"], true).is_err());
+    /// ```
+    pub fn cast_to_enum(&self, categories: &[&str], strict: bool) -> Result<Self> {
+        let ca = match self.dtype() {
+            DataType::Utf8 => self.utf8()?.cast_to_enum(categories, strict)?,
+            DataType::Categorical(_) => self
+                .cast::<Utf8Type>()?
+                .utf8()?
+                .cast_to_enum(categories, strict)?,
+            dt => {
+                return Err(PolarsError::DataTypeMisMatch(
+                    format!("cannot cast {:?} to an enum", dt).into(),
+                ))
+            }
+        };
+        Ok(ca.into_series())
+    }
+
     /// Returns `None` if the array is empty or only contains null values.
     /// ```
     /// # use polars_core::prelude::*;
@@ -1056,6 +1500,24 @@ impl Series {
             .and_then(|s| s.f64().unwrap().get(0).and_then(T::from))
     }
 
+    /// Get the `k` largest values, largest first. Null values are skipped.
+    ///
+    /// ```
+    /// # use polars_core::prelude::*;
+    /// let s = Series::new("days", [3, 1, 5, 2].as_ref());
+    /// let top = s.top_k(2).unwrap();
+    /// assert_eq!(Vec::from(top.i32().unwrap()), &[Some(5), Some(3)]);
+    /// ```
+    pub fn top_k(&self, k: usize) -> Result<Series> {
+        let groups = vec![(0, (0..self.len() as IdxSize).collect())];
+        let idx = self.agg_top_k_idx(&groups, k).ok_or_else(|| {
+            PolarsError::InvalidOperation(
+                format!("top_k is not supported for dtype {:?}", self.dtype()).into(),
+            )
+        })?;
+        Ok(unsafe { self.take_iter_unchecked(&mut idx[0].iter().map(|i| *i as usize)) })
+    }
+
     /// Explode a list or utf8 Series. This expands every item to a new row..
     pub fn explode(&self) -> Result<Series> {
         match self.dtype() {
@@ -1215,6 +1677,59 @@ impl<T: AsRef<[Series]>> NamedFrom<T, ListType> for Series {
     }
 }
 
+macro_rules! impl_named_from_nested {
+    ($type:ty $(, $lt:lifetime)?) => {
+        impl<$($lt,)? T: AsRef<[Vec<$type>]>> NamedFrom<T, [Vec<$type>]> for Series {
+            fn new(name: &str, v: T) -> Self {
+                named_from_nested(name, v.as_ref(), |v: &Vec<$type>| Series::new("", v))
+            }
+        }
+        impl<$($lt,)? T: AsRef<[Vec<Option<$type>>]>> NamedFrom<T, [Vec<Option<$type>>]>
+            for Series
+        {
+            fn new(name: &str, v: T) -> Self {
+                named_from_nested(name, v.as_ref(), |v: &Vec<Option<$type>>| {
+                    Series::new("", v)
+                })
+            }
+        }
+    };
+}
+
+/// Create a list Series with one list per element of `values`. The inner dtype is taken from an
+/// empty Series, so that an empty outer slice still gets a typed list column.
+fn named_from_nested<V, F>(name: &str, values: &[V], to_series: F) -> Series
+where
+    V: Default,
+    F: Fn(&V) -> Series,
+{
+    let inner = values.iter().map(&to_series).collect::<Vec<_>>();
+    let dtype = to_series(&V::default()).dtype().clone();
+    let values_cap = inner.iter().fold(0, |acc, s| acc + s.len());
+
+    let mut builder = get_list_builder(&dtype, values_cap, inner.len(), name);
+    for series in &inner {
+        builder.append_series(series)
+    }
+    builder.finish().into_series()
+}
+
+impl_named_from_nested!(&'a str, 'a);
+impl_named_from_nested!(String);
+impl_named_from_nested!(bool);
+impl_named_from_nested!(u8);
+impl_named_from_nested!(u16);
+impl_named_from_nested!(u32);
+impl_named_from_nested!(u64);
+#[cfg(feature = "dtype-i8")]
+impl_named_from_nested!(i8);
+#[cfg(feature = "dtype-i16")]
+impl_named_from_nested!(i16);
+impl_named_from_nested!(i32);
+impl_named_from_nested!(i64);
+impl_named_from_nested!(f32);
+impl_named_from_nested!(f64);
+
 // TODO: add types
 impl std::convert::TryFrom<(&str, Vec<ArrayRef>)> for Series {
     type Error = PolarsError;
@@ -1303,12 +1818,20 @@ impl std::convert::TryFrom<(&str, Vec<ArrayRef>)> for Series {
                 Ok(ListChunked::new_from_chunks(name, chunks).into_series())
             }
             ArrowDataType::Null => {
-                // we don't support null types yet so we use a small digit type filled with nulls
                 let len = chunks.iter().fold(0, |acc, array| acc + array.len());
-                #[cfg(feature = "dtype-i8")]
-                return Ok(Int8Chunked::full_null(name, len).into_series());
-                #[cfg(not(feature = "dtype-i8"))]
-                Ok(Int32Chunked::full_null(name, len).into_series())
+                Ok(NullChunked::new(name, len).into_series())
+            }
+            // the dictionaries of the chunks may differ, so the categories are mapped again
+            ArrowDataType::Dictionary(_, values)
+                if matches!(**values, ArrowDataType::Utf8 | ArrowDataType::LargeUtf8) =>
+            {
+                let chunks = chunks
+                    .iter()
+                    .map(|arr| cast(arr, &ArrowDataType::LargeUtf8))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                Utf8Chunked::new_from_chunks(name, chunks)
+                    .cast::<CategoricalType>()
+                    .map(|ca| ca.into_series())
             }
             dt => Err(PolarsError::InvalidOperation(
                 format!("Cannot create polars series from {:?} type", dt).into(),
@@ -1386,4 +1909,126 @@ mod test {
         let s2 = Series::new("b", &[3.0]);
         assert!(s1.append(&s2).is_err())
     }
+
+    #[test]
+    fn broadcast() -> Result<()> {
+        let mut s = Series::new("flag", &[1]).broadcast(4);
+        assert_eq!(s.len(), 4);
+        assert_eq!(s.dtype(), &DataType::Int32);
+        assert!(s.0.constant_value().is_some());
+
+        // operations on the constant don't materialize it
+        s.rename("a");
+        let filtered = s.filter(&BooleanChunked::new_from_slice(
+            "",
+            &[true, false, true, true],
+        ))?;
+        assert_eq!(filtered.len(), 3);
+        assert_eq!(s.slice(1, 2)?.len(), 2);
+        assert_eq!(s.n_unique()?, 1);
+        assert_eq!(s.get(3), AnyValue::Int32(1));
+        assert_eq!(s.cast::<Float64Type>()?.get(0), AnyValue::Float64(1.0));
+
+        // kernels use the materialized values
+        let added = &s + &Series::new("b", &[1, 2, 3, 4]);
+        assert_eq!(
+            Vec::from(added.i32()?),
+            &[Some(2), Some(3), Some(4), Some(5)]
+        );
+        s.append(&Series::new("b", &[2]))?;
+        assert_eq!(
+            Vec::from(s.i32()?),
+            &[Some(1), Some(1), Some(1), Some(1), Some(2)]
+        );
+
+        let null = Series::new("n", &[None::<i32>]).broadcast(2);
+        assert_eq!(null.null_count(), 2);
+        assert_eq!(null.drop_nulls().len(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn broadcast_take_every() {
+        let s = Series::new("flag", &[1]).broadcast(5);
+        let values = Series::new("flag", &[1; 5]);
+        for n in 1..7 {
+            assert!(s.take_every(n).series_equal(&values.take_every(n)));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn broadcast_take_every_zero() {
+        Series::new("flag", &[1]).broadcast(5).take_every(0);
+    }
+
+    #[test]
+    fn null_take_every() -> Result<()> {
+        let s = Series::full_null("n", 5, &DataType::Null)?;
+        let ints = Series::full_null("n", 5, &DataType::Int32)?;
+        for n in 1..7 {
+            let taken = s.take_every(n);
+            assert_eq!(taken.dtype(), &DataType::Null);
+            assert_eq!(taken.len(), ints.take_every(n).len());
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn null_take_every_zero() {
+        Series::full_null("n", 5, &DataType::Null)
+            .unwrap()
+            .take_every(0);
+    }
+
+    #[test]
+    fn dedup_strings() -> Result<()> {
+        let values = &[Some("disk full"), None, Some("ok"), Some("disk full")];
+        let s = Series::new("msg", values).dedup_strings()?;
+        assert_eq!(s.dtype(), &DataType::Utf8);
+        assert_eq!(s.len(), 4);
+        assert_eq!(s.null_count(), 1);
+        assert_eq!(s.n_unique()?, 3);
+        assert_eq!(s.get(3), AnyValue::Utf8("disk full"));
+
+        // selecting rows keeps the deduplicated storage
+        let filtered = s.filter(&BooleanChunked::new_from_slice(
+            "",
+            &[true, false, false, true],
+        ))?;
+        assert_eq!(filtered.len(), 2);
+        assert_eq!(
+            Vec::from(s.take_every(2).utf8()?),
+            &[Some("disk full"), Some("ok")]
+        );
+
+        // the other operations decode them
+        assert_eq!(Vec::from(s.utf8()?), values);
+        assert!(s.series_equal_missing(&Series::new("msg", values)));
+
+        // sorting works on the ranks of the distinct strings
+        let mut s = Series::new("msg", &["b", "a", "c", "a"]).dedup_strings()?;
+        assert_eq!(
+            Vec::from(s.sort(false).utf8()?),
+            &[Some("a"), Some("a"), Some("b"), Some("c")]
+        );
+        let idx = s.argsort(true);
+        assert_eq!(idx.get(0), Some(2));
+        assert_eq!(idx.get(1), Some(0));
+        s.sort_in_place(true);
+        assert_eq!(
+            Vec::from(s.utf8()?),
+            &[Some("c"), Some("b"), Some("a"), Some("a")]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_sorted_unsupported_dtype() -> Result<()> {
+        assert!(Series::new("a", &["a", "b", "b"]).is_sorted(false)?);
+        let list = Series::new("list", &[Series::new("", &[1, 2]), Series::new("", &[3])]);
+        assert!(list.is_sorted(false).is_err());
+        Ok(())
+    }
 }
@@ -0,0 +1,379 @@
+//! A Series of `DataType::Null`. It only stores a length, all values are null.
+use super::private;
+use super::IntoSeries;
+use super::SeriesTrait;
+use crate::frame::group_by::GroupTuples;
+use crate::prelude::*;
+use ahash::RandomState;
+use arrow::array::{ArrayDataRef, ArrayRef, NullArray};
+use arrow::buffer::Buffer;
+
+#[derive(Clone)]
+pub struct NullChunked {
+    field: Field,
+    length: usize,
+    chunks: Vec<ArrayRef>,
+    chunk_id: Vec<usize>,
+}
+
+impl NullChunked {
+    pub fn new(name: &str, length: usize) -> Self {
+        NullChunked {
+            field: Field::new(name, DataType::Null),
+            length,
+            chunks: vec![Arc::new(NullArray::new(length))],
+            chunk_id: vec![length],
+        }
+    }
+
+    fn with_len(&self, length: usize) -> Series {
+        NullChunked::new(self.name(), length).into_series()
+    }
+}
+
+impl IntoSeries for NullChunked {
+    fn into_series(self) -> Series {
+        Series(Arc::new(self))
+    }
+}
+
+impl private::PrivateSeries for NullChunked {
+    fn vec_hash(&self, _random_state: RandomState) -> UInt64Chunked {
+        UInt64Chunked::full(self.name(), 0, self.length)
+    }
+    fn agg_min(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+        Some(self.with_len(groups.len()))
+    }
+    fn agg_max(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<Series> {
+        Some(self.with_len(groups.len()))
+    }
+    fn agg_first(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
+        self.with_len(groups.len())
+    }
+    fn agg_last(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Series {
+        self.with_len(groups.len())
+    }
+    fn agg_n_unique(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<IdxCa> {
+        Some(IdxCa::full(self.name(), 1, groups.len()))
+    }
+    fn agg_null_count(&self, groups: &[(IdxSize, Vec<IdxSize>)]) -> Option<IdxCa> {
+        let ca: NoNull<IdxCa> = groups.iter().map(|(_, idx)| idx.len() as IdxSize).collect();
+        let mut ca = ca.into_inner();
+        ca.rename(self.name());
+        Some(ca)
+    }
+    // nulls never match in a join
+    fn hash_join_inner(&self, _other: &Series, _strategy: JoinStrategy) -> Vec<(IdxSize, IdxSize)> {
+        vec![]
+    }
+    fn hash_join_left(
+        &self,
+        _other: &Series,
+        _strategy: JoinStrategy,
+    ) -> Vec<(IdxSize, Option<IdxSize>)> {
+        (0..self.length as IdxSize).map(|idx| (idx, None)).collect()
+    }
+    fn hash_join_outer(&self, other: &Series) -> Vec<(Option<IdxSize>, Option<IdxSize>)> {
+        (0..self.length as IdxSize)
+            .map(|idx| (Some(idx), None))
+            .chain((0..other.len() as IdxSize).map(|idx| (None, Some(idx))))
+            .collect()
+    }
+    fn zip_outer_join_column(
+        &self,
+        _right_column: &Series,
+        opt_join_tuples: &[(Option<IdxSize>, Option<IdxSize>)],
+    ) -> Series {
+        self.with_len(opt_join_tuples.len())
+    }
+    fn group_tuples(&self, _multithreaded: bool) -> GroupTuples {
+        if self.length == 0 {
+            vec![]
+        } else {
+            vec![(0, (0..self.length as IdxSize).collect())]
+        }
+    }
+}
+
+impl SeriesTrait for NullChunked {
+    fn rename(&mut self, name: &str) {
+        self.field = Field::new(name, DataType::Null).with_metadata(self.field.metadata().clone())
+    }
+
+    fn set_metadata(&mut self, metadata: FieldMetadata) {
+        self.field = self.field.clone().with_metadata(metadata)
+    }
+
+    fn array_data(&self) -> Vec<ArrayDataRef> {
+        self.chunks.iter().map(|arr| arr.data()).collect()
+    }
+
+    fn chunk_lengths(&self) -> &Vec<usize> {
+        &self.chunk_id
+    }
+
+    fn name(&self) -> &str {
+        self.field.name()
+    }
+
+    fn field(&self) -> &Field {
+        &self.field
+    }
+
+    fn chunks(&self) -> &Vec<ArrayRef> {
+        &self.chunks
+    }
+
+    fn append_array(&mut self, other: ArrayRef) -> Result<()> {
+        if other.data_type() != &ArrowDataType::Null {
+            return Err(PolarsError::DataTypeMisMatch(
+                format!(
+                    "cannot append array of type {:?} to null",
+                    other.data_type()
+                )
+                .into(),
+            ));
+        }
+        self.length += other.len();
+        self.chunk_id.push(other.len());
+        self.chunks.push(other);
+        Ok(())
+    }
+
+    fn slice(&self, offset: usize, length: usize) -> Result<Series> {
+        if offset + length > self.length {
+            return Err(PolarsError::OutOfBounds(
+                "offset and length was larger than the size of the Series during slice operation"
+                    .into(),
+            ));
+        }
+        Ok(self.with_len(length))
+    }
+
+    fn append(&mut self, other: &Series) -> Result<()> {
+        if other.dtype() == &DataType::Null {
+            self.length += other.len();
+            self.chunks.extend_from_slice(other.chunks());
+            self.chunk_id.extend_from_slice(other.chunk_lengths());
+            Ok(())
+        } else {
+            Err(PolarsError::DataTypeMisMatch(
+                "cannot append Series; data types don't match".into(),
+            ))
+        }
+    }
+
+    fn filter(&self, filter: &BooleanChunked) -> Result<Series> {
+        if filter.len() != self.length {
+            return Err(PolarsError::ShapeMisMatch(
+                "filter's length differs from that of the Series".into(),
+            ));
+        }
+        let len = filter
+            .into_iter()
+            .filter(|opt_v| matches!(opt_v, Some(true)))
+            .count();
+        Ok(self.with_len(len))
+    }
+
+    fn take_iter(&self, iter: &mut dyn Iterator<Item = usize>) -> Series {
+        self.with_len(iter.count())
+    }
+
+    unsafe fn take_iter_unchecked(&self, iter: &mut dyn Iterator<Item = usize>) -> Series {
+        self.with_len(iter.count())
+    }
+
+    unsafe fn take_unchecked(&self, idx: &IdxCa) -> Result<Series> {
+        Ok(self.with_len(idx.len()))
+    }
+
+    unsafe fn take_opt_iter_unchecked(
+        &self,
+        iter: &mut dyn Iterator<Item = Option<usize>>,
+    ) -> Series {
+        self.with_len(iter.count())
+    }
+
+    fn take_opt_iter(&self, iter: &mut dyn Iterator<Item = Option<usize>>) -> Series {
+        self.with_len(iter.count())
+    }
+
+    fn take(&self, indices: &IdxCa) -> Series {
+        self.with_len(indices.len())
+    }
+
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn rechunk(&self) -> Series {
+        self.with_len(self.length)
+    }
+
+    fn head(&self, length: Option<usize>) -> Series {
+        self.with_len(std::cmp::min(length.unwrap_or(10), self.length))
+    }
+
+    fn tail(&self, length: Option<usize>) -> Series {
+        self.with_len(std::cmp::min(length.unwrap_or(10), self.length))
+    }
+
+    fn take_every(&self, n: usize) -> Series {
+        // step_by panics on a step of 0, like the ChunkedArray implementation
+        self.with_len((0..self.length).step_by(n).len())
+    }
+
+    fn drop_nulls(&self) -> Series {
+        self.with_len(0)
+    }
+
+    fn expand_at_index(&self, _index: usize, length: usize) -> Series {
+        self.with_len(length)
+    }
+
+    fn cast_with_datatype(&self, data_type: &DataType) -> Result<Series> {
+        Series::full_null(self.name(), self.length, data_type)
+    }
+
+    fn get(&self, _index: usize) -> AnyValue {
+        AnyValue::Null
+    }
+
+    unsafe fn get_unchecked(&self, _index: usize) -> AnyValue {
+        AnyValue::Null
+    }
+
+    fn sort_in_place(&mut self, _reverse: bool) {}
+
+    fn sort(&self, _reverse: bool) -> Series {
+        self.with_len(self.length)
+    }
+
+    fn argsort(&self, _reverse: bool) -> IdxCa {
+        (0..self.length as IdxSize)
+            .collect::<NoNull<_>>()
+            .into_inner()
+    }
+
+    fn null_count(&self) -> usize {
+        self.length
+    }
+
+    fn unique(&self) -> Result<Series> {
+        Ok(self.with_len(std::cmp::min(1, self.length)))
+    }
+
+    fn n_unique(&self) -> Result<usize> {
+        Ok(std::cmp::min(1, self.length))
+    }
+
+    fn approx_n_unique(&self) -> Result<usize> {
+        self.n_unique()
+    }
+
+    fn arg_unique(&self) -> Result<Vec<IdxSize>> {
+        Ok(if self.length == 0 { vec![] } else { vec![0] })
+    }
+
+    fn is_null(&self) -> BooleanChunked {
+        BooleanChunked::full(self.name(), true, self.length)
+    }
+
+    fn is_not_null(&self) -> BooleanChunked {
+        BooleanChunked::full(self.name(), false, self.length)
+    }
+
+    fn is_unique(&self) -> Result<BooleanChunked> {
+        Ok(BooleanChunked::full(
+            self.name(),
+            self.length == 1,
+            self.length,
+        ))
+    }
+
+    fn is_duplicated(&self) -> Result<BooleanChunked> {
+        Ok(BooleanChunked::full(
+            self.name(),
+            self.length > 1,
+            self.length,
+        ))
+    }
+
+    fn null_bits(&self) -> Vec<(usize, Option<Buffer>)> {
+        self.chunks
+            .iter()
+            .map(|arr| (arr.null_count(), None))
+            .collect()
+    }
+
+    fn reverse(&self) -> Series {
+        self.with_len(self.length)
+    }
+
+    fn shift(&self, _periods: i64) -> Series {
+        self.with_len(self.length)
+    }
+
+    fn fill_none(&self, _strategy: FillNoneStrategy) -> Result<Series> {
+        Ok(self.with_len(self.length))
+    }
+
+    fn sum_as_series(&self) -> Series {
+        self.with_len(1)
+    }
+
+    fn max_as_series(&self) -> Series {
+        self.with_len(1)
+    }
+
+    fn min_as_series(&self) -> Series {
+        self.with_len(1)
+    }
+
+    fn mean_as_series(&self) -> Series {
+        self.with_len(1)
+    }
+
+    fn median_as_series(&self) -> Series {
+        self.with_len(1)
+    }
+
+    fn var_as_series(&self) -> Series {
+        self.with_len(1)
+    }
+
+    fn std_as_series(&self) -> Series {
+        self.with_len(1)
+    }
+
+    fn quantile_as_series(&self, _quantile: f64) -> Result<Series> {
+        Ok(self.with_len(1))
+    }
+
+    fn fmt_list(&self) -> String {
+        format!("[null; {}]", self.length)
+    }
+
+    fn clone_inner(&self) -> Arc<dyn SeriesTrait> {
+        Arc::new(self.clone())
+    }
+
+    #[cfg(feature = "random")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "random")))]
+    fn sample_n(&self, n: usize, with_replacement: bool) -> Result<Series> {
+        if !with_replacement && n > self.length {
+            return Err(PolarsError::ShapeMisMatch(
+                "n is larger than the number of elements in this array".into(),
+            ));
+        }
+        Ok(self.with_len(n))
+    }
+
+    #[cfg(feature = "random")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "random")))]
+    fn sample_frac(&self, frac: f64, with_replacement: bool) -> Result<Series> {
+        let n = (self.length as f64 * frac) as usize;
+        self.sample_n(n, with_replacement)
+    }
+}
@@ -110,6 +110,48 @@ macro_rules! split_array {
     }};
 }
 
+/// The estimated size in bytes of a value of `dtype`. Strings, lists and objects are estimated
+/// at 16 bytes.
+fn estimated_value_bytes(dtype: &DataType) -> usize {
+    match dtype {
+        DataType::Boolean | DataType::UInt8 | DataType::Int8 | DataType::Null => 1,
+        DataType::UInt16 | DataType::Int16 => 2,
+        DataType::UInt32
+        | DataType::Int32
+        | DataType::Float32
+        | DataType::Date32
+        | DataType::Categorical(_) => 4,
+        DataType::UInt64
+        | DataType::Int64
+        | DataType::Float64
+        | DataType::Date64
+        | DataType::Time64(_)
+        | DataType::Duration(_) => 8,
+        _ => 16,
+    }
+}
+
+/// The estimated size in bytes of a row with `schema`.
+pub fn estimated_row_bytes(schema: &Schema) -> usize {
+    schema
+        .fields()
+        .iter()
+        .map(|field| estimated_value_bytes(field.data_type()))
+        .sum()
+}
+
+/// The number of parts to split `n_rows` rows of `row_bytes` bytes in. This follows the
+/// [chunk_size](crate::chunk_size) if it is set, and is `n_threads` otherwise.
+pub fn n_splits(n_rows: usize, row_bytes: usize, n_threads: usize) -> usize {
+    match crate::chunk_size() {
+        Some(size) => {
+            let rows = size.rows(row_bytes);
+            std::cmp::max((n_rows + rows - 1) / rows, 1)
+        }
+        None => n_threads,
+    }
+}
+
 pub fn split_ca<T>(ca: &ChunkedArray<T>, n: usize) -> Result<Vec<ChunkedArray<T>>> {
     split_array!(ca, n)
 }
@@ -130,6 +172,38 @@ pub fn split_df(df: &DataFrame, n: usize) -> Result<Vec<DataFrame>> {
     split_array!(df, n)
 }
 
+/// Normalize a column name to snake_case, e.g. `"Sepal Width"` and `"sepalWidth"` both become
+/// `"sepal_width"`. Runs of characters that are not alphanumeric are replaced by a single `_`.
+pub fn to_snake_case(name: &str) -> String {
+    fn push_separator(out: &mut String) {
+        if !out.is_empty() && !out.ends_with('_') {
+            out.push('_')
+        }
+    }
+
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            push_separator(&mut out);
+            continue;
+        }
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).map_or(false, |c| c.is_lowercase());
+            // a word starts at "aB", "1B" or at the last capital of an acronym "ABc"
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower) {
+                push_separator(&mut out);
+            }
+        }
+        out.extend(c.to_lowercase());
+    }
+    if out.ends_with('_') {
+        out.pop();
+    }
+    out
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Node(pub usize);
 
@@ -394,17 +468,46 @@ macro_rules! static_zip {
     };
 }
 
+/// Create a [DataFrame](crate::frame::DataFrame) from column names and values.
+///
+/// Every column is created with `Series::new`, so the values can be slices of native values,
+/// `Option` values (where `None` is a null), or nested `Vec`s for list columns. A column can be
+/// followed by `=> DataType` to cast it to that data type. A `Result` is returned, which is an
+/// error if the casts fail or if the columns have different lengths.
+///
+/// # Example
+///
+/// ```
+/// use polars_core::prelude::*;
+/// use polars_core::df;
+///
+/// let df = df! {
+///     "ints" => [Some(1), None, Some(3)],
+///     "floats" => [1, 2, 3] => DataType::Float64,
+///     "lists" => [vec![1, 2], vec![], vec![3]],
+/// }
+/// .unwrap();
+/// assert_eq!(df.column("floats").unwrap().dtype(), &DataType::Float64);
+/// assert_eq!(df.column("ints").unwrap().null_count(), 1);
+/// ```
 #[macro_export]
 macro_rules! df {
-    ($($col_name:expr => $slice:expr), +) => {
-        {
+    ($($col_name:expr => $slice:expr $(=> $dtype:expr)?),+ $(,)?) => {
+        (|| -> $crate::prelude::Result<$crate::prelude::DataFrame> {
             let mut columns = vec![];
             $(
-                columns.push(Series::new($col_name, $slice));
+                #[allow(unused_mut)]
+                let mut s = <$crate::prelude::Series as $crate::prelude::NamedFrom<_, _>>::new(
+                    $col_name,
+                    $slice,
+                );
+                $(
+                    s = s.cast_with_datatype(&$dtype)?;
+                )?
+                columns.push(s);
             )+
-            DataFrame::new(columns)
-        }
-
+            $crate::prelude::DataFrame::new(columns)
+        })()
     }
 }
 
@@ -427,8 +530,11 @@ fn _get_supertype(l: &DataType, r: &DataType) -> Option<DataType> {
         return Some(l.clone());
     }
 
-    // TODO! add list and temporal types
+    // TODO! add list types
     match (l, r) {
+        // a column of nulls can be cast to any type
+        (Null, dt) => Some(dt.clone()),
+
         (Duration(_), Int8) => Some(Int64),
         (Duration(_), Int16) => Some(Int64),
         (Duration(_), Int32) => Some(Int64),
@@ -453,6 +559,12 @@ fn _get_supertype(l: &DataType, r: &DataType) -> Option<DataType> {
         (Duration(_), Float32) => Some(Float32),
         (Duration(_), Float64) => Some(Float64),
 
+        // adding a duration to a date gives a date
+        (Date64, Duration(TimeUnit::Millisecond)) => Some(Date64),
+        (Duration(TimeUnit::Millisecond), Date64) => Some(Date64),
+        (Date32, Duration(TimeUnit::Millisecond)) => Some(Date64),
+        (Duration(TimeUnit::Millisecond), Date32) => Some(Date64),
+
         (UInt8, Int8) => Some(Int8),
         (UInt8, Int16) => Some(Int16),
         (UInt8, Int32) => Some(Int32),
@@ -561,11 +673,13 @@ fn _get_supertype(l: &DataType, r: &DataType) -> Option<DataType> {
         (Date32, Int64) => Some(Int64),
         (Date32, Float32) => Some(Float32),
         (Date32, Float64) => Some(Float64),
+        (Date32, Date64) => Some(Date64),
 
         (Date64, Int32) => Some(Int64),
         (Date64, Int64) => Some(Int64),
         (Date64, Float32) => Some(Float64),
         (Date64, Float64) => Some(Float64),
+        (Date64, Date32) => Some(Date64),
 
         (Utf8, _) => Some(Utf8),
         (_, Utf8) => Some(Utf8),
@@ -582,6 +696,16 @@ fn _get_supertype(l: &DataType, r: &DataType) -> Option<DataType> {
         (Boolean, Float32) => Some(Float32),
         (Boolean, Float64) => Some(Float64),
 
+        // categoricals with a different ordering are compared and stacked by their String
+        // values, unless one of them is an enum, whose closed set of categories is kept. Enums
+        // with different categories have no supertype.
+        (Categorical(CategoricalOrdering::Enum(_)), Categorical(CategoricalOrdering::Enum(_))) => {
+            None
+        }
+        (Categorical(CategoricalOrdering::Enum(_)), Categorical(_)) => Some(l.clone()),
+        (Categorical(_), Categorical(CategoricalOrdering::Enum(_))) => Some(r.clone()),
+        (Categorical(_), Categorical(_)) => Some(Categorical(CategoricalOrdering::Lexical)),
+
         _ => None,
     }
 }
@@ -598,6 +722,29 @@ where
     Ok(acc_df)
 }
 
+/// Give all DataFrames the same schema, so that they can be concatenated.
+///
+/// The schema has all columns in order of first occurrence. Columns that exist in multiple
+/// DataFrames are cast to their supertype and missing columns are filled with nulls.
+pub fn align_frames(dfs: &[DataFrame]) -> Result<Vec<DataFrame>> {
+    let mut fields: Vec<Field> = vec![];
+    for df in dfs {
+        for s in df.get_columns() {
+            match fields.iter_mut().find(|f| f.name() == s.name()) {
+                Some(field) => {
+                    let dtype = get_supertype(field.data_type(), s.dtype())?;
+                    *field = Field::new(s.name(), dtype);
+                }
+                None => fields.push(Field::new(s.name(), s.dtype().clone())),
+            }
+        }
+    }
+    let schema = Schema::new(fields);
+    dfs.iter()
+        .map(|df| df.match_schema(&schema, false))
+        .collect()
+}
+
 pub fn accumulate_dataframes_horizontal(dfs: Vec<DataFrame>) -> Result<DataFrame> {
     let mut iter = dfs.into_iter();
     let mut acc_df = iter.next().unwrap();
@@ -607,6 +754,29 @@ pub fn accumulate_dataframes_horizontal(dfs: Vec<DataFrame>) -> Result<DataFrame
     Ok(acc_df)
 }
 
+fn out_of_bounds_err(idx: usize, len: usize) -> PolarsError {
+    PolarsError::OutOfBounds(format!("index {} is out of bounds for length {}", idx, len).into())
+}
+
+/// Check that all indices are smaller than `len`.
+pub(crate) fn check_bounds<I>(mut iter: I, len: usize) -> Result<()>
+where
+    I: Iterator<Item = usize>,
+{
+    match iter.find(|&idx| idx >= len) {
+        Some(idx) => Err(out_of_bounds_err(idx, len)),
+        None => Ok(()),
+    }
+}
+
+/// Check that all indices are smaller than `len`. Null indices are ignored.
+pub(crate) fn check_bounds_ca(indices: &IdxCa, len: usize) -> Result<()> {
+    match indices.max() {
+        Some(max) if max as usize >= len => Err(out_of_bounds_err(max as usize, len)),
+        _ => Ok(()),
+    }
+}
+
 #[cfg(target_os = "linux")]
 extern "C" {
     #[allow(dead_code)]
@@ -619,8 +789,15 @@ pub fn parallel_op<F>(f: F, s: Series, n_threads: Option<usize>) -> Result<Serie
 where
     F: Fn(Series) -> Result<Series> + Send + Sync,
 {
-    let n_threads = n_threads.unwrap_or_else(|| POOL.current_num_threads());
-    let slices = split_series(&s, n_threads)?;
+    let n_parts = match n_threads {
+        Some(n_threads) => n_threads,
+        None => n_splits(
+            s.len(),
+            estimated_value_bytes(s.dtype()),
+            POOL.current_num_threads(),
+        ),
+    };
+    let slices = split_series(&s, n_parts)?;
 
     let chunks = POOL.install(|| slices.into_par_iter().map(&f).collect::<Result<Vec<_>>>())?;
 
@@ -749,3 +926,100 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ChunkSize;
+
+    #[test]
+    fn test_chunk_size_rows() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int64),
+            Field::new("b", DataType::Utf8),
+            Field::new("c", DataType::Boolean),
+        ]);
+        assert_eq!(estimated_row_bytes(&schema), 25);
+        assert_eq!(ChunkSize::Bytes(100).rows(25), 4);
+        assert_eq!(ChunkSize::Bytes(10).rows(25), 1);
+        assert_eq!(ChunkSize::Rows(1000).rows(25), 1000);
+    }
+
+    #[test]
+    fn test_get_supertype() {
+        use DataType::*;
+        assert_eq!(get_supertype(&Int32, &Float64).unwrap(), Float64);
+        assert_eq!(get_supertype(&UInt32, &Int64).unwrap(), Int64);
+        assert_eq!(get_supertype(&Date32, &Date64).unwrap(), Date64);
+        assert_eq!(get_supertype(&Date64, &Date32).unwrap(), Date64);
+        assert_eq!(
+            get_supertype(&Date32, &Duration(TimeUnit::Millisecond)).unwrap(),
+            Date64
+        );
+        assert_eq!(get_supertype(&Int64, &Utf8).unwrap(), Utf8);
+        assert!(get_supertype(&Boolean, &Date64).is_err());
+
+        let physical = Categorical(CategoricalOrdering::Physical);
+        let lexical = Categorical(CategoricalOrdering::Lexical);
+        let enum_ = |cats: &[&str]| {
+            Categorical(CategoricalOrdering::Enum(Arc::new(
+                cats.iter().map(|cat| cat.to_string()).collect(),
+            )))
+        };
+        assert_eq!(get_supertype(&physical, &lexical).unwrap(), lexical);
+        assert_eq!(get_supertype(&lexical, &physical).unwrap(), lexical);
+        assert_eq!(
+            get_supertype(&physical, &enum_(&["a", "b"])).unwrap(),
+            enum_(&["a", "b"])
+        );
+        assert_eq!(
+            get_supertype(&enum_(&["a", "b"]), &lexical).unwrap(),
+            enum_(&["a", "b"])
+        );
+        assert!(get_supertype(&enum_(&["a", "b"]), &enum_(&["b", "a"])).is_err());
+    }
+
+    #[test]
+    fn test_align_frames_disjoint_columns() -> Result<()> {
+        let df_a = DataFrame::new(vec![Series::new("a", &[1, 2])])?;
+        let df_b = DataFrame::new(vec![Series::new("b", &["x"])])?;
+        let aligned = align_frames(&[df_a, df_b])?;
+
+        for df in &aligned {
+            assert_eq!(df.get_column_names(), &["a", "b"]);
+            assert_eq!(df.column("a")?.dtype(), &DataType::Int32);
+            assert_eq!(df.column("b")?.dtype(), &DataType::Utf8);
+        }
+        // the missing columns are filled with nulls
+        assert_eq!(aligned[0].column("b")?.null_count(), 2);
+        assert_eq!(aligned[1].column("a")?.null_count(), 1);
+
+        let out = accumulate_dataframes_vertical(aligned)?;
+        assert_eq!(out.shape(), (3, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_align_frames_mismatched_dtypes() -> Result<()> {
+        let df_a = DataFrame::new(vec![
+            Series::new("a", &[1, 2]),
+            Series::new("b", &[true, false]),
+        ])?;
+        let df_b = DataFrame::new(vec![Series::new("b", &["x"]), Series::new("a", &[1.5])])?;
+        let aligned = align_frames(&[df_a, df_b])?;
+
+        // the columns are cast to their supertype and ordered by first occurrence
+        for df in &aligned {
+            assert_eq!(df.get_column_names(), &["a", "b"]);
+            assert_eq!(df.column("a")?.dtype(), &DataType::Float64);
+            assert_eq!(df.column("b")?.dtype(), &DataType::Utf8);
+        }
+        assert_eq!(Vec::from(aligned[1].column("a")?.f64()?), &[Some(1.5)]);
+
+        // there is no supertype of a boolean and a list
+        let df_c = DataFrame::new(vec![Series::new("b", &[true])])?;
+        let df_d = DataFrame::new(vec![Series::new("b", &[Series::new("", &[1, 2])])])?;
+        assert!(align_frames(&[df_c, df_d]).is_err());
+        Ok(())
+    }
+}
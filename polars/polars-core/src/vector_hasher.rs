@@ -40,7 +40,7 @@ pub(crate) type IdBuildHasher = BuildHasherDefault<IdHasher>;
 
 pub(crate) struct IdxHash {
     // idx in row of Series, DataFrame
-    pub(crate) idx: u32,
+    pub(crate) idx: IdxSize,
     // precomputed hash of T
     hash: u64,
 }
@@ -53,22 +53,31 @@ impl Hash for IdxHash {
 
 impl IdxHash {
     #[inline]
-    pub(crate) fn new(idx: u32, hash: u64) -> Self {
+    pub(crate) fn new(idx: IdxSize, hash: u64) -> Self {
         IdxHash { idx, hash }
     }
 }
 
-/// Check if a hash should be processed in that thread.
+/// The partition of a hash in a radix partitioned join. The hash tables use the low bits of a
+/// hash to find a bucket and the highest 7 bits as control byte. The partition is taken from the
+/// bits in between, so that the keys of a partition still spread over the whole table.
 #[inline]
-pub(crate) fn this_thread(h: u64, thread_no: u64, n_threads: u64) -> bool {
-    (h + thread_no) % n_threads == 0
+pub(crate) fn hash_to_partition(h: u64, n_partitions: usize) -> usize {
+    debug_assert!(n_partitions.is_power_of_two());
+    ((h >> 32) as usize) & (n_partitions - 1)
+}
+
+/// Number of radix partitions of a threaded hash operation. A multiple of the number of threads,
+/// so that the work is balanced, and a power of two, so that the partition is a mask of the hash.
+pub(crate) fn n_hash_partitions(n_threads: usize) -> usize {
+    (4 * n_threads).next_power_of_two()
 }
 
 fn finish_table_from_key_hashes<T>(
     hashes_nd_keys: Vec<(u64, T)>,
-    mut hash_tbl: HashMap<T, Vec<u32>, RandomState>,
+    mut hash_tbl: HashMap<T, Vec<IdxSize>, RandomState>,
     offset: usize,
-) -> HashMap<T, Vec<u32>, RandomState>
+) -> HashMap<T, Vec<IdxSize>, RandomState>
 where
     T: Hash + Eq,
 {
@@ -76,7 +85,7 @@ where
         .into_iter()
         .enumerate()
         .for_each(|(idx, (h, t))| {
-            let idx = (idx + offset) as u32;
+            let idx = (idx + offset) as IdxSize;
             hash_tbl
                 .raw_entry_mut()
                 // uses the key to check equality to find and entry
@@ -93,7 +102,7 @@ where
 
 pub(crate) fn prepare_hashed_relation<T>(
     b: impl Iterator<Item = T>,
-) -> HashMap<T, Vec<u32>, RandomState>
+) -> HashMap<T, Vec<IdxSize>, RandomState>
 where
     T: Hash + Eq,
 {
@@ -107,71 +116,88 @@ where
         })
         .collect::<Vec<_>>();
 
-    let hash_tbl: HashMap<T, Vec<u32>, RandomState> =
+    let hash_tbl: HashMap<T, Vec<IdxSize>, RandomState> =
         HashMap::with_capacity_and_hasher(hashes_nd_keys.len(), random_state);
 
     finish_table_from_key_hashes(hashes_nd_keys, hash_tbl, 0)
 }
 
-pub(crate) fn prepare_hashed_relation_threaded<T, I>(
-    iters: Vec<I>,
-) -> Vec<HashMap<T, Vec<u32>, RandomState>>
+/// Hashed keys with their row index, for every partition.
+pub(crate) type PartitionedKeys<T> = Vec<Vec<(IdxSize, u64, T)>>;
+
+/// Scatter the hashed keys of every chunk to `n_partitions` partitions by their hash. The row
+/// index of a key is its position in all chunks. The result is indexed by chunk and then by
+/// partition, so the keys of a partition are in row order when the chunks are traversed in order.
+pub(crate) fn partition_hashed_keys<T>(
+    hashes_and_keys: Vec<Vec<(u64, T)>>,
+    n_partitions: usize,
+) -> Vec<PartitionedKeys<T>>
 where
-    I: Iterator<Item = T> + Send,
-    T: Send + Hash + Eq + Sync + Copy,
+    T: Send,
 {
-    let n_threads = iters.len();
-    let (hashes_and_keys, random_state) = create_hash_and_keys_threaded_vectorized(iters, None);
-    let size = hashes_and_keys.iter().fold(0, |acc, v| acc + v.len());
+    let offsets = hashes_and_keys
+        .iter()
+        .scan(0, |state, chunk| {
+            let offset = *state;
+            *state += chunk.len();
+            Some(offset)
+        })
+        .collect_vec();
 
-    // We will create a hashtable in every thread.
-    // We use the hash to partition the keys to the matching hashtable.
-    // Every thread traverses all keys/hashes and ignores the ones that doesn't fall in that partition.
     POOL.install(|| {
-        (0..n_threads).into_par_iter().map(|thread_no| {
-            let random_state = random_state.clone();
-            let hashes_and_keys = &hashes_and_keys;
-            let thread_no = thread_no as u64;
-            let mut hash_tbl: HashMap<T, Vec<u32>, RandomState> =
-                HashMap::with_capacity_and_hasher(size / (5 * n_threads), random_state);
+        hashes_and_keys
+            .into_par_iter()
+            .zip(offsets)
+            .map(|(chunk, offset)| {
+                let mut partitions = (0..n_partitions)
+                    .map(|_| Vec::with_capacity(chunk.len() / n_partitions))
+                    .collect_vec();
+                for (idx, (h, k)) in chunk.into_iter().enumerate() {
+                    let idx = (idx + offset) as IdxSize;
+                    // Safety:
+                    // the partition is masked by n_partitions - 1
+                    unsafe {
+                        partitions
+                            .get_unchecked_mut(hash_to_partition(h, n_partitions))
+                            .push((idx, h, k))
+                    }
+                }
+                partitions
+            })
+            .collect()
+    })
+}
 
-            let n_threads = n_threads as u64;
-            let mut offset = 0;
-            for hashes_and_keys in hashes_and_keys {
-                let len = hashes_and_keys.len();
-                hashes_and_keys
-                    .iter()
-                    .enumerate()
-                    .for_each(|(idx, (h, k))| {
-                        let idx = idx as u32;
-                        // partition hashes by thread no.
-                        // So only a part of the hashes go to this hashmap
-                        if this_thread(*h, thread_no, n_threads) {
-                            let idx = idx + offset;
-                            let entry = hash_tbl
-                                .raw_entry_mut()
-                                // uses the key to check equality to find and entry
-                                .from_key_hashed_nocheck(*h, &k);
+/// Create the hash table of partition `partition` of the build relation.
+pub(crate) fn prepare_hashed_partition<T>(
+    partitioned: &[PartitionedKeys<T>],
+    partition: usize,
+    random_state: RandomState,
+) -> HashMap<T, Vec<IdxSize>, RandomState>
+where
+    T: Hash + Eq + Copy,
+{
+    let size = partitioned.iter().map(|p| p[partition].len()).sum();
+    let mut hash_tbl: HashMap<T, Vec<IdxSize>, RandomState> =
+        HashMap::with_capacity_and_hasher(size, random_state);
 
-                            match entry {
-                                RawEntryMut::Vacant(entry) => {
-                                    entry.insert_hashed_nocheck(*h, *k, vec![idx]);
-                                }
-                                RawEntryMut::Occupied(mut entry) => {
-                                    let (_k, v) = entry.get_key_value_mut();
-                                    v.push(idx);
-                                }
-                            }
-                        }
-                    });
+    for (idx, h, k) in partitioned.iter().flat_map(|p| &p[partition]) {
+        let entry = hash_tbl
+            .raw_entry_mut()
+            // uses the key to check equality to find and entry
+            .from_key_hashed_nocheck(*h, k);
 
-                offset += len as u32;
+        match entry {
+            RawEntryMut::Vacant(entry) => {
+                entry.insert_hashed_nocheck(*h, *k, vec![*idx]);
             }
-            hash_tbl.shrink_to_fit();
-            hash_tbl
-        })
-    })
-    .collect()
+            RawEntryMut::Occupied(mut entry) => {
+                let (_k, v) = entry.get_key_value_mut();
+                v.push(*idx);
+            }
+        }
+    }
+    hash_tbl
 }
 
 pub(crate) fn create_hash_and_keys_threaded_vectorized<I, T>(
@@ -203,7 +229,7 @@ where
 
 // Combines two hashes into one hash
 // http://myeyesareblind.com/2017/02/06/Combine-hash-values/
-fn combine_hashes(l: u64, r: u64) -> u64 {
+pub(crate) fn combine_hashes(l: u64, r: u64) -> u64 {
     let hash = (17 * 37u64).wrapping_add(l);
     hash.wrapping_mul(37).wrapping_add(r)
 }
@@ -55,12 +55,16 @@
 //! # assert_eq!(1, df.column("sepal.length").unwrap().chunks().len());
 //! ```
 //!
+pub use crate::csv_core::csv::BatchedCsvReader;
 use crate::csv_core::csv::{build_csv_reader, SequentialReader};
+use crate::csv_core::sniffer::{sniff_dialect, SNIFF_SAMPLE_BYTES};
+pub use crate::csv_core::sniffer::{CsvDialect, LineTerminator};
+use crate::transform::{ColumnTransformer, ColumnTransformers};
 use crate::{SerReader, SerWriter};
 pub use arrow::csv::WriterBuilder;
 use polars_core::prelude::*;
 use std::fs::File;
-use std::io::{Read, Seek, Write};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::sync::Arc;
 
 /// Write a DataFrame to csv.
@@ -70,6 +74,7 @@ pub struct CsvWriter<'a, W: Write> {
     /// Builds an Arrow CSV Writer
     writer_builder: WriterBuilder,
     buffer_size: usize,
+    column_transformers: ColumnTransformers,
 }
 
 impl<'a, W> SerWriter<'a, W> for CsvWriter<'a, W>
@@ -81,10 +86,13 @@ where
             buffer,
             writer_builder: WriterBuilder::new(),
             buffer_size: 1000,
+            column_transformers: ColumnTransformers::default(),
         }
     }
 
     fn finish(self, df: &mut DataFrame) -> Result<()> {
+        let mut transformed = self.column_transformers.transformed(df)?;
+        let df = transformed.as_mut().unwrap_or(df);
         let mut csv_writer = self.writer_builder.build(self.buffer);
 
         let iter = df.iter_record_batches(self.buffer_size);
@@ -134,6 +142,23 @@ where
         self.buffer_size = batch_size;
         self
     }
+
+    /// Transform the column `name` before it is written, e.g. to tokenize it. The DataFrame
+    /// that is passed to `finish` isn't modified.
+    pub fn with_column_transformer<T>(mut self, name: &str, transformer: T) -> Self
+    where
+        T: ColumnTransformer + 'static,
+    {
+        self.column_transformers.push(name, Arc::new(transformer));
+        self
+    }
+
+    /// Set the transformers of the columns that are written, replacing those that were added
+    /// with [with_column_transformer](Self::with_column_transformer).
+    pub fn with_column_transformers(mut self, column_transformers: ColumnTransformers) -> Self {
+        self.column_transformers = column_transformers;
+        self
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -186,6 +211,7 @@ where
     schema_overwrite: Option<&'a Schema>,
     sample_size: usize,
     stable_parser: bool,
+    column_transformers: ColumnTransformers,
 }
 
 impl<'a, R> CsvReader<'a, R>
@@ -303,8 +329,57 @@ where
         self
     }
 
+    /// Transform the column `name` after it is read, e.g. to decrypt or mask it.
+    pub fn with_column_transformer<T>(mut self, name: &str, transformer: T) -> Self
+    where
+        T: ColumnTransformer + 'static,
+    {
+        self.column_transformers.push(name, Arc::new(transformer));
+        self
+    }
+
+    /// Set the transformers of the columns that are read, replacing those that were added
+    /// with [with_column_transformer](Self::with_column_transformer).
+    pub fn with_column_transformers(mut self, column_transformers: ColumnTransformers) -> Self {
+        self.column_transformers = column_transformers;
+        self
+    }
+
+    /// Infer the delimiter, quote character, header and line terminator from a sample of the
+    /// first lines. The reader is rewound, so the inferred options can be confirmed and set with
+    /// [with_dialect](CsvReader::with_dialect) before the file is read.
+    pub fn sniff(&mut self) -> Result<CsvDialect> {
+        let start = self.reader.seek(SeekFrom::Current(0))?;
+        let mut sample = Vec::with_capacity(SNIFF_SAMPLE_BYTES);
+        (&mut self.reader)
+            .take(SNIFF_SAMPLE_BYTES as u64)
+            .read_to_end(&mut sample)?;
+        self.reader.seek(SeekFrom::Start(start))?;
+        sniff_dialect(&sample)
+    }
+
+    /// Set the delimiter and header of a dialect inferred by [sniff](CsvReader::sniff).
+    /// Returns an error if the quote character or line terminator of the dialect isn't
+    /// supported, see [CsvDialect::check_supported].
+    pub fn with_dialect(self, dialect: &CsvDialect) -> Result<Self> {
+        dialect.check_supported()?;
+        Ok(self
+            .with_delimiter(dialect.delimiter)
+            .has_header(dialect.has_header))
+    }
+
+    /// Read the file in DataFrames of at most `batch_size` rows on a single thread, so that a
+    /// file that doesn't fit in memory can be processed batch by batch. The reader must be
+    /// created with [CsvReader::from_path].
+    pub fn batched(self, batch_size: usize) -> Result<BatchedCsvReader> {
+        self.build_inner_reader()?.batched(batch_size)
+    }
+
+    /// Build the reader that parses the file. The column transformers are applied by the
+    /// reader, so that a predicate or an aggregation sees the transformed values.
     pub fn build_inner_reader(self) -> Result<SequentialReader<R>> {
-        build_csv_reader(
+        let column_transformers = self.column_transformers;
+        let reader = build_csv_reader(
             self.reader,
             self.stop_after_n_rows,
             self.skip_rows,
@@ -322,7 +397,8 @@ where
             self.schema_overwrite,
             self.sample_size,
             self.stable_parser,
-        )
+        )?;
+        Ok(reader.with_column_transformers(column_transformers))
     }
 }
 
@@ -359,6 +435,7 @@ where
             schema_overwrite: None,
             sample_size: 1024,
             stable_parser: false,
+            column_transformers: ColumnTransformers::default(),
         }
     }
 
@@ -386,6 +463,7 @@ mod test {
     use crate::prelude::*;
     use polars_core::datatypes::AnyValue;
     use polars_core::prelude::*;
+    use polars_core::utils::accumulate_dataframes_vertical;
     use std::io::Cursor;
 
     #[test]
@@ -412,6 +490,17 @@ mod test {
         dbg!(df);
     }
 
+    #[test]
+    fn test_csv_bytes_read() -> Result<()> {
+        let path = "../../examples/aggregate_multiple_files_in_chunks/datasets/foods1.csv";
+        let len = std::fs::metadata(path)?.len() as usize;
+
+        let mut reader = CsvReader::from_path(path)?.build_inner_reader()?;
+        reader.as_df(None, None)?;
+        assert_eq!(reader.bytes_read(), len);
+        Ok(())
+    }
+
     #[test]
     fn test_parser() {
         let s = r#"
@@ -674,4 +763,111 @@ id090,id048,id0000067778,24,2,51862,4,9,"#;
         let file = Cursor::new(s);
         let df = CsvReader::new(file).has_header(true).finish().unwrap();
     }
+
+    #[test]
+    fn test_read_temporal() -> Result<()> {
+        let csv = r#"day,ts
+2021-01-02,2021-01-02 12:00:00
+1969-12-31,
+2021-01-04,1969-12-31 23:00:00.500"#;
+
+        let overwrite = Schema::new(vec![
+            Field::new("day", DataType::Date32),
+            Field::new("ts", DataType::Date64),
+        ]);
+        let df = CsvReader::new(Cursor::new(csv))
+            .has_header(true)
+            .with_dtype_overwrite(Some(&overwrite))
+            .finish()?;
+        assert_eq!(df.column("day")?.dtype(), &DataType::Date32);
+        assert_eq!(df.column("ts")?.dtype(), &DataType::Date64);
+        assert_eq!(
+            Vec::from(df.column("day")?.date32()?),
+            &[Some(18629), Some(-1), Some(18631)]
+        );
+        assert_eq!(
+            Vec::from(df.column("ts")?.date64()?),
+            &[Some(1_609_588_800_000), None, Some(-3_599_500)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_column_transformers() -> Result<()> {
+        let mut df = create_df();
+        let mut buf: Vec<u8> = Vec::new();
+        // mask the days in the file
+        CsvWriter::new(&mut buf)
+            .has_headers(true)
+            .with_column_transformer("days", |s: Series| {
+                Ok(Series::new("masked", vec!["***"; s.len()]))
+            })
+            .finish(&mut df)?;
+        let csv = std::str::from_utf8(&buf).unwrap();
+        assert!(csv.starts_with("days,temp\n***,22.1\n"));
+        assert!(df.frame_equal(&create_df()));
+
+        let out = CsvReader::new(Cursor::new(buf.clone()))
+            .with_column_transformer("temp", |s: Series| Ok(&s + &s))
+            .finish()?;
+        assert_eq!(out.get_column_names(), &["days", "temp"]);
+        let temp = df.column("temp")?;
+        assert!(out.column("temp")?.series_equal(&(temp + temp)));
+
+        // the length of a column can't change
+        let out = CsvReader::new(Cursor::new(buf.clone()))
+            .with_column_transformer("temp", |s: Series| Ok(s.head(Some(1))))
+            .finish();
+        assert!(out.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sniff_and_read() -> Result<()> {
+        let csv = "1;\"a;b\";2.5\n2;c;3.5\n";
+        let mut reader = CsvReader::new(Cursor::new(csv));
+        let dialect = reader.sniff()?;
+        assert_eq!(dialect.delimiter, b';');
+        assert!(!dialect.has_header);
+
+        let df = reader.with_dialect(&dialect)?.finish()?;
+        assert_eq!(df.shape(), (2, 3));
+        assert_eq!(
+            Vec::from(df.select_at_idx(1).unwrap().utf8()?),
+            &[Some("a;b"), Some("c")]
+        );
+
+        // the parser doesn't support other quote characters or \r line terminators
+        let csv = "1;'a;b';2.5\r2;c;3.5\r";
+        let mut reader = CsvReader::new(Cursor::new(csv));
+        let dialect = reader.sniff()?;
+        assert_eq!(dialect.quote_char, Some(b'\''));
+        assert_eq!(dialect.line_terminator, LineTerminator::Cr);
+        assert!(reader.with_dialect(&dialect).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_batched() -> Result<()> {
+        let path = "../../examples/aggregate_multiple_files_in_chunks/datasets/foods1.csv";
+        let expected = CsvReader::from_path(path)?.finish()?;
+
+        let batches = CsvReader::from_path(path)?
+            .batched(10)?
+            .collect::<Result<Vec<_>>>()?;
+        let heights: Vec<_> = batches.iter().map(|df| df.height()).collect();
+        assert_eq!(heights, &[10, 10, 7]);
+        let df = accumulate_dataframes_vertical(batches)?;
+        assert!(df.frame_equal(&expected));
+
+        let batches = CsvReader::from_path(path)?
+            .with_columns(Some(vec!["calories".to_string()]))
+            .with_stop_after_n_rows(Some(15))
+            .batched(10)?
+            .collect::<Result<Vec<_>>>()?;
+        let df = accumulate_dataframes_vertical(batches)?;
+        assert_eq!(df.get_column_names(), &["calories"]);
+        assert!(df.frame_equal(&expected.select("calories")?.head(Some(15))));
+        Ok(())
+    }
 }
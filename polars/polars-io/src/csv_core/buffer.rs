@@ -195,7 +195,10 @@ fn field_to_builder(i: usize, capacity: usize, schema: &SchemaRef) -> Result<Buf
         &DataType::UInt64 => Buffer::UInt64(Vec::with_capacity(capacity)),
         &DataType::Float32 => Buffer::Float32(Vec::with_capacity(capacity)),
         &DataType::Float64 => Buffer::Float64(Vec::with_capacity(capacity)),
-        &DataType::Utf8 => Buffer::Utf8(Vec::with_capacity(capacity), 0),
+        // temporal values are parsed from the strings when the column is finished
+        &DataType::Utf8 | &DataType::Date32 | &DataType::Date64 => {
+            Buffer::Utf8(Vec::with_capacity(capacity), 0)
+        }
         other => {
             return Err(PolarsError::Other(
                 format!("Unsupported data type {:?} when reading a csv", other).into(),
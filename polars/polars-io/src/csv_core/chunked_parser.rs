@@ -1,6 +1,8 @@
 use crate::csv::CsvEncoding;
 use crate::csv_core::buffer::PrimitiveParser;
 use crate::csv_core::utils::parse_bytes_with_encoding;
+use crate::predicates::apply_predicate;
+use crate::transform::ColumnTransformers;
 use crate::PhysicalIoExpr;
 use crate::ScanAggregation;
 use polars_core::prelude::*;
@@ -29,6 +31,10 @@ fn field_to_builder(i: usize, capacity: usize, schema: &SchemaRef) -> Result<Bui
         &DataType::Float32 => Builder::Float32(PrimitiveChunkedBuilder::new(name, capacity)),
         &DataType::Float64 => Builder::Float64(PrimitiveChunkedBuilder::new(name, capacity)),
         &DataType::Utf8 => Builder::Utf8(Utf8ChunkedBuilder::new(name, capacity, capacity * 32)),
+        dt @ &DataType::Date32 | dt @ &DataType::Date64 => Builder::Temporal(
+            Utf8ChunkedBuilder::new(name, capacity, capacity * 32),
+            dt.clone(),
+        ),
         other => {
             return Err(PolarsError::Other(
                 format!("Unsupported data type {:?} when reading a csv", other).into(),
@@ -38,9 +44,12 @@ fn field_to_builder(i: usize, capacity: usize, schema: &SchemaRef) -> Result<Bui
     Ok(builder)
 }
 
-fn builders_to_df(builders: Vec<Builder>) -> DataFrame {
-    let columns = builders.into_iter().map(|b| b.into_series()).collect();
-    DataFrame::new_no_checks(columns)
+pub(crate) fn builders_to_df(builders: Vec<Builder>) -> Result<DataFrame> {
+    let columns = builders
+        .into_iter()
+        .map(|b| b.into_series())
+        .collect::<Result<_>>()?;
+    Ok(DataFrame::new_no_checks(columns))
 }
 
 #[inline]
@@ -66,7 +75,9 @@ pub(crate) fn add_to_builders_core(
             DataType::Float64 => {
                 add_to_primitive_core(rows, *i, builder.f64(), ignore_parser_error)
             }
-            DataType::Utf8 => add_to_utf8_builder_core(rows, *i, builder.utf8(), encoding),
+            DataType::Utf8 | DataType::Date32 | DataType::Date64 => {
+                add_to_utf8_builder_core(rows, *i, builder.utf8(), encoding)
+            }
             _ => panic!("datatype not supported"),
         }
     };
@@ -275,14 +286,14 @@ pub(crate) fn finish_builder(
     parsed_dfs: &mut Vec<DataFrame>,
     predicate: Option<&Arc<dyn PhysicalIoExpr>>,
     aggregate: Option<&[ScanAggregation]>,
+    column_transformers: &ColumnTransformers,
 ) -> Result<()> {
-    let mut df = builders_to_df(builders);
+    let mut df = builders_to_df(builders)?;
+    // the predicate and the aggregations see the transformed values
+    column_transformers.apply(&mut df)?;
     if let Some(predicate) = predicate {
-        let s = predicate.evaluate(&df)?;
-        let mask = s.bool().expect("filter predicates was not of type boolean");
-        let local_df = df.filter(mask)?;
         if df.height() > 0 {
-            df = local_df;
+            df = apply_predicate(df, predicate.as_ref())?;
         }
     }
     // IMPORTANT the assumption of the aggregations is that all column are aggregated.
@@ -311,6 +322,8 @@ pub(crate) enum Builder {
     Float32(PrimitiveChunkedBuilder<Float32Type>),
     Float64(PrimitiveChunkedBuilder<Float64Type>),
     Utf8(Utf8ChunkedBuilder),
+    /// Temporal values are collected as strings and parsed into the data type when finished.
+    Temporal(Utf8ChunkedBuilder, DataType),
 }
 
 impl Builder {
@@ -358,15 +371,16 @@ impl Builder {
     }
     fn utf8(&mut self) -> &mut Utf8ChunkedBuilder {
         match self {
-            Builder::Utf8(builder) => builder,
+            Builder::Utf8(builder) | Builder::Temporal(builder, _) => builder,
             _ => panic!("implementation error"),
         }
     }
 
-    fn into_series(self) -> Series {
+    fn into_series(self) -> Result<Series> {
         use Builder::*;
-        match self {
+        let s = match self {
             Utf8(b) => b.finish().into_series(),
+            Temporal(b, dt) => return b.finish().into_series().cast_with_datatype(&dt),
             Int32(b) => b.finish().into_series(),
             Int64(b) => b.finish().into_series(),
             UInt32(b) => b.finish().into_series(),
@@ -374,6 +388,7 @@ impl Builder {
             Float32(b) => b.finish().into_series(),
             Float64(b) => b.finish().into_series(),
             Boolean(b) => b.finish().into_series(),
-        }
+        };
+        Ok(s)
     }
 }
@@ -1,13 +1,17 @@
 use crate::csv::CsvEncoding;
 use crate::csv_core::chunked_parser::{
-    add_to_builders_core, finish_builder, init_builders, next_rows_core,
+    add_to_builders_core, builders_to_df, finish_builder, init_builders, next_rows_core,
+    PolarsCsvRecord,
 };
 use crate::csv_core::utils::*;
 use crate::csv_core::{buffer::*, parser::*};
+use crate::predicates::{flag_sorted_columns, should_read, BatchStats, StatsIndex};
+use crate::transform::ColumnTransformers;
 use crate::PhysicalIoExpr;
 use crate::ScanAggregation;
 use csv::ByteRecordsIntoIter;
-use polars_core::{prelude::*, POOL};
+use polars_core::utils::{estimated_row_bytes, n_splits};
+use polars_core::{chunk_size, prelude::*, POOL};
 use rayon::prelude::*;
 use std::fmt;
 use std::io::{Read, Seek};
@@ -38,6 +42,9 @@ pub struct SequentialReader<R: Read> {
     delimiter: u8,
     sample_size: usize,
     stable_parser: bool,
+    /// Bytes of the input that were parsed by `as_df`.
+    bytes_read: usize,
+    column_transformers: ColumnTransformers,
 }
 
 impl<R> fmt::Debug for SequentialReader<R>
@@ -110,9 +117,27 @@ impl<R: Read + Sync + Send> SequentialReader<R> {
             delimiter,
             sample_size,
             stable_parser,
+            bytes_read: 0,
+            column_transformers: ColumnTransformers::default(),
         }
     }
 
+    /// Transform the columns of every parsed batch, before the predicate and the aggregations
+    /// of [as_df](SequentialReader::as_df) are applied.
+    pub(crate) fn with_column_transformers(
+        mut self,
+        column_transformers: ColumnTransformers,
+    ) -> Self {
+        self.column_transformers = column_transformers;
+        self
+    }
+
+    /// The number of bytes of the input that were parsed by [as_df](SequentialReader::as_df).
+    /// Batches that are skipped based on the statistics of an earlier scan are not counted.
+    pub fn bytes_read(&self) -> usize {
+        self.bytes_read
+    }
+
     fn find_starting_point<'a>(&self, mut bytes: &'a [u8]) -> Result<&'a [u8]> {
         // Skip all leading white space and the occasional utf8-bom
         bytes = skip_line_ending(skip_whitespace(skip_bom(bytes)).0).0;
@@ -132,6 +157,23 @@ impl<R: Read + Sync + Send> SequentialReader<R> {
         Ok(bytes)
     }
 
+    /// The number of chunks to parse `bytes` in: a chunk per thread, or chunks of the
+    /// [chunk_size](polars_core::chunk_size) if it is set.
+    fn n_file_chunks(&self, bytes: &[u8], n_threads: usize) -> usize {
+        if chunk_size().is_none() {
+            return n_threads;
+        }
+        let n_rows = match get_line_stats(bytes, self.sample_size) {
+            Some((mean, _)) => (bytes.len() as f32 / mean) as usize,
+            None => 128,
+        };
+        n_splits(n_rows, estimated_row_bytes(&self.schema), n_threads)
+    }
+
+    /// Parse the file in batches of `batch_size` rows. With a predicate on a time column, the
+    /// batches are indexed by their byte offset in the file, see [StatsIndex]. The time columns
+    /// of a batch are parsed first, and the other columns only if the batch can contain matching
+    /// rows. The statistics of the batches are returned in the order of the rows.
     fn parse_csv_chunked(
         &mut self,
         predicate: Option<&Arc<dyn PhysicalIoExpr>>,
@@ -139,124 +181,207 @@ impl<R: Read + Sync + Send> SequentialReader<R> {
         capacity: usize,
         n_threads: usize,
         bytes: &[u8],
-    ) -> Result<Vec<DataFrame>> {
+    ) -> Result<(Vec<DataFrame>, Vec<BatchStats>)> {
         let projection = self
             .projection
             .take()
             .unwrap_or_else(|| (0..self.schema.fields().len()).collect());
+        let file_len = bytes.len();
         let bytes = self.find_starting_point(bytes)?;
+        // offsets in `bytes` + `base` are offsets in the file
+        let base = file_len - bytes.len();
+
+        let temporal = (0..self.schema.fields().len())
+            .filter(|&i| {
+                matches!(
+                    self.schema.field(i).unwrap().data_type(),
+                    DataType::Date32 | DataType::Date64
+                )
+            })
+            .collect::<Vec<_>>();
+        // the statistics of the index are of the values in the file, not of the transformed values
+        let indexed = match (predicate, &self.path) {
+            (Some(predicate), Some(path))
+                if predicate.as_stats_evaluator().is_some()
+                    && !temporal.is_empty()
+                    && self.column_transformers.is_empty() =>
+            {
+                Some((StatsIndex::of_file(path), predicate))
+            }
+            _ => None,
+        };
 
+        let n_chunks = self.n_file_chunks(bytes, n_threads);
         let file_chunks =
-            get_file_chunks(bytes, n_threads, self.schema.fields().len(), self.delimiter);
-
-        let parsed_dfs = POOL
-            .install(|| {
-                file_chunks
-                    .into_par_iter()
-                    .enumerate()
-                    .map(|(thread_no, (mut total_bytes_offset, stop_at_nbytes))| {
-                        let delimiter = self.delimiter;
-                        let batch_size = self.batch_size;
-                        let schema = self.schema.clone();
-                        let ignore_parser_errors = self.ignore_parser_errors;
-                        let encoding = self.encoding;
-                        let projection = &projection;
-
-                        // container to ammortize allocs
-                        let mut rows = Vec::with_capacity(batch_size);
-                        rows.resize_with(batch_size, Default::default);
-
-                        let mut builders = init_builders(&projection, capacity, &schema).unwrap();
-
-                        #[cfg(target_os = "linux")]
-                        let has_utf8 = builders
-                            .iter()
-                            .any(|b| matches!(b, super::chunked_parser::Builder::Utf8(_)));
-
-                        let mut local_parsed_dfs = Vec::with_capacity(16);
-
-                        let mut local_bytes;
-                        let mut core_reader =
-                            csv_core::ReaderBuilder::new().delimiter(delimiter).build();
-
-                        let mut count = 0;
-                        loop {
-                            count += 1;
-                            local_bytes = &bytes[total_bytes_offset..stop_at_nbytes];
-                            let (correctly_parsed, bytes_read) = next_rows_core(
-                                &mut rows,
-                                local_bytes,
-                                &mut core_reader,
-                                batch_size,
-                            );
-                            total_bytes_offset += bytes_read;
+            get_file_chunks(bytes, n_chunks, self.schema.fields().len(), self.delimiter);
 
-                            if correctly_parsed < batch_size {
-                                if correctly_parsed == 0 {
-                                    break;
+        let parsed = POOL.install(|| {
+            file_chunks
+                .into_par_iter()
+                .enumerate()
+                .map(|(thread_no, (mut total_bytes_offset, stop_at_nbytes))| {
+                    let delimiter = self.delimiter;
+                    let batch_size = self.batch_size;
+                    let schema = self.schema.clone();
+                    let ignore_parser_errors = self.ignore_parser_errors;
+                    let encoding = self.encoding;
+                    let projection = &projection;
+                    let column_transformers = &self.column_transformers;
+
+                    // container to ammortize allocs
+                    let mut rows = Vec::with_capacity(batch_size);
+                    rows.resize_with(batch_size, Default::default);
+
+                    let mut builders = init_builders(&projection, capacity, &schema).unwrap();
+
+                    #[cfg(target_os = "linux")]
+                    let has_utf8 = builders.iter().any(|b| {
+                        matches!(
+                            b,
+                            super::chunked_parser::Builder::Utf8(_)
+                                | super::chunked_parser::Builder::Temporal(..)
+                        )
+                    });
+
+                    let mut local_parsed_dfs = Vec::with_capacity(16);
+                    let mut local_stats = vec![];
+
+                    let mut local_bytes;
+                    let mut core_reader =
+                        csv_core::ReaderBuilder::new().delimiter(delimiter).build();
+
+                    let mut count = 0;
+                    let mut local_bytes_read = 0;
+                    loop {
+                        count += 1;
+                        // consult the index of the earlier scans before the rows are read
+                        if let Some((index, predicate)) = &indexed {
+                            if let Some((end, stats)) = index.get(base + total_bytes_offset) {
+                                if !should_read(predicate.as_ref(), &stats)? {
+                                    local_stats.push(stats);
+                                    total_bytes_offset = end - base;
+                                    if total_bytes_offset >= stop_at_nbytes {
+                                        break;
+                                    }
+                                    continue;
                                 }
-                                // this only happens at the last batch if it doesn't fit a whole batch.
-                                rows.truncate(correctly_parsed);
                             }
+                        }
+
+                        let batch_start = total_bytes_offset;
+                        local_bytes = &bytes[total_bytes_offset..stop_at_nbytes];
+                        let (correctly_parsed, bytes_read) =
+                            next_rows_core(&mut rows, local_bytes, &mut core_reader, batch_size);
+                        total_bytes_offset += bytes_read;
+                        local_bytes_read += bytes_read;
+
+                        if correctly_parsed < batch_size {
+                            if correctly_parsed == 0 {
+                                break;
+                            }
+                            // this only happens at the last batch if it doesn't fit a whole batch.
+                            rows.truncate(correctly_parsed);
+                        }
+
+                        if let Some((index, predicate)) = &indexed {
+                            let mut temporal_builders =
+                                init_builders(&temporal, rows.len(), &schema)?;
                             add_to_builders_core(
-                                &mut builders,
-                                &projection,
+                                &mut temporal_builders,
+                                &temporal,
                                 &rows,
                                 &schema,
                                 ignore_parser_errors,
                                 encoding,
                             )?;
-
-                            if total_bytes_offset >= stop_at_nbytes {
-                                break;
+                            let stats = BatchStats::new(&builders_to_df(temporal_builders)?)?;
+                            let read = should_read(predicate.as_ref(), &stats)?;
+                            index.insert(
+                                base + batch_start,
+                                base + total_bytes_offset,
+                                stats.clone(),
+                            );
+                            local_stats.push(stats);
+                            if !read {
+                                if total_bytes_offset >= stop_at_nbytes {
+                                    break;
+                                }
+                                continue;
                             }
+                        }
+                        add_to_builders_core(
+                            &mut builders,
+                            &projection,
+                            &rows,
+                            &schema,
+                            ignore_parser_errors,
+                            encoding,
+                        )?;
+
+                        if total_bytes_offset >= stop_at_nbytes {
+                            break;
+                        }
 
-                            if count % CAPACITY_MULTIPLIER == 0 {
-                                let mut builders_tmp =
-                                    init_builders(&projection, capacity, &schema).unwrap();
-                                std::mem::swap(&mut builders_tmp, &mut builders);
-                                finish_builder(
-                                    builders_tmp,
-                                    &mut local_parsed_dfs,
-                                    predicate,
-                                    aggregate,
-                                )
-                                .unwrap();
-
-                                #[cfg(target_os = "linux")]
+                        if count % CAPACITY_MULTIPLIER == 0 {
+                            let mut builders_tmp =
+                                init_builders(&projection, capacity, &schema).unwrap();
+                            std::mem::swap(&mut builders_tmp, &mut builders);
+                            finish_builder(
+                                builders_tmp,
+                                &mut local_parsed_dfs,
+                                predicate,
+                                aggregate,
+                                column_transformers,
+                            )
+                            .unwrap();
+
+                            #[cfg(target_os = "linux")]
+                            {
+                                // linux global allocators don't return freed memory immediately to the OS.
+                                // macos and windows return more aggressively.
+                                // We choose this location to do trim heap memory as this will be called after CSV read
+                                // which may have some over-allocated utf8
+                                // This is an expensive operation therefore we don't want to call it too often, and only when
+                                // there are utf8 arrays.
+                                if has_utf8
+                                    && thread_no == 0
+                                    && count % (CAPACITY_MULTIPLIER * 16) == 0
                                 {
-                                    // linux global allocators don't return freed memory immediately to the OS.
-                                    // macos and windows return more aggressively.
-                                    // We choose this location to do trim heap memory as this will be called after CSV read
-                                    // which may have some over-allocated utf8
-                                    // This is an expensive operation therefore we don't want to call it too often, and only when
-                                    // there are utf8 arrays.
-                                    if has_utf8
-                                        && thread_no == 0
-                                        && count % (CAPACITY_MULTIPLIER * 16) == 0
-                                    {
-                                        use polars_core::utils::malloc_trim;
-                                        unsafe { malloc_trim(0) };
-                                    }
+                                    use polars_core::utils::malloc_trim;
+                                    unsafe { malloc_trim(0) };
                                 }
                             }
                         }
-                        finish_builder(builders, &mut local_parsed_dfs, predicate, aggregate)?;
-
-                        Ok(local_parsed_dfs)
-                    })
-                    .collect::<Result<Vec<_>>>()
-            })?
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>();
+                    }
+                    finish_builder(
+                        builders,
+                        &mut local_parsed_dfs,
+                        predicate,
+                        aggregate,
+                        column_transformers,
+                    )?;
 
-        Ok(parsed_dfs)
+                    Ok((local_parsed_dfs, local_stats, local_bytes_read))
+                })
+                .collect::<Result<Vec<_>>>()
+        })?;
+
+        let mut parsed_dfs = Vec::with_capacity(parsed.len());
+        let mut stats = vec![];
+        self.bytes_read = base;
+        for (dfs, local_stats, local_bytes_read) in parsed {
+            parsed_dfs.extend(dfs);
+            stats.extend(local_stats);
+            self.bytes_read += local_bytes_read;
+        }
+        Ok((parsed_dfs, stats))
     }
 
     fn parse_csv_fast(&mut self, n_threads: usize, bytes: &[u8]) -> Result<DataFrame> {
+        let file_len = bytes.len();
         // Make the variable mutable so that we can reassign the sliced file to this variable.
         let mut bytes = self.find_starting_point(bytes)?;
+        let base = file_len - bytes.len();
 
         // initial row guess. We use the line statistic to guess the number of rows to allocate
         let mut total_rows = 128;
@@ -286,6 +411,7 @@ impl<R: Read + Sync + Send> SequentialReader<R> {
                 }
             }
         }
+        self.bytes_read = base + bytes.len();
 
         // we also need to sort the projection to have predictable output.
         // the `parse_lines` function expects this.
@@ -300,9 +426,10 @@ impl<R: Read + Sync + Send> SequentialReader<R> {
 
         // split the file by the nearest new line characters such that every thread processes
         // approximately the same number of rows.
+        let n_chunks = self.n_file_chunks(bytes, n_threads);
         let file_chunks =
-            get_file_chunks(bytes, n_threads, self.schema.fields().len(), self.delimiter);
-        let local_capacity = total_rows / n_threads;
+            get_file_chunks(bytes, n_chunks, self.schema.fields().len(), self.delimiter);
+        let local_capacity = total_rows / n_chunks;
 
         // all the buffers returned from the threads
         // Structure:
@@ -359,8 +486,12 @@ impl<R: Read + Sync + Send> SequentialReader<R> {
                     self.encoding,
                     self.delimiter,
                 )?;
-                let name = self.schema.field(idx).unwrap().name();
-                s.rename(name);
+                let field = self.schema.field(idx).unwrap();
+                // temporal columns are parsed as strings
+                if s.dtype() != field.data_type() {
+                    s = s.cast_with_datatype(field.data_type())?;
+                }
+                s.rename(field.name());
                 Ok(s)
             })
             .collect::<Result<Vec<_>>>()?;
@@ -376,6 +507,7 @@ impl<R: Read + Sync + Send> SequentialReader<R> {
     ) -> Result<DataFrame> {
         let n_threads = self.n_threads.unwrap_or_else(num_cpus::get);
 
+        let mut batch_stats = vec![];
         let mut df = if predicate.is_some() || self.stable_parser || aggregate.is_some() {
             let mut capacity = self.batch_size * CAPACITY_MULTIPLIER;
             if let Some(n) = self.n_rows {
@@ -388,11 +520,12 @@ impl<R: Read + Sync + Send> SequentialReader<R> {
             let mmap = unsafe { memmap::Mmap::map(&file).unwrap() };
             let bytes = mmap[..].as_ref();
 
-            let parsed_dfs =
+            let (parsed_dfs, stats) =
                 self.parse_csv_chunked(predicate.as_ref(), aggregate, capacity, n_threads, bytes)?;
+            batch_stats = stats;
             polars_core::utils::accumulate_dataframes_vertical(parsed_dfs)?
         } else {
-            match (&self.path, self.record_iter.is_some()) {
+            let mut df = match (&self.path, self.record_iter.is_some()) {
                 (Some(p), _) => {
                     let file = std::fs::File::open(p).unwrap();
                     let mmap = unsafe { memmap::Mmap::map(&file).unwrap() };
@@ -403,13 +536,16 @@ impl<R: Read + Sync + Send> SequentialReader<R> {
                     let mut r = std::mem::take(&mut self.record_iter).unwrap().into_reader();
                     let mut bytes = Vec::with_capacity(1024 * 128);
                     r.get_mut().read_to_end(&mut bytes)?;
+                    self.bytes_read = bytes.len();
                     if bytes[bytes.len() - 1] != b'\n' || bytes[bytes.len() - 1] != b'\r' {
                         bytes.push(b'\n')
                     }
                     self.parse_csv_fast(n_threads, &bytes)?
                 }
                 _ => return Err(PolarsError::Other("file or reader must be set".into())),
-            }
+            };
+            self.column_transformers.apply(&mut df)?;
+            df
         };
 
         if let Some(aggregate) = aggregate {
@@ -427,10 +563,112 @@ impl<R: Read + Sync + Send> SequentialReader<R> {
                 df = df.slice(0, n_rows).unwrap()
             }
         }
+        if aggregate.is_none() && !batch_stats.is_empty() {
+            flag_sorted_columns(&mut df, &batch_stats)?;
+        }
+        Ok(df)
+    }
+
+    /// Parse the file in DataFrames of at most `batch_size` rows, see [BatchedCsvReader].
+    pub(crate) fn batched(self, batch_size: usize) -> Result<BatchedCsvReader> {
+        let path = self
+            .path
+            .as_ref()
+            .ok_or_else(|| PolarsError::Other("a batched csv reader needs a path".into()))?;
+        let file = std::fs::File::open(path)?;
+        let mmap = unsafe { memmap::Mmap::map(&file)? };
+        let offset = mmap.len() - self.find_starting_point(&mmap)?.len();
+        let n_fields = self.schema.fields().len();
+        let projection = self.projection.unwrap_or_else(|| (0..n_fields).collect());
+
+        let mut rows = Vec::with_capacity(batch_size);
+        rows.resize_with(batch_size, Default::default);
+        Ok(BatchedCsvReader {
+            mmap,
+            offset,
+            schema: self.schema,
+            projection,
+            batch_size,
+            ignore_parser_errors: self.ignore_parser_errors,
+            encoding: self.encoding,
+            core_reader: csv_core::ReaderBuilder::new()
+                .delimiter(self.delimiter)
+                .build(),
+            rows,
+            rows_left: self.n_rows,
+            column_transformers: self.column_transformers,
+        })
+    }
+}
+
+/// Parses a csv file in DataFrames of at most `batch_size` rows on the calling thread, so that
+/// a file that doesn't fit in memory can be processed batch by batch. Created by
+/// [CsvReader::batched](crate::csv::CsvReader::batched).
+pub struct BatchedCsvReader {
+    mmap: memmap::Mmap,
+    /// The offset of the next row in the file.
+    offset: usize,
+    schema: SchemaRef,
+    projection: Vec<usize>,
+    batch_size: usize,
+    ignore_parser_errors: bool,
+    encoding: CsvEncoding,
+    core_reader: csv_core::Reader,
+    /// container to amortize allocs
+    rows: Vec<PolarsCsvRecord>,
+    rows_left: Option<usize>,
+    column_transformers: ColumnTransformers,
+}
+
+impl BatchedCsvReader {
+    /// The schema of the batches.
+    pub fn schema(&self) -> Schema {
+        let fields = self.schema.fields();
+        Schema::new(self.projection.iter().map(|&i| fields[i].clone()).collect())
+    }
+
+    fn parse_rows(&self, n_rows: usize) -> Result<DataFrame> {
+        let mut builders = init_builders(&self.projection, n_rows, &self.schema)?;
+        add_to_builders_core(
+            &mut builders,
+            &self.projection,
+            &self.rows[..n_rows],
+            &self.schema,
+            self.ignore_parser_errors,
+            self.encoding,
+        )?;
+        let mut df = builders_to_df(builders)?;
+        self.column_transformers.apply(&mut df)?;
         Ok(df)
     }
 }
 
+impl Iterator for BatchedCsvReader {
+    type Item = Result<DataFrame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let batch_size = match self.rows_left {
+            Some(0) => return None,
+            Some(n) => std::cmp::min(n, self.batch_size),
+            None => self.batch_size,
+        };
+        let (n_rows, bytes_read) = next_rows_core(
+            &mut self.rows,
+            &self.mmap[self.offset..],
+            &mut self.core_reader,
+            batch_size,
+        );
+        self.offset += bytes_read;
+        if n_rows == 0 {
+            return None;
+        }
+        if let Some(rows_left) = &mut self.rows_left {
+            *rows_left -= n_rows;
+        }
+        Some(self.parse_rows(n_rows))
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn build_csv_reader<R: 'static + Read + Seek + Sync + Send>(
     mut reader: R,
@@ -2,4 +2,5 @@ pub(crate) mod buffer;
 mod chunked_parser;
 pub mod csv;
 pub(crate) mod parser;
+pub(crate) mod sniffer;
 pub mod utils;
@@ -0,0 +1,262 @@
+//! Detect the dialect of a csv file from a sample of its first lines.
+use polars_core::prelude::*;
+
+/// Number of bytes read from the start of a file to sniff its dialect.
+pub(crate) const SNIFF_SAMPLE_BYTES: usize = 64 * 1024;
+/// Maximum number of lines of the sample that are used.
+const SNIFF_MAX_LINES: usize = 100;
+/// The candidate delimiters, in order of preference.
+const DELIMITERS: [u8; 5] = [b',', b'\t', b';', b'|', b' '];
+/// The candidate quote characters, in order of preference.
+const QUOTES: [u8; 2] = [b'"', b'\''];
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LineTerminator {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    CrLf,
+    /// `\r`
+    Cr,
+}
+
+/// The options of a csv file, as inferred by [CsvReader::sniff](crate::csv::CsvReader::sniff).
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvDialect {
+    pub delimiter: u8,
+    /// The character that quotes fields, or `None` if no field is quoted in the sample.
+    pub quote_char: Option<u8>,
+    pub has_header: bool,
+    pub line_terminator: LineTerminator,
+}
+
+impl CsvDialect {
+    /// Return an error if the csv parser can't read this dialect. The parser only supports `"`
+    /// as quote character and `\n` or `\r\n` line terminators.
+    pub fn check_supported(&self) -> Result<()> {
+        if let Some(quote_char) = self.quote_char.filter(|&quote_char| quote_char != b'"') {
+            return Err(PolarsError::InvalidOperation(
+                format!(
+                    "the csv parser doesn't support {:?} as quote character",
+                    quote_char as char
+                )
+                .into(),
+            ));
+        }
+        if self.line_terminator == LineTerminator::Cr {
+            return Err(PolarsError::InvalidOperation(
+                "the csv parser doesn't support \\r line terminators".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Infer the dialect from the first bytes of a csv file. A trailing incomplete line is ignored.
+pub fn sniff_dialect(sample: &[u8]) -> Result<CsvDialect> {
+    let text = String::from_utf8_lossy(sample);
+    let line_terminator = if text.contains("\r\n") {
+        LineTerminator::CrLf
+    } else if text.contains('\n') || !text.contains('\r') {
+        LineTerminator::Lf
+    } else {
+        LineTerminator::Cr
+    };
+    let terminator = match line_terminator {
+        LineTerminator::Lf | LineTerminator::CrLf => '\n',
+        LineTerminator::Cr => '\r',
+    };
+
+    let mut lines = text
+        .split(terminator)
+        .map(|line| line.trim_end_matches('\r'))
+        .collect::<Vec<_>>();
+    // the sample may end in the middle of a line
+    if sample.len() >= SNIFF_SAMPLE_BYTES && lines.len() > 1 {
+        lines.pop();
+    }
+    let lines = lines
+        .into_iter()
+        .filter(|line| !line.is_empty())
+        .take(SNIFF_MAX_LINES)
+        .map(|line| line.as_bytes())
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        return Err(PolarsError::NoData(
+            "cannot sniff the dialect of an empty csv file".into(),
+        ));
+    }
+
+    let quote_char = sniff_quote_char(&lines);
+    let delimiter = sniff_delimiter(&lines, quote_char);
+    let rows = lines
+        .iter()
+        .map(|line| split_fields(line, delimiter, quote_char))
+        .collect::<Vec<_>>();
+    Ok(CsvDialect {
+        delimiter,
+        quote_char,
+        has_header: sniff_header(&rows),
+        line_terminator,
+    })
+}
+
+/// The quote character that most often opens a field, i.e. directly follows the start of a line
+/// or a candidate delimiter.
+fn sniff_quote_char(lines: &[&[u8]]) -> Option<u8> {
+    let opened = |quote: u8| {
+        lines
+            .iter()
+            .map(|line| {
+                let mut previous = None;
+                line.iter()
+                    .filter(|&&b| {
+                        let opens =
+                            b == quote && previous.map_or(true, |p: u8| DELIMITERS.contains(&p));
+                        previous = Some(b);
+                        opens
+                    })
+                    .count()
+            })
+            .sum::<usize>()
+    };
+    QUOTES
+        .iter()
+        .map(|&quote| (quote, opened(quote)))
+        .filter(|(_, count)| *count > 0)
+        // prefer the first candidate on ties
+        .fold(
+            None,
+            |best: Option<(u8, usize)>, (quote, count)| match best {
+                Some((_, best_count)) if best_count >= count => best,
+                _ => Some((quote, count)),
+            },
+        )
+        .map(|(quote, _)| quote)
+}
+
+/// Count the delimiters outside of quoted fields.
+fn count_delimiters(line: &[u8], delimiter: u8, quote_char: Option<u8>) -> usize {
+    let mut in_quotes = false;
+    line.iter()
+        .filter(|&&b| {
+            if Some(b) == quote_char {
+                in_quotes = !in_quotes;
+            }
+            b == delimiter && !in_quotes
+        })
+        .count()
+}
+
+/// The delimiter that splits the most lines in the same number of fields. Defaults to `,` if no
+/// candidate is found, e.g. for a file with a single column.
+fn sniff_delimiter(lines: &[&[u8]], quote_char: Option<u8>) -> u8 {
+    let mut best = (b',', 0.0);
+    for &delimiter in DELIMITERS.iter() {
+        let counts = lines
+            .iter()
+            .map(|line| count_delimiters(line, delimiter, quote_char))
+            .collect::<Vec<_>>();
+        let mode = most_frequent(&counts);
+        if mode == 0 {
+            continue;
+        }
+        let consistency =
+            counts.iter().filter(|&&count| count == mode).count() as f64 / counts.len() as f64;
+        if consistency > best.1 {
+            best = (delimiter, consistency);
+        }
+    }
+    best.0
+}
+
+fn most_frequent(values: &[usize]) -> usize {
+    let mut best = (0, 0);
+    for &value in values {
+        let count = values.iter().filter(|&&v| v == value).count();
+        if count > best.1 {
+            best = (value, count);
+        }
+    }
+    best.0
+}
+
+fn split_fields(line: &[u8], delimiter: u8, quote_char: Option<u8>) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = vec![];
+    let mut in_quotes = false;
+    for &b in line {
+        if Some(b) == quote_char {
+            in_quotes = !in_quotes;
+        } else if b == delimiter && !in_quotes {
+            fields.push(String::from_utf8_lossy(&field).trim().to_string());
+            field.clear();
+        } else {
+            field.push(b);
+        }
+    }
+    fields.push(String::from_utf8_lossy(&field).trim().to_string());
+    fields
+}
+
+/// Decide per column whether the first row looks different from the other rows. A column votes
+/// for a header if its values are numeric and the first value isn't, or if its values have the
+/// same length and the first value doesn't. A column votes against a header if the first value
+/// looks like the other values. Without evidence the file is assumed to have a header.
+fn sniff_header(rows: &[Vec<String>]) -> bool {
+    let (header, data) = match rows.split_first() {
+        Some((header, data)) if !data.is_empty() => (header, data),
+        _ => return true,
+    };
+    let is_numeric = |v: &str| v.parse::<f64>().is_ok();
+    let mut votes = 0i32;
+    for (idx, name) in header.iter().enumerate() {
+        let values = data
+            .iter()
+            .filter_map(|row| row.get(idx))
+            .filter(|v| !v.is_empty())
+            .collect::<Vec<_>>();
+        if values.is_empty() {
+            continue;
+        }
+        if values.iter().all(|v| is_numeric(v)) {
+            votes += if is_numeric(name) { -1 } else { 1 };
+        } else if values.iter().all(|v| v.len() == values[0].len()) {
+            votes += if name.len() == values[0].len() { -1 } else { 1 };
+        }
+    }
+    votes >= 0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sniff_dialect() -> Result<()> {
+        let dialect = sniff_dialect(b"name;age;city\n\"Doe; John\";42;NY\nJane;7;LA\n")?;
+        assert_eq!(
+            dialect,
+            CsvDialect {
+                delimiter: b';',
+                quote_char: Some(b'"'),
+                has_header: true,
+                line_terminator: LineTerminator::Lf,
+            }
+        );
+
+        let dialect = sniff_dialect(b"1\t2.5\tfoo\r\n3\t4.0\tbar\r\n")?;
+        assert_eq!(dialect.delimiter, b'\t');
+        assert_eq!(dialect.quote_char, None);
+        assert!(!dialect.has_header);
+        assert_eq!(dialect.line_terminator, LineTerminator::CrLf);
+
+        // the commas in the text aren't consistent, the pipes are
+        let dialect = sniff_dialect(b"id|text\n1|a, b\n2|c\n3|d, e, f\n")?;
+        assert_eq!(dialect.delimiter, b'|');
+        assert!(dialect.has_header);
+
+        assert!(sniff_dialect(b"\n\n").is_err());
+        Ok(())
+    }
+}
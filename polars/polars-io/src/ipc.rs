@@ -34,10 +34,16 @@
 //! ```
 use super::{finish_reader, ArrowReader, ArrowResult, RecordBatch};
 use crate::prelude::*;
+use crate::{null_columns, restore_null_columns, restore_null_fields, NULL_KEY_PREFIX};
+#[cfg(feature = "lazy")]
+use crate::{predicates::StatsIndex, PhysicalIoExpr, ScanAggregation};
+use arrow::compute::cast;
 use arrow::ipc::{
     reader::FileReader as ArrowIPCFileReader, writer::FileWriter as ArrowIPCFileWriter,
 };
 use polars_core::prelude::*;
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::io::{Read, Seek, Write};
 use std::sync::Arc;
 
@@ -47,6 +53,236 @@ pub struct IpcReader<R> {
     reader: R,
     /// Aggregates chunks afterwards to a single chunk.
     rechunk: bool,
+    stop_after_n_rows: Option<usize>,
+    column_transformers: ColumnTransformers,
+}
+
+impl<R> IpcReader<R>
+where
+    R: Read + Seek,
+{
+    /// Get the schema of the file.
+    pub fn schema(self) -> Result<Schema> {
+        let ipc_reader = ArrowIPCFileReader::try_new(self.reader)?;
+        let schema = ipc_reader.schema();
+        Ok(restore_null_fields(
+            (&*schema).into(),
+            &ipc_null_columns(&schema),
+        ))
+    }
+
+    /// Stop reading when `n` rows are read. The record batch that contains the `n`th row is
+    /// read completely.
+    pub fn with_stop_after_n_rows(mut self, num_rows: Option<usize>) -> Self {
+        self.stop_after_n_rows = num_rows;
+        self
+    }
+
+    /// Transform the column `name` after it is read, e.g. to decrypt or mask it.
+    pub fn with_column_transformer<T>(mut self, name: &str, transformer: T) -> Self
+    where
+        T: ColumnTransformer + 'static,
+    {
+        self.column_transformers.push(name, Arc::new(transformer));
+        self
+    }
+
+    /// Set the transformers of the columns that are read, replacing those that were added
+    /// with [with_column_transformer](Self::with_column_transformer).
+    pub fn with_column_transformers(mut self, column_transformers: ColumnTransformers) -> Self {
+        self.column_transformers = column_transformers;
+        self
+    }
+
+    /// Read the file in DataFrames of at most `batch_size` rows, so that a file that doesn't fit
+    /// in memory can be processed batch by batch. Only the columns at the indexes in `projection`
+    /// are kept, if given.
+    ///
+    /// The record batches of the file are decoded one at a time and sliced in batches, so the
+    /// memory use is bounded by the largest record batch of the file.
+    pub fn batched(
+        self,
+        batch_size: usize,
+        projection: Option<&[usize]>,
+    ) -> Result<BatchedIpcReader<R>> {
+        let reader = ArrowIPCFileReader::try_new(self.reader)?;
+        let null_columns = ipc_null_columns(&reader.schema());
+        Ok(BatchedIpcReader {
+            reader,
+            batch_size,
+            projection: projection.map(|p| p.to_vec()),
+            null_columns,
+            column_transformers: self.column_transformers,
+            rows_left: self.stop_after_n_rows,
+            pending: None,
+        })
+    }
+
+    #[cfg(feature = "lazy")]
+    // todo! hoist to lazy crate
+    pub fn finish_with_scan_ops(
+        self,
+        predicate: Option<Arc<dyn PhysicalIoExpr>>,
+        aggregate: Option<&[ScanAggregation]>,
+        projection: Option<&[usize]>,
+        stats_index: Option<&StatsIndex>,
+    ) -> Result<DataFrame> {
+        let rechunk = self.rechunk;
+        let ipc_reader = ArrowIPCFileReader::try_new(self.reader)?;
+        let null_columns = ipc_null_columns(&ipc_reader.schema());
+        let schema = ipc_reader.schema();
+        let schema = match projection {
+            Some(projection) => {
+                let fields = projection
+                    .iter()
+                    .map(|&i| schema.field(i).clone())
+                    .collect();
+                Arc::new(ArrowSchema::new(fields))
+            }
+            None => schema,
+        };
+        let reader = ScanReader {
+            reader: ipc_reader,
+            projection: projection.map(|p| p.to_vec()),
+            schema,
+            next_batch: 0,
+        };
+        let mut df = finish_reader(
+            reader,
+            rechunk,
+            self.stop_after_n_rows,
+            predicate,
+            aggregate,
+            None,
+            stats_index,
+            Some(&self.column_transformers),
+        )?;
+        restore_null_columns(&mut df, &null_columns)?;
+        Ok(df)
+    }
+}
+
+/// Reads an IPC file in DataFrames of at most `batch_size` rows. Created by [IpcReader::batched].
+pub struct BatchedIpcReader<R: Read + Seek> {
+    reader: ArrowIPCFileReader<R>,
+    batch_size: usize,
+    projection: Option<Vec<usize>>,
+    null_columns: Vec<String>,
+    column_transformers: ColumnTransformers,
+    rows_left: Option<usize>,
+    /// The rows of the last record batch that are not yielded yet.
+    pending: Option<DataFrame>,
+}
+
+impl<R: Read + Seek> BatchedIpcReader<R> {
+    fn batch_to_df(&self, batch: RecordBatch) -> Result<DataFrame> {
+        let mut df = DataFrame::try_from(batch)?;
+        if let Some(projection) = &self.projection {
+            let columns = projection
+                .iter()
+                .map(|&i| df.select_at_idx(i).unwrap().clone())
+                .collect();
+            df = DataFrame::new_no_checks(columns);
+        }
+        restore_null_columns(&mut df, &self.null_columns)?;
+        self.column_transformers.apply(&mut df)?;
+        Ok(df)
+    }
+}
+
+impl<R: Read + Seek> Iterator for BatchedIpcReader<R> {
+    type Item = Result<DataFrame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.rows_left == Some(0) {
+                return None;
+            }
+            if let Some(df) = self.pending.take() {
+                let mut len = std::cmp::min(self.batch_size, df.height());
+                if let Some(rows_left) = &mut self.rows_left {
+                    len = std::cmp::min(len, *rows_left);
+                    *rows_left -= len;
+                }
+                if len < df.height() {
+                    match df.slice(len, df.height() - len) {
+                        Ok(rest) => self.pending = Some(rest),
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+                return Some(df.slice(0, len));
+            }
+            match self.reader.next()? {
+                Ok(batch) => match self.batch_to_df(batch) {
+                    // empty record batches are skipped
+                    Ok(df) if df.height() == 0 => {}
+                    Ok(df) => self.pending = Some(df),
+                    Err(e) => return Some(Err(e)),
+                },
+                Err(e) => return Some(Err(e.into())),
+            }
+        }
+    }
+}
+
+/// Names of the columns that were Null when the file was written by [IpcWriter].
+fn ipc_null_columns(schema: &ArrowSchema) -> Vec<String> {
+    null_columns(schema.metadata().keys().map(|key| key.as_str()))
+}
+
+/// Reads the record batches of an IPC file for a scan. Only keeps the `projection` columns, the
+/// IPC reader of arrow doesn't support projections, so the other columns are still read. Skipped
+/// record batches are not decoded.
+#[cfg(feature = "lazy")]
+struct ScanReader<R: Read + Seek> {
+    reader: ArrowIPCFileReader<R>,
+    projection: Option<Vec<usize>>,
+    schema: Arc<ArrowSchema>,
+    /// The number of the next record batch.
+    next_batch: usize,
+}
+
+#[cfg(feature = "lazy")]
+impl<R> ArrowReader for ScanReader<R>
+where
+    R: Read + Seek,
+{
+    fn next_record_batch(&mut self) -> ArrowResult<Option<RecordBatch>> {
+        if self.next_batch >= self.reader.num_batches() {
+            return Ok(None);
+        }
+        let batch = match self.reader.next_record_batch()? {
+            Some(batch) => batch,
+            None => return Ok(None),
+        };
+        self.next_batch += 1;
+        match &self.projection {
+            Some(projection) => {
+                let columns = projection
+                    .iter()
+                    .map(|&i| batch.column(i).clone())
+                    .collect();
+                RecordBatch::try_new(self.schema.clone(), columns).map(Some)
+            }
+            None => Ok(Some(batch)),
+        }
+    }
+
+    fn skip_record_batch(&mut self) -> ArrowResult<bool> {
+        if self.next_batch >= self.reader.num_batches() {
+            return Ok(false);
+        }
+        self.next_batch += 1;
+        // the reader can't be positioned after the last batch, `next_record_batch` checks that
+        if self.next_batch < self.reader.num_batches() {
+            self.reader.set_index(self.next_batch)?;
+        }
+        Ok(true)
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        Arc::new((&*self.schema).into())
+    }
 }
 
 impl<R> ArrowReader for ArrowIPCFileReader<R>
@@ -70,6 +306,8 @@ where
         IpcReader {
             reader,
             rechunk: true,
+            stop_after_n_rows: None,
+            column_transformers: ColumnTransformers::default(),
         }
     }
     fn set_rechunk(mut self, rechunk: bool) -> Self {
@@ -80,13 +318,49 @@ where
     fn finish(self) -> Result<DataFrame> {
         let rechunk = self.rechunk;
         let ipc_reader = ArrowIPCFileReader::try_new(self.reader)?;
-        finish_reader(ipc_reader, rechunk, None, None, None)
+        let null_columns = ipc_null_columns(&ipc_reader.schema());
+        let mut df = finish_reader(
+            ipc_reader,
+            rechunk,
+            self.stop_after_n_rows,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )?;
+        restore_null_columns(&mut df, &null_columns)?;
+        self.column_transformers.apply(&mut df)?;
+        Ok(df)
     }
 }
 
 /// Write a DataFrame to Arrow's IPC format
 pub struct IpcWriter<'a, W> {
     writer: &'a mut W,
+    column_transformers: ColumnTransformers,
+}
+
+impl<'a, W> IpcWriter<'a, W>
+where
+    W: Write,
+{
+    /// Transform the column `name` before it is written, e.g. to tokenize it. The DataFrame
+    /// that is passed to `finish` isn't modified.
+    pub fn with_column_transformer<T>(mut self, name: &str, transformer: T) -> Self
+    where
+        T: ColumnTransformer + 'static,
+    {
+        self.column_transformers.push(name, Arc::new(transformer));
+        self
+    }
+
+    /// Set the transformers of the columns that are written, replacing those that were added
+    /// with [with_column_transformer](Self::with_column_transformer).
+    pub fn with_column_transformers(mut self, column_transformers: ColumnTransformers) -> Self {
+        self.column_transformers = column_transformers;
+        self
+    }
 }
 
 impl<'a, W> SerWriter<'a, W> for IpcWriter<'a, W>
@@ -94,15 +368,19 @@ where
     W: Write,
 {
     fn new(writer: &'a mut W) -> Self {
-        IpcWriter { writer }
+        IpcWriter {
+            writer,
+            column_transformers: ColumnTransformers::default(),
+        }
     }
 
     fn finish(self, df: &mut DataFrame) -> Result<()> {
-        let mut ipc_writer = ArrowIPCFileWriter::try_new(self.writer, &df.schema().to_arrow())?;
+        let mut transformed = self.column_transformers.transformed(df)?;
+        let df = transformed.as_mut().unwrap_or(df);
+        let (schema, batches) = to_dictionary_batches(df)?;
+        let mut ipc_writer = ArrowIPCFileWriter::try_new(self.writer, &schema)?;
 
-        let iter = df.iter_record_batches(df.height());
-
-        for batch in iter {
+        for batch in batches {
             ipc_writer.write(&batch)?
         }
         let _ = ipc_writer.finish()?;
@@ -110,10 +388,90 @@ where
     }
 }
 
+/// Get the arrow schema and record batches of `df`. Categorical columns are written as
+/// dictionary arrays of their categories, so that they are read back as Categorical. Null
+/// columns are written as booleans and marked in the schema metadata.
+fn to_dictionary_batches(df: &mut DataFrame) -> Result<(Arc<ArrowSchema>, Vec<RecordBatch>)> {
+    let null_metadata = df
+        .get_columns()
+        .iter()
+        .filter(|s| s.dtype() == &DataType::Null)
+        .map(|s| (format!("{}{}", NULL_KEY_PREFIX, s.name()), String::new()))
+        .collect::<HashMap<_, _>>();
+    let mut without_nulls;
+    let df = if null_metadata.is_empty() {
+        df
+    } else {
+        let columns = df
+            .get_columns()
+            .iter()
+            .map(|s| match s.dtype() {
+                DataType::Null => s.cast_with_datatype(&DataType::Boolean),
+                _ => Ok(s.clone()),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        without_nulls = DataFrame::new_no_checks(columns);
+        &mut without_nulls
+    };
+
+    let categorical_idx = df
+        .get_columns()
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| matches!(s.dtype(), DataType::Categorical(_)))
+        .map(|(idx, _)| idx)
+        .collect::<Vec<_>>();
+    if categorical_idx.is_empty() {
+        let schema = Arc::new(ArrowSchema::new_with_metadata(
+            df.schema().to_arrow().fields().clone(),
+            null_metadata,
+        ));
+        let batches = df.iter_record_batches(df.height()).collect();
+        return Ok((schema, batches));
+    }
+
+    let mut columns = df.get_columns().clone();
+    for &idx in &categorical_idx {
+        columns[idx] = columns[idx].cast::<Utf8Type>()?;
+    }
+    let mut df = DataFrame::new_no_checks(columns);
+    let dict_type = ArrowDataType::Dictionary(
+        Box::new(ArrowDataType::UInt32),
+        Box::new(ArrowDataType::Utf8),
+    );
+    let mut fields = df.schema().to_arrow().fields().clone();
+    for &idx in &categorical_idx {
+        let mut field = ArrowField::new_dict(
+            fields[idx].name(),
+            dict_type.clone(),
+            true,
+            idx as i64,
+            false,
+        );
+        field.set_metadata(fields[idx].metadata().clone());
+        fields[idx] = field;
+    }
+    let schema = Arc::new(ArrowSchema::new_with_metadata(fields, null_metadata));
+
+    let batches = df
+        .iter_record_batches(df.height())
+        .map(|batch| {
+            let mut columns = batch.columns().to_vec();
+            for &idx in &categorical_idx {
+                columns[idx] = cast(&columns[idx], &dict_type)?;
+            }
+            RecordBatch::try_new(schema.clone(), columns)
+        })
+        .collect::<ArrowResult<Vec<_>>>()?;
+    Ok((schema, batches))
+}
+
 #[cfg(test)]
 mod test {
     use crate::prelude::*;
+    use polars_core::prelude::*;
     use std::io::Cursor;
+    use std::sync::atomic::{AtomicU64, Ordering};
 
     #[test]
     fn write_and_read_ipc() {
@@ -131,4 +489,124 @@ mod test {
         let df_read = IpcReader::new(buf).finish().unwrap();
         assert!(df.frame_equal(&df_read));
     }
+
+    #[test]
+    fn read_ipc_counts_bytes() -> Result<()> {
+        let mut buf: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+        let mut df = create_df();
+        IpcWriter::new(&mut buf).finish(&mut df)?;
+        buf.set_position(0);
+        let len = buf.get_ref().len() as u64;
+
+        let count = Arc::new(AtomicU64::new(0));
+        let df_read = IpcReader::new(CountingReader::new(buf, count.clone())).finish()?;
+        assert!(df.frame_equal(&df_read));
+        let read = count.load(Ordering::Relaxed);
+        assert!(read > 0 && read <= len);
+        Ok(())
+    }
+
+    #[test]
+    fn write_and_read_ipc_metadata() -> Result<()> {
+        let mut buf: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+        let mut df = create_df();
+        let mut s = df.drop_in_place("temp")?;
+        let mut metadata = FieldMetadata::new();
+        metadata.insert("unit".to_string(), "celsius".to_string());
+        s.set_metadata(metadata.clone());
+        df.add_column(s)?;
+
+        IpcWriter::new(&mut buf).finish(&mut df)?;
+        buf.set_position(0);
+
+        let df_read = IpcReader::new(buf).finish()?;
+        assert_eq!(df_read.column("temp")?.metadata(), &metadata);
+        assert!(df_read.column("days")?.metadata().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn write_and_read_ipc_null() -> Result<()> {
+        let mut df = DataFrame::new(vec![
+            Series::full_null("a", 3, &DataType::Null)?,
+            Series::new("n", &[1, 2, 3]),
+        ])?;
+        let mut buf: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+        IpcWriter::new(&mut buf).finish(&mut df)?;
+
+        buf.set_position(0);
+        let schema = IpcReader::new(buf.clone()).schema()?;
+        assert_eq!(schema.field_with_name("a")?.data_type(), &DataType::Null);
+        let df_read = IpcReader::new(buf).finish()?;
+        assert_eq!(df_read.column("a")?.dtype(), &DataType::Null);
+        assert_eq!(df_read.column("a")?.len(), 3);
+        assert!(df_read.column("n")?.series_equal(df.column("n")?));
+        Ok(())
+    }
+
+    #[test]
+    fn write_and_read_ipc_categorical() -> Result<()> {
+        polars_core::toggle_string_cache(true);
+        let fruit = Series::new("fruit", &[Some("apple"), None, Some("pear"), Some("apple")])
+            .cast::<CategoricalType>()?;
+        let mut df = DataFrame::new(vec![fruit])?;
+        let mut buf: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+        IpcWriter::new(&mut buf).finish(&mut df)?;
+        buf.set_position(0);
+
+        let df_read = IpcReader::new(buf).finish()?;
+        let fruit = df_read.column("fruit")?;
+        assert_eq!(
+            fruit.dtype(),
+            &DataType::Categorical(CategoricalOrdering::Physical)
+        );
+        assert_eq!(
+            Vec::from(fruit.cast::<Utf8Type>()?.utf8()?),
+            &[Some("apple"), None, Some("pear"), Some("apple")]
+        );
+
+        // the categories that are read can be appended to and joined with the written ones
+        let mut stacked = df_read.clone();
+        stacked.vstack_mut(&df)?;
+        assert_eq!(stacked.column("fruit")?.null_count(), 2);
+        let prices = DataFrame::new(vec![
+            Series::new("fruit", &["apple", "pear"]).cast::<CategoricalType>()?,
+            Series::new("price", &[1, 2]),
+        ])?;
+        let joined = df_read.left_join(&prices, "fruit", "fruit")?;
+        assert_eq!(joined.column("price")?.sum::<i32>(), Some(4));
+        assert_eq!(joined.column("price")?.null_count(), 1);
+        polars_core::toggle_string_cache(false);
+        Ok(())
+    }
+
+    #[test]
+    fn read_ipc_batched() -> Result<()> {
+        let mut df = create_df();
+        let mut buf: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+        IpcWriter::new(&mut buf).finish(&mut df)?;
+
+        // the single record batch is sliced in batches
+        buf.set_position(0);
+        let batches = IpcReader::new(buf.clone())
+            .batched(2, None)?
+            .collect::<Result<Vec<_>>>()?;
+        let heights: Vec<_> = batches.iter().map(|df| df.height()).collect();
+        assert_eq!(heights, &[2, 2, 1]);
+        let mut stacked = batches[0].clone();
+        for batch in &batches[1..] {
+            stacked.vstack_mut(batch)?;
+        }
+        assert!(stacked.frame_equal(&df));
+
+        buf.set_position(0);
+        let batches = IpcReader::new(buf)
+            .with_stop_after_n_rows(Some(3))
+            .batched(2, Some(&[1]))?
+            .collect::<Result<Vec<_>>>()?;
+        let heights: Vec<_> = batches.iter().map(|df| df.height()).collect();
+        assert_eq!(heights, &[2, 1]);
+        assert_eq!(batches[0].get_column_names(), &["temp"]);
+        Ok(())
+    }
 }
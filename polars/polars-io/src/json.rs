@@ -74,6 +74,7 @@ where
     reader: R,
     reader_builder: ReaderBuilder,
     rechunk: bool,
+    column_transformers: ColumnTransformers,
 }
 
 impl<R> SerReader<R> for JsonReader<R>
@@ -85,6 +86,7 @@ where
             reader,
             reader_builder: ReaderBuilder::new(),
             rechunk: true,
+            column_transformers: ColumnTransformers::default(),
         }
     }
 
@@ -95,13 +97,18 @@ where
 
     fn finish(self) -> Result<DataFrame> {
         let rechunk = self.rechunk;
-        finish_reader(
+        let mut df = finish_reader(
             self.reader_builder.build(self.reader)?,
             rechunk,
             None,
             None,
             None,
-        )
+            None,
+            None,
+            None,
+        )?;
+        self.column_transformers.apply(&mut df)?;
+        Ok(df)
     }
 }
 
@@ -133,6 +140,15 @@ where
         self.reader_builder = self.reader_builder.with_projection(projection);
         self
     }
+
+    /// Transform the column `name` after it is read, e.g. to decrypt or mask it.
+    pub fn with_column_transformer<T>(mut self, name: &str, transformer: T) -> Self
+    where
+        T: ColumnTransformer + 'static,
+    {
+        self.column_transformers.push(name, Arc::new(transformer));
+        self
+    }
 }
 
 #[cfg(test)]
@@ -10,20 +10,33 @@ pub mod json;
 #[cfg(feature = "parquet")]
 #[cfg_attr(docsrs, doc(cfg(feature = "feature")))]
 pub mod parquet;
+pub mod predicates;
 pub mod prelude;
+pub mod transform;
 
+use crate::predicates::{
+    apply_predicate, flag_sorted_columns, should_read, BatchStats, StatsEvaluator, StatsIndex,
+};
+use crate::transform::ColumnTransformers;
 use arrow::{
     csv::Reader as ArrowCsvReader, error::Result as ArrowResult, json::Reader as ArrowJsonReader,
     record_batch::RecordBatch,
 };
+use polars_core::chunked_array::builder::CategoricalChunkedBuilder;
 use polars_core::prelude::*;
 use polars_core::utils::accumulate_dataframes_vertical;
 use std::convert::TryFrom;
-use std::io::{Read, Seek, Write};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 pub trait PhysicalIoExpr: Send + Sync {
     fn evaluate(&self, df: &DataFrame) -> Result<Series>;
+
+    /// Get the evaluator that can skip batches based on their min/max index.
+    fn as_stats_evaluator(&self) -> Option<&dyn StatsEvaluator> {
+        None
+    }
 }
 
 pub trait SerReader<R>
@@ -52,9 +65,61 @@ where
     fn finish(self, df: &mut DataFrame) -> Result<()>;
 }
 
+/// Wraps a reader and adds the number of bytes that are read from it to a shared counter.
+pub struct CountingReader<R> {
+    reader: R,
+    count: Arc<AtomicU64>,
+}
+
+impl<R> CountingReader<R> {
+    pub fn new(reader: R, count: Arc<AtomicU64>) -> Self {
+        CountingReader { reader, count }
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.reader.read(buf)?;
+        self.count.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
+impl<R: Seek> Seek for CountingReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.reader.seek(pos)
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl<R: parquet_lib::file::reader::Length> parquet_lib::file::reader::Length for CountingReader<R> {
+    fn len(&self) -> u64 {
+        self.reader.len()
+    }
+}
+
+// the parquet reader reads the column chunks through new readers, which share the counter
+#[cfg(feature = "parquet")]
+impl<R: parquet_lib::file::reader::ChunkReader> parquet_lib::file::reader::ChunkReader
+    for CountingReader<R>
+{
+    type T = CountingReader<R::T>;
+
+    fn get_read(&self, start: u64, length: usize) -> parquet_lib::errors::Result<Self::T> {
+        let reader = self.reader.get_read(start, length)?;
+        Ok(CountingReader::new(reader, self.count.clone()))
+    }
+}
+
 pub trait ArrowReader {
     fn next_record_batch(&mut self) -> ArrowResult<Option<RecordBatch>>;
 
+    /// Skip the next record batch. Readers that can seek to a record batch don't decode it.
+    /// Returns `false` at the end of the input.
+    fn skip_record_batch(&mut self) -> ArrowResult<bool> {
+        Ok(self.next_record_batch()?.is_some())
+    }
+
     fn schema(&self) -> Arc<Schema>;
 }
 
@@ -78,25 +143,157 @@ impl<R: Read> ArrowReader for ArrowJsonReader<R> {
     }
 }
 
+/// Prefix of the metadata keys that mark a column as Null, e.g. `polars.null.a`. Parquet and IPC
+/// files can't store the Null type, so these columns are written as all null boolean columns.
+#[cfg(any(feature = "parquet", feature = "ipc"))]
+pub(crate) const NULL_KEY_PREFIX: &str = "polars.null.";
+
+/// Names of the Null columns, given the metadata keys of a file.
+#[cfg(any(feature = "parquet", feature = "ipc"))]
+pub(crate) fn null_columns<'a>(keys: impl Iterator<Item = &'a str>) -> Vec<String> {
+    keys.filter_map(|key| key.strip_prefix(NULL_KEY_PREFIX))
+        .map(|name| name.to_string())
+        .collect()
+}
+
+/// Replace the columns that were written as all null booleans by Null columns. Columns that
+/// aren't read, e.g. because of a projection, are skipped.
+#[cfg(any(feature = "parquet", feature = "ipc"))]
+pub(crate) fn restore_null_columns(df: &mut DataFrame, null_columns: &[String]) -> Result<()> {
+    for name in null_columns {
+        if let Some(idx) = df.find_idx_by_name(name) {
+            let s = Series::full_null(name, df.height(), &DataType::Null)?;
+            df.replace_at_idx(idx, s)?;
+        }
+    }
+    Ok(())
+}
+
+/// Set the data type of the `null_columns` in a schema that is read from a file.
+#[cfg(any(feature = "parquet", feature = "ipc"))]
+pub(crate) fn restore_null_fields(schema: Schema, null_columns: &[String]) -> Schema {
+    if null_columns.is_empty() {
+        return schema;
+    }
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            if null_columns.contains(field.name()) {
+                Field::new(field.name(), DataType::Null).with_metadata(field.metadata().clone())
+            } else {
+                field.clone()
+            }
+        })
+        .collect();
+    Schema::new(fields)
+}
+
+/// Read all record batches of `reader`. With a `stats_index` the batches are indexed by their
+/// number, and batches that the index of an earlier scan rules out for the predicate aren't
+/// decoded.
+///
+/// The `column_transformers` are applied to every batch before the predicate and the
+/// aggregations, so these see the transformed values.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn finish_reader<R: ArrowReader>(
     mut reader: R,
     rechunk: bool,
     stop_after_n_rows: Option<usize>,
     predicate: Option<Arc<dyn PhysicalIoExpr>>,
     aggregate: Option<&[ScanAggregation]>,
+    categorical_columns: Option<&[String]>,
+    stats_index: Option<&StatsIndex>,
+    column_transformers: Option<&ColumnTransformers>,
 ) -> Result<DataFrame> {
+    let column_transformers = column_transformers.filter(|t| !t.is_empty());
+    // the statistics of the index are of the values in the file, not of the transformed values
+    let stats_index = stats_index.filter(|_| column_transformers.is_none());
     let mut n_rows = 0;
     let mut parsed_dfs = Vec::with_capacity(1024);
 
-    while let Some(batch) = reader.next_record_batch()? {
+    // The categorical columns are built over all batches, so that they share a single mapping
+    // and the strings of a batch can be dropped as soon as they are encoded. This includes the
+    // dictionary encoded columns of the file, which are Categorical per batch.
+    let mut categorical_builders = match aggregate {
+        None => {
+            let schema = reader.schema();
+            schema
+                .fields()
+                .iter()
+                .enumerate()
+                .filter(|(_, field)| match field.data_type() {
+                    DataType::Categorical(_) => true,
+                    DataType::Utf8 => categorical_columns
+                        .map_or(false, |names| names.iter().any(|name| name == field.name())),
+                    _ => false,
+                })
+                .map(|(idx, field)| (idx, CategoricalChunkedBuilder::new(field.name(), 1024)))
+                .collect::<Vec<_>>()
+        }
+        Some(_) => vec![],
+    };
+    categorical_builders.sort_by_key(|(idx, _)| *idx);
+
+    let mut batch_stats = vec![];
+    let mut batch_idx = 0;
+    loop {
+        // The first batch is always decoded, it determines the schema of the output.
+        if let (Some(predicate), Some(index), false) =
+            (&predicate, stats_index, parsed_dfs.is_empty())
+        {
+            if let Some((_, stats)) = index.get(batch_idx) {
+                if !should_read(predicate.as_ref(), &stats)? {
+                    if !reader.skip_record_batch()? {
+                        break;
+                    }
+                    batch_idx += 1;
+                    n_rows += stats.height();
+                    batch_stats.push(stats);
+                    if matches!(stop_after_n_rows, Some(n) if n_rows >= n) {
+                        break;
+                    }
+                    continue;
+                }
+            }
+        }
+        let batch = match reader.next_record_batch()? {
+            Some(batch) => batch,
+            None => break,
+        };
         n_rows += batch.num_rows();
 
         let mut df = DataFrame::try_from(batch)?;
+        if let Some(column_transformers) = column_transformers {
+            column_transformers.apply(&mut df)?;
+        }
+
+        let stats = match stats_index {
+            Some(index) => {
+                let stats = BatchStats::new(&df)?;
+                index.insert(batch_idx, batch_idx + 1, stats.clone());
+                Some(stats)
+            }
+            None => None,
+        };
+        batch_idx += 1;
 
         if let Some(predicate) = &predicate {
-            let s = predicate.evaluate(&df)?;
-            let mask = s.bool().expect("filter predicates was not of type boolean");
-            df = df.filter(mask)?;
+            df = match &stats {
+                Some(stats) if !should_read(predicate.as_ref(), stats)? => df.slice(0, 0)?,
+                _ => apply_predicate(df, predicate.as_ref())?,
+            };
+        }
+        batch_stats.extend(stats);
+
+        // reverse order, so that the indexes of the remaining columns stay valid
+        for (idx, builder) in categorical_builders.iter_mut().rev() {
+            let name = df.get_columns()[*idx].name().to_string();
+            let mut s = df.drop_in_place(&name)?;
+            if matches!(s.dtype(), DataType::Categorical(_)) {
+                s = s.cast::<Utf8Type>()?;
+            }
+            builder.append_values(s.utf8()?.into_iter());
         }
 
         if let Some(aggregate) = aggregate {
@@ -120,6 +317,14 @@ pub(crate) fn finish_reader<R: ArrowReader>(
     }
     let mut df = accumulate_dataframes_vertical(parsed_dfs)?;
 
+    if !categorical_builders.is_empty() {
+        let mut columns = df.get_columns().clone();
+        for (idx, builder) in categorical_builders {
+            columns.insert(idx, builder.finish().into_series());
+        }
+        df = DataFrame::new(columns)?;
+    }
+
     if let Some(aggregate) = aggregate {
         let cols = aggregate
             .iter()
@@ -128,10 +333,13 @@ pub(crate) fn finish_reader<R: ArrowReader>(
         df = DataFrame::new_no_checks(cols)
     }
 
-    match rechunk {
-        true => Ok(df.agg_chunks()),
-        false => Ok(df),
+    if rechunk {
+        df = df.agg_chunks();
+    }
+    if stats_index.is_some() && aggregate.is_none() {
+        flag_sorted_columns(&mut df, &batch_stats)?;
     }
+    Ok(df)
 }
 
 pub enum ScanAggregation {
@@ -14,10 +14,16 @@
 //! }
 //! ```
 //!
-use super::{finish_reader, ArrowReader, ArrowResult, RecordBatch};
+use super::{
+    finish_reader, null_columns, restore_null_columns, restore_null_fields, ArrowReader,
+    ArrowResult, RecordBatch, NULL_KEY_PREFIX,
+};
 use crate::prelude::*;
 use crate::{PhysicalIoExpr, ScanAggregation};
 use arrow::record_batch::RecordBatchReader;
+use parquet_lib::basic::Encoding;
+use parquet_lib::file::metadata::{KeyValue, ParquetMetaData};
+use parquet_lib::file::properties::WriterProperties;
 use parquet_lib::file::reader::{FileReader, SerializedFileReader};
 pub use parquet_lib::file::serialized_reader::SliceableCursor;
 use parquet_lib::{
@@ -28,9 +34,27 @@ use parquet_lib::{
     file::writer::TryClone,
 };
 use polars_core::prelude::*;
+use polars_core::{chunk_size, utils::estimated_row_bytes};
+use std::convert::TryFrom;
 use std::io::{Read, Seek, Write};
 use std::sync::Arc;
 
+/// Prefix of the file metadata keys that mark a column as Categorical, e.g.
+/// `polars.categorical.fruit`. The categories are written as dictionary encoded strings.
+const CATEGORICAL_KEY_PREFIX: &str = "polars.categorical.";
+
+/// The rows per batch: the [chunk_size](polars_core::chunk_size) if it is set, `max_rows`
+/// otherwise.
+fn target_batch_size(max_rows: usize, arrow_reader: &mut ParquetFileArrowReader) -> Result<usize> {
+    Ok(match chunk_size() {
+        Some(size) => {
+            let schema: Schema = (&arrow_reader.get_schema()?).into();
+            size.rows(estimated_row_bytes(&schema))
+        }
+        None => max_rows,
+    })
+}
+
 fn set_batch_size(max_rows: usize, stop_after_n_rows: Option<usize>) -> usize {
     let mut batch_size = max_rows;
     if let Some(n) = stop_after_n_rows {
@@ -41,11 +65,59 @@ fn set_batch_size(max_rows: usize, stop_after_n_rows: Option<usize>) -> usize {
     batch_size
 }
 
+/// Names of the columns that are dictionary encoded in every row group.
+fn dictionary_encoded_columns(metadata: &ParquetMetaData) -> Vec<String> {
+    let row_groups = metadata.row_groups();
+    let n_columns = row_groups.first().map_or(0, |rg| rg.num_columns());
+    (0..n_columns)
+        .filter(|&i| {
+            row_groups.iter().all(|rg| {
+                rg.column(i)
+                    .encodings()
+                    .iter()
+                    .any(|enc| matches!(enc, Encoding::PLAIN_DICTIONARY | Encoding::RLE_DICTIONARY))
+            })
+        })
+        .map(|i| row_groups[0].column(i).column_descr().name().to_string())
+        .collect()
+}
+
+/// Names of the columns that are read as Categorical: the columns that were Categorical when the
+/// file was written by [ParquetWriter] and, if `dictionary_as_categorical`, the columns that are
+/// dictionary encoded.
+fn categorical_columns(metadata: &ParquetMetaData, dictionary_as_categorical: bool) -> Vec<String> {
+    let mut names = if dictionary_as_categorical {
+        dictionary_encoded_columns(metadata)
+    } else {
+        vec![]
+    };
+    if let Some(key_values) = metadata.file_metadata().key_value_metadata() {
+        for kv in key_values {
+            if let Some(name) = kv.key.strip_prefix(CATEGORICAL_KEY_PREFIX) {
+                if !names.iter().any(|n| n == name) {
+                    names.push(name.to_string())
+                }
+            }
+        }
+    }
+    names
+}
+
+/// Names of the columns that were Null when the file was written by [ParquetWriter].
+fn parquet_null_columns(metadata: &ParquetMetaData) -> Vec<String> {
+    match metadata.file_metadata().key_value_metadata() {
+        Some(key_values) => null_columns(key_values.iter().map(|kv| kv.key.as_str())),
+        None => vec![],
+    }
+}
+
 /// Read Apache parquet format into a DataFrame.
 pub struct ParquetReader<R> {
     reader: R,
     rechunk: bool,
     stop_after_n_rows: Option<usize>,
+    dictionary_as_categorical: bool,
+    column_transformers: ColumnTransformers,
 }
 
 impl<R> ParquetReader<R>
@@ -64,6 +136,9 @@ where
 
         let file_reader = Arc::new(SerializedFileReader::new(self.reader)?);
         let rows_in_file = file_reader.metadata().file_metadata().num_rows() as usize;
+        let categorical_columns =
+            categorical_columns(file_reader.metadata(), self.dictionary_as_categorical);
+        let null_columns = parquet_null_columns(file_reader.metadata());
 
         if let Some(stop_after_n_rows) = self.stop_after_n_rows {
             if stop_after_n_rows > rows_in_file {
@@ -71,26 +146,63 @@ where
             }
         }
 
+        let mut arrow_reader = ParquetFileArrowReader::new(file_reader);
         let batch_size = match predicate {
             Some(_) => 512 * 1024,
             None => rows_in_file,
         };
+        let batch_size = target_batch_size(batch_size, &mut arrow_reader)?;
         let batch_size = set_batch_size(batch_size, self.stop_after_n_rows);
 
-        let mut arrow_reader = ParquetFileArrowReader::new(file_reader);
         let record_reader = match projection {
             Some(projection) => {
                 arrow_reader.get_record_reader_by_columns(projection.iter().copied(), batch_size)
             }
             None => arrow_reader.get_record_reader(batch_size),
         }?;
-        finish_reader(
+        let mut df = finish_reader(
             record_reader,
             rechunk,
             self.stop_after_n_rows,
             predicate,
             aggregate,
-        )
+            Some(&categorical_columns),
+            None,
+            Some(&self.column_transformers),
+        )?;
+        restore_null_columns(&mut df, &null_columns)?;
+        Ok(df)
+    }
+
+    /// Read the file in DataFrames of at most `batch_size` rows, so that a file that doesn't fit
+    /// in memory can be processed batch by batch. Only the columns at the indexes in
+    /// `projection` are read, if given.
+    ///
+    /// The Categorical columns are encoded per batch, so their categories are only comparable
+    /// between batches if the global string cache is toggled.
+    pub fn batched(
+        self,
+        batch_size: usize,
+        projection: Option<&[usize]>,
+    ) -> Result<BatchedParquetReader> {
+        let file_reader = Arc::new(SerializedFileReader::new(self.reader)?);
+        let categorical_columns =
+            categorical_columns(file_reader.metadata(), self.dictionary_as_categorical);
+        let null_columns = parquet_null_columns(file_reader.metadata());
+        let mut arrow_reader = ParquetFileArrowReader::new(file_reader);
+        let reader = match projection {
+            Some(projection) => {
+                arrow_reader.get_record_reader_by_columns(projection.iter().copied(), batch_size)
+            }
+            None => arrow_reader.get_record_reader(batch_size),
+        }?;
+        Ok(BatchedParquetReader {
+            reader,
+            categorical_columns,
+            null_columns,
+            column_transformers: self.column_transformers,
+            rows_left: self.stop_after_n_rows,
+        })
     }
 
     /// Stop parsing when `n` rows are parsed. By settings this parameter the csv will be parsed
@@ -100,11 +212,106 @@ where
         self
     }
 
+    /// Read the Utf8 columns that are dictionary encoded in the file as Categorical. The strings
+    /// of a column are encoded batch by batch, so the full Utf8 column is never materialized.
+    /// This saves a lot of memory for low cardinality string columns.
+    ///
+    /// The categories are local to the column, unless the global string cache is toggled.
+    pub fn with_dictionary_as_categorical(mut self, toggle: bool) -> Self {
+        self.dictionary_as_categorical = toggle;
+        self
+    }
+
+    /// Transform the column `name` after it is read, e.g. to decrypt or mask it.
+    pub fn with_column_transformer<T>(mut self, name: &str, transformer: T) -> Self
+    where
+        T: ColumnTransformer + 'static,
+    {
+        self.column_transformers.push(name, Arc::new(transformer));
+        self
+    }
+
+    /// Set the transformers of the columns that are read, replacing those that were added
+    /// with [with_column_transformer](Self::with_column_transformer).
+    pub fn with_column_transformers(mut self, column_transformers: ColumnTransformers) -> Self {
+        self.column_transformers = column_transformers;
+        self
+    }
+
     pub fn schema(self) -> Result<Schema> {
         let file_reader = Arc::new(SerializedFileReader::new(self.reader)?);
+        let categorical_columns =
+            categorical_columns(file_reader.metadata(), self.dictionary_as_categorical);
+        let null_columns = parquet_null_columns(file_reader.metadata());
         let mut arrow_reader = ParquetFileArrowReader::new(file_reader);
-        let schema = arrow_reader.get_schema()?;
-        Ok(schema.into())
+        let schema: Schema = arrow_reader.get_schema()?.into();
+        let fields = schema
+            .fields()
+            .iter()
+            .map(|field| match field.data_type() {
+                DataType::Utf8 if categorical_columns.contains(field.name()) => Field::new(
+                    field.name(),
+                    DataType::Categorical(CategoricalOrdering::Physical),
+                )
+                .with_metadata(field.metadata().clone()),
+                _ => field.clone(),
+            })
+            .collect();
+        Ok(restore_null_fields(Schema::new(fields), &null_columns))
+    }
+
+    /// Get the number of rows in the file from its metadata, without reading any data.
+    pub fn num_rows(self) -> Result<usize> {
+        let file_reader = SerializedFileReader::new(self.reader)?;
+        Ok(file_reader.metadata().file_metadata().num_rows() as usize)
+    }
+}
+
+/// Reads a parquet file in DataFrames of a fixed number of rows. Created by
+/// [ParquetReader::batched].
+pub struct BatchedParquetReader {
+    reader: ParquetRecordBatchReader,
+    categorical_columns: Vec<String>,
+    null_columns: Vec<String>,
+    column_transformers: ColumnTransformers,
+    rows_left: Option<usize>,
+}
+
+impl BatchedParquetReader {
+    fn batch_to_df(&mut self, batch: RecordBatch) -> Result<DataFrame> {
+        let mut df = DataFrame::try_from(batch)?;
+        if let Some(rows_left) = &mut self.rows_left {
+            if df.height() > *rows_left {
+                df = df.slice(0, *rows_left)?;
+            }
+            *rows_left -= df.height();
+        }
+        for name in &self.categorical_columns {
+            if let Some(idx) = df.find_idx_by_name(name) {
+                let s = df.select_at_idx(idx).unwrap();
+                if !matches!(s.dtype(), DataType::Categorical(_)) {
+                    let s = s.cast::<CategoricalType>()?;
+                    df.replace_at_idx(idx, s)?;
+                }
+            }
+        }
+        restore_null_columns(&mut df, &self.null_columns)?;
+        self.column_transformers.apply(&mut df)?;
+        Ok(df)
+    }
+}
+
+impl Iterator for BatchedParquetReader {
+    type Item = Result<DataFrame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rows_left == Some(0) {
+            return None;
+        }
+        match self.reader.next()? {
+            Ok(batch) => Some(self.batch_to_df(batch)),
+            Err(e) => Some(Err(e.into())),
+        }
     }
 }
 
@@ -129,6 +336,8 @@ where
             reader,
             rechunk: false,
             stop_after_n_rows: None,
+            dictionary_as_categorical: false,
+            column_transformers: ColumnTransformers::default(),
         }
     }
 
@@ -141,16 +350,39 @@ where
         let rechunk = self.rechunk;
         let file_reader = Arc::new(SerializedFileReader::new(self.reader)?);
         let n_rows = file_reader.metadata().file_metadata().num_rows() as usize;
-        let batch_size = set_batch_size(n_rows, self.stop_after_n_rows);
+        let categorical_columns =
+            categorical_columns(file_reader.metadata(), self.dictionary_as_categorical);
+        let null_columns = parquet_null_columns(file_reader.metadata());
+        // read in batches, so that only a batch of strings is in memory at a time
+        let max_rows = if categorical_columns.is_empty() {
+            n_rows
+        } else {
+            512 * 1024
+        };
         let mut arrow_reader = ParquetFileArrowReader::new(file_reader);
+        let max_rows = target_batch_size(max_rows, &mut arrow_reader)?;
+        let batch_size = set_batch_size(max_rows, self.stop_after_n_rows);
         let record_reader = arrow_reader.get_record_reader(batch_size)?;
-        finish_reader(record_reader, rechunk, self.stop_after_n_rows, None, None)
+        let mut df = finish_reader(
+            record_reader,
+            rechunk,
+            self.stop_after_n_rows,
+            None,
+            None,
+            Some(&categorical_columns),
+            None,
+            None,
+        )?;
+        restore_null_columns(&mut df, &null_columns)?;
+        self.column_transformers.apply(&mut df)?;
+        Ok(df)
     }
 }
 
 /// Write a DataFrame to parquet format
 pub struct ParquetWriter<W> {
     writer: W,
+    column_transformers: ColumnTransformers,
 }
 
 impl<W> ParquetWriter<W>
@@ -162,20 +394,119 @@ where
     where
         W: 'static + Write + Seek + TryClone,
     {
-        ParquetWriter { writer }
+        ParquetWriter {
+            writer,
+            column_transformers: ColumnTransformers::default(),
+        }
+    }
+
+    /// Transform the column `name` before it is written, e.g. to tokenize it. The DataFrame
+    /// that is passed to `finish` isn't modified.
+    pub fn with_column_transformer<T>(mut self, name: &str, transformer: T) -> Self
+    where
+        T: ColumnTransformer + 'static,
+    {
+        self.column_transformers.push(name, Arc::new(transformer));
+        self
+    }
+
+    /// Set the transformers of the columns that are written, replacing those that were added
+    /// with [with_column_transformer](Self::with_column_transformer).
+    pub fn with_column_transformers(mut self, column_transformers: ColumnTransformers) -> Self {
+        self.column_transformers = column_transformers;
+        self
     }
 
     /// Write the given DataFrame in the the writer `W`.
     pub fn finish(self, df: &mut DataFrame) -> Result<()> {
-        let mut parquet_writer =
-            ParquetArrowWriter::try_new(self.writer, Arc::new(df.schema().to_arrow()), None)?;
+        let mut writer = self.batched();
+        writer.write_batch(df)?;
+        writer.finish()
+    }
+
+    /// Write DataFrames with the same schema one after the other, e.g. the batches of a query
+    /// that doesn't fit in memory. Every DataFrame is written as a row group.
+    pub fn batched(self) -> BatchedParquetWriter<W> {
+        BatchedParquetWriter {
+            writer: Some(self.writer),
+            parquet_writer: None,
+            column_transformers: self.column_transformers,
+        }
+    }
+}
+
+/// Writes DataFrames to a parquet file batch by batch. Created by [ParquetWriter::batched].
+pub struct BatchedParquetWriter<W: 'static + Write + Seek + TryClone> {
+    writer: Option<W>,
+    /// Created from the schema of the first batch.
+    parquet_writer: Option<ParquetArrowWriter<W>>,
+    column_transformers: ColumnTransformers,
+}
+
+impl<W> BatchedParquetWriter<W>
+where
+    W: 'static + Write + Seek + TryClone,
+{
+    /// Write a DataFrame. It must have the same schema as the DataFrames written before.
+    pub fn write_batch(&mut self, df: &mut DataFrame) -> Result<()> {
+        let mut transformed = self.column_transformers.transformed(df)?;
+        let df = transformed.as_mut().unwrap_or(df);
+        // the categories are written as strings, the parquet writer encodes them in a dictionary.
+        // Null columns are written as booleans.
+        let mut key_values = vec![];
+        let mut columns = df.get_columns().clone();
+        for s in columns.iter_mut() {
+            match s.dtype() {
+                DataType::Categorical(_) => {
+                    key_values.push(KeyValue {
+                        key: format!("{}{}", CATEGORICAL_KEY_PREFIX, s.name()),
+                        value: None,
+                    });
+                    *s = s.cast::<Utf8Type>()?;
+                }
+                DataType::Null => {
+                    key_values.push(KeyValue {
+                        key: format!("{}{}", NULL_KEY_PREFIX, s.name()),
+                        value: None,
+                    });
+                    *s = s.cast_with_datatype(&DataType::Boolean)?;
+                }
+                _ => {}
+            }
+        }
+        let mut written;
+        let df = if key_values.is_empty() {
+            df
+        } else {
+            written = DataFrame::new_no_checks(columns);
+            &mut written
+        };
+
+        if self.parquet_writer.is_none() {
+            let props = WriterProperties::builder()
+                .set_key_value_metadata(Some(key_values))
+                .build();
+            self.parquet_writer = Some(ParquetArrowWriter::try_new(
+                self.writer.take().unwrap(),
+                Arc::new(df.schema().to_arrow()),
+                Some(props),
+            )?);
+        }
+        let parquet_writer = self.parquet_writer.as_mut().unwrap();
 
         let iter = df.iter_record_batches(df.height());
 
         for batch in iter {
             parquet_writer.write(&batch)?
         }
-        let _ = parquet_writer.close()?;
+        Ok(())
+    }
+
+    /// Write the metadata of the file. Nothing is written if no batch was written.
+    pub fn finish(self) -> Result<()> {
+        if let Some(mut parquet_writer) = self.parquet_writer {
+            let _ = parquet_writer.close()?;
+        }
         Ok(())
     }
 }
@@ -183,6 +514,8 @@ where
 #[cfg(test)]
 mod test {
     use crate::prelude::*;
+    use polars_core::prelude::*;
+    use polars_core::utils::accumulate_dataframes_vertical;
     use std::fs::File;
 
     #[test]
@@ -195,4 +528,71 @@ mod test {
             assert_eq!(df.shape(), (3, 2));
         }
     }
+
+    #[test]
+    fn test_parquet_categorical_round_trip() -> Result<()> {
+        let path = std::env::temp_dir().join("polars_categorical_round_trip.parquet");
+        let fruit = Series::new("fruit", &[Some("apple"), None, Some("pear"), Some("apple")])
+            .cast::<CategoricalType>()?;
+        let mut df = DataFrame::new(vec![fruit, Series::new("n", &[1, 2, 3, 4])])?;
+        ParquetWriter::new(File::create(&path)?).finish(&mut df)?;
+
+        let schema = ParquetReader::new(File::open(&path)?).schema()?;
+        assert_eq!(
+            schema.field_with_name("fruit")?.data_type(),
+            &DataType::Categorical(CategoricalOrdering::Physical)
+        );
+        let df_read = ParquetReader::new(File::open(&path)?).finish()?;
+        let fruit = df_read.column("fruit")?;
+        assert_eq!(
+            fruit.dtype(),
+            &DataType::Categorical(CategoricalOrdering::Physical)
+        );
+        assert_eq!(
+            Vec::from(fruit.cast::<Utf8Type>()?.utf8()?),
+            &[Some("apple"), None, Some("pear"), Some("apple")]
+        );
+        assert_eq!(df_read.column("n")?.dtype(), &DataType::Int32);
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_parquet_null_round_trip() -> Result<()> {
+        let path = std::env::temp_dir().join("polars_null_round_trip.parquet");
+        let mut df = DataFrame::new(vec![
+            Series::full_null("a", 3, &DataType::Null)?,
+            Series::new("n", &[1, 2, 3]),
+        ])?;
+        ParquetWriter::new(File::create(&path)?).finish(&mut df)?;
+
+        let schema = ParquetReader::new(File::open(&path)?).schema()?;
+        assert_eq!(schema.field_with_name("a")?.data_type(), &DataType::Null);
+        let df_read = ParquetReader::new(File::open(&path)?).finish()?;
+        assert_eq!(df_read.column("a")?.dtype(), &DataType::Null);
+        assert_eq!(df_read.column("a")?.len(), 3);
+        assert!(df_read.column("n")?.series_equal(df.column("n")?));
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_parquet_batched() -> Result<()> {
+        let path = std::env::temp_dir().join("polars_parquet_batched.parquet");
+        let df = create_df();
+        let mut writer = ParquetWriter::new(File::create(&path)?).batched();
+        writer.write_batch(&mut df.slice(0, 2)?)?;
+        writer.write_batch(&mut df.slice(2, 3)?)?;
+        writer.finish()?;
+
+        let batches = ParquetReader::new(File::open(&path)?)
+            .with_stop_after_n_rows(Some(4))
+            .batched(3, Some(&[1]))?
+            .collect::<Result<Vec<_>>>()?;
+        assert!(batches.iter().all(|batch| batch.height() <= 3));
+        let df_read = accumulate_dataframes_vertical(batches)?;
+        assert!(df_read.frame_equal(&df.select("temp")?.slice(0, 4)?));
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
 }
@@ -0,0 +1,254 @@
+//! A min/max index on the temporal columns of the batches of a scan.
+//!
+//! Sources like CSV and IPC files don't store statistics. The index is built by the first scan of
+//! a file and kept in a [StatsIndex] for the next scans. A predicate on a time column consults it
+//! before a batch is parsed or decoded, and skips the batches that can't contain any matching row.
+//! The index also records whether a time column is sorted, which lets scans flag the column as
+//! sorted for asof and range joins.
+use crate::PhysicalIoExpr;
+use lazy_static::lazy_static;
+use polars_core::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use std::time::SystemTime;
+
+const MILLISECONDS_IN_DAY: i64 = 86_400_000;
+
+/// Get the values of a Date32 or Date64 Series as milliseconds since the epoch.
+/// Returns `None` for other data types.
+pub fn to_milliseconds(s: &Series) -> Result<Option<Int64Chunked>> {
+    let ms = match s.dtype() {
+        DataType::Date32 => s
+            .cast::<Int64Type>()?
+            .i64()?
+            .apply(|days| days * MILLISECONDS_IN_DAY),
+        DataType::Date64 => s.cast::<Int64Type>()?.i64()?.clone(),
+        _ => return Ok(None),
+    };
+    Ok(Some(ms))
+}
+
+/// The min and max of a temporal column in a batch, in milliseconds since the epoch.
+#[derive(Debug, Clone)]
+pub struct ColumnStats {
+    name: String,
+    min: Option<i64>,
+    max: Option<i64>,
+    /// The column has no nulls and its values are in ascending order.
+    sorted: bool,
+}
+
+impl ColumnStats {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.min
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.max
+    }
+
+    pub fn is_sorted(&self) -> bool {
+        self.sorted
+    }
+}
+
+/// The min/max index of the temporal columns of a single batch.
+#[derive(Debug, Clone, Default)]
+pub struct BatchStats {
+    columns: Vec<ColumnStats>,
+    height: usize,
+}
+
+impl BatchStats {
+    /// Build the index of the Date32 and Date64 columns of `df`.
+    pub fn new(df: &DataFrame) -> Result<Self> {
+        let mut columns = vec![];
+        for s in df.get_columns() {
+            if let Some(ms) = to_milliseconds(s)? {
+                let mut prev = i64::MIN;
+                let sorted = ms.null_count() == 0
+                    && ms.into_no_null_iter().all(|v| {
+                        let in_order = prev <= v;
+                        prev = v;
+                        in_order
+                    });
+                columns.push(ColumnStats {
+                    name: s.name().to_string(),
+                    min: ms.min(),
+                    max: ms.max(),
+                    sorted,
+                })
+            }
+        }
+        Ok(BatchStats {
+            columns,
+            height: df.height(),
+        })
+    }
+
+    /// Get the statistics of the column `name`, if it is indexed.
+    pub fn get(&self, name: &str) -> Option<&ColumnStats> {
+        self.columns.iter().find(|stats| stats.name == name)
+    }
+
+    /// The number of rows of the batch.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+}
+
+/// The length and modification time of a file, to detect that an index is outdated.
+type FileVersion = (u64, Option<SystemTime>);
+
+lazy_static! {
+    static ref FILE_INDEXES: Mutex<HashMap<String, (FileVersion, Arc<StatsIndex>)>> =
+        Mutex::new(HashMap::new());
+}
+
+/// The [BatchStats] of the batches of a file, by the position at which a batch starts. The
+/// position is a byte offset for CSV files and the number of the record batch for IPC files.
+#[derive(Default)]
+pub struct StatsIndex {
+    batches: RwLock<HashMap<usize, (usize, BatchStats)>>,
+}
+
+impl StatsIndex {
+    /// Get the index of the file at `path`, which is shared by all scans of the file in this
+    /// process. A new index is started when the file is modified.
+    pub fn of_file(path: &str) -> Arc<StatsIndex> {
+        let version = match std::fs::metadata(path) {
+            Ok(metadata) => (metadata.len(), metadata.modified().ok()),
+            // not persisted
+            Err(_) => return Arc::new(StatsIndex::default()),
+        };
+        let mut indexes = FILE_INDEXES.lock().unwrap();
+        match indexes.get(path) {
+            Some((v, index)) if *v == version => index.clone(),
+            _ => {
+                let index = Arc::new(StatsIndex::default());
+                indexes.insert(path.to_string(), (version, index.clone()));
+                index
+            }
+        }
+    }
+
+    /// Get the position after the batch that starts at `start`, and its statistics.
+    pub fn get(&self, start: usize) -> Option<(usize, BatchStats)> {
+        self.batches.read().unwrap().get(&start).cloned()
+    }
+
+    /// Add the statistics of the batch that spans the positions `start..end`.
+    pub fn insert(&self, start: usize, end: usize, stats: BatchStats) {
+        self.batches.write().unwrap().insert(start, (end, stats));
+    }
+}
+
+/// Flag the temporal columns of `df` as sorted if the index shows that they are sorted within and
+/// across its batches, which are in the order of the rows of `df`. Filtering the batches keeps
+/// them sorted.
+pub(crate) fn flag_sorted_columns(df: &mut DataFrame, batches: &[BatchStats]) -> Result<()> {
+    let first = match batches.first() {
+        Some(first) => first,
+        None => return Ok(()),
+    };
+    for stats in &first.columns {
+        let name = &stats.name;
+        let mut prev_max = None;
+        let sorted = batches.iter().all(|batch| match batch.get(name) {
+            Some(stats) if stats.sorted => {
+                // an empty batch has no min and max
+                let in_order = stats.min.is_none() || prev_max <= stats.min;
+                if stats.max.is_some() {
+                    prev_max = stats.max;
+                }
+                in_order
+            }
+            _ => false,
+        });
+        if sorted {
+            if let Some(idx) = df.find_idx_by_name(name) {
+                let mut s = df.get_columns()[idx].clone();
+                s.set_sorted_flag(IsSorted::Ascending);
+                df.replace_at_idx(idx, s)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A predicate that can use the [BatchStats] of a batch to decide whether it has to be evaluated.
+pub trait StatsEvaluator {
+    /// Returns `false` only if no row of the batch can match the predicate.
+    fn should_read(&self, stats: &BatchStats) -> Result<bool>;
+}
+
+/// Whether a batch with `stats` can contain rows that match the predicate.
+pub(crate) fn should_read(predicate: &dyn PhysicalIoExpr, stats: &BatchStats) -> Result<bool> {
+    match predicate.as_stats_evaluator() {
+        Some(evaluator) => evaluator.should_read(stats),
+        None => Ok(true),
+    }
+}
+
+/// Filter a batch with the predicate.
+pub(crate) fn apply_predicate(df: DataFrame, predicate: &dyn PhysicalIoExpr) -> Result<DataFrame> {
+    let s = predicate.evaluate(&df)?;
+    let mask = s.bool().expect("filter predicates was not of type boolean");
+    df.filter(mask)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_batch_stats() -> Result<()> {
+        let day = Series::new("day", &[Some(3), None, Some(-1)]).cast::<Date32Type>()?;
+        let ts = Series::new("ts", &[5i64, 2]).cast::<Date64Type>()?;
+        let value = Series::new("value", &[1, 2, 3]);
+        let df = DataFrame::new(vec![day, value])?;
+
+        let stats = BatchStats::new(&df)?;
+        let day = stats.get("day").unwrap();
+        assert_eq!(day.min(), Some(-MILLISECONDS_IN_DAY));
+        assert_eq!(day.max(), Some(3 * MILLISECONDS_IN_DAY));
+        // only temporal columns are indexed
+        assert!(stats.get("value").is_none());
+
+        let stats = BatchStats::new(&DataFrame::new(vec![ts])?)?;
+        assert_eq!(stats.get("ts").unwrap().min(), Some(2));
+        assert_eq!(stats.get("ts").unwrap().max(), Some(5));
+        assert!(!stats.get("ts").unwrap().is_sorted());
+        Ok(())
+    }
+
+    #[test]
+    fn test_flag_sorted_columns() -> Result<()> {
+        let batch = |days: &[i32]| -> Result<DataFrame> {
+            let day = Series::new("day", days).cast::<Date32Type>()?;
+            DataFrame::new(vec![day])
+        };
+        let batches = vec![batch(&[1, 2])?, batch(&[])?, batch(&[2, 5])?];
+        let stats = batches
+            .iter()
+            .map(BatchStats::new)
+            .collect::<Result<Vec<_>>>()?;
+        let mut df = batch(&[1, 2, 2, 5])?;
+        flag_sorted_columns(&mut df, &stats)?;
+        assert_eq!(df.column("day")?.sorted_flag(), IsSorted::Ascending);
+
+        // the batches overlap
+        let stats = vec![
+            BatchStats::new(&batch(&[1, 3])?)?,
+            BatchStats::new(&batch(&[2])?)?,
+        ];
+        let mut df = batch(&[1, 3, 2])?;
+        flag_sorted_columns(&mut df, &stats)?;
+        assert_eq!(df.column("day")?.sorted_flag(), IsSorted::Not);
+        Ok(())
+    }
+}
@@ -1,4 +1,8 @@
-pub use crate::{csv::*, SerReader, SerWriter};
+pub use crate::{
+    csv::*,
+    transform::{ColumnTransformer, ColumnTransformers},
+    CountingReader, SerReader, SerWriter,
+};
 
 #[cfg(feature = "ipc")]
 pub use crate::ipc::*;
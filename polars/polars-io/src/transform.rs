@@ -0,0 +1,78 @@
+//! Column transformers that are applied by the readers and writers, e.g. to decrypt or mask
+//! columns with sensitive data while reading and to tokenize them while writing.
+use polars_core::prelude::*;
+use std::fmt;
+use std::sync::Arc;
+
+/// Transform the values of a column. The output must have the same length as the input.
+pub trait ColumnTransformer: Send + Sync {
+    fn transform(&self, s: Series) -> Result<Series>;
+}
+
+impl<F> ColumnTransformer for F
+where
+    F: Fn(Series) -> Result<Series> + Send + Sync,
+{
+    fn transform(&self, s: Series) -> Result<Series> {
+        self(s)
+    }
+}
+
+/// The transformers of a reader or writer, by column name.
+#[derive(Clone, Default)]
+pub struct ColumnTransformers {
+    transformers: Vec<(String, Arc<dyn ColumnTransformer>)>,
+}
+
+impl fmt::Debug for ColumnTransformers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.transformers.iter().map(|(name, _)| name))
+            .finish()
+    }
+}
+
+impl ColumnTransformers {
+    /// Transform the column `name`. Used to pass transformers to the scans and sinks of a
+    /// lazy query.
+    pub fn with_transformer<T>(mut self, name: &str, transformer: T) -> Self
+    where
+        T: ColumnTransformer + 'static,
+    {
+        self.push(name, Arc::new(transformer));
+        self
+    }
+
+    pub(crate) fn push(&mut self, name: &str, transformer: Arc<dyn ColumnTransformer>) {
+        self.transformers.push((name.to_string(), transformer))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transformers.is_empty()
+    }
+
+    /// Transform the columns of `df` in place. The transformed columns keep their name.
+    /// Columns that are not in `df`, e.g. because they were not projected, are skipped.
+    pub fn apply(&self, df: &mut DataFrame) -> Result<()> {
+        for (name, transformer) in &self.transformers {
+            if let Some(idx) = df.find_idx_by_name(name) {
+                let s = df.select_at_idx(idx).unwrap().clone();
+                let mut out = transformer.transform(s)?;
+                out.rename(name);
+                df.replace_at_idx(idx, out)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Get a copy of `df` with its columns transformed, or `None` if there are no
+    /// transformers. Used by the writers, which must not modify the DataFrame they write.
+    pub(crate) fn transformed(&self, df: &DataFrame) -> Result<Option<DataFrame>> {
+        if self.is_empty() {
+            return Ok(None);
+        }
+        let mut df = df.clone();
+        self.apply(&mut df)?;
+        Ok(Some(df))
+    }
+}
@@ -113,12 +113,14 @@ pub enum AggExpr {
     Max(Box<Expr>),
     Median(Box<Expr>),
     NUnique(Box<Expr>),
+    NullCount(Box<Expr>),
     First(Box<Expr>),
     Last(Box<Expr>),
     Mean(Box<Expr>),
     List(Box<Expr>),
     Count(Box<Expr>),
     Quantile { expr: Box<Expr>, quantile: f64 },
+    TopK { expr: Box<Expr>, k: usize },
     Sum(Box<Expr>),
     AggGroups(Box<Expr>),
     Std(Box<Expr>),
@@ -133,12 +135,14 @@ impl AsRef<Expr> for AggExpr {
             Max(e) => e,
             Median(e) => e,
             NUnique(e) => e,
+            NullCount(e) => e,
             First(e) => e,
             Last(e) => e,
             Mean(e) => e,
             List(e) => e,
             Count(e) => e,
             Quantile { expr, .. } => expr,
+            TopK { expr, .. } => expr,
             Sum(e) => e,
             AggGroups(e) => e,
             Std(e) => e,
@@ -147,6 +151,19 @@ impl AsRef<Expr> for AggExpr {
     }
 }
 
+/// A string is a column name: `"foo".into()` is `col("foo")`.
+impl From<&str> for Expr {
+    fn from(name: &str) -> Self {
+        col(name)
+    }
+}
+
+impl From<&Expr> for Expr {
+    fn from(expr: &Expr) -> Self {
+        expr.clone()
+    }
+}
+
 impl From<AggExpr> for Expr {
     fn from(agg: AggExpr) -> Self {
         Expr::Agg(agg)
@@ -192,6 +209,14 @@ pub enum Expr {
         periods: i64,
     },
     Reverse(Box<Expr>),
+    CumCount(Box<Expr>),
+    Standardize(Box<Expr>),
+    MinMaxScale(Box<Expr>),
+    ClipQuantile {
+        input: Box<Expr>,
+        low_q: f64,
+        high_q: f64,
+    },
     Duplicated(Box<Expr>),
     Unique(Box<Expr>),
     Explode(Box<Expr>),
@@ -241,8 +266,16 @@ impl Expr {
                 Ok(Field::new(field.name(), DataType::Boolean))
             }
             Reverse(expr) => expr.to_field(&schema, ctxt),
+            CumCount(expr) => {
+                let field = expr.to_field(&schema, ctxt)?;
+                Ok(Field::new(field.name(), IdxType::get_dtype()))
+            }
+            Standardize(expr) | MinMaxScale(expr) => {
+                let field = expr.to_field(&schema, ctxt)?;
+                Ok(Field::new(field.name(), DataType::Float64))
+            }
             Explode(expr) => expr.to_field(&schema, ctxt),
-            Alias(expr, name) => Ok(Field::new(name, expr.get_type(schema, ctxt)?)),
+            Alias(expr, name) => Ok(rename_field(&expr.to_field(schema, ctxt)?, name)),
             Column(name) => {
                 let field = schema.field_with_name(name).map(|f| f.clone())?;
                 Ok(field)
@@ -288,73 +321,67 @@ impl Expr {
                 use AggExpr::*;
                 let field = match agg {
                     Min(expr) => {
-                        field_by_context(expr.to_field(schema, ctxt)?, ctxt, GroupByMethod::Min)
+                        field_by_context(expr.to_field(schema, ctxt)?, ctxt, GroupByMethod::Min)?
                     }
                     Max(expr) => {
-                        field_by_context(expr.to_field(schema, ctxt)?, ctxt, GroupByMethod::Max)
+                        field_by_context(expr.to_field(schema, ctxt)?, ctxt, GroupByMethod::Max)?
                     }
                     Median(expr) => {
-                        field_by_context(expr.to_field(schema, ctxt)?, ctxt, GroupByMethod::Median)
+                        field_by_context(expr.to_field(schema, ctxt)?, ctxt, GroupByMethod::Median)?
                     }
                     Mean(expr) => {
-                        field_by_context(expr.to_field(schema, ctxt)?, ctxt, GroupByMethod::Mean)
+                        field_by_context(expr.to_field(schema, ctxt)?, ctxt, GroupByMethod::Mean)?
                     }
                     First(expr) => {
-                        field_by_context(expr.to_field(schema, ctxt)?, ctxt, GroupByMethod::First)
+                        field_by_context(expr.to_field(schema, ctxt)?, ctxt, GroupByMethod::First)?
                     }
                     Last(expr) => {
-                        field_by_context(expr.to_field(schema, ctxt)?, ctxt, GroupByMethod::Last)
+                        field_by_context(expr.to_field(schema, ctxt)?, ctxt, GroupByMethod::Last)?
                     }
                     List(expr) => {
-                        field_by_context(expr.to_field(schema, ctxt)?, ctxt, GroupByMethod::List)
-                    }
-                    NUnique(expr) => {
-                        let field = expr.to_field(schema, ctxt)?;
-                        let field = Field::new(field.name(), DataType::UInt32);
-                        match ctxt {
-                            Context::Other => field,
-                            Context::Aggregation => {
-                                let new_name =
-                                    fmt_groupby_column(field.name(), GroupByMethod::NUnique);
-                                rename_field(&field, &new_name)
-                            }
-                        }
+                        field_by_context(expr.to_field(schema, ctxt)?, ctxt, GroupByMethod::List)?
                     }
+                    NUnique(expr) => field_by_context(
+                        expr.to_field(schema, ctxt)?,
+                        ctxt,
+                        GroupByMethod::NUnique,
+                    )?,
+                    NullCount(expr) => field_by_context(
+                        expr.to_field(schema, ctxt)?,
+                        ctxt,
+                        GroupByMethod::NullCount,
+                    )?,
                     Sum(expr) => {
-                        field_by_context(expr.to_field(schema, ctxt)?, ctxt, GroupByMethod::Sum)
+                        field_by_context(expr.to_field(schema, ctxt)?, ctxt, GroupByMethod::Sum)?
                     }
                     Std(expr) => {
-                        let field = expr.to_field(schema, ctxt)?;
-                        let field = Field::new(field.name(), DataType::Float64);
-                        field_by_context(field, ctxt, GroupByMethod::Std)
+                        field_by_context(expr.to_field(schema, ctxt)?, ctxt, GroupByMethod::Std)?
                     }
                     Var(expr) => {
-                        let field = expr.to_field(schema, ctxt)?;
-                        let field = Field::new(field.name(), DataType::Float64);
-                        field_by_context(field, ctxt, GroupByMethod::Var)
+                        field_by_context(expr.to_field(schema, ctxt)?, ctxt, GroupByMethod::Var)?
                     }
                     Count(expr) => {
-                        let field = expr.to_field(schema, ctxt)?;
-                        let field = Field::new(field.name(), DataType::UInt32);
-                        match ctxt {
-                            Context::Other => field,
-                            Context::Aggregation => {
-                                let new_name =
-                                    fmt_groupby_column(field.name(), GroupByMethod::Count);
-                                rename_field(&field, &new_name)
-                            }
-                        }
+                        field_by_context(expr.to_field(schema, ctxt)?, ctxt, GroupByMethod::Count)?
                     }
                     AggGroups(expr) => {
                         let field = expr.to_field(schema, ctxt)?;
                         let new_name = fmt_groupby_column(field.name(), GroupByMethod::Groups);
-                        Field::new(&new_name, DataType::List(ArrowDataType::UInt32))
+                        Field::new(&new_name, DataType::List(IdxType::get_dtype().to_arrow()))
                     }
                     Quantile { expr, quantile } => field_by_context(
                         expr.to_field(schema, ctxt)?,
                         ctxt,
                         GroupByMethod::Quantile(*quantile),
-                    ),
+                    )?,
+                    TopK { expr, k } => {
+                        let field = expr.to_field(schema, ctxt)?;
+                        match ctxt {
+                            Context::Other => field,
+                            Context::Aggregation => {
+                                field_by_context(field, ctxt, GroupByMethod::TopK(*k))?
+                            }
+                        }
+                    }
                 };
                 Ok(field)
             }
@@ -392,9 +419,14 @@ impl Expr {
                     }))
             }
             Shift { input, .. } => input.to_field(schema, ctxt),
+            ClipQuantile { input, .. } => input.to_field(schema, ctxt),
             Slice { input, .. } => input.to_field(schema, ctxt),
-            Wildcard => panic!("should be no wildcard at this point"),
-            Except(_) => panic!("should be no except at this point"),
+            Wildcard => Err(PolarsError::InvalidOperation(
+                "a wildcard can only be used in a projection or an aggregation".into(),
+            )),
+            Except(_) => Err(PolarsError::InvalidOperation(
+                "an exception can only be used in a projection or an aggregation".into(),
+            )),
         }
     }
 }
@@ -416,6 +448,9 @@ impl fmt::Debug for Expr {
             Explode(expr) => write!(f, "EXPLODE {:?}", expr),
             Duplicated(expr) => write!(f, "DUPLICATED {:?}", expr),
             Reverse(expr) => write!(f, "REVERSE {:?}", expr),
+            CumCount(expr) => write!(f, "CUMCOUNT {:?}", expr),
+            Standardize(expr) => write!(f, "STANDARDIZE {:?}", expr),
+            MinMaxScale(expr) => write!(f, "MIN_MAX_SCALE {:?}", expr),
             Alias(expr, name) => write!(f, "{:?} AS {}", expr, name),
             Column(name) => write!(f, "{}", name),
             Literal(v) => write!(f, "{:?}", v),
@@ -438,12 +473,14 @@ impl fmt::Debug for Expr {
                     Last(expr) => write!(f, "AGG LAST {:?}", expr),
                     List(expr) => write!(f, "AGG LIST {:?}", expr),
                     NUnique(expr) => write!(f, "AGG N UNIQUE {:?}", expr),
+                    NullCount(expr) => write!(f, "AGG NULL COUNT {:?}", expr),
                     Sum(expr) => write!(f, "AGG SUM {:?}", expr),
                     AggGroups(expr) => write!(f, "AGG GROUPS {:?}", expr),
                     Count(expr) => write!(f, "AGG COUNT {:?}", expr),
                     Var(expr) => write!(f, "AGG VAR {:?}", expr),
                     Std(expr) => write!(f, "AGG STD {:?}", expr),
                     Quantile { expr, .. } => write!(f, "AGG QUANTILE {:?}", expr),
+                    TopK { expr, k } => write!(f, "AGG TOP_K {} {:?}", k, expr),
                 }
             }
             Cast { expr, data_type } => write!(f, "CAST {:?} TO {:?}", expr, data_type),
@@ -461,6 +498,11 @@ impl fmt::Debug for Expr {
                 input_a, input_b, ..
             } => write!(f, "BinaryFunction({:?}, {:?})", input_a, input_b),
             Shift { input, periods, .. } => write!(f, "SHIFT {:?} by {}", input, periods),
+            ClipQuantile {
+                input,
+                low_q,
+                high_q,
+            } => write!(f, "CLIP_QUANTILE {:?} to [{}, {}]", input, low_q, high_q),
             Slice {
                 input,
                 offset,
@@ -489,10 +531,8 @@ impl fmt::Debug for Expr {
 /// }
 /// ```
 pub fn except(name: &str) -> Expr {
-    match name {
-        "*" => panic!("cannot use a wildcard as a column exception"),
-        _ => Expr::Except(Box::new(col(name))),
-    }
+    // a wildcard exception is an error when the projection is built
+    Expr::Except(Box::new(col(name)))
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -648,6 +688,12 @@ impl Expr {
         AggExpr::NUnique(Box::new(self)).into()
     }
 
+    /// Get the number of null values in the groups. The nulls are counted on the validity
+    /// bitmap. Use `col("*").null_count()` to count the nulls of every column.
+    pub fn null_count(self) -> Self {
+        AggExpr::NullCount(Box::new(self)).into()
+    }
+
     /// Get the first value in the group.
     pub fn first(self) -> Self {
         AggExpr::First(Box::new(self)).into()
@@ -672,6 +718,16 @@ impl Expr {
         .into()
     }
 
+    /// Get the `k` largest values, largest first. In a groupby context these are the `k` largest
+    /// values of every group, aggregated to a list.
+    pub fn top_k(self, k: usize) -> Self {
+        AggExpr::TopK {
+            expr: Box::new(self),
+            k,
+        }
+        .into()
+    }
+
     /// Get the group indexes of the group by operation.
     pub fn agg_groups(self) -> Self {
         AggExpr::AggGroups(Box::new(self)).into()
@@ -723,6 +779,75 @@ impl Expr {
         Expr::Reverse(Box::new(self))
     }
 
+    /// Get the 0-based running index of every row, in row order.
+    /// Combined with [over](Expr::over) this numbers the rows within every partition.
+    ///
+    /// ```rust
+    /// use polars_lazy::prelude::*;
+    ///
+    /// // rank the events of every user
+    /// fn number_events(events: LazyFrame) -> LazyFrame {
+    ///     events.with_column(col("time").cumcount().over(col("user")).alias("event_nr"))
+    /// }
+    /// ```
+    pub fn cumcount(self) -> Self {
+        Expr::CumCount(Box::new(self))
+    }
+
+    /// Clip the values to the `low_q` and `high_q` quantiles (winsorize). Combined with
+    /// [over](Expr::over) the quantiles are computed per partition. In a groupby aggregation
+    /// every group is clipped on its own quantiles, which gives a list per group.
+    ///
+    /// ```rust
+    /// use polars_lazy::prelude::*;
+    ///
+    /// fn winsorize_prices(df: LazyFrame) -> LazyFrame {
+    ///     df.with_column(
+    ///         col("price")
+    ///             .clip_quantile(0.05, 0.95)
+    ///             .over(col("product"))
+    ///             .alias("price"),
+    ///     )
+    /// }
+    /// ```
+    pub fn clip_quantile(self, low_q: f64, high_q: f64) -> Self {
+        Expr::ClipQuantile {
+            input: Box::new(self),
+            low_q,
+            high_q,
+        }
+    }
+
+    /// Standardize the values to their z-scores: subtract the mean and divide by the
+    /// standard deviation. The output is `Float64`. Combined with [over](Expr::over) the mean
+    /// and standard deviation are computed per partition.
+    ///
+    /// ```rust
+    /// use polars_lazy::prelude::*;
+    ///
+    /// fn standardize_scores(df: LazyFrame) -> LazyFrame {
+    ///     df.with_column(col("score").standardize().over(col("exam")).alias("z"))
+    /// }
+    /// ```
+    pub fn standardize(self) -> Self {
+        Expr::Standardize(Box::new(self))
+    }
+
+    /// Scale the values to the range [0.0, 1.0] with the minimum and maximum of the values.
+    /// The output is `Float64`. Combined with [over](Expr::over) the minimum and maximum are
+    /// computed per partition.
+    pub fn min_max_scale(self) -> Self {
+        Expr::MinMaxScale(Box::new(self))
+    }
+
+    /// Hash the values with a fixed `seed`. See [the eager implementation](polars_core::series::Series::hash).
+    pub fn hash(self, seed: u64) -> Self {
+        self.map(
+            move |s: Series| s.hash(seed).map(|ca| ca.into_series()),
+            Some(DataType::UInt64),
+        )
+    }
+
     /// Apply a function/closure once the logical plan get executed.
     /// It is the responsibility of the caller that the schema is correct by giving
     /// the correct output_type. If None given the output type of the input expr is used.
@@ -865,11 +990,15 @@ impl Expr {
 
     /// Shift the values in the array by some period. See [the eager implementation](polars_core::series::SeriesTrait::fill_none).
     pub fn fill_none(self, fill_value: Expr) -> Self {
-        let name = output_name(&self).unwrap();
-        when(self.is_null())
-            .then(fill_value)
-            .otherwise(col(&*name))
-            .alias(&*name)
+        match output_name(&self) {
+            Ok(name) => when(self.is_null())
+                .then(fill_value)
+                .otherwise(col(&*name))
+                .alias(&*name),
+            Err(_) => when(self.clone().is_null())
+                .then(fill_value)
+                .otherwise(self),
+        }
     }
 
     /// Count the values of the Series
@@ -916,6 +1045,35 @@ impl Expr {
         self.map(move |s: Series| s.pow(exponent), Some(DataType::Float64))
     }
 
+    /// Set the ordering of a Categorical. This determines if sorting, min/max and comparisons
+    /// use the physical u32 indexes or the String values of the categories.
+    /// Setting [CategoricalOrdering::Enum] validates the values against its categories.
+    pub fn cat_set_ordering(self, ordering: CategoricalOrdering) -> Expr {
+        let dtype = DataType::Categorical(ordering.clone());
+        let output_type = dtype.clone();
+        let function = move |s: Series| match &ordering {
+            CategoricalOrdering::Enum(_) => {
+                s.categorical()?;
+                s.cast_with_datatype(&output_type)
+            }
+            _ => s
+                .categorical()
+                .map(|ca| ca.clone().set_ordering(ordering.clone()).into_series()),
+        };
+        self.map(function, Some(dtype))
+    }
+
+    /// Cast a Utf8 or Categorical to a Categorical with a closed set of categories.
+    /// Values that are not in `categories` raise an error if `strict`, otherwise they become null.
+    pub fn cast_to_enum(self, categories: Vec<String>, strict: bool) -> Expr {
+        let dtype = DataType::Categorical(CategoricalOrdering::Enum(Arc::new(categories.clone())));
+        let function = move |s: Series| {
+            let categories: Vec<&str> = categories.iter().map(|s| s.as_str()).collect();
+            s.cast_to_enum(&categories, strict)
+        };
+        self.map(function, Some(dtype))
+    }
+
     /// Get the year of a Date32/Date64
     #[cfg(feature = "temporal")]
     pub fn year(self) -> Expr {
@@ -1064,6 +1222,31 @@ where
     acc
 }
 
+/// Hash the values per row with a fixed `seed`, e.g. for multi-column feature hashing. See
+/// [the eager implementation](polars_core::functions::hash_rows). Without expressions the
+/// expression fails when it is evaluated.
+pub fn hash_exprs(exprs: Vec<Expr>, seed: u64) -> Expr {
+    let mut iter = exprs.into_iter();
+    let first = match iter.next() {
+        Some(first) => first.hash(seed),
+        None => {
+            return lit(0u64).map(
+                |_: Series| {
+                    Err(PolarsError::NoData(
+                        "cannot hash the rows of zero expressions".into(),
+                    ))
+                },
+                Some(DataType::UInt64),
+            )
+        }
+    };
+    let func = move |acc: Series, s: Series| {
+        let hashes = polars_core::functions::hash_combine(acc.u64()?, &s.hash(seed)?)?;
+        Ok(hashes.into_series())
+    };
+    fold_exprs(first, func, iter.collect())
+}
+
 /// Get the the sum of the values per row
 pub fn sum_exprs(exprs: Vec<Expr>) -> Expr {
     let func = |s1, s2| Ok(&s1 + &s2);
@@ -1160,6 +1343,17 @@ pub fn lit<L: Literal>(t: L) -> Expr {
     t.lit()
 }
 
+/// Create a millisecond duration literal from a duration string like `"3d12h30m"`.
+/// Add it to or subtract it from a date column to shift the dates.
+///
+/// See [parse_duration](polars_core::chunked_array::temporal::parse_duration) for the format.
+#[cfg(all(feature = "temporal", feature = "dtype-duration-ms"))]
+pub fn duration(duration: &str) -> Result<Expr> {
+    use polars_core::chunked_array::temporal::parse_duration;
+    let ms = parse_duration(duration)?;
+    Ok(Expr::Literal(LiteralValue::Duration(ms)))
+}
+
 /// [Not](Expr::Not) expression.
 pub fn not(expr: Expr) -> Expr {
     Expr::Not(Box::new(expr))
@@ -1,16 +1,33 @@
 //! Lazy variant of a [DataFrame](polars_core::frame::DataFrame).
+use crate::logical_plan::explain::{self, ExplainNode};
 use crate::logical_plan::optimizer::aggregate_pushdown::AggregatePushdown;
+use crate::logical_plan::optimizer::common_subplan::eliminate_common_subplans;
+use crate::logical_plan::optimizer::join_reorder::JoinReorder;
 use crate::logical_plan::optimizer::simplify_expr::SimplifyExprRule;
+use crate::logical_plan::optimizer::slice_pushdown::SlicePushDown;
+use crate::logical_plan::Context;
+use crate::physical_plan::NodeProfiles;
 use crate::prelude::simplify_expr::SimplifyBooleanRule;
-use crate::utils::combine_predicates_expr;
+use crate::utils::{combine_predicates_expr, expand_paths, expressions_to_schema};
 use crate::{logical_plan::FETCH_ROWS, prelude::*};
 use ahash::RandomState;
+#[cfg(feature = "async")]
+use futures::channel::oneshot;
+use polars_core::frame::group_by::{fmt_groupby_column, groupby_output_dtype, GroupByMethod};
 use polars_core::frame::hash_join::JoinType;
+use polars_core::frame::select::Selection;
 use polars_core::prelude::*;
-use polars_core::toggle_string_cache;
-use std::collections::HashMap;
+use polars_core::utils::{accumulate_dataframes_vertical, to_snake_case};
+#[cfg(feature = "async")]
+use polars_core::POOL;
+use polars_io::csv::{CsvDialect, CsvReader};
+use polars_io::transform::{ColumnTransformer, ColumnTransformers};
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "async")]
+use std::future::Future;
+#[cfg(feature = "async")]
+use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
-use std::sync::Mutex;
 
 use crate::logical_plan::optimizer::aggregate_scan_projections::AggScanProjection;
 use crate::logical_plan::optimizer::{
@@ -18,9 +35,18 @@ use crate::logical_plan::optimizer::{
 };
 use crate::prelude::aggregate_scan_projections::agg_projection;
 
+/// Prefix of the temporary key columns of [drop_duplicates_by](LazyFrame::drop_duplicates_by).
+const DISTINCT_KEY_PREFIX: &str = "__POLARS_DISTINCT_KEY_";
+/// Suffix of the joined columns of the other LazyFrame in [update](LazyFrame::update).
+const UPDATE_SUFFIX: &str = "__POLARS_UPDATE_RIGHT";
+/// Marks the rows of the other LazyFrame in [except](LazyFrame::except).
+const EXCEPT_MARKER_NAME: &str = "__POLARS_EXCEPT_MARKER";
+/// Prefix of the evaluated inputs of [with_udf_columns](LazyFrame::with_udf_columns).
+const UDF_INPUT_PREFIX: &str = "__POLARS_UDF_INPUT_";
+
 #[derive(Clone)]
 pub struct LazyCsvReader<'a> {
-    path: String,
+    paths: Vec<String>,
     delimiter: u8,
     has_header: bool,
     ignore_errors: bool,
@@ -29,12 +55,24 @@ pub struct LazyCsvReader<'a> {
     cache: bool,
     schema: Option<SchemaRef>,
     schema_overwrite: Option<&'a Schema>,
+    dtype_cast: Option<&'a Schema>,
+    column_transformers: ColumnTransformers,
 }
 
 impl<'a> LazyCsvReader<'a> {
+    /// Scan the CSV file at `path`. If `path` is a glob pattern, e.g. `"data/*.csv"`, all
+    /// matching files are scanned as one LazyFrame.
     pub fn new(path: String) -> Self {
+        Self::new_paths(vec![path])
+    }
+
+    /// Scan the CSV files at `paths` as one LazyFrame. The paths may be glob patterns.
+    ///
+    /// Every file is scanned with the same options and must have the same schema, unless a
+    /// schema is set with [with_schema](LazyCsvReader::with_schema).
+    pub fn new_paths(paths: Vec<String>) -> Self {
         LazyCsvReader {
-            path,
+            paths,
             delimiter: b',',
             has_header: true,
             ignore_errors: false,
@@ -43,6 +81,8 @@ impl<'a> LazyCsvReader<'a> {
             cache: true,
             schema: None,
             schema_overwrite: None,
+            dtype_cast: None,
+            column_transformers: ColumnTransformers::default(),
         }
     }
 
@@ -78,6 +118,17 @@ impl<'a> LazyCsvReader<'a> {
         self
     }
 
+    /// Cast the columns in this given Schema to its dtypes right after the scan. The given schema
+    /// may be a subset of the total schema.
+    ///
+    /// Unlike [with_dtype_overwrite](LazyCsvReader::with_dtype_overwrite) the columns are parsed
+    /// with their inferred dtype, so this also works for casts the parser doesn't support. The
+    /// casts are part of the query plan and are only done for the columns that are used.
+    pub fn with_dtype_cast(mut self, schema: Option<&'a Schema>) -> Self {
+        self.dtype_cast = schema;
+        self
+    }
+
     /// Set whether the CSV file has headers
     pub fn has_header(mut self, has_header: bool) -> Self {
         self.has_header = has_header;
@@ -96,29 +147,98 @@ impl<'a> LazyCsvReader<'a> {
         self
     }
 
+    /// Transform the column `name` right after it is read, e.g. to decrypt or mask it. The
+    /// filters and aggregations that are pushed down to the scan see the transformed values.
+    pub fn with_column_transformer<T>(mut self, name: &str, transformer: T) -> Self
+    where
+        T: ColumnTransformer + 'static,
+    {
+        self.column_transformers = self.column_transformers.with_transformer(name, transformer);
+        self
+    }
+
+    /// Set the delimiter and header of a dialect inferred by [CsvReader::sniff]. Returns an
+    /// error if the quote character or line terminator of the dialect isn't supported, see
+    /// [CsvDialect::check_supported].
+    pub fn with_dialect(self, dialect: &CsvDialect) -> Result<Self> {
+        dialect.check_supported()?;
+        Ok(self
+            .with_delimiter(dialect.delimiter)
+            .has_header(dialect.has_header))
+    }
+
     pub fn finish(self) -> LazyFrame {
-        let mut lf: LazyFrame = LogicalPlanBuilder::scan_csv(
-            self.path,
-            self.delimiter,
-            self.has_header,
-            self.ignore_errors,
-            self.skip_rows,
-            self.stop_after_n_rows,
-            self.cache,
-            self.schema,
-            self.schema_overwrite,
-        )
-        .build()
-        .into();
+        let mut builder = scan_paths(&self.paths, self.stop_after_n_rows, |path| {
+            LogicalPlanBuilder::scan_csv(
+                path,
+                self.delimiter,
+                self.has_header,
+                self.ignore_errors,
+                self.skip_rows,
+                self.stop_after_n_rows,
+                self.cache,
+                self.schema.clone(),
+                self.schema_overwrite,
+                self.column_transformers.clone(),
+            )
+        });
+        if let Some(dtypes) = self.dtype_cast {
+            builder = builder.cast(dtypes);
+        }
+        let mut lf: LazyFrame = builder.build().into();
         lf.opt_state.agg_scan_projection = true;
         lf
     }
 }
 
-#[derive(Copy, Clone, Debug)]
+/// Scan every file that matches the (glob) `paths`. Multiple files are concatenated in one
+/// union node, on which `stop_after_n_rows` is applied again.
+fn scan_paths<F>(paths: &[String], stop_after_n_rows: Option<usize>, scan: F) -> LogicalPlanBuilder
+where
+    F: Fn(String) -> LogicalPlanBuilder,
+{
+    let mut paths: Vec<_> = match paths
+        .iter()
+        .map(|path| expand_paths(path))
+        .collect::<Result<Vec<_>>>()
+    {
+        Ok(paths) => paths.into_iter().flatten().collect(),
+        Err(err) => return LogicalPlanBuilder::scan_error(err),
+    };
+    if paths.len() == 1 {
+        return scan(paths.pop().unwrap());
+    }
+    let inputs = paths.into_iter().map(|path| scan(path).build()).collect();
+    // the scanned files have different schemas
+    let builder = match LogicalPlanBuilder::union(inputs) {
+        Ok(builder) => builder,
+        Err(err) => return LogicalPlanBuilder::scan_error(err),
+    };
+    match stop_after_n_rows {
+        Some(n) => builder.slice(0, n),
+        None => builder,
+    }
+}
+
+#[derive(Clone, Debug)]
 pub struct JoinOptions {
     pub allow_parallel: bool,
     pub force_parallel: bool,
+    /// Suffix for the columns of the right table that are also in the left table.
+    /// Defaults to `"_right"`.
+    pub suffix: Option<String>,
+    /// Check that the join keys are unique on the left and/or right side.
+    /// Defaults to [JoinValidation::ManyToMany](JoinValidation::ManyToMany), which checks nothing.
+    pub validate: JoinValidation,
+    /// Append a Categorical `"_merge"` column that records if an output row was matched on the
+    /// `"left_only"`, `"right_only"` or `"both"` sides. Defaults to `false`.
+    pub indicator: bool,
+    /// Hint how to build and probe the hash table of a join on a single key column.
+    /// Defaults to [JoinStrategy::Auto](JoinStrategy::Auto), which broadcasts tiny build sides.
+    pub strategy: JoinStrategy,
+    /// Order the rows of an outer join by the left rows, followed by the unmatched right rows.
+    /// Defaults to `false`, in which case the order of an outer join is not defined.
+    pub maintain_order: bool,
 }
 
 impl Default for JoinOptions {
@@ -126,10 +246,27 @@ impl Default for JoinOptions {
         JoinOptions {
             allow_parallel: true,
             force_parallel: false,
+            suffix: None,
+            validate: JoinValidation::ManyToMany,
+            indicator: false,
+            strategy: JoinStrategy::Auto,
+            maintain_order: false,
         }
     }
 }
 
+impl JoinOptions {
+    /// The arguments of the eager [join](DataFrame::join) of a `how` join with these options.
+    pub(crate) fn args(&self, how: JoinType) -> JoinArgs {
+        JoinArgs::new(how)
+            .with_suffix(self.suffix.clone())
+            .with_validation(self.validate)
+            .with_indicator(self.indicator)
+            .with_strategy(self.strategy)
+            .with_maintain_order(self.maintain_order)
+    }
+}
+
 pub trait IntoLazy {
     fn lazy(self) -> LazyFrame;
 }
@@ -141,9 +278,45 @@ impl IntoLazy for DataFrame {
     }
 }
 
+/// Select the output of an expression in eager code, e.g. `df.select(col("a") * lit(2))`.
+impl<'a> Selection<'a, Expr> for Expr {
+    fn select_from(self, df: &DataFrame) -> Result<Vec<Series>> {
+        vec![self].select_from(df)
+    }
+}
+
+/// Select the output of expressions in eager code. The expressions are evaluated as a lazy
+/// projection of the `DataFrame`.
+impl<'a> Selection<'a, Expr> for Vec<Expr> {
+    fn select_from(self, df: &DataFrame) -> Result<Vec<Series>> {
+        let out = df.clone().lazy().select(self).collect()?;
+        Ok(out.get_columns().clone())
+    }
+}
+
 /// Lazy abstraction over an eager `DataFrame`.
 /// It really is an abstraction over a logical plan. The methods of this struct will incrementally
 /// modify a logical plan until output is requested (via [collect](crate::frame::LazyFrame::collect))
+///
+/// # Reuse
+///
+/// The nodes of the logical plan are reference counted, so cloning a LazyFrame only clones the
+/// top node of the plan. A LazyFrame can be cloned to collect it more than once, or to build
+/// several queries on top of it. Every collect runs the full query again, including the scans
+/// of the sources, and gives the same result as long as the sources don't change. To reuse the
+/// scanned data, collect it once and continue from the DataFrame with [lazy](IntoLazy::lazy).
+///
+/// ```rust
+/// use polars_core::prelude::*;
+/// use polars_lazy::prelude::*;
+///
+/// fn example(df: DataFrame) -> Result<(DataFrame, DataFrame)> {
+///     let base = df.lazy().filter(col("foo").gt(lit(2)));
+///     let sums = base.clone().groupby(vec![col("bar")]).agg(vec![col("foo").sum()]);
+///     let firsts = base.limit(10);
+///     Ok((sums.collect()?, firsts.collect()?))
+/// }
+/// ```
 #[derive(Clone)]
 pub struct LazyFrame {
     pub(crate) logical_plan: LogicalPlan,
@@ -168,7 +341,10 @@ impl From<LogicalPlan> for LazyFrame {
     }
 }
 
-#[derive(Copy, Clone)]
+/// Creates a new instance of a custom optimization rule for every query that is optimized.
+pub type OptimizationRuleFactory = Arc<dyn Fn() -> Box<dyn OptimizationRule> + Send + Sync>;
+
+#[derive(Clone)]
 /// State of the allowed optimizations
 pub struct OptState {
     pub projection_pushdown: bool,
@@ -177,7 +353,13 @@ pub struct OptState {
     pub simplify_expr: bool,
     pub agg_scan_projection: bool,
     pub aggregate_pushdown: bool,
+    pub slice_pushdown: bool,
+    pub common_subplan_elimination: bool,
+    pub join_reorder: bool,
+    pub agg_fusion: bool,
     pub global_string_cache: bool,
+    /// Rules registered with [LazyFrame::with_optimization_rule].
+    pub custom_rules: Vec<OptimizationRuleFactory>,
 }
 
 impl Default for OptState {
@@ -189,7 +371,12 @@ impl Default for OptState {
             simplify_expr: true,
             agg_scan_projection: false,
             aggregate_pushdown: false,
+            slice_pushdown: true,
+            common_subplan_elimination: true,
+            join_reorder: true,
+            agg_fusion: true,
             global_string_cache: true,
+            custom_rules: vec![],
         }
     }
 }
@@ -198,12 +385,118 @@ impl Default for OptState {
 pub type AllowedOptimizations = OptState;
 
 impl LazyFrame {
-    /// Create a LazyFrame directly from a parquet scan.
+    /// Create a LazyFrame directly from a parquet scan. If `path` is a glob pattern, all matching
+    /// files are scanned as one LazyFrame.
     #[cfg(feature = "parquet")]
     pub fn new_from_parquet(path: String, stop_after_n_rows: Option<usize>, cache: bool) -> Self {
-        let mut lf: LazyFrame = LogicalPlanBuilder::scan_parquet(path, stop_after_n_rows, cache)
-            .build()
-            .into();
+        Self::new_from_parquet_paths(vec![path], stop_after_n_rows, cache)
+    }
+
+    /// Create a LazyFrame from a scan of the parquet files at `paths`, which may be glob
+    /// patterns. All files must have the same schema.
+    #[cfg(feature = "parquet")]
+    pub fn new_from_parquet_paths(
+        paths: Vec<String>,
+        stop_after_n_rows: Option<usize>,
+        cache: bool,
+    ) -> Self {
+        Self::scan_parquet_paths(
+            paths,
+            stop_after_n_rows,
+            cache,
+            ColumnTransformers::default(),
+        )
+    }
+
+    /// Create a LazyFrame from a parquet scan whose columns are transformed right after they
+    /// are read, e.g. to decrypt or mask them. The filters and aggregations that are pushed
+    /// down to the scan see the transformed values.
+    #[cfg(feature = "parquet")]
+    pub fn new_from_parquet_with_transformers(
+        path: String,
+        stop_after_n_rows: Option<usize>,
+        cache: bool,
+        column_transformers: ColumnTransformers,
+    ) -> Self {
+        Self::scan_parquet_paths(vec![path], stop_after_n_rows, cache, column_transformers)
+    }
+
+    #[cfg(feature = "parquet")]
+    fn scan_parquet_paths(
+        paths: Vec<String>,
+        stop_after_n_rows: Option<usize>,
+        cache: bool,
+        column_transformers: ColumnTransformers,
+    ) -> Self {
+        let mut lf: LazyFrame = scan_paths(&paths, stop_after_n_rows, |path| {
+            LogicalPlanBuilder::scan_parquet(
+                path,
+                stop_after_n_rows,
+                cache,
+                column_transformers.clone(),
+            )
+        })
+        .build()
+        .into();
+        lf.opt_state.agg_scan_projection = true;
+        lf
+    }
+
+    /// Scan a csv file with the delimiter and header that are inferred from a sample of its
+    /// first lines. If `path` is a glob pattern, the first matching file is sampled.
+    /// The inferred dialect is returned, so that it can be checked before the query runs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// fn example() -> Result<DataFrame> {
+    ///     let (lf, dialect) = LazyFrame::scan_csv_auto("data.csv".into())?;
+    ///     assert!(dialect.has_header);
+    ///     lf.collect()
+    /// }
+    /// ```
+    pub fn scan_csv_auto(path: String) -> Result<(Self, CsvDialect)> {
+        let sample_path = expand_paths(&path)?.swap_remove(0);
+        let dialect = CsvReader::from_path(&sample_path)?.sniff()?;
+        let lf = LazyCsvReader::new(path).with_dialect(&dialect)?.finish();
+        Ok((lf, dialect))
+    }
+
+    /// Create a LazyFrame directly from an Arrow IPC (Feather v2) file scan. If `path` is a glob
+    /// pattern, all matching files are scanned as one LazyFrame.
+    #[cfg(feature = "ipc")]
+    pub fn scan_ipc(path: String, stop_after_n_rows: Option<usize>, cache: bool) -> Self {
+        Self::scan_ipc_with_transformers(
+            path,
+            stop_after_n_rows,
+            cache,
+            ColumnTransformers::default(),
+        )
+    }
+
+    /// Create a LazyFrame from an Arrow IPC file scan whose columns are transformed right after
+    /// they are read. The filters and aggregations that are pushed down to the scan see the
+    /// transformed values.
+    #[cfg(feature = "ipc")]
+    pub fn scan_ipc_with_transformers(
+        path: String,
+        stop_after_n_rows: Option<usize>,
+        cache: bool,
+        column_transformers: ColumnTransformers,
+    ) -> Self {
+        let mut lf: LazyFrame = scan_paths(&[path], stop_after_n_rows, |path| {
+            LogicalPlanBuilder::scan_ipc(
+                path,
+                stop_after_n_rows,
+                cache,
+                column_transformers.clone(),
+            )
+        })
+        .build()
+        .into();
         lf.opt_state.agg_scan_projection = true;
         lf
     }
@@ -231,11 +524,11 @@ impl LazyFrame {
         LogicalPlanBuilder::from(self.logical_plan)
     }
 
-    fn get_opt_state(&self) -> OptState {
-        self.opt_state
+    pub(crate) fn get_opt_state(&self) -> OptState {
+        self.opt_state.clone()
     }
 
-    fn from_logical_plan(logical_plan: LogicalPlan, opt_state: OptState) -> Self {
+    pub(crate) fn from_logical_plan(logical_plan: LogicalPlan, opt_state: OptState) -> Self {
         LazyFrame {
             logical_plan,
             opt_state,
@@ -272,6 +565,81 @@ impl LazyFrame {
         self
     }
 
+    /// Toggle slice pushdown, which lets scans stop reading after the rows of a `slice`/`limit`.
+    pub fn with_slice_pushdown(mut self, toggle: bool) -> Self {
+        self.opt_state.slice_pushdown = toggle;
+        self
+    }
+
+    /// Toggle common subplan elimination, which executes a subplan that is used more than once
+    /// in the query only once.
+    pub fn with_common_subplan_elimination(mut self, toggle: bool) -> Self {
+        self.opt_state.common_subplan_elimination = toggle;
+        self
+    }
+
+    /// Toggle join reordering, which joins the smallest relations of a chain of inner joins
+    /// first. The sizes are known for in-memory DataFrames and scans that stop after `n` rows.
+    pub fn with_join_reorder(mut self, toggle: bool) -> Self {
+        self.opt_state.join_reorder = toggle;
+        self
+    }
+
+    /// Toggle aggregation fusion, which computes aggregations of the same column in a groupby
+    /// together if they can be computed in a single pass, e.g. the `min` and `max`.
+    pub fn with_agg_fusion(mut self, toggle: bool) -> Self {
+        self.opt_state.agg_fusion = toggle;
+        self
+    }
+
+    /// Register a custom optimization rule. The rule runs in the optimizer loop after the
+    /// builtin rules, until none of the rules changes the plan anymore. `make_rule` creates a
+    /// new instance of the rule for every query that is optimized.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// /// Replace a UDF by its input, e.g. because the UDF only logs the DataFrame.
+    /// struct RemoveUdf {}
+    ///
+    /// impl OptimizationRule for RemoveUdf {
+    ///     fn optimize_plan(
+    ///         &mut self,
+    ///         lp_arena: &mut Arena<ALogicalPlan>,
+    ///         _expr_arena: &mut Arena<AExpr>,
+    ///         node: Node,
+    ///     ) -> Option<ALogicalPlan> {
+    ///         match lp_arena.get(node) {
+    ///             ALogicalPlan::Udf { input, .. } => Some(lp_arena.get(*input).clone()),
+    ///             _ => None,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// fn example(df: DataFrame) -> Result<DataFrame> {
+    ///     df.lazy()
+    ///         .map(|df: DataFrame| -> Result<DataFrame> {
+    ///             println!("{:?}", df);
+    ///             Ok(df)
+    ///         }, None, None)
+    ///         .with_optimization_rule(|| RemoveUdf {})
+    ///         .collect()
+    /// }
+    /// ```
+    pub fn with_optimization_rule<F, R>(mut self, make_rule: F) -> Self
+    where
+        F: Fn() -> R + Send + Sync + 'static,
+        R: OptimizationRule + 'static,
+    {
+        self.opt_state.custom_rules.push(Arc::new(move || {
+            Box::new(make_rule()) as Box<dyn OptimizationRule>
+        }));
+        self
+    }
+
     /// Toggle global string cache.
     pub fn with_string_cache(mut self, toggle: bool) -> Self {
         self.opt_state.global_string_cache = toggle;
@@ -292,6 +660,124 @@ impl LazyFrame {
         Ok(logical_plan.describe())
     }
 
+    /// Describe the optimized plan with the estimated number of rows and the cost of every node.
+    /// Rows are estimated from the metadata of the scanned sources and fixed selectivities of
+    /// the predicates, the cost is the estimated number of rows processed by a node and its
+    /// inputs.
+    ///
+    /// If `analyze` is `true` the query is executed and every executed node is also annotated
+    /// with the actual number of rows and the time spent in the node, excluding its inputs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// fn example(df: DataFrame) -> Result<()> {
+    ///     let explained = df.lazy()
+    ///         .filter(col("foo").gt(lit(2)))
+    ///         .groupby(vec![col("bar")])
+    ///         .agg(vec![col("foo").sum()])
+    ///         .explain(true)?;
+    ///     println!("{}", explained);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn explain(&self, analyze: bool) -> Result<String> {
+        let use_string_cache = self.opt_state.global_string_cache;
+        let mut expr_arena = Arena::with_capacity(512);
+        let mut lp_arena = Arena::with_capacity(512);
+        let lp_top = self.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+        let mut explained = ExplainNode::new(lp_top, &lp_arena, &expr_arena);
+
+        if analyze {
+            let profiles = NodeProfiles::default();
+            let planner = DefaultPlanner::default()
+                .with_agg_fusion(self.opt_state.agg_fusion)
+                .with_profiles(profiles.clone());
+            let root = planner.create_physical_plan(lp_top, &mut lp_arena, &mut expr_arena)?;
+            PhysicalPlan::new(root)
+                .with_string_cache(use_string_cache)
+                .execute()?;
+            explained.set_profiles(&profiles.lock().unwrap());
+        }
+        Ok(explained.to_string())
+    }
+
+    /// Execute the query and return its result together with a profile of the query. The
+    /// profile has a row for every node of the plan with the `node` name, the `start` and `end`
+    /// of its execution in microseconds since the start of the query and the `rows` it produced.
+    /// The rows are in the order in which the nodes finished. The time of a node includes the
+    /// time of its inputs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// fn example(df: DataFrame) -> Result<DataFrame> {
+    ///     let (out, profile) = df.lazy()
+    ///         .filter(col("foo").gt(lit(2)))
+    ///         .groupby(vec![col("bar")])
+    ///         .agg(vec![col("foo").sum()])
+    ///         .profile()?;
+    ///     println!("{:?}", profile);
+    ///     Ok(out)
+    /// }
+    /// ```
+    pub fn profile(self) -> Result<(DataFrame, DataFrame)> {
+        let use_string_cache = self.opt_state.global_string_cache;
+        let mut expr_arena = Arena::with_capacity(512);
+        let mut lp_arena = Arena::with_capacity(512);
+        let lp_top = self.optimize(&mut lp_arena, &mut expr_arena)?;
+
+        let profiles = NodeProfiles::default();
+        let planner = DefaultPlanner::default()
+            .with_agg_fusion(self.opt_state.agg_fusion)
+            .with_profiles(profiles.clone());
+        let root = planner.create_physical_plan(lp_top, &mut lp_arena, &mut expr_arena)?;
+        let query_start = std::time::Instant::now();
+        let out = PhysicalPlan::new(root)
+            .with_string_cache(use_string_cache)
+            .execute()?;
+
+        let profiles = profiles.lock().unwrap();
+        let mut nodes = profiles
+            .iter()
+            .map(|(&node, profile)| {
+                let start = profile.started.saturating_duration_since(query_start);
+                (node, start, start + profile.elapsed, profile.rows)
+            })
+            .collect::<Vec<_>>();
+        nodes.sort_by_key(|&(node, _, end, _)| (end, node));
+
+        let names = nodes
+            .iter()
+            .map(|(node, ..)| explain::label(lp_arena.get(Node(*node))))
+            .collect::<Vec<_>>();
+        let start = nodes
+            .iter()
+            .map(|(_, start, ..)| start.as_micros() as u64)
+            .collect::<Vec<_>>();
+        let end = nodes
+            .iter()
+            .map(|(_, _, end, _)| end.as_micros() as u64)
+            .collect::<Vec<_>>();
+        let rows = nodes
+            .iter()
+            .map(|(.., rows)| *rows as u64)
+            .collect::<Vec<_>>();
+        let profile = DataFrame::new(vec![
+            Series::new("node", &names),
+            Series::new("start", &start),
+            Series::new("end", &end),
+            Series::new("rows", &rows),
+        ])?;
+        Ok((out, profile))
+    }
+
     /// Add a sort operation to the logical plan.
     ///
     /// # Example
@@ -307,11 +793,33 @@ impl LazyFrame {
     /// }
     /// ```
     pub fn sort(self, by_column: &str, reverse: bool) -> Self {
+        self.sort_by_exprs(vec![col(by_column)], vec![reverse])
+    }
+
+    /// Add a sort operation on multiple expressions to the logical plan. The rows are sorted
+    /// lexicographically: ties in the first expression are broken by the second one, and so on.
+    /// Every expression has its own `reverse` flag.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// /// Sort DataFrame by 'species' and then by descending 'sepal.width'
+    /// fn example(df: DataFrame) -> LazyFrame {
+    ///       df.lazy()
+    ///         .sort_by_exprs(vec![col("species"), col("sepal.width")], vec![false, true])
+    /// }
+    /// ```
+    pub fn sort_by_exprs<I, E>(self, by: I, reverse: Vec<bool>) -> Self
+    where
+        I: IntoIterator<Item = E>,
+        E: Into<Expr>,
+    {
+        let by = by.into_iter().map(|e| e.into()).collect::<Vec<Expr>>();
         let opt_state = self.get_opt_state();
-        let lp = self
-            .get_plan_builder()
-            .sort(by_column.into(), reverse)
-            .build();
+        let lp = self.get_plan_builder().sort(by, reverse).build();
         Self::from_logical_plan(lp, opt_state)
     }
 
@@ -332,12 +840,21 @@ impl LazyFrame {
         self.select_local(vec![col("*").reverse()])
     }
 
-    /// Rename a column in the DataFrame
+    /// Rename a column in the DataFrame. Renaming a column that doesn't exist fails when the
+    /// query is collected.
     pub fn with_column_renamed(self, existing_name: &str, new_name: &str) -> Self {
-        let schema = self.logical_plan.schema();
-        let schema = schema
+        let schema = match self
+            .logical_plan
+            .schema()
             .rename(&[existing_name], &[new_name])
-            .expect("cannot rename non existing column");
+        {
+            Ok(schema) => schema,
+            Err(err) => {
+                let opt_state = self.get_opt_state();
+                let lp = self.get_plan_builder().error(err).build();
+                return Self::from_logical_plan(lp, opt_state);
+            }
+        };
 
         // first make sure that the column is projected, then we
         let init = self.with_column(col(existing_name));
@@ -351,6 +868,131 @@ impl LazyFrame {
         init.map(f, Some(AllowedOptimizations::default()), Some(schema))
     }
 
+    /// Rename the columns `existing` to the names at the same position in `new`. All columns
+    /// are renamed at once, so names may be swapped. Predicates and projections on the new names
+    /// are pushed down to the input under their existing names.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// fn example(df: DataFrame) -> LazyFrame {
+    ///       df.lazy()
+    ///         .rename(&["foo", "bar"], &["bar", "foo"])
+    /// }
+    /// ```
+    pub fn rename<I, J, T, S>(self, existing: I, new: J) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        J: IntoIterator<Item = S>,
+        T: AsRef<str>,
+        S: AsRef<str>,
+    {
+        let existing = existing
+            .into_iter()
+            .map(|name| name.as_ref().to_string())
+            .collect();
+        let new = new
+            .into_iter()
+            .map(|name| name.as_ref().to_string())
+            .collect();
+        let opt_state = self.get_opt_state();
+        let lp = self.get_plan_builder().rename(existing, new).build();
+        Self::from_logical_plan(lp, opt_state)
+    }
+
+    /// Rename all columns with `f`, that maps the current name of a column to its new name.
+    /// All columns are renamed in a single projection.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// fn example(df: DataFrame) -> LazyFrame {
+    ///       df.lazy()
+    ///         .rename_with(|name| name.replace(".", "_"))
+    /// }
+    /// ```
+    pub fn rename_with<F: Fn(&str) -> String>(self, f: F) -> Self {
+        let exprs = self
+            .logical_plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|fld| {
+                let name = f(fld.name());
+                if &name == fld.name() {
+                    col(fld.name())
+                } else {
+                    col(fld.name()).alias(&name)
+                }
+            })
+            .collect::<Vec<_>>();
+        self.select(exprs)
+    }
+
+    /// Normalize all column names to snake_case.
+    /// See [the eager implementation](polars_core::frame::DataFrame::rename_snake_case).
+    pub fn rename_snake_case(self) -> Self {
+        self.rename_with(to_snake_case)
+    }
+
+    /// Add a prefix to all column names.
+    pub fn add_prefix(self, prefix: &str) -> Self {
+        self.rename_with(|name| format!("{}{}", prefix, name))
+    }
+
+    /// Add a suffix to all column names.
+    pub fn add_suffix(self, suffix: &str) -> Self {
+        self.rename_with(|name| format!("{}{}", name, suffix))
+    }
+
+    /// Add a column `name` with session ids. A new session starts when the time between two
+    /// consecutive rows of a partition is larger than `gap`. An existing column `name` is
+    /// replaced in place.
+    /// See [the eager implementation](polars_core::frame::DataFrame::session_id).
+    pub fn with_session_id(
+        self,
+        time_col: &str,
+        gap: i64,
+        partition_by: &[&str],
+        name: &str,
+    ) -> Self {
+        let field = Field::new(name, DataType::UInt32);
+        let mut fields = self.logical_plan.schema().fields().clone();
+        match fields.iter().position(|fld| fld.name() == name) {
+            Some(idx) => fields[idx] = field,
+            None => fields.push(field),
+        }
+        let schema = Schema::new(fields);
+
+        let time_col = time_col.to_string();
+        let partition_by = partition_by
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>();
+        let name = name.to_string();
+        let f = move |df: DataFrame| {
+            let partition_by = partition_by.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+            let mut sessions = df.session_id(&time_col, gap, &partition_by)?;
+            sessions.rename(&name);
+            let mut df = df;
+            df.replace_or_add(&name, sessions)?;
+            Ok(df)
+        };
+        // sessions depend on all rows and on the time and partition columns
+        let optimizations = AllowedOptimizations {
+            projection_pushdown: false,
+            predicate_pushdown: false,
+            ..Default::default()
+        };
+        self.map(f, Some(optimizations), Some(schema))
+    }
+
     /// Shift the values by a given period and fill the parts that will be empty due to this operation
     /// with `Nones`.
     ///
@@ -399,8 +1041,18 @@ impl LazyFrame {
         let simplify_expr = self.opt_state.simplify_expr;
         let agg_scan_projection = self.opt_state.agg_scan_projection;
         let aggregate_pushdown = self.opt_state.aggregate_pushdown;
-
-        let logical_plan = self.get_plan_builder().build();
+        let slice_pushdown = self.opt_state.slice_pushdown;
+        let common_subplan_elimination = self.opt_state.common_subplan_elimination;
+        let join_reorder = self.opt_state.join_reorder;
+        let custom_rules = self.opt_state.custom_rules.clone();
+
+        let mut logical_plan = self.get_plan_builder().build();
+        if let Some(err) = logical_plan.error() {
+            return Err(err);
+        }
+        if common_subplan_elimination {
+            logical_plan = eliminate_common_subplans(logical_plan);
+        }
 
         // gradually fill the rules passed to the optimizer
         let mut rules: Vec<Box<dyn OptimizationRule>> = Vec::with_capacity(8);
@@ -416,17 +1068,13 @@ impl LazyFrame {
 
         if projection_pushdown {
             let alp = lp_arena.take(lp_top);
-            let alp = projection_pushdown_opt
-                .optimize(alp, lp_arena, expr_arena)
-                .expect("projection pushdown failed");
+            let alp = projection_pushdown_opt.optimize(alp, lp_arena, expr_arena)?;
             lp_arena.replace(lp_top, alp);
         }
 
         if predicate_pushdown {
             let alp = lp_arena.take(lp_top);
-            let alp = predicate_pushdown_opt
-                .optimize(alp, lp_arena, expr_arena)
-                .expect("predicate pushdown failed");
+            let alp = predicate_pushdown_opt.optimize(alp, lp_arena, expr_arena)?;
             lp_arena.replace(lp_top, alp);
         }
 
@@ -443,9 +1091,24 @@ impl LazyFrame {
             rules.push(Box::new(AggregatePushdown::new()))
         }
 
+        rules.extend(custom_rules.iter().map(|make_rule| make_rule()));
+
         let opt = StackOptimizer {};
         lp_top = opt.optimize_loop(&mut rules, expr_arena, lp_arena, lp_top);
 
+        // runs after the other rules, so that it sees the final predicates and aggregations of
+        // the scans
+        if slice_pushdown {
+            let mut rules: Vec<Box<dyn OptimizationRule>> = vec![Box::new(SlicePushDown {})];
+            lp_top = opt.optimize_loop(&mut rules, expr_arena, lp_arena, lp_top);
+        }
+
+        // runs after the slice pushdown, which sets the number of rows the scans read
+        if join_reorder {
+            let mut rules: Vec<Box<dyn OptimizationRule>> = vec![Box::new(JoinReorder {})];
+            lp_top = opt.optimize_loop(&mut rules, expr_arena, lp_arena, lp_top);
+        }
+
         if agg_scan_projection {
             // scan the LP to aggregate all the column used in scans
             // these columns will be added to the state of the AggScanProjection rule
@@ -483,27 +1146,11 @@ impl LazyFrame {
     /// }
     /// ```
     pub fn collect(self) -> Result<DataFrame> {
-        let use_string_cache = self.opt_state.global_string_cache;
-        let mut expr_arena = Arena::with_capacity(512);
-        let mut lp_arena = Arena::with_capacity(512);
-        let lp_top = self.optimize(&mut lp_arena, &mut expr_arena)?;
-
-        toggle_string_cache(use_string_cache);
-        let planner = DefaultPlanner::default();
-        let mut physical_plan =
-            planner.create_physical_plan(lp_top, &mut lp_arena, &mut expr_arena)?;
-        let cache = Arc::new(Mutex::new(HashMap::with_capacity_and_hasher(
-            64,
-            RandomState::default(),
-        )));
-        let out = physical_plan.execute(&cache);
-        if use_string_cache {
-            toggle_string_cache(!use_string_cache);
-        }
-        out
+        self.collect_with_planner(DefaultPlanner::default())
     }
 
-    /// Filter by some predicate expression.
+    /// Execute all the lazy operations and collect them into a DataFrame, or return
+    /// `PolarsError::Cancelled` if the token is cancelled before the query finished.
     ///
     /// # Example
     ///
@@ -511,22 +1158,20 @@ impl LazyFrame {
     /// use polars_core::prelude::*;
     /// use polars_lazy::prelude::*;
     ///
-    /// fn example(df: DataFrame) -> LazyFrame {
-    ///       df.lazy()
-    ///         .filter(col("sepal.width").is_not_null())
-    ///         .select(&[col("sepal.width"), col("sepal.length")])
+    /// fn example(df: DataFrame, token: CancellationToken) -> Result<DataFrame> {
+    ///     // the token can be cloned and cancelled from another thread
+    ///     df.lazy()
+    ///         .groupby(vec![col("foo")])
+    ///         .agg(vec![col("bar").sum()])
+    ///         .collect_cancellable(token)
     /// }
     /// ```
-    pub fn filter(self, predicate: Expr) -> Self {
-        let opt_state = self.get_opt_state();
-        let lp = self.get_plan_builder().filter(predicate).build();
-        Self::from_logical_plan(lp, opt_state)
+    pub fn collect_cancellable(self, token: CancellationToken) -> Result<DataFrame> {
+        self.collect_with_planner(DefaultPlanner::default().with_cancellation_token(token))
     }
 
-    /// Select (and rename) columns from the query.
-    ///
-    /// Columns can be selected with [col](crate::dsl::col);
-    /// If you want to select all columns use `col("*")`.
+    /// Execute all the lazy operations and collect them into a DataFrame, while `callback`
+    /// is called with the rows scanned per source and every finished node of the query.
     ///
     /// # Example
     ///
@@ -534,14 +1179,240 @@ impl LazyFrame {
     /// use polars_core::prelude::*;
     /// use polars_lazy::prelude::*;
     ///
-    /// /// This function selects column "foo" and column "bar".
-    /// /// Column "bar" is renamed to "ham".
-    /// fn example(df: DataFrame) -> LazyFrame {
+    /// fn example(df: DataFrame) -> Result<DataFrame> {
+    ///     df.lazy()
+    ///         .filter(col("foo").gt(lit(2)))
+    ///         .collect_with_progress(|event: ProgressEvent| println!("{:?}", event))
+    /// }
+    /// ```
+    pub fn collect_with_progress<F>(self, callback: F) -> Result<DataFrame>
+    where
+        F: ProgressCallback + 'static,
+    {
+        self.collect_with_planner(
+            DefaultPlanner::default().with_progress_callback(Arc::new(callback)),
+        )
+    }
+
+    /// Execute all the lazy operations and collect them into a DataFrame, while the events of
+    /// the query (plan started, node finished, bytes read, etc.) are sent to `observer`.
+    pub fn collect_with_observer<O>(self, observer: O) -> Result<DataFrame>
+    where
+        O: QueryObserver + 'static,
+    {
+        self.collect_with_planner(DefaultPlanner::default().with_observer(Arc::new(observer)))
+    }
+
+    fn collect_with_planner(self, planner: DefaultPlanner) -> Result<DataFrame> {
+        self.to_physical_plan_with_planner(planner)?.execute()
+    }
+
+    /// Optimize the query and create the physical plan that executes it. The plan shows the
+    /// operators that are chosen, e.g. the join strategy or a partitioned groupby, and can be
+    /// executed with [PhysicalPlan::execute].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// fn example(df: DataFrame) -> Result<DataFrame> {
+    ///     let plan = df
+    ///         .lazy()
+    ///         .groupby(vec![col("foo")])
+    ///         .agg(vec![col("bar").sum()])
+    ///         .to_physical_plan()?;
+    ///     println!("{:?}", plan);
+    ///     plan.execute()
+    /// }
+    /// ```
+    pub fn to_physical_plan(self) -> Result<PhysicalPlan> {
+        self.to_physical_plan_with_planner(DefaultPlanner::default())
+    }
+
+    fn to_physical_plan_with_planner(self, planner: DefaultPlanner) -> Result<PhysicalPlan> {
+        let use_string_cache = self.opt_state.global_string_cache;
+        let planner = planner.with_agg_fusion(self.opt_state.agg_fusion);
+        let mut expr_arena = Arena::with_capacity(512);
+        let mut lp_arena = Arena::with_capacity(512);
+        let lp_top = self.optimize(&mut lp_arena, &mut expr_arena)?;
+
+        let root = planner.create_physical_plan(lp_top, &mut lp_arena, &mut expr_arena)?;
+        Ok(PhysicalPlan::new(root)
+            .with_string_cache(use_string_cache)
+            .with_observer(planner.observer.clone()))
+    }
+
+    /// Execute the query and write the result to the csv file at `path`.
+    ///
+    /// Queries that only filter rows and compute columns of a DataFrame or a parquet file are
+    /// executed batch by batch, so the full result is never in memory. Other queries are
+    /// collected before they are written.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// fn example() -> Result<()> {
+    ///     LazyFrame::new_from_parquet("big.parquet".into(), None, false)
+    ///         .filter(col("foo").gt(lit(2)))
+    ///         .sink_csv("filtered.csv".into())
+    /// }
+    /// ```
+    pub fn sink_csv(self, path: String) -> Result<()> {
+        self.sink(path, SinkFileType::Csv, ColumnTransformers::default())
+    }
+
+    /// Execute the query and write the result to the csv file at `path`, with the columns
+    /// transformed right before they are written, e.g. to tokenize them.
+    pub fn sink_csv_with_transformers(
+        self,
+        path: String,
+        column_transformers: ColumnTransformers,
+    ) -> Result<()> {
+        self.sink(path, SinkFileType::Csv, column_transformers)
+    }
+
+    /// Execute the query and write the result to the parquet file at `path`. Every batch of the
+    /// query is written as a row group, see [sink_csv](LazyFrame::sink_csv) for the queries that
+    /// are executed batch by batch.
+    #[cfg(feature = "parquet")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parquet")))]
+    pub fn sink_parquet(self, path: String) -> Result<()> {
+        self.sink(path, SinkFileType::Parquet, ColumnTransformers::default())
+    }
+
+    /// Execute the query and write the result to the parquet file at `path`, with the columns
+    /// transformed right before they are written, e.g. to tokenize them.
+    #[cfg(feature = "parquet")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parquet")))]
+    pub fn sink_parquet_with_transformers(
+        self,
+        path: String,
+        column_transformers: ColumnTransformers,
+    ) -> Result<()> {
+        self.sink(path, SinkFileType::Parquet, column_transformers)
+    }
+
+    fn sink(
+        self,
+        path: String,
+        file_type: SinkFileType,
+        column_transformers: ColumnTransformers,
+    ) -> Result<()> {
+        let opt_state = self.get_opt_state();
+        let lp = self
+            .get_plan_builder()
+            .sink(path, file_type, column_transformers)
+            .build();
+        Self::from_logical_plan(lp, opt_state).collect().map(|_| ())
+    }
+
+    /// Execute all the lazy operations on the thread pool of polars and return a future that
+    /// resolves to the resulting DataFrame. Awaiting the future doesn't block the executor, so
+    /// this can be used from async runtimes like tokio. A panic during the query resolves to an
+    /// error.
+    ///
+    /// The scans still read their files with blocking IO on the pool, as there are no remote
+    /// scans with an async reader yet.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// async fn example(df: DataFrame) -> Result<DataFrame> {
+    ///     df.lazy()
+    ///         .filter(col("foo").gt(lit(2)))
+    ///         .collect_async()
+    ///         .await
+    /// }
+    /// ```
+    #[cfg(feature = "async")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    pub fn collect_async(self) -> impl Future<Output = Result<DataFrame>> + Send {
+        let (tx, rx) = oneshot::channel();
+        // the parallel kernels of the query run on the same pool, so many concurrent queries
+        // share its threads instead of spawning a thread each
+        POOL.spawn(move || {
+            let out = std::panic::catch_unwind(AssertUnwindSafe(|| self.collect())).unwrap_or_else(
+                |payload| {
+                    let msg = payload
+                        .downcast_ref::<&str>()
+                        .map(|msg| msg.to_string())
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_else(|| "unknown panic".to_string());
+                    Err(PolarsError::Other(
+                        format!("the query panicked: {}", msg).into(),
+                    ))
+                },
+            );
+            // the receiver may already be dropped, then there is nobody to notify
+            let _ = tx.send(out);
+        });
+        async move {
+            rx.await.unwrap_or_else(|_| {
+                Err(PolarsError::Other(
+                    "the query was dropped before it finished".into(),
+                ))
+            })
+        }
+    }
+
+    /// Filter by some predicate expression.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// fn example(df: DataFrame) -> LazyFrame {
+    ///       df.lazy()
+    ///         .filter(col("sepal.width").is_not_null())
+    ///         .select(&[col("sepal.width"), col("sepal.length")])
+    /// }
+    /// ```
+    pub fn filter(self, predicate: Expr) -> Self {
+        let opt_state = self.get_opt_state();
+        let lp = self.get_plan_builder().filter(predicate).build();
+        Self::from_logical_plan(lp, opt_state)
+    }
+
+    /// Select (and rename) columns from the query.
+    ///
+    /// Columns can be selected with [col](crate::dsl::col) or by name, and names can be mixed
+    /// with expressions by converting them with `.into()`. If you want to select all columns use
+    /// `col("*")`.
+    ///
+    /// Like [with_columns](LazyFrame::with_columns), [groupby](LazyFrame::groupby) and
+    /// [agg](LazyGroupBy::agg) this takes any iterable of items that convert to an [Expr].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// /// This function selects column "foo" and column "bar".
+    /// /// Column "bar" is renamed to "ham".
+    /// fn example(df: DataFrame) -> LazyFrame {
     ///       df.lazy()
     ///         .select(&[col("foo"),
     ///                   col("bar").alias("ham")])
     /// }
     ///
+    /// /// Select columns by name, or mix names and expressions.
+    /// fn select_by_name(df: DataFrame) -> LazyFrame {
+    ///       df.lazy()
+    ///         .select(["foo", "bar"])
+    ///         .select([col("foo") * lit(2), "bar".into()])
+    /// }
+    ///
     /// /// This function selects all columns except "foo"
     /// fn exclude_a_column(df: DataFrame) -> LazyFrame {
     ///       df.lazy()
@@ -549,11 +1420,15 @@ impl LazyFrame {
     ///                   except("foo")])
     /// }
     /// ```
-    pub fn select<E: AsRef<[Expr]>>(self, exprs: E) -> Self {
+    pub fn select<I, E>(self, exprs: I) -> Self
+    where
+        I: IntoIterator<Item = E>,
+        E: Into<Expr>,
+    {
         let opt_state = self.get_opt_state();
         let lp = self
             .get_plan_builder()
-            .project(exprs.as_ref().to_vec())
+            .project(exprs.into_iter().map(Into::into).collect())
             .build();
         Self::from_logical_plan(lp, opt_state)
     }
@@ -585,12 +1460,16 @@ impl LazyFrame {
     ///        .sort("date", false)
     /// }
     /// ```
-    pub fn groupby(self, by: Vec<Expr>) -> LazyGroupBy {
+    pub fn groupby<I, E>(self, by: I) -> LazyGroupBy
+    where
+        I: IntoIterator<Item = E>,
+        E: Into<Expr>,
+    {
         let opt_state = self.get_opt_state();
         LazyGroupBy {
             logical_plan: self.logical_plan,
             opt_state,
-            keys: by,
+            keys: by.into_iter().map(Into::into).collect(),
         }
     }
 
@@ -650,6 +1529,22 @@ impl LazyFrame {
         )
     }
 
+    /// Create the cartesian product of this query and the other lazy query.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    /// fn join_dataframes(ldf: LazyFrame, other: LazyFrame) -> LazyFrame {
+    ///         ldf
+    ///         .cross_join(other)
+    /// }
+    /// ```
+    pub fn cross_join(self, other: LazyFrame) -> LazyFrame {
+        self.join(other, vec![], vec![], None, JoinType::Cross)
+    }
+
     /// Join query with other lazy query.
     ///
     /// # Example
@@ -700,7 +1595,6 @@ impl LazyFrame {
         how: JoinType,
     ) -> LazyFrame {
         let opt_state = self.get_opt_state();
-        let opts = options.unwrap_or_default();
         let lp = self
             .get_plan_builder()
             .join(
@@ -708,13 +1602,255 @@ impl LazyFrame {
                 how,
                 left_on,
                 right_on,
-                opts.allow_parallel,
-                opts.force_parallel,
+                options.unwrap_or_default(),
             )
             .build();
         Self::from_logical_plan(lp, opt_state)
     }
 
+    /// Join with the other lazy query on arbitrary boolean predicates. The predicates are
+    /// evaluated on the joined schema: all columns of both tables, where the right columns that
+    /// are also in the left table get the `suffix` (default `"_right"`). A row pair is kept
+    /// when all predicates are true.
+    ///
+    /// Predicates that compare a column of the left table with a column of the right table
+    /// with `eq`, `lt`, `lt_eq`, `gt` or `gt_eq` are used to sort the right table and only visit
+    /// the right rows in range. Other predicates are evaluated on all row pairs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// fn events_in_windows(windows: LazyFrame, events: LazyFrame) -> LazyFrame {
+    ///     windows.join_where(
+    ///         events,
+    ///         vec![col("start").lt_eq(col("ts")), col("ts").lt(col("end"))],
+    ///         None,
+    ///     )
+    /// }
+    /// ```
+    pub fn join_where(
+        self,
+        other: LazyFrame,
+        predicates: Vec<Expr>,
+        suffix: Option<String>,
+    ) -> LazyFrame {
+        let opt_state = self.get_opt_state();
+        let lp = self
+            .get_plan_builder()
+            .join_where(other.logical_plan, predicates, suffix)
+            .build();
+        Self::from_logical_plan(lp, opt_state)
+    }
+
+    /// Join this query with itself. The columns of both sides get a suffix: `suffixes.0` for the
+    /// left side and `suffixes.1` for the right side, so that no manual renaming is needed. Key
+    /// columns that have the same name on both sides are kept once, without suffix.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// /// Every employee next to their manager, with columns `name_employee`, `name_manager` etc.
+    /// fn with_managers(employees: LazyFrame) -> LazyFrame {
+    ///     employees.self_join(
+    ///         &["manager_id"],
+    ///         &["id"],
+    ///         JoinType::Left,
+    ///         ("_employee", "_manager"),
+    ///     )
+    /// }
+    /// ```
+    pub fn self_join(
+        self,
+        left_on: &[&str],
+        right_on: &[&str],
+        how: JoinType,
+        suffixes: (&str, &str),
+    ) -> LazyFrame {
+        if left_on.len() != right_on.len() {
+            let err = PolarsError::ValueError(
+                format!(
+                    "the number of left and right keys of a self join must be equal, got {} and {}",
+                    left_on.len(),
+                    right_on.len()
+                )
+                .into(),
+            );
+            let opt_state = self.get_opt_state();
+            let lp = self.get_plan_builder().error(err).build();
+            return Self::from_logical_plan(lp, opt_state);
+        }
+        let shared_keys: Vec<&str> = left_on
+            .iter()
+            .zip(right_on)
+            .filter(|(l, r)| l == r)
+            .map(|(l, _)| *l)
+            .collect();
+        let qualify = |name: &str, suffix: &str| {
+            if shared_keys.contains(&name) {
+                name.to_string()
+            } else {
+                format!("{}{}", name, suffix)
+            }
+        };
+        let (left_suffix, right_suffix) = suffixes;
+        let left_keys = left_on
+            .iter()
+            .map(|name| col(&qualify(name, left_suffix)))
+            .collect();
+        let right_keys = right_on
+            .iter()
+            .map(|name| col(&qualify(name, right_suffix)))
+            .collect();
+
+        let left = self.clone().rename_with(|name| qualify(name, left_suffix));
+        let right = self.rename_with(|name| qualify(name, right_suffix));
+        left.join(right, left_keys, right_keys, None, how)
+    }
+
+    /// Update the values of this LazyFrame with the values of the rows of `other` that have
+    /// equal values in the `on` columns. [See eager](polars_core::prelude::DataFrame::update).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// fn apply_corrections(prices: LazyFrame, corrections: LazyFrame) -> LazyFrame {
+    ///     prices.update(corrections, &["date", "ticker"], UpdateStrategy::PreferRight)
+    /// }
+    /// ```
+    pub fn update(self, other: LazyFrame, on: &[&str], strategy: UpdateStrategy) -> LazyFrame {
+        let schema = self.logical_plan.schema().clone();
+        let other_schema = other.logical_plan.schema().clone();
+        let update_fields: Vec<&Field> = other_schema
+            .fields()
+            .iter()
+            .filter(|fld| {
+                !on.contains(&fld.name().as_str()) && schema.field_with_name(fld.name()).is_ok()
+            })
+            .collect();
+
+        let keys: Vec<Expr> = on.iter().map(|name| col(name)).collect();
+        let right_columns: Vec<Expr> = keys
+            .iter()
+            .cloned()
+            .chain(update_fields.iter().map(|fld| col(fld.name())))
+            .collect();
+        let options = JoinOptions {
+            suffix: Some(UPDATE_SUFFIX.to_string()),
+            validate: JoinValidation::ManyToOne,
+            ..Default::default()
+        };
+        let joined = self.join(
+            other.select(right_columns),
+            keys.clone(),
+            keys,
+            Some(options),
+            JoinType::Left,
+        );
+
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|fld| {
+                let name = fld.name();
+                if let Some(upd) = update_fields.iter().find(|upd| upd.name() == name) {
+                    let left = col(name);
+                    let mut right = col(&format!("{}{}", name, UPDATE_SUFFIX));
+                    if upd.data_type() != fld.data_type() {
+                        right = right.cast(fld.data_type().clone());
+                    }
+                    match strategy {
+                        UpdateStrategy::PreferLeft => {
+                            when(left.clone().is_not_null()).then(left).otherwise(right)
+                        }
+                        UpdateStrategy::PreferRight => when(right.clone().is_not_null())
+                            .then(right)
+                            .otherwise(left),
+                    }
+                    .alias(name)
+                } else {
+                    col(name)
+                }
+            })
+            .collect::<Vec<_>>();
+        joined.select(columns)
+    }
+
+    /// Apply a set operation on the rows of this LazyFrame and `other`.
+    /// See [DataFrame::set_operation](polars_core::frame::DataFrame::set_operation).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// fn new_customers(today: LazyFrame, yesterday: LazyFrame) -> LazyFrame {
+    ///     today.set_operation(yesterday, SetOperation::Difference)
+    /// }
+    /// ```
+    pub fn set_operation(self, other: LazyFrame, op: SetOperation) -> LazyFrame {
+        let opt_state = self.get_opt_state();
+        let lp = self
+            .get_plan_builder()
+            .set_operation(other.logical_plan, op)
+            .build();
+        Self::from_logical_plan(lp, opt_state)
+    }
+
+    /// Get the distinct rows that are in this LazyFrame or in `other`.
+    pub fn union(self, other: LazyFrame) -> LazyFrame {
+        self.set_operation(other, SetOperation::Union)
+    }
+
+    /// Get the distinct rows that are both in this LazyFrame and in `other`.
+    pub fn intersect(self, other: LazyFrame) -> LazyFrame {
+        self.set_operation(other, SetOperation::Intersect)
+    }
+
+    /// Get the distinct rows of this LazyFrame that are not in `other`.
+    pub fn difference(self, other: LazyFrame) -> LazyFrame {
+        self.set_operation(other, SetOperation::Difference)
+    }
+
+    /// Get the distinct rows of this LazyFrame that are not in `other`, optionally only
+    /// comparing the `on` columns. See [the eager implementation](polars_core::frame::DataFrame::except).
+    pub fn except(self, other: LazyFrame, on: Option<&[&str]>) -> LazyFrame {
+        let on = match on {
+            Some(on) => on,
+            None => return self.difference(other),
+        };
+        let columns: Vec<Expr> = self
+            .logical_plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|fld| col(fld.name()))
+            .collect();
+        let keys: Vec<Expr> = on.iter().map(|name| col(name)).collect();
+        let right = other
+            .select(keys.clone())
+            .drop_duplicates(false, None)
+            .with_column(lit(true).alias(EXCEPT_MARKER_NAME));
+        self.join(right, keys.clone(), keys, None, JoinType::Left)
+            .filter(col(EXCEPT_MARKER_NAME).is_null())
+            .select(columns)
+            .drop_duplicates(true, None)
+    }
+
+    /// Get the distinct rows that are in only one of this LazyFrame and `other`.
+    pub fn symmetric_difference(self, other: LazyFrame) -> LazyFrame {
+        self.set_operation(other, SetOperation::SymmetricDifference)
+    }
+
     /// Add a column to a DataFrame
     ///
     /// # Example
@@ -752,71 +1888,133 @@ impl LazyFrame {
     ///          )
     /// }
     /// ```
-    pub fn with_columns(self, exprs: Vec<Expr>) -> LazyFrame {
+    pub fn with_columns<I, E>(self, exprs: I) -> LazyFrame
+    where
+        I: IntoIterator<Item = E>,
+        E: Into<Expr>,
+    {
         let opt_state = self.get_opt_state();
-        let lp = self.get_plan_builder().with_columns(exprs).build();
+        let lp = self
+            .get_plan_builder()
+            .with_columns(exprs.into_iter().map(Into::into).collect())
+            .build();
         Self::from_logical_plan(lp, opt_state)
     }
 
-    /// Aggregate all the columns as their maximum values.
-    pub fn max(self) -> LazyFrame {
-        self.select_local(vec![col("*").max()])
-    }
-
-    /// Aggregate all the columns as their minimum values.
-    pub fn min(self) -> LazyFrame {
-        self.select_local(vec![col("*").min()])
+    /// Cast the columns in the given Schema to its dtypes. The given schema may be a subset of
+    /// the total schema.
+    ///
+    /// Directly after a scan this fixes the dtypes of a file in the query plan, where the
+    /// optimizer only casts the columns that are used.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// fn scan_prices(path: String) -> LazyFrame {
+    ///     let dtypes = Schema::new(vec![Field::new("price", DataType::Float64)]);
+    ///     LazyFrame::new_from_parquet(path, None, true).cast(&dtypes)
+    /// }
+    /// ```
+    pub fn cast(self, dtypes: &Schema) -> LazyFrame {
+        let opt_state = self.get_opt_state();
+        let lp = self.get_plan_builder().cast(dtypes).build();
+        Self::from_logical_plan(lp, opt_state)
     }
 
-    /// Aggregate all the columns as their sum values.
-    pub fn sum(self) -> LazyFrame {
-        self.select_local(vec![col("*").sum()])
+    /// Aggregate every column with `agg`. Like the eager aggregations, columns of which the data
+    /// type doesn't support the aggregation become null.
+    fn agg_all_columns<F>(self, method: GroupByMethod, agg: F) -> LazyFrame
+    where
+        F: Fn(Expr) -> Expr,
+    {
+        let exprs = self
+            .logical_plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| {
+                let name = field.name();
+                match groupby_output_dtype(field.data_type(), method) {
+                    Ok(_) => agg(col(name)),
+                    Err(_) => Expr::Literal(LiteralValue::Null)
+                        .cast(field.data_type().clone())
+                        .alias(name),
+                }
+            })
+            .collect();
+        self.select_local(exprs)
+    }
+
+    /// Aggregate all the columns as their maximum values.
+    pub fn max(self) -> LazyFrame {
+        self.agg_all_columns(GroupByMethod::Max, |e| e.max())
+    }
+
+    /// Aggregate all the columns as their minimum values.
+    pub fn min(self) -> LazyFrame {
+        self.agg_all_columns(GroupByMethod::Min, |e| e.min())
+    }
+
+    /// Aggregate all the columns as their sum values.
+    pub fn sum(self) -> LazyFrame {
+        self.agg_all_columns(GroupByMethod::Sum, |e| e.sum())
     }
 
     /// Aggregate all the columns as their mean values.
     pub fn mean(self) -> LazyFrame {
-        self.select_local(vec![col("*").mean()])
+        self.agg_all_columns(GroupByMethod::Mean, |e| e.mean())
     }
 
     /// Aggregate all the columns as their median values.
     pub fn median(self) -> LazyFrame {
-        self.select_local(vec![col("*").median()])
+        self.agg_all_columns(GroupByMethod::Median, |e| e.median())
     }
 
     /// Aggregate all the columns as their quantile values.
     pub fn quantile(self, quantile: f64) -> LazyFrame {
-        self.select_local(vec![col("*").quantile(quantile)])
+        self.agg_all_columns(GroupByMethod::Quantile(quantile), |e| e.quantile(quantile))
     }
 
     /// Aggregate all the columns as their standard deviation values.
     pub fn std(self) -> LazyFrame {
-        self.select_local(vec![col("*").std()])
+        self.agg_all_columns(GroupByMethod::Std, |e| e.std())
     }
 
     /// Aggregate all the columns as their variance values.
     pub fn var(self) -> LazyFrame {
-        self.select_local(vec![col("*").var()])
+        self.agg_all_columns(GroupByMethod::Var, |e| e.var())
     }
 
     /// Apply explode operation. [See eager explode](polars_core::frame::DataFrame::explode).
+    /// The order of the rows is always maintained.
     pub fn explode(self, columns: &[Expr]) -> LazyFrame {
         let columns = columns
             .iter()
             .map(|e| {
                 if let Expr::Column(name) = e {
-                    (**name).clone()
+                    Ok((**name).clone())
                 } else {
-                    panic!("expected column expression")
+                    Err(PolarsError::InvalidOperation(
+                        format!("can only explode columns, not {:?}", e).into(),
+                    ))
                 }
             })
-            .collect();
+            .collect::<Result<_>>();
         // Note: this operation affects multiple columns. Therefore it isn't implemented as expression.
         let opt_state = self.get_opt_state();
-        let lp = self.get_plan_builder().explode(columns).build();
+        let lp = match columns {
+            Ok(columns) => self.get_plan_builder().explode(columns),
+            Err(err) => self.get_plan_builder().error(err),
+        }
+        .build();
         Self::from_logical_plan(lp, opt_state)
     }
 
     /// Drop duplicate rows. [See eager](polars_core::prelude::DataFrame::drop_duplicates).
+    /// With `maintain_order` the first occurrences are kept in their original order.
     pub fn drop_duplicates(self, maintain_order: bool, subset: Option<Vec<String>>) -> LazyFrame {
         let opt_state = self.get_opt_state();
         let lp = self
@@ -826,6 +2024,52 @@ impl LazyFrame {
         Self::from_logical_plan(lp, opt_state)
     }
 
+    /// Drop rows with duplicate values of the `subset` expressions, e.g. a normalized column.
+    ///
+    /// Expressions that are not plain columns are added as temporary key columns, which are
+    /// dropped again after the duplicates are removed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// // keep the first row of every whole price
+    /// fn example(df: DataFrame) -> LazyFrame {
+    ///       df.lazy()
+    ///         .drop_duplicates_by(true, vec![col("price").cast(DataType::Int64)])
+    /// }
+    /// ```
+    pub fn drop_duplicates_by(self, maintain_order: bool, subset: Vec<Expr>) -> LazyFrame {
+        let columns = self
+            .logical_plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|fld| col(fld.name()))
+            .collect::<Vec<_>>();
+
+        let mut keys = Vec::with_capacity(subset.len());
+        let mut key_names = Vec::with_capacity(subset.len());
+        for (i, e) in subset.into_iter().enumerate() {
+            match e {
+                Expr::Column(name) => key_names.push((*name).clone()),
+                e => {
+                    let name = format!("{}{}", DISTINCT_KEY_PREFIX, i);
+                    keys.push(e.alias(&name));
+                    key_names.push(name);
+                }
+            }
+        }
+        if keys.is_empty() {
+            return self.drop_duplicates(maintain_order, Some(key_names));
+        }
+        self.with_columns(keys)
+            .drop_duplicates(maintain_order, Some(key_names))
+            .select(columns)
+    }
+
     /// Drop null rows.
     ///
     /// Equal to `LazyFrame::filter(col("*").is_not_null())`
@@ -840,6 +2084,62 @@ impl LazyFrame {
         }
     }
 
+    /// Drop `columns` by projecting the remaining columns in their original order.
+    /// If `strict`, dropping a column that doesn't exist is an error, otherwise it is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// fn example(df: DataFrame) -> LazyFrame {
+    ///       df.lazy()
+    ///         .drop_columns(&["foo", "bar"], true)
+    /// }
+    /// ```
+    pub fn drop_columns<I, S>(self, columns: I, strict: bool) -> LazyFrame
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let schema = self.logical_plan.schema();
+        let mut drop = HashSet::new();
+        let mut err = None;
+        for name in columns {
+            let name = name.as_ref();
+            if strict && schema.field_with_name(name).is_err() {
+                err = Some(PolarsError::NotFound(format!(
+                    "cannot drop column {}, it is not in the schema",
+                    name
+                )));
+                break;
+            }
+            drop.insert(name.to_string());
+        }
+        let exprs = schema
+            .fields()
+            .iter()
+            .filter(|fld| !drop.contains(fld.name()))
+            .map(|fld| col(fld.name()))
+            .collect::<Vec<_>>();
+        // an empty projection would select all columns
+        if err.is_none() && exprs.is_empty() {
+            err = Some(PolarsError::InvalidOperation(
+                "cannot drop all columns of a LazyFrame".into(),
+            ));
+        }
+
+        match err {
+            Some(err) => {
+                let opt_state = self.get_opt_state();
+                let lp = self.get_plan_builder().error(err).build();
+                Self::from_logical_plan(lp, opt_state)
+            }
+            None => self.select(exprs),
+        }
+    }
+
     /// Slice the DataFrame.
     pub fn slice(self, offset: usize, len: usize) -> LazyFrame {
         let opt_state = self.get_opt_state();
@@ -852,12 +2152,18 @@ impl LazyFrame {
         self.slice(0, 1)
     }
 
-    /// Melt the DataFrame from wide to long format
-    pub fn melt(self, id_vars: Vec<String>, value_vars: Vec<String>) -> LazyFrame {
+    /// Melt the DataFrame from wide to long format.
+    /// With `maintain_order` the rows keep their order, [see eager](polars_core::frame::DataFrame::melt).
+    pub fn melt(
+        self,
+        id_vars: Vec<String>,
+        value_vars: Vec<String>,
+        maintain_order: bool,
+    ) -> LazyFrame {
         let opt_state = self.get_opt_state();
         let lp = self
             .get_plan_builder()
-            .melt(Arc::new(id_vars), Arc::new(value_vars))
+            .melt(Arc::new(id_vars), Arc::new(value_vars), maintain_order)
             .build();
         Self::from_logical_plan(lp, opt_state)
     }
@@ -891,10 +2197,201 @@ impl LazyFrame {
                 function,
                 optimizations.unwrap_or_default(),
                 schema.map(Arc::new),
+                None,
+            )
+            .build();
+        Self::from_logical_plan(lp, opt_state)
+    }
+
+    /// Apply a function/closure that only reads the `input_columns` once the logical plan gets
+    /// executed, see [map](LazyFrame::map). The projection pushdown projects these columns below
+    /// the function, so the function receives only these columns and scans don't read the others.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// fn example(df: DataFrame, schema: Schema) -> LazyFrame {
+    ///     // only "foo" is read from `df`
+    ///     df.lazy().map_with_input_columns(
+    ///         |df: DataFrame| df.select("foo"),
+    ///         &["foo"],
+    ///         None,
+    ///         Some(schema),
+    ///     )
+    /// }
+    /// ```
+    pub fn map_with_input_columns<F>(
+        self,
+        function: F,
+        input_columns: &[&str],
+        optimizations: Option<AllowedOptimizations>,
+        schema: Option<Schema>,
+    ) -> LazyFrame
+    where
+        F: DataFrameUdf + 'static,
+    {
+        let opt_state = self.get_opt_state();
+        let input_columns = input_columns.iter().map(|s| s.to_string()).collect();
+        let lp = self
+            .get_plan_builder()
+            .map(
+                function,
+                optimizations.unwrap_or_default(),
+                schema.map(Arc::new),
+                Some(Arc::new(input_columns)),
             )
             .build();
         Self::from_logical_plan(lp, opt_state)
     }
+
+    /// Add the columns of the DataFrame that `function` returns for the Series of the `inputs`.
+    /// The function is called once, so several columns can be derived from a single expensive
+    /// computation, e.g. parsing a composite string, instead of repeating it for every column.
+    ///
+    /// The `output_schema` declares the names and data types of the added columns, so that the
+    /// rest of the query can be planned. Executing the query returns an error if the DataFrame of
+    /// `function` doesn't have these columns or doesn't have a row for every input row.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// /// Split "EUR/USD" pairs into a base and a quote column.
+    /// fn split_pairs(ldf: LazyFrame) -> LazyFrame {
+    ///     let schema = Schema::new(vec![
+    ///         Field::new("base", DataType::Utf8),
+    ///         Field::new("quote", DataType::Utf8),
+    ///     ]);
+    ///     ldf.with_udf_columns(
+    ///         vec![col("pair")],
+    ///         |inputs: &[Series]| {
+    ///             let pairs = inputs[0].utf8()?;
+    ///             let base: Utf8Chunked = pairs
+    ///                 .into_iter()
+    ///                 .map(|opt_v| opt_v.and_then(|v| v.split('/').next()))
+    ///                 .collect();
+    ///             let quote: Utf8Chunked = pairs
+    ///                 .into_iter()
+    ///                 .map(|opt_v| opt_v.and_then(|v| v.split('/').nth(1)))
+    ///                 .collect();
+    ///             DataFrame::new(vec![
+    ///                 base.into_series().rename("base").clone(),
+    ///                 quote.into_series().rename("quote").clone(),
+    ///             ])
+    ///         },
+    ///         schema,
+    ///     )
+    /// }
+    /// ```
+    pub fn with_udf_columns<F>(
+        self,
+        inputs: Vec<Expr>,
+        function: F,
+        output_schema: Schema,
+    ) -> LazyFrame
+    where
+        F: 'static + Fn(&[Series]) -> Result<DataFrame> + Send + Sync,
+    {
+        let input_names: Vec<String> = (0..inputs.len())
+            .map(|i| format!("{}{}", UDF_INPUT_PREFIX, i))
+            .collect();
+        let inputs: Vec<Expr> = inputs
+            .into_iter()
+            .zip(&input_names)
+            .map(|(e, name)| e.alias(name))
+            .collect();
+        let mut fields = self.logical_plan.schema().fields().clone();
+        fields.extend(output_schema.fields().iter().cloned());
+        let schema = Schema::new(fields);
+
+        let udf = move |mut df: DataFrame| {
+            let inputs = input_names
+                .iter()
+                .map(|name| df.drop_in_place(name))
+                .collect::<Result<Vec<_>>>()?;
+            let out = function(&inputs)?;
+            if out.height() != df.height() {
+                return Err(PolarsError::ShapeMisMatch(
+                    format!(
+                        "the udf returned {} rows for {} input rows",
+                        out.height(),
+                        df.height()
+                    )
+                    .into(),
+                ));
+            }
+            let columns = output_schema
+                .fields()
+                .iter()
+                .map(|fld| {
+                    let s = out.column(fld.name())?;
+                    if s.dtype() == fld.data_type() {
+                        Ok(s.clone())
+                    } else {
+                        Err(PolarsError::DataTypeMisMatch(
+                            format!(
+                                "the udf returned column {} of type {:?} instead of {:?}",
+                                fld.name(),
+                                s.dtype(),
+                                fld.data_type()
+                            )
+                            .into(),
+                        ))
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?;
+            df.hstack_mut(&columns)?;
+            Ok(df)
+        };
+        // the udf needs its inputs and creates the columns that predicates may refer to
+        let optimizations = AllowedOptimizations {
+            projection_pushdown: false,
+            predicate_pushdown: false,
+            ..Default::default()
+        };
+        self.with_columns(inputs)
+            .map(udf, Some(optimizations), Some(schema))
+    }
+
+    /// Apply a function/closure on batches of at most `batch_size` rows once the logical plan
+    /// get executed. The batches are processed one after another and the results are stacked
+    /// vertically, so the function never sees more than `batch_size` rows at once.
+    ///
+    /// ## Warning
+    /// The function must be row-wise (e.g. scoring or enriching rows), as it never sees the whole
+    /// DataFrame. The same schema warning as [map](LazyFrame::map) applies.
+    pub fn map_partitions<F>(
+        self,
+        function: F,
+        batch_size: usize,
+        optimizations: Option<AllowedOptimizations>,
+        schema: Option<Schema>,
+    ) -> LazyFrame
+    where
+        F: DataFrameUdf + 'static,
+    {
+        let batch_size = std::cmp::max(batch_size, 1);
+        let udf = move |df: DataFrame| {
+            let height = df.height();
+            if height <= batch_size {
+                return function.call_udf(df);
+            }
+            let dfs = (0..height)
+                .step_by(batch_size)
+                .map(|offset| {
+                    let batch = df.slice(offset, std::cmp::min(batch_size, height - offset))?;
+                    function.call_udf(batch)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            accumulate_dataframes_vertical(dfs)
+        };
+        self.map(udf, optimizations, schema)
+    }
 }
 
 /// Utility struct for lazy groupby operation.
@@ -927,7 +2424,12 @@ impl LazyGroupBy {
     ///        .sort("date", false)
     /// }
     /// ```
-    pub fn agg(self, aggs: Vec<Expr>) -> LazyFrame {
+    pub fn agg<I, E>(self, aggs: I) -> LazyFrame
+    where
+        I: IntoIterator<Item = E>,
+        E: Into<Expr>,
+    {
+        let aggs = aggs.into_iter().map(Into::into).collect();
         let lp = LogicalPlanBuilder::from(self.logical_plan)
             .groupby(Arc::new(self.keys), aggs, None)
             .build();
@@ -943,6 +2445,62 @@ impl LazyGroupBy {
             .build();
         LazyFrame::from_logical_plan(lp, self.opt_state)
     }
+
+    /// Count the distinct combinations of values in `columns` per group. The output has the keys
+    /// and a `{columns}_n_unique` column, where the names of the `columns` are joined by `_`.
+    /// See [the eager implementation](polars_core::frame::group_by::GroupBy::n_unique_multi).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// fn example(df: DataFrame) -> LazyFrame {
+    ///     // distinct (user, session) pairs per day in the "user_session_n_unique" column
+    ///     df.lazy()
+    ///         .groupby(vec![col("date")])
+    ///         .n_unique_multi(&["user", "session"])
+    /// }
+    /// ```
+    pub fn n_unique_multi<S: AsRef<str>>(self, columns: &[S]) -> LazyFrame {
+        let columns: Vec<String> = columns.iter().map(|s| s.as_ref().to_string()).collect();
+        let lf = LazyFrame::from_logical_plan(self.logical_plan, self.opt_state);
+        let key_schema =
+            match expressions_to_schema(&self.keys, lf.logical_plan.schema(), Context::Other) {
+                Ok(schema) => schema,
+                Err(err) => {
+                    let opt_state = lf.get_opt_state();
+                    let lp = lf.get_plan_builder().error(err).build();
+                    return LazyFrame::from_logical_plan(lp, opt_state);
+                }
+            };
+        let key_names: Vec<String> = key_schema
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect();
+        let mut fields = key_schema.fields().clone();
+        fields.push(Field::new(
+            &fmt_groupby_column(&columns.join("_"), GroupByMethod::NUnique),
+            IdxType::get_dtype(),
+        ));
+
+        let input_columns: Vec<String> = key_names.iter().chain(&columns).cloned().collect();
+        let input_columns: Vec<&str> = input_columns.iter().map(|s| s.as_str()).collect();
+
+        // the predicates above refer to the counts, which don't exist below
+        let optimizations = AllowedOptimizations {
+            predicate_pushdown: false,
+            ..Default::default()
+        };
+        lf.with_columns(self.keys).map_with_input_columns(
+            move |df: DataFrame| df.groupby(&key_names)?.n_unique_multi(&columns),
+            &input_columns,
+            Some(optimizations),
+            Some(Schema::new(fields)),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -952,6 +2510,7 @@ mod test {
     use crate::tests::get_df;
     use polars_core::utils::chrono::{NaiveDate, NaiveDateTime, NaiveTime};
     use polars_core::*;
+    use std::sync::Mutex;
 
     fn scan_foods_csv() -> LazyFrame {
         let path = "../../examples/aggregate_multiple_files_in_chunks/datasets/foods1.csv";
@@ -1033,6 +2592,7 @@ mod test {
             .melt(
                 vec!["petal.width".to_string(), "petal.length".to_string()],
                 vec!["sepal.length".to_string(), "sepal.width".to_string()],
+                false,
             )
             .filter(col("variable").eq(lit("sepal.length")))
             .select(vec![col("variable"), col("petal.width"), col("value")])
@@ -1043,92 +2603,509 @@ mod test {
     }
 
     #[test]
-    fn test_lazy_drop_nulls() {
+    fn test_lazy_drop_duplicates_by() -> Result<()> {
         let df = df! {
-            "foo" => &[Some(1), None, Some(3)],
-            "bar" => &[Some(1), Some(2), None]
-        }
-        .unwrap();
+            "a" => [1.2, 3.5, 1.7, 2.0],
+            "b" => [0, 1, 2, 3],
+            "c" => ["x", "y", "x", "z"]
+        }?;
 
-        let new = df.clone().lazy().drop_nulls(None).collect().unwrap();
-        let out = df! {
-            "foo" => &[Some(1)],
-            "bar" => &[Some(1)]
-        }
-        .unwrap();
-        assert!(new.frame_equal(&out));
-    }
+        // the root column "a" of the key is projected, although only "b" is selected
+        let out = df
+            .clone()
+            .lazy()
+            .drop_duplicates_by(true, vec![col("a").cast(DataType::Int64)])
+            .select(&[col("b")])
+            .collect()?;
+        assert_eq!(out.get_column_names(), &["b"]);
+        assert_eq!(
+            Vec::from(out.column("b")?.i32()?),
+            &[Some(0), Some(1), Some(3)]
+        );
 
-    #[test]
-    fn test_lazy_udf() {
-        let df = get_df();
-        let new = df
+        let out = df
             .lazy()
-            .select(&[col("sepal.width").map(|s| Ok(s * 200.0), None)])
-            .collect()
-            .unwrap();
+            .drop_duplicates_by(true, vec![col("c"), col("b").gt(lit(5))])
+            .collect()?;
+        assert_eq!(out.get_column_names(), &["a", "b", "c"]);
         assert_eq!(
-            new.column("sepal.width").unwrap().f64().unwrap().get(0),
-            Some(700.0)
+            Vec::from(out.column("b")?.i32()?),
+            &[Some(0), Some(1), Some(3)]
         );
+        Ok(())
     }
 
     #[test]
-    fn test_lazy_is_null() {
-        let df = get_df();
-        let new = df
+    fn test_lazy_self_join() -> Result<()> {
+        let df = df![
+            "id" => &[1, 2, 3],
+            "manager_id" => &[None, Some(1), Some(1)],
+            "name" => &["ann", "bob", "cid"]
+        ]?;
+
+        let out = df
             .clone()
             .lazy()
-            .filter(col("sepal.width").is_null())
-            .collect()
-            .unwrap();
+            .self_join(
+                &["manager_id"],
+                &["id"],
+                JoinType::Left,
+                ("_employee", "_manager"),
+            )
+            .collect()?;
+        assert_eq!(
+            out.get_column_names(),
+            &[
+                "id_employee",
+                "manager_id_employee",
+                "name_employee",
+                "manager_id_manager",
+                "name_manager"
+            ]
+        );
+        assert_eq!(
+            Vec::from(out.column("name_manager")?.utf8()?),
+            &[None, Some("ann"), Some("ann")]
+        );
 
-        assert_eq!(new.height(), 0);
+        let out = df
+            .lazy()
+            .self_join(&["id"], &["id"], JoinType::Inner, ("_l", "_r"))
+            .collect()?;
+        assert_eq!(
+            out.get_column_names(),
+            &["id", "manager_id_l", "name_l", "manager_id_r", "name_r"]
+        );
+        Ok(())
+    }
 
-        let new = df
+    #[test]
+    fn test_lazy_except() -> Result<()> {
+        let df_a = df![
+            "a" => &[Some(1), Some(2), None, Some(2), Some(3)],
+            "b" => &["x", "y", "z", "y", "w"]
+        ]?;
+        let df_b = df![
+            "a" => &[None, Some(1), Some(1)],
+            "b" => &["z", "q", "q"]
+        ]?;
+
+        let out = df_a
             .clone()
             .lazy()
-            .filter(col("sepal.width").is_not_null())
-            .collect()
-            .unwrap();
-        assert_eq!(new.height(), df.height());
+            .except(df_b.clone().lazy(), Some(&["a"]))
+            .collect()?;
+        assert!(out.frame_equal(&df_a.except(&df_b, Some(&["a"]))?));
+        assert_eq!(Vec::from(out.column("b")?.utf8()?), &[Some("y"), Some("w")]);
 
-        let new = df
+        let out = df_a
+            .clone()
             .lazy()
-            .groupby(vec![col("variety")])
-            .agg(vec![col("sepal.width").min()])
-            .collect()
-            .unwrap();
-
-        println!("{:?}", new);
-        assert_eq!(new.shape(), (1, 2));
+            .except(df_b.clone().lazy(), None)
+            .collect()?;
+        assert!(out.frame_equal(&df_a.except(&df_b, None)?));
+        assert_eq!(out.height(), 3);
+        Ok(())
     }
 
     #[test]
-    fn test_lazy_pushdown_through_agg() {
-        // An aggregation changes the schema names, check if the pushdown succeeds.
-        let df = get_df();
-        let new = df
-            .lazy()
-            .groupby(vec![col("variety")])
-            .agg(vec![
-                col("sepal.length").min(),
-                col("petal.length").min().alias("foo"),
+    fn test_lazy_with_udf_columns() -> Result<()> {
+        let df = df![
+            "range" => &["1-3", "2-5", "4-4"],
+            "b" => &[1, 2, 3]
+        ]?;
+        let schema = Schema::new(vec![
+            Field::new("low", DataType::Int64),
+            Field::new("high", DataType::Int64),
+        ]);
+        let split = |inputs: &[Series]| {
+            let bounds = inputs[0]
+                .utf8()?
+                .into_no_null_iter()
+                .map(|v| {
+                    let mut parts = v.split('-').map(|p| p.parse::<i64>().unwrap());
+                    (parts.next().unwrap(), parts.next().unwrap())
+                })
+                .collect::<Vec<_>>();
+            DataFrame::new(vec![
+                Series::new("low", bounds.iter().map(|b| b.0).collect::<Vec<_>>()),
+                Series::new("high", bounds.iter().map(|b| b.1).collect::<Vec<_>>()),
             ])
-            .select(&[col("foo")])
-            // second selection is to test if optimizer can handle that
-            .select(&[col("foo").alias("bar")])
+        };
+
+        let out = df
+            .clone()
+            .lazy()
+            .with_udf_columns(vec![col("range")], split, schema.clone())
+            .filter(col("high").gt(lit(3)))
+            .select(vec![col("b"), col("low"), col("high")])
+            .collect()?;
+        assert_eq!(out.get_column_names(), &["b", "low", "high"]);
+        assert_eq!(Vec::from(out.column("low")?.i64()?), &[Some(2), Some(4)]);
+
+        let wrong_schema = Schema::new(vec![Field::new("low", DataType::Utf8)]);
+        assert!(df
+            .lazy()
+            .with_udf_columns(vec![col("range")], split, wrong_schema)
             .collect()
-            .unwrap();
+            .is_err());
+        Ok(())
+    }
 
-        println!("{:?}", new);
+    #[test]
+    fn test_lazy_outer_join_maintain_order() -> Result<()> {
+        let left = df!["key" => &[3, 1, 5], "a" => &[0, 1, 2]]?;
+        let right = df!["key" => &[4, 1, 3, 6], "b" => &[0, 1, 2, 3]]?;
+        let options = JoinOptions {
+            maintain_order: true,
+            ..Default::default()
+        };
+        let out = left
+            .lazy()
+            .outer_join(right.lazy(), col("key"), col("key"), Some(options))
+            .collect()?;
+        assert_eq!(
+            Vec::from(out.column("key")?.i32()?),
+            &[Some(3), Some(1), Some(5), Some(4), Some(6)]
+        );
+        assert_eq!(
+            Vec::from(out.column("b")?.i32()?),
+            &[Some(2), Some(1), None, Some(0), Some(3)]
+        );
+        Ok(())
     }
 
     #[test]
-    fn test_lazy_agg() {
-        let s0 = Date32Chunked::parse_from_str_slice(
-            "date",
-            &[
+    fn test_lazy_update() -> Result<()> {
+        let df = df![
+            "id" => &[1, 2, 3],
+            "price" => &[Some(1.0), None, None],
+            "name" => &["a", "b", "c"]
+        ]?;
+        let other = df![
+            "id" => &[3, 1, 4],
+            "price" => &[Some(30.0), Some(10.0), Some(40.0)],
+            "name" => &[Some("x"), None, Some("y")]
+        ]?;
+
+        let out = df
+            .clone()
+            .lazy()
+            .update(other.clone().lazy(), &["id"], UpdateStrategy::PreferRight)
+            .collect()?;
+        assert_eq!(out.get_column_names(), &["id", "price", "name"]);
+        assert_eq!(
+            Vec::from(out.column("price")?.f64()?),
+            &[Some(10.0), None, Some(30.0)]
+        );
+        assert_eq!(
+            Vec::from(out.column("name")?.utf8()?),
+            &[Some("a"), Some("b"), Some("x")]
+        );
+
+        let out = df
+            .lazy()
+            .update(other.lazy(), &["id"], UpdateStrategy::PreferLeft)
+            .collect()?;
+        assert_eq!(
+            Vec::from(out.column("price")?.f64()?),
+            &[Some(1.0), None, Some(30.0)]
+        );
+        assert_eq!(
+            Vec::from(out.column("name")?.utf8()?),
+            &[Some("a"), Some("b"), Some("c")]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_order_guarantees() -> Result<()> {
+        let df = df! {
+            "id" => [3, 1, 3, 2],
+            "a" => [10, 11, 12, 13],
+            "b" => [20, 21, 22, 23]
+        }?;
+
+        let out = df
+            .clone()
+            .lazy()
+            .melt(
+                vec!["id".to_string()],
+                vec!["a".to_string(), "b".to_string()],
+                true,
+            )
+            .filter(col("value").gt(lit(10)))
+            .collect()?;
+        assert_eq!(
+            Vec::from(out.column("value")?.i32()?),
+            &[
+                Some(20),
+                Some(11),
+                Some(21),
+                Some(12),
+                Some(22),
+                Some(13),
+                Some(23)
+            ]
+        );
+
+        let out = df
+            .lazy()
+            .drop_duplicates(true, Some(vec!["id".to_string()]))
+            .collect()?;
+        assert_eq!(
+            Vec::from(out.column("a")?.i32()?),
+            &[Some(10), Some(11), Some(13)]
+        );
+
+        let list = Series::new(
+            "list",
+            &[
+                Series::new("", &[1, 2]),
+                Series::new("", &[3]),
+                Series::new("", &[4, 5, 6]),
+            ],
+        );
+        let df = DataFrame::new(vec![list, Series::new("id", &[3, 1, 2])])?;
+        let out = df
+            .lazy()
+            .explode(&[col("list")])
+            .filter(col("list").neq(lit(5)))
+            .collect()?;
+        assert_eq!(
+            Vec::from(out.column("list")?.i32()?),
+            &[Some(1), Some(2), Some(3), Some(4), Some(6)]
+        );
+        assert_eq!(
+            Vec::from(out.column("id")?.i32()?),
+            &[Some(3), Some(3), Some(1), Some(2), Some(2)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_drop_nulls() {
+        let df = df! {
+            "foo" => &[Some(1), None, Some(3)],
+            "bar" => &[Some(1), Some(2), None]
+        }
+        .unwrap();
+
+        let new = df.clone().lazy().drop_nulls(None).collect().unwrap();
+        let out = df! {
+            "foo" => &[Some(1)],
+            "bar" => &[Some(1)]
+        }
+        .unwrap();
+        assert!(new.frame_equal(&out));
+    }
+
+    #[test]
+    fn test_lazy_udf() {
+        let df = get_df();
+        let new = df
+            .lazy()
+            .select(&[col("sepal.width").map(|s| Ok(s * 200.0), None)])
+            .collect()
+            .unwrap();
+        assert_eq!(
+            new.column("sepal.width").unwrap().f64().unwrap().get(0),
+            Some(700.0)
+        );
+    }
+
+    #[test]
+    fn test_lazy_map_partitions() {
+        let df = get_df();
+        let height = df.height();
+        let out = df
+            .lazy()
+            .map_partitions(
+                |df: DataFrame| {
+                    assert!(df.height() <= 2);
+                    Ok(df)
+                },
+                2,
+                None,
+                None,
+            )
+            .collect()
+            .unwrap();
+        assert_eq!(out.height(), height);
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn test_lazy_collect_async() {
+        let df = get_df();
+        let expected = df.clone().lazy().collect().unwrap();
+        let out = futures::executor::block_on(df.clone().lazy().collect_async()).unwrap();
+        assert!(out.frame_equal(&expected));
+        // more concurrent queries than threads in the pool
+        let queries =
+            (0..POOL.current_num_threads() * 4).map(|_| df.clone().lazy().collect_async());
+        let outs = futures::executor::block_on(futures::future::join_all(queries));
+        assert!(outs
+            .iter()
+            .all(|out| out.as_ref().unwrap().frame_equal(&expected)));
+
+        let query = df.lazy().map(
+            |_df: DataFrame| -> Result<DataFrame> { panic!("boom") },
+            None,
+            None,
+        );
+        let out = futures::executor::block_on(query.collect_async());
+        assert!(matches!(out, Err(PolarsError::Other(msg)) if msg.contains("boom")));
+    }
+
+    #[test]
+    fn test_lazy_cancellation() {
+        let df = get_df();
+        let token = CancellationToken::new();
+        assert!(df.clone().lazy().collect_cancellable(token.clone()).is_ok());
+        token.cancel();
+        let out = df.lazy().collect_cancellable(token);
+        assert!(matches!(out, Err(PolarsError::Cancelled)));
+    }
+
+    #[test]
+    fn test_lazy_progress() {
+        let df = get_df();
+        let events = Arc::new(Mutex::new(vec![]));
+        let events_clone = events.clone();
+        df.lazy()
+            .filter(col("sepal.width").gt(lit(3.0)))
+            .collect_with_progress(move |event: ProgressEvent| {
+                events_clone.lock().unwrap().push(event)
+            })
+            .unwrap();
+        let events = events.lock().unwrap();
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ProgressEvent::NodeFinished {
+                node: "dataframe scan",
+                ..
+            }
+        )));
+    }
+
+    #[test]
+    fn test_lazy_observer() {
+        let df = get_df();
+        let events = Arc::new(Mutex::new(vec![]));
+        let events_clone = events.clone();
+        df.lazy()
+            .select(&[col("sepal.width")])
+            .collect_with_observer(move |event: &QueryEvent| {
+                events_clone.lock().unwrap().push(event.clone())
+            })
+            .unwrap();
+        let events = events.lock().unwrap();
+        assert_eq!(events[0], QueryEvent::PlanStarted);
+        assert!(matches!(
+            events.last(),
+            Some(QueryEvent::PlanFinished { rows: 7, .. })
+        ));
+    }
+
+    #[test]
+    fn test_lazy_observer_bytes_read_and_failure() -> Result<()> {
+        let path = "../../examples/aggregate_multiple_files_in_chunks/datasets/foods1.csv";
+        let len = std::fs::metadata(path)?.len();
+
+        let events = Arc::new(Mutex::new(vec![]));
+        let events_clone = events.clone();
+        scan_foods_csv().collect_with_observer(move |event: &QueryEvent| {
+            events_clone.lock().unwrap().push(event.clone())
+        })?;
+        let read: Vec<_> = events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|event| match event {
+                QueryEvent::BytesRead { bytes, .. } => Some(*bytes),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(read, [len]);
+
+        let events = Arc::new(Mutex::new(vec![]));
+        let events_clone = events.clone();
+        let out = get_df()
+            .lazy()
+            .map(
+                |_: DataFrame| -> Result<DataFrame> { Err(PolarsError::Other("udf".into())) },
+                None,
+                None,
+            )
+            .collect_with_observer(move |event: &QueryEvent| {
+                events_clone.lock().unwrap().push(event.clone())
+            });
+        assert!(out.is_err());
+        let events = events.lock().unwrap();
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, QueryEvent::NodeFailed { node: "udf", .. })));
+        assert!(matches!(events.last(), Some(QueryEvent::PlanFailed { .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_is_null() {
+        let df = get_df();
+        let new = df
+            .clone()
+            .lazy()
+            .filter(col("sepal.width").is_null())
+            .collect()
+            .unwrap();
+
+        assert_eq!(new.height(), 0);
+
+        let new = df
+            .clone()
+            .lazy()
+            .filter(col("sepal.width").is_not_null())
+            .collect()
+            .unwrap();
+        assert_eq!(new.height(), df.height());
+
+        let new = df
+            .lazy()
+            .groupby(vec![col("variety")])
+            .agg(vec![col("sepal.width").min()])
+            .collect()
+            .unwrap();
+
+        println!("{:?}", new);
+        assert_eq!(new.shape(), (1, 2));
+    }
+
+    #[test]
+    fn test_lazy_pushdown_through_agg() {
+        // An aggregation changes the schema names, check if the pushdown succeeds.
+        let df = get_df();
+        let new = df
+            .lazy()
+            .groupby(vec![col("variety")])
+            .agg(vec![
+                col("sepal.length").min(),
+                col("petal.length").min().alias("foo"),
+            ])
+            .select(&[col("foo")])
+            // second selection is to test if optimizer can handle that
+            .select(&[col("foo").alias("bar")])
+            .collect()
+            .unwrap();
+
+        println!("{:?}", new);
+    }
+
+    #[test]
+    fn test_lazy_agg() {
+        let s0 = Date32Chunked::parse_from_str_slice(
+            "date",
+            &[
                 "2020-08-21",
                 "2020-08-21",
                 "2020-08-22",
@@ -1464,6 +3441,34 @@ mod test {
             .frame_equal_missing(&df.reverse()))
     }
 
+    #[test]
+    fn test_lazy_rename_with() -> Result<()> {
+        let df = get_df();
+        let lf = df.lazy().rename_snake_case();
+        // all columns are renamed in a single projection
+        assert!(matches!(
+            lf.logical_plan,
+            LogicalPlan::Projection { ref input, .. } if matches!(**input, LogicalPlan::DataFrameScan { .. })
+        ));
+
+        let out = lf
+            .add_prefix("iris_")
+            .filter(col("iris_sepal_length").gt(lit(5.0)))
+            .collect()?;
+        assert_eq!(
+            out.get_column_names(),
+            &[
+                "iris_sepal_length",
+                "iris_sepal_width",
+                "iris_petal_length",
+                "iris_petal_width",
+                "iris_variety"
+            ]
+        );
+        assert_eq!(out.height(), 2);
+        Ok(())
+    }
+
     #[test]
     fn test_lazy_filter_and_rename() {
         let df = load_df();
@@ -1603,58 +3608,598 @@ mod test {
     }
 
     #[test]
-    fn test_lazy_double_projection() {
-        let df = df! {
-            "foo" => &[1, 2, 3]
-        }
-        .unwrap();
-        df.lazy()
-            .select(&[col("foo").alias("bar")])
-            .select(&[col("bar")])
-            .collect()
-            .unwrap();
+    fn test_lazy_join_where() -> Result<()> {
+        let windows = df! {
+            "id" => &[1, 2, 3],
+            "start" => &[0, 10, 20],
+            "end" => &[10, 20, 30]
+        }?;
+        let events = df! {
+            "id" => &[10, 11, 12, 13, 14, 15],
+            "ts" => &[5, 15, 10, 25, 40, 0]
+        }?;
+
+        // range join, pruned on the sorted "ts" column
+        let out = windows
+            .clone()
+            .lazy()
+            .join_where(
+                events.clone().lazy(),
+                vec![col("start").lt_eq(col("ts")), col("ts").lt(col("end"))],
+                None,
+            )
+            .collect()?;
+        assert_eq!(
+            out.get_column_names(),
+            &["id", "start", "end", "id_right", "ts"]
+        );
+        assert_eq!(
+            Vec::from(out.column("id")?.i32()?),
+            &[Some(1), Some(1), Some(2), Some(2), Some(3)]
+        );
+        assert_eq!(
+            Vec::from(out.column("id_right")?.i32()?),
+            &[Some(10), Some(15), Some(11), Some(12), Some(13)]
+        );
+
+        // not a column comparison, so every pair is visited
+        let out = windows
+            .lazy()
+            .join_where(
+                events.lazy(),
+                vec![(col("ts") - col("start"))
+                    .gt_eq(lit(0))
+                    .and((col("ts") - col("start")).lt(lit(3)))],
+                Some("_event".into()),
+            )
+            .filter(col("id").gt(lit(1)))
+            .collect()?;
+        assert_eq!(Vec::from(out.column("id")?.i32()?), &[Some(2)]);
+        assert_eq!(Vec::from(out.column("id_event")?.i32()?), &[Some(12)]);
+        Ok(())
     }
 
     #[test]
-    fn test_type_coercion() {
-        let df = df! {
-            "foo" => &[1, 2, 3],
-            "bar" => &[1.0, 2.0, 3.0]
-        }
-        .unwrap();
-
-        let lp = df.lazy().select(&[col("foo") * col("bar")]).logical_plan;
-
-        let mut expr_arena = Arena::new();
-        let mut lp_arena = Arena::new();
-        let rules: &mut [Box<dyn OptimizationRule>] = &mut [Box::new(TypeCoercionRule {})];
-
-        let optimizer = StackOptimizer {};
-        let mut lp_top = to_alp(lp, &mut expr_arena, &mut lp_arena);
-        lp_top = optimizer.optimize_loop(rules, &mut expr_arena, &mut lp_arena, lp_top);
-        let lp = node_to_lp(lp_top, &mut expr_arena, &mut lp_arena);
+    fn test_lazy_set_operations() -> Result<()> {
+        let df_a = df! {
+            "a" => &[1, 2, 3, 3],
+            "b" => &["x", "y", "z", "z"]
+        }?;
+        let df_b = df! {
+            "a" => &[3, 4],
+            "b" => &["z", "w"]
+        }?;
+
+        let out = df_a
+            .clone()
+            .lazy()
+            .intersect(df_b.clone().lazy())
+            .filter(col("a").gt(lit(1)))
+            .select(&[col("b")])
+            .collect()?;
+        assert_eq!(Vec::from(out.column("b")?.utf8()?), &[Some("z")]);
 
-        if let LogicalPlan::Projection { expr, .. } = lp {
-            if let Expr::BinaryExpr { left, right, .. } = &expr[0] {
-                assert!(matches!(&**left, Expr::Cast { .. }));
-                assert!(matches!(&**right, Expr::Cast { .. }));
-            } else {
-                panic!()
-            }
-        };
+        let out = df_a
+            .clone()
+            .lazy()
+            .difference(df_b.clone().lazy())
+            .collect()?;
+        assert!(out.frame_equal(&df_a.difference(&df_b)?));
+        Ok(())
     }
 
     #[test]
-    fn test_lazy_partition_agg() {
+    fn test_lazy_groupby_n_unique_multi() -> Result<()> {
         let df = df! {
-            "foo" => &[1, 1, 2, 2, 3],
-            "bar" => &[1.0, 1.0, 2.0, 2.0, 3.0]
-        }
-        .unwrap();
-
+            "g" => [1, 1, 1, 2, 2],
+            "a" => [1, 1, 2, 1, 1],
+            "b" => [Some("x"), Some("x"), Some("x"), None, None],
+            "c" => [1, 2, 3, 4, 5]
+        }?;
         let out = df
+            .clone()
             .lazy()
-            .groupby(vec![col("foo")])
+            .groupby(vec![col("g")])
+            .n_unique_multi(&["a", "b"])
+            .filter(col("a_b_n_unique").gt(lit(0u32)))
+            .sort("g", false)
+            .collect()?;
+        let expected = df
+            .groupby("g")?
+            .n_unique_multi(&["a", "b"])?
+            .sort("g", false)?;
+        assert_eq!(out.get_column_names(), &["g", "a_b_n_unique"]);
+        assert!(out.frame_equal(&expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_hash() -> Result<()> {
+        let df = df! {
+            "a" => &[1, 2, 1],
+            "b" => &["x", "y", "x"]
+        }?;
+
+        let out = df
+            .clone()
+            .lazy()
+            .select(&[
+                col("a").hash(7),
+                hash_exprs(vec![col("a"), col("b")], 7).alias("row_id"),
+            ])
+            .collect()?;
+        assert_eq!(
+            Vec::from(out.column("a")?.u64()?),
+            Vec::from(&df.column("a")?.hash(7)?)
+        );
+        assert_eq!(
+            Vec::from(out.column("row_id")?.u64()?),
+            Vec::from(&df.hash_rows(7)?)
+        );
+        assert!(df
+            .lazy()
+            .select(&[hash_exprs(vec![], 7)])
+            .collect()
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_join_validation() -> Result<()> {
+        let left = df! {
+            "key" => &[1, 1, 2],
+            "val" => &["a", "b", "c"]
+        }?;
+        let right = df! {
+            "key" => &[1, 2],
+            "other" => &["c", "d"]
+        }?;
+        let options = |validate| JoinOptions {
+            validate,
+            ..Default::default()
+        };
+
+        let out = left
+            .clone()
+            .lazy()
+            .left_join(
+                right.clone().lazy(),
+                col("key"),
+                col("key"),
+                Some(options(JoinValidation::ManyToOne)),
+            )
+            .collect()?;
+        assert_eq!(out.height(), 3);
+
+        let out = left
+            .lazy()
+            .left_join(
+                right.lazy(),
+                col("key"),
+                col("key"),
+                Some(options(JoinValidation::OneToOne)),
+            )
+            .collect();
+        assert!(out.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_join_strategy() -> Result<()> {
+        let left = df! {
+            "key" => &[3, 1, 2, 1, 4],
+            "val" => &[1, 2, 3, 4, 5]
+        }?;
+        let right = df! {
+            "key" => &[1, 2, 5],
+            "other" => &["a", "b", "c"]
+        }?;
+        let join = |strategy| {
+            left.clone()
+                .lazy()
+                .left_join(
+                    right.clone().lazy(),
+                    col("key"),
+                    col("key"),
+                    Some(JoinOptions {
+                        strategy,
+                        ..Default::default()
+                    }),
+                )
+                .sort("val", false)
+                .collect()
+        };
+
+        let threaded = join(JoinStrategy::Threaded)?;
+        let broadcast = join(JoinStrategy::Broadcast)?;
+        assert!(threaded.frame_equal_missing(&broadcast));
+        assert_eq!(broadcast.height(), 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_join_suffix() -> Result<()> {
+        let left = df! {
+            "key" => &[1, 2],
+            "val" => &["a", "b"]
+        }?;
+        let right = df! {
+            "key" => &[1, 2],
+            "val" => &["c", "d"]
+        }?;
+        let options = JoinOptions {
+            suffix: Some("_new".into()),
+            ..Default::default()
+        };
+
+        let out = left
+            .lazy()
+            .inner_join(right.lazy(), col("key"), col("key"), Some(options))
+            .select(&[col("val_new")])
+            .sort("val_new", false)
+            .collect()?;
+        assert_eq!(
+            Vec::from(out.column("val_new")?.utf8()?),
+            &[Some("c"), Some("d")]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_join_indicator() -> Result<()> {
+        let left = df! {
+            "key" => &[1, 2],
+            "val" => &["a", "b"]
+        }?;
+        let right = df! {
+            "key" => &[2, 3],
+            "other" => &["c", "d"]
+        }?;
+        let options = JoinOptions {
+            indicator: true,
+            ..Default::default()
+        };
+
+        let lf = left
+            .lazy()
+            .outer_join(right.lazy(), col("key"), col("key"), Some(options))
+            .select(&[col("key"), col(JOIN_INDICATOR_NAME)])
+            .sort("key", false);
+        assert_eq!(
+            lf.logical_plan
+                .schema()
+                .field_with_name(JOIN_INDICATOR_NAME)?
+                .data_type(),
+            &DataType::Categorical(CategoricalOrdering::Physical)
+        );
+        let out = lf.collect()?;
+        let indicator = out.column(JOIN_INDICATOR_NAME)?.cast::<Utf8Type>()?;
+        assert_eq!(
+            Vec::from(indicator.utf8()?),
+            &[Some("left_only"), Some("both"), Some("right_only")]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_cross_join() -> Result<()> {
+        let sizes = df! {
+            "size" => &["S", "M"]
+        }?;
+        let colors = df! {
+            "color" => &["red", "green", "blue"]
+        }?;
+
+        let out = sizes
+            .lazy()
+            .cross_join(colors.lazy())
+            .filter(col("color").neq(lit("green")))
+            .collect()?;
+        assert_eq!(out.get_column_names(), &["size", "color"]);
+        assert_eq!(
+            Vec::from(out.column("color")?.utf8()?),
+            &[Some("red"), Some("blue"), Some("red"), Some("blue")]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_cross_join_to_hash_join() -> Result<()> {
+        let orders = df! {
+            "id" => &[Some(1), Some(2), None, Some(3)],
+            "qty" => &[10, 20, 30, 40]
+        }?;
+        let items = df! {
+            "item_id" => &[Some(3), Some(1), None, Some(2)],
+            "qty" => &[50, 50, 1, 25]
+        }?;
+
+        let lf = orders.lazy().cross_join(items.lazy()).filter(
+            col("item_id")
+                .eq(col("id"))
+                .and(col("qty").lt(col("qty_right")))
+                .and(col("qty").gt(lit(10))),
+        );
+        // the equality became the key of a hash join
+        assert!(lf
+            .describe_optimized_plan()?
+            .contains("ON (left: [id] right: [item_id])"));
+
+        let out = lf.clone().collect()?.sort("id", false)?;
+        let expected = lf
+            .with_predicate_pushdown(false)
+            .collect()?
+            .sort("id", false)?;
+        assert_eq!(
+            out.get_column_names(),
+            &["id", "qty", "item_id", "qty_right"]
+        );
+        assert!(out.frame_equal_missing(&expected));
+        assert_eq!(
+            Vec::from(out.column("item_id")?.i32()?),
+            &[Some(2), Some(3)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_with_session_id() -> Result<()> {
+        let df = df! {
+            "user" => &["a", "b", "a", "a", "b"],
+            "time" => &[0, 5, 10, 100, 200]
+        }?;
+
+        let out = df
+            .clone()
+            .lazy()
+            .with_session_id("time", 30, &["user"], "session")
+            .filter(col("user").eq(lit("a")))
+            .select(&[col("session")])
+            .collect()?;
+        assert_eq!(
+            Vec::from(out.column("session")?.u32()?),
+            &[Some(0), Some(0), Some(1)]
+        );
+
+        // an existing column is replaced in place, as in the planned schema
+        let lf = df
+            .lazy()
+            .with_column(lit("web").alias("session"))
+            .with_column(col("user").alias("other"))
+            .with_session_id("time", 30, &["user"], "session");
+        let schema = lf.logical_plan.schema().clone();
+        let out = lf.collect()?;
+        assert_eq!(
+            out.get_column_names(),
+            &["user", "time", "session", "other"]
+        );
+        assert_eq!(out.schema(), schema);
+        assert_eq!(
+            Vec::from(out.column("session")?.u32()?),
+            &[Some(0), Some(0), Some(0), Some(1), Some(1)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_cumcount_over() -> Result<()> {
+        let df = df! {
+            "groups" => &[1, 1, 2, 2, 1, 2, 3, 3, 1],
+            "values" => &[1, 2, 3, 4, 5, 6, 7, 8, 8]
+        }?;
+
+        let out = df
+            .lazy()
+            .with_column(col("values").cumcount().over(col("groups")).alias("nr"))
+            .collect()?;
+        let nr = out.column("nr")?.cast::<UInt32Type>()?;
+        assert_eq!(
+            Vec::from(nr.u32()?),
+            &[0, 1, 0, 1, 2, 2, 0, 1, 3]
+                .iter()
+                .copied()
+                .map(Some)
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_clip_quantile() -> Result<()> {
+        let df = df! {
+            "groups" => &[1, 1, 1, 2, 2, 1, 2],
+            "values" => &[-50, 2, 3, 10, 20, 100, 30]
+        }?;
+
+        let out = df
+            .clone()
+            .lazy()
+            .select(vec![col("values").clip_quantile(0.2, 0.8)])
+            .collect()?;
+        assert_eq!(
+            Vec::from(out.column("values")?.i32()?),
+            &[
+                Some(2),
+                Some(2),
+                Some(3),
+                Some(10),
+                Some(20),
+                Some(30),
+                Some(30)
+            ]
+        );
+
+        let out = df
+            .clone()
+            .lazy()
+            .with_column(
+                col("values")
+                    .clip_quantile(0.25, 0.5)
+                    .over(col("groups"))
+                    .alias("clipped"),
+            )
+            .collect()?;
+        assert_eq!(
+            Vec::from(out.column("clipped")?.i32()?),
+            &[
+                Some(2),
+                Some(2),
+                Some(3),
+                Some(10),
+                Some(20),
+                Some(3),
+                Some(20)
+            ]
+        );
+
+        let out = df
+            .lazy()
+            .groupby(vec![col("groups")])
+            .agg(vec![col("values").clip_quantile(0.25, 0.5)])
+            .sort("groups", false)
+            .collect()?;
+        let lists = out.column("values")?.list()?;
+        let second = lists.get(1).unwrap();
+        assert_eq!(Vec::from(second.i32()?), &[Some(10), Some(20), Some(20)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_scale() -> Result<()> {
+        let df = df! {
+            "groups" => &[1, 1, 2, 2, 1],
+            "values" => &[0, 10, 20, 40, 5]
+        }?;
+
+        let out = df
+            .clone()
+            .lazy()
+            .select(vec![col("values").min_max_scale()])
+            .collect()?;
+        assert_eq!(
+            Vec::from(out.column("values")?.f64()?),
+            &[Some(0.0), Some(0.25), Some(0.5), Some(1.0), Some(0.125)]
+        );
+
+        let out = df
+            .clone()
+            .lazy()
+            .with_columns(vec![
+                col("values")
+                    .min_max_scale()
+                    .over(col("groups"))
+                    .alias("scaled"),
+                col("values").standardize().over(col("groups")).alias("z"),
+            ])
+            .collect()?;
+        assert_eq!(
+            Vec::from(out.column("scaled")?.f64()?),
+            &[Some(0.0), Some(1.0), Some(0.0), Some(1.0), Some(0.5)]
+        );
+        let expected = [-1.0, 1.0, -0.5f64.sqrt(), 0.5f64.sqrt(), 0.0];
+        for (z, expected) in out.column("z")?.f64()?.into_no_null_iter().zip(&expected) {
+            assert!((z - expected).abs() < 1e-12);
+        }
+
+        let out = df
+            .lazy()
+            .groupby(vec![col("groups")])
+            .agg(vec![col("values").min_max_scale()])
+            .sort("groups", false)
+            .collect()?;
+        let lists = out.column("values")?.list()?;
+        let first = lists.get(0).unwrap();
+        assert_eq!(Vec::from(first.f64()?), &[Some(0.0), Some(1.0), Some(0.5)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_null_count() -> Result<()> {
+        let df = df! {
+            "g" => &[1, 1, 2, 2],
+            "a" => &[Some(1), None, None, None],
+            "b" => &[Some(1.0), Some(2.0), Some(3.0), None]
+        }?;
+
+        let out = df
+            .clone()
+            .lazy()
+            .groupby(vec![col("g")])
+            .agg(vec![col("*").null_count()])
+            .sort("g", false)
+            .collect()?;
+        assert_eq!(
+            Vec::from(out.column("a_null_count")?.idx()?),
+            &[Some(1), Some(2)]
+        );
+        assert_eq!(
+            Vec::from(out.column("b_null_count")?.idx()?),
+            &[Some(0), Some(1)]
+        );
+
+        let out = df
+            .lazy()
+            .select(vec![col("a").null_count(), col("b").null_count()])
+            .collect()?;
+        assert_eq!(Vec::from(out.column("a")?.idx()?), &[Some(3)]);
+        assert_eq!(Vec::from(out.column("b")?.idx()?), &[Some(1)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_double_projection() {
+        let df = df! {
+            "foo" => &[1, 2, 3]
+        }
+        .unwrap();
+        df.lazy()
+            .select(&[col("foo").alias("bar")])
+            .select(&[col("bar")])
+            .collect()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_type_coercion() {
+        let df = df! {
+            "foo" => &[1, 2, 3],
+            "bar" => &[1.0, 2.0, 3.0]
+        }
+        .unwrap();
+
+        let lp = df.lazy().select(&[col("foo") * col("bar")]).logical_plan;
+
+        let mut expr_arena = Arena::new();
+        let mut lp_arena = Arena::new();
+        let rules: &mut [Box<dyn OptimizationRule>] = &mut [Box::new(TypeCoercionRule {})];
+
+        let optimizer = StackOptimizer {};
+        let mut lp_top = to_alp(lp, &mut expr_arena, &mut lp_arena);
+        lp_top = optimizer.optimize_loop(rules, &mut expr_arena, &mut lp_arena, lp_top);
+        let lp = node_to_lp(lp_top, &mut expr_arena, &mut lp_arena);
+
+        if let LogicalPlan::Projection { expr, .. } = lp {
+            if let Expr::BinaryExpr { left, right, .. } = &expr[0] {
+                assert!(matches!(&**left, Expr::Cast { .. }));
+                // "bar" already has the supertype
+                assert!(matches!(&**right, Expr::Column(_)));
+            } else {
+                panic!()
+            }
+        };
+    }
+
+    #[test]
+    fn test_lazy_partition_agg() {
+        let df = df! {
+            "foo" => &[1, 1, 2, 2, 3],
+            "bar" => &[1.0, 1.0, 2.0, 2.0, 3.0]
+        }
+        .unwrap();
+
+        let out = df
+            .lazy()
+            .groupby(vec![col("foo")])
             .agg(vec![col("bar").mean()])
             .sort("foo", false)
             .collect()
@@ -1690,6 +4235,120 @@ mod test {
         )
     }
 
+    #[test]
+    #[cfg(feature = "ipc")]
+    fn test_lazy_scan_ipc() -> Result<()> {
+        use polars_io::prelude::{IpcWriter, SerWriter};
+
+        let mut df = scan_foods_csv().collect()?;
+        let path = std::env::temp_dir().join("polars_test_lazy_scan_ipc.ipc");
+        let mut file = std::fs::File::create(&path)?;
+        IpcWriter::new(&mut file).finish(&mut df)?;
+
+        let path = path.to_string_lossy().to_string();
+        let out = LazyFrame::scan_ipc(path.clone(), None, false)
+            .filter(col("calories").gt(lit(100)))
+            .select(&[col("category"), col("calories")])
+            .collect()?;
+        let expected = df
+            .lazy()
+            .filter(col("calories").gt(lit(100)))
+            .select(&[col("category"), col("calories")])
+            .collect()?;
+        assert!(out.frame_equal(&expected));
+
+        // the projection and the predicate are pushed down to the scan
+        let plan = LazyFrame::scan_ipc(path, None, false)
+            .filter(col("calories").gt(lit(100)))
+            .select(&[col("category")])
+            .describe_optimized_plan()?;
+        assert!(plan.contains("IPC SCAN"));
+        assert!(!plan.contains("FILTER"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_scan_dtype_cast() -> Result<()> {
+        let path = "../../examples/aggregate_multiple_files_in_chunks/datasets/foods1.csv";
+        let dtypes = Schema::new(vec![
+            Field::new("calories", DataType::Float64),
+            Field::new("sugars_g", DataType::Utf8),
+            Field::new("not_in_file", DataType::Int32),
+        ]);
+        let out = LazyCsvReader::new(path.to_string())
+            .with_dtype_cast(Some(&dtypes))
+            .finish()
+            .select(&[col("category"), col("calories")])
+            .collect()?;
+        assert_eq!(out.get_column_names(), &["category", "calories"]);
+        assert_eq!(out.column("calories")?.dtype(), &DataType::Float64);
+
+        let out = scan_foods_csv().cast(&dtypes).collect()?;
+        assert_eq!(out.column("sugars_g")?.dtype(), &DataType::Utf8);
+        assert_eq!(out.column("fats_g")?.dtype(), &DataType::Float64);
+
+        // an enum cast validates the values of the column
+        let categories = ["vegetables", "seafood", "meat", "fruit"];
+        let enum_dtype = DataType::Categorical(CategoricalOrdering::Enum(Arc::new(
+            categories.iter().map(|cat| cat.to_string()).collect(),
+        )));
+        let dtypes = Schema::new(vec![Field::new("category", enum_dtype.clone())]);
+        let out = LazyCsvReader::new(path.to_string())
+            .with_dtype_cast(Some(&dtypes))
+            .finish()
+            .select(&[col("category")])
+            .collect()?;
+        assert_eq!(out.column("category")?.dtype(), &enum_dtype);
+        assert_eq!(
+            out.column("category")?.max_as_series().get(0),
+            AnyValue::Utf8("fruit")
+        );
+
+        let dtypes = Schema::new(vec![Field::new(
+            "category",
+            DataType::Categorical(CategoricalOrdering::Enum(Arc::new(vec!["meat".into()]))),
+        )]);
+        let out = LazyCsvReader::new(path.to_string())
+            .with_dtype_cast(Some(&dtypes))
+            .finish()
+            .collect();
+        assert!(out.is_err());
+
+        // enums with different categories can't be compared
+        let df = DataFrame::new(vec![
+            Series::new("a", &["x", "y"]).cast_to_enum(&["x", "y"], true)?,
+            Series::new("b", &["y", "x"]).cast_to_enum(&["y", "x"], true)?,
+        ])?;
+        let out = df.lazy().filter(col("a").lt(col("b"))).collect();
+        assert!(out.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_metadata() -> Result<()> {
+        let mut df = get_df();
+        let mut s = df.drop_in_place("sepal.width")?;
+        let mut metadata = FieldMetadata::new();
+        metadata.insert("unit".to_string(), "cm".to_string());
+        s.set_metadata(metadata.clone());
+        df.add_column(s)?;
+
+        let lf = df
+            .lazy()
+            .filter(col("sepal.length").gt(lit(5.0)))
+            .select(&[col("sepal.width").alias("width")]);
+        assert_eq!(
+            lf.logical_plan
+                .schema()
+                .field_with_name("width")?
+                .metadata(),
+            &metadata
+        );
+        let out = lf.collect()?;
+        assert_eq!(out.column("width")?.metadata(), &metadata);
+        Ok(())
+    }
+
     #[test]
     fn test_select_except() {
         let df = df! {
@@ -1707,4 +4366,1247 @@ mod test {
 
         assert_eq!(out.get_column_names(), &["ham", "bar"]);
     }
+
+    #[test]
+    fn test_lazy_top_k() -> Result<()> {
+        let df = df! {
+            "groups" => &[1, 1, 1, 2, 2, 1, 2],
+            "values" => &[-50, 2, 3, 10, 20, 100, 30]
+        }?;
+
+        let out = df
+            .clone()
+            .lazy()
+            .groupby(vec![col("groups")])
+            .agg(vec![col("values").top_k(2)])
+            .sort("groups", false)
+            .collect()?;
+        assert_eq!(out.get_column_names(), &["groups", "values_top_2"]);
+        let top = out.column("values_top_2")?.explode()?;
+        assert_eq!(
+            Vec::from(top.i32()?),
+            &[Some(100), Some(3), Some(30), Some(20)]
+        );
+
+        let out = df.lazy().select(vec![col("values").top_k(3)]).collect()?;
+        assert_eq!(
+            Vec::from(out.column("values")?.i32()?),
+            &[Some(100), Some(30), Some(20)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_scan_csv_glob() -> Result<()> {
+        let dir = "../../examples/aggregate_multiple_files_in_chunks/datasets";
+        let out = LazyCsvReader::new(format!("{}/foods*.csv", dir))
+            .finish()
+            .filter(col("category").eq(lit("meat")))
+            .select(vec![col("calories")])
+            .collect()?;
+        let expected =
+            LazyCsvReader::new_paths((1..=5).map(|i| format!("{}/foods{}.csv", dir, i)).collect())
+                .finish()
+                .filter(col("category").eq(lit("meat")))
+                .select(vec![col("calories")])
+                .collect()?;
+        assert_eq!(out.get_column_names(), &["calories"]);
+        assert!(out.frame_equal(&expected));
+
+        let first = scan_foods_csv()
+            .filter(col("category").eq(lit("meat")))
+            .collect()?;
+        assert!(out.height() > first.height());
+
+        let out = LazyCsvReader::new(format!("{}/foods[12].csv", dir))
+            .with_stop_after_n_rows(Some(30))
+            .finish()
+            .collect()?;
+        assert_eq!(out.height(), 30);
+
+        let out = LazyCsvReader::new(format!("{}/missing*.csv", dir))
+            .finish()
+            .collect();
+        assert!(matches!(out, Err(PolarsError::NotFound(_))));
+        let out = LazyCsvReader::new(format!("{}/foods[.csv", dir))
+            .finish()
+            .collect();
+        assert!(matches!(out, Err(PolarsError::ValueError(_))));
+        assert!(matches!(
+            LazyFrame::scan_csv_auto(format!("{}/missing*.csv", dir)),
+            Err(PolarsError::NotFound(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_union_schema_mismatch() {
+        let a = df! {"a" => [1, 2]}.unwrap().lazy().logical_plan;
+        let b = df! {"b" => [1, 2]}.unwrap().lazy().logical_plan;
+        assert!(LogicalPlanBuilder::union(vec![a.clone(), a.clone()]).is_ok());
+        assert!(LogicalPlanBuilder::union(vec![a, b]).is_err());
+    }
+
+    #[test]
+    fn test_lazy_reuse_shares_plan() -> Result<()> {
+        let base = get_df().lazy().filter(col("sepal.width").gt(lit(3.1)));
+        let cloned = base.clone();
+        match (&base.logical_plan, &cloned.logical_plan) {
+            (LogicalPlan::Selection { input: a, .. }, LogicalPlan::Selection { input: b, .. }) => {
+                assert!(Arc::ptr_eq(a, b))
+            }
+            _ => panic!("expected a selection"),
+        }
+
+        let first = cloned.clone().collect()?;
+        let sum = cloned.select(vec![col("sepal.length").sum()]).collect()?;
+        assert!(base.clone().collect()?.frame_equal(&first));
+        assert_eq!(
+            sum.column("sepal.length")?.f64()?.get(0),
+            first.column("sepal.length")?.sum()
+        );
+        assert!(base.collect()?.frame_equal(&first));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_concat() -> Result<()> {
+        let a = df! {
+            "a" => [1, 2, 3],
+            "b" => ["x", "y", "z"]
+        }?;
+        let b = df! {
+            "a" => [4, 5],
+            "b" => ["v", "w"]
+        }?;
+
+        let lf = crate::functions::concat(&[a.lazy(), b.clone().lazy()])?
+            .filter(col("a").gt(lit(2)))
+            .select(vec![col("b")]);
+        // the filter is pushed into the inputs
+        let plan = lf.describe_optimized_plan()?;
+        assert!(plan.contains("UNION"));
+        assert!(!plan.contains("FILTER"));
+        let out = lf.collect()?;
+        assert_eq!(
+            Vec::from(out.column("b")?.utf8()?),
+            &[Some("z"), Some("v"), Some("w")]
+        );
+
+        let c = df! {"c" => [1]}?;
+        assert!(crate::functions::concat(&[b.lazy(), c.lazy()]).is_err());
+        assert!(crate::functions::concat(&[]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_into_expr_arguments() -> Result<()> {
+        let df = df! {
+            "a" => [1, 2, 1],
+            "b" => [1, 2, 3]
+        }?;
+
+        let out = df.clone().lazy().select(["b", "a"]).collect()?;
+        assert_eq!(out.get_column_names(), &["b", "a"]);
+
+        let names = vec!["a".to_string()];
+        let out = df
+            .clone()
+            .lazy()
+            .with_columns([(col("b") * lit(10)).alias("c")])
+            .groupby(names.iter().map(|name| col(name)))
+            .agg(&[col("c").sum()])
+            .sort("a", false)
+            .select(vec![col("a"), "c_sum".into()])
+            .collect()?;
+        assert_eq!(
+            Vec::from(out.column("c_sum")?.i32()?),
+            &[Some(40), Some(20)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_sort_by_exprs() -> Result<()> {
+        let df = df! {
+            "a" => [1, 2, 1, 2],
+            "b" => [1, 2, 3, 4]
+        }?;
+
+        let out = df
+            .clone()
+            .lazy()
+            .sort_by_exprs(vec![col("a"), col("b")], vec![false, true])
+            .collect()?;
+        assert_eq!(
+            Vec::from(out.column("b")?.i32()?),
+            &[Some(3), Some(1), Some(4), Some(2)]
+        );
+
+        // the sort expressions are projected, even when they are not selected
+        let out = df
+            .lazy()
+            .sort_by_exprs(vec![col("a") * lit(-1), col("b")], vec![false, false])
+            .select(vec![col("b")])
+            .collect()?;
+        assert_eq!(
+            Vec::from(out.column("b")?.i32()?),
+            &[Some(2), Some(4), Some(1), Some(3)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_slice_pushdown() -> Result<()> {
+        // the number of rows the csv scan reads after optimization
+        let scan_n_rows = |lf: LazyFrame| {
+            let mut expr_arena = Arena::with_capacity(16);
+            let mut lp_arena = Arena::with_capacity(16);
+            let mut node = lf.optimize(&mut lp_arena, &mut expr_arena).unwrap();
+            loop {
+                match lp_arena.get(node) {
+                    ALogicalPlan::CsvScan {
+                        stop_after_n_rows, ..
+                    } => return *stop_after_n_rows,
+                    ALogicalPlan::Slice { input, .. }
+                    | ALogicalPlan::Projection { input, .. }
+                    | ALogicalPlan::Sort { input, .. } => node = *input,
+                    _ => unreachable!(),
+                }
+            }
+        };
+
+        assert_eq!(scan_n_rows(scan_foods_csv().slice(2, 3)), Some(5));
+        assert_eq!(
+            scan_n_rows(scan_foods_csv().select(vec![col("fats_g")]).limit(3)),
+            Some(3)
+        );
+        // filters and sorts need all the rows
+        assert_eq!(
+            scan_n_rows(
+                scan_foods_csv()
+                    .filter(col("category").eq(lit("meat")))
+                    .limit(3)
+            ),
+            None
+        );
+        assert_eq!(
+            scan_n_rows(scan_foods_csv().sort("fats_g", false).limit(3)),
+            None
+        );
+        assert_eq!(
+            scan_n_rows(scan_foods_csv().with_slice_pushdown(false).limit(3)),
+            None
+        );
+
+        let out = scan_foods_csv().slice(2, 3).collect()?;
+        let expected = scan_foods_csv().collect()?.slice(2, 3)?;
+        assert!(out.frame_equal(&expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_common_subplan_elimination() -> Result<()> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let shared = get_df().lazy().map(
+            move |df: DataFrame| {
+                counter.fetch_add(1, Ordering::Relaxed);
+                Ok(df)
+            },
+            None,
+            None,
+        );
+        let query = |shared: LazyFrame| {
+            let left = shared
+                .clone()
+                .filter(col("sepal.width").gt(lit(3.2)))
+                .select(vec![col("sepal.length"), col("petal.width")]);
+            let right = shared.select(vec![col("sepal.length"), col("variety")]);
+            left.inner_join(right, col("sepal.length"), col("sepal.length"), None)
+                .sort("sepal.length", false)
+        };
+
+        let out = query(shared.clone()).collect()?;
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+        let expected = query(shared.with_common_subplan_elimination(false)).collect()?;
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        assert!(out.frame_equal(&expected));
+
+        // a frame that is used once isn't cached
+        let plan = get_df()
+            .lazy()
+            .select(vec![col("variety")])
+            .describe_optimized_plan()?;
+        assert!(!plan.contains("CACHE"));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(all(feature = "ipc", feature = "temporal", feature = "dtype-date64"))]
+    fn test_lazy_scan_ipc_time_range() -> Result<()> {
+        use polars_io::prelude::{IpcWriter, SerWriter};
+
+        let ms_in_day = 86_400_000i64;
+        let mut df = df![
+            "ts" => [0, ms_in_day, 10 * ms_in_day, 11 * ms_in_day] => DataType::Date64,
+            "value" => [1, 2, 3, 4]
+        ]?;
+        let path = std::env::temp_dir().join("polars_test_lazy_scan_ipc_time_range.ipc");
+        let mut file = std::fs::File::create(&path)?;
+        IpcWriter::new(&mut file).finish(&mut df)?;
+        let path = path.to_string_lossy().to_string();
+
+        let out = LazyFrame::scan_ipc(path.clone(), None, false)
+            .filter(col("ts").gt_eq(lit(NaiveDate::from_ymd(1970, 1, 11))))
+            .collect()?;
+        assert_eq!(Vec::from(out.column("value")?.i32()?), &[Some(3), Some(4)]);
+
+        let out = LazyFrame::scan_ipc(path.clone(), None, false)
+            .filter(
+                lit(NaiveDate::from_ymd(1970, 1, 2))
+                    .gt_eq(col("ts"))
+                    .or(col("ts").eq(lit(NaiveDate::from_ymd(1970, 1, 12)))),
+            )
+            .collect()?;
+        assert_eq!(
+            Vec::from(out.column("value")?.i32()?),
+            &[Some(1), Some(2), Some(4)]
+        );
+
+        // the min/max of the batch excludes all rows, the schema is kept
+        let out = LazyFrame::scan_ipc(path, None, false)
+            .filter(col("ts").lt(lit(NaiveDate::from_ymd(1969, 12, 31))))
+            .collect()?;
+        assert_eq!(out.shape(), (0, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_physical_plan() -> Result<()> {
+        use crate::physical_plan::Cache;
+
+        let df = get_df();
+        let plan = df
+            .clone()
+            .lazy()
+            .groupby(vec![col("variety")])
+            .agg(vec![col("sepal.length").sum()])
+            .to_physical_plan()?;
+        let described = format!("{:?}", plan);
+        assert!(described.contains("GROUPBY"));
+        assert!(described.contains("  DATAFRAME SCAN"));
+        assert!(plan.to_dot().starts_with("graph  polars_query {"));
+        assert_eq!(plan.execute()?.height(), 1);
+
+        let plan = df
+            .clone()
+            .lazy()
+            .inner_join(df.lazy(), col("variety"), col("variety"), None)
+            .to_physical_plan()?;
+        assert!(format!("{:?}", plan).starts_with("Inner HASH JOIN"));
+        assert_eq!(plan.root().inputs().len(), 2);
+
+        // a custom executor on top of the plan
+        struct HeadExec {
+            input: Box<dyn Executor>,
+        }
+        impl Executor for HeadExec {
+            fn execute(&mut self, cache: &Cache) -> Result<DataFrame> {
+                Ok(self.input.execute(cache)?.head(Some(2)))
+            }
+
+            fn inputs(&self) -> Vec<&dyn Executor> {
+                vec![self.input.as_ref()]
+            }
+        }
+        let plan = PhysicalPlan::new(Box::new(HeadExec {
+            input: plan.into_executor(),
+        }));
+        assert!(format!("{:?}", plan).starts_with("HeadExec\n  Inner HASH JOIN"));
+        assert_eq!(plan.execute()?.height(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_custom_optimization_rule() -> Result<()> {
+        struct RemoveUdf {}
+
+        impl OptimizationRule for RemoveUdf {
+            fn optimize_plan(
+                &mut self,
+                lp_arena: &mut Arena<ALogicalPlan>,
+                _expr_arena: &mut Arena<AExpr>,
+                node: Node,
+            ) -> Option<ALogicalPlan> {
+                match lp_arena.get(node) {
+                    ALogicalPlan::Udf { input, .. } => Some(lp_arena.get(*input).clone()),
+                    _ => None,
+                }
+            }
+        }
+
+        let df = get_df();
+        let failing_udf = |_df: DataFrame| -> Result<DataFrame> {
+            Err(PolarsError::Other("the udf should be removed".into()))
+        };
+        // the rule is kept by the operations after it is registered
+        let out = df
+            .clone()
+            .lazy()
+            .with_optimization_rule(|| RemoveUdf {})
+            .map(failing_udf, None, None)
+            .filter(col("sepal.length").gt(lit(5.0)))
+            .collect()?;
+        let expected = df
+            .clone()
+            .lazy()
+            .filter(col("sepal.length").gt(lit(5.0)))
+            .collect()?;
+        assert!(out.frame_equal(&expected));
+
+        assert!(df.lazy().map(failing_udf, None, None).collect().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_explain() -> Result<()> {
+        let a = (0..100).collect::<Vec<i32>>();
+        let b = a.iter().map(|v| v % 10).collect::<Vec<_>>();
+        let df = DataFrame::new(vec![Series::new("a", &a), Series::new("b", &b)])?;
+        let lf = df
+            .lazy()
+            .filter(col("a").lt(lit(50)))
+            .groupby(vec![col("b")])
+            .agg(vec![col("a").sum()]);
+        let line = |explained: &str, node: &str| {
+            explained
+                .lines()
+                .find(|line| line.trim_start().starts_with(node))
+                .unwrap()
+                .to_string()
+        };
+
+        let explained = lf.explain(false)?;
+        // the filter is pushed down to the scan and estimated to keep a third of the rows
+        let scan = line(&explained, "DATAFRAME SCAN");
+        assert!(scan.contains("estimated rows: 33, cost: 100]"));
+        assert!(line(&explained, "GROUPBY").contains("estimated rows: 3,"));
+
+        let explained = lf.explain(true)?;
+        assert!(line(&explained, "DATAFRAME SCAN").contains("actual rows: 50,"));
+        assert!(line(&explained, "GROUPBY").contains("actual rows: 10,"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_profile() -> Result<()> {
+        let a = (0..100).collect::<Vec<i32>>();
+        let b = a.iter().map(|v| v % 10).collect::<Vec<_>>();
+        let df = DataFrame::new(vec![Series::new("a", &a), Series::new("b", &b)])?;
+        let (out, profile) = df
+            .lazy()
+            .filter(col("a").lt(lit(50)))
+            .groupby(vec![col("b")])
+            .agg(vec![col("a").sum()])
+            .profile()?;
+        assert_eq!(out.height(), 10);
+        assert_eq!(
+            profile.get_column_names(),
+            &["node", "start", "end", "rows"]
+        );
+
+        let nodes = Vec::from(profile.column("node")?.utf8()?);
+        let rows = Vec::from(profile.column("rows")?.u64()?);
+        // the scan finishes before the groupby that consumes it
+        let scan = nodes
+            .iter()
+            .position(|n| n.unwrap().starts_with("DATAFRAME SCAN"));
+        let groupby = nodes.iter().position(|n| n.unwrap().starts_with("GROUPBY"));
+        assert!(scan.unwrap() < groupby.unwrap());
+        assert_eq!(rows[scan.unwrap()], Some(50));
+        assert_eq!(rows[groupby.unwrap()], Some(10));
+        let start = profile.column("start")?.u64()?;
+        let end = profile.column("end")?.u64()?;
+        assert!(start
+            .into_iter()
+            .zip(end.into_iter())
+            .all(|(start, end)| start <= end));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_scan_csv_auto() -> Result<()> {
+        let path = "../../examples/aggregate_multiple_files_in_chunks/datasets/foods1.csv";
+        let (lf, dialect) = LazyFrame::scan_csv_auto(path.to_string())?;
+        assert_eq!(dialect.delimiter, b',');
+        assert!(dialect.has_header);
+        assert!(lf.collect()?.frame_equal(&scan_foods_csv().collect()?));
+
+        let path = std::env::temp_dir().join("polars_lazy_scan_csv_auto.csv");
+        let path = path.to_string_lossy().to_string();
+        std::fs::write(&path, "name;value\r\n\"a;b\";1\r\n\"c\";2\r\n")?;
+        let (lf, dialect) = LazyFrame::scan_csv_auto(path.clone())?;
+        assert_eq!(dialect.delimiter, b';');
+        assert_eq!(
+            dialect.line_terminator,
+            polars_io::csv::LineTerminator::CrLf
+        );
+        let out = lf.collect()?;
+        assert_eq!(
+            Vec::from(out.column("name")?.utf8()?),
+            &[Some("a;b"), Some("c")]
+        );
+        assert_eq!(Vec::from(out.column("value")?.i64()?), &[Some(1), Some(2)]);
+
+        // the parser doesn't support ' as quote character or \r line terminators
+        std::fs::write(&path, "name;value\r'a;b';1\r'c';2\r")?;
+        assert!(LazyFrame::scan_csv_auto(path.clone()).is_err());
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_sink() -> Result<()> {
+        use polars_io::SerReader;
+
+        let a = (0..100_000).collect::<Vec<i64>>();
+        let df = DataFrame::new(vec![Series::new("a", &a)])?;
+        let query = || {
+            df.clone()
+                .lazy()
+                .filter(col("a").gt(lit(10)))
+                .with_column((col("a") * lit(2i64)).alias("b"))
+        };
+        let expected = query().collect()?;
+
+        let csv_path = std::env::temp_dir().join("polars_lazy_sink.csv");
+        let csv_path = csv_path.to_string_lossy().to_string();
+        query().sink_csv(csv_path.clone())?;
+        let out = CsvReader::from_path(&csv_path)?.finish()?;
+        assert!(out.frame_equal(&expected));
+        // a csv scan is sinked batch by batch
+        let copy_path = std::env::temp_dir().join("polars_lazy_sink_copy.csv");
+        let copy_path = copy_path.to_string_lossy().to_string();
+        LazyCsvReader::new(csv_path.clone())
+            .finish()
+            .filter(col("b").lt(lit(100i64)))
+            .sink_csv(copy_path.clone())?;
+        let out = CsvReader::from_path(&copy_path)?.finish()?;
+        let filtered = expected
+            .clone()
+            .lazy()
+            .filter(col("b").lt(lit(100i64)))
+            .collect()?;
+        assert!(out.frame_equal(&filtered));
+        std::fs::remove_file(&csv_path)?;
+        std::fs::remove_file(&copy_path)?;
+
+        #[cfg(feature = "ipc")]
+        {
+            use polars_io::prelude::{IpcWriter, SerWriter};
+            let path = std::env::temp_dir().join("polars_lazy_sink.ipc");
+            let path = path.to_string_lossy().to_string();
+            let mut file = std::fs::File::create(&path)?;
+            IpcWriter::new(&mut file).finish(&mut expected.clone())?;
+            // an ipc scan is sinked batch by batch as well
+            let copy_path = std::env::temp_dir().join("polars_lazy_sink_ipc_copy.csv");
+            let copy_path = copy_path.to_string_lossy().to_string();
+            LazyFrame::scan_ipc(path.clone(), None, false)
+                .select(vec![col("b")])
+                .sink_csv(copy_path.clone())?;
+            let out = CsvReader::from_path(&copy_path)?.finish()?;
+            assert!(out.frame_equal(&expected.select("b")?));
+            std::fs::remove_file(&path)?;
+            std::fs::remove_file(&copy_path)?;
+        }
+
+        #[cfg(feature = "parquet")]
+        {
+            let path = std::env::temp_dir().join("polars_lazy_sink.parquet");
+            let path = path.to_string_lossy().to_string();
+            query().sink_parquet(path.clone())?;
+            // a parquet scan is sinked batch by batch as well
+            let copy_path = std::env::temp_dir().join("polars_lazy_sink_copy.parquet");
+            let copy_path = copy_path.to_string_lossy().to_string();
+            LazyFrame::new_from_parquet(path.clone(), None, false)
+                .filter(col("b").lt(lit(100i64)))
+                .sink_parquet(copy_path.clone())?;
+            let out = LazyFrame::new_from_parquet(copy_path.clone(), None, false).collect()?;
+            let expected = expected.lazy().filter(col("b").lt(lit(100i64))).collect()?;
+            assert!(out.frame_equal(&expected));
+            std::fs::remove_file(&path)?;
+            std::fs::remove_file(&copy_path)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_column_transformers() -> Result<()> {
+        use polars_io::SerReader;
+
+        let df = df! {
+            "a" => &[1i64, 2, 3, 4],
+            "b" => &[10i64, 20, 30, 40]
+        }?;
+        let double = |s: Series| -> Result<Series> { Ok(&s + &s) };
+        let path = std::env::temp_dir().join("polars_lazy_column_transformers.csv");
+        let path = path.to_string_lossy().to_string();
+        df.clone().lazy().sink_csv_with_transformers(
+            path.clone(),
+            ColumnTransformers::default().with_transformer("b", double),
+        )?;
+        let out = CsvReader::from_path(&path)?.finish()?;
+        assert_eq!(
+            Vec::from(out.column("a")?.i64()?),
+            &[Some(1), Some(2), Some(3), Some(4)]
+        );
+        assert_eq!(
+            Vec::from(out.column("b")?.i64()?),
+            &[Some(20), Some(40), Some(60), Some(80)]
+        );
+
+        // the filter is pushed down to the scan and sees the transformed values
+        let scan = || {
+            LazyCsvReader::new(path.clone())
+                .with_column_transformer("a", double)
+                .finish()
+                .filter(col("a").gt(lit(5i64)))
+        };
+        let out = scan().collect()?;
+        assert_eq!(Vec::from(out.column("a")?.i64()?), &[Some(6), Some(8)]);
+        assert_eq!(Vec::from(out.column("b")?.i64()?), &[Some(60), Some(80)]);
+        // the transformed scan doesn't share the cache with a plain scan of the same file
+        let out = crate::functions::concat(&[
+            LazyCsvReader::new(path.clone())
+                .with_column_transformer("a", double)
+                .finish(),
+            LazyCsvReader::new(path.clone()).finish(),
+        ])?
+        .select(vec![col("a")])
+        .collect()?;
+        assert_eq!(
+            Vec::from(out.column("a")?.i64()?),
+            &[
+                Some(2),
+                Some(4),
+                Some(6),
+                Some(8),
+                Some(1),
+                Some(2),
+                Some(3),
+                Some(4)
+            ]
+        );
+        // a sinked scan is transformed batch by batch
+        let copy_path = std::env::temp_dir().join("polars_lazy_column_transformers_copy.csv");
+        let copy_path = copy_path.to_string_lossy().to_string();
+        scan().sink_csv(copy_path.clone())?;
+        let out = CsvReader::from_path(&copy_path)?.finish()?;
+        assert_eq!(Vec::from(out.column("a")?.i64()?), &[Some(6), Some(8)]);
+        std::fs::remove_file(&path)?;
+        std::fs::remove_file(&copy_path)?;
+
+        #[cfg(feature = "parquet")]
+        {
+            let path = std::env::temp_dir().join("polars_lazy_column_transformers.parquet");
+            let path = path.to_string_lossy().to_string();
+            df.clone().lazy().sink_parquet_with_transformers(
+                path.clone(),
+                ColumnTransformers::default().with_transformer("b", double),
+            )?;
+            let out = LazyFrame::new_from_parquet_with_transformers(
+                path.clone(),
+                None,
+                false,
+                ColumnTransformers::default().with_transformer("a", double),
+            )
+            .filter(col("b").lt(lit(50i64)))
+            .collect()?;
+            assert_eq!(Vec::from(out.column("a")?.i64()?), &[Some(2), Some(4)]);
+            assert_eq!(Vec::from(out.column("b")?.i64()?), &[Some(20), Some(40)]);
+            std::fs::remove_file(&path)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_map_with_input_columns() -> Result<()> {
+        let df = df! {
+            "a" => &[1, 2, 3],
+            "b" => &[4, 5, 6],
+            "c" => &[7, 8, 9]
+        }?;
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32),
+            Field::new("width", DataType::UInt32),
+        ]);
+        let out = df
+            .lazy()
+            .map_with_input_columns(
+                |df: DataFrame| {
+                    let width = Series::new("width", &vec![df.width() as u32; df.height()]);
+                    df.select("a")?.with_column(width)
+                },
+                &["a"],
+                None,
+                Some(schema),
+            )
+            .select(&[col("width"), col("a")])
+            .collect()?;
+        assert_eq!(out.get_column_names(), &["width", "a"]);
+        assert_eq!(Vec::from(out.column("width")?.u32()?), &[Some(1); 3]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_join_reorder() -> Result<()> {
+        let base = df! {
+            "key" => &[1, 2, 3, 4],
+            "a" => &[1, 2, 3, 4]
+        }?;
+        let big = df! {
+            "key" => &[1, 2, 3, 4, 5, 6],
+            "b" => &[10, 20, 30, 40, 50, 60]
+        }?;
+        let small = df! {
+            "key" => &[2, 3],
+            "s" => &["x", "y"]
+        }?;
+        // joins on a column of `big`, so it has to be joined after `big`
+        let tiny = df! {
+            "b" => &[30],
+            "c" => &[true]
+        }?;
+        let query = || {
+            base.clone()
+                .lazy()
+                .inner_join(big.clone().lazy(), col("key"), col("key"), None)
+                .inner_join(small.clone().lazy(), col("key"), col("key"), None)
+                .inner_join(tiny.clone().lazy(), col("b"), col("b"), None)
+        };
+
+        // the heights of the right inputs of the chain, from the top to the bottom
+        let mut expr_arena = Arena::with_capacity(16);
+        let mut lp_arena = Arena::with_capacity(16);
+        let mut node = query().optimize(&mut lp_arena, &mut expr_arena)?;
+        let mut heights = vec![];
+        loop {
+            match lp_arena.get(node) {
+                ALogicalPlan::Projection { input, .. } => node = *input,
+                ALogicalPlan::Join {
+                    input_left,
+                    input_right,
+                    ..
+                } => {
+                    if let ALogicalPlan::DataFrameScan { df, .. } = lp_arena.get(*input_right) {
+                        heights.push(df.height())
+                    }
+                    node = *input_left
+                }
+                _ => break,
+            }
+        }
+        assert_eq!(heights, &[1, 6, 2]);
+
+        let out = query().collect()?;
+        let expected = query().with_join_reorder(false).collect()?;
+        assert_eq!(out.get_column_names(), &["key", "a", "b", "s", "c"]);
+        assert!(out.frame_equal(&expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_constant_folding() -> Result<()> {
+        let df = df! {
+            "a" => &[true, false, true],
+            "b" => &[false, false, true]
+        }?;
+        let query = || {
+            df.clone().lazy().select(&[
+                (lit(2) + lit(3)).alias("five"),
+                (lit(1) + lit(2.5)).alias("sum"),
+                col("a").and(lit(true)).alias("a_and"),
+                col("a").or(lit(true)).alias("a_or"),
+                not(not(col("b"))).alias("not_not"),
+                when(lit(false))
+                    .then(col("a"))
+                    .otherwise(col("b"))
+                    .alias("when"),
+            ])
+        };
+
+        let mut expr_arena = Arena::with_capacity(16);
+        let mut lp_arena = Arena::with_capacity(16);
+        let node = query().optimize(&mut lp_arena, &mut expr_arena)?;
+        let plan = node_to_lp(node, &mut expr_arena, &mut lp_arena);
+        let expr = match plan {
+            LogicalPlan::Projection { expr, .. } => expr,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            expr,
+            &[
+                lit(5).alias("five"),
+                lit(3.5).alias("sum"),
+                col("a").alias("a_and"),
+                lit(true).alias("a_or"),
+                col("b").alias("not_not"),
+                col("b").alias("a").alias("when"),
+            ]
+        );
+
+        let out = query().collect()?;
+        let expected = query().with_simplify_expr(false).collect()?;
+        assert!(out.frame_equal(&expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_invalid_queries_return_err() -> Result<()> {
+        let df = df! {
+            "a" => &[1, 2, 3],
+            "b" => &[4, 5, 6]
+        }?;
+        let lf = || df.clone().lazy();
+        // a csv file with another schema than the foods files
+        let other_csv = std::env::temp_dir().join("polars_test_invalid_queries.csv");
+        std::fs::write(&other_csv, "a,b\n1,2\n")?;
+
+        // none of these queries may panic
+        let queries = vec![
+            lf().select(&[col("missing")]),
+            lf().with_column(col("missing").alias("c")),
+            lf().filter(col("missing").gt(lit(1))),
+            lf().groupby(vec![col("a")]).agg(vec![col("missing").sum()]),
+            lf().select(&[col("*"), except("*")]),
+            lf().explode(&[col("a") + lit(1)]),
+            lf().select(&[col("a").agg_groups()]),
+            lf().select(&[col("a").list()]),
+            lf().melt(vec!["a".into()], vec!["missing".into()], false),
+            lf().melt(vec!["a".into()], vec![], false),
+            lf().inner_join(lf(), col("a"), lit(1), None),
+            lf().with_column_renamed("missing", "c"),
+            lf().self_join(&["a", "b"], &["a"], JoinType::Inner, ("_l", "_r")),
+            LazyCsvReader::new("does/not/exist.csv".into()).finish(),
+            LazyCsvReader::new("does/not/exist/*.csv".into()).finish(),
+            LazyCsvReader::new_paths(vec![
+                "../../examples/aggregate_multiple_files_in_chunks/datasets/foods1.csv".into(),
+                other_csv.to_string_lossy().into_owned(),
+            ])
+            .finish(),
+            lf().select(&[col("missing")]).select(&[col("a")]),
+            scan_foods_csv()
+                .select(&[col("calories").min() + lit(1)])
+                .with_aggregate_pushdown(true),
+            scan_foods_csv()
+                .select(&[col("calories").min().mean()])
+                .with_aggregate_pushdown(true),
+        ];
+        for query in queries {
+            assert!(query.collect().is_err());
+        }
+
+        // aggregations that have their own physical expression are not handled here
+        let agg = crate::physical_plan::expressions::PhysicalAggExpr::new(
+            Arc::new(crate::physical_plan::expressions::ColumnExpr::new(
+                Arc::new("a".into()),
+                col("a"),
+            )),
+            GroupByMethod::Quantile(0.5),
+        );
+        let groups = vec![(0, vec![0, 1, 2])];
+        assert!(agg.as_agg_expr()?.evaluate(&df, &groups).is_err());
+
+        // a null literal has a data type
+        let out = lf()
+            .select(&[col("a"), Expr::Literal(LiteralValue::Null).alias("null")])
+            .collect()?;
+        assert_eq!(out.column("null")?.null_count(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_type_coercion_binary_expr() -> Result<()> {
+        let df = df! {
+            "i32" => &[1i32, 2, 3],
+            "u32" => &[1u32, 2, 3],
+            "i64" => &[2i64, 2, 2],
+            "str" => &["1", "2", "4"]
+        }?;
+
+        let out = df
+            .lazy()
+            .select(&[
+                (col("i32") + lit(1.5)).alias("float"),
+                (col("u32") + col("i64")).alias("int"),
+                col("str").eq(col("i32")).alias("str_eq"),
+            ])
+            .collect()?;
+        assert_eq!(out.column("float")?.dtype(), &DataType::Float64);
+        assert_eq!(
+            Vec::from(out.column("float")?.f64()?),
+            &[Some(2.5), Some(3.5), Some(4.5)]
+        );
+        assert_eq!(out.column("int")?.dtype(), &DataType::Int64);
+        assert_eq!(
+            Vec::from(out.column("int")?.i64()?),
+            &[Some(3), Some(4), Some(5)]
+        );
+        assert_eq!(
+            Vec::from(out.column("str_eq")?.bool()?),
+            &[Some(true), Some(true), Some(false)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(all(feature = "dtype-date32", feature = "dtype-date64"))]
+    fn test_lazy_type_coercion_dates() -> Result<()> {
+        let ms_in_day = 86_400_000i64;
+        let df = df! {
+            "date32" => &[0i32, 1, 2] => DataType::Date32,
+            "date64" => &[0i64, ms_in_day, 3 * ms_in_day] => DataType::Date64
+        }?;
+
+        let out = df
+            .lazy()
+            .filter(col("date32").eq(col("date64")))
+            .collect()?;
+        assert_eq!(out.height(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_agg_schema_matches_output() -> Result<()> {
+        let df = df! {
+            "g" => &[1, 1, 2],
+            "i32" => &[1i32, 2, 3],
+            "i64" => &[1i64, 2, 3],
+            "u32" => &[1u32, 2, 3],
+            "f32" => &[1.0f32, 2.0, 3.0],
+            "f64" => &[1.0f64, 2.0, 3.0]
+        }?;
+        let aggs: Vec<fn(Expr) -> Expr> = vec![
+            |e| e.min(),
+            |e| e.max(),
+            |e| e.sum(),
+            |e| e.mean(),
+            |e| e.median(),
+            |e| e.quantile(0.5),
+            |e| e.std(),
+            |e| e.var(),
+            |e| e.first(),
+            |e| e.last(),
+            |e| e.n_unique(),
+            |e| e.count(),
+            |e| e.null_count(),
+            |e| e.list(),
+        ];
+
+        let check = |lf: LazyFrame| -> Result<()> {
+            let schema = lf.logical_plan.schema().clone();
+            let out = lf.collect()?;
+            for (field, s) in schema.fields().iter().zip(out.get_columns()) {
+                assert_eq!(field.data_type(), s.dtype(), "column {}", field.name());
+            }
+            Ok(())
+        };
+        for name in &["i32", "i64", "u32", "f32", "f64"] {
+            for (i, agg) in aggs.iter().enumerate() {
+                check(
+                    df.clone()
+                        .lazy()
+                        .groupby(vec![col("g")])
+                        .agg(vec![agg(col(name))]),
+                )?;
+                // a list can only be aggregated in a groupby
+                if i != aggs.len() - 1 {
+                    check(df.clone().lazy().select(&[agg(col(name))]))?;
+                }
+            }
+        }
+
+        // non-numeric columns and the index outputs
+        let df_str = df! {
+            "g" => &[1, 1, 2],
+            "str" => &["a", "b", "c"]
+        }?;
+        let aggs: Vec<fn(Expr) -> Expr> = vec![
+            |e| e.min(),
+            |e| e.max(),
+            |e| e.first(),
+            |e| e.last(),
+            |e| e.n_unique(),
+            |e| e.count(),
+            |e| e.null_count(),
+            |e| e.list(),
+            |e| e.agg_groups(),
+        ];
+        for agg in &aggs {
+            check(
+                df_str
+                    .clone()
+                    .lazy()
+                    .groupby(vec![col("g")])
+                    .agg(vec![agg(col("str"))]),
+            )?;
+        }
+
+        let out = df
+            .lazy()
+            .groupby(vec![col("g")])
+            .agg(vec![col("i32").mean(), col("i32").var()])
+            .sort("g", false)
+            .collect()?;
+        assert_eq!(
+            Vec::from(out.column("i32_mean")?.f64()?),
+            &[Some(1.5), Some(3.0)]
+        );
+        assert_eq!(out.column("i32_agg_var")?.f64()?.get(0), Some(0.5));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "dtype-date64")]
+    fn test_lazy_agg_non_numeric() -> Result<()> {
+        let ms_in_day = 86_400_000i64;
+        let df = df! {
+            "g" => &[1, 1, 1, 2],
+            "str" => &["b", "c", "a", "d"],
+            "bool" => &[true, false, true, true],
+            "date" => &[0, 3 * ms_in_day, ms_in_day, 2 * ms_in_day] => DataType::Date64
+        }?;
+
+        let out = df
+            .clone()
+            .lazy()
+            .groupby(vec![col("g")])
+            .agg(vec![
+                col("str").min(),
+                col("str").max(),
+                col("bool").sum(),
+                col("date").median(),
+            ])
+            .sort("g", false)
+            .collect()?;
+        assert_eq!(
+            Vec::from(out.column("str_min")?.utf8()?),
+            &[Some("a"), Some("d")]
+        );
+        assert_eq!(
+            Vec::from(out.column("str_max")?.utf8()?),
+            &[Some("c"), Some("d")]
+        );
+        assert_eq!(
+            Vec::from(out.column("bool_sum")?.u32()?),
+            &[Some(2), Some(1)]
+        );
+        assert_eq!(out.column("date_median")?.dtype(), &DataType::Date64);
+        assert_eq!(
+            Vec::from(out.column("date_median")?.date64()?),
+            &[Some(ms_in_day), Some(2 * ms_in_day)]
+        );
+
+        let out = df.clone().lazy().select(&[col("str").max()]).collect()?;
+        assert_eq!(out.column("str")?.utf8()?.get(0), Some("d"));
+
+        // unsupported aggregations are rejected when the plan is built
+        let lf = || df.clone().lazy();
+        for lf in vec![
+            lf().groupby(vec![col("g")]).agg(vec![col("str").mean()]),
+            lf().groupby(vec![col("g")]).agg(vec![col("date").sum()]),
+            lf().select(&[col("str").median()]),
+            lf().select(&[col("bool").std()]),
+        ] {
+            assert!(lf.logical_plan.error().is_some());
+            assert!(matches!(
+                lf.collect(),
+                Err(PolarsError::InvalidOperation(_))
+            ));
+        }
+
+        // a wildcard skips the columns that don't support the aggregation
+        let out = lf()
+            .groupby(vec![col("g")])
+            .agg(vec![col("*").sum()])
+            .collect()?;
+        assert_eq!(out.get_column_names(), &["g", "g_sum", "bool_sum"]);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(all(
+        feature = "temporal",
+        feature = "dtype-date64",
+        feature = "dtype-duration-ms"
+    ))]
+    fn test_lazy_duration_arithmetic() -> Result<()> {
+        let ms_in_day = 86_400_000i64;
+        let df = df![
+            "date" => &[0, ms_in_day] => DataType::Date64
+        ]?;
+
+        let out = df
+            .lazy()
+            .select(&[
+                (col("date") + duration("3d12h")?).alias("later"),
+                (col("date") - duration("1w")?).alias("earlier"),
+            ])
+            .collect()?;
+        let later = out.column("later")?.date64()?;
+        assert_eq!(
+            Vec::from(later),
+            &[
+                Some(3 * ms_in_day + ms_in_day / 2),
+                Some(4 * ms_in_day + ms_in_day / 2)
+            ]
+        );
+        let earlier = out.column("earlier")?.date64()?;
+        assert_eq!(
+            Vec::from(earlier),
+            &[Some(-7 * ms_in_day), Some(-6 * ms_in_day)]
+        );
+
+        assert!(matches!(duration("3x"), Err(PolarsError::ValueError(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_drop_columns() -> Result<()> {
+        let lf = scan_foods_csv().drop_columns(&["fats_g", "calories"], true);
+        // only the remaining columns are read
+        assert!(lf
+            .describe_optimized_plan()?
+            .contains("PROJECT 2/4 COLUMNS"));
+        let out = lf.collect()?;
+        assert_eq!(out.get_column_names(), &["category", "sugars_g"]);
+
+        // unknown columns are an error if strict and ignored otherwise
+        let lf = scan_foods_csv().drop_columns(&["fats_g", "protein_g"], true);
+        assert!(matches!(lf.collect(), Err(PolarsError::NotFound(_))));
+        let out = scan_foods_csv()
+            .drop_columns(&["fats_g", "protein_g"], false)
+            .collect()?;
+        assert_eq!(
+            out.get_column_names(),
+            &["category", "calories", "sugars_g"]
+        );
+
+        let lf =
+            scan_foods_csv().drop_columns(&["category", "calories", "fats_g", "sugars_g"], true);
+        assert!(matches!(
+            lf.collect(),
+            Err(PolarsError::InvalidOperation(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_agg_fusion() -> Result<()> {
+        let df = df![
+            "g" => &[1, 2, 1, 3, 2, 2],
+            "a" => &[3, 1, -2, 8, 5, 4],
+            "b" => &[0.5, 1.5, 2.0, -1.0, 3.0, 0.25]
+        ]?;
+        let query = |fusion: bool| {
+            df.clone()
+                .lazy()
+                .with_agg_fusion(fusion)
+                .groupby(vec![col("g")])
+                .agg(vec![
+                    col("a").min(),
+                    col("b").sum(),
+                    col("a").max().alias("a_largest"),
+                    col("b").count().alias("n"),
+                    col("a").mean(),
+                    col("a").count(),
+                    col("b").max(),
+                ])
+                .sort("g", false)
+                .collect()
+        };
+        let fused = query(true)?;
+        let expected = query(false)?;
+        assert_eq!(
+            fused.get_column_names(),
+            &[
+                "g",
+                "a_min",
+                "b_sum",
+                "a_largest",
+                "n",
+                "a_mean",
+                "a_count",
+                "b_max"
+            ]
+        );
+        assert_eq!(fused.schema(), expected.schema());
+        assert!(fused.frame_equal(&expected));
+        assert_eq!(
+            Vec::from(fused.column("a_mean")?.f64()?),
+            &[Some(0.5), Some(10.0 / 3.0), Some(8.0)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_rename() -> Result<()> {
+        // swap two columns, then filter and select on the new names
+        let lf = scan_foods_csv()
+            .rename(&["calories", "fats_g"], &["fats_g", "calories"])
+            .filter(col("calories").gt(lit(5)))
+            .select(&[col("category"), col("calories")]);
+
+        // the predicate and projection reach the scan under the existing names
+        let plan = lf.describe_optimized_plan()?;
+        assert!(!plan.contains("FILTER"));
+        assert!(plan.contains("PROJECT 2/4 COLUMNS"));
+        assert!(plan.contains(r#"col("fats_g")"#));
+
+        let expected = scan_foods_csv()
+            .filter(col("fats_g").gt(lit(5)))
+            .select(&[col("category"), col("fats_g").alias("calories")])
+            .collect()?;
+        let out = lf.collect()?;
+        assert!(out.height() > 0);
+        assert!(out.frame_equal(&expected));
+
+        let out = scan_foods_csv()
+            .rename(&["calories", "fats_g"], &["fats_g", "calories"])
+            .collect()?;
+        assert_eq!(
+            out.get_column_names(),
+            &["category", "fats_g", "calories", "sugars_g"]
+        );
+
+        let lf = scan_foods_csv().rename(&["calories"], &["kcal", "fats"]);
+        assert!(matches!(lf.collect(), Err(PolarsError::ShapeMisMatch(_))));
+        let lf = scan_foods_csv().rename(&["protein_g"], &["protein"]);
+        assert!(matches!(lf.collect(), Err(PolarsError::NotFound(_))));
+        let lf = scan_foods_csv().rename(&["calories"], &["fats_g"]);
+        assert!(matches!(lf.collect(), Err(PolarsError::Duplicate(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_eager_select_expr() -> Result<()> {
+        let df = load_df();
+        let out = df.select(col("a") * lit(2))?;
+        assert_eq!(
+            Vec::from(out.column("a")?.i32()?),
+            &[Some(2), Some(4), Some(6), Some(8), Some(10)]
+        );
+
+        let out = df.select(vec![col("b"), col("c").alias("d")])?;
+        assert_eq!(out.get_column_names(), &["b", "d"]);
+        let out = df.select(col("c").sum())?;
+        assert_eq!(out.column("c")?.i32()?.get(0), Some(15));
+
+        // the keys of an eager groupby can be expressions as well
+        let out = df.groupby(col("a").gt(lit(2)))?.select("c").sum()?;
+        assert_eq!(out.height(), 2);
+        Ok(())
+    }
 }
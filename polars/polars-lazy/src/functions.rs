@@ -54,3 +54,30 @@ pub fn pearson_corr(a: Expr, b: Expr) -> Expr {
     };
     map_binary(a, b, function, Some(Field::new(name, DataType::Float32))).alias(name)
 }
+
+/// Concatenate the LazyFrames vertically without collecting them. The LazyFrames must have the
+/// same schema. Filters and projections on the result are pushed down into every input. The
+/// result gets the optimization settings of the first LazyFrame.
+///
+/// # Example
+///
+/// ```rust
+/// use polars_core::prelude::*;
+/// use polars_lazy::functions::concat;
+/// use polars_lazy::prelude::*;
+///
+/// fn example(jan: LazyFrame, feb: LazyFrame) -> Result<DataFrame> {
+///     concat(&[jan, feb])?
+///         .filter(col("amount").gt(lit(100)))
+///         .collect()
+/// }
+/// ```
+pub fn concat(inputs: &[LazyFrame]) -> Result<LazyFrame> {
+    let opt_state = inputs
+        .first()
+        .ok_or_else(|| PolarsError::NoData("cannot concat zero LazyFrames".into()))?
+        .get_opt_state();
+    let lp = LogicalPlanBuilder::union(inputs.iter().map(|lf| lf.logical_plan.clone()).collect())?
+        .build();
+    Ok(LazyFrame::from_logical_plan(lp, opt_state))
+}
@@ -199,6 +199,9 @@ pub mod functions;
 mod logical_plan;
 pub mod physical_plan;
 pub mod prelude;
+#[cfg(feature = "sql")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sql")))]
+pub mod sql;
 pub(crate) mod utils;
 
 #[cfg(test)]
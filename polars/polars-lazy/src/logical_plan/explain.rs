@@ -0,0 +1,349 @@
+//! Estimated cardinalities and costs of the nodes of an optimized logical plan, rendered by
+//! [LazyFrame::explain](crate::frame::LazyFrame::explain).
+//!
+//! Scans are estimated from the metadata of their source: the height of a DataFrame, the row
+//! count in the footer of a parquet file and the file size of csv and ipc files. The other nodes
+//! derive their estimate from their inputs with fixed selectivities. The cost of a node is the
+//! number of rows it processes plus the cost of its inputs.
+use crate::physical_plan::planner::node_description;
+use crate::physical_plan::NodeProfile;
+use crate::prelude::*;
+use polars_core::frame::hash_join::JoinType;
+use polars_core::prelude::*;
+#[cfg(feature = "parquet")]
+use polars_io::prelude::*;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+/// Fraction of the rows that pass an equality predicate.
+const EQ_SELECTIVITY: f64 = 0.1;
+/// Fraction of the rows that pass a range predicate, e.g. `a < 10`.
+const RANGE_SELECTIVITY: f64 = 1.0 / 3.0;
+/// Fraction of the rows that pass any other predicate.
+const DEFAULT_SELECTIVITY: f64 = 0.5;
+/// Number of groups, or unique rows, as a fraction of the input rows.
+const GROUPS_FRACTION: f64 = 0.1;
+/// Assumed size of a value in a csv or ipc file, used to estimate its number of rows.
+const BYTES_PER_VALUE: u64 = 8;
+
+/// A node of the plan with its estimates and, after an analyzed run, its actual statistics.
+pub(crate) struct ExplainNode {
+    node: Node,
+    label: String,
+    estimated_rows: f64,
+    cost: f64,
+    actual: Option<NodeProfile>,
+    inputs: Vec<ExplainNode>,
+}
+
+impl ExplainNode {
+    pub(crate) fn new(
+        node: Node,
+        lp_arena: &Arena<ALogicalPlan>,
+        expr_arena: &Arena<AExpr>,
+    ) -> Self {
+        use ALogicalPlan::*;
+        let lp = lp_arena.get(node);
+        let inputs = plan_inputs(lp)
+            .into_iter()
+            .map(|input| ExplainNode::new(input, lp_arena, expr_arena))
+            .collect::<Vec<_>>();
+        let input_rows = inputs.iter().map(|input| input.estimated_rows).sum::<f64>();
+        let input_cost = inputs.iter().map(|input| input.cost).sum::<f64>();
+        let predicate_selectivity = |predicate: &Option<Node>| {
+            predicate.map_or(1.0, |predicate| selectivity(predicate, expr_arena))
+        };
+
+        // the rows that are processed by this node and the rows that it outputs
+        let (processed, estimated_rows) = match lp {
+            CsvScan {
+                path,
+                schema,
+                skip_rows,
+                stop_after_n_rows,
+                predicate,
+                aggregate,
+                ..
+            } => {
+                let rows = (file_rows(path, schema) - *skip_rows as f64).max(0.0);
+                scan_estimate(
+                    rows,
+                    *stop_after_n_rows,
+                    predicate_selectivity(predicate),
+                    aggregate,
+                )
+            }
+            #[cfg(feature = "parquet")]
+            ParquetScan {
+                path,
+                schema,
+                stop_after_n_rows,
+                predicate,
+                aggregate,
+                ..
+            } => {
+                let rows = std::fs::File::open(path)
+                    .ok()
+                    .and_then(|file| ParquetReader::new(file).num_rows().ok())
+                    .map_or_else(|| file_rows(path, schema), |rows| rows as f64);
+                scan_estimate(
+                    rows,
+                    *stop_after_n_rows,
+                    predicate_selectivity(predicate),
+                    aggregate,
+                )
+            }
+            #[cfg(feature = "ipc")]
+            IpcScan {
+                path,
+                schema,
+                stop_after_n_rows,
+                predicate,
+                aggregate,
+                ..
+            } => scan_estimate(
+                file_rows(path, schema),
+                *stop_after_n_rows,
+                predicate_selectivity(predicate),
+                aggregate,
+            ),
+            DataFrameScan { df, selection, .. } => {
+                let rows = df.height() as f64;
+                (rows, rows * predicate_selectivity(selection))
+            }
+            Selection { predicate, .. } => {
+                (input_rows, input_rows * selectivity(*predicate, expr_arena))
+            }
+            Slice { offset, len, .. } => (
+                input_rows,
+                (input_rows - *offset as f64).max(0.0).min(*len as f64),
+            ),
+            Projection { expr, .. } | LocalProjection { expr, .. } => {
+                if !expr.is_empty() && expr.iter().all(|e| is_aggregation(*e, expr_arena)) {
+                    (input_rows, 1.0_f64.min(input_rows))
+                } else {
+                    (input_rows, input_rows)
+                }
+            }
+            Sort { .. } => (input_rows * input_rows.max(2.0).log2(), input_rows),
+            Aggregate { .. } | Distinct { .. } => (
+                input_rows,
+                (input_rows * GROUPS_FRACTION).max(input_rows.min(1.0)),
+            ),
+            Join { how, .. } => {
+                let (left, right) = (inputs[0].estimated_rows, inputs[1].estimated_rows);
+                let rows = match how {
+                    JoinType::Inner => left.max(right),
+                    JoinType::Left => left,
+                    JoinType::Outer => left + right,
+                    JoinType::Cross => left * right,
+                };
+                (input_rows + rows, rows)
+            }
+            JoinWhere { predicates, .. } => {
+                let pairs = inputs[0].estimated_rows * inputs[1].estimated_rows;
+                let rows = predicates
+                    .iter()
+                    .fold(pairs, |rows, p| rows * selectivity(*p, expr_arena));
+                (pairs, rows)
+            }
+            SetOp { op, .. } => {
+                let (left, right) = (inputs[0].estimated_rows, inputs[1].estimated_rows);
+                let rows = match op {
+                    SetOperation::Union | SetOperation::SymmetricDifference => left + right,
+                    SetOperation::Intersect => left.min(right),
+                    SetOperation::Difference => left,
+                };
+                (input_rows, rows)
+            }
+            Melt {
+                input,
+                id_vars,
+                value_vars,
+                ..
+            } => {
+                let n_values = if value_vars.is_empty() {
+                    lp_arena.get(*input).schema(lp_arena).len() - id_vars.len()
+                } else {
+                    value_vars.len()
+                };
+                (input_rows, input_rows * n_values as f64)
+            }
+            Union { .. }
+            | Explode { .. }
+            | Rename { .. }
+            | Cache { .. }
+            | HStack { .. }
+            | Udf { .. }
+            | Sink { .. } => (input_rows, input_rows),
+        };
+
+        ExplainNode {
+            node,
+            label: label(lp),
+            estimated_rows,
+            cost: processed + input_cost,
+            actual: None,
+            inputs,
+        }
+    }
+
+    /// Set the actual statistics of the nodes that were executed, by their node in the arena.
+    pub(crate) fn set_profiles(&mut self, profiles: &HashMap<usize, NodeProfile>) {
+        self.actual = profiles.get(&self.node.0).cloned();
+        for input in &mut self.inputs {
+            input.set_profiles(profiles)
+        }
+    }
+
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+        write!(
+            f,
+            "{:indent$}{}  [estimated rows: {:.0}, cost: {:.0}",
+            "",
+            self.label,
+            self.estimated_rows,
+            self.cost,
+            indent = indent
+        )?;
+        if let Some(actual) = &self.actual {
+            // the execution time of the inputs is included in the time of a node
+            let inputs_elapsed = self
+                .inputs
+                .iter()
+                .filter_map(|input| input.actual.as_ref().map(|actual| actual.elapsed))
+                .sum::<Duration>();
+            write!(
+                f,
+                ", actual rows: {}, time: {:?}",
+                actual.rows,
+                actual
+                    .elapsed
+                    .checked_sub(inputs_elapsed)
+                    .unwrap_or_default()
+            )?;
+        }
+        writeln!(f, "]")?;
+        for input in &self.inputs {
+            input.fmt_indented(f, indent + 2)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ExplainNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+pub(crate) fn label(lp: &ALogicalPlan) -> String {
+    let (name, source) = node_description(lp);
+    let mut label = match lp {
+        ALogicalPlan::Join { how, .. } => format!("{:?} {}", how, name),
+        _ => name.to_string(),
+    }
+    .to_uppercase();
+    if let Some(source) = source {
+        label.push(' ');
+        label.push_str(&source);
+    }
+    label
+}
+
+fn plan_inputs(lp: &ALogicalPlan) -> Vec<Node> {
+    use ALogicalPlan::*;
+    match lp {
+        Melt { input, .. }
+        | Slice { input, .. }
+        | Selection { input, .. }
+        | Projection { input, .. }
+        | LocalProjection { input, .. }
+        | Sort { input, .. }
+        | Explode { input, .. }
+        | Rename { input, .. }
+        | Cache { input, .. }
+        | Aggregate { input, .. }
+        | HStack { input, .. }
+        | Distinct { input, .. }
+        | Udf { input, .. }
+        | Sink { input, .. } => vec![*input],
+        Join {
+            input_left,
+            input_right,
+            ..
+        }
+        | SetOp {
+            input_left,
+            input_right,
+            ..
+        }
+        | JoinWhere {
+            input_left,
+            input_right,
+            ..
+        } => vec![*input_left, *input_right],
+        Union { inputs } => inputs.clone(),
+        #[cfg(feature = "parquet")]
+        ParquetScan { .. } => vec![],
+        #[cfg(feature = "ipc")]
+        IpcScan { .. } => vec![],
+        CsvScan { .. } | DataFrameScan { .. } => vec![],
+    }
+}
+
+/// Estimate the number of rows in a file from its size. Returns 0 if the file can't be read.
+fn file_rows(path: &str, schema: &Schema) -> f64 {
+    let bytes = std::fs::metadata(path).map_or(0, |metadata| metadata.len());
+    let bytes_per_row = BYTES_PER_VALUE * std::cmp::max(schema.len(), 1) as u64;
+    (bytes as f64 / bytes_per_row as f64).ceil()
+}
+
+/// The rows that are read by a scan and the rows that it outputs.
+fn scan_estimate(
+    rows: f64,
+    stop_after_n_rows: Option<usize>,
+    selectivity: f64,
+    aggregate: &[Node],
+) -> (f64, f64) {
+    let rows = stop_after_n_rows.map_or(rows, |n| rows.min(n as f64));
+    if aggregate.is_empty() {
+        (rows, rows * selectivity)
+    } else {
+        (rows, 1.0_f64.min(rows))
+    }
+}
+
+fn is_aggregation(node: Node, expr_arena: &Arena<AExpr>) -> bool {
+    match expr_arena.get(node) {
+        AExpr::Agg(_) => true,
+        AExpr::Alias(e, _) => is_aggregation(*e, expr_arena),
+        _ => false,
+    }
+}
+
+/// Estimate the fraction of the rows that pass the `predicate`.
+fn selectivity(predicate: Node, expr_arena: &Arena<AExpr>) -> f64 {
+    match expr_arena.get(predicate) {
+        AExpr::BinaryExpr { left, op, right } => match op {
+            Operator::And => selectivity(*left, expr_arena) * selectivity(*right, expr_arena),
+            Operator::Or => {
+                let left = selectivity(*left, expr_arena);
+                let right = selectivity(*right, expr_arena);
+                left + right - left * right
+            }
+            Operator::Eq => EQ_SELECTIVITY,
+            Operator::NotEq => 1.0 - EQ_SELECTIVITY,
+            Operator::Lt | Operator::LtEq | Operator::Gt | Operator::GtEq => RANGE_SELECTIVITY,
+            _ => DEFAULT_SELECTIVITY,
+        },
+        AExpr::Not(e) => 1.0 - selectivity(*e, expr_arena),
+        AExpr::IsNull(_) => EQ_SELECTIVITY,
+        AExpr::IsNotNull(_) => 1.0 - EQ_SELECTIVITY,
+        AExpr::Alias(e, _) => selectivity(*e, expr_arena),
+        AExpr::Literal(LiteralValue::Boolean(true)) => 1.0,
+        AExpr::Literal(LiteralValue::Boolean(false)) => 0.0,
+        _ => DEFAULT_SELECTIVITY,
+    }
+}
@@ -32,11 +32,13 @@ impl<'a> Iterator for ExprIter<'a> {
                         Mean(e) => push(e),
                         Median(e) => push(e),
                         NUnique(e) => push(e),
+                        NullCount(e) => push(e),
                         First(e) => push(e),
                         Last(e) => push(e),
                         List(e) => push(e),
                         Count(e) => push(e),
                         Quantile { expr, .. } => push(expr),
+                        TopK { expr, .. } => push(expr),
                         Sum(e) => push(e),
                         AggGroups(e) => push(e),
                         Std(e) => push(e),
@@ -54,7 +56,11 @@ impl<'a> Iterator for ExprIter<'a> {
                 }
                 Udf { input, .. } => push(input),
                 Shift { input, .. } => push(input),
+                ClipQuantile { input, .. } => push(input),
                 Reverse(e) => push(e),
+                CumCount(e) => push(e),
+                Standardize(e) => push(e),
+                MinMaxScale(e) => push(e),
                 Duplicated(e) => push(e),
                 Unique(e) => push(e),
                 Explode(e) => push(e),
@@ -120,11 +126,13 @@ impl AExpr {
                     Mean(e) => push(e),
                     Median(e) => push(e),
                     NUnique(e) => push(e),
+                    NullCount(e) => push(e),
                     First(e) => push(e),
                     Last(e) => push(e),
                     List(e) => push(e),
                     Count(e) => push(e),
                     Quantile { expr, .. } => push(expr),
+                    TopK { expr, .. } => push(expr),
                     Sum(e) => push(e),
                     AggGroups(e) => push(e),
                     Std(e) => push(e),
@@ -142,7 +150,11 @@ impl AExpr {
             }
             Udf { input, .. } => push(input),
             Shift { input, .. } => push(input),
+            ClipQuantile { input, .. } => push(input),
             Reverse(e) => push(e),
+            CumCount(e) => push(e),
+            Standardize(e) => push(e),
+            MinMaxScale(e) => push(e),
             Duplicated(e) => push(e),
             Unique(e) => push(e),
             Explode(e) => push(e),
@@ -1,3 +1,4 @@
+pub(crate) mod explain;
 pub(crate) mod iterator;
 pub(crate) mod optimizer;
 
@@ -17,7 +18,10 @@ use std::collections::HashSet;
 use std::{
     cell::Cell,
     fmt::{self, Debug, Formatter, Write},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
 };
 
 #[cfg_attr(docsrs, doc(cfg(feature = "temporal")))]
@@ -27,6 +31,13 @@ use polars_core::utils::chrono::NaiveDateTime;
 // Will be set/ unset in the fetch operation to communicate overwriting the number of rows to scan.
 thread_local! {pub(crate) static FETCH_ROWS: Cell<Option<usize>> = Cell::new(None)}
 
+static CACHE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Get a new id for a `Cache` node. All the `Cache` nodes with the same id share the result.
+pub(crate) fn next_cache_id() -> usize {
+    CACHE_ID.fetch_add(1, Ordering::Relaxed)
+}
+
 #[derive(Clone, Copy)]
 pub enum Context {
     Aggregation,
@@ -88,6 +99,9 @@ pub enum LiteralValue {
     },
     #[cfg(all(feature = "temporal", feature = "dtype-date64"))]
     DateTime(NaiveDateTime),
+    /// A duration in milliseconds.
+    #[cfg(all(feature = "temporal", feature = "dtype-duration-ms"))]
+    Duration(i64),
 }
 
 impl LiteralValue {
@@ -111,7 +125,10 @@ impl LiteralValue {
             LiteralValue::Range { data_type, .. } => data_type.clone(),
             #[cfg(all(feature = "temporal", feature = "dtype-date64"))]
             LiteralValue::DateTime(_) => DataType::Date64,
-            _ => panic!("Cannot treat {:?} as scalar value", self),
+            #[cfg(all(feature = "temporal", feature = "dtype-duration-ms"))]
+            LiteralValue::Duration(_) => DataType::Duration(TimeUnit::Millisecond),
+            // a null literal is evaluated as a boolean series
+            LiteralValue::Null => DataType::Boolean,
         }
     }
 }
@@ -121,12 +138,11 @@ impl LiteralValue {
 pub enum LogicalPlan {
     // filter on a boolean mask
     Selection {
-        input: Box<LogicalPlan>,
+        input: Arc<LogicalPlan>,
         predicate: Expr,
     },
-    Cache {
-        input: Box<LogicalPlan>,
-    },
+    /// Execute the input once and share the result with all `Cache` nodes with the same `id`.
+    Cache { input: Arc<LogicalPlan>, id: usize },
     CsvScan {
         path: String,
         schema: SchemaRef,
@@ -141,6 +157,8 @@ pub enum LogicalPlan {
         /// Aggregations at the scan level
         aggregate: Vec<Expr>,
         cache: bool,
+        /// Applied to the columns that are read, before the predicate and the aggregations.
+        column_transformers: ColumnTransformers,
     },
     #[cfg(feature = "parquet")]
     #[cfg_attr(docsrs, doc(cfg(feature = "parquet")))]
@@ -152,6 +170,19 @@ pub enum LogicalPlan {
         aggregate: Vec<Expr>,
         stop_after_n_rows: Option<usize>,
         cache: bool,
+        column_transformers: ColumnTransformers,
+    },
+    #[cfg(feature = "ipc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ipc")))]
+    IpcScan {
+        path: String,
+        schema: SchemaRef,
+        with_columns: Option<Vec<String>>,
+        predicate: Option<Expr>,
+        aggregate: Vec<Expr>,
+        stop_after_n_rows: Option<usize>,
+        cache: bool,
+        column_transformers: ColumnTransformers,
     },
     // we keep track of the projection and selection as it is cheaper to first project and then filter
     DataFrameScan {
@@ -164,73 +195,125 @@ pub enum LogicalPlan {
     // or may drop projected columns if they aren't in current schema (after optimization)
     LocalProjection {
         expr: Vec<Expr>,
-        input: Box<LogicalPlan>,
+        input: Arc<LogicalPlan>,
         schema: SchemaRef,
     },
     // vertical selection
     Projection {
         expr: Vec<Expr>,
-        input: Box<LogicalPlan>,
+        input: Arc<LogicalPlan>,
         schema: SchemaRef,
     },
     Aggregate {
-        input: Box<LogicalPlan>,
+        input: Arc<LogicalPlan>,
         keys: Arc<Vec<Expr>>,
         aggs: Vec<Expr>,
         schema: SchemaRef,
         apply: Option<Arc<dyn DataFrameUdf>>,
     },
     Join {
-        input_left: Box<LogicalPlan>,
-        input_right: Box<LogicalPlan>,
+        input_left: Arc<LogicalPlan>,
+        input_right: Arc<LogicalPlan>,
         schema: SchemaRef,
         how: JoinType,
         left_on: Vec<Expr>,
         right_on: Vec<Expr>,
-        allow_par: bool,
-        force_par: bool,
+        options: JoinOptions,
+    },
+    SetOp {
+        input_left: Arc<LogicalPlan>,
+        input_right: Arc<LogicalPlan>,
+        op: SetOperation,
+    },
+    JoinWhere {
+        input_left: Arc<LogicalPlan>,
+        input_right: Arc<LogicalPlan>,
+        schema: SchemaRef,
+        predicates: Vec<Expr>,
+        suffix: Option<String>,
     },
+    /// Concatenate the inputs vertically. All inputs have the same schema.
+    Union { inputs: Vec<LogicalPlan> },
     HStack {
-        input: Box<LogicalPlan>,
+        input: Arc<LogicalPlan>,
         exprs: Vec<Expr>,
         schema: SchemaRef,
     },
     Distinct {
-        input: Box<LogicalPlan>,
+        input: Arc<LogicalPlan>,
         maintain_order: bool,
         subset: Arc<Option<Vec<String>>>,
     },
+    /// Sort the rows lexicographically by the `by` expressions, with a sort direction for every
+    /// expression.
     Sort {
-        input: Box<LogicalPlan>,
-        by_column: String,
-        reverse: bool,
+        input: Arc<LogicalPlan>,
+        by: Vec<Expr>,
+        reverse: Vec<bool>,
     },
     Explode {
-        input: Box<LogicalPlan>,
+        input: Arc<LogicalPlan>,
         columns: Vec<String>,
     },
+    /// Rename columns of the input. The renames are applied simultaneously,
+    /// so names may be swapped.
+    Rename {
+        input: Arc<LogicalPlan>,
+        existing: Arc<Vec<String>>,
+        new: Arc<Vec<String>>,
+        schema: SchemaRef,
+    },
     Slice {
-        input: Box<LogicalPlan>,
+        input: Arc<LogicalPlan>,
         offset: usize,
         len: usize,
     },
     Melt {
-        input: Box<LogicalPlan>,
+        input: Arc<LogicalPlan>,
         id_vars: Arc<Vec<String>>,
         value_vars: Arc<Vec<String>>,
+        maintain_order: bool,
         schema: SchemaRef,
     },
     Udf {
-        input: Box<LogicalPlan>,
+        input: Arc<LogicalPlan>,
         function: Arc<dyn DataFrameUdf>,
         ///  allow predicate pushdown optimizations
         predicate_pd: bool,
         ///  allow projection pushdown optimizations
         projection_pd: bool,
         schema: Option<SchemaRef>,
+        /// The columns of the input that the function reads. Only these columns are projected
+        /// below the function, if set.
+        input_columns: Option<Arc<Vec<String>>>,
+    },
+    /// Write the result of `input` to the file at `path`. This is the terminal node of a query
+    /// that is executed with [LazyFrame::sink_csv](crate::frame::LazyFrame::sink_csv) or
+    /// [LazyFrame::sink_parquet](crate::frame::LazyFrame::sink_parquet).
+    Sink {
+        input: Arc<LogicalPlan>,
+        path: String,
+        file_type: SinkFileType,
+        /// Applied to the columns before they are written.
+        column_transformers: ColumnTransformers,
+    },
+    /// The plan could not be extended beyond `input`, e.g. because an expression refers to a
+    /// column that doesn't exist. The error is returned when the plan is optimized or executed.
+    Error {
+        input: Arc<LogicalPlan>,
+        err: Arc<PolarsError>,
     },
 }
 
+/// The file format that a [Sink](LogicalPlan::Sink) writes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SinkFileType {
+    Csv,
+    #[cfg(feature = "parquet")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parquet")))]
+    Parquet,
+}
+
 impl Default for LogicalPlan {
     fn default() -> Self {
         CsvScan {
@@ -245,6 +328,7 @@ impl Default for LogicalPlan {
             predicate: None,
             aggregate: vec![],
             cache: true,
+            column_transformers: ColumnTransformers::default(),
         }
     }
 }
@@ -253,7 +337,7 @@ impl fmt::Debug for LogicalPlan {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use LogicalPlan::*;
         match self {
-            Cache { input } => write!(f, "CACHE {:?}", input),
+            Cache { input, id } => write!(f, "CACHE {} {:?}", id, input),
             #[cfg(feature = "parquet")]
             ParquetScan {
                 path,
@@ -273,6 +357,25 @@ impl fmt::Debug for LogicalPlan {
                     path, n_columns, total_columns, predicate
                 )
             }
+            #[cfg(feature = "ipc")]
+            IpcScan {
+                path,
+                schema,
+                with_columns,
+                predicate,
+                ..
+            } => {
+                let total_columns = schema.fields().len();
+                let mut n_columns = "*".to_string();
+                if let Some(columns) = with_columns {
+                    n_columns = format!("{}", columns.len());
+                }
+                write!(
+                    f,
+                    "IPC SCAN {}; PROJECT {}/{} COLUMNS; SELECTION: {:?}",
+                    path, n_columns, total_columns, predicate
+                )
+            }
             Selection { predicate, input } => {
                 write!(f, "FILTER\n\t{:?}\nFROM\n\t{:?}", predicate, input)
             }
@@ -334,12 +437,16 @@ impl fmt::Debug for LogicalPlan {
                     input
                 )
             }
-            Sort {
-                input, by_column, ..
-            } => write!(f, "SORT {:?} BY COLUMN {}", input, by_column),
+            Sort { input, by, .. } => write!(f, "SORT {:?} BY {:?}", input, by),
             Explode { input, columns, .. } => {
                 write!(f, "EXPLODE COLUMN(S) {:?} OF {:?}", columns, input)
             }
+            Rename {
+                input,
+                existing,
+                new,
+                ..
+            } => write!(f, "RENAME {:?} TO {:?} OF {:?}", existing, new, input),
             Aggregate {
                 input, keys, aggs, ..
             } => write!(f, "Aggregate\n\t{:?} BY {:?} FROM {:?}", aggs, keys, input),
@@ -354,6 +461,26 @@ impl fmt::Debug for LogicalPlan {
                 "JOIN\n\t({:?})\nWITH\n\t({:?})\nON (left: {:?} right: {:?})",
                 input_left, input_right, left_on, right_on
             ),
+            SetOp {
+                input_left,
+                input_right,
+                op,
+            } => write!(
+                f,
+                "{:?}\n\t({:?})\nWITH\n\t({:?})",
+                op, input_left, input_right
+            ),
+            Union { inputs } => write!(f, "UNION {:?}", inputs),
+            JoinWhere {
+                input_left,
+                input_right,
+                predicates,
+                ..
+            } => write!(
+                f,
+                "JOIN\n\t({:?})\nWITH\n\t({:?})\nWHERE {:?}",
+                input_left, input_right, predicates
+            ),
             HStack { input, exprs, .. } => {
                 write!(f, "STACK [{:?}\n\tWITH COLUMN(S)\n{:?}\n]", input, exprs)
             }
@@ -362,6 +489,13 @@ impl fmt::Debug for LogicalPlan {
                 write!(f, "SLICE {:?}, offset: {}, len: {}", input, offset, len)
             }
             Udf { input, .. } => write!(f, "UDF {:?}", input),
+            Sink {
+                input,
+                path,
+                file_type,
+                ..
+            } => write!(f, "SINK {:?} {}\n\t{:?}", file_type, path, input),
+            Error { input, err } => write!(f, "ERROR: {}\n\t{:?}", err, input),
         }
     }
 }
@@ -400,8 +534,11 @@ impl LogicalPlan {
     pub(crate) fn dot(&self, acc_str: &mut String, id: usize, prev_node: &str) -> std::fmt::Result {
         use LogicalPlan::*;
         match self {
-            Cache { input } => {
-                let current_node = format!("CACHE [{}]", id);
+            Cache {
+                input,
+                id: cache_id,
+            } => {
+                let current_node = format!("CACHE {} [{}]", cache_id, id);
                 self.write_dot(acc_str, prev_node, &current_node, id)?;
                 input.dot(acc_str, id + 1, &current_node)
             }
@@ -470,10 +607,8 @@ impl LogicalPlan {
                 self.write_dot(acc_str, prev_node, &current_node, id)?;
                 input.dot(acc_str, id + 1, &current_node)
             }
-            Sort {
-                input, by_column, ..
-            } => {
-                let current_node = format!("SORT by {} [{}]", by_column, id);
+            Sort { input, by, .. } => {
+                let current_node = format!("SORT BY {:?} [{}]", by, id);
                 self.write_dot(acc_str, prev_node, &current_node, id)?;
                 input.dot(acc_str, id + 1, &current_node)
             }
@@ -492,6 +627,16 @@ impl LogicalPlan {
                 self.write_dot(acc_str, prev_node, &current_node, id)?;
                 input.dot(acc_str, id + 1, &current_node)
             }
+            Rename {
+                input,
+                existing,
+                new,
+                ..
+            } => {
+                let current_node = format!("RENAME {:?} TO {:?} [{}]", existing, new, id);
+                self.write_dot(acc_str, prev_node, &current_node, id)?;
+                input.dot(acc_str, id + 1, &current_node)
+            }
             Melt { input, .. } => {
                 let current_node = format!("MELT [{}]", id);
                 self.write_dot(acc_str, prev_node, &current_node, id)?;
@@ -569,6 +714,32 @@ impl LogicalPlan {
                     self.write_dot(acc_str, prev_node, &current_node, id)
                 }
             }
+            #[cfg(feature = "ipc")]
+            IpcScan {
+                path,
+                schema,
+                with_columns,
+                predicate,
+                ..
+            } => {
+                let total_columns = schema.fields().len();
+                let mut n_columns = "*".to_string();
+                if let Some(columns) = with_columns {
+                    n_columns = format!("{}", columns.len());
+                }
+
+                let pred = fmt_predicate(predicate.as_ref());
+                let current_node = format!(
+                    "IPC SCAN {};\nπ {}/{};\nσ {} [{}]",
+                    path, n_columns, total_columns, pred, id
+                );
+                if id == 0 {
+                    self.write_dot(acc_str, prev_node, &current_node, id)?;
+                    write!(acc_str, "\"{}\"", current_node)
+                } else {
+                    self.write_dot(acc_str, prev_node, &current_node, id)
+                }
+            }
             Join {
                 input_left,
                 input_right,
@@ -582,11 +753,55 @@ impl LogicalPlan {
                 input_left.dot(acc_str, id + 1, &current_node)?;
                 input_right.dot(acc_str, id + 1, &current_node)
             }
+            SetOp {
+                input_left,
+                input_right,
+                op,
+            } => {
+                let current_node = format!("{:?} [{}]", op, id);
+                self.write_dot(acc_str, prev_node, &current_node, id)?;
+                input_left.dot(acc_str, id + 1, &current_node)?;
+                input_right.dot(acc_str, id + 1, &current_node)
+            }
+            Union { inputs } => {
+                let current_node = format!("UNION [{}]", id);
+                self.write_dot(acc_str, prev_node, &current_node, id)?;
+                for input in inputs {
+                    input.dot(acc_str, id + 1, &current_node)?;
+                }
+                Ok(())
+            }
+            JoinWhere {
+                input_left,
+                input_right,
+                predicates,
+                ..
+            } => {
+                let current_node = format!("JOIN WHERE {:?} [{}]", predicates, id);
+                self.write_dot(acc_str, prev_node, &current_node, id)?;
+                input_left.dot(acc_str, id + 1, &current_node)?;
+                input_right.dot(acc_str, id + 1, &current_node)
+            }
             Udf { input, .. } => {
                 let current_node = format!("UDF [{}]", id);
                 self.write_dot(acc_str, prev_node, &current_node, id)?;
                 input.dot(acc_str, id + 1, &current_node)
             }
+            Sink {
+                input,
+                path,
+                file_type,
+                ..
+            } => {
+                let current_node = format!("SINK {:?} {} [{}]", file_type, path, id);
+                self.write_dot(acc_str, prev_node, &current_node, id)?;
+                input.dot(acc_str, id + 1, &current_node)
+            }
+            Error { input, err } => {
+                let current_node = format!("ERROR {} [{}]", err, id);
+                self.write_dot(acc_str, prev_node, &current_node, id)?;
+                input.dot(acc_str, id + 1, &current_node)
+            }
         }
     }
 }
@@ -611,6 +826,15 @@ fn replace_wildcard_with_column(expr: Expr, column_name: Arc<String>) -> Expr {
         Expr::Reverse(expr) => {
             Expr::Reverse(Box::new(replace_wildcard_with_column(*expr, column_name)))
         }
+        Expr::CumCount(expr) => {
+            Expr::CumCount(Box::new(replace_wildcard_with_column(*expr, column_name)))
+        }
+        Expr::Standardize(expr) => {
+            Expr::Standardize(Box::new(replace_wildcard_with_column(*expr, column_name)))
+        }
+        Expr::MinMaxScale(expr) => {
+            Expr::MinMaxScale(Box::new(replace_wildcard_with_column(*expr, column_name)))
+        }
         Expr::Explode(expr) => {
             Expr::Explode(Box::new(replace_wildcard_with_column(*expr, column_name)))
         }
@@ -686,6 +910,9 @@ fn replace_wildcard_with_column(expr: Expr, column_name: Arc<String>) -> Expr {
             AggExpr::NUnique(e) => {
                 AggExpr::NUnique(Box::new(replace_wildcard_with_column(*e, column_name)))
             }
+            AggExpr::NullCount(e) => {
+                AggExpr::NullCount(Box::new(replace_wildcard_with_column(*e, column_name)))
+            }
             AggExpr::AggGroups(e) => {
                 AggExpr::AggGroups(Box::new(replace_wildcard_with_column(*e, column_name)))
             }
@@ -693,6 +920,10 @@ fn replace_wildcard_with_column(expr: Expr, column_name: Arc<String>) -> Expr {
                 expr: Box::new(replace_wildcard_with_column(*expr, column_name)),
                 quantile,
             },
+            AggExpr::TopK { expr, k } => AggExpr::TopK {
+                expr: Box::new(replace_wildcard_with_column(*expr, column_name)),
+                k,
+            },
             AggExpr::List(e) => {
                 AggExpr::List(Box::new(replace_wildcard_with_column(*e, column_name)))
             }
@@ -708,6 +939,15 @@ fn replace_wildcard_with_column(expr: Expr, column_name: Arc<String>) -> Expr {
             input: Box::new(replace_wildcard_with_column(*input, column_name)),
             periods,
         },
+        Expr::ClipQuantile {
+            input,
+            low_q,
+            high_q,
+        } => Expr::ClipQuantile {
+            input: Box::new(replace_wildcard_with_column(*input, column_name)),
+            low_q,
+            high_q,
+        },
         Expr::Slice {
             input,
             offset,
@@ -733,7 +973,7 @@ fn replace_wildcard_with_column(expr: Expr, column_name: Arc<String>) -> Expr {
 
 /// In case of single col(*) -> do nothing, no selection is the same as select all
 /// In other cases replace the wildcard with an expression with all columns
-fn rewrite_projections(exprs: Vec<Expr>, schema: &Schema) -> Vec<Expr> {
+fn rewrite_projections(exprs: Vec<Expr>, schema: &Schema) -> Result<Vec<Expr>> {
     let mut result = Vec::with_capacity(exprs.len() + schema.fields().len());
     let mut exclude = vec![];
     for expr in exprs {
@@ -744,7 +984,9 @@ fn rewrite_projections(exprs: Vec<Expr>, schema: &Schema) -> Vec<Expr> {
                 exclude.push(name.clone());
                 continue;
             } else {
-                panic!("Except expression should have column name")
+                return Err(PolarsError::InvalidOperation(
+                    format!("cannot use {:?} as a column exception", column).into(),
+                ));
             }
         }
 
@@ -761,8 +1003,10 @@ fn rewrite_projections(exprs: Vec<Expr>, schema: &Schema) -> Vec<Expr> {
             // if count wildcard. count one column
             let dummy = &Expr::Agg(AggExpr::Count(Box::new(Expr::Wildcard)));
             if has_expr(&expr, dummy) {
-                let new_name = Arc::new(schema.field(0).unwrap().name().clone());
-                let expr = rename_expr_root_name(&expr, new_name).unwrap();
+                let field = schema.field(0).ok_or_else(|| {
+                    PolarsError::NoData("cannot count the rows of a plan without columns".into())
+                })?;
+                let expr = rename_expr_root_name(&expr, Arc::new(field.name().clone()))?;
 
                 let expr = if let Expr::Alias(_, _) = &expr {
                     expr
@@ -777,6 +1021,12 @@ fn rewrite_projections(exprs: Vec<Expr>, schema: &Schema) -> Vec<Expr> {
             for field in schema.fields() {
                 let name = field.name();
                 let new_expr = replace_wildcard_with_column(expr.clone(), Arc::new(name.clone()));
+                // a wildcard only aggregates the columns that support the aggregation
+                if let Err(PolarsError::InvalidOperation(_)) =
+                    new_expr.to_field(schema, Context::Other)
+                {
+                    continue;
+                }
                 result.push(new_expr)
             }
         } else {
@@ -796,7 +1046,7 @@ fn rewrite_projections(exprs: Vec<Expr>, schema: &Schema) -> Vec<Expr> {
             }
         }
     }
-    result
+    Ok(result)
 }
 
 pub struct LogicalPlanBuilder(LogicalPlan);
@@ -805,11 +1055,14 @@ impl LogicalPlan {
     pub(crate) fn schema(&self) -> &Schema {
         use LogicalPlan::*;
         match self {
-            Cache { input } => input.schema(),
+            Cache { input, .. } => input.schema(),
             Sort { input, .. } => input.schema(),
             Explode { input, .. } => input.schema(),
+            Rename { schema, .. } => schema,
             #[cfg(feature = "parquet")]
             ParquetScan { schema, .. } => schema,
+            #[cfg(feature = "ipc")]
+            IpcScan { schema, .. } => schema,
             DataFrameScan { schema, .. } => schema,
             Selection { input, .. } => input.schema(),
             CsvScan { schema, .. } => schema,
@@ -817,6 +1070,9 @@ impl LogicalPlan {
             LocalProjection { schema, .. } => schema,
             Aggregate { schema, .. } => schema,
             Join { schema, .. } => schema,
+            SetOp { input_left, .. } => input_left.schema(),
+            JoinWhere { schema, .. } => schema,
+            Union { inputs } => inputs[0].schema(),
             HStack { schema, .. } => schema,
             Distinct { input, .. } => input.schema(),
             Slice { input, .. } => input.schema(),
@@ -825,11 +1081,46 @@ impl LogicalPlan {
                 Some(schema) => schema,
                 None => input.schema(),
             },
+            Sink { input, .. } => input.schema(),
+            Error { input, .. } => input.schema(),
         }
     }
     pub fn describe(&self) -> String {
         format!("{:#?}", self)
     }
+
+    /// The first error that occurred while the plan was built, if any.
+    pub(crate) fn error(&self) -> Option<PolarsError> {
+        let input_err = match self {
+            LogicalPlan::Union { inputs } => inputs.iter().find_map(|lp| lp.error()),
+            lp => optimizer::common_subplan::inputs(lp)
+                .into_iter()
+                .find_map(|lp| lp.error()),
+        };
+        input_err.or_else(|| match self {
+            LogicalPlan::Error { err, .. } => Some(copy_error(err)),
+            _ => None,
+        })
+    }
+}
+
+/// A copy of an error of an [Error](LogicalPlan::Error) node, as a plan can be executed more
+/// than once.
+fn copy_error(err: &PolarsError) -> PolarsError {
+    use PolarsError::*;
+    match err {
+        InvalidOperation(msg) => InvalidOperation(msg.clone()),
+        DataTypeMisMatch(msg) => DataTypeMisMatch(msg.clone()),
+        NotFound(msg) => NotFound(msg.clone()),
+        ShapeMisMatch(msg) => ShapeMisMatch(msg.clone()),
+        OutOfBounds(msg) => OutOfBounds(msg.clone()),
+        NoData(msg) => NoData(msg.clone()),
+        ValueError(msg) => ValueError(msg.clone()),
+        UnknownSchema(msg) => UnknownSchema(msg.clone()),
+        Duplicate(msg) => Duplicate(msg.clone()),
+        Io(err) => Io(std::io::Error::new(err.kind(), err.to_string())),
+        err => Other(err.to_string().into()),
+    }
 }
 
 impl From<LogicalPlan> for LogicalPlanBuilder {
@@ -838,22 +1129,28 @@ impl From<LogicalPlan> for LogicalPlanBuilder {
     }
 }
 
-pub(crate) fn prepare_projection(exprs: Vec<Expr>, schema: &Schema) -> (Vec<Expr>, Schema) {
-    let exprs = rewrite_projections(exprs, schema);
-    let schema = utils::expressions_to_schema(&exprs, schema, Context::Other);
-    (exprs, schema)
+pub(crate) fn prepare_projection(exprs: Vec<Expr>, schema: &Schema) -> Result<(Vec<Expr>, Schema)> {
+    let exprs = rewrite_projections(exprs, schema)?;
+    let schema = utils::expressions_to_schema(&exprs, schema, Context::Other)?;
+    Ok((exprs, schema))
 }
 
 impl LogicalPlanBuilder {
     #[cfg(feature = "parquet")]
     #[cfg_attr(docsrs, doc(cfg(feature = "parquet")))]
-    pub fn scan_parquet(path: String, stop_after_n_rows: Option<usize>, cache: bool) -> Self {
-        let file = std::fs::File::open(&path).expect("could not open file");
-        let schema = Arc::new(
-            ParquetReader::new(file)
-                .schema()
-                .expect("could not get parquet schema"),
-        );
+    pub fn scan_parquet(
+        path: String,
+        stop_after_n_rows: Option<usize>,
+        cache: bool,
+        column_transformers: ColumnTransformers,
+    ) -> Self {
+        let schema = match std::fs::File::open(&path)
+            .map_err(PolarsError::from)
+            .and_then(|file| ParquetReader::new(file).schema())
+        {
+            Ok(schema) => Arc::new(schema),
+            Err(err) => return Self::scan_error(err),
+        };
 
         LogicalPlan::ParquetScan {
             path,
@@ -863,6 +1160,35 @@ impl LogicalPlanBuilder {
             predicate: None,
             aggregate: vec![],
             cache,
+            column_transformers,
+        }
+        .into()
+    }
+    #[cfg(feature = "ipc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ipc")))]
+    pub fn scan_ipc(
+        path: String,
+        stop_after_n_rows: Option<usize>,
+        cache: bool,
+        column_transformers: ColumnTransformers,
+    ) -> Self {
+        let schema = match std::fs::File::open(&path)
+            .map_err(PolarsError::from)
+            .and_then(|file| IpcReader::new(file).schema())
+        {
+            Ok(schema) => Arc::new(schema),
+            Err(err) => return Self::scan_error(err),
+        };
+
+        LogicalPlan::IpcScan {
+            path,
+            schema,
+            stop_after_n_rows,
+            with_columns: None,
+            predicate: None,
+            aggregate: vec![],
+            cache,
+            column_transformers,
         }
         .into()
     }
@@ -878,20 +1204,28 @@ impl LogicalPlanBuilder {
         cache: bool,
         schema: Option<Arc<Schema>>,
         schema_overwrite: Option<&Schema>,
+        column_transformers: ColumnTransformers,
     ) -> Self {
-        let mut file = std::fs::File::open(&path).expect("could not open file");
-
-        let schema = schema.unwrap_or_else(|| {
-            let (schema, _) = infer_file_schema(
-                &mut file,
-                delimiter,
-                Some(100),
-                has_header,
-                schema_overwrite,
-            )
-            .expect("could not read schema");
-            Arc::new(schema)
-        });
+        let schema = match schema {
+            Some(schema) => schema,
+            None => {
+                let inferred = std::fs::File::open(&path)
+                    .map_err(PolarsError::from)
+                    .and_then(|mut file| {
+                        infer_file_schema(
+                            &mut file,
+                            delimiter,
+                            Some(100),
+                            has_header,
+                            schema_overwrite,
+                        )
+                    });
+                match inferred {
+                    Ok((schema, _)) => Arc::new(schema),
+                    Err(err) => return Self::scan_error(err),
+                }
+            }
+        };
         LogicalPlan::CsvScan {
             path,
             schema,
@@ -904,25 +1238,44 @@ impl LogicalPlanBuilder {
             predicate: None,
             aggregate: vec![],
             cache,
+            column_transformers,
+        }
+        .into()
+    }
+
+    /// A plan that fails with `err` when it is executed.
+    pub(crate) fn error(self, err: PolarsError) -> Self {
+        LogicalPlan::Error {
+            input: Arc::new(self.0),
+            err: Arc::new(err),
         }
         .into()
     }
 
+    /// A scan that failed to find its files or to read their schema.
+    pub(crate) fn scan_error(err: PolarsError) -> Self {
+        Self::from_existing_df(DataFrame::new_no_checks(vec![])).error(err)
+    }
+
     pub fn cache(self) -> Self {
         LogicalPlan::Cache {
-            input: Box::new(self.0),
+            input: Arc::new(self.0),
+            id: next_cache_id(),
         }
         .into()
     }
 
     pub fn project(self, exprs: Vec<Expr>) -> Self {
-        let (exprs, schema) = prepare_projection(exprs, &self.0.schema());
+        let (exprs, schema) = match prepare_projection(exprs, &self.0.schema()) {
+            Ok(projection) => projection,
+            Err(err) => return self.error(err),
+        };
 
         // if len == 0, no projection has to be done. This is a select all operation.
         if !exprs.is_empty() {
             LogicalPlan::Projection {
                 expr: exprs,
-                input: Box::new(self.0),
+                input: Arc::new(self.0),
                 schema: Arc::new(schema),
             }
             .into()
@@ -932,11 +1285,14 @@ impl LogicalPlanBuilder {
     }
 
     pub fn project_local(self, exprs: Vec<Expr>) -> Self {
-        let (exprs, schema) = prepare_projection(exprs, &self.0.schema());
+        let (exprs, schema) = match prepare_projection(exprs, &self.0.schema()) {
+            Ok(projection) => projection,
+            Err(err) => return self.error(err),
+        };
         if !exprs.is_empty() {
             LogicalPlan::LocalProjection {
                 expr: exprs,
-                input: Box::new(self.0),
+                input: Arc::new(self.0),
                 schema: Arc::new(schema),
             }
             .into()
@@ -968,7 +1324,10 @@ impl LogicalPlanBuilder {
         let mut new_fields = schema.fields().clone();
 
         for e in &exprs {
-            let field = e.to_field(schema, Context::Other).unwrap();
+            let field = match e.to_field(schema, Context::Other) {
+                Ok(field) => field,
+                Err(err) => return self.error(err),
+            };
             match schema.index_of(field.name()) {
                 Ok(idx) => {
                     new_fields[idx] = field;
@@ -980,13 +1339,33 @@ impl LogicalPlanBuilder {
         let new_schema = Schema::new(new_fields);
 
         LogicalPlan::HStack {
-            input: Box::new(self.0),
+            input: Arc::new(self.0),
             exprs,
             schema: Arc::new(new_schema),
         }
         .into()
     }
 
+    /// Cast the columns of `dtypes` to their data type. Columns that are not in the schema, or
+    /// that already have the data type, are ignored.
+    pub fn cast(self, dtypes: &Schema) -> Self {
+        let schema = self.0.schema();
+        let exprs = dtypes
+            .fields()
+            .iter()
+            .filter(|f| match schema.field_with_name(f.name()) {
+                Ok(field) => field.data_type() != f.data_type(),
+                Err(_) => false,
+            })
+            .map(|f| col(f.name()).cast(f.data_type().clone()))
+            .collect::<Vec<_>>();
+        if exprs.is_empty() {
+            self
+        } else {
+            self.with_columns(exprs)
+        }
+    }
+
     /// Apply a filter
     pub fn filter(self, predicate: Expr) -> Self {
         let predicate = if has_expr(&predicate, &Expr::Wildcard) {
@@ -999,7 +1378,7 @@ impl LogicalPlanBuilder {
         };
         LogicalPlan::Selection {
             predicate,
-            input: Box::new(self.0),
+            input: Arc::new(self.0),
         }
         .into()
     }
@@ -1012,14 +1391,19 @@ impl LogicalPlanBuilder {
     ) -> Self {
         debug_assert!(!keys.is_empty());
         let current_schema = self.0.schema();
-        let aggs = rewrite_projections(aggs, current_schema);
-
-        let schema1 = utils::expressions_to_schema(&keys, current_schema, Context::Other);
-        let schema2 = utils::expressions_to_schema(&aggs, current_schema, Context::Aggregation);
-        let schema = Schema::try_merge(&[schema1, schema2]).unwrap();
+        let schema = rewrite_projections(aggs, current_schema).and_then(|aggs| {
+            let schema1 = utils::expressions_to_schema(&keys, current_schema, Context::Other)?;
+            let schema2 =
+                utils::expressions_to_schema(&aggs, current_schema, Context::Aggregation)?;
+            Ok((aggs, Schema::try_merge(&[schema1, schema2])?))
+        });
+        let (aggs, schema) = match schema {
+            Ok(schema) => schema,
+            Err(err) => return self.error(err),
+        };
 
         LogicalPlan::Aggregate {
-            input: Box::new(self.0),
+            input: Arc::new(self.0),
             keys,
             aggs,
             schema: Arc::new(schema),
@@ -1043,10 +1427,10 @@ impl LogicalPlanBuilder {
         .into()
     }
 
-    pub fn sort(self, by_column: String, reverse: bool) -> Self {
+    pub fn sort(self, by: Vec<Expr>, reverse: Vec<bool>) -> Self {
         LogicalPlan::Sort {
-            input: Box::new(self.0),
-            by_column,
+            input: Arc::new(self.0),
+            by,
             reverse,
         }
         .into()
@@ -1054,18 +1438,41 @@ impl LogicalPlanBuilder {
 
     pub fn explode(self, columns: Vec<String>) -> Self {
         LogicalPlan::Explode {
-            input: Box::new(self.0),
+            input: Arc::new(self.0),
             columns,
         }
         .into()
     }
 
-    pub fn melt(self, id_vars: Arc<Vec<String>>, value_vars: Arc<Vec<String>>) -> Self {
-        let schema = det_melt_schema(&value_vars, self.0.schema());
+    pub fn rename(self, existing: Vec<String>, new: Vec<String>) -> Self {
+        let schema = match det_rename_schema(&existing, &new, self.0.schema()) {
+            Ok(schema) => schema,
+            Err(err) => return self.error(err),
+        };
+        LogicalPlan::Rename {
+            input: Arc::new(self.0),
+            existing: Arc::new(existing),
+            new: Arc::new(new),
+            schema,
+        }
+        .into()
+    }
+
+    pub fn melt(
+        self,
+        id_vars: Arc<Vec<String>>,
+        value_vars: Arc<Vec<String>>,
+        maintain_order: bool,
+    ) -> Self {
+        let schema = match det_melt_schema(&value_vars, self.0.schema()) {
+            Ok(schema) => schema,
+            Err(err) => return self.error(err),
+        };
         LogicalPlan::Melt {
-            input: Box::new(self.0),
+            input: Arc::new(self.0),
             id_vars,
             value_vars,
+            maintain_order,
             schema,
         }
         .into()
@@ -1073,7 +1480,7 @@ impl LogicalPlanBuilder {
 
     pub fn drop_duplicates(self, maintain_order: bool, subset: Option<Vec<String>>) -> Self {
         LogicalPlan::Distinct {
-            input: Box::new(self.0),
+            input: Arc::new(self.0),
             maintain_order,
             subset: Arc::new(subset),
         }
@@ -1082,7 +1489,7 @@ impl LogicalPlanBuilder {
 
     pub fn slice(self, offset: usize, len: usize) -> Self {
         LogicalPlan::Slice {
-            input: Box::new(self.0),
+            input: Arc::new(self.0),
             offset,
             len,
         }
@@ -1095,8 +1502,7 @@ impl LogicalPlanBuilder {
         how: JoinType,
         left_on: Vec<Expr>,
         right_on: Vec<Expr>,
-        allow_par: bool,
-        force_par: bool,
+        options: JoinOptions,
     ) -> Self {
         let schema_left = self.0.schema();
         let schema_right = other.schema();
@@ -1113,15 +1519,20 @@ impl LogicalPlanBuilder {
 
         let right_names: HashSet<_, RandomState> = right_on
             .iter()
-            .map(|e| utils::output_name(e).expect("could not find name"))
-            .collect();
+            .map(utils::output_name)
+            .collect::<Result<_>>();
+        let right_names: HashSet<_, RandomState> = match right_names {
+            Ok(right_names) => right_names,
+            Err(err) => return self.error(err),
+        };
 
         for f in schema_right.fields() {
             let name = f.name();
 
             if !right_names.contains(name) {
                 if names.contains(name) {
-                    let new_name = format!("{}_right", name);
+                    let new_name =
+                        format!("{}{}", name, options.suffix.as_deref().unwrap_or("_right"));
                     let field = Field::new(&new_name, f.data_type().clone());
                     fields.push(field)
                 } else {
@@ -1129,42 +1540,123 @@ impl LogicalPlanBuilder {
                 }
             }
         }
+        if options.indicator {
+            fields.push(Field::new(
+                JOIN_INDICATOR_NAME,
+                DataType::Categorical(CategoricalOrdering::Physical),
+            ));
+        }
 
         let schema = Arc::new(Schema::new(fields));
 
         LogicalPlan::Join {
-            input_left: Box::new(self.0),
-            input_right: Box::new(other),
+            input_left: Arc::new(self.0),
+            input_right: Arc::new(other),
             how,
             schema,
             left_on,
             right_on,
-            allow_par,
-            force_par,
+            options,
         }
         .into()
     }
+
+    pub fn join_where(
+        self,
+        other: LogicalPlan,
+        predicates: Vec<Expr>,
+        suffix: Option<String>,
+    ) -> Self {
+        let schema_left = self.0.schema();
+        let schema_right = other.schema();
+
+        // all columns of both tables are kept, duplicate names of the right table get the suffix
+        let mut fields = schema_left.fields().clone();
+        for f in schema_right.fields() {
+            if schema_left.field_with_name(f.name()).is_ok() {
+                let new_name = format!("{}{}", f.name(), suffix.as_deref().unwrap_or("_right"));
+                fields.push(Field::new(&new_name, f.data_type().clone()))
+            } else {
+                fields.push(f.clone())
+            }
+        }
+        let schema = Arc::new(Schema::new(fields));
+
+        LogicalPlan::JoinWhere {
+            input_left: Arc::new(self.0),
+            input_right: Arc::new(other),
+            schema,
+            predicates,
+            suffix,
+        }
+        .into()
+    }
+
+    /// Concatenate the plans vertically. Errors if the plans don't all have the same schema.
+    pub fn union(inputs: Vec<LogicalPlan>) -> Result<Self> {
+        let first = inputs
+            .first()
+            .ok_or_else(|| PolarsError::NoData("cannot union zero plans".into()))?;
+        if let Some(other) = inputs.iter().find(|lp| lp.schema() != first.schema()) {
+            return Err(PolarsError::UnknownSchema(
+                format!(
+                    "cannot union plans with different schemas: {:?} and {:?}",
+                    first.schema(),
+                    other.schema()
+                )
+                .into(),
+            ));
+        }
+        Ok(LogicalPlan::Union { inputs }.into())
+    }
+
+    pub fn set_operation(self, other: LogicalPlan, op: SetOperation) -> Self {
+        LogicalPlan::SetOp {
+            input_left: Arc::new(self.0),
+            input_right: Arc::new(other),
+            op,
+        }
+        .into()
+    }
+
     pub fn map<F>(
         self,
         function: F,
         optimizations: AllowedOptimizations,
         schema: Option<SchemaRef>,
+        input_columns: Option<Arc<Vec<String>>>,
     ) -> Self
     where
         F: DataFrameUdf + 'static,
     {
         LogicalPlan::Udf {
-            input: Box::new(self.0),
+            input: Arc::new(self.0),
             function: Arc::new(function),
             predicate_pd: optimizations.predicate_pushdown,
             projection_pd: optimizations.projection_pushdown,
             schema,
+            input_columns,
+        }
+        .into()
+    }
+
+    pub fn sink(
+        self,
+        path: String,
+        file_type: SinkFileType,
+        column_transformers: ColumnTransformers,
+    ) -> Self {
+        LogicalPlan::Sink {
+            input: Arc::new(self.0),
+            path,
+            file_type,
+            column_transformers,
         }
         .into()
     }
 }
 
-pub(crate) fn det_melt_schema(value_vars: &[String], input_schema: &Schema) -> SchemaRef {
+pub(crate) fn det_melt_schema(value_vars: &[String], input_schema: &Schema) -> Result<SchemaRef> {
     let mut fields = input_schema
         .fields()
         .iter()
@@ -1174,15 +1666,66 @@ pub(crate) fn det_melt_schema(value_vars: &[String], input_schema: &Schema) -> S
 
     fields.reserve(2);
 
-    let value_dtype = input_schema
-        .field_with_name(&value_vars[0])
-        .expect("field not found")
-        .data_type();
+    let value_var = value_vars
+        .first()
+        .ok_or_else(|| PolarsError::NoData("cannot melt without value columns".into()))?;
+    let value_dtype = input_schema.field_with_name(value_var)?.data_type();
 
     fields.push(Field::new("variable", DataType::Utf8));
     fields.push(Field::new("value", value_dtype.clone()));
 
-    Arc::new(Schema::new(fields))
+    Ok(Arc::new(Schema::new(fields)))
+}
+
+/// Determine the output schema of a rename. All renames are applied at once.
+pub(crate) fn det_rename_schema(
+    existing: &[String],
+    new: &[String],
+    input_schema: &Schema,
+) -> Result<SchemaRef> {
+    if existing.len() != new.len() {
+        return Err(PolarsError::ShapeMisMatch(
+            format!(
+                "cannot rename {} columns to {} new names",
+                existing.len(),
+                new.len()
+            )
+            .into(),
+        ));
+    }
+    for name in existing {
+        if input_schema.index_of(name).is_err() {
+            return Err(PolarsError::NotFound(format!(
+                "cannot rename column {}, it is not in the schema",
+                name
+            )));
+        }
+    }
+
+    let fields = input_schema
+        .fields()
+        .iter()
+        .map(
+            |field| match existing.iter().position(|name| name == field.name()) {
+                Some(idx) => Field::new(&new[idx], field.data_type().clone()),
+                None => field.clone(),
+            },
+        )
+        .collect_vec();
+
+    let mut names = HashSet::with_capacity(fields.len());
+    for field in &fields {
+        if !names.insert(field.name()) {
+            return Err(PolarsError::Duplicate(
+                format!(
+                    "renaming would create more than one column named '{}'",
+                    field.name()
+                )
+                .into(),
+            ));
+        }
+    }
+    Ok(Arc::new(Schema::new(fields)))
 }
 
 #[cfg(test)]
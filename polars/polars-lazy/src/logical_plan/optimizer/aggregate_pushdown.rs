@@ -95,7 +95,13 @@ impl OptimizationRule for AggregatePushdown {
                 schema,
             } => self.pushdown_projection(node, expr, input, schema, lp_arena, expr_arena),
             // todo! hstack should pushown not dependent columns
-            Join { .. } | Aggregate { .. } | HStack { .. } | DataFrameScan { .. } => {
+            Join { .. }
+            | SetOp { .. }
+            | Union { .. }
+            | JoinWhere { .. }
+            | Aggregate { .. }
+            | HStack { .. }
+            | DataFrameScan { .. } => {
                 if self.state.is_empty() {
                     lp_arena.replace(node, lp);
                     None
@@ -134,6 +140,7 @@ impl OptimizationRule for AggregatePushdown {
                 predicate,
                 aggregate,
                 cache,
+                column_transformers,
             } => match self.state.is_empty() {
                 true => {
                     lp_arena.replace(
@@ -150,6 +157,7 @@ impl OptimizationRule for AggregatePushdown {
                             predicate,
                             aggregate,
                             cache,
+                            column_transformers,
                         },
                     );
                     None
@@ -168,6 +176,7 @@ impl OptimizationRule for AggregatePushdown {
                         predicate,
                         aggregate,
                         cache,
+                        column_transformers,
                     })
                 }
             },
@@ -180,6 +189,7 @@ impl OptimizationRule for AggregatePushdown {
                 aggregate,
                 stop_after_n_rows,
                 cache,
+                column_transformers,
             } => match self.state.is_empty() {
                 true => {
                     lp_arena.replace(
@@ -192,6 +202,7 @@ impl OptimizationRule for AggregatePushdown {
                             aggregate,
                             stop_after_n_rows,
                             cache,
+                            column_transformers,
                         },
                     );
                     None
@@ -206,6 +217,48 @@ impl OptimizationRule for AggregatePushdown {
                         aggregate,
                         stop_after_n_rows,
                         cache,
+                        column_transformers,
+                    })
+                }
+            },
+            #[cfg(feature = "ipc")]
+            IpcScan {
+                path,
+                schema,
+                with_columns,
+                predicate,
+                aggregate,
+                stop_after_n_rows,
+                cache,
+                column_transformers,
+            } => match self.state.is_empty() {
+                true => {
+                    lp_arena.replace(
+                        node,
+                        IpcScan {
+                            path,
+                            schema,
+                            with_columns,
+                            predicate,
+                            aggregate,
+                            stop_after_n_rows,
+                            cache,
+                            column_transformers,
+                        },
+                    );
+                    None
+                }
+                false => {
+                    let aggregate = self.drain_nodes().collect();
+                    Some(ALogicalPlan::IpcScan {
+                        path,
+                        schema,
+                        with_columns,
+                        predicate,
+                        aggregate,
+                        stop_after_n_rows,
+                        cache,
+                        column_transformers,
                     })
                 }
             },
@@ -31,7 +31,7 @@ pub(crate) fn agg_projection(
         Selection { input, .. } => {
             agg_projection(*input, columns, lp_arena);
         }
-        Cache { input } => {
+        Cache { input, .. } => {
             agg_projection(*input, columns, lp_arena);
         }
         CsvScan {
@@ -45,6 +45,12 @@ pub(crate) fn agg_projection(
         } => {
             process_with_columns(&path, &with_columns, columns);
         }
+        #[cfg(feature = "ipc")]
+        IpcScan {
+            path, with_columns, ..
+        } => {
+            process_with_columns(&path, &with_columns, columns);
+        }
         DataFrameScan { .. } => (),
         Projection { input, .. } => {
             agg_projection(*input, columns, lp_arena);
@@ -58,6 +64,9 @@ pub(crate) fn agg_projection(
         Explode { input, .. } => {
             agg_projection(*input, columns, lp_arena);
         }
+        Rename { input, .. } => {
+            agg_projection(*input, columns, lp_arena);
+        }
         Distinct { input, .. } => {
             agg_projection(*input, columns, lp_arena);
         }
@@ -72,6 +81,24 @@ pub(crate) fn agg_projection(
             agg_projection(*input_left, columns, lp_arena);
             agg_projection(*input_right, columns, lp_arena);
         }
+        SetOp {
+            input_left,
+            input_right,
+            ..
+        }
+        | JoinWhere {
+            input_left,
+            input_right,
+            ..
+        } => {
+            agg_projection(*input_left, columns, lp_arena);
+            agg_projection(*input_right, columns, lp_arena);
+        }
+        Union { inputs } => {
+            for input in inputs {
+                agg_projection(*input, columns, lp_arena);
+            }
+        }
         HStack { input, .. } => {
             agg_projection(*input, columns, lp_arena);
         }
@@ -81,12 +108,16 @@ pub(crate) fn agg_projection(
         Udf { input, .. } => {
             agg_projection(*input, columns, lp_arena);
         }
+        Sink { input, .. } => {
+            agg_projection(*input, columns, lp_arena);
+        }
     }
 }
 
 /// Aggregate all the columns used in csv scans and make sure that all columns are scanned in one go.
 /// Due to self joins there can be multiple Scans of the same file in a LP. We already cache the scans
 /// in the PhysicalPlan, but we need to make sure that the first scan has all the columns needed.
+/// Scans with column transformers are not cached, so these keep their own projection.
 pub struct AggScanProjection {
     pub columns: HashMap<String, HashSet<String, RandomState>, RandomState>,
 }
@@ -141,6 +172,7 @@ impl OptimizationRule for AggScanProjection {
                     with_columns,
                     stop_after_n_rows,
                     cache,
+                    column_transformers,
                 } = lp
                 {
                     let new_with_columns = self
@@ -148,7 +180,7 @@ impl OptimizationRule for AggScanProjection {
                         .get(&path)
                         .map(|agg| agg.iter().cloned().collect());
                     // prevent infinite loop
-                    if with_columns == new_with_columns {
+                    if with_columns == new_with_columns || !column_transformers.is_empty() {
                         let lp = ALogicalPlan::ParquetScan {
                             path,
                             schema,
@@ -157,6 +189,7 @@ impl OptimizationRule for AggScanProjection {
                             with_columns,
                             stop_after_n_rows,
                             cache,
+                            column_transformers,
                         };
                         lp_arena.replace(node, lp);
                         return None;
@@ -170,6 +203,56 @@ impl OptimizationRule for AggScanProjection {
                         aggregate,
                         stop_after_n_rows,
                         cache,
+                        column_transformers,
+                    };
+                    Some(self.finish_rewrite(lp, expr_arena, lp_arena, &path, with_columns))
+                } else {
+                    unreachable!()
+                }
+            }
+            #[cfg(feature = "ipc")]
+            IpcScan { .. } => {
+                let lp = std::mem::take(lp);
+                if let ALogicalPlan::IpcScan {
+                    path,
+                    schema,
+                    predicate,
+                    aggregate,
+                    with_columns,
+                    stop_after_n_rows,
+                    cache,
+                    column_transformers,
+                } = lp
+                {
+                    let new_with_columns = self
+                        .columns
+                        .get(&path)
+                        .map(|agg| agg.iter().cloned().collect());
+                    // prevent infinite loop
+                    if with_columns == new_with_columns || !column_transformers.is_empty() {
+                        let lp = ALogicalPlan::IpcScan {
+                            path,
+                            schema,
+                            predicate,
+                            aggregate,
+                            with_columns,
+                            stop_after_n_rows,
+                            cache,
+                            column_transformers,
+                        };
+                        lp_arena.replace(node, lp);
+                        return None;
+                    }
+
+                    let lp = IpcScan {
+                        path: path.clone(),
+                        schema,
+                        with_columns: new_with_columns,
+                        predicate,
+                        aggregate,
+                        stop_after_n_rows,
+                        cache,
+                        column_transformers,
                     };
                     Some(self.finish_rewrite(lp, expr_arena, lp_arena, &path, with_columns))
                 } else {
@@ -190,13 +273,14 @@ impl OptimizationRule for AggScanProjection {
                     aggregate,
                     with_columns,
                     cache,
+                    column_transformers,
                 } = lp
                 {
                     let new_with_columns = self
                         .columns
                         .get(&path)
                         .map(|agg| agg.iter().cloned().collect());
-                    if with_columns == new_with_columns {
+                    if with_columns == new_with_columns || !column_transformers.is_empty() {
                         let lp = ALogicalPlan::CsvScan {
                             path,
                             schema,
@@ -209,6 +293,7 @@ impl OptimizationRule for AggScanProjection {
                             aggregate,
                             with_columns,
                             cache,
+                            column_transformers,
                         };
                         lp_arena.replace(node, lp);
                         return None;
@@ -225,6 +310,7 @@ impl OptimizationRule for AggScanProjection {
                         predicate,
                         aggregate,
                         cache,
+                        column_transformers,
                     };
                     Some(self.finish_rewrite(lp, expr_arena, lp_arena, &path, with_columns))
                 } else {
@@ -0,0 +1,159 @@
+//! Common subplan elimination.
+//!
+//! A LazyFrame that is used more than once in a query, e.g. on both sides of a join, shares its
+//! logical plan nodes. Such a shared subplan is wrapped in a `Cache` node, so that it is
+//! executed once and its result is reused by all consumers.
+use crate::logical_plan::next_cache_id;
+use crate::prelude::*;
+use ahash::RandomState;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+type PlanCounts = HashMap<*const LogicalPlan, usize, RandomState>;
+
+/// The inputs of a node. The inputs of a union are owned by the union, so the inputs of those
+/// are returned instead.
+pub(crate) fn inputs(lp: &LogicalPlan) -> Vec<&Arc<LogicalPlan>> {
+    use LogicalPlan::*;
+    match lp {
+        Selection { input, .. }
+        | Cache { input, .. }
+        | LocalProjection { input, .. }
+        | Projection { input, .. }
+        | Aggregate { input, .. }
+        | HStack { input, .. }
+        | Distinct { input, .. }
+        | Sort { input, .. }
+        | Explode { input, .. }
+        | Rename { input, .. }
+        | Slice { input, .. }
+        | Melt { input, .. }
+        | Udf { input, .. }
+        | Sink { input, .. }
+        | Error { input, .. } => vec![input],
+        Join {
+            input_left,
+            input_right,
+            ..
+        }
+        | SetOp {
+            input_left,
+            input_right,
+            ..
+        }
+        | JoinWhere {
+            input_left,
+            input_right,
+            ..
+        } => vec![input_left, input_right],
+        Union { inputs: plans } => plans.iter().flat_map(inputs).collect(),
+        CsvScan { .. } | DataFrameScan { .. } => vec![],
+        #[cfg(feature = "parquet")]
+        ParquetScan { .. } => vec![],
+        #[cfg(feature = "ipc")]
+        IpcScan { .. } => vec![],
+    }
+}
+
+/// Mutable version of [inputs].
+fn inputs_mut(lp: &mut LogicalPlan) -> Vec<&mut Arc<LogicalPlan>> {
+    use LogicalPlan::*;
+    match lp {
+        Selection { input, .. }
+        | Cache { input, .. }
+        | LocalProjection { input, .. }
+        | Projection { input, .. }
+        | Aggregate { input, .. }
+        | HStack { input, .. }
+        | Distinct { input, .. }
+        | Sort { input, .. }
+        | Explode { input, .. }
+        | Rename { input, .. }
+        | Slice { input, .. }
+        | Melt { input, .. }
+        | Udf { input, .. }
+        | Sink { input, .. }
+        | Error { input, .. } => vec![input],
+        Join {
+            input_left,
+            input_right,
+            ..
+        }
+        | SetOp {
+            input_left,
+            input_right,
+            ..
+        }
+        | JoinWhere {
+            input_left,
+            input_right,
+            ..
+        } => vec![input_left, input_right],
+        Union { inputs: plans } => plans.iter_mut().flat_map(inputs_mut).collect(),
+        CsvScan { .. } | DataFrameScan { .. } => vec![],
+        #[cfg(feature = "parquet")]
+        ParquetScan { .. } => vec![],
+        #[cfg(feature = "ipc")]
+        IpcScan { .. } => vec![],
+    }
+}
+
+/// Count how often every node is used as an input. The inputs of a shared node are counted once.
+fn count_inputs(lp: &LogicalPlan, counts: &mut PlanCounts) {
+    for input in inputs(lp) {
+        let count = counts.entry(Arc::as_ptr(input)).or_insert(0);
+        *count += 1;
+        if *count == 1 {
+            count_inputs(input, counts)
+        }
+    }
+}
+
+fn insert_caches(
+    lp: &mut LogicalPlan,
+    counts: &PlanCounts,
+    caches: &mut HashMap<*const LogicalPlan, Arc<LogicalPlan>, RandomState>,
+) {
+    for input in inputs_mut(lp) {
+        let ptr = Arc::as_ptr(input);
+        // a scan of a DataFrame in memory is cheaper than a cache
+        let shared = counts[&ptr] > 1
+            && !matches!(
+                **input,
+                LogicalPlan::Cache { .. } | LogicalPlan::DataFrameScan { .. }
+            );
+        if shared {
+            let cache = match caches.get(&ptr) {
+                Some(cache) => cache.clone(),
+                None => {
+                    let mut subplan = (**input).clone();
+                    insert_caches(&mut subplan, counts, caches);
+                    let cache = Arc::new(LogicalPlan::Cache {
+                        input: Arc::new(subplan),
+                        id: next_cache_id(),
+                    });
+                    caches.insert(ptr, cache.clone());
+                    cache
+                }
+            };
+            *input = cache;
+        } else {
+            insert_caches(Arc::make_mut(input), counts, caches)
+        }
+    }
+}
+
+/// Wrap the subplans that are used more than once in `lp` in a shared `Cache` node.
+pub(crate) fn eliminate_common_subplans(mut lp: LogicalPlan) -> LogicalPlan {
+    let mut counts = HashMap::with_capacity_and_hasher(32, RandomState::default());
+    count_inputs(&lp, &mut counts);
+    if counts.values().all(|count| *count == 1) {
+        return lp;
+    }
+    // keep the original nodes alive, so that their addresses are not reused during the rewrite
+    let original = lp.clone();
+    let mut caches = HashMap::with_capacity_and_hasher(8, RandomState::default());
+    insert_caches(&mut lp, &counts, &mut caches);
+    drop(original);
+    lp
+}
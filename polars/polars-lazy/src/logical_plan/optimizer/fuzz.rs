@@ -0,0 +1,206 @@
+//! Randomized tests that check if optimized and unoptimized plans produce the same result.
+//!
+//! A plan is generated by applying a random sequence of filters, projections, joins,
+//! slices and sorts to a scan. The result of the plan with all optimizations turned off
+//! is the reference the optimized plan is checked against.
+use crate::prelude::*;
+use polars_core::prelude::*;
+use polars_core::rand::{Distribution, RandomGenerator};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const N_PLANS: u64 = 250;
+const MAX_OPS: usize = 8;
+
+fn left_df(gen: &mut RandomGenerator) -> DataFrame {
+    let n = 30;
+    let float = Distribution::Uniform {
+        low: 0.0,
+        high: 10.0,
+    };
+    let mut df = gen
+        .frame(
+            n,
+            &[
+                ("key", Distribution::UniformInt { low: 0, high: 10 }),
+                ("a", float.clone()),
+                ("b", float),
+            ],
+        )
+        .unwrap();
+    for name in &["a", "b"] {
+        df.may_apply(name, |s| gen.with_nulls(s, 0.1)).unwrap();
+    }
+    let idx: Vec<u32> = (0..n as u32).collect();
+    df.insert_at_idx(0, Series::new("idx", &idx)).unwrap();
+    df
+}
+
+fn right_df(gen: &mut RandomGenerator) -> DataFrame {
+    // unique keys, so that a join doesn't create duplicate `idx` values
+    let key: Vec<i64> = (0..8).collect();
+    let val = Distribution::Uniform {
+        low: 0.0,
+        high: 10.0,
+    };
+    DataFrame::new(vec![
+        Series::new("key", &key),
+        gen.series("r_val", 8, &val).unwrap(),
+    ])
+    .unwrap()
+}
+
+/// A plan under construction and the columns that are available at its root.
+struct PlanState {
+    lf: LazyFrame,
+    all: Vec<String>,
+    numeric: Vec<String>,
+    joined: bool,
+    n_added: usize,
+}
+
+fn choose<'a>(rng: &mut StdRng, names: &'a [String]) -> &'a str {
+    &names[rng.gen_range(0..names.len())]
+}
+
+fn apply_random_op(mut state: PlanState, rng: &mut StdRng, right: &DataFrame) -> PlanState {
+    match rng.gen_range(0..6) {
+        // filter
+        0 if !state.numeric.is_empty() => {
+            let c = choose(rng, &state.numeric).to_string();
+            let threshold: f64 = rng.gen_range(0.0..10.0);
+            let predicate = if rng.gen() {
+                col(&c).gt(lit(threshold))
+            } else {
+                col(&c).lt(lit(threshold)).or(col(&c).is_null())
+            };
+            state.lf = state.lf.filter(predicate);
+        }
+        // projection, `idx` is always kept so that the results can be compared
+        1 => {
+            let keep: Vec<String> = state
+                .all
+                .iter()
+                .filter(|name| name.as_str() == "idx" || rng.gen::<bool>())
+                .cloned()
+                .collect();
+            let exprs: Vec<Expr> = keep.iter().map(|name| col(name)).collect();
+            state.lf = state.lf.select(exprs);
+            state.numeric.retain(|name| keep.contains(name));
+            state.all = keep;
+        }
+        // add a column
+        2 if !state.numeric.is_empty() => {
+            let c = choose(rng, &state.numeric).to_string();
+            let name = format!("added_{}", state.n_added);
+            state.n_added += 1;
+            state.lf = state.lf.with_column((col(&c) * lit(2.0)).alias(&name));
+            state.numeric.push(name.clone());
+            state.all.push(name);
+        }
+        // join
+        3 if !state.joined && state.all.iter().any(|name| name == "key") => {
+            let how = if rng.gen() {
+                JoinType::Inner
+            } else {
+                JoinType::Left
+            };
+            state.lf = state
+                .lf
+                .join(
+                    right.clone().lazy(),
+                    vec![col("key")],
+                    vec![col("key")],
+                    None,
+                    how,
+                )
+                // the row order of a join is not defined
+                .sort("idx", false);
+            state.joined = true;
+            state.numeric.push("r_val".to_string());
+            state.all.push("r_val".to_string());
+        }
+        // slice
+        4 => {
+            let offset = rng.gen_range(0..5);
+            let len = rng.gen_range(1..20);
+            state.lf = state.lf.slice(offset, len);
+        }
+        // sort
+        5 => {
+            state.lf = state.lf.sort("idx", rng.gen());
+        }
+        _ => {}
+    }
+    state
+}
+
+/// Turn off every toggle of [OptState]. The struct is listed in full, so that a new toggle can't
+/// be forgotten here.
+fn no_optimizations(lf: LazyFrame) -> LazyFrame {
+    let opt_state = OptState {
+        projection_pushdown: false,
+        predicate_pushdown: false,
+        type_coercion: false,
+        simplify_expr: false,
+        agg_scan_projection: false,
+        aggregate_pushdown: false,
+        slice_pushdown: false,
+        common_subplan_elimination: false,
+        join_reorder: false,
+        agg_fusion: false,
+        // not an optimization, only decides if categoricals are built with the string cache
+        global_string_cache: lf.get_opt_state().global_string_cache,
+        custom_rules: vec![],
+    };
+    LazyFrame::from_logical_plan(lf.logical_plan, opt_state)
+}
+
+#[test]
+fn test_fuzz_optimized_equals_unoptimized() {
+    for seed in 0..N_PLANS {
+        // the data and the operations of a plan are drawn with the same seed
+        let mut gen = RandomGenerator::new(Some(seed));
+        let left = left_df(&mut gen);
+        let right = right_df(&mut gen);
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let mut state = PlanState {
+            lf: left.lazy(),
+            all: vec!["idx".into(), "key".into(), "a".into(), "b".into()],
+            numeric: vec!["a".into(), "b".into()],
+            joined: false,
+            n_added: 0,
+        };
+        let n_ops = rng.gen_range(1..=MAX_OPS);
+        for _ in 0..n_ops {
+            state = apply_random_op(state, &mut rng, &right);
+        }
+
+        let plan = state.lf.describe_plan();
+        let expected = no_optimizations(state.lf.clone())
+            .collect()
+            .unwrap_or_else(|e| panic!("seed {}: {:?}\n{}", seed, e, plan));
+        let out = state
+            .lf
+            .collect()
+            .unwrap_or_else(|e| panic!("seed {}: {:?}\n{}", seed, e, plan));
+
+        assert_eq!(
+            expected.get_column_names(),
+            out.get_column_names(),
+            "seed {}\n{}",
+            seed,
+            plan
+        );
+        let expected = expected.sort("idx", false).unwrap();
+        let out = out.sort("idx", false).unwrap();
+        assert!(
+            out.frame_equal_missing(&expected),
+            "seed {}\n{}\nexpected:\n{:?}\ngot:\n{:?}",
+            seed,
+            plan,
+            expected,
+            out
+        );
+    }
+}
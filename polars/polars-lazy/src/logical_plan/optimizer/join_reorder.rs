@@ -0,0 +1,236 @@
+use crate::logical_plan::optimizer::OptimizationRule;
+use crate::prelude::*;
+use ahash::RandomState;
+use polars_core::prelude::*;
+use std::collections::HashSet;
+
+/// Reorder a chain of inner joins `((base ⋈ r0) ⋈ r1) ⋈ r2` so that the relations with the
+/// fewest estimated rows are joined first. The intermediate results stay small and the hash
+/// tables are built on the small relations.
+///
+/// Every relation stays the right input of its own join, so the key columns that are dropped
+/// don't change. A relation can only be joined once the columns its left keys refer to are
+/// joined. A projection on top of the new chain restores the original column order.
+///
+/// Chains are only reordered if no column names clash between the relations, so that no
+/// suffixes are applied, and if none of the joins maintains the order or validates the
+/// uniqueness of the left keys.
+pub(crate) struct JoinReorder {}
+
+/// An inner join of the chain, with its left input left out.
+struct ChainedJoin {
+    input_right: Node,
+    left_on: Vec<Node>,
+    right_on: Vec<Node>,
+    left_names: Vec<Arc<String>>,
+    right_names: Vec<Arc<String>>,
+    options: JoinOptions,
+}
+
+/// The names of the join keys if they all are plain columns.
+fn key_names(keys: &[Node], expr_arena: &Arena<AExpr>) -> Option<Vec<Arc<String>>> {
+    keys.iter()
+        .map(|node| match expr_arena.get(*node) {
+            AExpr::Column(name) => Some(name.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// An upper bound of the number of rows `node` produces, if it is known without executing it.
+fn estimate_n_rows(node: Node, lp_arena: &Arena<ALogicalPlan>) -> Option<usize> {
+    use ALogicalPlan::*;
+    match lp_arena.get(node) {
+        DataFrameScan { df, .. } => Some(df.height()),
+        CsvScan {
+            stop_after_n_rows, ..
+        } => *stop_after_n_rows,
+        #[cfg(feature = "parquet")]
+        ParquetScan {
+            stop_after_n_rows, ..
+        } => *stop_after_n_rows,
+        #[cfg(feature = "ipc")]
+        IpcScan {
+            stop_after_n_rows, ..
+        } => *stop_after_n_rows,
+        Slice { input, len, .. } => {
+            Some(estimate_n_rows(*input, lp_arena).map_or(*len, |n| std::cmp::min(n, *len)))
+        }
+        Selection { input, .. }
+        | Projection { input, .. }
+        | LocalProjection { input, .. }
+        | HStack { input, .. }
+        | Sort { input, .. }
+        | Rename { input, .. }
+        | Distinct { input, .. }
+        | Cache { input, .. } => estimate_n_rows(*input, lp_arena),
+        _ => None,
+    }
+}
+
+impl JoinReorder {
+    /// Collect the chain of inner joins that starts at `node`. Returns the input at the bottom
+    /// of the chain and the joins from the bottom to the top.
+    fn collect_chain(
+        &self,
+        mut node: Node,
+        lp_arena: &Arena<ALogicalPlan>,
+        expr_arena: &Arena<AExpr>,
+    ) -> (Node, Vec<ChainedJoin>) {
+        let mut chain = vec![];
+        while let ALogicalPlan::Join {
+            input_left,
+            input_right,
+            how: JoinType::Inner,
+            left_on,
+            right_on,
+            options,
+            ..
+        } = lp_arena.get(node)
+        {
+            if options.indicator || options.maintain_order {
+                break;
+            }
+            // the uniqueness of the left keys depends on the relations joined before
+            if !matches!(
+                options.validate,
+                JoinValidation::ManyToMany | JoinValidation::ManyToOne
+            ) {
+                break;
+            }
+            let (left_names, right_names) = match (
+                key_names(left_on, expr_arena),
+                key_names(right_on, expr_arena),
+            ) {
+                (Some(left_names), Some(right_names)) => (left_names, right_names),
+                _ => break,
+            };
+            chain.push(ChainedJoin {
+                input_right: *input_right,
+                left_on: left_on.clone(),
+                right_on: right_on.clone(),
+                left_names,
+                right_names,
+                options: options.clone(),
+            });
+            node = *input_left;
+        }
+        chain.reverse();
+        (node, chain)
+    }
+}
+
+impl OptimizationRule for JoinReorder {
+    fn optimize_plan(
+        &mut self,
+        lp_arena: &mut Arena<ALogicalPlan>,
+        expr_arena: &mut Arena<AExpr>,
+        node: Node,
+    ) -> Option<ALogicalPlan> {
+        let (base, chain) = self.collect_chain(node, lp_arena, expr_arena);
+        if chain.len() < 2 {
+            return None;
+        }
+
+        // the columns of the joined relations, a clash would lead to a suffix
+        let mut columns: HashSet<String, RandomState> = lp_arena
+            .get(base)
+            .schema(lp_arena)
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect();
+        let mut right_columns = Vec::with_capacity(chain.len());
+        for join in &chain {
+            let cols = lp_arena
+                .get(join.input_right)
+                .schema(lp_arena)
+                .fields()
+                .iter()
+                .map(|f| f.name().clone())
+                .filter(|name| !join.right_names.iter().any(|key| key.as_str() == name))
+                .collect::<Vec<_>>();
+            for name in &cols {
+                if !columns.insert(name.clone()) {
+                    return None;
+                }
+            }
+            right_columns.push(cols);
+        }
+
+        // greedily join the smallest relation of which the left keys are available
+        let n_rows = chain
+            .iter()
+            .map(|join| estimate_n_rows(join.input_right, lp_arena).unwrap_or(usize::MAX))
+            .collect::<Vec<_>>();
+        let mut available: HashSet<String, RandomState> = lp_arena
+            .get(base)
+            .schema(lp_arena)
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect();
+        let mut pending = (0..chain.len()).collect::<Vec<_>>();
+        let mut order = Vec::with_capacity(chain.len());
+        while !pending.is_empty() {
+            let (pos, idx) = pending
+                .iter()
+                .enumerate()
+                .filter(|(_, idx)| {
+                    chain[**idx]
+                        .left_names
+                        .iter()
+                        .all(|name| available.contains(name.as_str()))
+                })
+                .min_by_key(|(_, idx)| n_rows[**idx])?;
+            let idx = *idx;
+            available.extend(right_columns[idx].iter().cloned());
+            pending.remove(pos);
+            order.push(idx);
+        }
+        if order.iter().enumerate().all(|(i, idx)| i == *idx) {
+            return None;
+        }
+
+        let mut input_left = base;
+        for idx in order {
+            let join = &chain[idx];
+            let schema_left = lp_arena.get(input_left).schema(lp_arena);
+            let schema_right = lp_arena.get(join.input_right).schema(lp_arena);
+            let fields = schema_left
+                .fields()
+                .iter()
+                .chain(
+                    schema_right
+                        .fields()
+                        .iter()
+                        .filter(|f| right_columns[idx].contains(f.name())),
+                )
+                .cloned()
+                .collect();
+            let lp = ALogicalPlan::Join {
+                input_left,
+                input_right: join.input_right,
+                schema: Arc::new(Schema::new(fields)),
+                how: JoinType::Inner,
+                left_on: join.left_on.clone(),
+                right_on: join.right_on.clone(),
+                options: join.options.clone(),
+            };
+            input_left = lp_arena.add(lp);
+        }
+
+        // restore the column order of the original chain
+        let schema = lp_arena.get(node).schema(lp_arena).clone();
+        let expr = schema
+            .fields()
+            .iter()
+            .map(|f| expr_arena.add(AExpr::Column(Arc::new(f.name().clone()))))
+            .collect();
+        Some(ALogicalPlan::Projection {
+            expr,
+            input: input_left,
+            schema: Arc::new(schema),
+        })
+    }
+}
@@ -2,20 +2,26 @@ use std::collections::{HashMap, HashSet};
 
 use ahash::RandomState;
 
-use polars_core::frame::group_by::{fmt_groupby_column, GroupByMethod};
+use polars_core::frame::group_by::{fmt_groupby_column, groupby_output_dtype, GroupByMethod};
 use polars_core::frame::hash_join::JoinType;
 use polars_core::prelude::*;
 use polars_core::utils::{get_supertype, Arena, Node};
+use polars_io::transform::ColumnTransformers;
 
-use crate::logical_plan::{det_melt_schema, Context};
+use crate::logical_plan::{det_melt_schema, det_rename_schema, Context};
 use crate::prelude::*;
 use crate::utils::{aexprs_to_schema, rename_field};
 
 pub(crate) mod aggregate_pushdown;
 pub(crate) mod aggregate_scan_projections;
+pub(crate) mod common_subplan;
+#[cfg(test)]
+mod fuzz;
+pub(crate) mod join_reorder;
 pub(crate) mod predicate_pushdown;
 pub(crate) mod projection_pushdown;
 pub(crate) mod simplify_expr;
+pub(crate) mod slice_pushdown;
 pub(crate) mod type_coercion;
 
 pub trait Optimize {
@@ -84,13 +90,17 @@ impl StackOptimizer {
                         plans.push(*input);
                         exprs.extend(expr.iter().map(|e| (*e, *input)));
                     }
-                    ALogicalPlan::Sort { input, .. } => {
+                    ALogicalPlan::Sort { input, by, .. } => {
                         plans.push(*input);
+                        exprs.extend(by.iter().map(|e| (*e, *input)));
                     }
                     ALogicalPlan::Explode { input, .. } => {
                         plans.push(*input);
                     }
-                    ALogicalPlan::Cache { input } => {
+                    ALogicalPlan::Rename { input, .. } => {
+                        plans.push(*input);
+                    }
+                    ALogicalPlan::Cache { input, .. } => {
                         plans.push(*input);
                     }
                     ALogicalPlan::Aggregate {
@@ -108,6 +118,28 @@ impl StackOptimizer {
                         plans.push(*input_left);
                         plans.push(*input_right);
                     }
+                    ALogicalPlan::SetOp {
+                        input_left,
+                        input_right,
+                        ..
+                    } => {
+                        plans.push(*input_left);
+                        plans.push(*input_right);
+                    }
+                    ALogicalPlan::Union { inputs } => {
+                        plans.extend_from_slice(inputs);
+                    }
+                    ALogicalPlan::JoinWhere {
+                        input_left,
+                        input_right,
+                        predicates,
+                        ..
+                    } => {
+                        plans.push(*input_left);
+                        plans.push(*input_right);
+                        // the predicates are evaluated on the joined schema
+                        exprs.extend(predicates.iter().map(|e| (*e, current_node)));
+                    }
                     ALogicalPlan::HStack {
                         input, exprs: e2, ..
                     } => {
@@ -131,8 +163,15 @@ impl StackOptimizer {
                             exprs.push((predicate, current_node))
                         }
                     }
+                    #[cfg(feature = "ipc")]
+                    ALogicalPlan::IpcScan { predicate, .. } => {
+                        if let Some(predicate) = *predicate {
+                            exprs.push((predicate, current_node))
+                        }
+                    }
                     ALogicalPlan::Melt { input, .. } => plans.push(*input),
                     ALogicalPlan::Udf { input, .. } => plans.push(*input),
+                    ALogicalPlan::Sink { input, .. } => plans.push(*input),
                 }
 
                 // process the expressions on the stack and apply optimizations.
@@ -170,11 +209,13 @@ pub enum AAggExpr {
     Max(Node),
     Median(Node),
     NUnique(Node),
+    NullCount(Node),
     First(Node),
     Last(Node),
     Mean(Node),
     List(Node),
     Quantile { expr: Node, quantile: f64 },
+    TopK { expr: Node, k: usize },
     Sum(Node),
     Count(Node),
     Std(Node),
@@ -188,6 +229,9 @@ pub enum AExpr {
     Unique(Node),
     Duplicated(Node),
     Reverse(Node),
+    CumCount(Node),
+    Standardize(Node),
+    MinMaxScale(Node),
     Explode(Node),
     Alias(Node, Arc<String>),
     Column(Arc<String>),
@@ -223,6 +267,11 @@ pub enum AExpr {
         input: Node,
         periods: i64,
     },
+    ClipQuantile {
+        input: Node,
+        low_q: f64,
+        high_q: f64,
+    },
     Window {
         function: Node,
         partition_by: Node,
@@ -250,22 +299,25 @@ impl Default for AExpr {
     }
 }
 
+/// The output field of an aggregation. The data type follows the kernels of `polars-core`,
+/// aggregations that are not supported for the input data type return an error.
 pub(crate) fn field_by_context(
-    mut field: Field,
+    field: Field,
     ctxt: Context,
     groupby_method: GroupByMethod,
-) -> Field {
-    if &DataType::Boolean == field.data_type() {
-        field = Field::new(field.name(), DataType::UInt32)
-    }
+) -> Result<Field> {
+    let field = Field::new(
+        field.name(),
+        groupby_output_dtype(field.data_type(), groupby_method)?,
+    );
 
-    match ctxt {
+    Ok(match ctxt {
         Context::Other => field,
         Context::Aggregation => {
             let new_name = fmt_groupby_column(field.name(), groupby_method);
             rename_field(&field, &new_name)
         }
-    }
+    })
 }
 
 impl AExpr {
@@ -299,10 +351,18 @@ impl AExpr {
                 Ok(Field::new(field.name(), DataType::Boolean))
             }
             Reverse(expr) => arena.get(*expr).to_field(&schema, ctxt, arena),
+            CumCount(expr) => {
+                let field = arena.get(*expr).to_field(&schema, ctxt, arena)?;
+                Ok(Field::new(field.name(), IdxType::get_dtype()))
+            }
+            Standardize(expr) | MinMaxScale(expr) => {
+                let field = arena.get(*expr).to_field(&schema, ctxt, arena)?;
+                Ok(Field::new(field.name(), DataType::Float64))
+            }
             Explode(expr) => arena.get(*expr).to_field(&schema, ctxt, arena),
-            Alias(expr, name) => Ok(Field::new(
+            Alias(expr, name) => Ok(rename_field(
+                &arena.get(*expr).to_field(schema, ctxt, arena)?,
                 name,
-                arena.get(*expr).get_type(schema, ctxt, arena)?,
             )),
             Column(name) => {
                 let field = schema.field_with_name(name).map(|f| f.clone())?;
@@ -352,86 +412,86 @@ impl AExpr {
                         arena.get(*expr).to_field(schema, ctxt, arena)?,
                         ctxt,
                         GroupByMethod::Min,
-                    ),
+                    )?,
                     Max(expr) => field_by_context(
                         arena.get(*expr).to_field(schema, ctxt, arena)?,
                         ctxt,
                         GroupByMethod::Max,
-                    ),
+                    )?,
                     Median(expr) => field_by_context(
                         arena.get(*expr).to_field(schema, ctxt, arena)?,
                         ctxt,
                         GroupByMethod::Median,
-                    ),
+                    )?,
                     Mean(expr) => field_by_context(
                         arena.get(*expr).to_field(schema, ctxt, arena)?,
                         ctxt,
                         GroupByMethod::Mean,
-                    ),
+                    )?,
                     First(expr) => field_by_context(
                         arena.get(*expr).to_field(schema, ctxt, arena)?,
                         ctxt,
                         GroupByMethod::First,
-                    ),
+                    )?,
                     Last(expr) => field_by_context(
                         arena.get(*expr).to_field(schema, ctxt, arena)?,
                         ctxt,
                         GroupByMethod::Last,
-                    ),
+                    )?,
                     List(expr) => field_by_context(
                         arena.get(*expr).to_field(schema, ctxt, arena)?,
                         ctxt,
                         GroupByMethod::List,
-                    ),
-                    Std(expr) => {
-                        let field = arena.get(*expr).to_field(schema, ctxt, arena)?;
-                        let field = Field::new(field.name(), DataType::Float64);
-                        field_by_context(field, ctxt, GroupByMethod::Std)
-                    }
-                    Var(expr) => {
-                        let field = arena.get(*expr).to_field(schema, ctxt, arena)?;
-                        let field = Field::new(field.name(), DataType::Float64);
-                        field_by_context(field, ctxt, GroupByMethod::Var)
-                    }
-                    NUnique(expr) => {
-                        let field = arena.get(*expr).to_field(schema, ctxt, arena)?;
-                        let field = Field::new(field.name(), DataType::UInt32);
-                        match ctxt {
-                            Context::Other => field,
-                            Context::Aggregation => {
-                                let new_name =
-                                    fmt_groupby_column(field.name(), GroupByMethod::NUnique);
-                                rename_field(&field, &new_name)
-                            }
-                        }
-                    }
+                    )?,
+                    Std(expr) => field_by_context(
+                        arena.get(*expr).to_field(schema, ctxt, arena)?,
+                        ctxt,
+                        GroupByMethod::Std,
+                    )?,
+                    Var(expr) => field_by_context(
+                        arena.get(*expr).to_field(schema, ctxt, arena)?,
+                        ctxt,
+                        GroupByMethod::Var,
+                    )?,
+                    NUnique(expr) => field_by_context(
+                        arena.get(*expr).to_field(schema, ctxt, arena)?,
+                        ctxt,
+                        GroupByMethod::NUnique,
+                    )?,
+                    NullCount(expr) => field_by_context(
+                        arena.get(*expr).to_field(schema, ctxt, arena)?,
+                        ctxt,
+                        GroupByMethod::NullCount,
+                    )?,
                     Sum(expr) => field_by_context(
                         arena.get(*expr).to_field(schema, ctxt, arena)?,
                         ctxt,
                         GroupByMethod::Sum,
-                    ),
-                    Count(expr) => {
-                        let field = arena.get(*expr).to_field(schema, ctxt, arena)?;
-                        let field = Field::new(field.name(), DataType::UInt32);
-                        match ctxt {
-                            Context::Other => field,
-                            Context::Aggregation => {
-                                let new_name =
-                                    fmt_groupby_column(field.name(), GroupByMethod::Count);
-                                rename_field(&field, &new_name)
-                            }
-                        }
-                    }
+                    )?,
+                    Count(expr) => field_by_context(
+                        arena.get(*expr).to_field(schema, ctxt, arena)?,
+                        ctxt,
+                        GroupByMethod::Count,
+                    )?,
                     AggGroups(expr) => {
                         let field = arena.get(*expr).to_field(schema, ctxt, arena)?;
                         let new_name = fmt_groupby_column(field.name(), GroupByMethod::Groups);
-                        Field::new(&new_name, DataType::List(ArrowDataType::UInt32))
+                        Field::new(&new_name, DataType::List(IdxType::get_dtype().to_arrow()))
                     }
                     Quantile { expr, quantile } => field_by_context(
                         arena.get(*expr).to_field(schema, ctxt, arena)?,
                         ctxt,
                         GroupByMethod::Quantile(*quantile),
-                    ),
+                    )?,
+                    TopK { expr, k } => {
+                        let field = arena.get(*expr).to_field(schema, ctxt, arena)?;
+                        match ctxt {
+                            Context::Other => field,
+                            Context::Aggregation => {
+                                field_by_context(field, ctxt, GroupByMethod::TopK(*k))?
+                            }
+                        }
+                    }
                 };
                 Ok(field)
             }
@@ -461,9 +521,14 @@ impl AExpr {
                 Ok(out.unwrap())
             }
             Shift { input, .. } => arena.get(*input).to_field(schema, ctxt, arena),
+            ClipQuantile { input, .. } => arena.get(*input).to_field(schema, ctxt, arena),
             Slice { input, .. } => arena.get(*input).to_field(schema, ctxt, arena),
-            Wildcard => panic!("should be no wildcard at this point"),
-            Except(_) => panic!("should be no except at this point"),
+            Wildcard => Err(PolarsError::InvalidOperation(
+                "a wildcard can only be used in a projection or an aggregation".into(),
+            )),
+            Except(_) => Err(PolarsError::InvalidOperation(
+                "an exception can only be used in a projection or an aggregation".into(),
+            )),
         }
     }
 }
@@ -475,6 +540,7 @@ pub enum ALogicalPlan {
         input: Node,
         id_vars: Arc<Vec<String>>,
         value_vars: Arc<Vec<String>>,
+        maintain_order: bool,
         schema: SchemaRef,
     },
     Slice {
@@ -498,6 +564,7 @@ pub enum ALogicalPlan {
         predicate: Option<Node>,
         aggregate: Vec<Node>,
         cache: bool,
+        column_transformers: ColumnTransformers,
     },
     #[cfg(feature = "parquet")]
     ParquetScan {
@@ -508,6 +575,18 @@ pub enum ALogicalPlan {
         aggregate: Vec<Node>,
         stop_after_n_rows: Option<usize>,
         cache: bool,
+        column_transformers: ColumnTransformers,
+    },
+    #[cfg(feature = "ipc")]
+    IpcScan {
+        path: String,
+        schema: SchemaRef,
+        with_columns: Option<Vec<String>>,
+        predicate: Option<Node>,
+        aggregate: Vec<Node>,
+        stop_after_n_rows: Option<usize>,
+        cache: bool,
+        column_transformers: ColumnTransformers,
     },
     DataFrameScan {
         df: Arc<DataFrame>,
@@ -527,15 +606,22 @@ pub enum ALogicalPlan {
     },
     Sort {
         input: Node,
-        by_column: String,
-        reverse: bool,
+        by: Vec<Node>,
+        reverse: Vec<bool>,
     },
     Explode {
         input: Node,
         columns: Vec<String>,
     },
+    Rename {
+        input: Node,
+        existing: Arc<Vec<String>>,
+        new: Arc<Vec<String>>,
+        schema: SchemaRef,
+    },
     Cache {
         input: Node,
+        id: usize,
     },
     Aggregate {
         input: Node,
@@ -551,8 +637,22 @@ pub enum ALogicalPlan {
         how: JoinType,
         left_on: Vec<Node>,
         right_on: Vec<Node>,
-        allow_par: bool,
-        force_par: bool,
+        options: JoinOptions,
+    },
+    SetOp {
+        input_left: Node,
+        input_right: Node,
+        op: SetOperation,
+    },
+    JoinWhere {
+        input_left: Node,
+        input_right: Node,
+        schema: SchemaRef,
+        predicates: Vec<Node>,
+        suffix: Option<String>,
+    },
+    Union {
+        inputs: Vec<Node>,
     },
     HStack {
         input: Node,
@@ -572,6 +672,15 @@ pub enum ALogicalPlan {
         ///  allow projection pushdown optimizations
         projection_pd: bool,
         schema: Option<SchemaRef>,
+        /// The columns of the input that the function reads. Only these columns are projected
+        /// below the function, if set.
+        input_columns: Option<Arc<Vec<String>>>,
+    },
+    Sink {
+        input: Node,
+        path: String,
+        file_type: SinkFileType,
+        column_transformers: ColumnTransformers,
     },
 }
 
@@ -590,11 +699,14 @@ impl ALogicalPlan {
     pub(crate) fn schema<'a>(&'a self, arena: &'a Arena<ALogicalPlan>) -> &'a Schema {
         use ALogicalPlan::*;
         match self {
-            Cache { input } => arena.get(*input).schema(arena),
+            Cache { input, .. } => arena.get(*input).schema(arena),
             Sort { input, .. } => arena.get(*input).schema(arena),
             Explode { input, .. } => arena.get(*input).schema(arena),
+            Rename { schema, .. } => schema,
             #[cfg(feature = "parquet")]
             ParquetScan { schema, .. } => schema,
+            #[cfg(feature = "ipc")]
+            IpcScan { schema, .. } => schema,
             DataFrameScan { schema, .. } => schema,
             Selection { input, .. } => arena.get(*input).schema(arena),
             CsvScan { schema, .. } => schema,
@@ -602,6 +714,9 @@ impl ALogicalPlan {
             LocalProjection { schema, .. } => schema,
             Aggregate { schema, .. } => schema,
             Join { schema, .. } => schema,
+            SetOp { input_left, .. } => arena.get(*input_left).schema(arena),
+            JoinWhere { schema, .. } => schema,
+            Union { inputs } => arena.get(inputs[0]).schema(arena),
             HStack { schema, .. } => schema,
             Distinct { input, .. } => arena.get(*input).schema(arena),
             Slice { input, .. } => arena.get(*input).schema(arena),
@@ -610,6 +725,7 @@ impl ALogicalPlan {
                 Some(schema) => schema,
                 None => arena.get(*input).schema(arena),
             },
+            Sink { input, .. } => arena.get(*input).schema(arena),
         }
     }
 }
@@ -620,6 +736,9 @@ pub(crate) fn to_aexpr(expr: Expr, arena: &mut Arena<AExpr>) -> Node {
         Expr::Unique(expr) => AExpr::Unique(to_aexpr(*expr, arena)),
         Expr::Duplicated(expr) => AExpr::Duplicated(to_aexpr(*expr, arena)),
         Expr::Reverse(expr) => AExpr::Reverse(to_aexpr(*expr, arena)),
+        Expr::CumCount(expr) => AExpr::CumCount(to_aexpr(*expr, arena)),
+        Expr::Standardize(expr) => AExpr::Standardize(to_aexpr(*expr, arena)),
+        Expr::MinMaxScale(expr) => AExpr::MinMaxScale(to_aexpr(*expr, arena)),
         Expr::Explode(expr) => AExpr::Explode(to_aexpr(*expr, arena)),
         Expr::Alias(e, name) => AExpr::Alias(to_aexpr(*e, arena), name),
         Expr::Literal(value) => AExpr::Literal(value),
@@ -651,6 +770,7 @@ pub(crate) fn to_aexpr(expr: Expr, arena: &mut Arena<AExpr>) -> Node {
                 AggExpr::Max(expr) => AAggExpr::Max(to_aexpr(*expr, arena)),
                 AggExpr::Median(expr) => AAggExpr::Median(to_aexpr(*expr, arena)),
                 AggExpr::NUnique(expr) => AAggExpr::NUnique(to_aexpr(*expr, arena)),
+                AggExpr::NullCount(expr) => AAggExpr::NullCount(to_aexpr(*expr, arena)),
                 AggExpr::First(expr) => AAggExpr::First(to_aexpr(*expr, arena)),
                 AggExpr::Last(expr) => AAggExpr::Last(to_aexpr(*expr, arena)),
                 AggExpr::Mean(expr) => AAggExpr::Mean(to_aexpr(*expr, arena)),
@@ -660,6 +780,10 @@ pub(crate) fn to_aexpr(expr: Expr, arena: &mut Arena<AExpr>) -> Node {
                     expr: to_aexpr(*expr, arena),
                     quantile,
                 },
+                AggExpr::TopK { expr, k } => AAggExpr::TopK {
+                    expr: to_aexpr(*expr, arena),
+                    k,
+                },
                 AggExpr::Sum(expr) => AAggExpr::Sum(to_aexpr(*expr, arena)),
                 AggExpr::Std(expr) => AAggExpr::Std(to_aexpr(*expr, arena)),
                 AggExpr::Var(expr) => AAggExpr::Var(to_aexpr(*expr, arena)),
@@ -705,6 +829,15 @@ pub(crate) fn to_aexpr(expr: Expr, arena: &mut Arena<AExpr>) -> Node {
             input: to_aexpr(*input, arena),
             periods,
         },
+        Expr::ClipQuantile {
+            input,
+            low_q,
+            high_q,
+        } => AExpr::ClipQuantile {
+            input: to_aexpr(*input, arena),
+            low_q,
+            high_q,
+        },
         Expr::Window {
             function,
             partition_by,
@@ -729,6 +862,12 @@ pub(crate) fn to_aexpr(expr: Expr, arena: &mut Arena<AExpr>) -> Node {
     arena.add(v)
 }
 
+/// Take the plan out of its `Arc`. The plan is only cloned if it is shared with another plan,
+/// e.g. a LazyFrame that was cloned before it was collected.
+fn unwrap_plan(lp: Arc<LogicalPlan>) -> LogicalPlan {
+    Arc::try_unwrap(lp).unwrap_or_else(|lp| (*lp).clone())
+}
+
 pub(crate) fn to_alp(
     lp: LogicalPlan,
     expr_arena: &mut Arena<AExpr>,
@@ -736,7 +875,7 @@ pub(crate) fn to_alp(
 ) -> Node {
     let v = match lp {
         LogicalPlan::Selection { input, predicate } => {
-            let i = to_alp(*input, expr_arena, lp_arena);
+            let i = to_alp(unwrap_plan(input), expr_arena, lp_arena);
             let p = to_aexpr(predicate, expr_arena);
             ALogicalPlan::Selection {
                 input: i,
@@ -744,20 +883,22 @@ pub(crate) fn to_alp(
             }
         }
         LogicalPlan::Slice { input, offset, len } => {
-            let input = to_alp(*input, expr_arena, lp_arena);
+            let input = to_alp(unwrap_plan(input), expr_arena, lp_arena);
             ALogicalPlan::Slice { input, offset, len }
         }
         LogicalPlan::Melt {
             input,
             id_vars,
             value_vars,
+            maintain_order,
             schema,
         } => {
-            let input = to_alp(*input, expr_arena, lp_arena);
+            let input = to_alp(unwrap_plan(input), expr_arena, lp_arena);
             ALogicalPlan::Melt {
                 input,
                 id_vars,
                 value_vars,
+                maintain_order,
                 schema,
             }
         }
@@ -773,6 +914,7 @@ pub(crate) fn to_alp(
             predicate,
             aggregate,
             cache,
+            column_transformers,
         } => ALogicalPlan::CsvScan {
             path,
             schema,
@@ -788,6 +930,7 @@ pub(crate) fn to_alp(
                 .map(|expr| to_aexpr(expr, expr_arena))
                 .collect(),
             cache,
+            column_transformers,
         },
         #[cfg(feature = "parquet")]
         LogicalPlan::ParquetScan {
@@ -798,6 +941,7 @@ pub(crate) fn to_alp(
             aggregate,
             stop_after_n_rows,
             cache,
+            column_transformers,
         } => ALogicalPlan::ParquetScan {
             path,
             schema,
@@ -809,6 +953,30 @@ pub(crate) fn to_alp(
                 .collect(),
             stop_after_n_rows,
             cache,
+            column_transformers,
+        },
+        #[cfg(feature = "ipc")]
+        LogicalPlan::IpcScan {
+            path,
+            schema,
+            with_columns,
+            predicate,
+            aggregate,
+            stop_after_n_rows,
+            cache,
+            column_transformers,
+        } => ALogicalPlan::IpcScan {
+            path,
+            schema,
+            with_columns,
+            predicate: predicate.map(|expr| to_aexpr(expr, expr_arena)),
+            aggregate: aggregate
+                .into_iter()
+                .map(|expr| to_aexpr(expr, expr_arena))
+                .collect(),
+            stop_after_n_rows,
+            cache,
+            column_transformers,
         },
         LogicalPlan::DataFrameScan {
             df,
@@ -828,7 +996,7 @@ pub(crate) fn to_alp(
             schema,
         } => {
             let exp = expr.into_iter().map(|x| to_aexpr(x, expr_arena)).collect();
-            let i = to_alp(*input, expr_arena, lp_arena);
+            let i = to_alp(unwrap_plan(input), expr_arena, lp_arena);
             ALogicalPlan::Projection {
                 expr: exp,
                 input: i,
@@ -841,32 +1009,39 @@ pub(crate) fn to_alp(
             schema,
         } => {
             let exp = expr.into_iter().map(|x| to_aexpr(x, expr_arena)).collect();
-            let i = to_alp(*input, expr_arena, lp_arena);
+            let i = to_alp(unwrap_plan(input), expr_arena, lp_arena);
             ALogicalPlan::LocalProjection {
                 expr: exp,
                 input: i,
                 schema,
             }
         }
-        LogicalPlan::Sort {
+        LogicalPlan::Sort { input, by, reverse } => {
+            let input = to_alp(unwrap_plan(input), expr_arena, lp_arena);
+            let by = by.into_iter().map(|x| to_aexpr(x, expr_arena)).collect();
+            ALogicalPlan::Sort { input, by, reverse }
+        }
+        LogicalPlan::Explode { input, columns } => {
+            let input = to_alp(unwrap_plan(input), expr_arena, lp_arena);
+            ALogicalPlan::Explode { input, columns }
+        }
+        LogicalPlan::Rename {
             input,
-            by_column,
-            reverse,
+            existing,
+            new,
+            schema,
         } => {
-            let input = to_alp(*input, expr_arena, lp_arena);
-            ALogicalPlan::Sort {
+            let input = to_alp(unwrap_plan(input), expr_arena, lp_arena);
+            ALogicalPlan::Rename {
                 input,
-                by_column,
-                reverse,
+                existing,
+                new,
+                schema,
             }
         }
-        LogicalPlan::Explode { input, columns } => {
-            let input = to_alp(*input, expr_arena, lp_arena);
-            ALogicalPlan::Explode { input, columns }
-        }
-        LogicalPlan::Cache { input } => {
-            let input = to_alp(*input, expr_arena, lp_arena);
-            ALogicalPlan::Cache { input }
+        LogicalPlan::Cache { input, id } => {
+            let input = to_alp(unwrap_plan(input), expr_arena, lp_arena);
+            ALogicalPlan::Cache { input, id }
         }
         LogicalPlan::Aggregate {
             input,
@@ -875,7 +1050,7 @@ pub(crate) fn to_alp(
             schema,
             apply,
         } => {
-            let i = to_alp(*input, expr_arena, lp_arena);
+            let i = to_alp(unwrap_plan(input), expr_arena, lp_arena);
             let aggs_new = aggs.into_iter().map(|x| to_aexpr(x, expr_arena)).collect();
             let keys_new = keys
                 .iter()
@@ -897,11 +1072,10 @@ pub(crate) fn to_alp(
             how,
             left_on,
             right_on,
-            allow_par,
-            force_par,
+            options,
         } => {
-            let i_l = to_alp(*input_left, expr_arena, lp_arena);
-            let i_r = to_alp(*input_right, expr_arena, lp_arena);
+            let i_l = to_alp(unwrap_plan(input_left), expr_arena, lp_arena);
+            let i_r = to_alp(unwrap_plan(input_right), expr_arena, lp_arena);
 
             let l_on = left_on
                 .into_iter()
@@ -919,17 +1093,57 @@ pub(crate) fn to_alp(
                 left_on: l_on,
                 how,
                 right_on: r_on,
-                allow_par,
-                force_par,
+                options,
+            }
+        }
+        LogicalPlan::SetOp {
+            input_left,
+            input_right,
+            op,
+        } => {
+            let i_l = to_alp(unwrap_plan(input_left), expr_arena, lp_arena);
+            let i_r = to_alp(unwrap_plan(input_right), expr_arena, lp_arena);
+            ALogicalPlan::SetOp {
+                input_left: i_l,
+                input_right: i_r,
+                op,
+            }
+        }
+        LogicalPlan::JoinWhere {
+            input_left,
+            input_right,
+            schema,
+            predicates,
+            suffix,
+        } => {
+            let i_l = to_alp(unwrap_plan(input_left), expr_arena, lp_arena);
+            let i_r = to_alp(unwrap_plan(input_right), expr_arena, lp_arena);
+            let predicates = predicates
+                .into_iter()
+                .map(|x| to_aexpr(x, expr_arena))
+                .collect();
+            ALogicalPlan::JoinWhere {
+                input_left: i_l,
+                input_right: i_r,
+                schema,
+                predicates,
+                suffix,
             }
         }
+        LogicalPlan::Union { inputs } => {
+            let inputs = inputs
+                .into_iter()
+                .map(|lp| to_alp(lp, expr_arena, lp_arena))
+                .collect();
+            ALogicalPlan::Union { inputs }
+        }
         LogicalPlan::HStack {
             input,
             exprs,
             schema,
         } => {
             let exp = exprs.into_iter().map(|x| to_aexpr(x, expr_arena)).collect();
-            let i = to_alp(*input, expr_arena, lp_arena);
+            let i = to_alp(unwrap_plan(input), expr_arena, lp_arena);
             ALogicalPlan::HStack {
                 input: i,
                 exprs: exp,
@@ -941,7 +1155,7 @@ pub(crate) fn to_alp(
             maintain_order,
             subset,
         } => {
-            let i = to_alp(*input, expr_arena, lp_arena);
+            let i = to_alp(unwrap_plan(input), expr_arena, lp_arena);
             ALogicalPlan::Distinct {
                 input: i,
                 maintain_order,
@@ -954,16 +1168,36 @@ pub(crate) fn to_alp(
             projection_pd,
             predicate_pd,
             schema,
+            input_columns,
         } => {
-            let input = to_alp(*input, expr_arena, lp_arena);
+            let input = to_alp(unwrap_plan(input), expr_arena, lp_arena);
             ALogicalPlan::Udf {
                 input,
                 function,
                 projection_pd,
                 predicate_pd,
                 schema,
+                input_columns,
+            }
+        }
+        LogicalPlan::Sink {
+            input,
+            path,
+            file_type,
+            column_transformers,
+        } => {
+            let input = to_alp(unwrap_plan(input), expr_arena, lp_arena);
+            ALogicalPlan::Sink {
+                input,
+                path,
+                file_type,
+                column_transformers,
             }
         }
+        // the errors are returned before the plan is converted, see `LogicalPlan::error`
+        LogicalPlan::Error { input, .. } => {
+            return to_alp(unwrap_plan(input), expr_arena, lp_arena);
+        }
     };
     lp_arena.add(v)
 }
@@ -975,6 +1209,9 @@ pub(crate) fn node_to_exp(node: Node, expr_arena: &Arena<AExpr>) -> Expr {
         AExpr::Duplicated(node) => Expr::Duplicated(Box::new(node_to_exp(node, expr_arena))),
         AExpr::Unique(node) => Expr::Unique(Box::new(node_to_exp(node, expr_arena))),
         AExpr::Reverse(node) => Expr::Reverse(Box::new(node_to_exp(node, expr_arena))),
+        AExpr::CumCount(node) => Expr::CumCount(Box::new(node_to_exp(node, expr_arena))),
+        AExpr::Standardize(node) => Expr::Standardize(Box::new(node_to_exp(node, expr_arena))),
+        AExpr::MinMaxScale(node) => Expr::MinMaxScale(Box::new(node_to_exp(node, expr_arena))),
         AExpr::Explode(node) => Expr::Explode(Box::new(node_to_exp(node, expr_arena))),
         AExpr::Alias(expr, name) => {
             let exp = node_to_exp(expr, expr_arena);
@@ -1035,6 +1272,10 @@ pub(crate) fn node_to_exp(node: Node, expr_arena: &Arena<AExpr>) -> Expr {
                 let exp = node_to_exp(expr, expr_arena);
                 AggExpr::NUnique(Box::new(exp)).into()
             }
+            AAggExpr::NullCount(expr) => {
+                let exp = node_to_exp(expr, expr_arena);
+                AggExpr::NullCount(Box::new(exp)).into()
+            }
             AAggExpr::First(expr) => {
                 let exp = node_to_exp(expr, expr_arena);
                 AggExpr::First(Box::new(exp)).into()
@@ -1059,6 +1300,14 @@ pub(crate) fn node_to_exp(node: Node, expr_arena: &Arena<AExpr>) -> Expr {
                 }
                 .into()
             }
+            AAggExpr::TopK { expr, k } => {
+                let exp = node_to_exp(expr, expr_arena);
+                AggExpr::TopK {
+                    expr: Box::new(exp),
+                    k,
+                }
+                .into()
+            }
             AAggExpr::Sum(expr) => {
                 let exp = node_to_exp(expr, expr_arena);
                 AggExpr::Sum(Box::new(exp)).into()
@@ -1087,6 +1336,18 @@ pub(crate) fn node_to_exp(node: Node, expr_arena: &Arena<AExpr>) -> Expr {
                 periods,
             }
         }
+        AExpr::ClipQuantile {
+            input,
+            low_q,
+            high_q,
+        } => {
+            let e = node_to_exp(input, expr_arena);
+            Expr::ClipQuantile {
+                input: Box::new(e),
+                low_q,
+                high_q,
+            }
+        }
         AExpr::Ternary {
             predicate,
             truthy,
@@ -1165,7 +1426,7 @@ pub(crate) fn node_to_lp(
         ALogicalPlan::Slice { input, offset, len } => {
             let lp = node_to_lp(input, expr_arena, lp_arena);
             LogicalPlan::Slice {
-                input: Box::new(lp),
+                input: Arc::new(lp),
                 offset,
                 len,
             }
@@ -1174,7 +1435,7 @@ pub(crate) fn node_to_lp(
             let lp = node_to_lp(input, expr_arena, lp_arena);
             let p = node_to_exp(predicate, expr_arena);
             LogicalPlan::Selection {
-                input: Box::new(lp),
+                input: Arc::new(lp),
                 predicate: p,
             }
         }
@@ -1190,6 +1451,7 @@ pub(crate) fn node_to_lp(
             predicate,
             aggregate,
             cache,
+            column_transformers,
         } => LogicalPlan::CsvScan {
             path,
             schema,
@@ -1205,6 +1467,7 @@ pub(crate) fn node_to_lp(
                 .map(|n| node_to_exp(n, expr_arena))
                 .collect(),
             cache,
+            column_transformers,
         },
         #[cfg(feature = "parquet")]
         ALogicalPlan::ParquetScan {
@@ -1215,6 +1478,7 @@ pub(crate) fn node_to_lp(
             aggregate,
             stop_after_n_rows,
             cache,
+            column_transformers,
         } => LogicalPlan::ParquetScan {
             path,
             schema,
@@ -1226,6 +1490,30 @@ pub(crate) fn node_to_lp(
                 .collect(),
             stop_after_n_rows,
             cache,
+            column_transformers,
+        },
+        #[cfg(feature = "ipc")]
+        ALogicalPlan::IpcScan {
+            path,
+            schema,
+            with_columns,
+            predicate,
+            aggregate,
+            stop_after_n_rows,
+            cache,
+            column_transformers,
+        } => LogicalPlan::IpcScan {
+            path,
+            schema,
+            with_columns,
+            predicate: predicate.map(|n| node_to_exp(n, expr_arena)),
+            aggregate: aggregate
+                .into_iter()
+                .map(|n| node_to_exp(n, expr_arena))
+                .collect(),
+            stop_after_n_rows,
+            cache,
+            column_transformers,
         },
         ALogicalPlan::DataFrameScan {
             df,
@@ -1250,7 +1538,7 @@ pub(crate) fn node_to_lp(
 
             LogicalPlan::Projection {
                 expr: exprs,
-                input: Box::new(i),
+                input: Arc::new(i),
                 schema,
             }
         }
@@ -1264,29 +1552,36 @@ pub(crate) fn node_to_lp(
 
             LogicalPlan::LocalProjection {
                 expr: exprs,
-                input: Box::new(i),
+                input: Arc::new(i),
                 schema,
             }
         }
-        ALogicalPlan::Sort {
+        ALogicalPlan::Sort { input, by, reverse } => {
+            let input = Arc::new(node_to_lp(input, expr_arena, lp_arena));
+            let by = by.iter().map(|x| node_to_exp(*x, expr_arena)).collect();
+            LogicalPlan::Sort { input, by, reverse }
+        }
+        ALogicalPlan::Explode { input, columns } => {
+            let input = Arc::new(node_to_lp(input, expr_arena, lp_arena));
+            LogicalPlan::Explode { input, columns }
+        }
+        ALogicalPlan::Rename {
             input,
-            by_column,
-            reverse,
+            existing,
+            new,
+            schema,
         } => {
-            let input = Box::new(node_to_lp(input, expr_arena, lp_arena));
-            LogicalPlan::Sort {
+            let input = Arc::new(node_to_lp(input, expr_arena, lp_arena));
+            LogicalPlan::Rename {
                 input,
-                by_column,
-                reverse,
+                existing,
+                new,
+                schema,
             }
         }
-        ALogicalPlan::Explode { input, columns } => {
-            let input = Box::new(node_to_lp(input, expr_arena, lp_arena));
-            LogicalPlan::Explode { input, columns }
-        }
-        ALogicalPlan::Cache { input } => {
-            let input = Box::new(node_to_lp(input, expr_arena, lp_arena));
-            LogicalPlan::Cache { input }
+        ALogicalPlan::Cache { input, id } => {
+            let input = Arc::new(node_to_lp(input, expr_arena, lp_arena));
+            LogicalPlan::Cache { input, id }
         }
         ALogicalPlan::Aggregate {
             input,
@@ -1300,7 +1595,7 @@ pub(crate) fn node_to_lp(
             let keys = Arc::new(keys.iter().map(|x| node_to_exp(*x, expr_arena)).collect());
 
             LogicalPlan::Aggregate {
-                input: Box::new(i),
+                input: Arc::new(i),
                 keys,
                 aggs: a,
                 schema,
@@ -1314,8 +1609,7 @@ pub(crate) fn node_to_lp(
             how,
             left_on,
             right_on,
-            allow_par,
-            force_par,
+            options,
         } => {
             let i_l = node_to_lp(input_left, expr_arena, lp_arena);
             let i_r = node_to_lp(input_right, expr_arena, lp_arena);
@@ -1330,16 +1624,56 @@ pub(crate) fn node_to_lp(
                 .collect();
 
             LogicalPlan::Join {
-                input_left: Box::new(i_l),
-                input_right: Box::new(i_r),
+                input_left: Arc::new(i_l),
+                input_right: Arc::new(i_r),
                 schema,
                 how,
                 left_on: l_on,
                 right_on: r_on,
-                allow_par,
-                force_par,
+                options,
+            }
+        }
+        ALogicalPlan::SetOp {
+            input_left,
+            input_right,
+            op,
+        } => {
+            let i_l = node_to_lp(input_left, expr_arena, lp_arena);
+            let i_r = node_to_lp(input_right, expr_arena, lp_arena);
+            LogicalPlan::SetOp {
+                input_left: Arc::new(i_l),
+                input_right: Arc::new(i_r),
+                op,
+            }
+        }
+        ALogicalPlan::JoinWhere {
+            input_left,
+            input_right,
+            schema,
+            predicates,
+            suffix,
+        } => {
+            let i_l = node_to_lp(input_left, expr_arena, lp_arena);
+            let i_r = node_to_lp(input_right, expr_arena, lp_arena);
+            let predicates = predicates
+                .iter()
+                .map(|x| node_to_exp(*x, expr_arena))
+                .collect();
+            LogicalPlan::JoinWhere {
+                input_left: Arc::new(i_l),
+                input_right: Arc::new(i_r),
+                schema,
+                predicates,
+                suffix,
             }
         }
+        ALogicalPlan::Union { inputs } => {
+            let inputs = inputs
+                .iter()
+                .map(|node| node_to_lp(*node, expr_arena, lp_arena))
+                .collect();
+            LogicalPlan::Union { inputs }
+        }
         ALogicalPlan::HStack {
             input,
             exprs,
@@ -1349,7 +1683,7 @@ pub(crate) fn node_to_lp(
             let e = exprs.iter().map(|x| node_to_exp(*x, expr_arena)).collect();
 
             LogicalPlan::HStack {
-                input: Box::new(i),
+                input: Arc::new(i),
                 exprs: e,
                 schema,
             }
@@ -1361,7 +1695,7 @@ pub(crate) fn node_to_lp(
         } => {
             let i = node_to_lp(input, expr_arena, lp_arena);
             LogicalPlan::Distinct {
-                input: Box::new(i),
+                input: Arc::new(i),
                 maintain_order,
                 subset,
             }
@@ -1370,13 +1704,15 @@ pub(crate) fn node_to_lp(
             input,
             id_vars,
             value_vars,
+            maintain_order,
             schema,
         } => {
             let input = node_to_lp(input, expr_arena, lp_arena);
             LogicalPlan::Melt {
-                input: Box::new(input),
+                input: Arc::new(input),
                 id_vars,
                 value_vars,
+                maintain_order,
                 schema,
             }
         }
@@ -1386,14 +1722,30 @@ pub(crate) fn node_to_lp(
             predicate_pd,
             projection_pd,
             schema,
+            input_columns,
         } => {
-            let input = Box::new(node_to_lp(input, expr_arena, lp_arena));
+            let input = Arc::new(node_to_lp(input, expr_arena, lp_arena));
             LogicalPlan::Udf {
                 input,
                 function,
                 predicate_pd,
                 projection_pd,
                 schema,
+                input_columns,
+            }
+        }
+        ALogicalPlan::Sink {
+            input,
+            path,
+            file_type,
+            column_transformers,
+        } => {
+            let input = Arc::new(node_to_lp(input, expr_arena, lp_arena));
+            LogicalPlan::Sink {
+                input,
+                path,
+                file_type,
+                column_transformers,
             }
         }
     }
@@ -1443,13 +1795,34 @@ impl<'a> ALogicalPlanBuilder<'a> {
         }
     }
 
-    pub fn melt(self, id_vars: Arc<Vec<String>>, value_vars: Arc<Vec<String>>) -> Self {
-        let schema = det_melt_schema(&value_vars, self.schema());
+    pub fn melt(
+        self,
+        id_vars: Arc<Vec<String>>,
+        value_vars: Arc<Vec<String>>,
+        maintain_order: bool,
+    ) -> Self {
+        let schema = det_melt_schema(&value_vars, self.schema())
+            .expect("melt schema checked by the builder");
 
         let lp = ALogicalPlan::Melt {
             input: self.root,
             id_vars,
             value_vars,
+            maintain_order,
+            schema,
+        };
+        let node = self.lp_arena.add(lp);
+        ALogicalPlanBuilder::new(node, self.expr_arena, self.lp_arena)
+    }
+
+    pub fn rename(self, existing: Arc<Vec<String>>, new: Arc<Vec<String>>) -> Self {
+        let schema = det_rename_schema(&existing, &new, self.schema())
+            .expect("rename schema checked by the builder");
+
+        let lp = ALogicalPlan::Rename {
+            input: self.root,
+            existing,
+            new,
             schema,
         };
         let node = self.lp_arena.add(lp);
@@ -1566,8 +1939,7 @@ impl<'a> ALogicalPlanBuilder<'a> {
         how: JoinType,
         left_on: Vec<Node>,
         right_on: Vec<Node>,
-        allow_par: bool,
-        force_par: bool,
+        options: JoinOptions,
     ) -> Self {
         let schema_left = self.schema();
         let schema_right = self.lp_arena.get(other).schema(self.lp_arena);
@@ -1598,7 +1970,8 @@ impl<'a> ALogicalPlanBuilder<'a> {
             let name = f.name();
             if !right_names.contains(name) {
                 if names.contains(name) {
-                    let new_name = format!("{}_right", name);
+                    let new_name =
+                        format!("{}{}", name, options.suffix.as_deref().unwrap_or("_right"));
                     let field = Field::new(&new_name, f.data_type().clone());
                     fields.push(field)
                 } else {
@@ -1606,6 +1979,12 @@ impl<'a> ALogicalPlanBuilder<'a> {
                 }
             }
         }
+        if options.indicator {
+            fields.push(Field::new(
+                JOIN_INDICATOR_NAME,
+                DataType::Categorical(CategoricalOrdering::Physical),
+            ));
+        }
 
         let schema = Arc::new(Schema::new(fields));
 
@@ -1616,8 +1995,7 @@ impl<'a> ALogicalPlanBuilder<'a> {
             schema,
             left_on,
             right_on,
-            allow_par,
-            force_par,
+            options,
         };
         let root = self.lp_arena.add(lp);
         Self::new(root, self.expr_arena, self.lp_arena)
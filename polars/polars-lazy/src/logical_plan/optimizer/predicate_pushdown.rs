@@ -4,7 +4,7 @@ use crate::prelude::*;
 use crate::utils::{
     aexpr_to_root_column_name, aexpr_to_root_names, aexprs_to_schema, check_down_node,
 };
-use crate::utils::{has_aexpr, rename_aexpr_root_name};
+use crate::utils::{has_aexpr, rename_aexpr_root_name, rename_aexpr_root_names};
 use ahash::RandomState;
 use polars_core::prelude::*;
 use std::collections::HashMap;
@@ -91,6 +91,71 @@ fn roots_to_key(roots: &[Arc<String>]) -> Arc<String> {
     }
 }
 
+/// Split a predicate in the predicates that are combined with `&`.
+fn split_conjuncts(node: Node, arena: &Arena<AExpr>, conjuncts: &mut Vec<Node>) {
+    match arena.get(node) {
+        AExpr::BinaryExpr {
+            left,
+            op: Operator::And,
+            right,
+        } => {
+            split_conjuncts(*left, arena, conjuncts);
+            split_conjuncts(*right, arena, conjuncts);
+        }
+        _ => conjuncts.push(node),
+    }
+}
+
+/// Key columns of a cross join predicate that can be evaluated by a hash join.
+struct EquiJoinKey {
+    left: Arc<String>,
+    /// Name of the key in the right input.
+    right: Arc<String>,
+    /// Name of the key in the output of the cross join.
+    right_output: Arc<String>,
+}
+
+/// If `predicate` is `col(a) == col(b)` with `a` a column of the left input and `b` a column of
+/// the right input of equal data type, return the keys of the equivalent hash join.
+fn equi_join_key(
+    predicate: Node,
+    arena: &Arena<AExpr>,
+    schema_left: &Schema,
+    schema_right: &Schema,
+    right_inputs: &HashMap<Arc<String>, Arc<String>, RandomState>,
+) -> Option<EquiJoinKey> {
+    let (a, b) = match arena.get(predicate) {
+        AExpr::BinaryExpr {
+            left,
+            op: Operator::Eq,
+            right,
+        } => match (arena.get(*left), arena.get(*right)) {
+            (AExpr::Column(a), AExpr::Column(b)) => (a.clone(), b.clone()),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let is_left = |name: &str| schema_left.field_with_name(name).is_ok();
+    let (left, right_output) = if is_left(&a) && !is_left(&b) {
+        (a, b)
+    } else if is_left(&b) && !is_left(&a) {
+        (b, a)
+    } else {
+        return None;
+    };
+    let right = right_inputs.get(&right_output)?.clone();
+    let dtype_left = schema_left.field_with_name(&left).ok()?.data_type();
+    let dtype_right = schema_right.field_with_name(&right).ok()?.data_type();
+    if dtype_left != dtype_right {
+        return None;
+    }
+    Some(EquiJoinKey {
+        left,
+        right,
+        right_output,
+    })
+}
+
 pub(crate) struct PredicatePushDown {}
 
 impl Default for PredicatePushDown {
@@ -172,6 +237,148 @@ impl PredicatePushDown {
         }
     }
 
+    /// Push the predicates on a cross join down. The equalities between a left and a right
+    /// column turn the cross join into a hash join, the predicates on a single input are pushed
+    /// to that input and the other predicates filter the output.
+    fn push_down_cross_join(
+        &self,
+        input_left: Node,
+        input_right: Node,
+        schema: SchemaRef,
+        options: JoinOptions,
+        acc_predicates: HashMap<Arc<String>, Node, RandomState>,
+        lp_arena: &mut Arena<ALogicalPlan>,
+        expr_arena: &mut Arena<AExpr>,
+    ) -> Result<ALogicalPlan> {
+        let schema_left = lp_arena.get(input_left).schema(lp_arena).clone();
+        let schema_right = lp_arena.get(input_right).schema(lp_arena).clone();
+
+        // right columns that are also in the left input get the suffix in the output
+        let mut right_inputs = optimizer::init_hashmap();
+        for f in schema_right.fields() {
+            let output_name = if schema_left.field_with_name(f.name()).is_ok() {
+                format!(
+                    "{}{}",
+                    f.name(),
+                    options.suffix.as_deref().unwrap_or("_right")
+                )
+            } else {
+                f.name().clone()
+            };
+            right_inputs.insert(Arc::new(output_name), Arc::new(f.name().clone()));
+        }
+
+        let mut conjuncts = Vec::with_capacity(acc_predicates.len());
+        for (_, predicate) in acc_predicates {
+            split_conjuncts(predicate, expr_arena, &mut conjuncts);
+        }
+
+        let mut keys = vec![];
+        let mut pushdown_left = optimizer::init_hashmap();
+        let mut pushdown_right = optimizer::init_hashmap();
+        let mut local_predicates = Vec::with_capacity(conjuncts.len());
+        for predicate in conjuncts {
+            if has_aexpr(predicate, expr_arena, &AExpr::Unique(Default::default()))
+                || has_aexpr(
+                    predicate,
+                    expr_arena,
+                    &AExpr::Duplicated(Default::default()),
+                )
+            {
+                local_predicates.push(predicate);
+                continue;
+            }
+            if let Some(key) = equi_join_key(
+                predicate,
+                expr_arena,
+                &schema_left,
+                &schema_right,
+                &right_inputs,
+            ) {
+                // null keys are equal in a hash join, but not in the predicate
+                let column = expr_arena.add(AExpr::Column(key.left.clone()));
+                let not_null = expr_arena.add(AExpr::IsNotNull(column));
+                insert_and_combine_predicate(
+                    &mut pushdown_left,
+                    key.left.clone(),
+                    not_null,
+                    expr_arena,
+                );
+                keys.push(key);
+                continue;
+            }
+            let roots = aexpr_to_root_names(predicate, expr_arena);
+            if roots
+                .iter()
+                .all(|name| schema_left.field_with_name(name).is_ok())
+            {
+                insert_and_combine_predicate(
+                    &mut pushdown_left,
+                    roots_to_key(&roots),
+                    predicate,
+                    expr_arena,
+                );
+            } else if roots
+                .iter()
+                .all(|name| right_inputs.get(name) == Some(name))
+            {
+                insert_and_combine_predicate(
+                    &mut pushdown_right,
+                    roots_to_key(&roots),
+                    predicate,
+                    expr_arena,
+                );
+            } else {
+                local_predicates.push(predicate);
+            }
+        }
+
+        self.pushdown_and_assign(input_left, pushdown_left, lp_arena, expr_arena)?;
+        self.pushdown_and_assign(input_right, pushdown_right, lp_arena, expr_arena)?;
+
+        if keys.is_empty() {
+            let lp = ALogicalPlan::Join {
+                input_left,
+                input_right,
+                schema,
+                how: JoinType::Cross,
+                left_on: vec![],
+                right_on: vec![],
+                options,
+            };
+            return Ok(self.apply_predicate(lp, local_predicates, lp_arena, expr_arena));
+        }
+
+        let left_on = keys
+            .iter()
+            .map(|key| expr_arena.add(AExpr::Column(key.left.clone())))
+            .collect();
+        let right_on = keys
+            .iter()
+            .map(|key| expr_arena.add(AExpr::Column(key.right.clone())))
+            .collect();
+        // the hash join drops the right keys, they are equal to the left keys
+        let right_keys = keys
+            .iter()
+            .map(|key| {
+                let column = expr_arena.add(AExpr::Column(key.left.clone()));
+                expr_arena.add(AExpr::Alias(column, key.right_output.clone()))
+            })
+            .collect();
+        // and the columns are selected in the order of the cross join
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|f| expr_arena.add(AExpr::Column(Arc::new(f.name().clone()))))
+            .collect();
+        let lp = ALogicalPlanBuilder::new(input_left, expr_arena, lp_arena)
+            .join(input_right, JoinType::Inner, left_on, right_on, options)
+            .with_columns(right_keys)
+            .project(columns)
+            .build();
+        Ok(self.apply_predicate(lp, local_predicates, lp_arena, expr_arena))
+    }
+
     fn pushdown_and_assign(
         &self,
         input: Node,
@@ -207,8 +414,11 @@ impl PredicatePushDown {
 
         match logical_plan {
             Slice { input, offset, len } => {
-                self.pushdown_and_assign(input, acc_predicates, lp_arena, expr_arena)?;
-                Ok(Slice { input, offset, len })
+                // filtering before the slice changes the rows that are selected
+                // so the predicates are applied after the slice.
+                self.pushdown_and_assign(input, optimizer::init_hashmap(), lp_arena, expr_arena)?;
+                let lp = Slice { input, offset, len };
+                Ok(self.finish_at_leaf(lp, acc_predicates, lp_arena, expr_arena))
             }
             Selection { predicate, input } => {
                 let name = roots_to_key(&aexpr_to_root_names(predicate, expr_arena));
@@ -251,7 +461,17 @@ impl PredicatePushDown {
                     no_pushdown_preds(
                         *node,
                         &expr_arena,
-                        &[AExpr::Explode(Default::default())],
+                        &[
+                            AExpr::Explode(Default::default()),
+                            AExpr::CumCount(Default::default()),
+                            AExpr::Standardize(Default::default()),
+                            AExpr::MinMaxScale(Default::default()),
+                            AExpr::ClipQuantile {
+                                input: Default::default(),
+                                low_q: Default::default(),
+                                high_q: Default::default(),
+                            },
+                        ],
                         &mut local_predicates,
                         &mut acc_predicates,
                     );
@@ -285,6 +505,7 @@ impl PredicatePushDown {
                 input,
                 id_vars,
                 value_vars,
+                maintain_order,
                 schema,
             } => {
                 // predicates that will be done at this level
@@ -313,6 +534,7 @@ impl PredicatePushDown {
                     input,
                     id_vars,
                     value_vars,
+                    maintain_order,
                     schema,
                 };
                 Ok(self.apply_predicate(lp, local_predicates, lp_arena, expr_arena))
@@ -343,6 +565,7 @@ impl PredicatePushDown {
                 aggregate,
                 stop_after_n_rows,
                 cache,
+                column_transformers,
             } => {
                 let predicate = predicate_at_scan(acc_predicates, predicate, expr_arena);
 
@@ -354,6 +577,32 @@ impl PredicatePushDown {
                     aggregate,
                     stop_after_n_rows,
                     cache,
+                    column_transformers,
+                };
+                Ok(lp)
+            }
+            #[cfg(feature = "ipc")]
+            IpcScan {
+                path,
+                schema,
+                with_columns,
+                predicate,
+                aggregate,
+                stop_after_n_rows,
+                cache,
+                column_transformers,
+            } => {
+                let predicate = predicate_at_scan(acc_predicates, predicate, expr_arena);
+
+                let lp = IpcScan {
+                    path,
+                    schema,
+                    with_columns,
+                    predicate,
+                    aggregate,
+                    stop_after_n_rows,
+                    cache,
+                    column_transformers,
                 };
                 Ok(lp)
             }
@@ -369,6 +618,7 @@ impl PredicatePushDown {
                 predicate,
                 aggregate,
                 cache,
+                column_transformers,
             } => {
                 let predicate = predicate_at_scan(acc_predicates, predicate, expr_arena);
 
@@ -384,20 +634,13 @@ impl PredicatePushDown {
                     predicate,
                     aggregate,
                     cache,
+                    column_transformers,
                 };
                 Ok(lp)
             }
-            Sort {
-                input,
-                by_column,
-                reverse,
-            } => {
+            Sort { input, by, reverse } => {
                 self.pushdown_and_assign(input, acc_predicates, lp_arena, expr_arena)?;
-                Ok(Sort {
-                    input,
-                    by_column,
-                    reverse,
-                })
+                Ok(Sort { input, by, reverse })
             }
             Explode { input, columns } => {
                 // we remove predicates that are done in one of the exploded columns.
@@ -422,9 +665,37 @@ impl PredicatePushDown {
                 let lp = Explode { input, columns };
                 Ok(self.apply_predicate(lp, local_predicates, lp_arena, expr_arena))
             }
-            Cache { input } => {
-                self.pushdown_and_assign(input, acc_predicates, lp_arena, expr_arena)?;
-                Ok(Cache { input })
+            Rename {
+                input,
+                existing,
+                new,
+                schema,
+            } => {
+                // the predicates refer to the new names; map them back to the names of the input
+                let mut new_acc_predicates = optimizer::init_hashmap();
+                for (_, predicate) in acc_predicates {
+                    rename_aexpr_root_names(predicate, expr_arena, &new, &existing);
+                    let name = roots_to_key(&aexpr_to_root_names(predicate, expr_arena));
+                    insert_and_combine_predicate(
+                        &mut new_acc_predicates,
+                        name,
+                        predicate,
+                        expr_arena,
+                    );
+                }
+                self.pushdown_and_assign(input, new_acc_predicates, lp_arena, expr_arena)?;
+                Ok(Rename {
+                    input,
+                    existing,
+                    new,
+                    schema,
+                })
+            }
+            // the result of a cache is shared, so the predicates are applied after the cache
+            Cache { input, id } => {
+                self.pushdown_and_assign(input, HashMap::default(), lp_arena, expr_arena)?;
+                let lp = Cache { input, id };
+                Ok(self.finish_at_leaf(lp, acc_predicates, lp_arena, expr_arena))
             }
             Distinct {
                 input,
@@ -487,10 +758,20 @@ impl PredicatePushDown {
                 left_on,
                 right_on,
                 how,
-                allow_par,
-                force_par,
+                options,
                 schema,
             } => {
+                if how == JoinType::Cross && !options.indicator {
+                    return self.push_down_cross_join(
+                        input_left,
+                        input_right,
+                        schema,
+                        options,
+                        acc_predicates,
+                        lp_arena,
+                        expr_arena,
+                    );
+                }
                 let schema_left = lp_arena.get(input_left).schema(lp_arena);
                 let schema_right = lp_arena.get(input_right).schema(lp_arena);
 
@@ -577,8 +858,7 @@ impl PredicatePushDown {
                     left_on,
                     right_on,
                     how,
-                    allow_par,
-                    force_par,
+                    options,
                     schema,
                 };
                 Ok(self.apply_predicate(lp, local_predicates, lp_arena, expr_arena))
@@ -606,22 +886,36 @@ impl PredicatePushDown {
                 // *use a vec instead of a set because of the low number of expected columns
                 let mut added_cols = Vec::with_capacity(exprs.len());
                 for e in &exprs {
-                    // shifts | sorts are influenced by a filter so we do all predicates before the shift | sort
-                    if has_aexpr(
-                        *e,
-                        expr_arena,
-                        &AExpr::Shift {
-                            input: Default::default(),
-                            periods: Default::default(),
-                        },
-                    ) || has_aexpr(
-                        *e,
-                        expr_arena,
-                        &AExpr::Sort {
-                            expr: Default::default(),
-                            reverse: Default::default(),
-                        },
-                    ) {
+                    // shifts | sorts | cumcounts | scaling | quantile clips are influenced by a filter so we do all predicates before them
+                    if has_aexpr(*e, expr_arena, &AExpr::CumCount(Default::default()))
+                        || has_aexpr(*e, expr_arena, &AExpr::Standardize(Default::default()))
+                        || has_aexpr(*e, expr_arena, &AExpr::MinMaxScale(Default::default()))
+                        || has_aexpr(
+                            *e,
+                            expr_arena,
+                            &AExpr::ClipQuantile {
+                                input: Default::default(),
+                                low_q: Default::default(),
+                                high_q: Default::default(),
+                            },
+                        )
+                        || has_aexpr(
+                            *e,
+                            expr_arena,
+                            &AExpr::Shift {
+                                input: Default::default(),
+                                periods: Default::default(),
+                            },
+                        )
+                        || has_aexpr(
+                            *e,
+                            expr_arena,
+                            &AExpr::Sort {
+                                expr: Default::default(),
+                                reverse: Default::default(),
+                            },
+                        )
+                    {
                         let lp = ALogicalPlanBuilder::new(input, expr_arena, lp_arena)
                             .with_columns(exprs)
                             .build();
@@ -660,12 +954,67 @@ impl PredicatePushDown {
                 Ok(self.apply_predicate(lp, local_predicates, lp_arena, expr_arena))
             }
 
+            SetOp {
+                input_left,
+                input_right,
+                op,
+            } => {
+                // the predicates are applied after the set operation, we only optimize the inputs.
+                for input in [input_left, input_right].iter() {
+                    self.pushdown_and_assign(
+                        *input,
+                        optimizer::init_hashmap(),
+                        lp_arena,
+                        expr_arena,
+                    )?;
+                }
+                let lp = SetOp {
+                    input_left,
+                    input_right,
+                    op,
+                };
+                Ok(self.finish_at_leaf(lp, acc_predicates, lp_arena, expr_arena))
+            }
+            Union { inputs } => {
+                // a row passes the predicates independent of the input it comes from, so the
+                // predicates are pushed down to every input
+                for input in &inputs {
+                    self.pushdown_and_assign(*input, acc_predicates.clone(), lp_arena, expr_arena)?;
+                }
+                Ok(Union { inputs })
+            }
+            JoinWhere {
+                input_left,
+                input_right,
+                schema,
+                predicates,
+                suffix,
+            } => {
+                // the predicates are applied after the join, we only optimize the inputs.
+                for input in [input_left, input_right].iter() {
+                    self.pushdown_and_assign(
+                        *input,
+                        optimizer::init_hashmap(),
+                        lp_arena,
+                        expr_arena,
+                    )?;
+                }
+                let lp = JoinWhere {
+                    input_left,
+                    input_right,
+                    schema,
+                    predicates,
+                    suffix,
+                };
+                Ok(self.finish_at_leaf(lp, acc_predicates, lp_arena, expr_arena))
+            }
             Udf {
                 input,
                 function,
                 predicate_pd,
                 projection_pd,
                 schema,
+                input_columns,
             } => {
                 if predicate_pd {
                     let input_schema = lp_arena.get(input).schema(lp_arena);
@@ -698,6 +1047,7 @@ impl PredicatePushDown {
                         predicate_pd,
                         projection_pd,
                         schema,
+                        input_columns,
                     };
 
                     return Ok(self.apply_predicate(lp, local_predicates, lp_arena, expr_arena));
@@ -708,6 +1058,22 @@ impl PredicatePushDown {
                     predicate_pd,
                     projection_pd,
                     schema,
+                    input_columns,
+                })
+            }
+            Sink {
+                input,
+                path,
+                file_type,
+                column_transformers,
+            } => {
+                // the sink has the schema of its input
+                self.pushdown_and_assign(input, acc_predicates, lp_arena, expr_arena)?;
+                Ok(Sink {
+                    input,
+                    path,
+                    file_type,
+                    column_transformers,
                 })
             }
         }
@@ -303,6 +303,7 @@ impl ProjectionPushDown {
                 aggregate,
                 stop_after_n_rows,
                 cache,
+                column_transformers,
                 ..
             } => {
                 let with_columns = get_scan_columns(&mut acc_projections, expr_arena);
@@ -314,6 +315,31 @@ impl ProjectionPushDown {
                     aggregate,
                     stop_after_n_rows,
                     cache,
+                    column_transformers,
+                };
+                Ok(lp)
+            }
+            #[cfg(feature = "ipc")]
+            IpcScan {
+                path,
+                schema,
+                predicate,
+                aggregate,
+                stop_after_n_rows,
+                cache,
+                column_transformers,
+                ..
+            } => {
+                let with_columns = get_scan_columns(&mut acc_projections, expr_arena);
+                let lp = IpcScan {
+                    path,
+                    schema,
+                    with_columns,
+                    predicate,
+                    aggregate,
+                    stop_after_n_rows,
+                    cache,
+                    column_transformers,
                 };
                 Ok(lp)
             }
@@ -328,6 +354,7 @@ impl ProjectionPushDown {
                 predicate,
                 aggregate,
                 cache,
+                column_transformers,
                 ..
             } => {
                 let with_columns = get_scan_columns(&mut acc_projections, expr_arena);
@@ -343,18 +370,16 @@ impl ProjectionPushDown {
                     predicate,
                     aggregate,
                     cache,
+                    column_transformers,
                 };
                 Ok(lp)
             }
-            Sort {
-                input,
-                by_column,
-                reverse,
-            } => {
+            Sort { input, by, reverse } => {
                 if !acc_projections.is_empty() {
-                    // Make sure that the column used for the sort is projected
-                    let node = expr_arena.add(AExpr::Column(Arc::new(by_column.clone())));
-                    add_to_accumulated(node, &mut acc_projections, &mut names, expr_arena);
+                    // Make sure that the columns used for the sort are projected
+                    for node in &by {
+                        add_to_accumulated(*node, &mut acc_projections, &mut names, expr_arena);
+                    }
                 }
 
                 self.pushdown_and_assign(
@@ -365,11 +390,7 @@ impl ProjectionPushDown {
                     lp_arena,
                     expr_arena,
                 )?;
-                Ok(Sort {
-                    input,
-                    by_column,
-                    reverse,
-                })
+                Ok(Sort { input, by, reverse })
             }
             Explode { input, columns } => {
                 if !acc_projections.is_empty() {
@@ -389,16 +410,53 @@ impl ProjectionPushDown {
                 )?;
                 Ok(Explode { input, columns })
             }
-            Cache { input } => {
+            Rename {
+                input,
+                existing,
+                new,
+                ..
+            } => {
+                // map the projected names back to the names of the input
+                let mut input_projections = Vec::with_capacity(acc_projections.len());
+                let mut input_names = init_set();
+                for proj in acc_projections {
+                    for name in aexpr_to_root_names(proj, expr_arena) {
+                        let name = match new.iter().position(|n| n == &*name) {
+                            Some(idx) => Arc::new(existing[idx].clone()),
+                            None => name,
+                        };
+                        if input_names.insert(name.clone()) {
+                            input_projections.push(expr_arena.add(AExpr::Column(name)));
+                        }
+                    }
+                }
                 self.pushdown_and_assign(
                     input,
-                    acc_projections,
-                    names,
+                    input_projections,
+                    input_names,
                     projections_seen,
                     lp_arena,
                     expr_arena,
                 )?;
-                Ok(Cache { input })
+
+                // only rename the columns that are still produced by the input
+                let input_schema = lp_arena.get(input).schema(lp_arena);
+                let (existing, new): (Vec<_>, Vec<_>) = existing
+                    .iter()
+                    .zip(new.iter())
+                    .filter(|(name, _)| input_schema.index_of(name).is_ok())
+                    .map(|(existing, new)| (existing.clone(), new.clone()))
+                    .unzip();
+                Ok(ALogicalPlanBuilder::new(input, expr_arena, lp_arena)
+                    .rename(Arc::new(existing), Arc::new(new))
+                    .build())
+            }
+            // the result of a cache is shared, so the projections are applied after the cache
+            Cache { input, id } => {
+                self.pushdown_and_assign(input, init_vec(), init_set(), 0, lp_arena, expr_arena)?;
+                let lp = lp_arena.add(Cache { input, id });
+                let builder = ALogicalPlanBuilder::new(lp, expr_arena, lp_arena);
+                Ok(self.finish_node(acc_projections, builder))
             }
             Distinct {
                 input,
@@ -447,6 +505,7 @@ impl ProjectionPushDown {
                 input,
                 id_vars,
                 value_vars,
+                maintain_order,
                 ..
             } => {
                 let (mut acc_projections, mut local_projections, names) = split_acc_projections(
@@ -480,8 +539,11 @@ impl ProjectionPushDown {
                     expr_arena,
                 )?;
 
-                let builder =
-                    ALogicalPlanBuilder::new(input, expr_arena, lp_arena).melt(id_vars, value_vars);
+                let builder = ALogicalPlanBuilder::new(input, expr_arena, lp_arena).melt(
+                    id_vars,
+                    value_vars,
+                    maintain_order,
+                );
                 Ok(self.finish_node(local_projections, builder))
             }
             Aggregate {
@@ -542,8 +604,7 @@ impl ProjectionPushDown {
                 left_on,
                 right_on,
                 how,
-                allow_par,
-                force_par,
+                options,
                 ..
             } => {
                 let mut pushdown_left = init_vec();
@@ -557,6 +618,8 @@ impl ProjectionPushDown {
                     let schema_left = lp_arena.get(input_left).schema(lp_arena);
                     let schema_right = lp_arena.get(input_right).schema(lp_arena);
 
+                    let suffix = options.suffix.as_deref().unwrap_or("_right");
+
                     // We need the join columns so we push the projection downwards
                     pushdown_left.extend_from_slice(&left_on);
                     pushdown_right.extend_from_slice(&right_on);
@@ -578,7 +641,7 @@ impl ProjectionPushDown {
                         }
 
                         // Path for renamed columns due to the join. The column name of the left table
-                        // stays as is, the column of the right will have the "_right" suffix.
+                        // stays as is, the column of the right will have the suffix, "_right" by default.
                         // Thus joining two tables with both a foo column leads to ["foo", "foo_right"]
                         if !self.join_push_down(
                             schema_left,
@@ -594,12 +657,18 @@ impl ProjectionPushDown {
                             let root_column_name =
                                 aexpr_to_root_names(proj, expr_arena).pop().unwrap();
 
-                            // If _right suffix exists we need to push a projection down without this
+                            // The indicator column is created by the join itself.
+                            if options.indicator && root_column_name.as_str() == JOIN_INDICATOR_NAME
+                            {
+                                if add_local {
+                                    local_projection.push(proj)
+                                }
+                            // If the suffix exists we need to push a projection down without this
                             // suffix.
-                            if root_column_name.ends_with("_right") {
-                                // downwards name is the name without the _right i.e. "foo".
+                            } else if root_column_name.ends_with(suffix) {
+                                // downwards name is the name without the suffix i.e. "foo".
                                 let (downwards_name, _) = root_column_name
-                                    .split_at(root_column_name.len() - "_right".len());
+                                    .split_at(root_column_name.len() - suffix.len());
 
                                 let downwards_name_column =
                                     expr_arena.add(AExpr::Column(Arc::new(downwards_name.into())));
@@ -611,7 +680,7 @@ impl ProjectionPushDown {
                                 // locally we project and alias
                                 let projection = expr_arena.add(AExpr::Alias(
                                     downwards_name_column,
-                                    Arc::new(format!("{}_right", downwards_name)),
+                                    Arc::new(format!("{}{}", downwards_name, suffix)),
                                 ));
                                 local_projection.push(projection);
                             }
@@ -652,15 +721,28 @@ impl ProjectionPushDown {
                     how,
                     left_on,
                     right_on,
-                    allow_par,
-                    force_par,
+                    options,
                 );
                 Ok(self.finish_node(local_projection, builder))
             }
             HStack { input, exprs, .. } => {
                 // Make sure that columns selected with_columns are available
                 // only if not empty. If empty we already select everything.
-                if !acc_projections.is_empty() {
+                let exprs = if acc_projections.is_empty() {
+                    exprs
+                } else {
+                    // Columns that are not projected don't have to be computed.
+                    let input_schema = lp_arena.get(input).schema(lp_arena);
+                    let exprs = exprs
+                        .into_iter()
+                        .filter(|e| {
+                            expr_arena
+                                .get(*e)
+                                .to_field(input_schema, Context::Other, expr_arena)
+                                .map(|field| names.contains(field.name()))
+                                .unwrap_or(true)
+                        })
+                        .collect::<Vec<_>>();
                     for expression in &exprs {
                         add_to_accumulated(
                             *expression,
@@ -669,7 +751,8 @@ impl ProjectionPushDown {
                             expr_arena,
                         );
                     }
-                }
+                    exprs
+                };
 
                 let (acc_projections, _, names) = split_acc_projections(
                     acc_projections,
@@ -685,20 +768,111 @@ impl ProjectionPushDown {
                     lp_arena,
                     expr_arena,
                 )?;
+                if exprs.is_empty() {
+                    return Ok(lp_arena.take(input));
+                }
                 let lp = ALogicalPlanBuilder::new(input, expr_arena, lp_arena)
                     .with_columns(exprs)
                     .build();
                 Ok(lp)
             }
 
+            SetOp {
+                input_left,
+                input_right,
+                op,
+            } => {
+                // all columns determine if rows are equal, so we cannot project before the set
+                // operation. We do still optimize the inputs.
+                for input in [input_left, input_right].iter() {
+                    self.pushdown_and_assign(
+                        *input,
+                        init_vec(),
+                        init_set(),
+                        projections_seen,
+                        lp_arena,
+                        expr_arena,
+                    )?;
+                }
+                let lp = SetOp {
+                    input_left,
+                    input_right,
+                    op,
+                };
+                let input = lp_arena.add(lp);
+                let builder = ALogicalPlanBuilder::new(input, expr_arena, lp_arena);
+                Ok(self.finish_node(acc_projections, builder))
+            }
+            Union { inputs } => {
+                // all inputs have the same schema, so every input gets the same projections
+                for input in &inputs {
+                    self.pushdown_and_assign(
+                        *input,
+                        acc_projections.clone(),
+                        names.clone(),
+                        projections_seen,
+                        lp_arena,
+                        expr_arena,
+                    )?;
+                }
+                Ok(Union { inputs })
+            }
+            JoinWhere {
+                input_left,
+                input_right,
+                schema,
+                predicates,
+                suffix,
+            } => {
+                // the predicates may use any column of the joined schema, so we project after
+                // the join. We do still optimize the inputs.
+                for input in [input_left, input_right].iter() {
+                    self.pushdown_and_assign(
+                        *input,
+                        init_vec(),
+                        init_set(),
+                        projections_seen,
+                        lp_arena,
+                        expr_arena,
+                    )?;
+                }
+                let lp = JoinWhere {
+                    input_left,
+                    input_right,
+                    schema,
+                    predicates,
+                    suffix,
+                };
+                let input = lp_arena.add(lp);
+                let builder = ALogicalPlanBuilder::new(input, expr_arena, lp_arena);
+                Ok(self.finish_node(acc_projections, builder))
+            }
             Udf {
                 input,
                 function,
                 predicate_pd,
                 projection_pd,
                 schema,
+                input_columns,
             } => {
-                if projection_pd {
+                if let Some(columns) = &input_columns {
+                    // the function only reads these columns. The projections above refer to its
+                    // output, so they stay above it
+                    let mut acc_projections = init_vec();
+                    let mut names = init_set();
+                    for name in columns.iter() {
+                        let node = expr_arena.add(AExpr::Column(Arc::new(name.clone())));
+                        add_to_accumulated(node, &mut acc_projections, &mut names, expr_arena);
+                    }
+                    self.pushdown_and_assign(
+                        input,
+                        acc_projections,
+                        names,
+                        projections_seen,
+                        lp_arena,
+                        expr_arena,
+                    )?;
+                } else if projection_pd {
                     self.pushdown_and_assign(
                         input,
                         acc_projections,
@@ -714,6 +888,28 @@ impl ProjectionPushDown {
                     predicate_pd,
                     projection_pd,
                     schema,
+                    input_columns,
+                })
+            }
+            Sink {
+                input,
+                path,
+                file_type,
+                column_transformers,
+            } => {
+                self.pushdown_and_assign(
+                    input,
+                    acc_projections,
+                    names,
+                    projections_seen,
+                    lp_arena,
+                    expr_arena,
+                )?;
+                Ok(Sink {
+                    input,
+                    path,
+                    file_type,
+                    column_transformers,
                 })
             }
         }
@@ -1,9 +1,12 @@
 use crate::logical_plan::*;
 use crate::prelude::*;
+use polars_core::prelude::*;
 use polars_core::utils::Arena;
 
+/// Fold an arithmetic operation on two literals of the same type. Integer operations that
+/// overflow or divide by zero are not folded, so that they behave the same as at runtime.
 macro_rules! eval_binary_same_type {
-    ($lhs:expr, $operand: tt, $rhs:expr) => {{
+    ($lhs:expr, $operand: tt, $checked: ident, $rhs:expr) => {{
     if let (AExpr::Literal(lit_left), AExpr::Literal(lit_right)) = ($lhs, $rhs) {
         return match (lit_left, lit_right) {
             (LiteralValue::Float32(x), LiteralValue::Float32(y)) => {
@@ -14,29 +17,29 @@ macro_rules! eval_binary_same_type {
             }
             #[cfg(feature = "dtype-i8")]
             (LiteralValue::Int8(x), LiteralValue::Int8(y)) => {
-                Some(AExpr::Literal(LiteralValue::Int8(x $operand y)))
+                x.$checked(*y).map(|v| AExpr::Literal(LiteralValue::Int8(v)))
             }
             #[cfg(feature = "dtype-i16")]
             (LiteralValue::Int16(x), LiteralValue::Int16(y)) => {
-                Some(AExpr::Literal(LiteralValue::Int16(x $operand y)))
+                x.$checked(*y).map(|v| AExpr::Literal(LiteralValue::Int16(v)))
             }
             (LiteralValue::Int32(x), LiteralValue::Int32(y)) => {
-                Some(AExpr::Literal(LiteralValue::Int32(x $operand y)))
+                x.$checked(*y).map(|v| AExpr::Literal(LiteralValue::Int32(v)))
             }
             (LiteralValue::Int64(x), LiteralValue::Int64(y)) => {
-                Some(AExpr::Literal(LiteralValue::Int64(x $operand y)))
+                x.$checked(*y).map(|v| AExpr::Literal(LiteralValue::Int64(v)))
             }
             (LiteralValue::UInt8(x), LiteralValue::UInt8(y)) => {
-                Some(AExpr::Literal(LiteralValue::UInt8(x $operand y)))
+                x.$checked(*y).map(|v| AExpr::Literal(LiteralValue::UInt8(v)))
             }
             (LiteralValue::UInt16(x), LiteralValue::UInt16(y)) => {
-                Some(AExpr::Literal(LiteralValue::UInt16(x $operand y)))
+                x.$checked(*y).map(|v| AExpr::Literal(LiteralValue::UInt16(v)))
             }
             (LiteralValue::UInt32(x), LiteralValue::UInt32(y)) => {
-                Some(AExpr::Literal(LiteralValue::UInt32(x $operand y)))
+                x.$checked(*y).map(|v| AExpr::Literal(LiteralValue::UInt32(v)))
             }
             (LiteralValue::UInt64(x), LiteralValue::UInt64(y)) => {
-                Some(AExpr::Literal(LiteralValue::UInt64(x $operand y)))
+                x.$checked(*y).map(|v| AExpr::Literal(LiteralValue::UInt64(v)))
             }
             _ => None,
         };
@@ -93,6 +96,55 @@ macro_rules! eval_binary_bool_type {
     }}
 }
 
+/// The name of the output column of a (aliased) column expression.
+fn output_name(node: Node, expr_arena: &Arena<AExpr>) -> Option<Arc<String>> {
+    match expr_arena.get(node) {
+        AExpr::Column(name) | AExpr::Alias(_, name) => Some(name.clone()),
+        _ => None,
+    }
+}
+
+/// Cast a numeric literal to another numeric type. Floats are not cast to integers, and
+/// integers that don't fit the new type are not cast, as these casts differ at runtime.
+fn cast_literal(value: &LiteralValue, data_type: &DataType) -> Option<LiteralValue> {
+    use std::convert::TryFrom;
+    use LiteralValue::*;
+    let int = match value {
+        #[cfg(feature = "dtype-i8")]
+        Int8(v) => Some(*v as i128),
+        #[cfg(feature = "dtype-i16")]
+        Int16(v) => Some(*v as i128),
+        Int32(v) => Some(*v as i128),
+        Int64(v) => Some(*v as i128),
+        UInt8(v) => Some(*v as i128),
+        UInt16(v) => Some(*v as i128),
+        UInt32(v) => Some(*v as i128),
+        UInt64(v) => Some(*v as i128),
+        _ => None,
+    };
+    let float = match value {
+        Float32(v) => Some(*v as f64),
+        Float64(v) => Some(*v),
+        _ => int.map(|v| v as f64),
+    }?;
+    let cast = match data_type {
+        DataType::Float32 => Float32(float as f32),
+        DataType::Float64 => Float64(float),
+        #[cfg(feature = "dtype-i8")]
+        DataType::Int8 => Int8(i8::try_from(int?).ok()?),
+        #[cfg(feature = "dtype-i16")]
+        DataType::Int16 => Int16(i16::try_from(int?).ok()?),
+        DataType::Int32 => Int32(i32::try_from(int?).ok()?),
+        DataType::Int64 => Int64(i64::try_from(int?).ok()?),
+        DataType::UInt8 => UInt8(u8::try_from(int?).ok()?),
+        DataType::UInt16 => UInt16(u16::try_from(int?).ok()?),
+        DataType::UInt32 => UInt32(u32::try_from(int?).ok()?),
+        DataType::UInt64 => UInt64(u64::try_from(int?).ok()?),
+        _ => return None,
+    };
+    Some(cast)
+}
+
 pub(crate) struct SimplifyBooleanRule {}
 
 impl OptimizationRule for SimplifyBooleanRule {
@@ -153,7 +205,7 @@ impl OptimizationRule for SimplifyBooleanRule {
             {
                 Some(AExpr::Literal(LiteralValue::Boolean(false)))
             }
-            // false or x => x
+            // false OR x => x
             AExpr::BinaryExpr {
                 left,
                 op: Operator::Or,
@@ -165,56 +217,64 @@ impl OptimizationRule for SimplifyBooleanRule {
             {
                 Some(expr_arena.get(*right).clone())
             }
-            // x or false => x
+            // x OR false => x
             AExpr::BinaryExpr {
+                left,
                 op: Operator::Or,
                 right,
-                ..
             } if matches!(
                 expr_arena.get(*right),
                 AExpr::Literal(LiteralValue::Boolean(false))
             ) =>
             {
-                Some(expr_arena.get(*right).clone())
+                Some(expr_arena.get(*left).clone())
             }
-
-            // false OR x => x
+            // x OR true => true
             AExpr::BinaryExpr {
-                left,
                 op: Operator::Or,
                 right,
+                ..
             } if matches!(
-                expr_arena.get(*left),
-                AExpr::Literal(LiteralValue::Boolean(false))
+                expr_arena.get(*right),
+                AExpr::Literal(LiteralValue::Boolean(true))
             ) =>
             {
-                Some(expr_arena.get(*right).clone())
+                Some(AExpr::Literal(LiteralValue::Boolean(true)))
             }
-
             // true OR x => true
             AExpr::BinaryExpr {
                 op: Operator::Or,
-                right,
+                left,
                 ..
             } if matches!(
-                expr_arena.get(*right),
+                expr_arena.get(*left),
                 AExpr::Literal(LiteralValue::Boolean(true))
             ) =>
             {
-                Some(AExpr::Literal(LiteralValue::Boolean(false)))
+                Some(AExpr::Literal(LiteralValue::Boolean(true)))
             }
-
-            // x OR true => true
-            AExpr::BinaryExpr {
-                op: Operator::Or,
-                left,
-                ..
+            // when(true).then(x).otherwise(y) => x
+            AExpr::Ternary {
+                predicate, truthy, ..
             } if matches!(
-                expr_arena.get(*left),
+                expr_arena.get(*predicate),
                 AExpr::Literal(LiteralValue::Boolean(true))
             ) =>
             {
-                Some(AExpr::Literal(LiteralValue::Boolean(false)))
+                Some(expr_arena.get(*truthy).clone())
+            }
+            // when(false).then(x).otherwise(y) => y.alias(name of x)
+            AExpr::Ternary {
+                predicate,
+                truthy,
+                falsy,
+            } if matches!(
+                expr_arena.get(*predicate),
+                AExpr::Literal(LiteralValue::Boolean(false))
+            ) =>
+            {
+                let falsy = *falsy;
+                output_name(*truthy, expr_arena).map(|name| AExpr::Alias(falsy, name))
             }
 
             AExpr::Not(x) => {
@@ -286,22 +346,27 @@ impl OptimizationRule for SimplifyExprRule {
                 let right = expr_arena.get(*right);
 
                 match op {
-                    Operator::Plus => eval_binary_same_type!(left, +, right),
-                    Operator::Minus => eval_binary_same_type!(left, -, right),
-                    Operator::Multiply => eval_binary_same_type!(left, *, right),
-                    Operator::Divide => eval_binary_same_type!(left, /, right),
-                    Operator::Modulus => eval_binary_same_type!(left, %, right),
+                    Operator::Plus => eval_binary_same_type!(left, +, checked_add, right),
+                    Operator::Minus => eval_binary_same_type!(left, -, checked_sub, right),
+                    Operator::Multiply => eval_binary_same_type!(left, *, checked_mul, right),
+                    Operator::Divide => eval_binary_same_type!(left, /, checked_div, right),
+                    Operator::Modulus => eval_binary_same_type!(left, %, checked_rem, right),
                     Operator::Lt => eval_binary_bool_type!(left, <, right),
                     Operator::Gt => eval_binary_bool_type!(left, >, right),
                     Operator::Eq => eval_binary_bool_type!(left, ==, right),
                     Operator::NotEq => eval_binary_bool_type!(left, !=, right),
                     Operator::GtEq => eval_binary_bool_type!(left, >=, right),
-                    Operator::LtEq => eval_binary_bool_type!(left, >=, right),
+                    Operator::LtEq => eval_binary_bool_type!(left, <=, right),
                     Operator::And => eval_and(left, right),
                     Operator::Or => eval_or(left, right),
                     _ => None,
                 }
             }
+            // lit(x).cast(dtype) => lit(x as dtype)
+            AExpr::Cast { expr, data_type } => match expr_arena.get(*expr) {
+                AExpr::Literal(value) => cast_literal(value, data_type).map(AExpr::Literal),
+                _ => None,
+            },
             _ => None,
         }
     }
@@ -0,0 +1,93 @@
+use crate::logical_plan::optimizer::OptimizationRule;
+use crate::prelude::*;
+use polars_core::prelude::*;
+
+/// Push the end of a `Slice` into the scans below it, so that the scans stop reading after
+/// `offset + len` rows. The `Slice` node itself is kept to apply the offset.
+///
+/// The slice is only pushed through nodes that keep the rows and their order:
+/// projections of (aliased) columns, renames and unions. Scans that filter or aggregate
+/// while reading are left untouched.
+pub(crate) struct SlicePushDown {}
+
+/// Only plain (aliased) columns can be selected on fewer rows without changing the values.
+fn is_column_selection(node: Node, expr_arena: &Arena<AExpr>) -> bool {
+    match expr_arena.get(node) {
+        AExpr::Column(_) => true,
+        AExpr::Alias(input, _) => is_column_selection(*input, expr_arena),
+        _ => false,
+    }
+}
+
+/// Lower the number of rows a scan reads to `n_rows`.
+fn set_stop_after_n_rows(stop_after_n_rows: &mut Option<usize>, n_rows: usize) {
+    *stop_after_n_rows = Some(stop_after_n_rows.map_or(n_rows, |n| std::cmp::min(n, n_rows)));
+}
+
+impl SlicePushDown {
+    /// Let the scans under `node` read at most `n_rows`.
+    fn pushdown(
+        &self,
+        node: Node,
+        n_rows: usize,
+        lp_arena: &mut Arena<ALogicalPlan>,
+        expr_arena: &Arena<AExpr>,
+    ) {
+        use ALogicalPlan::*;
+        match lp_arena.get_mut(node) {
+            CsvScan {
+                stop_after_n_rows,
+                predicate: None,
+                aggregate,
+                ..
+            } if aggregate.is_empty() => set_stop_after_n_rows(stop_after_n_rows, n_rows),
+            #[cfg(feature = "parquet")]
+            ParquetScan {
+                stop_after_n_rows,
+                predicate: None,
+                aggregate,
+                ..
+            } if aggregate.is_empty() => set_stop_after_n_rows(stop_after_n_rows, n_rows),
+            #[cfg(feature = "ipc")]
+            IpcScan {
+                stop_after_n_rows,
+                predicate: None,
+                aggregate,
+                ..
+            } if aggregate.is_empty() => set_stop_after_n_rows(stop_after_n_rows, n_rows),
+            // every input of a union has to deliver at most `n_rows`
+            Union { inputs } => {
+                for input in inputs.clone() {
+                    self.pushdown(input, n_rows, lp_arena, expr_arena)
+                }
+            }
+            Projection { expr, input, .. }
+                if expr.iter().all(|e| is_column_selection(*e, expr_arena)) =>
+            {
+                let input = *input;
+                self.pushdown(input, n_rows, lp_arena, expr_arena)
+            }
+            Rename { input, .. } => {
+                let input = *input;
+                self.pushdown(input, n_rows, lp_arena, expr_arena)
+            }
+            _ => {}
+        }
+    }
+}
+
+impl OptimizationRule for SlicePushDown {
+    fn optimize_plan(
+        &mut self,
+        lp_arena: &mut Arena<ALogicalPlan>,
+        expr_arena: &mut Arena<AExpr>,
+        node: Node,
+    ) -> Option<ALogicalPlan> {
+        if let ALogicalPlan::Slice { input, offset, len } = lp_arena.get(node) {
+            let (input, n_rows) = (*input, offset.saturating_add(*len));
+            // the scans are updated in place, the slice itself doesn't change
+            self.pushdown(input, n_rows, lp_arena, expr_arena);
+        }
+        None
+    }
+}
@@ -2,8 +2,27 @@ use crate::logical_plan::Context;
 use crate::prelude::*;
 use polars_core::utils::get_supertype;
 
+/// Casts the inputs of binary expressions and of the branches of `when -> then -> otherwise`
+/// to their supertype, e.g. `col("i32") + lit(1.5)` becomes a `Float64` addition.
 pub struct TypeCoercionRule {}
 
+/// Cast `node` to `st` if it doesn't have that type already.
+fn cast_to_supertype(
+    expr_arena: &mut Arena<AExpr>,
+    node: Node,
+    dtype: &DataType,
+    st: &DataType,
+) -> Node {
+    if dtype == st {
+        node
+    } else {
+        expr_arena.add(AExpr::Cast {
+            expr: node,
+            data_type: st.clone(),
+        })
+    }
+}
+
 impl OptimizationRule for TypeCoercionRule {
     fn optimize_expr(
         &self,
@@ -24,23 +43,19 @@ impl OptimizationRule for TypeCoercionRule {
                 let falsy = expr_arena.get(falsy_node);
                 let type_true = truthy
                     .get_type(input_schema, Context::Other, expr_arena)
-                    .expect("could not dtype");
+                    .ok()?;
                 let type_false = falsy
                     .get_type(input_schema, Context::Other, expr_arena)
-                    .expect("could not dtype");
+                    .ok()?;
 
                 if type_true == type_false {
                     None
                 } else {
-                    let st = get_supertype(&type_true, &type_false).expect("supertype");
-                    let new_node_truthy = expr_arena.add(AExpr::Cast {
-                        expr: truthy_node,
-                        data_type: st.clone(),
-                    });
-                    let new_node_falsy = expr_arena.add(AExpr::Cast {
-                        expr: falsy_node,
-                        data_type: st,
-                    });
+                    let st = get_supertype(&type_true, &type_false).ok()?;
+                    let new_node_truthy =
+                        cast_to_supertype(expr_arena, truthy_node, &type_true, &st);
+                    let new_node_falsy =
+                        cast_to_supertype(expr_arena, falsy_node, &type_false, &st);
                     Some(AExpr::Ternary {
                         truthy: new_node_truthy,
                         falsy: new_node_falsy,
@@ -60,23 +75,18 @@ impl OptimizationRule for TypeCoercionRule {
 
                 let type_left = left
                     .get_type(input_schema, Context::Other, expr_arena)
-                    .expect("could not get dtype");
+                    .ok()?;
                 let type_right = right
                     .get_type(input_schema, Context::Other, expr_arena)
-                    .expect("could not get dtype");
+                    .ok()?;
                 if type_left == type_right {
                     None
                 } else {
-                    let st = get_supertype(&type_left, &type_right)
-                        .expect("could not find supertype of binary expr");
-                    let new_node_left = expr_arena.add(AExpr::Cast {
-                        expr: node_left,
-                        data_type: st.clone(),
-                    });
-                    let new_node_right = expr_arena.add(AExpr::Cast {
-                        expr: node_right,
-                        data_type: st,
-                    });
+                    // types without a supertype error when the expression is evaluated
+                    let st = get_supertype(&type_left, &type_right).ok()?;
+                    let new_node_left = cast_to_supertype(expr_arena, node_left, &type_left, &st);
+                    let new_node_right =
+                        cast_to_supertype(expr_arena, node_right, &type_right, &st);
 
                     Some(AExpr::BinaryExpr {
                         left: new_node_left,
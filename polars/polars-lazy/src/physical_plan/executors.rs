@@ -2,21 +2,28 @@ use super::*;
 use crate::logical_plan::{Context, FETCH_ROWS};
 use crate::utils::rename_aexpr_root_name;
 use itertools::Itertools;
-use polars_core::utils::{accumulate_dataframes_vertical, num_cpus, split_df};
-use polars_core::{frame::hash_join::JoinType, POOL};
+use polars_core::utils::{
+    accumulate_dataframes_vertical, estimated_row_bytes, n_splits, num_cpus, split_df,
+};
+use polars_core::{frame::hash_join::JoinArgs, functions::argsort_by, POOL};
+#[cfg(feature = "ipc")]
+use polars_io::predicates::StatsIndex;
 use polars_io::prelude::*;
 use polars_io::{csv::CsvEncoding, ScanAggregation};
 use rayon::prelude::*;
 use std::io::{Read, Seek};
 use std::mem;
+#[cfg(any(feature = "parquet", feature = "ipc"))]
+use std::sync::atomic::{AtomicU64, Ordering};
 
 trait FinishScanOps {
-    /// Read the file and create the DataFrame. Used from lazy execution
+    /// Read the file and create the DataFrame. Used from lazy execution.
+    /// Also returns the number of bytes of the file that were parsed.
     fn finish_with_scan_ops(
         self,
         predicate: Option<Arc<dyn PhysicalExpr>>,
         aggregate: Option<&[ScanAggregation]>,
-    ) -> Result<DataFrame>;
+    ) -> Result<(DataFrame, usize)>;
 }
 
 impl<'a, R: 'static + Read + Seek + Sync + Send> FinishScanOps for CsvReader<'a, R> {
@@ -24,17 +31,18 @@ impl<'a, R: 'static + Read + Seek + Sync + Send> FinishScanOps for CsvReader<'a,
         self,
         predicate: Option<Arc<dyn PhysicalExpr>>,
         aggregate: Option<&[ScanAggregation]>,
-    ) -> Result<DataFrame> {
+    ) -> Result<(DataFrame, usize)> {
         let predicate =
             predicate.map(|expr| Arc::new(PhysicalIoHelper::new(expr)) as Arc<dyn PhysicalIoExpr>);
 
         let rechunk = self.rechunk;
         let mut csv_reader = self.build_inner_reader()?;
         let df = csv_reader.as_df(predicate, aggregate)?;
-        match rechunk {
-            true => Ok(df.agg_chunks()),
-            false => Ok(df),
-        }
+        let df = match rechunk {
+            true => df.agg_chunks(),
+            false => df,
+        };
+        Ok((df, csv_reader.bytes_read()))
     }
 }
 
@@ -75,6 +83,14 @@ impl Executor for CacheExec {
         }
         Ok(df)
     }
+
+    fn describe(&self) -> String {
+        format!("CACHE {}", self.key)
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        vec![self.input.as_ref()]
+    }
 }
 
 #[cfg(feature = "parquet")]
@@ -86,10 +102,13 @@ pub struct ParquetExec {
     aggregate: Vec<ScanAggregation>,
     stop_after_n_rows: Option<usize>,
     cache: bool,
+    column_transformers: ColumnTransformers,
+    bytes_read: Option<u64>,
 }
 
 #[cfg(feature = "parquet")]
 impl ParquetExec {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         path: String,
         schema: SchemaRef,
@@ -98,6 +117,7 @@ impl ParquetExec {
         aggregate: Vec<ScanAggregation>,
         stop_after_n_rows: Option<usize>,
         cache: bool,
+        column_transformers: ColumnTransformers,
     ) -> Self {
         ParquetExec {
             path,
@@ -107,6 +127,8 @@ impl ParquetExec {
             aggregate,
             stop_after_n_rows,
             cache,
+            column_transformers,
+            bytes_read: None,
         }
     }
 }
@@ -114,11 +136,20 @@ impl ParquetExec {
 #[cfg(feature = "parquet")]
 impl Executor for ParquetExec {
     fn execute(&mut self, cache: &Cache) -> Result<DataFrame> {
+        // a scan can be limited by the slice pushdown, so the rows are part of the key
         let cache_key = match &self.predicate {
-            Some(predicate) => format!("{}{:?}", self.path, predicate.as_expression()),
-            None => self.path.to_string(),
+            Some(predicate) => format!(
+                "{}{:?}{:?}",
+                self.path,
+                self.stop_after_n_rows,
+                predicate.as_expression()
+            ),
+            None => format!("{}{:?}", self.path, self.stop_after_n_rows),
         };
-        if self.cache {
+        self.bytes_read = None;
+        // the transformers can't be part of the key, so a transformed scan isn't cached
+        let use_cache = self.cache && self.column_transformers.is_empty();
+        if use_cache {
             let guard = cache.lock().unwrap();
             // cache hit
             if let Some(df) = guard.get(&cache_key) {
@@ -151,15 +182,18 @@ impl Executor for ParquetExec {
             .clone()
             .map(|expr| Arc::new(PhysicalIoHelper::new(expr)) as Arc<dyn PhysicalIoExpr>);
 
-        let df = ParquetReader::new(file)
+        let count = Arc::new(AtomicU64::new(0));
+        let df = ParquetReader::new(CountingReader::new(file, count.clone()))
             .with_stop_after_n_rows(stop_after_n_rows)
+            .with_column_transformers(self.column_transformers.clone())
             .finish_with_scan_ops(
                 predicate,
                 aggregate,
                 projection.as_ref().map(|v| v.as_ref()),
             )?;
+        self.bytes_read = Some(count.load(Ordering::Relaxed));
 
-        if self.cache {
+        if use_cache {
             let mut guard = cache.lock().unwrap();
             guard.insert(cache_key, df.clone());
         }
@@ -169,6 +203,150 @@ impl Executor for ParquetExec {
 
         Ok(df)
     }
+
+    fn describe(&self) -> String {
+        match &self.predicate {
+            Some(predicate) => format!(
+                "PARQUET SCAN {}; SELECTION: {:?}",
+                self.path,
+                predicate.as_expression()
+            ),
+            None => format!("PARQUET SCAN {}", self.path),
+        }
+    }
+
+    fn bytes_read(&self) -> Option<u64> {
+        self.bytes_read
+    }
+}
+
+#[cfg(feature = "ipc")]
+pub struct IpcExec {
+    path: String,
+    schema: SchemaRef,
+    with_columns: Option<Vec<String>>,
+    predicate: Option<Arc<dyn PhysicalExpr>>,
+    aggregate: Vec<ScanAggregation>,
+    stop_after_n_rows: Option<usize>,
+    cache: bool,
+    column_transformers: ColumnTransformers,
+    bytes_read: Option<u64>,
+}
+
+#[cfg(feature = "ipc")]
+impl IpcExec {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        path: String,
+        schema: SchemaRef,
+        with_columns: Option<Vec<String>>,
+        predicate: Option<Arc<dyn PhysicalExpr>>,
+        aggregate: Vec<ScanAggregation>,
+        stop_after_n_rows: Option<usize>,
+        cache: bool,
+        column_transformers: ColumnTransformers,
+    ) -> Self {
+        IpcExec {
+            path,
+            schema,
+            with_columns,
+            predicate,
+            aggregate,
+            stop_after_n_rows,
+            cache,
+            column_transformers,
+            bytes_read: None,
+        }
+    }
+}
+
+#[cfg(feature = "ipc")]
+impl Executor for IpcExec {
+    fn execute(&mut self, cache: &Cache) -> Result<DataFrame> {
+        // a scan can be limited by the slice pushdown, so the rows are part of the key
+        let cache_key = match &self.predicate {
+            Some(predicate) => format!(
+                "{}{:?}{:?}",
+                self.path,
+                self.stop_after_n_rows,
+                predicate.as_expression()
+            ),
+            None => format!("{}{:?}", self.path, self.stop_after_n_rows),
+        };
+        self.bytes_read = None;
+        // the transformers can't be part of the key, so a transformed scan isn't cached
+        let use_cache = self.cache && self.column_transformers.is_empty();
+        if use_cache {
+            let guard = cache.lock().unwrap();
+            // cache hit
+            if let Some(df) = guard.get(&cache_key) {
+                return Ok(df.clone());
+            }
+            drop(guard);
+        }
+
+        // cache miss
+        let file = std::fs::File::open(&self.path).unwrap();
+
+        let with_columns = mem::take(&mut self.with_columns);
+        let schema = mem::take(&mut self.schema);
+
+        let projection: Option<Vec<_>> = with_columns.map(|with_columns| {
+            with_columns
+                .iter()
+                .map(|name| schema.column_with_name(name).unwrap().0)
+                .collect()
+        });
+
+        let stop_after_n_rows = set_n_rows(self.stop_after_n_rows);
+        let aggregate = if self.aggregate.is_empty() {
+            None
+        } else {
+            Some(self.aggregate.as_slice())
+        };
+        let predicate = self
+            .predicate
+            .clone()
+            .map(|expr| Arc::new(PhysicalIoHelper::new(expr)) as Arc<dyn PhysicalIoExpr>);
+
+        let stats_index = StatsIndex::of_file(&self.path);
+        let count = Arc::new(AtomicU64::new(0));
+        let df = IpcReader::new(CountingReader::new(file, count.clone()))
+            .with_stop_after_n_rows(stop_after_n_rows)
+            .with_column_transformers(self.column_transformers.clone())
+            .finish_with_scan_ops(
+                predicate,
+                aggregate,
+                projection.as_ref().map(|v| v.as_ref()),
+                Some(&stats_index),
+            )?;
+        self.bytes_read = Some(count.load(Ordering::Relaxed));
+
+        if use_cache {
+            let mut guard = cache.lock().unwrap();
+            guard.insert(cache_key, df.clone());
+        }
+        if std::env::var(POLARS_VERBOSE).is_ok() {
+            println!("ipc {:?} read", self.path);
+        }
+
+        Ok(df)
+    }
+
+    fn describe(&self) -> String {
+        match &self.predicate {
+            Some(predicate) => format!(
+                "IPC SCAN {}; SELECTION: {:?}",
+                self.path,
+                predicate.as_expression()
+            ),
+            None => format!("IPC SCAN {}", self.path),
+        }
+    }
+
+    fn bytes_read(&self) -> Option<u64> {
+        self.bytes_read
+    }
 }
 
 pub struct CsvExec {
@@ -183,6 +361,8 @@ pub struct CsvExec {
     predicate: Option<Arc<dyn PhysicalExpr>>,
     aggregate: Vec<ScanAggregation>,
     cache: bool,
+    column_transformers: ColumnTransformers,
+    bytes_read: Option<u64>,
 }
 
 impl CsvExec {
@@ -199,6 +379,7 @@ impl CsvExec {
         predicate: Option<Arc<dyn PhysicalExpr>>,
         aggregate: Vec<ScanAggregation>,
         cache: bool,
+        column_transformers: ColumnTransformers,
     ) -> Self {
         CsvExec {
             path,
@@ -212,17 +393,28 @@ impl CsvExec {
             predicate,
             aggregate,
             cache,
+            column_transformers,
+            bytes_read: None,
         }
     }
 }
 
 impl Executor for CsvExec {
     fn execute(&mut self, cache: &Cache) -> Result<DataFrame> {
+        // a scan can be limited by the slice pushdown, so the rows are part of the key
         let cache_key = match &self.predicate {
-            Some(predicate) => format!("{}{:?}", self.path, predicate.as_expression()),
-            None => self.path.to_string(),
+            Some(predicate) => format!(
+                "{}{:?}{:?}",
+                self.path,
+                self.stop_after_n_rows,
+                predicate.as_expression()
+            ),
+            None => format!("{}{:?}", self.path, self.stop_after_n_rows),
         };
-        if self.cache {
+        self.bytes_read = None;
+        // the transformers can't be part of the key, so a transformed scan isn't cached
+        let use_cache = self.cache && self.column_transformers.is_empty();
+        if use_cache {
             let guard = cache.lock().unwrap();
             // cache hit
             if let Some(df) = guard.get(&cache_key) {
@@ -254,7 +446,8 @@ impl Executor for CsvExec {
             .with_skip_rows(self.skip_rows)
             .with_stop_after_n_rows(stop_after_n_rows)
             .with_columns(with_columns)
-            .with_encoding(CsvEncoding::LossyUtf8);
+            .with_encoding(CsvEncoding::LossyUtf8)
+            .with_column_transformers(self.column_transformers.clone());
 
         let aggregate = if self.aggregate.is_empty() {
             None
@@ -262,9 +455,10 @@ impl Executor for CsvExec {
             Some(self.aggregate.as_slice())
         };
 
-        let df = reader.finish_with_scan_ops(self.predicate.clone(), aggregate)?;
+        let (df, bytes_read) = reader.finish_with_scan_ops(self.predicate.clone(), aggregate)?;
+        self.bytes_read = Some(bytes_read as u64);
 
-        if self.cache {
+        if use_cache {
             let mut guard = cache.lock().unwrap();
             guard.insert(cache_key, df.clone());
         }
@@ -274,6 +468,21 @@ impl Executor for CsvExec {
 
         Ok(df)
     }
+
+    fn describe(&self) -> String {
+        match &self.predicate {
+            Some(predicate) => format!(
+                "CSV SCAN {}; SELECTION: {:?}",
+                self.path,
+                predicate.as_expression()
+            ),
+            None => format!("CSV SCAN {}", self.path),
+        }
+    }
+
+    fn bytes_read(&self) -> Option<u64> {
+        self.bytes_read
+    }
 }
 
 pub struct FilterExec {
@@ -298,6 +507,14 @@ impl Executor for FilterExec {
         }
         Ok(df)
     }
+
+    fn describe(&self) -> String {
+        "FILTER".to_string()
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        vec![self.input.as_ref()]
+    }
 }
 
 pub struct DataFrameExec {
@@ -344,6 +561,10 @@ impl Executor for DataFrameExec {
             Ok(df)
         }
     }
+
+    fn describe(&self) -> String {
+        "DATAFRAME SCAN".to_string()
+    }
 }
 
 /// Take an input Executor (creates the input DataFrame)
@@ -387,7 +608,7 @@ pub(crate) fn evaluate_physical_expressions(
                 .into_iter()
                 .map(|series| {
                     if series.len() == 1 && height > 1 {
-                        series.expand_at_index(0, height)
+                        series.broadcast(height)
                     } else {
                         series
                     }
@@ -409,6 +630,14 @@ impl Executor for StandardExec {
         }
         df
     }
+
+    fn describe(&self) -> String {
+        self.operation.to_uppercase()
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        vec![self.input.as_ref()]
+    }
 }
 
 pub(crate) struct ExplodeExec {
@@ -421,18 +650,72 @@ impl Executor for ExplodeExec {
         let df = self.input.execute(cache)?;
         df.explode(&self.columns)
     }
+
+    fn describe(&self) -> String {
+        format!("EXPLODE {:?}", self.columns)
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        vec![self.input.as_ref()]
+    }
+}
+
+pub(crate) struct RenameExec {
+    pub(crate) input: Box<dyn Executor>,
+    pub(crate) existing: Arc<Vec<String>>,
+    pub(crate) new: Arc<Vec<String>>,
+}
+
+impl Executor for RenameExec {
+    fn execute(&mut self, cache: &Cache) -> Result<DataFrame> {
+        let mut df = self.input.execute(cache)?;
+        // columns that were pruned by projection pushdown are not in the input
+        df.rename_with(|name| match self.existing.iter().position(|e| e == name) {
+            Some(idx) => self.new[idx].clone(),
+            None => name.to_string(),
+        })?;
+        Ok(df)
+    }
+
+    fn describe(&self) -> String {
+        format!("RENAME {:?} TO {:?}", self.existing, self.new)
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        vec![self.input.as_ref()]
+    }
 }
 
 pub(crate) struct SortExec {
     pub(crate) input: Box<dyn Executor>,
-    pub(crate) by_column: String,
-    pub(crate) reverse: bool,
+    pub(crate) by: Vec<Arc<dyn PhysicalExpr>>,
+    pub(crate) reverse: Vec<bool>,
 }
 
 impl Executor for SortExec {
     fn execute(&mut self, cache: &Cache) -> Result<DataFrame> {
         let df = self.input.execute(cache)?;
-        df.sort(&self.by_column, self.reverse)
+        let by = self
+            .by
+            .iter()
+            .map(|e| e.evaluate(&df))
+            .collect::<Result<Vec<_>>>()?;
+        // a single column is sorted in the DataFrame, which also flags the column as sorted
+        if let ([s], [reverse]) = (by.as_slice(), self.reverse.as_slice()) {
+            if df.column(s.name()).map_or(false, |col| col.series_equal(s)) {
+                return df.sort(s.name(), *reverse);
+            }
+        }
+        let idx = argsort_by(&by, &self.reverse)?;
+        Ok(df.take(&idx))
+    }
+
+    fn describe(&self) -> String {
+        format!("SORT; REVERSE: {:?}", self.reverse)
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        vec![self.input.as_ref()]
     }
 }
 
@@ -450,6 +733,242 @@ impl Executor for DropDuplicatesExec {
             self.subset.as_ref().map(|v| v.as_ref()),
         )
     }
+
+    fn describe(&self) -> String {
+        "DISTINCT".to_string()
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        vec![self.input.as_ref()]
+    }
+}
+
+pub(crate) struct SetOpExec {
+    pub(crate) input_left: Box<dyn Executor>,
+    pub(crate) input_right: Box<dyn Executor>,
+    pub(crate) op: SetOperation,
+}
+
+impl Executor for SetOpExec {
+    fn execute(&mut self, cache: &Cache) -> Result<DataFrame> {
+        let df_left = self.input_left.execute(cache)?;
+        let df_right = self.input_right.execute(cache)?;
+        df_left.set_operation(&df_right, self.op)
+    }
+
+    fn describe(&self) -> String {
+        format!("SET OPERATION {:?}", self.op)
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        vec![self.input_left.as_ref(), self.input_right.as_ref()]
+    }
+}
+
+pub(crate) struct UnionExec {
+    pub(crate) inputs: Vec<Box<dyn Executor>>,
+}
+
+impl Executor for UnionExec {
+    fn execute(&mut self, cache: &Cache) -> Result<DataFrame> {
+        let dfs = self
+            .inputs
+            .iter_mut()
+            .map(|input| input.execute(cache))
+            .collect::<Result<Vec<_>>>()?;
+        accumulate_dataframes_vertical(dfs)
+    }
+
+    fn describe(&self) -> String {
+        "UNION".to_string()
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        self.inputs.iter().map(|input| input.as_ref()).collect()
+    }
+}
+
+/// A comparison `left_on op right_on` between a column of the left and a column of the right
+/// table of a join where. These are used to prune the candidate pairs of the nested loop.
+pub(crate) struct RangeBound {
+    pub(crate) left_on: String,
+    pub(crate) op: Operator,
+    pub(crate) right_on: String,
+}
+
+/// Maximum number of candidate pairs that are materialized before they are filtered.
+const JOIN_WHERE_BATCH_SIZE: usize = 1 << 16;
+
+pub(crate) struct JoinWhereExec {
+    pub(crate) input_left: Box<dyn Executor>,
+    pub(crate) input_right: Box<dyn Executor>,
+    pub(crate) predicates: Vec<Arc<dyn PhysicalExpr>>,
+    /// All bounds are on the same right column.
+    pub(crate) bounds: Vec<RangeBound>,
+    pub(crate) suffix: Option<String>,
+}
+
+fn to_float64(s: &Series) -> Result<Float64Chunked> {
+    let s = match s.dtype() {
+        DataType::Date32 => s.cast::<Int32Type>()?.cast::<Float64Type>()?,
+        DataType::Date64 => s.cast::<Int64Type>()?.cast::<Float64Type>()?,
+        _ => s.cast::<Float64Type>()?,
+    };
+    Ok(s.f64()?.clone())
+}
+
+/// Index of the first value in `sorted` that is not smaller than `v` (`or_equal == false`) or
+/// not smaller or equal to `v` (`or_equal == true`).
+fn search_sorted(sorted: &[(f64, IdxSize)], v: f64, or_equal: bool) -> usize {
+    sorted
+        .binary_search_by(|(x, _)| {
+            if *x < v || (or_equal && *x == v) {
+                std::cmp::Ordering::Less
+            } else {
+                std::cmp::Ordering::Greater
+            }
+        })
+        .unwrap_err()
+}
+
+impl JoinWhereExec {
+    /// Materialize the candidate pairs and keep the rows for which all predicates hold.
+    fn filter_pairs(
+        &self,
+        df_left: &DataFrame,
+        df_right: &DataFrame,
+        pairs: &mut Vec<(IdxSize, IdxSize)>,
+    ) -> Result<DataFrame> {
+        let mut df = df_left.take_iter(pairs.iter().map(|(left, _)| *left as usize));
+        let right = df_right.take_iter(pairs.iter().map(|(_, right)| *right as usize));
+        pairs.clear();
+        df.hstack_mut(right.get_columns())?;
+
+        let mut mask: Option<BooleanChunked> = None;
+        for predicate in &self.predicates {
+            let s = predicate.evaluate(&df)?;
+            let ca = s.bool()?;
+            mask = Some(match mask {
+                Some(mask) => &mask & ca,
+                None => ca.clone(),
+            });
+        }
+        match mask {
+            Some(mask) => df.filter(&mask),
+            None => Ok(df),
+        }
+    }
+}
+
+impl Executor for JoinWhereExec {
+    fn execute(&mut self, cache: &Cache) -> Result<DataFrame> {
+        let df_left = self.input_left.execute(cache)?;
+        let mut df_right = self.input_right.execute(cache)?;
+
+        // sort the right column of the bounds once, so that every left row only visits the
+        // right rows in its range. A column that is flagged as sorted, e.g. by a scan that
+        // indexed it, is already in order.
+        let sorted_right = match self.bounds.first() {
+            Some(bound) => {
+                let s = df_right.column(&bound.right_on)?;
+                let is_sorted = s.sorted_flag() == IsSorted::Ascending;
+                let ca = to_float64(s)?;
+                let mut sorted = ca
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(idx, opt_v)| match opt_v {
+                        Some(v) if !v.is_nan() => Some((v, idx as IdxSize)),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                if !is_sorted {
+                    sorted.sort_unstable_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+                }
+                Some(sorted)
+            }
+            None => None,
+        };
+        let left_keys = self
+            .bounds
+            .iter()
+            .map(|bound| to_float64(df_left.column(&bound.left_on)?))
+            .collect::<Result<Vec<_>>>()?;
+
+        let suffix = self.suffix.as_deref().unwrap_or("_right");
+        let duplicates = df_right
+            .get_column_names()
+            .into_iter()
+            .filter(|name| df_left.column(name).is_ok())
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+        for name in duplicates {
+            df_right.rename(&name, &format!("{}{}", name, suffix))?;
+        }
+
+        let n_right = df_right.height();
+        let mut pairs = Vec::with_capacity(JOIN_WHERE_BATCH_SIZE);
+        let mut dfs = vec![];
+        for left_idx in 0..df_left.height() {
+            match &sorted_right {
+                Some(sorted) => {
+                    // The ranges include equal values for every operator, so that precision
+                    // lost in the cast to f64 never prunes a match. The predicates decide.
+                    let (mut lo, mut hi) = (0, sorted.len());
+                    for (bound, keys) in self.bounds.iter().zip(&left_keys) {
+                        let v = match keys.get(left_idx) {
+                            Some(v) if !v.is_nan() => v,
+                            // comparisons with null or NaN never hold
+                            _ => {
+                                hi = 0;
+                                break;
+                            }
+                        };
+                        match bound.op {
+                            Operator::Eq => {
+                                lo = std::cmp::max(lo, search_sorted(sorted, v, false));
+                                hi = std::cmp::min(hi, search_sorted(sorted, v, true));
+                            }
+                            Operator::Lt | Operator::LtEq => {
+                                lo = std::cmp::max(lo, search_sorted(sorted, v, false))
+                            }
+                            _ => hi = std::cmp::min(hi, search_sorted(sorted, v, true)),
+                        }
+                    }
+                    if lo < hi {
+                        let start = pairs.len();
+                        pairs.extend(
+                            sorted[lo..hi]
+                                .iter()
+                                .map(|(_, right_idx)| (left_idx as IdxSize, *right_idx)),
+                        );
+                        // keep the row order of the right table
+                        pairs[start..].sort_unstable_by_key(|(_, right_idx)| *right_idx);
+                    }
+                }
+                None => pairs.extend(
+                    (0..n_right).map(|right_idx| (left_idx as IdxSize, right_idx as IdxSize)),
+                ),
+            }
+            if pairs.len() >= JOIN_WHERE_BATCH_SIZE {
+                dfs.push(self.filter_pairs(&df_left, &df_right, &mut pairs)?);
+            }
+        }
+        if !pairs.is_empty() || dfs.is_empty() {
+            dfs.push(self.filter_pairs(&df_left, &df_right, &mut pairs)?);
+        }
+        if std::env::var(POLARS_VERBOSE).is_ok() {
+            println!("join where with {} range bound(s)", self.bounds.len());
+        }
+        accumulate_dataframes_vertical(dfs)
+    }
+
+    fn describe(&self) -> String {
+        format!("JOIN WHERE; RANGE BOUNDS: {}", self.bounds.len())
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        vec![self.input_left.as_ref(), self.input_right.as_ref()]
+    }
 }
 
 /// Take an input Executor and a multiple expressions
@@ -458,6 +977,9 @@ pub struct GroupByExec {
     keys: Vec<Arc<dyn PhysicalExpr>>,
     aggs: Vec<Arc<dyn PhysicalExpr>>,
     apply: Option<Arc<dyn DataFrameUdf>>,
+    /// The output schema if aggregations are fused. The columns of a fused aggregation are
+    /// output together and are moved back to their position in the schema.
+    fused_schema: Option<SchemaRef>,
 }
 
 impl GroupByExec {
@@ -466,12 +988,14 @@ impl GroupByExec {
         keys: Vec<Arc<dyn PhysicalExpr>>,
         aggs: Vec<Arc<dyn PhysicalExpr>>,
         apply: Option<Arc<dyn DataFrameUdf>>,
+        fused_schema: Option<SchemaRef>,
     ) -> Self {
         Self {
             input,
             keys,
             aggs,
             apply,
+            fused_schema,
         }
     }
 }
@@ -481,6 +1005,7 @@ fn groupby_helper(
     keys: Vec<Series>,
     aggs: &[Arc<dyn PhysicalExpr>],
     apply: Option<&Arc<dyn DataFrameUdf>>,
+    fused_schema: Option<&Schema>,
 ) -> Result<DataFrame> {
     let gb = df.groupby_with_series(keys, true)?;
     if let Some(f) = apply {
@@ -498,22 +1023,28 @@ fn groupby_helper(
             .iter()
             .map(|expr| {
                 let agg_expr = expr.as_agg_expr()?;
-                let opt_agg = agg_expr.evaluate(&df, groups)?;
-                if let Some(agg) = &opt_agg {
+                let aggs = agg_expr.evaluate_multiple(&df, groups)?;
+                for agg in &aggs {
                     if agg.len() != groups.len() {
-                        panic!(
-                            "returned aggregation is a different length: {} than the group lengths: {}",
-                            agg.len(),
-                            groups.len()
-                        )
+                        return Err(PolarsError::ShapeMisMatch(
+                            format!(
+                                "returned aggregation is a different length: {} than the group lengths: {}",
+                                agg.len(),
+                                groups.len()
+                            )
+                            .into(),
+                        ));
                     }
-                };
-                Ok(opt_agg)
+                }
+                Ok(aggs)
             })
             .collect::<Result<Vec<_>>>()
     })?;
 
     columns.extend(agg_columns.into_iter().flatten());
+    if let Some(schema) = fused_schema {
+        columns.sort_by_key(|s| schema.index_of(s.name()).unwrap_or(usize::MAX));
+    }
 
     let df = DataFrame::new_no_checks(columns);
     Ok(df)
@@ -527,7 +1058,21 @@ impl Executor for GroupByExec {
             .iter()
             .map(|e| e.evaluate(&df))
             .collect::<Result<_>>()?;
-        groupby_helper(df, keys, &self.aggs, self.apply.as_ref())
+        groupby_helper(
+            df,
+            keys,
+            &self.aggs,
+            self.apply.as_ref(),
+            self.fused_schema.as_deref(),
+        )
+    }
+
+    fn describe(&self) -> String {
+        "HASH GROUPBY".to_string()
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        vec![self.input.as_ref()]
     }
 }
 
@@ -578,7 +1123,7 @@ impl Executor for PartitionGroupByExec {
             let frac = cat_map.len() as f32 / ca.len() as f32;
             // TODO! proper benchmark which boundary should be chosen.
             if frac > 0.3 {
-                return groupby_helper(original_df, keys, &self.phys_aggs, None);
+                return groupby_helper(original_df, keys, &self.phys_aggs, None, None);
             }
         }
         let mut expr_arena = Arena::with_capacity(64);
@@ -601,7 +1146,7 @@ impl Executor for PartitionGroupByExec {
             })
             .collect::<Result<Vec<_>>>()?;
 
-        let planner = DefaultPlanner {};
+        let planner = DefaultPlanner::default();
         let outer_phys_aggs = aggs_and_names
             .iter()
             .map(|(e, _)| planner.create_physical_expr(*e, Context::Aggregation, &mut expr_arena))
@@ -610,7 +1155,12 @@ impl Executor for PartitionGroupByExec {
         let n_threads = num_cpus::get();
         // We do a partitioned groupby. Meaning that we first do the groupby operation arbitrarily
         // splitted on several threads. Than the final result we apply the same groupby again.
-        let dfs = split_df(&original_df, n_threads)?;
+        let n_parts = n_splits(
+            original_df.height(),
+            estimated_row_bytes(&original_df.schema()),
+            n_threads,
+        );
+        let dfs = split_df(&original_df, n_parts)?;
 
         let dfs = POOL.install(|| {
             dfs.into_par_iter()
@@ -632,11 +1182,14 @@ impl Executor for PartitionGroupByExec {
                             let opt_agg = agg_expr.evaluate_partitioned(&df, groups)?;
                             if let Some(agg) = &opt_agg {
                                 if agg[0].len() != groups.len() {
-                                    panic!(
-                                        "returned aggregation is a different length: {} than the group lengths: {}",
-                                        agg.len(),
-                                        groups.len()
-                                    )
+                                    return Err(PolarsError::ShapeMisMatch(
+                                        format!(
+                                            "returned aggregation is a different length: {} than the group lengths: {}",
+                                            agg[0].len(),
+                                            groups.len()
+                                        )
+                                        .into(),
+                                    ));
                                 }
                             };
                             Ok(opt_agg)
@@ -687,33 +1240,41 @@ impl Executor for PartitionGroupByExec {
         let df = DataFrame::new_no_checks(columns);
         Ok(df)
     }
+
+    fn describe(&self) -> String {
+        "PARTITIONED GROUPBY".to_string()
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        vec![self.input.as_ref()]
+    }
 }
 
 pub struct JoinExec {
     input_left: Option<Box<dyn Executor>>,
     input_right: Option<Box<dyn Executor>>,
-    how: JoinType,
     left_on: Vec<Arc<dyn PhysicalExpr>>,
     right_on: Vec<Arc<dyn PhysicalExpr>>,
     parallel: bool,
+    args: JoinArgs,
 }
 
 impl JoinExec {
     pub(crate) fn new(
         input_left: Box<dyn Executor>,
         input_right: Box<dyn Executor>,
-        how: JoinType,
         left_on: Vec<Arc<dyn PhysicalExpr>>,
         right_on: Vec<Arc<dyn PhysicalExpr>>,
         parallel: bool,
+        args: JoinArgs,
     ) -> Self {
         JoinExec {
             input_left: Some(input_left),
             input_right: Some(input_right),
-            how,
             left_on,
             right_on,
             parallel,
+            args,
         }
     }
 }
@@ -758,11 +1319,35 @@ impl Executor for JoinExec {
             .map(|e| e.evaluate(&df_right).map(|s| s.name().to_string()))
             .collect::<Result<Vec<_>>>()?;
 
-        let df = df_left.join(&df_right, &left_names, &right_names, self.how);
         if std::env::var(POLARS_VERBOSE).is_ok() {
-            println!("{:?} join dataframes finished", self.how);
-        };
-        df
+            let (df, stats) = df_left.join_with_statistics(
+                &df_right,
+                &left_names,
+                &right_names,
+                self.args.clone(),
+            )?;
+            println!("{:?} join statistics: {}", self.args.how, stats);
+            println!("{:?} join dataframes finished", self.args.how);
+            Ok(df)
+        } else {
+            df_left.join(&df_right, &left_names, &right_names, self.args.clone())
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "{:?} HASH JOIN; STRATEGY: {:?}; PARALLEL: {}",
+            self.args.how, self.args.strategy, self.parallel
+        )
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        // the inputs are taken when the join is executed
+        self.input_left
+            .iter()
+            .chain(self.input_right.iter())
+            .map(|input| input.as_ref())
+            .collect()
     }
 }
 pub struct StackExec {
@@ -783,9 +1368,9 @@ impl Executor for StackExec {
 
         let res: Result<_> = self.expr.iter().try_for_each(|expr| {
             let s = expr.evaluate(&df).map(|series| {
-                // literal series. Should be whole column size
+                // literal series. Broadcast to the whole column size without materializing it
                 if series.len() == 1 && height > 1 {
-                    series.expand_at_index(0, height)
+                    series.broadcast(height)
                 } else {
                     series
                 }
@@ -801,6 +1386,14 @@ impl Executor for StackExec {
         let _ = res?;
         Ok(df)
     }
+
+    fn describe(&self) -> String {
+        "WITH COLUMNS".to_string()
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        vec![self.input.as_ref()]
+    }
 }
 
 pub struct SliceExec {
@@ -814,17 +1407,38 @@ impl Executor for SliceExec {
         let df = self.input.execute(cache)?;
         df.slice(self.offset, self.len)
     }
+
+    fn describe(&self) -> String {
+        format!("SLICE; OFFSET: {}, LEN: {}", self.offset, self.len)
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        vec![self.input.as_ref()]
+    }
 }
 pub struct MeltExec {
     pub input: Box<dyn Executor>,
     pub id_vars: Arc<Vec<String>>,
     pub value_vars: Arc<Vec<String>>,
+    pub maintain_order: bool,
 }
 
 impl Executor for MeltExec {
     fn execute(&mut self, cache: &Cache) -> Result<DataFrame> {
         let df = self.input.execute(cache)?;
-        df.melt(&self.id_vars.as_slice(), &self.value_vars.as_slice())
+        df.melt(
+            &self.id_vars.as_slice(),
+            &self.value_vars.as_slice(),
+            self.maintain_order,
+        )
+    }
+
+    fn describe(&self) -> String {
+        "MELT".to_string()
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        vec![self.input.as_ref()]
     }
 }
 
@@ -838,4 +1452,542 @@ impl Executor for UdfExec {
         let df = self.input.execute(cache)?;
         self.function.call_udf(df)
     }
+
+    fn describe(&self) -> String {
+        "UDF".to_string()
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        vec![self.input.as_ref()]
+    }
+}
+
+/// The batch that is processed by the executors of a query that is executed batch by batch.
+pub(crate) type BatchSlot = Arc<Mutex<Option<DataFrame>>>;
+
+/// Yields the batch in the slot. The scan of a query that is executed batch by batch.
+pub(crate) struct BatchExec {
+    pub(crate) slot: BatchSlot,
+}
+
+impl Executor for BatchExec {
+    fn execute(&mut self, _: &Cache) -> Result<DataFrame> {
+        Ok(self
+            .slot
+            .lock()
+            .unwrap()
+            .take()
+            .expect("batch should be set before the query is executed"))
+    }
+
+    fn describe(&self) -> String {
+        "BATCH SCAN".to_string()
+    }
+}
+
+fn filter_by_predicate(df: DataFrame, predicate: &dyn PhysicalExpr) -> Result<DataFrame> {
+    let s = predicate.evaluate(&df)?;
+    let mask = s
+        .bool()
+        .map_err(|_| PolarsError::Other("filter predicate was not of type boolean".into()))?;
+    df.filter(mask)
+}
+
+/// The scan of a query that is executed batch by batch, see [SinkExec].
+pub(crate) enum BatchSource {
+    DataFrame {
+        df: Arc<DataFrame>,
+        projection: Option<Vec<Arc<dyn PhysicalExpr>>>,
+        selection: Option<Arc<dyn PhysicalExpr>>,
+    },
+    #[cfg(feature = "parquet")]
+    Parquet {
+        path: String,
+        schema: SchemaRef,
+        with_columns: Option<Vec<String>>,
+        predicate: Option<Arc<dyn PhysicalExpr>>,
+        stop_after_n_rows: Option<usize>,
+        column_transformers: ColumnTransformers,
+    },
+    Csv {
+        path: String,
+        schema: SchemaRef,
+        has_header: bool,
+        delimiter: u8,
+        ignore_errors: bool,
+        skip_rows: usize,
+        stop_after_n_rows: Option<usize>,
+        with_columns: Option<Vec<String>>,
+        predicate: Option<Arc<dyn PhysicalExpr>>,
+        column_transformers: ColumnTransformers,
+    },
+    #[cfg(feature = "ipc")]
+    Ipc {
+        path: String,
+        schema: SchemaRef,
+        with_columns: Option<Vec<String>>,
+        predicate: Option<Arc<dyn PhysicalExpr>>,
+        stop_after_n_rows: Option<usize>,
+        column_transformers: ColumnTransformers,
+    },
+}
+
+/// The indexes of the projected columns in the schema of a file.
+#[cfg(any(feature = "parquet", feature = "ipc"))]
+fn projection_indexes(schema: &Schema, with_columns: &Option<Vec<String>>) -> Option<Vec<usize>> {
+    with_columns.as_ref().map(|with_columns| {
+        with_columns
+            .iter()
+            .map(|name| schema.column_with_name(name).unwrap().0)
+            .collect()
+    })
+}
+
+/// A DataFrame without rows with the given fields.
+fn empty_batch<'a>(fields: impl Iterator<Item = &'a Field>) -> Result<DataFrame> {
+    let columns = fields
+        .map(|field| Series::new_empty(field.name(), field.data_type()))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(DataFrame::new_no_checks(columns))
+}
+
+/// Apply the predicate to the batches of a file. If the file has no batches, a single empty
+/// batch is returned, as an empty file still has a schema that must be written.
+fn filter_batches<'a, I, F>(
+    batches: I,
+    empty: F,
+    predicate: &'a Option<Arc<dyn PhysicalExpr>>,
+) -> Result<Box<dyn Iterator<Item = Result<DataFrame>> + 'a>>
+where
+    I: Iterator<Item = Result<DataFrame>> + 'a,
+    F: FnOnce() -> Result<DataFrame>,
+{
+    let filter = move |batch: Result<DataFrame>| match predicate {
+        Some(predicate) => filter_by_predicate(batch?, predicate.as_ref()),
+        None => batch,
+    };
+    let mut batches = batches.peekable();
+    if batches.peek().is_some() {
+        Ok(Box::new(batches.map(filter)))
+    } else {
+        Ok(Box::new(std::iter::once(empty()).map(filter)))
+    }
+}
+
+impl BatchSource {
+    /// Scan the source in DataFrames of at most `batch_size` rows, with the projection and
+    /// predicate of the scan applied. There is at least one, possibly empty, batch.
+    fn batches(
+        &self,
+        batch_size: usize,
+    ) -> Result<Box<dyn Iterator<Item = Result<DataFrame>> + '_>> {
+        match self {
+            BatchSource::DataFrame {
+                df,
+                projection,
+                selection,
+            } => {
+                let n_batches = std::cmp::max((df.height() + batch_size - 1) / batch_size, 1);
+                Ok(Box::new((0..n_batches).map(move |i| {
+                    let offset = i * batch_size;
+                    let len = std::cmp::min(batch_size, df.height() - offset);
+                    let mut batch = df.slice(offset, len)?;
+                    if let Some(projection) = projection {
+                        batch = evaluate_physical_expressions(&batch, projection)?;
+                    }
+                    if let Some(selection) = selection {
+                        batch = filter_by_predicate(batch, selection.as_ref())?;
+                    }
+                    Ok(batch)
+                })))
+            }
+            #[cfg(feature = "parquet")]
+            BatchSource::Parquet {
+                path,
+                schema,
+                with_columns,
+                predicate,
+                stop_after_n_rows,
+                column_transformers,
+            } => {
+                let file = std::fs::File::open(path)?;
+                let projection = projection_indexes(schema, with_columns);
+                let batches = ParquetReader::new(file)
+                    .with_stop_after_n_rows(set_n_rows(*stop_after_n_rows))
+                    .with_column_transformers(column_transformers.clone())
+                    .batched(batch_size, projection.as_deref())?;
+                let fields = schema.fields();
+                filter_batches(
+                    batches,
+                    || match &projection {
+                        Some(projection) => empty_batch(projection.iter().map(|&i| &fields[i])),
+                        None => empty_batch(fields.iter()),
+                    },
+                    predicate,
+                )
+            }
+            BatchSource::Csv {
+                path,
+                schema,
+                has_header,
+                delimiter,
+                ignore_errors,
+                skip_rows,
+                stop_after_n_rows,
+                with_columns,
+                predicate,
+                column_transformers,
+            } => {
+                let with_columns = with_columns
+                    .clone()
+                    .filter(|with_columns| !with_columns.is_empty());
+                let batches = CsvReader::from_path(path)?
+                    .has_header(*has_header)
+                    .with_schema(schema.clone())
+                    .with_delimiter(*delimiter)
+                    .with_ignore_parser_errors(*ignore_errors)
+                    .with_skip_rows(*skip_rows)
+                    .with_stop_after_n_rows(set_n_rows(*stop_after_n_rows))
+                    .with_columns(with_columns)
+                    .with_encoding(CsvEncoding::LossyUtf8)
+                    .with_column_transformers(column_transformers.clone())
+                    .batched(batch_size)?;
+                let batch_schema = batches.schema();
+                filter_batches(
+                    batches,
+                    || empty_batch(batch_schema.fields().iter()),
+                    predicate,
+                )
+            }
+            #[cfg(feature = "ipc")]
+            BatchSource::Ipc {
+                path,
+                schema,
+                with_columns,
+                predicate,
+                stop_after_n_rows,
+                column_transformers,
+            } => {
+                let file = std::fs::File::open(path)?;
+                let projection = projection_indexes(schema, with_columns);
+                let batches = IpcReader::new(file)
+                    .with_stop_after_n_rows(set_n_rows(*stop_after_n_rows))
+                    .with_column_transformers(column_transformers.clone())
+                    .batched(batch_size, projection.as_deref())?;
+                let fields = schema.fields();
+                filter_batches(
+                    batches,
+                    || match &projection {
+                        Some(projection) => empty_batch(projection.iter().map(|&i| &fields[i])),
+                        None => empty_batch(fields.iter()),
+                    },
+                    predicate,
+                )
+            }
+        }
+    }
+}
+
+enum SinkWriter {
+    Csv {
+        file: std::fs::File,
+        has_headers: bool,
+        column_transformers: ColumnTransformers,
+    },
+    #[cfg(feature = "parquet")]
+    Parquet(BatchedParquetWriter<std::fs::File>),
+}
+
+impl SinkWriter {
+    fn new(
+        file: std::fs::File,
+        file_type: SinkFileType,
+        column_transformers: ColumnTransformers,
+    ) -> Self {
+        match file_type {
+            SinkFileType::Csv => SinkWriter::Csv {
+                file,
+                has_headers: true,
+                column_transformers,
+            },
+            #[cfg(feature = "parquet")]
+            SinkFileType::Parquet => SinkWriter::Parquet(
+                ParquetWriter::new(file)
+                    .with_column_transformers(column_transformers)
+                    .batched(),
+            ),
+        }
+    }
+
+    fn write(&mut self, df: &mut DataFrame) -> Result<()> {
+        match self {
+            SinkWriter::Csv {
+                file,
+                has_headers,
+                column_transformers,
+            } => {
+                CsvWriter::new(file)
+                    .has_headers(*has_headers)
+                    .with_column_transformers(column_transformers.clone())
+                    .finish(df)?;
+                // only the first batch has the header
+                *has_headers = false;
+                Ok(())
+            }
+            #[cfg(feature = "parquet")]
+            SinkWriter::Parquet(writer) => writer.write_batch(df),
+        }
+    }
+
+    fn finish(self) -> Result<()> {
+        match self {
+            SinkWriter::Csv { .. } => Ok(()),
+            #[cfg(feature = "parquet")]
+            SinkWriter::Parquet(writer) => writer.finish(),
+        }
+    }
+}
+
+/// Rows per batch of a query that is executed batch by batch.
+const SINK_BATCH_SIZE: usize = 64 * 1024;
+
+/// Writes the output of its input to a file and returns an empty DataFrame. If there is a batch
+/// source, the input is executed and written once per batch of the source, so the full output
+/// is never in memory.
+pub(crate) struct SinkExec {
+    pub(crate) input: Box<dyn Executor>,
+    pub(crate) source: Option<(BatchSource, BatchSlot)>,
+    pub(crate) path: String,
+    pub(crate) file_type: SinkFileType,
+    pub(crate) column_transformers: ColumnTransformers,
+}
+
+impl Executor for SinkExec {
+    fn execute(&mut self, cache: &Cache) -> Result<DataFrame> {
+        let file = std::fs::File::create(&self.path)?;
+        let mut writer = SinkWriter::new(file, self.file_type, self.column_transformers.clone());
+        match &self.source {
+            Some((source, slot)) => {
+                for batch in source.batches(SINK_BATCH_SIZE)? {
+                    *slot.lock().unwrap() = Some(batch?);
+                    let mut df = self.input.execute(cache)?;
+                    writer.write(&mut df)?;
+                }
+            }
+            None => {
+                let mut df = self.input.execute(cache)?;
+                writer.write(&mut df)?;
+            }
+        }
+        writer.finish()?;
+        if std::env::var(POLARS_VERBOSE).is_ok() {
+            println!("query written to {:?}", self.path);
+        }
+        Ok(DataFrame::new_no_checks(vec![]))
+    }
+
+    fn describe(&self) -> String {
+        match self.source {
+            Some(_) => format!("BATCHED SINK {}", self.path),
+            None => format!("SINK {}", self.path),
+        }
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        vec![self.input.as_ref()]
+    }
+}
+
+/// Wraps a node of the physical plan and stops the query if it is cancelled.
+pub(crate) struct CancellableExec {
+    pub(crate) input: Box<dyn Executor>,
+    pub(crate) token: CancellationToken,
+}
+
+impl Executor for CancellableExec {
+    fn execute(&mut self, cache: &Cache) -> Result<DataFrame> {
+        self.token.check()?;
+        let df = self.input.execute(cache)?;
+        self.token.check()?;
+        Ok(df)
+    }
+
+    fn describe(&self) -> String {
+        self.input.describe()
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        self.input.inputs()
+    }
+
+    fn bytes_read(&self) -> Option<u64> {
+        self.input.bytes_read()
+    }
+}
+
+/// Wraps a node of the physical plan and reports its progress.
+pub(crate) struct ProgressExec {
+    pub(crate) input: Box<dyn Executor>,
+    pub(crate) node: &'static str,
+    /// Set if this node scans a source.
+    pub(crate) source: Option<String>,
+    pub(crate) callback: Arc<dyn ProgressCallback>,
+}
+
+impl Executor for ProgressExec {
+    fn execute(&mut self, cache: &Cache) -> Result<DataFrame> {
+        let df = self.input.execute(cache)?;
+        let rows = df.height();
+        if let Some(source) = &self.source {
+            self.callback.on_progress(ProgressEvent::RowsScanned {
+                source: source.clone(),
+                rows,
+            });
+        }
+        self.callback.on_progress(ProgressEvent::NodeFinished {
+            node: self.node,
+            rows,
+        });
+        Ok(df)
+    }
+
+    fn describe(&self) -> String {
+        self.input.describe()
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        self.input.inputs()
+    }
+
+    fn bytes_read(&self) -> Option<u64> {
+        self.input.bytes_read()
+    }
+}
+
+/// Wraps a node of the physical plan and sends its events to an observer.
+pub(crate) struct ObservedExec {
+    pub(crate) input: Box<dyn Executor>,
+    pub(crate) node: &'static str,
+    /// Set if this node scans a source.
+    pub(crate) source: Option<String>,
+    pub(crate) observer: Arc<dyn QueryObserver>,
+}
+
+impl Executor for ObservedExec {
+    fn execute(&mut self, cache: &Cache) -> Result<DataFrame> {
+        self.observer
+            .on_event(&QueryEvent::NodeStarted { node: self.node });
+        let start = std::time::Instant::now();
+        let df = match self.input.execute(cache) {
+            Ok(df) => df,
+            Err(e) => {
+                self.observer.on_event(&QueryEvent::NodeFailed {
+                    node: self.node,
+                    error: e.to_string(),
+                });
+                return Err(e);
+            }
+        };
+        if let (Some(source), Some(bytes)) = (&self.source, self.input.bytes_read()) {
+            self.observer.on_event(&QueryEvent::BytesRead {
+                source: source.clone(),
+                bytes,
+            });
+        }
+        self.observer.on_event(&QueryEvent::NodeFinished {
+            node: self.node,
+            rows: df.height(),
+            elapsed: start.elapsed(),
+        });
+        Ok(df)
+    }
+
+    fn describe(&self) -> String {
+        self.input.describe()
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        self.input.inputs()
+    }
+
+    fn bytes_read(&self) -> Option<u64> {
+        self.input.bytes_read()
+    }
+}
+
+/// Wraps a node of the physical plan and records its output rows and execution time.
+pub(crate) struct ProfiledExec {
+    pub(crate) input: Box<dyn Executor>,
+    /// The logical plan of the node in the arena.
+    pub(crate) node: Node,
+    pub(crate) profiles: NodeProfiles,
+}
+
+impl Executor for ProfiledExec {
+    fn execute(&mut self, cache: &Cache) -> Result<DataFrame> {
+        let start = std::time::Instant::now();
+        let df = self.input.execute(cache)?;
+        let profile = NodeProfile {
+            rows: df.height(),
+            started: start,
+            elapsed: start.elapsed(),
+        };
+        self.profiles.lock().unwrap().insert(self.node.0, profile);
+        Ok(df)
+    }
+
+    fn describe(&self) -> String {
+        self.input.describe()
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        self.input.inputs()
+    }
+
+    fn bytes_read(&self) -> Option<u64> {
+        self.input.bytes_read()
+    }
+}
+
+/// Wraps a node of the physical plan in a `tracing` span with the node type,
+/// the number of output rows and the execution time.
+#[cfg(feature = "tracing")]
+pub(crate) struct TracedExec {
+    pub(crate) input: Box<dyn Executor>,
+    pub(crate) node: &'static str,
+}
+
+#[cfg(feature = "tracing")]
+impl Executor for TracedExec {
+    fn execute(&mut self, cache: &Cache) -> Result<DataFrame> {
+        let span = tracing::info_span!(
+            "execute",
+            node = self.node,
+            rows = tracing::field::Empty,
+            elapsed_ms = tracing::field::Empty
+        );
+        let _guard = span.enter();
+        let start = std::time::Instant::now();
+        let out = self.input.execute(cache);
+        span.record("elapsed_ms", &(start.elapsed().as_millis() as u64));
+        match &out {
+            Ok(df) => {
+                span.record("rows", &df.height());
+            }
+            Err(e) => tracing::error!(error = %e, "node failed"),
+        }
+        out
+    }
+
+    fn describe(&self) -> String {
+        self.input.describe()
+    }
+
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        self.input.inputs()
+    }
+
+    fn bytes_read(&self) -> Option<u64> {
+        self.input.bytes_read()
+    }
 }
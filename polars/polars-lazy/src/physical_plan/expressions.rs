@@ -3,9 +3,12 @@ use crate::physical_plan::AggPhysicalExpr;
 use crate::prelude::*;
 use polars_arrow::array::ValueSize;
 use polars_core::chunked_array::builder::get_list_builder;
-use polars_core::frame::group_by::{fmt_groupby_column, GroupByMethod};
+use polars_core::frame::group_by::{
+    agg_fused, fmt_groupby_column, groupby_output_dtype, GroupByMethod,
+};
 use polars_core::prelude::*;
-use polars_core::utils::NoNull;
+use polars_core::utils::{get_supertype, NoNull};
+use polars_io::predicates::{to_milliseconds, BatchStats, StatsEvaluator};
 use std::sync::Arc;
 
 pub struct LiteralExpr(pub LiteralValue, Expr);
@@ -78,6 +81,8 @@ impl PhysicalExpr for LiteralExpr {
                 let timestamp = naive_datetime_to_date64(ndt);
                 Date64Chunked::full("literal", timestamp, 1).into_series()
             }
+            #[cfg(all(feature = "temporal", feature = "dtype-duration-ms"))]
+            Duration(v) => DurationMillisecondChunked::full("literal", *v, 1).into_series(),
         };
         Ok(s)
     }
@@ -104,6 +109,8 @@ impl PhysicalExpr for LiteralExpr {
             Range { data_type, .. } => Field::new(name, data_type.clone()),
             #[cfg(all(feature = "temporal", feature = "dtype-date64"))]
             DateTime(_) => Field::new(name, DataType::Date64),
+            #[cfg(all(feature = "temporal", feature = "dtype-duration-ms"))]
+            Duration(_) => Field::new(name, DataType::Duration(TimeUnit::Millisecond)),
         };
         Ok(field)
     }
@@ -132,6 +139,19 @@ impl BinaryExpr {
     }
 }
 
+/// Cast both sides of a comparison to their supertype. The comparison kernels panic on types
+/// that can't be coerced, like enums with different categories, so these return an error here.
+fn coerce_comparison(lhs: Series, rhs: Series) -> Result<(Series, Series)> {
+    if lhs.dtype() == rhs.dtype() {
+        return Ok((lhs, rhs));
+    }
+    let dtype = get_supertype(lhs.dtype(), rhs.dtype())?;
+    Ok((
+        lhs.cast_with_datatype(&dtype)?,
+        rhs.cast_with_datatype(&dtype)?,
+    ))
+}
+
 impl PhysicalExpr for BinaryExpr {
     fn as_expression(&self) -> &Expr {
         &self.expr
@@ -140,6 +160,16 @@ impl PhysicalExpr for BinaryExpr {
     fn evaluate(&self, df: &DataFrame) -> Result<Series> {
         let lhs = self.left.evaluate(df)?;
         let rhs = self.right.evaluate(df)?;
+        let (lhs, rhs) = match self.op {
+            Operator::Gt
+            | Operator::GtEq
+            | Operator::Lt
+            | Operator::LtEq
+            | Operator::Eq
+            | Operator::NotEq
+            | Operator::Not => coerce_comparison(lhs, rhs)?,
+            _ => (lhs, rhs),
+        };
         let left = &lhs;
         let right = &rhs;
 
@@ -157,13 +187,83 @@ impl PhysicalExpr for BinaryExpr {
             Operator::And => Ok((left.bool()? & right.bool()?).into_series()),
             Operator::Or => Ok((left.bool()? | right.bool()?).into_series()),
             Operator::Not => Ok(ChunkCompare::<&Series>::eq(left, right).into_series()),
-            Operator::Like => todo!(),
-            Operator::NotLike => todo!(),
+            Operator::Like | Operator::NotLike => Err(PolarsError::InvalidOperation(
+                format!("operator {:?} is not supported", self.op).into(),
+            )),
             Operator::Modulus => Ok(left % right),
         }
     }
-    fn to_field(&self, _input_schema: &Schema) -> Result<Field> {
-        todo!()
+    fn to_field(&self, input_schema: &Schema) -> Result<Field> {
+        self.expr.to_field(input_schema, Context::Other)
+    }
+
+    fn as_stats_evaluator(&self) -> Option<&dyn StatsEvaluator> {
+        Some(self)
+    }
+}
+
+impl BinaryExpr {
+    /// Check a comparison of a temporal column with a literal against the min/max of the column.
+    fn should_read_comparison(&self, stats: &BatchStats) -> Result<bool> {
+        use Operator::*;
+        // normalize to `column op literal`
+        let (name, literal, op) = match &self.expr {
+            Expr::BinaryExpr { left, right, .. } => match (&**left, &**right) {
+                (Expr::Column(name), Expr::Literal(_)) => (name, &self.right, self.op),
+                (Expr::Literal(_), Expr::Column(name)) => {
+                    let op = match self.op {
+                        Gt => Lt,
+                        GtEq => LtEq,
+                        Lt => Gt,
+                        LtEq => GtEq,
+                        op => op,
+                    };
+                    (name, &self.left, op)
+                }
+                _ => return Ok(true),
+            },
+            _ => return Ok(true),
+        };
+        let (min, max) = match stats.get(name) {
+            Some(stats) => match (stats.min(), stats.max()) {
+                (Some(min), Some(max)) => (min, max),
+                _ => return Ok(true),
+            },
+            None => return Ok(true),
+        };
+        let empty = DataFrame::new_no_checks(vec![]);
+        let value = match to_milliseconds(&literal.evaluate(&empty)?)? {
+            Some(ca) => match ca.get(0) {
+                Some(value) => value,
+                None => return Ok(true),
+            },
+            None => return Ok(true),
+        };
+
+        let should_read = match op {
+            Gt => max > value,
+            GtEq => max >= value,
+            Lt => min < value,
+            LtEq => min <= value,
+            Eq => min <= value && value <= max,
+            _ => true,
+        };
+        Ok(should_read)
+    }
+}
+
+impl StatsEvaluator for BinaryExpr {
+    fn should_read(&self, stats: &BatchStats) -> Result<bool> {
+        // a side that can't be evaluated on the statistics may match any batch
+        let side_should_read = |side: &Arc<dyn PhysicalExpr>| match side.as_stats_evaluator() {
+            Some(evaluator) => evaluator.should_read(stats),
+            None => Ok(true),
+        };
+        match self.op {
+            Operator::And => Ok(side_should_read(&self.left)? && side_should_read(&self.right)?),
+            Operator::Or => Ok(side_should_read(&self.left)? || side_should_read(&self.right)?),
+            _ => self.should_read_comparison(stats),
+        }
     }
 }
 
@@ -294,7 +394,11 @@ impl PhysicalExpr for AliasExpr {
 }
 
 impl AggPhysicalExpr for AliasExpr {
-    fn evaluate(&self, df: &DataFrame, groups: &[(u32, Vec<u32>)]) -> Result<Option<Series>> {
+    fn evaluate(
+        &self,
+        df: &DataFrame,
+        groups: &[(IdxSize, Vec<IdxSize>)],
+    ) -> Result<Option<Series>> {
         let agg_expr = self.physical_expr.as_agg_expr()?;
         let opt_agg = agg_expr.evaluate(df, groups)?;
         Ok(opt_agg.map(|mut agg| {
@@ -360,12 +464,14 @@ impl PhysicalExpr for IsNotNullExpr {
     }
 }
 
-// TODO: to_field for groups and n_unique is probably wrong as the Datatype changes to Uint32
 macro_rules! impl_to_field_for_agg {
     ($self:ident, $input_schema:ident, $groupby_method_variant:expr) => {{
         let field = $self.expr.to_field($input_schema)?;
         let new_name = fmt_groupby_column(field.name(), $groupby_method_variant);
-        Ok(Field::new(&new_name, field.data_type().clone()))
+        Ok(Field::new(
+            &new_name,
+            groupby_output_dtype(field.data_type(), $groupby_method_variant)?,
+        ))
     }};
 }
 
@@ -382,13 +488,13 @@ impl PhysicalAggExpr {
 
 impl PhysicalExpr for PhysicalAggExpr {
     fn evaluate(&self, _df: &DataFrame) -> Result<Series> {
-        unimplemented!()
+        Err(PolarsError::InvalidOperation(
+            "an aggregation can only be evaluated on the groups of a groupby".into(),
+        ))
     }
 
     fn to_field(&self, input_schema: &Schema) -> Result<Field> {
-        let field = self.expr.to_field(input_schema)?;
-        let new_name = fmt_groupby_column(field.name(), self.agg_type);
-        Ok(Field::new(&new_name, field.data_type().clone()))
+        impl_to_field_for_agg!(self, input_schema, self.agg_type)
     }
 
     fn as_agg_expr(&self) -> Result<&dyn AggPhysicalExpr> {
@@ -396,6 +502,85 @@ impl PhysicalExpr for PhysicalAggExpr {
     }
 }
 
+/// Two aggregations of the same column that are computed in a single pass.
+/// See [agg_fused](polars_core::frame::group_by::agg_fused) for the supported pairs.
+pub(crate) struct FusedAggExpr {
+    expr: Arc<dyn PhysicalExpr>,
+    agg_types: [GroupByMethod; 2],
+    aliases: [Option<Arc<String>>; 2],
+}
+
+impl FusedAggExpr {
+    pub fn new(
+        expr: Arc<dyn PhysicalExpr>,
+        agg_types: [GroupByMethod; 2],
+        aliases: [Option<Arc<String>>; 2],
+    ) -> Self {
+        Self {
+            expr,
+            agg_types,
+            aliases,
+        }
+    }
+}
+
+impl PhysicalExpr for FusedAggExpr {
+    fn evaluate(&self, _df: &DataFrame) -> Result<Series> {
+        Err(PolarsError::InvalidOperation(
+            "an aggregation can only be evaluated on the groups of a groupby".into(),
+        ))
+    }
+
+    fn to_field(&self, _input_schema: &Schema) -> Result<Field> {
+        Err(PolarsError::InvalidOperation(
+            "a fused aggregation has multiple output fields".into(),
+        ))
+    }
+
+    fn as_agg_expr(&self) -> Result<&dyn AggPhysicalExpr> {
+        Ok(self)
+    }
+}
+
+impl AggPhysicalExpr for FusedAggExpr {
+    fn evaluate(
+        &self,
+        _df: &DataFrame,
+        _groups: &[(IdxSize, Vec<IdxSize>)],
+    ) -> Result<Option<Series>> {
+        Err(PolarsError::InvalidOperation(
+            "a fused aggregation outputs multiple columns".into(),
+        ))
+    }
+
+    fn evaluate_multiple(
+        &self,
+        df: &DataFrame,
+        groups: &[(IdxSize, Vec<IdxSize>)],
+    ) -> Result<Vec<Series>> {
+        let series = self.expr.evaluate(df)?;
+        let aggs = agg_fused(&series, groups, self.agg_types).ok_or_else(|| {
+            PolarsError::InvalidOperation(
+                format!("cannot fuse the aggregations {:?}", self.agg_types).into(),
+            )
+        })?;
+        let out = aggs
+            .iter()
+            .zip(&self.agg_types)
+            .zip(&self.aliases)
+            .map(|((agg, agg_type), alias)| {
+                let mut agg = agg.clone();
+                match alias {
+                    Some(name) => agg.rename(name),
+                    None => agg.rename(&fmt_groupby_column(series.name(), *agg_type)),
+                };
+                agg
+            })
+            .collect();
+        Ok(out)
+    }
+}
+
 fn rename_option_series(opt: Option<Series>, name: &str) -> Option<Series> {
     opt.map(|mut s| {
         s.rename(name);
@@ -404,7 +589,11 @@ fn rename_option_series(opt: Option<Series>, name: &str) -> Option<Series> {
 }
 
 impl AggPhysicalExpr for PhysicalAggExpr {
-    fn evaluate(&self, df: &DataFrame, groups: &[(u32, Vec<u32>)]) -> Result<Option<Series>> {
+    fn evaluate(
+        &self,
+        df: &DataFrame,
+        groups: &[(IdxSize, Vec<IdxSize>)],
+    ) -> Result<Option<Series>> {
         let series = self.expr.evaluate(df)?;
         let new_name = fmt_groupby_column(series.name(), self.agg_type);
 
@@ -430,8 +619,8 @@ impl AggPhysicalExpr for PhysicalAggExpr {
                 Ok(rename_option_series(agg_s, &new_name))
             }
             GroupByMethod::Count => {
-                let mut ca: NoNull<UInt32Chunked> =
-                    groups.iter().map(|(_, g)| g.len() as u32).collect();
+                let mut ca: NoNull<IdxCa> =
+                    groups.iter().map(|(_, g)| g.len() as IdxSize).collect();
                 ca.rename(&new_name);
                 Ok(Some(ca.into_inner().into_series()))
             }
@@ -461,7 +650,7 @@ impl AggPhysicalExpr for PhysicalAggExpr {
                 let mut column: ListChunked = groups
                     .iter()
                     .map(|(_first, idx)| {
-                        let ca: NoNull<UInt32Chunked> = idx.iter().map(|&v| v as u32).collect();
+                        let ca: NoNull<IdxCa> = idx.iter().copied().collect();
                         ca.into_inner().into_series()
                     })
                     .collect();
@@ -477,8 +666,15 @@ impl AggPhysicalExpr for PhysicalAggExpr {
                 let agg_s = series.agg_var(groups);
                 Ok(rename_option_series(agg_s, &new_name))
             }
-            GroupByMethod::Quantile(_) => {
-                unimplemented!()
+            GroupByMethod::NullCount => {
+                let mut ca = series.agg_null_count(groups);
+                ca.rename(&new_name);
+                Ok(Some(ca.into_series()))
+            }
+            GroupByMethod::Quantile(_) | GroupByMethod::TopK(_) => {
+                Err(PolarsError::InvalidOperation(
+                    format!("{:?} is not supported in this aggregation", self.agg_type).into(),
+                ))
             }
         }
     }
@@ -486,7 +682,7 @@ impl AggPhysicalExpr for PhysicalAggExpr {
     fn evaluate_partitioned(
         &self,
         df: &DataFrame,
-        groups: &[(u32, Vec<u32>)],
+        groups: &[(IdxSize, Vec<IdxSize>)],
     ) -> Result<Option<Vec<Series>>> {
         match self.agg_type {
             GroupByMethod::Mean => {
@@ -497,8 +693,7 @@ impl AggPhysicalExpr for PhysicalAggExpr {
                 if let Some(mut agg_s) = agg_s {
                     agg_s.rename(&new_name);
                     new_name.push_str("__POLARS_MEAN_COUNT");
-                    let ca: NoNull<UInt32Chunked> =
-                        groups.iter().map(|t| t.1.len() as u32).collect();
+                    let ca: NoNull<IdxCa> = groups.iter().map(|t| t.1.len() as IdxSize).collect();
                     let mut count_s = ca.into_inner().into_series();
                     count_s.rename(&new_name);
                     Ok(Some(vec![agg_s, count_s]))
@@ -522,18 +717,24 @@ impl AggPhysicalExpr for PhysicalAggExpr {
     fn evaluate_partitioned_final(
         &self,
         final_df: &DataFrame,
-        groups: &[(u32, Vec<u32>)],
+        groups: &[(IdxSize, Vec<IdxSize>)],
     ) -> Result<Option<Series>> {
         match self.agg_type {
             GroupByMethod::Mean => {
                 let series = self.expr.evaluate(final_df)?;
                 let count_name = format!("{}__POLARS_MEAN_COUNT", series.name());
                 let new_name = fmt_groupby_column(series.name(), self.agg_type);
-                let count = final_df.column(&count_name).unwrap();
-                // divide by the count
-                let series = &series / count;
-                let agg_s = series.agg_sum(groups);
-                Ok(rename_option_series(agg_s, &new_name))
+                let count = final_df.column(&count_name)?;
+                // divide the summed partial sums by the summed counts
+                match (series.agg_sum(groups), count.agg_sum(groups)) {
+                    (Some(sum), Some(count)) => {
+                        let mut agg_s =
+                            &sum.cast::<Float64Type>()? / &count.cast::<Float64Type>()?;
+                        agg_s.rename(&new_name);
+                        Ok(Some(agg_s))
+                    }
+                    _ => Ok(None),
+                }
             }
             GroupByMethod::List => {
                 let series = self.expr.evaluate(final_df)?;
@@ -575,7 +776,9 @@ impl AggQuantileExpr {
 
 impl PhysicalExpr for AggQuantileExpr {
     fn evaluate(&self, _df: &DataFrame) -> Result<Series> {
-        unimplemented!()
+        Err(PolarsError::InvalidOperation(
+            "an aggregation can only be evaluated on the groups of a groupby".into(),
+        ))
     }
 
     fn to_field(&self, input_schema: &Schema) -> Result<Field> {
@@ -588,7 +791,11 @@ impl PhysicalExpr for AggQuantileExpr {
 }
 
 impl AggPhysicalExpr for AggQuantileExpr {
-    fn evaluate(&self, df: &DataFrame, groups: &[(u32, Vec<u32>)]) -> Result<Option<Series>> {
+    fn evaluate(
+        &self,
+        df: &DataFrame,
+        groups: &[(IdxSize, Vec<IdxSize>)],
+    ) -> Result<Option<Series>> {
         let series = self.expr.evaluate(df)?;
         let new_name = fmt_groupby_column(series.name(), GroupByMethod::Quantile(self.quantile));
         let opt_agg = series.agg_quantile(groups, self.quantile);
@@ -602,6 +809,51 @@ impl AggPhysicalExpr for AggQuantileExpr {
     }
 }
 
+pub struct AggTopKExpr {
+    expr: Arc<dyn PhysicalExpr>,
+    k: usize,
+}
+
+impl AggTopKExpr {
+    pub fn new(expr: Arc<dyn PhysicalExpr>, k: usize) -> Self {
+        Self { expr, k }
+    }
+}
+
+impl PhysicalExpr for AggTopKExpr {
+    fn evaluate(&self, _df: &DataFrame) -> Result<Series> {
+        Err(PolarsError::InvalidOperation(
+            "an aggregation can only be evaluated on the groups of a groupby".into(),
+        ))
+    }
+
+    fn to_field(&self, input_schema: &Schema) -> Result<Field> {
+        let field = self.expr.to_field(input_schema)?;
+        let new_name = fmt_groupby_column(field.name(), GroupByMethod::TopK(self.k));
+        Ok(Field::new(
+            &new_name,
+            groupby_output_dtype(field.data_type(), GroupByMethod::TopK(self.k))?,
+        ))
+    }
+
+    fn as_agg_expr(&self) -> Result<&dyn AggPhysicalExpr> {
+        Ok(self)
+    }
+}
+
+impl AggPhysicalExpr for AggTopKExpr {
+    fn evaluate(
+        &self,
+        df: &DataFrame,
+        groups: &[(IdxSize, Vec<IdxSize>)],
+    ) -> Result<Option<Series>> {
+        let series = self.expr.evaluate(df)?;
+        let new_name = fmt_groupby_column(series.name(), GroupByMethod::TopK(self.k));
+        let opt_agg = series.agg_top_k(groups, self.k);
+        Ok(rename_option_series(opt_agg, &new_name))
+    }
+}
+
 pub struct CastExpr {
     input: Arc<dyn PhysicalExpr>,
     data_type: DataType,
@@ -698,7 +950,11 @@ impl PhysicalExpr for ApplyExpr {
 }
 
 impl AggPhysicalExpr for ApplyExpr {
-    fn evaluate(&self, df: &DataFrame, groups: &[(u32, Vec<u32>)]) -> Result<Option<Series>> {
+    fn evaluate(
+        &self,
+        df: &DataFrame,
+        groups: &[(IdxSize, Vec<IdxSize>)],
+    ) -> Result<Option<Series>> {
         match self.input.as_agg_expr() {
             // layer below is also an aggregation expr.
             Ok(expr) => {
@@ -733,6 +989,33 @@ pub struct WindowExpr {
     pub(crate) function: Expr,
 }
 
+impl WindowExpr {
+    /// Apply `f` on the partitions aggregated to a list column, explode the result and
+    /// scatter the values back to the rows the partitions originate from.
+    fn map_partitions<F>(
+        &self,
+        df: &DataFrame,
+        groups: &[(IdxSize, Vec<IdxSize>)],
+        f: F,
+    ) -> Result<Series>
+    where
+        F: Fn(&Series) -> Result<Series>,
+    {
+        let s = df.column(self.apply_column.as_str())?;
+        let values = match s.agg_list(groups) {
+            Some(lists) => f(&lists)?.explode()?,
+            None => return Ok(s.clone()),
+        };
+        let mut take_idx = vec![0usize; df.height()];
+        for (i, &row) in groups.iter().flat_map(|(_, idx)| idx.iter()).enumerate() {
+            take_idx[row as usize] = i;
+        }
+        let mut out = values.take_iter(&mut take_idx.into_iter());
+        out.rename(self.out_name.as_str());
+        Ok(out)
+    }
+}
+
 impl PhysicalExpr for WindowExpr {
     // Note: this was first implemented with expression evaluation but this performed really bad.
     // Therefore we choose the groupby -> apply -> self join approach
@@ -741,6 +1024,33 @@ impl PhysicalExpr for WindowExpr {
             .groupby(self.group_column.as_str())?
             .select(self.apply_column.as_str());
 
+        // not an aggregation, so we number the rows directly on the group indexes
+        if let Expr::CumCount(_) = &self.function {
+            let mut values = vec![0 as IdxSize; df.height()];
+            for (_first, idx) in gb.get_groups() {
+                for (i, &row) in idx.iter().enumerate() {
+                    values[row as usize] = i as IdxSize;
+                }
+            }
+            return Ok(IdxCa::new_from_slice(self.out_name.as_str(), &values).into_series());
+        }
+
+        // these apply per partition and put the values back in row order
+        match &self.function {
+            Expr::ClipQuantile { low_q, high_q, .. } => {
+                return self.map_partitions(df, gb.get_groups(), |lists| {
+                    lists.clip_quantile(*low_q, *high_q)
+                })
+            }
+            Expr::Standardize(_) => {
+                return self.map_partitions(df, gb.get_groups(), |lists| lists.standardize())
+            }
+            Expr::MinMaxScale(_) => {
+                return self.map_partitions(df, gb.get_groups(), |lists| lists.min_max_scale())
+            }
+            _ => {}
+        }
+
         let out = match &self.function {
             Expr::Udf { function, .. } => {
                 let mut df = gb.agg_list()?;
@@ -757,7 +1067,20 @@ impl PhysicalExpr for WindowExpr {
                 AggExpr::Last(_) => gb.last(),
                 AggExpr::Count(_) => gb.count(),
                 AggExpr::NUnique(_) => gb.n_unique(),
+                AggExpr::NullCount(_) => gb.null_count(),
                 AggExpr::Quantile { quantile, .. } => gb.quantile(*quantile),
+                AggExpr::TopK { k, .. } => {
+                    let s = df.column(self.apply_column.as_str())?;
+                    let mut agg = s.agg_top_k(gb.get_groups(), *k).ok_or_else(|| {
+                        PolarsError::InvalidOperation(
+                            format!("top_k is not supported for dtype {:?}", s.dtype()).into(),
+                        )
+                    })?;
+                    agg.rename(&fmt_groupby_column(s.name(), GroupByMethod::TopK(*k)));
+                    let mut cols = gb.keys();
+                    cols.push(agg);
+                    DataFrame::new(cols)
+                }
                 AggExpr::List(_) => gb.agg_list(),
                 AggExpr::AggGroups(_) => gb.groups(),
                 AggExpr::Std(_) => gb.std(),
@@ -771,12 +1094,15 @@ impl PhysicalExpr for WindowExpr {
             .select(self.group_column.as_str())?
             .left_join(&out, self.group_column.as_str(), &self.group_column)?
             .select_at_idx(1)
-            .unwrap_or_else(|| {
-                panic!(
-                    "the aggregation function did not succeed on {}",
-                    self.apply_column
+            .ok_or_else(|| {
+                PolarsError::Other(
+                    format!(
+                        "the aggregation function did not succeed on {}",
+                        self.apply_column
+                    )
+                    .into(),
                 )
-            })
+            })?
             .clone();
         out.rename(self.out_name.as_str());
         Ok(out)
@@ -830,7 +1156,11 @@ impl PhysicalExpr for SliceExpr {
 }
 
 impl AggPhysicalExpr for SliceExpr {
-    fn evaluate(&self, df: &DataFrame, groups: &[(u32, Vec<u32>)]) -> Result<Option<Series>> {
+    fn evaluate(
+        &self,
+        df: &DataFrame,
+        groups: &[(IdxSize, Vec<IdxSize>)],
+    ) -> Result<Option<Series>> {
         let s = self.input.evaluate(df)?;
         let agg_s = s.agg_list(groups);
         let out = agg_s.map(|s| {
@@ -882,7 +1212,11 @@ impl PhysicalExpr for BinaryFunctionExpr {
 }
 
 impl AggPhysicalExpr for BinaryFunctionExpr {
-    fn evaluate(&self, df: &DataFrame, groups: &[(u32, Vec<u32>)]) -> Result<Option<Series>> {
+    fn evaluate(
+        &self,
+        df: &DataFrame,
+        groups: &[(IdxSize, Vec<IdxSize>)],
+    ) -> Result<Option<Series>> {
         let a = self.input_a.evaluate(df)?;
         let b = self.input_b.evaluate(df)?;
 
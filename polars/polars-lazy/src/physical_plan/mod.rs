@@ -5,9 +5,14 @@ pub mod planner;
 use crate::prelude::*;
 use ahash::RandomState;
 use polars_core::prelude::*;
+use polars_core::toggle_string_cache;
+use polars_io::predicates::StatsEvaluator;
 use polars_io::PhysicalIoExpr;
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 pub enum ExprVal {
     Series(Series),
@@ -29,9 +34,243 @@ pub trait PhysicalPlanner {
 /// Executors will evaluate physical expressions and collect them in a DataFrame.
 pub trait Executor: Send + Sync {
     fn execute(&mut self, cache: &Cache) -> Result<DataFrame>;
+
+    /// Describe the operator, e.g. `PARTITIONED GROUPBY`. Used to render the physical plan.
+    fn describe(&self) -> String {
+        let name = std::any::type_name::<Self>();
+        name.rsplit("::").next().unwrap_or(name).to_string()
+    }
+
+    /// The executors that produce the input of this operator.
+    fn inputs(&self) -> Vec<&dyn Executor> {
+        vec![]
+    }
+
+    /// The number of bytes the last execution of this operator read from the source it scans.
+    /// `None` if the operator doesn't scan a source or the scan was served from the cache.
+    fn bytes_read(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// The DataFrames that are cached during the execution of a physical plan, by their key.
+pub type Cache = Arc<Mutex<HashMap<String, DataFrame, RandomState>>>;
+
+/// The output rows and execution time of a node of the physical plan. The time includes the
+/// execution of the inputs of the node.
+#[derive(Clone, Debug)]
+pub(crate) struct NodeProfile {
+    pub(crate) rows: usize,
+    pub(crate) started: Instant,
+    pub(crate) elapsed: Duration,
+}
+
+/// The profiles of the executed nodes, by the index of their logical plan in the arena.
+pub(crate) type NodeProfiles = Arc<Mutex<HashMap<usize, NodeProfile>>>;
+
+/// An executable tree of operators, created by
+/// [LazyFrame::to_physical_plan](crate::frame::LazyFrame::to_physical_plan).
+///
+/// The `Debug` and dot representations show the operators that are chosen to run the query,
+/// e.g. the join strategy or whether a groupby is partitioned.
+pub struct PhysicalPlan {
+    root: Box<dyn Executor>,
+    use_string_cache: bool,
+    observer: Option<Arc<dyn QueryObserver>>,
+}
+
+impl PhysicalPlan {
+    /// Create a plan from an executor, e.g. a custom executor that wraps (a part of) another plan.
+    pub fn new(root: Box<dyn Executor>) -> Self {
+        PhysicalPlan {
+            root,
+            use_string_cache: false,
+            observer: None,
+        }
+    }
+
+    pub(crate) fn with_string_cache(mut self, toggle: bool) -> Self {
+        self.use_string_cache = toggle;
+        self
+    }
+
+    pub(crate) fn with_observer(mut self, observer: Option<Arc<dyn QueryObserver>>) -> Self {
+        self.observer = observer;
+        self
+    }
+
+    /// The root operator of the plan.
+    pub fn root(&self) -> &dyn Executor {
+        self.root.as_ref()
+    }
+
+    /// Take the root operator of the plan.
+    pub fn into_executor(self) -> Box<dyn Executor> {
+        self.root
+    }
+
+    /// Execute the plan. The operators consume their state, so a plan can be executed once.
+    pub fn execute(mut self) -> Result<DataFrame> {
+        toggle_string_cache(self.use_string_cache);
+        let cache = Arc::new(Mutex::new(HashMap::with_capacity_and_hasher(
+            64,
+            RandomState::default(),
+        )));
+        if let Some(observer) = &self.observer {
+            observer.on_event(&QueryEvent::PlanStarted);
+        }
+        let start = Instant::now();
+        let out = self.root.execute(&cache);
+        if self.use_string_cache {
+            toggle_string_cache(!self.use_string_cache);
+        }
+        if let Some(observer) = &self.observer {
+            let event = match &out {
+                Ok(df) => QueryEvent::PlanFinished {
+                    rows: df.height(),
+                    elapsed: start.elapsed(),
+                },
+                Err(e) => QueryEvent::PlanFailed {
+                    error: e.to_string(),
+                },
+            };
+            observer.on_event(&event);
+        }
+        out
+    }
+
+    /// Get a dot language representation of the plan.
+    pub fn to_dot(&self) -> String {
+        let mut s = String::with_capacity(512);
+        s.push_str("graph  polars_query {\n");
+        let mut id = 0;
+        dot_executor(self.root(), &mut s, &mut id, None);
+        s.push('}');
+        s
+    }
+}
+
+fn dot_executor(
+    exec: &dyn Executor,
+    acc_str: &mut String,
+    id: &mut usize,
+    prev_node: Option<&str>,
+) {
+    let current_node = format!("{} [{}]", exec.describe(), id);
+    *id += 1;
+    match prev_node {
+        Some(prev_node) => {
+            acc_str.push_str(&format!("\"{}\" -- \"{}\"\n", prev_node, current_node))
+        }
+        None => acc_str.push_str(&format!("\"{}\"\n", current_node)),
+    }
+    for input in exec.inputs() {
+        dot_executor(input, acc_str, id, Some(&current_node))
+    }
+}
+
+fn fmt_executor(exec: &dyn Executor, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+    writeln!(f, "{:indent$}{}", "", exec.describe(), indent = indent)?;
+    for input in exec.inputs() {
+        fmt_executor(input, f, indent + 2)?;
+    }
+    Ok(())
+}
+
+impl fmt::Debug for PhysicalPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_executor(self.root(), f, 0)
+    }
+}
+
+/// Flag that can be used to cancel a running query from another thread.
+/// The query checks the flag before and after every node in the physical plan and
+/// returns `PolarsError::Cancelled` once the flag is set.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request the query to stop.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            Err(PolarsError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Progress of a running query, passed to a [ProgressCallback](ProgressCallback).
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProgressEvent {
+    /// A scan read `rows` rows from `source`.
+    RowsScanned { source: String, rows: usize },
+    /// A node of the physical plan finished and produced `rows` rows.
+    NodeFinished { node: &'static str, rows: usize },
 }
 
-pub(crate) type Cache = Arc<Mutex<HashMap<String, DataFrame, RandomState>>>;
+pub trait ProgressCallback: Send + Sync {
+    fn on_progress(&self, event: ProgressEvent);
+}
+
+impl<F> ProgressCallback for F
+where
+    F: Fn(ProgressEvent) + Send + Sync,
+{
+    fn on_progress(&self, event: ProgressEvent) {
+        self(event)
+    }
+}
+
+/// Machine readable events emitted during the execution of a query,
+/// passed to a [QueryObserver](QueryObserver).
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum QueryEvent {
+    /// The physical plan is created and starts executing.
+    PlanStarted,
+    /// A node of the physical plan starts executing.
+    NodeStarted { node: &'static str },
+    /// A node of the physical plan finished and produced `rows` rows.
+    NodeFinished {
+        node: &'static str,
+        rows: usize,
+        elapsed: Duration,
+    },
+    /// A node of the physical plan failed with `error`.
+    NodeFailed { node: &'static str, error: String },
+    /// A scan read `bytes` bytes of its source. Not sent if the scan is served from the cache.
+    BytesRead { source: String, bytes: u64 },
+    /// The query finished and produced `rows` rows.
+    PlanFinished { rows: usize, elapsed: Duration },
+    /// The query failed with `error`.
+    PlanFailed { error: String },
+}
+
+pub trait QueryObserver: Send + Sync {
+    fn on_event(&self, event: &QueryEvent);
+}
+
+impl<F> QueryObserver for F
+where
+    F: Fn(&QueryEvent) + Send + Sync,
+{
+    fn on_event(&self, event: &QueryEvent) {
+        self(event)
+    }
+}
 
 /// Take a DataFrame and evaluate the expressions.
 /// Implement this for Column, lt, eq, etc
@@ -53,6 +292,12 @@ pub trait PhysicalExpr: Send + Sync {
             format!("{:?} is not an agg expression", e).into(),
         ))
     }
+
+    /// Get the evaluator that decides from the min/max index of a batch whether this
+    /// predicate has to be evaluated on the batch.
+    fn as_stats_evaluator(&self) -> Option<&dyn StatsEvaluator> {
+        None
+    }
 }
 
 trait ToPhysicalIoExpr {
@@ -73,6 +318,10 @@ impl PhysicalIoExpr for PhysicalIoHelper {
     fn evaluate(&self, df: &DataFrame) -> Result<Series> {
         self.expr.evaluate(df)
     }
+
+    fn as_stats_evaluator(&self) -> Option<&dyn StatsEvaluator> {
+        self.expr.as_stats_evaluator()
+    }
 }
 
 impl PhysicalIoExpr for dyn PhysicalExpr {
@@ -82,12 +331,26 @@ impl PhysicalIoExpr for dyn PhysicalExpr {
 }
 
 pub trait AggPhysicalExpr {
-    fn evaluate(&self, df: &DataFrame, groups: &[(u32, Vec<u32>)]) -> Result<Option<Series>>;
+    fn evaluate(
+        &self,
+        df: &DataFrame,
+        groups: &[(IdxSize, Vec<IdxSize>)],
+    ) -> Result<Option<Series>>;
+
+    /// Evaluate an aggregation that may output multiple columns, like a fused aggregation.
+    fn evaluate_multiple(
+        &self,
+        df: &DataFrame,
+        groups: &[(IdxSize, Vec<IdxSize>)],
+    ) -> Result<Vec<Series>> {
+        self.evaluate(df, groups)
+            .map(|opt| opt.into_iter().collect())
+    }
 
     fn evaluate_partitioned(
         &self,
         df: &DataFrame,
-        groups: &[(u32, Vec<u32>)],
+        groups: &[(IdxSize, Vec<IdxSize>)],
     ) -> Result<Option<Vec<Series>>> {
         // we return a vec, such that an implementor can return more information, such as a sum and count.
         self.evaluate(df, groups).map(|opt| opt.map(|s| vec![s]))
@@ -96,7 +359,7 @@ pub trait AggPhysicalExpr {
     fn evaluate_partitioned_final(
         &self,
         final_df: &DataFrame,
-        groups: &[(u32, Vec<u32>)],
+        groups: &[(IdxSize, Vec<IdxSize>)],
     ) -> Result<Option<Series>> {
         self.evaluate(final_df, groups)
     }
@@ -1,20 +1,113 @@
 use super::expressions as phys_expr;
+use crate::logical_plan::iterator::ArenaExprIter;
 use crate::logical_plan::Context;
 use crate::physical_plan::executors::*;
+use crate::physical_plan::NodeProfiles;
 use crate::prelude::*;
 use crate::utils::{aexpr_to_root_names, agg_source_paths};
 use ahash::RandomState;
 use itertools::Itertools;
+use polars_core::frame::group_by::{can_fuse_aggs, GroupByMethod};
 use polars_core::prelude::*;
-use polars_core::{frame::group_by::GroupByMethod, utils::parallel_op};
+use polars_core::utils::parallel_op;
 use polars_io::ScanAggregation;
 use std::collections::HashSet;
 use std::sync::Arc;
 
+/// Find the predicates of a join where that compare a left column with a right column, so that
+/// the candidate rows can be pruned with a binary search on the sorted right column. Only the
+/// bounds on the right column of the first usable predicate are returned.
+fn range_bounds(
+    predicates: &[Node],
+    schema_left: &Schema,
+    schema_right: &Schema,
+    suffix: &str,
+    expr_arena: &Arena<AExpr>,
+) -> Vec<RangeBound> {
+    let sortable = |dtype: &DataType| {
+        matches!(
+            dtype,
+            DataType::UInt8
+                | DataType::UInt16
+                | DataType::UInt32
+                | DataType::UInt64
+                | DataType::Int8
+                | DataType::Int16
+                | DataType::Int32
+                | DataType::Int64
+                | DataType::Float32
+                | DataType::Float64
+                | DataType::Date32
+                | DataType::Date64
+        )
+    };
+    let left_column = |name: &str| match schema_left.field_with_name(name) {
+        Ok(field) if sortable(field.data_type()) => Some(name.to_string()),
+        _ => None,
+    };
+    // right columns that are also in the left table have the suffix in the joined schema
+    let right_column = |name: &str| {
+        if schema_left.field_with_name(name).is_ok() {
+            return None;
+        }
+        let name = match name.strip_suffix(suffix) {
+            Some(stripped) if schema_left.field_with_name(stripped).is_ok() => stripped,
+            _ => name,
+        };
+        match schema_right.field_with_name(name) {
+            Ok(field) if sortable(field.data_type()) => Some(name.to_string()),
+            _ => None,
+        }
+    };
+
+    let mut bounds: Vec<RangeBound> = vec![];
+    for node in predicates {
+        if let AExpr::BinaryExpr { left, op, right } = expr_arena.get(*node) {
+            let (a, b) = match (expr_arena.get(*left), expr_arena.get(*right)) {
+                (AExpr::Column(a), AExpr::Column(b)) => (a.as_str(), b.as_str()),
+                _ => continue,
+            };
+            // normalize to `left_on op right_on`
+            let (left_on, op, right_on) = match (left_column(a), right_column(b)) {
+                (Some(l), Some(r)) => (l, *op, r),
+                _ => match (left_column(b), right_column(a)) {
+                    (Some(l), Some(r)) => {
+                        let op = match op {
+                            Operator::Lt => Operator::Gt,
+                            Operator::LtEq => Operator::GtEq,
+                            Operator::Gt => Operator::Lt,
+                            Operator::GtEq => Operator::LtEq,
+                            op => *op,
+                        };
+                        (l, op, r)
+                    }
+                    _ => continue,
+                },
+            };
+            let usable = matches!(
+                op,
+                Operator::Eq | Operator::Lt | Operator::LtEq | Operator::Gt | Operator::GtEq
+            );
+            if usable
+                && bounds
+                    .first()
+                    .map_or(true, |first| first.right_on == right_on)
+            {
+                bounds.push(RangeBound {
+                    left_on,
+                    op,
+                    right_on,
+                })
+            }
+        }
+    }
+    bounds
+}
+
 fn aggregate_expr_to_scan_agg(
     aggregate: Vec<Node>,
     expr_arena: &mut Arena<AExpr>,
-) -> Vec<ScanAggregation> {
+) -> Result<Vec<ScanAggregation>> {
     aggregate
         .into_iter()
         .map(|mut expr| {
@@ -25,39 +118,203 @@ fn aggregate_expr_to_scan_agg(
             };
             if let AExpr::Agg(agg) = expr_arena.get(expr) {
                 match agg {
-                    AAggExpr::Min(e) => ScanAggregation::Min {
+                    AAggExpr::Min(e) => Ok(ScanAggregation::Min {
                         column: (*aexpr_to_root_names(*e, expr_arena).pop().unwrap()).clone(),
                         alias,
-                    },
-                    AAggExpr::Max(e) => ScanAggregation::Max {
+                    }),
+                    AAggExpr::Max(e) => Ok(ScanAggregation::Max {
                         column: (*aexpr_to_root_names(*e, expr_arena).pop().unwrap()).clone(),
                         alias,
-                    },
-                    AAggExpr::Sum(e) => ScanAggregation::Sum {
+                    }),
+                    AAggExpr::Sum(e) => Ok(ScanAggregation::Sum {
                         column: (*aexpr_to_root_names(*e, expr_arena).pop().unwrap()).clone(),
                         alias,
-                    },
-                    AAggExpr::First(e) => ScanAggregation::First {
+                    }),
+                    AAggExpr::First(e) => Ok(ScanAggregation::First {
                         column: (*aexpr_to_root_names(*e, expr_arena).pop().unwrap()).clone(),
                         alias,
-                    },
-                    AAggExpr::Last(e) => ScanAggregation::Last {
+                    }),
+                    AAggExpr::Last(e) => Ok(ScanAggregation::Last {
                         column: (*aexpr_to_root_names(*e, expr_arena).pop().unwrap()).clone(),
                         alias,
-                    },
-                    _ => todo!(),
+                    }),
+                    _ => Err(PolarsError::InvalidOperation(
+                        format!(
+                            "cannot compute {:?} while scanning a file",
+                            node_to_exp(expr, expr_arena)
+                        )
+                        .into(),
+                    )),
                 }
             } else {
-                unreachable!()
+                Err(PolarsError::InvalidOperation(
+                    format!(
+                        "cannot compute {:?} while scanning a file",
+                        node_to_exp(expr, expr_arena)
+                    )
+                    .into(),
+                ))
             }
         })
         .collect()
 }
 
-pub struct DefaultPlanner {}
+/// Name of the node and the source it scans, used to report progress.
+pub(crate) fn node_description(lp: &ALogicalPlan) -> (&'static str, Option<String>) {
+    use ALogicalPlan::*;
+    match lp {
+        Melt { .. } => ("melt", None),
+        Slice { .. } => ("slice", None),
+        Selection { .. } => ("filter", None),
+        CsvScan { path, .. } => ("csv scan", Some(path.clone())),
+        #[cfg(feature = "parquet")]
+        ParquetScan { path, .. } => ("parquet scan", Some(path.clone())),
+        #[cfg(feature = "ipc")]
+        IpcScan { path, .. } => ("ipc scan", Some(path.clone())),
+        DataFrameScan { .. } => ("dataframe scan", None),
+        Projection { .. } | LocalProjection { .. } => ("select", None),
+        Sort { .. } => ("sort", None),
+        Explode { .. } => ("explode", None),
+        Rename { .. } => ("rename", None),
+        Cache { .. } => ("cache", None),
+        Aggregate { .. } => ("groupby", None),
+        Join { .. } => ("join", None),
+        SetOp { .. } => ("set operation", None),
+        Union { .. } => ("union", None),
+        JoinWhere { .. } => ("join where", None),
+        HStack { .. } => ("with_columns", None),
+        Distinct { .. } => ("distinct", None),
+        Udf { .. } => ("udf", None),
+        Sink { path, .. } => ("sink", Some(path.clone())),
+    }
+}
+
+/// Whether the expression only computes values row by row from the columns of its input, so
+/// that it can be evaluated batch by batch.
+fn is_elementwise(node: Node, expr_arena: &Arena<AExpr>) -> bool {
+    let mut has_column = false;
+    let elementwise = expr_arena.iter(node).all(|(_, e)| match e {
+        AExpr::Column(_) => {
+            has_column = true;
+            true
+        }
+        AExpr::Alias(..)
+        | AExpr::Literal(_)
+        | AExpr::BinaryExpr { .. }
+        | AExpr::Not(_)
+        | AExpr::IsNull(_)
+        | AExpr::IsNotNull(_)
+        | AExpr::Cast { .. }
+        | AExpr::Ternary { .. } => true,
+        _ => false,
+    });
+    // an expression without columns, e.g. a literal, produces a single row per batch
+    elementwise && has_column
+}
+
+/// The scan at the bottom of `node`, if the nodes above it only filter rows and compute
+/// columns elementwise. The query can then be executed batch by batch of the scan.
+fn batch_source(
+    mut node: Node,
+    lp_arena: &Arena<ALogicalPlan>,
+    expr_arena: &Arena<AExpr>,
+) -> Option<Node> {
+    use ALogicalPlan::*;
+    let all_elementwise = |exprs: &[Node]| exprs.iter().all(|&e| is_elementwise(e, expr_arena));
+    loop {
+        match lp_arena.get(node) {
+            Selection { input, predicate } if is_elementwise(*predicate, expr_arena) => {
+                node = *input
+            }
+            Projection { input, expr, .. } | LocalProjection { input, expr, .. }
+                if all_elementwise(expr) =>
+            {
+                node = *input
+            }
+            HStack { input, exprs, .. } if all_elementwise(exprs) => node = *input,
+            DataFrameScan {
+                projection,
+                selection,
+                ..
+            } if projection.as_deref().map_or(true, all_elementwise)
+                && selection.map_or(true, |e| is_elementwise(e, expr_arena)) =>
+            {
+                return Some(node)
+            }
+            CsvScan {
+                predicate,
+                aggregate,
+                ..
+            } if aggregate.is_empty()
+                && predicate.map_or(true, |e| is_elementwise(e, expr_arena)) =>
+            {
+                return Some(node)
+            }
+            #[cfg(feature = "ipc")]
+            IpcScan {
+                predicate,
+                aggregate,
+                ..
+            } if aggregate.is_empty()
+                && predicate.map_or(true, |e| is_elementwise(e, expr_arena)) =>
+            {
+                return Some(node)
+            }
+            #[cfg(feature = "parquet")]
+            ParquetScan {
+                predicate,
+                aggregate,
+                ..
+            } if aggregate.is_empty()
+                && predicate.map_or(true, |e| is_elementwise(e, expr_arena)) =>
+            {
+                return Some(node)
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// The column, method and alias of an aggregation that can be fused with another aggregation of
+/// the same column.
+fn fusable_agg(
+    node: Node,
+    expr_arena: &Arena<AExpr>,
+) -> Option<(Node, Arc<String>, GroupByMethod, Option<Arc<String>>)> {
+    let (node, alias) = match expr_arena.get(node) {
+        AExpr::Alias(e, name) => (*e, Some(name.clone())),
+        _ => (node, None),
+    };
+    let (input, method) = match expr_arena.get(node) {
+        AExpr::Agg(AAggExpr::Min(e)) => (*e, GroupByMethod::Min),
+        AExpr::Agg(AAggExpr::Max(e)) => (*e, GroupByMethod::Max),
+        AExpr::Agg(AAggExpr::Sum(e)) => (*e, GroupByMethod::Sum),
+        AExpr::Agg(AAggExpr::Mean(e)) => (*e, GroupByMethod::Mean),
+        AExpr::Agg(AAggExpr::Count(e)) => (*e, GroupByMethod::Count),
+        _ => return None,
+    };
+    match expr_arena.get(input) {
+        AExpr::Column(name) => Some((input, name.clone(), method, alias)),
+        _ => None,
+    }
+}
+
+pub struct DefaultPlanner {
+    cancellation_token: Option<CancellationToken>,
+    progress_callback: Option<Arc<dyn ProgressCallback>>,
+    pub(crate) observer: Option<Arc<dyn QueryObserver>>,
+    profiles: Option<NodeProfiles>,
+    agg_fusion: bool,
+}
 impl Default for DefaultPlanner {
     fn default() -> Self {
-        Self {}
+        Self {
+            cancellation_token: None,
+            progress_callback: None,
+            observer: None,
+            profiles: None,
+            agg_fusion: true,
+        }
     }
 }
 
@@ -84,11 +341,171 @@ impl DefaultPlanner {
             .map(|e| self.create_physical_expr(e, context, expr_arena))
             .collect()
     }
+    /// Check the token between the nodes of the physical plan, so that the query
+    /// can be cancelled from another thread.
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Report the progress of the query to `callback` while it is executed.
+    pub fn with_progress_callback(mut self, callback: Arc<dyn ProgressCallback>) -> Self {
+        self.progress_callback = Some(callback);
+        self
+    }
+
+    /// Send the events of the query to `observer` while it is executed.
+    pub fn with_observer(mut self, observer: Arc<dyn QueryObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Record the output rows and execution time of every node in `profiles`.
+    pub(crate) fn with_profiles(mut self, profiles: NodeProfiles) -> Self {
+        self.profiles = Some(profiles);
+        self
+    }
+
+    /// Toggle the fusion of aggregations of the same column that are computed in a single pass,
+    /// e.g. the `min` and `max`.
+    pub fn with_agg_fusion(mut self, toggle: bool) -> Self {
+        self.agg_fusion = toggle;
+        self
+    }
+
+    /// Create the physical aggregations of a groupby, where pairs of aggregations of the same
+    /// column that can be fused are replaced by a single [FusedAggExpr](phys_expr::FusedAggExpr).
+    /// Returns whether aggregations were fused.
+    fn create_groupby_aggregations(
+        &self,
+        aggs: Vec<Node>,
+        expr_arena: &mut Arena<AExpr>,
+    ) -> Result<(Vec<Arc<dyn PhysicalExpr>>, bool)> {
+        let fusable = aggs
+            .iter()
+            .map(|&node| {
+                if self.agg_fusion {
+                    fusable_agg(node, expr_arena)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // the index of the aggregation that is fused with the aggregation at an index
+        let mut partner: Vec<Option<usize>> = vec![None; aggs.len()];
+        for i in 0..aggs.len() {
+            if let (Some(a), None) = (&fusable[i], partner[i]) {
+                let j = (i + 1..aggs.len()).find(|&j| match &fusable[j] {
+                    Some(b) => partner[j].is_none() && a.1 == b.1 && can_fuse_aggs(a.2, b.2),
+                    None => false,
+                });
+                if let Some(j) = j {
+                    partner[i] = Some(j);
+                    partner[j] = Some(i);
+                }
+            }
+        }
+
+        let mut phys_aggs = Vec::with_capacity(aggs.len());
+        for (i, node) in aggs.into_iter().enumerate() {
+            match partner[i] {
+                // the second aggregation of a pair is computed by the first
+                Some(j) if j < i => {}
+                Some(j) => {
+                    let (column, _, a, alias_a) = fusable[i].clone().unwrap();
+                    let (_, _, b, alias_b) = fusable[j].clone().unwrap();
+                    let input =
+                        self.create_physical_expr(column, Context::Aggregation, expr_arena)?;
+                    phys_aggs.push(Arc::new(phys_expr::FusedAggExpr::new(
+                        input,
+                        [a, b],
+                        [alias_a, alias_b],
+                    )) as Arc<dyn PhysicalExpr>)
+                }
+                None => {
+                    phys_aggs.push(self.create_physical_expr(
+                        node,
+                        Context::Aggregation,
+                        expr_arena,
+                    )?);
+                }
+            }
+        }
+        let fused = partner.iter().any(|p| p.is_some());
+        Ok((phys_aggs, fused))
+    }
+
     pub fn create_initial_physical_plan(
         &self,
         root: Node,
         lp_arena: &mut Arena<ALogicalPlan>,
         expr_arena: &mut Arena<AExpr>,
+    ) -> Result<Box<dyn Executor>> {
+        let description = self.describe_node(lp_arena.get(root));
+        let exec = self.create_executor(root, lp_arena, expr_arena)?;
+        Ok(self.wrap_executor(root, description, exec))
+    }
+
+    /// The description of a node, only computed if an executor wrapper needs it.
+    fn describe_node(&self, lp: &ALogicalPlan) -> Option<(&'static str, Option<String>)> {
+        if self.progress_callback.is_some() || self.observer.is_some() || cfg!(feature = "tracing")
+        {
+            Some(node_description(lp))
+        } else {
+            None
+        }
+    }
+
+    /// Wrap the executor of a node in the profiling, tracing, observing, progress and
+    /// cancellation executors that are configured on this planner.
+    fn wrap_executor(
+        &self,
+        node: Node,
+        description: Option<(&'static str, Option<String>)>,
+        mut exec: Box<dyn Executor>,
+    ) -> Box<dyn Executor> {
+        if let Some(profiles) = &self.profiles {
+            exec = Box::new(ProfiledExec {
+                input: exec,
+                node,
+                profiles: profiles.clone(),
+            });
+        }
+        #[cfg(feature = "tracing")]
+        if let Some((node, _)) = &description {
+            exec = Box::new(TracedExec { input: exec, node });
+        }
+        if let (Some(observer), Some((node, source))) = (&self.observer, &description) {
+            exec = Box::new(ObservedExec {
+                input: exec,
+                node: *node,
+                source: source.clone(),
+                observer: observer.clone(),
+            });
+        }
+        if let (Some(callback), Some((node, source))) = (&self.progress_callback, description) {
+            exec = Box::new(ProgressExec {
+                input: exec,
+                node,
+                source,
+                callback: callback.clone(),
+            });
+        }
+        match &self.cancellation_token {
+            Some(token) => Box::new(CancellableExec {
+                input: exec,
+                token: token.clone(),
+            }),
+            None => exec,
+        }
+    }
+
+    fn create_executor(
+        &self,
+        root: Node,
+        lp_arena: &mut Arena<ALogicalPlan>,
+        expr_arena: &mut Arena<AExpr>,
     ) -> Result<Box<dyn Executor>> {
         use ALogicalPlan::*;
         let logical_plan = lp_arena.take(root);
@@ -97,6 +514,7 @@ impl DefaultPlanner {
                 input,
                 id_vars,
                 value_vars,
+                maintain_order,
                 ..
             } => {
                 let input = self.create_initial_physical_plan(input, lp_arena, expr_arena)?;
@@ -104,6 +522,7 @@ impl DefaultPlanner {
                     input,
                     id_vars,
                     value_vars,
+                    maintain_order,
                 }))
             }
             Slice { input, offset, len } => {
@@ -127,11 +546,12 @@ impl DefaultPlanner {
                 predicate,
                 aggregate,
                 cache,
+                column_transformers,
             } => {
                 let predicate = predicate
                     .map(|pred| self.create_physical_expr(pred, Context::Other, expr_arena))
                     .map_or(Ok(None), |v| v.map(Some))?;
-                let aggregate = aggregate_expr_to_scan_agg(aggregate, expr_arena);
+                let aggregate = aggregate_expr_to_scan_agg(aggregate, expr_arena)?;
                 Ok(Box::new(CsvExec::new(
                     path,
                     schema,
@@ -144,6 +564,7 @@ impl DefaultPlanner {
                     predicate,
                     aggregate,
                     cache,
+                    column_transformers,
                 )))
             }
             #[cfg(feature = "parquet")]
@@ -155,12 +576,13 @@ impl DefaultPlanner {
                 aggregate,
                 stop_after_n_rows,
                 cache,
+                column_transformers,
             } => {
                 let predicate = predicate
                     .map(|pred| self.create_physical_expr(pred, Context::Other, expr_arena))
                     .map_or(Ok(None), |v| v.map(Some))?;
 
-                let aggregate = aggregate_expr_to_scan_agg(aggregate, expr_arena);
+                let aggregate = aggregate_expr_to_scan_agg(aggregate, expr_arena)?;
                 Ok(Box::new(ParquetExec::new(
                     path,
                     schema,
@@ -169,6 +591,34 @@ impl DefaultPlanner {
                     aggregate,
                     stop_after_n_rows,
                     cache,
+                    column_transformers,
+                )))
+            }
+            #[cfg(feature = "ipc")]
+            IpcScan {
+                path,
+                schema,
+                with_columns,
+                predicate,
+                aggregate,
+                stop_after_n_rows,
+                cache,
+                column_transformers,
+            } => {
+                let predicate = predicate
+                    .map(|pred| self.create_physical_expr(pred, Context::Other, expr_arena))
+                    .map_or(Ok(None), |v| v.map(Some))?;
+
+                let aggregate = aggregate_expr_to_scan_agg(aggregate, expr_arena)?;
+                Ok(Box::new(IpcExec::new(
+                    path,
+                    schema,
+                    with_columns,
+                    predicate,
+                    aggregate,
+                    stop_after_n_rows,
+                    cache,
+                    column_transformers,
                 )))
             }
             Projection { expr, input, .. } => {
@@ -197,34 +647,30 @@ impl DefaultPlanner {
                     .map_or(Ok(None), |v| v.map(Some))?;
                 Ok(Box::new(DataFrameExec::new(df, projection, selection)))
             }
-            Sort {
-                input,
-                by_column,
-                reverse,
-            } => {
+            Sort { input, by, reverse } => {
                 let input = self.create_initial_physical_plan(input, lp_arena, expr_arena)?;
-                Ok(Box::new(SortExec {
-                    input,
-                    by_column,
-                    reverse,
-                }))
+                let by = self.create_physical_expressions(by, Context::Other, expr_arena)?;
+                Ok(Box::new(SortExec { input, by, reverse }))
             }
             Explode { input, columns } => {
                 let input = self.create_initial_physical_plan(input, lp_arena, expr_arena)?;
                 Ok(Box::new(ExplodeExec { input, columns }))
             }
-            Cache { input } => {
-                let fields = lp_arena.get(input).schema(lp_arena).fields();
-                // todo! fix the unique constraint in the schema. Probably in projection pushdown at joins
-                let mut unique =
-                    HashSet::with_capacity_and_hasher(fields.len(), RandomState::default());
-                // assumption of 80 characters per column name
-                let mut key = String::with_capacity(fields.len() * 80);
-                for field in fields {
-                    if unique.insert(field.name()) {
-                        key.push_str(field.name())
-                    }
-                }
+            Rename {
+                input,
+                existing,
+                new,
+                ..
+            } => {
+                let input = self.create_initial_physical_plan(input, lp_arena, expr_arena)?;
+                Ok(Box::new(RenameExec {
+                    input,
+                    existing,
+                    new,
+                }))
+            }
+            Cache { input, id } => {
+                let key = format!("cache_{}", id);
                 let input = self.create_initial_physical_plan(input, lp_arena, expr_arena)?;
                 Ok(Box::new(CacheExec { key, input }))
             }
@@ -246,7 +692,7 @@ impl DefaultPlanner {
                 keys,
                 aggs,
                 apply,
-                ..
+                schema,
             } => {
                 let input = self.create_initial_physical_plan(input, lp_arena, expr_arena)?;
                 let mut partitionable = true;
@@ -294,10 +740,14 @@ impl DefaultPlanner {
                             .collect(),
                     )))
                 } else {
-                    let phys_aggs =
-                        self.create_physical_expressions(aggs, Context::Aggregation, expr_arena)?;
+                    let (phys_aggs, fused) = self.create_groupby_aggregations(aggs, expr_arena)?;
+                    let fused_schema = if fused { Some(schema) } else { None };
                     Ok(Box::new(GroupByExec::new(
-                        input, phys_keys, phys_aggs, apply,
+                        input,
+                        phys_keys,
+                        phys_aggs,
+                        apply,
+                        fused_schema,
                     )))
                 }
             }
@@ -307,13 +757,12 @@ impl DefaultPlanner {
                 how,
                 left_on,
                 right_on,
-                allow_par,
-                force_par,
+                options,
                 ..
             } => {
-                let parallel = if force_par {
-                    force_par
-                } else if allow_par {
+                let parallel = if options.force_parallel {
+                    true
+                } else if options.allow_parallel {
                     // check if two DataFrames come from a separate source. If they don't we hope it is cached.
                     let mut sources_left =
                         HashSet::with_capacity_and_hasher(32, RandomState::default());
@@ -340,12 +789,62 @@ impl DefaultPlanner {
                 Ok(Box::new(JoinExec::new(
                     input_left,
                     input_right,
-                    how,
                     left_on,
                     right_on,
                     parallel,
+                    options.args(how),
                 )))
             }
+            SetOp {
+                input_left,
+                input_right,
+                op,
+            } => {
+                let input_left =
+                    self.create_initial_physical_plan(input_left, lp_arena, expr_arena)?;
+                let input_right =
+                    self.create_initial_physical_plan(input_right, lp_arena, expr_arena)?;
+                Ok(Box::new(SetOpExec {
+                    input_left,
+                    input_right,
+                    op,
+                }))
+            }
+            Union { inputs } => {
+                let inputs = inputs
+                    .into_iter()
+                    .map(|input| self.create_initial_physical_plan(input, lp_arena, expr_arena))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(Box::new(UnionExec { inputs }))
+            }
+            JoinWhere {
+                input_left,
+                input_right,
+                predicates,
+                suffix,
+                ..
+            } => {
+                let bounds = range_bounds(
+                    &predicates,
+                    lp_arena.get(input_left).schema(lp_arena),
+                    lp_arena.get(input_right).schema(lp_arena),
+                    suffix.as_deref().unwrap_or("_right"),
+                    expr_arena,
+                );
+                let input_left =
+                    self.create_initial_physical_plan(input_left, lp_arena, expr_arena)?;
+                let input_right =
+                    self.create_initial_physical_plan(input_right, lp_arena, expr_arena)?;
+                let predicates =
+                    self.create_physical_expressions(predicates, Context::Other, expr_arena)?;
+                Ok(Box::new(JoinWhereExec {
+                    input_left,
+                    input_right,
+                    predicates,
+                    bounds,
+                    suffix,
+                }))
+            }
             HStack { input, exprs, .. } => {
                 let input = self.create_initial_physical_plan(input, lp_arena, expr_arena)?;
                 let phys_expr =
@@ -358,9 +857,192 @@ impl DefaultPlanner {
                 let input = self.create_initial_physical_plan(input, lp_arena, expr_arena)?;
                 Ok(Box::new(UdfExec { input, function }))
             }
+            Sink {
+                input,
+                path,
+                file_type,
+                column_transformers,
+            } => match batch_source(input, lp_arena, expr_arena) {
+                Some(source_node) => {
+                    let slot = BatchSlot::default();
+                    let source = self.create_batch_source(source_node, lp_arena, expr_arena)?;
+                    let input = self.create_batch_executor(
+                        input,
+                        source_node,
+                        &slot,
+                        lp_arena,
+                        expr_arena,
+                    )?;
+                    Ok(Box::new(SinkExec {
+                        input,
+                        source: Some((source, slot)),
+                        path,
+                        file_type,
+                        column_transformers,
+                    }))
+                }
+                None => {
+                    let input = self.create_initial_physical_plan(input, lp_arena, expr_arena)?;
+                    Ok(Box::new(SinkExec {
+                        input,
+                        source: None,
+                        path,
+                        file_type,
+                        column_transformers,
+                    }))
+                }
+            },
         }
     }
 
+    /// Create the source of a query that is executed batch by batch from the scan found by
+    /// [batch_source].
+    fn create_batch_source(
+        &self,
+        node: Node,
+        lp_arena: &mut Arena<ALogicalPlan>,
+        expr_arena: &mut Arena<AExpr>,
+    ) -> Result<BatchSource> {
+        match lp_arena.take(node) {
+            ALogicalPlan::DataFrameScan {
+                df,
+                projection,
+                selection,
+                ..
+            } => {
+                let selection = selection
+                    .map(|pred| self.create_physical_expr(pred, Context::Other, expr_arena))
+                    .map_or(Ok(None), |v| v.map(Some))?;
+                let projection = projection
+                    .map(|proj| self.create_physical_expressions(proj, Context::Other, expr_arena))
+                    .map_or(Ok(None), |v| v.map(Some))?;
+                Ok(BatchSource::DataFrame {
+                    df,
+                    projection,
+                    selection,
+                })
+            }
+            ALogicalPlan::CsvScan {
+                path,
+                schema,
+                has_header,
+                delimiter,
+                ignore_errors,
+                skip_rows,
+                stop_after_n_rows,
+                with_columns,
+                predicate,
+                column_transformers,
+                ..
+            } => {
+                let predicate = predicate
+                    .map(|pred| self.create_physical_expr(pred, Context::Other, expr_arena))
+                    .map_or(Ok(None), |v| v.map(Some))?;
+                Ok(BatchSource::Csv {
+                    path,
+                    schema,
+                    has_header,
+                    delimiter,
+                    ignore_errors,
+                    skip_rows,
+                    stop_after_n_rows,
+                    with_columns,
+                    predicate,
+                    column_transformers,
+                })
+            }
+            #[cfg(feature = "ipc")]
+            ALogicalPlan::IpcScan {
+                path,
+                schema,
+                with_columns,
+                predicate,
+                stop_after_n_rows,
+                column_transformers,
+                ..
+            } => {
+                let predicate = predicate
+                    .map(|pred| self.create_physical_expr(pred, Context::Other, expr_arena))
+                    .map_or(Ok(None), |v| v.map(Some))?;
+                Ok(BatchSource::Ipc {
+                    path,
+                    schema,
+                    with_columns,
+                    predicate,
+                    stop_after_n_rows,
+                    column_transformers,
+                })
+            }
+            #[cfg(feature = "parquet")]
+            ALogicalPlan::ParquetScan {
+                path,
+                schema,
+                with_columns,
+                predicate,
+                stop_after_n_rows,
+                column_transformers,
+                ..
+            } => {
+                let predicate = predicate
+                    .map(|pred| self.create_physical_expr(pred, Context::Other, expr_arena))
+                    .map_or(Ok(None), |v| v.map(Some))?;
+                Ok(BatchSource::Parquet {
+                    path,
+                    schema,
+                    with_columns,
+                    predicate,
+                    stop_after_n_rows,
+                    column_transformers,
+                })
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Create the executors of the nodes above the batch source, which process the batch in
+    /// `slot` instead of the output of the scan.
+    fn create_batch_executor(
+        &self,
+        node: Node,
+        source: Node,
+        slot: &BatchSlot,
+        lp_arena: &mut Arena<ALogicalPlan>,
+        expr_arena: &mut Arena<AExpr>,
+    ) -> Result<Box<dyn Executor>> {
+        use ALogicalPlan::*;
+        let description = self.describe_node(lp_arena.get(node));
+        if node == source {
+            // the bytes of the source are read by the sink, not by the executor of a batch
+            let description = description.map(|(node, _)| (node, None));
+            let exec = Box::new(BatchExec { slot: slot.clone() });
+            return Ok(self.wrap_executor(node, description, exec));
+        }
+        let exec: Box<dyn Executor> = match lp_arena.take(node) {
+            Selection { input, predicate } => {
+                let input =
+                    self.create_batch_executor(input, source, slot, lp_arena, expr_arena)?;
+                let predicate = self.create_physical_expr(predicate, Context::Other, expr_arena)?;
+                Box::new(FilterExec::new(predicate, input))
+            }
+            Projection { expr, input, .. } | LocalProjection { expr, input, .. } => {
+                let input =
+                    self.create_batch_executor(input, source, slot, lp_arena, expr_arena)?;
+                let phys_expr =
+                    self.create_physical_expressions(expr, Context::Other, expr_arena)?;
+                Box::new(StandardExec::new("projection", input, phys_expr))
+            }
+            HStack { input, exprs, .. } => {
+                let input =
+                    self.create_batch_executor(input, source, slot, lp_arena, expr_arena)?;
+                let phys_expr =
+                    self.create_physical_expressions(exprs, Context::Other, expr_arena)?;
+                Box::new(StackExec::new(input, phys_expr))
+            }
+            _ => unreachable!(),
+        };
+        Ok(self.wrap_executor(node, description, exec))
+    }
+
     pub fn create_physical_expr(
         &self,
         expression: Node,
@@ -378,11 +1060,20 @@ impl DefaultPlanner {
                 // TODO! Order by
                 let group_column = aexpr_to_root_names(partition_by, expr_arena)
                     .pop()
-                    .expect("need a partition_by column for a window function");
+                    .ok_or_else(|| {
+                        PolarsError::InvalidOperation(
+                            "need a partition_by column for a window function".into(),
+                        )
+                    })?;
                 let out_name;
-                let apply_column = aexpr_to_root_names(function, expr_arena)
-                    .pop()
-                    .expect("need a root column for a window function");
+                let apply_column =
+                    aexpr_to_root_names(function, expr_arena)
+                        .pop()
+                        .ok_or_else(|| {
+                            PolarsError::InvalidOperation(
+                                "need a root column for a window function".into(),
+                            )
+                        })?;
 
                 if let Alias(expr, name) = expr_arena.get(function) {
                     function = *expr;
@@ -565,7 +1256,7 @@ impl DefaultPlanner {
                                 let function = NoEq::new(Arc::new(move |s: Series| {
                                     let len = s.len() as f64;
                                     parallel_op(|s| Ok(s.sum_as_series()), s, None)
-                                        .map(|s| s.cast::<Float64Type>().unwrap() / len)
+                                        .and_then(|s| Ok(s.cast::<Float64Type>()? / len))
                                 })
                                     as Arc<dyn SeriesUdf>);
                                 Ok(Arc::new(ApplyExpr {
@@ -640,11 +1331,10 @@ impl DefaultPlanner {
                             Context::Aggregation => {
                                 Ok(Arc::new(PhysicalAggExpr::new(input, GroupByMethod::List)))
                             }
-                            Context::Other => {
-                                panic!(
-                                    "list expression is only supported in the aggregation context"
-                                )
-                            }
+                            Context::Other => Err(PolarsError::InvalidOperation(
+                                "list expression is only supported in the aggregation context"
+                                    .into(),
+                            )),
                         }
                     }
                     AAggExpr::NUnique(expr) => {
@@ -657,7 +1347,7 @@ impl DefaultPlanner {
                             Context::Other => {
                                 let function = NoEq::new(Arc::new(move |s: Series| {
                                     s.n_unique().map(|count| {
-                                        UInt32Chunked::new_from_slice(s.name(), &[count as u32])
+                                        IdxCa::new_from_slice(s.name(), &[count as IdxSize])
                                             .into_series()
                                     })
                                 })
@@ -665,7 +1355,30 @@ impl DefaultPlanner {
                                 Ok(Arc::new(ApplyExpr {
                                     input,
                                     function,
-                                    output_type: Some(DataType::UInt32),
+                                    output_type: Some(IdxType::get_dtype()),
+                                    expr: node_to_exp(expression, expr_arena),
+                                }))
+                            }
+                        }
+                    }
+                    AAggExpr::NullCount(expr) => {
+                        let input = self.create_physical_expr(expr, ctxt, expr_arena)?;
+                        match ctxt {
+                            Context::Aggregation => Ok(Arc::new(PhysicalAggExpr::new(
+                                input,
+                                GroupByMethod::NullCount,
+                            ))),
+                            Context::Other => {
+                                let function = NoEq::new(Arc::new(move |s: Series| {
+                                    let count = s.null_count();
+                                    Ok(IdxCa::new_from_slice(s.name(), &[count as IdxSize])
+                                        .into_series())
+                                })
+                                    as Arc<dyn SeriesUdf>);
+                                Ok(Arc::new(ApplyExpr {
+                                    input,
+                                    function,
+                                    output_type: Some(IdxType::get_dtype()),
                                     expr: node_to_exp(expression, expr_arena),
                                 }))
                             }
@@ -692,9 +1405,29 @@ impl DefaultPlanner {
                             }
                         }
                     }
+                    AAggExpr::TopK { expr, k } => {
+                        let input = self.create_physical_expr(expr, ctxt, expr_arena)?;
+                        match ctxt {
+                            Context::Aggregation => Ok(Arc::new(AggTopKExpr::new(input, k))),
+                            Context::Other => {
+                                let function = NoEq::new(
+                                    Arc::new(move |s: Series| s.top_k(k)) as Arc<dyn SeriesUdf>
+                                );
+                                Ok(Arc::new(ApplyExpr {
+                                    input,
+                                    function,
+                                    output_type: None,
+                                    expr: node_to_exp(expression, expr_arena),
+                                }))
+                            }
+                        }
+                    }
                     AAggExpr::AggGroups(expr) => {
                         if let Context::Other = ctxt {
-                            panic!("agg groups expression only supported in aggregation context")
+                            return Err(PolarsError::InvalidOperation(
+                                "agg groups expression only supported in aggregation context"
+                                    .into(),
+                            ));
                         }
                         let phys_expr = self.create_physical_expr(expr, ctxt, expr_arena)?;
                         Ok(Arc::new(PhysicalAggExpr::new(
@@ -711,14 +1444,14 @@ impl DefaultPlanner {
                             Context::Other => {
                                 let function = NoEq::new(Arc::new(move |s: Series| {
                                     let count = s.len();
-                                    Ok(UInt32Chunked::new_from_slice(s.name(), &[count as u32])
+                                    Ok(IdxCa::new_from_slice(s.name(), &[count as IdxSize])
                                         .into_series())
                                 })
                                     as Arc<dyn SeriesUdf>);
                                 Ok(Arc::new(ApplyExpr {
                                     input,
                                     function,
-                                    output_type: Some(DataType::UInt32),
+                                    output_type: Some(IdxType::get_dtype()),
                                     expr: node_to_exp(expression, expr_arena),
                                 }))
                             }
@@ -785,6 +1518,44 @@ impl DefaultPlanner {
                     node_to_exp(expression, expr_arena),
                 )))
             }
+            Standardize(expr) => {
+                let input = self.create_physical_expr(expr, ctxt, expr_arena)?;
+                let function =
+                    NoEq::new(Arc::new(move |s: Series| s.standardize()) as Arc<dyn SeriesUdf>);
+                Ok(Arc::new(ApplyExpr::new(
+                    input,
+                    function,
+                    None,
+                    node_to_exp(expression, expr_arena),
+                )))
+            }
+            MinMaxScale(expr) => {
+                let input = self.create_physical_expr(expr, ctxt, expr_arena)?;
+                let function =
+                    NoEq::new(Arc::new(move |s: Series| s.min_max_scale()) as Arc<dyn SeriesUdf>);
+                Ok(Arc::new(ApplyExpr::new(
+                    input,
+                    function,
+                    None,
+                    node_to_exp(expression, expr_arena),
+                )))
+            }
+            ClipQuantile {
+                input,
+                low_q,
+                high_q,
+            } => {
+                let input = self.create_physical_expr(input, ctxt, expr_arena)?;
+                let function =
+                    NoEq::new(Arc::new(move |s: Series| s.clip_quantile(low_q, high_q))
+                        as Arc<dyn SeriesUdf>);
+                Ok(Arc::new(ApplyExpr::new(
+                    input,
+                    function,
+                    None,
+                    node_to_exp(expression, expr_arena),
+                )))
+            }
             Slice {
                 input,
                 offset,
@@ -808,6 +1579,21 @@ impl DefaultPlanner {
                     node_to_exp(expression, expr_arena),
                 )))
             }
+            CumCount(expr) => {
+                let input = self.create_physical_expr(expr, ctxt, expr_arena)?;
+                let function = NoEq::new(Arc::new(move |s: Series| {
+                    let ca: NoNull<IdxCa> = (0..s.len() as IdxSize).collect();
+                    let mut ca = ca.into_inner();
+                    ca.rename(s.name());
+                    Ok(ca.into_series())
+                }) as Arc<dyn SeriesUdf>);
+                Ok(Arc::new(ApplyExpr::new(
+                    input,
+                    function,
+                    None,
+                    node_to_exp(expression, expr_arena),
+                )))
+            }
             Duplicated(expr) => {
                 let input = self.create_physical_expr(expr, ctxt, expr_arena)?;
                 let function = NoEq::new(Arc::new(move |s: Series| {
@@ -845,8 +1631,12 @@ impl DefaultPlanner {
                     node_to_exp(expression, expr_arena),
                 )))
             }
-            Wildcard => panic!("should be no wildcard at this point"),
-            Except(_) => panic!("should be no except at this point"),
+            Wildcard => Err(PolarsError::InvalidOperation(
+                "a wildcard can only be used in a projection or an aggregation".into(),
+            )),
+            Except(_) => Err(PolarsError::InvalidOperation(
+                "an exception can only be used in a projection or an aggregation".into(),
+            )),
         }
     }
 }
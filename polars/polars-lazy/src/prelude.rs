@@ -3,14 +3,17 @@ pub use crate::{
     frame::*,
     logical_plan::{
         optimizer::{type_coercion::TypeCoercionRule, Optimize, *},
-        DataFrameUdf, LiteralValue, LogicalPlan, LogicalPlanBuilder,
+        DataFrameUdf, LiteralValue, LogicalPlan, LogicalPlanBuilder, SinkFileType,
     },
     physical_plan::{
         executors::{CsvExec, DataFrameExec, FilterExec, GroupByExec, StandardExec},
         expressions::*,
         planner::DefaultPlanner,
-        Executor, PhysicalExpr, PhysicalPlanner,
+        CancellationToken, Executor, PhysicalExpr, PhysicalPlan, PhysicalPlanner, ProgressCallback,
+        ProgressEvent, QueryEvent, QueryObserver,
     },
 };
 
+#[cfg(feature = "sql")]
+pub use crate::sql::SQLContext;
 pub use polars_core::utils::{Arena, Node};
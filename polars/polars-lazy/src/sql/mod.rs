@@ -0,0 +1,524 @@
+//! A SQL frontend for the lazy API.
+//!
+//! Queries are parsed and compiled to a [LazyFrame], so they are optimized and executed like any
+//! other lazy query. The supported subset of SQL is a single `SELECT` with:
+//!
+//! * `DISTINCT`, `*` and `[AS] alias` in the projection;
+//! * `[INNER] JOIN`, `LEFT [OUTER] JOIN` and `FULL [OUTER] JOIN` on equalities of columns;
+//! * `WHERE` with comparisons, arithmetic, `AND`, `OR`, `NOT` and `IS [NOT] NULL`;
+//! * `GROUP BY` with the aggregations `sum`, `min`, `max`, `avg`, `mean`, `median`, `first`,
+//!   `last`, `count(*)`, `count(column)` and `count(DISTINCT column)`;
+//! * `ORDER BY` on the output columns, `LIMIT` and `OFFSET`.
+//!
+//! Columns can be qualified with the name or alias of their table. Columns of a joined table that
+//! clash with a column of the tables before it are named with the `_right` suffix in the output,
+//! an unqualified reference to a clashing column is an error.
+//!
+//! # Example
+//!
+//! ```rust
+//! use polars_core::prelude::*;
+//! use polars_lazy::prelude::*;
+//!
+//! fn example(df: DataFrame) -> Result<DataFrame> {
+//!     let mut ctx = SQLContext::new();
+//!     ctx.register("t", df.lazy());
+//!     ctx.execute("SELECT a, sum(b) AS total FROM t GROUP BY a ORDER BY total DESC")?
+//!         .collect()
+//! }
+//! ```
+mod parser;
+
+use crate::prelude::*;
+use parser::{parse_query, sql_err, JoinClause, Query, SqlExpr, TableRef};
+use polars_core::prelude::*;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// Registry of the tables that can be queried with SQL.
+#[derive(Default, Clone)]
+pub struct SQLContext {
+    tables: HashMap<String, LazyFrame>,
+}
+
+impl SQLContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a lazy query as table `name`. A table that was registered under the same name
+    /// is replaced.
+    pub fn register(&mut self, name: &str, lf: LazyFrame) {
+        self.tables.insert(name.to_string(), lf);
+    }
+
+    /// Compile the SQL `query` to a [LazyFrame].
+    pub fn execute(&self, query: &str) -> Result<LazyFrame> {
+        let query = parse_query(query)?;
+        self.plan_query(query)
+    }
+
+    fn get_table(&self, table: &TableRef) -> Result<LazyFrame> {
+        self.tables
+            .get(&table.name)
+            .cloned()
+            .ok_or_else(|| PolarsError::NotFound(format!("table {}", table.name)))
+    }
+
+    fn plan_query(&self, query: Query) -> Result<LazyFrame> {
+        let mut lf = self.get_table(&query.from)?;
+        let mut scope = Scope::default();
+        scope.push(
+            &query.from,
+            lf.logical_plan
+                .schema()
+                .fields()
+                .iter()
+                .map(|f| (f.name().clone(), f.name().clone()))
+                .collect(),
+        );
+        for join in &query.joins {
+            let right = self.get_table(&join.table)?;
+            let (left_on, right_on) = join_keys(join, &scope)?;
+            // the output names of the columns of the right table, like the join computes them
+            let schema_left = lf.logical_plan.schema().clone();
+            let columns = right
+                .logical_plan
+                .schema()
+                .fields()
+                .iter()
+                .map(|f| {
+                    let name = f.name();
+                    let out = match right_on.iter().position(|key| key == name) {
+                        // the right keys are dropped, their values are in the left keys
+                        Some(idx) => left_on[idx].clone(),
+                        None if schema_left.field_with_name(name).is_ok() => {
+                            format!("{}_right", name)
+                        }
+                        None => name.clone(),
+                    };
+                    (name.clone(), out)
+                })
+                .collect();
+            scope.push(&join.table, columns);
+            lf = lf.join(
+                right,
+                left_on.iter().map(|name| col(name)).collect(),
+                right_on.iter().map(|name| col(name)).collect(),
+                None,
+                join.how,
+            );
+        }
+        if let Some(selection) = &query.selection {
+            lf = lf.filter(to_expr(selection, &scope)?);
+        }
+
+        lf = if query.group_by.is_empty() {
+            let is_wildcard = matches!(
+                query.projection.as_slice(),
+                [item] if item.expr == SqlExpr::Wildcard && item.alias.is_none()
+            );
+            if is_wildcard {
+                lf
+            } else {
+                let exprs = query
+                    .projection
+                    .iter()
+                    .map(|item| {
+                        let expr = to_expr(&item.expr, &scope)?;
+                        Ok(match &item.alias {
+                            Some(alias) => expr.alias(alias),
+                            None => expr,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                lf.select(exprs)
+            }
+        } else {
+            plan_groupby(lf, &query, &scope)?
+        };
+
+        if query.distinct {
+            lf = lf.drop_duplicates(true, None);
+        }
+        if !query.order_by.is_empty() {
+            let by = query
+                .order_by
+                .iter()
+                .map(|(expr, _)| match expr {
+                    // an alias of the projection
+                    SqlExpr::Column { table: None, name }
+                        if query
+                            .projection
+                            .iter()
+                            .any(|item| item.alias.as_ref() == Some(name)) =>
+                    {
+                        Ok(col(name))
+                    }
+                    _ => to_expr(expr, &scope),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let reverse = query.order_by.iter().map(|(_, desc)| *desc).collect();
+            lf = lf.sort_by_exprs(by, reverse);
+        }
+        match (query.limit, query.offset) {
+            (Some(limit), offset) => Ok(lf.slice(offset.unwrap_or(0), limit)),
+            (None, Some(_)) => Err(sql_err("OFFSET requires a LIMIT")),
+            (None, None) => Ok(lf),
+        }
+    }
+}
+
+/// The tables of a query and where their columns are in the joined [LazyFrame]. Columns of a
+/// joined table that clash with a column of the tables before it get the `_right` suffix.
+#[derive(Default)]
+struct Scope {
+    tables: Vec<ScopeTable>,
+}
+
+struct ScopeTable {
+    name: String,
+    alias: Option<String>,
+    /// The name of every column in the table and in the joined frame.
+    columns: Vec<(String, String)>,
+}
+
+impl Scope {
+    fn push(&mut self, table: &TableRef, columns: Vec<(String, String)>) {
+        self.tables.push(ScopeTable {
+            name: table.name.clone(),
+            alias: table.alias.clone(),
+            columns,
+        })
+    }
+
+    /// Get the name in the joined frame of column `name`, qualified with the name or alias of
+    /// `table`. An unqualified column must be in a single table, names that are in none of the
+    /// tables, like the aliases of the projection, are kept.
+    fn resolve(&self, table: &Option<String>, name: &str) -> Result<String> {
+        let lookup = |t: &ScopeTable| {
+            t.columns
+                .iter()
+                .find(|(column, _)| column == name)
+                .map(|(_, out)| out.clone())
+        };
+        match table {
+            Some(table) => {
+                let t = self
+                    .tables
+                    .iter()
+                    .find(|t| t.alias.as_ref() == Some(table))
+                    .or_else(|| self.tables.iter().find(|t| t.name == *table))
+                    .ok_or_else(|| PolarsError::NotFound(format!("table {}", table)))?;
+                lookup(t).ok_or_else(|| PolarsError::NotFound(format!("column {}.{}", table, name)))
+            }
+            None => {
+                let mut candidates = self.tables.iter().filter_map(lookup).collect::<Vec<_>>();
+                candidates.sort_unstable();
+                candidates.dedup();
+                match candidates.as_slice() {
+                    [] => Ok(name.to_string()),
+                    [out] => Ok(out.clone()),
+                    _ => Err(sql_err(format!(
+                        "column {} is ambiguous, qualify it with the name of its table",
+                        name
+                    ))),
+                }
+            }
+        }
+    }
+
+    /// The number of rows, counted on the first column of the first table.
+    fn row_count(&self) -> Result<Expr> {
+        match self.tables.first().and_then(|t| t.columns.first()) {
+            Some((_, out)) => Ok(col(out).count().alias("count")),
+            None => Err(sql_err("count(*) of a table without columns")),
+        }
+    }
+}
+
+/// Aggregate the projection of a `GROUP BY` query. The projected keys are selected, all other
+/// projected expressions are aggregations. The output is in the order of the projection.
+fn plan_groupby(lf: LazyFrame, query: &Query, scope: &Scope) -> Result<LazyFrame> {
+    let keys = query
+        .group_by
+        .iter()
+        .map(|expr| to_expr(expr, scope))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut aggs = vec![];
+    // per projected item the index of the key, or of the aggregation after the keys
+    let mut positions = Vec::with_capacity(query.projection.len());
+    for item in &query.projection {
+        let expr = to_expr(&item.expr, scope)?;
+        match keys.iter().position(|key| *key == expr) {
+            Some(idx) => positions.push(idx),
+            None => {
+                positions.push(keys.len() + aggs.len());
+                aggs.push(expr);
+            }
+        }
+    }
+
+    let lf = lf.groupby(keys).agg(aggs);
+    let schema = lf.logical_plan.schema().clone();
+    let exprs = query
+        .projection
+        .iter()
+        .zip(positions)
+        .map(|(item, idx)| {
+            let expr = col(schema.field(idx).unwrap().name());
+            match &item.alias {
+                Some(alias) => expr.alias(alias),
+                None => expr,
+            }
+        })
+        .collect::<Vec<_>>();
+    Ok(lf.select(exprs))
+}
+
+/// Get the left and right join keys of the `ON` condition of a join. The condition must be an
+/// equality of two columns, or multiple of those combined with `AND`. A column that is qualified
+/// with the name or alias of the joined table is a right key. The left keys are the names in the
+/// frame that is joined so far, the right keys the names in the joined table.
+fn join_keys(join: &JoinClause, scope: &Scope) -> Result<(Vec<String>, Vec<String>)> {
+    let mut equalities = vec![];
+    flatten_and(&join.on, &mut equalities);
+
+    let is_right = |table: &Option<String>| match table {
+        Some(table) => *table == join.table.name || Some(table) == join.table.alias.as_ref(),
+        None => false,
+    };
+    let mut left_on = vec![];
+    let mut right_on = vec![];
+    for expr in equalities {
+        match expr {
+            SqlExpr::Binary {
+                left,
+                op: Operator::Eq,
+                right,
+            } => match (left.as_ref(), right.as_ref()) {
+                (
+                    SqlExpr::Column {
+                        table: left_table,
+                        name: left_name,
+                    },
+                    SqlExpr::Column {
+                        table: right_table,
+                        name: right_name,
+                    },
+                ) => {
+                    if is_right(left_table) && !is_right(right_table) {
+                        left_on.push(scope.resolve(right_table, right_name)?);
+                        right_on.push(left_name.clone());
+                    } else {
+                        left_on.push(scope.resolve(left_table, left_name)?);
+                        right_on.push(right_name.clone());
+                    }
+                }
+                _ => {
+                    return Err(sql_err(format!(
+                        "join conditions must compare two columns, got {:?}",
+                        expr
+                    )))
+                }
+            },
+            _ => {
+                return Err(sql_err(format!(
+                    "only equality join conditions are supported, got {:?}",
+                    expr
+                )))
+            }
+        }
+    }
+    Ok((left_on, right_on))
+}
+
+fn flatten_and<'a>(expr: &'a SqlExpr, out: &mut Vec<&'a SqlExpr>) {
+    match expr {
+        SqlExpr::Binary {
+            left,
+            op: Operator::And,
+            right,
+        } => {
+            flatten_and(left, out);
+            flatten_and(right, out);
+        }
+        _ => out.push(expr),
+    }
+}
+
+fn to_expr(expr: &SqlExpr, scope: &Scope) -> Result<Expr> {
+    let expr = match expr {
+        SqlExpr::Column { table, name } => col(&scope.resolve(table, name)?),
+        SqlExpr::Wildcard => col("*"),
+        SqlExpr::Int(v) => match i32::try_from(*v) {
+            Ok(v) => lit(v),
+            Err(_) => lit(*v),
+        },
+        SqlExpr::Float(v) => lit(*v),
+        SqlExpr::Str(v) => lit(v.as_str()),
+        SqlExpr::Bool(v) => lit(*v),
+        SqlExpr::Null => Expr::Literal(LiteralValue::Null),
+        SqlExpr::Binary { left, op, right } => {
+            binary_expr(to_expr(left, scope)?, *op, to_expr(right, scope)?)
+        }
+        SqlExpr::Not(e) => to_expr(e, scope)?.not(),
+        SqlExpr::Neg(e) => binary_expr(lit(0), Operator::Minus, to_expr(e, scope)?),
+        SqlExpr::IsNull { expr, negated } => {
+            let expr = to_expr(expr, scope)?;
+            if *negated {
+                expr.is_not_null()
+            } else {
+                expr.is_null()
+            }
+        }
+        SqlExpr::Function {
+            name,
+            distinct: false,
+            args,
+        } if name == "count" && args.as_slice() == [SqlExpr::Wildcard] => scope.row_count()?,
+        SqlExpr::Function {
+            name,
+            distinct,
+            args,
+        } => {
+            let arg = match args.as_slice() {
+                [arg] => to_expr(arg, scope)?,
+                _ => {
+                    return Err(sql_err(format!(
+                        "{} expects a single argument, got {}",
+                        name,
+                        args.len()
+                    )))
+                }
+            };
+            match (name.as_str(), distinct) {
+                ("count", true) => arg.n_unique(),
+                ("count", false) => arg.count(),
+                (_, true) => return Err(sql_err(format!("DISTINCT is not supported in {}", name))),
+                ("sum", _) => arg.sum(),
+                ("min", _) => arg.min(),
+                ("max", _) => arg.max(),
+                ("avg", _) | ("mean", _) => arg.mean(),
+                ("median", _) => arg.median(),
+                ("first", _) => arg.first(),
+                ("last", _) => arg.last(),
+                _ => return Err(sql_err(format!("unsupported function {}", name))),
+            }
+        }
+    };
+    Ok(expr)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_context() -> SQLContext {
+        let sales = df! {
+            "shop" => &["a", "b", "a", "c", "b"],
+            "amount" => &[1, 2, 3, 4, 5],
+        }
+        .unwrap();
+        let shops = df! {
+            "id" => &["a", "b"],
+            "city" => &["Amsterdam", "Berlin"],
+        }
+        .unwrap();
+        let targets = df! {
+            "shop" => &["a", "b"],
+            "amount" => &[10, 20],
+        }
+        .unwrap();
+        let mut ctx = SQLContext::new();
+        ctx.register("sales", sales.lazy());
+        ctx.register("shops", shops.lazy());
+        ctx.register("targets", targets.lazy());
+        ctx
+    }
+
+    #[test]
+    fn test_sql_select_filter() -> Result<()> {
+        let ctx = get_context();
+        let df = ctx
+            .execute(
+                "SELECT shop, amount * 2 AS double FROM sales \
+                 WHERE amount > 1 AND NOT shop = 'c' ORDER BY amount DESC LIMIT 2",
+            )?
+            .collect()?;
+        assert_eq!(df.get_column_names(), &["shop", "double"]);
+        assert_eq!(Vec::from(df.column("double")?.i32()?), &[Some(10), Some(6)]);
+
+        let df = ctx
+            .execute("SELECT * FROM sales LIMIT 2 OFFSET 3")?
+            .collect()?;
+        assert_eq!(df.shape(), (2, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sql_groupby() -> Result<()> {
+        let ctx = get_context();
+        let df = ctx
+            .execute(
+                "SELECT sum(amount) AS total, shop, count(*) AS n FROM sales \
+                 GROUP BY shop ORDER BY shop",
+            )?
+            .collect()?;
+        assert_eq!(df.get_column_names(), &["total", "shop", "n"]);
+        assert_eq!(
+            Vec::from(df.column("total")?.i32()?),
+            &[Some(4), Some(7), Some(4)]
+        );
+        assert_eq!(
+            Vec::from(df.column("shop")?.utf8()?),
+            &[Some("a"), Some("b"), Some("c")]
+        );
+        assert_eq!(
+            Vec::from(df.column("n")?.idx()?),
+            &[Some(2), Some(2), Some(1)]
+        );
+
+        let df = ctx.execute("SELECT count(*) FROM sales")?.collect()?;
+        assert_eq!(df.get_column_names(), &["count"]);
+        assert_eq!(df.column("count")?.idx()?.get(0), Some(5));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sql_join() -> Result<()> {
+        let ctx = get_context();
+        let df = ctx
+            .execute(
+                "SELECT city, max(s.amount) AS top FROM sales s \
+                 JOIN shops ON shops.id = s.shop GROUP BY city ORDER BY city",
+            )?
+            .collect()?;
+        assert_eq!(
+            Vec::from(df.column("city")?.utf8()?),
+            &[Some("Amsterdam"), Some("Berlin")]
+        );
+        assert_eq!(Vec::from(df.column("top")?.i32()?), &[Some(3), Some(5)]);
+
+        // the clashing columns of the right table have the join suffix
+        let df = ctx
+            .execute(
+                "SELECT s.shop, s.amount, t.amount AS target FROM sales s \
+                 JOIN targets t ON s.shop = t.shop ORDER BY s.amount",
+            )?
+            .collect()?;
+        assert_eq!(df.get_column_names(), &["shop", "amount", "target"]);
+        assert_eq!(
+            Vec::from(df.column("target")?.i32()?),
+            &[Some(10), Some(20), Some(10), Some(20)]
+        );
+        assert!(ctx
+            .execute("SELECT amount FROM sales JOIN targets ON sales.shop = targets.shop")
+            .is_err());
+
+        assert!(ctx.execute("SELECT * FROM unknown").is_err());
+        assert!(ctx.execute("SELECT shop FROM sales HAVING").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,536 @@
+//! Tokenizer and recursive descent parser for the subset of SQL that is supported by the
+//! [SQLContext](super::SQLContext).
+use crate::prelude::*;
+use polars_core::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Unquoted identifier or keyword.
+    Word(String),
+    /// Identifier between double quotes, e.g. `"sepal.length"`.
+    QuotedIdent(String),
+    Number(String),
+    /// String literal between single quotes.
+    Str(String),
+    Symbol(&'static str),
+}
+
+const KEYWORDS: &[&str] = &[
+    "AND", "AS", "ASC", "BY", "DESC", "DISTINCT", "FALSE", "FROM", "FULL", "GROUP", "HAVING",
+    "INNER", "IS", "JOIN", "LEFT", "LIMIT", "NOT", "NULL", "OFFSET", "ON", "OR", "ORDER", "OUTER",
+    "SELECT", "TRUE", "WHERE",
+];
+
+pub(super) fn sql_err<S: Into<String>>(msg: S) -> PolarsError {
+    PolarsError::Other(format!("SQL: {}", msg.into()).into())
+}
+
+fn tokenize(sql: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Word(chars[start..i].iter().collect()));
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            tokens.push(Token::Number(chars[start..i].iter().collect()));
+        } else if c == '\'' || c == '"' {
+            // a quote is escaped by repeating it
+            let mut value = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => return Err(sql_err(format!("unterminated quote in: {}", sql))),
+                    Some(&ch) if ch == c && chars.get(i + 1) == Some(&c) => {
+                        value.push(c);
+                        i += 2;
+                    }
+                    Some(&ch) if ch == c => {
+                        i += 1;
+                        break;
+                    }
+                    Some(&ch) => {
+                        value.push(ch);
+                        i += 1;
+                    }
+                }
+            }
+            tokens.push(match c {
+                '\'' => Token::Str(value),
+                _ => Token::QuotedIdent(value),
+            });
+        } else {
+            let symbol = match (c, chars.get(i + 1)) {
+                ('<', Some('=')) => "<=",
+                ('>', Some('=')) => ">=",
+                ('!', Some('=')) | ('<', Some('>')) => "!=",
+                (',', _) => ",",
+                ('(', _) => "(",
+                (')', _) => ")",
+                ('*', _) => "*",
+                ('.', _) => ".",
+                ('=', _) => "=",
+                ('<', _) => "<",
+                ('>', _) => ">",
+                ('+', _) => "+",
+                ('-', _) => "-",
+                ('/', _) => "/",
+                ('%', _) => "%",
+                (';', _) => ";",
+                _ => return Err(sql_err(format!("unexpected character '{}'", c))),
+            };
+            i += symbol.len();
+            tokens.push(Token::Symbol(symbol));
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum SqlExpr {
+    Column {
+        table: Option<String>,
+        name: String,
+    },
+    Wildcard,
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Bool(bool),
+    Null,
+    Binary {
+        left: Box<SqlExpr>,
+        op: Operator,
+        right: Box<SqlExpr>,
+    },
+    Not(Box<SqlExpr>),
+    Neg(Box<SqlExpr>),
+    IsNull {
+        expr: Box<SqlExpr>,
+        negated: bool,
+    },
+    Function {
+        /// Lowercase name of the function.
+        name: String,
+        distinct: bool,
+        args: Vec<SqlExpr>,
+    },
+}
+
+#[derive(Debug)]
+pub(super) struct SelectItem {
+    pub(super) expr: SqlExpr,
+    pub(super) alias: Option<String>,
+}
+
+#[derive(Debug)]
+pub(super) struct TableRef {
+    pub(super) name: String,
+    pub(super) alias: Option<String>,
+}
+
+#[derive(Debug)]
+pub(super) struct JoinClause {
+    pub(super) how: JoinType,
+    pub(super) table: TableRef,
+    pub(super) on: SqlExpr,
+}
+
+#[derive(Debug)]
+pub(super) struct Query {
+    pub(super) distinct: bool,
+    pub(super) projection: Vec<SelectItem>,
+    pub(super) from: TableRef,
+    pub(super) joins: Vec<JoinClause>,
+    pub(super) selection: Option<SqlExpr>,
+    pub(super) group_by: Vec<SqlExpr>,
+    /// The expressions to sort by and whether they are sorted descending.
+    pub(super) order_by: Vec<(SqlExpr, bool)>,
+    pub(super) limit: Option<usize>,
+    pub(super) offset: Option<usize>,
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(w)) if w.eq_ignore_ascii_case(keyword))
+    }
+
+    /// Consume the keyword if it is the next token.
+    fn parse_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        if self.parse_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(sql_err(format!(
+                "expected {}, found {:?}",
+                keyword,
+                self.peek()
+            )))
+        }
+    }
+
+    /// Consume the symbol if it is the next token.
+    fn parse_symbol(&mut self, symbol: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Symbol(s)) if *s == symbol);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<()> {
+        if self.parse_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(sql_err(format!(
+                "expected '{}', found {:?}",
+                symbol,
+                self.peek()
+            )))
+        }
+    }
+
+    fn parse_identifier(&mut self) -> Result<String> {
+        match self.next() {
+            Some(Token::Word(w)) if !is_keyword(&w) => Ok(w),
+            Some(Token::QuotedIdent(w)) => Ok(w),
+            token => Err(sql_err(format!(
+                "expected an identifier, found {:?}",
+                token
+            ))),
+        }
+    }
+
+    /// Parse an optional `[AS] alias`.
+    fn parse_alias(&mut self) -> Result<Option<String>> {
+        if self.parse_keyword("AS") {
+            return self.parse_identifier().map(Some);
+        }
+        match self.peek() {
+            Some(Token::Word(w)) if !is_keyword(w) => self.parse_identifier().map(Some),
+            Some(Token::QuotedIdent(_)) => self.parse_identifier().map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    fn parse_usize(&mut self) -> Result<usize> {
+        match self.next() {
+            Some(Token::Number(n)) => n
+                .parse()
+                .map_err(|_| sql_err(format!("expected a positive integer, found {}", n))),
+            token => Err(sql_err(format!("expected a number, found {:?}", token))),
+        }
+    }
+
+    fn parse_comma_separated<T, F>(&mut self, mut f: F) -> Result<Vec<T>>
+    where
+        F: FnMut(&mut Self) -> Result<T>,
+    {
+        let mut values = vec![f(self)?];
+        while self.parse_symbol(",") {
+            values.push(f(self)?);
+        }
+        Ok(values)
+    }
+
+    fn parse_query(&mut self) -> Result<Query> {
+        self.expect_keyword("SELECT")?;
+        let distinct = self.parse_keyword("DISTINCT");
+        let projection = self.parse_comma_separated(|p| {
+            let expr = p.parse_expr()?;
+            let alias = p.parse_alias()?;
+            Ok(SelectItem { expr, alias })
+        })?;
+
+        self.expect_keyword("FROM")?;
+        let from = self.parse_table()?;
+
+        let mut joins = vec![];
+        loop {
+            let how = if self.parse_keyword("JOIN") {
+                JoinType::Inner
+            } else if self.parse_keyword("INNER") {
+                self.expect_keyword("JOIN")?;
+                JoinType::Inner
+            } else if self.parse_keyword("LEFT") {
+                self.parse_keyword("OUTER");
+                self.expect_keyword("JOIN")?;
+                JoinType::Left
+            } else if self.parse_keyword("FULL") || self.is_keyword("OUTER") {
+                self.parse_keyword("OUTER");
+                self.expect_keyword("JOIN")?;
+                JoinType::Outer
+            } else {
+                break;
+            };
+            let table = self.parse_table()?;
+            self.expect_keyword("ON")?;
+            let on = self.parse_expr()?;
+            joins.push(JoinClause { how, table, on });
+        }
+
+        let selection = if self.parse_keyword("WHERE") {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
+
+        let group_by = if self.parse_keyword("GROUP") {
+            self.expect_keyword("BY")?;
+            self.parse_comma_separated(Self::parse_expr)?
+        } else {
+            vec![]
+        };
+        if self.is_keyword("HAVING") {
+            return Err(sql_err("HAVING is not supported"));
+        }
+
+        let order_by = if self.parse_keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            self.parse_comma_separated(|p| {
+                let expr = p.parse_expr()?;
+                let descending = if p.parse_keyword("DESC") {
+                    true
+                } else {
+                    p.parse_keyword("ASC");
+                    false
+                };
+                Ok((expr, descending))
+            })?
+        } else {
+            vec![]
+        };
+
+        let limit = if self.parse_keyword("LIMIT") {
+            Some(self.parse_usize()?)
+        } else {
+            None
+        };
+        let offset = if self.parse_keyword("OFFSET") {
+            Some(self.parse_usize()?)
+        } else {
+            None
+        };
+
+        self.parse_symbol(";");
+        if let Some(token) = self.peek() {
+            return Err(sql_err(format!("unexpected {:?}", token)));
+        }
+        Ok(Query {
+            distinct,
+            projection,
+            from,
+            joins,
+            selection,
+            group_by,
+            order_by,
+            limit,
+            offset,
+        })
+    }
+
+    fn parse_table(&mut self) -> Result<TableRef> {
+        let name = self.parse_identifier()?;
+        let alias = self.parse_alias()?;
+        Ok(TableRef { name, alias })
+    }
+
+    fn parse_expr(&mut self) -> Result<SqlExpr> {
+        let mut left = self.parse_and()?;
+        while self.parse_keyword("OR") {
+            let right = self.parse_and()?;
+            left = binary(left, Operator::Or, right);
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<SqlExpr> {
+        let mut left = self.parse_not()?;
+        while self.parse_keyword("AND") {
+            let right = self.parse_not()?;
+            left = binary(left, Operator::And, right);
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<SqlExpr> {
+        if self.parse_keyword("NOT") {
+            Ok(SqlExpr::Not(Box::new(self.parse_not()?)))
+        } else {
+            self.parse_comparison()
+        }
+    }
+
+    fn parse_comparison(&mut self) -> Result<SqlExpr> {
+        let left = self.parse_additive()?;
+        if self.parse_keyword("IS") {
+            let negated = self.parse_keyword("NOT");
+            self.expect_keyword("NULL")?;
+            return Ok(SqlExpr::IsNull {
+                expr: Box::new(left),
+                negated,
+            });
+        }
+        let op = match self.peek() {
+            Some(Token::Symbol("=")) => Operator::Eq,
+            Some(Token::Symbol("!=")) => Operator::NotEq,
+            Some(Token::Symbol("<")) => Operator::Lt,
+            Some(Token::Symbol("<=")) => Operator::LtEq,
+            Some(Token::Symbol(">")) => Operator::Gt,
+            Some(Token::Symbol(">=")) => Operator::GtEq,
+            _ => return Ok(left),
+        };
+        self.pos += 1;
+        let right = self.parse_additive()?;
+        Ok(binary(left, op, right))
+    }
+
+    fn parse_additive(&mut self) -> Result<SqlExpr> {
+        let mut left = self.parse_multiplicative()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Symbol("+")) => Operator::Plus,
+                Some(Token::Symbol("-")) => Operator::Minus,
+                _ => return Ok(left),
+            };
+            self.pos += 1;
+            let right = self.parse_multiplicative()?;
+            left = binary(left, op, right);
+        }
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<SqlExpr> {
+        let mut left = self.parse_unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Symbol("*")) => Operator::Multiply,
+                Some(Token::Symbol("/")) => Operator::Divide,
+                Some(Token::Symbol("%")) => Operator::Modulus,
+                _ => return Ok(left),
+            };
+            self.pos += 1;
+            let right = self.parse_unary()?;
+            left = binary(left, op, right);
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<SqlExpr> {
+        if self.parse_symbol("-") {
+            Ok(match self.parse_unary()? {
+                SqlExpr::Int(v) => SqlExpr::Int(-v),
+                SqlExpr::Float(v) => SqlExpr::Float(-v),
+                expr => SqlExpr::Neg(Box::new(expr)),
+            })
+        } else {
+            self.parse_primary()
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<SqlExpr> {
+        match self.next() {
+            Some(Token::Number(n)) => {
+                if n.contains('.') {
+                    n.parse()
+                        .map(SqlExpr::Float)
+                        .map_err(|_| sql_err(format!("invalid number {}", n)))
+                } else {
+                    n.parse()
+                        .map(SqlExpr::Int)
+                        .map_err(|_| sql_err(format!("invalid number {}", n)))
+                }
+            }
+            Some(Token::Str(s)) => Ok(SqlExpr::Str(s)),
+            Some(Token::Symbol("*")) => Ok(SqlExpr::Wildcard),
+            Some(Token::Symbol("(")) => {
+                let expr = self.parse_expr()?;
+                self.expect_symbol(")")?;
+                Ok(expr)
+            }
+            Some(Token::Word(w)) if w.eq_ignore_ascii_case("TRUE") => Ok(SqlExpr::Bool(true)),
+            Some(Token::Word(w)) if w.eq_ignore_ascii_case("FALSE") => Ok(SqlExpr::Bool(false)),
+            Some(Token::Word(w)) if w.eq_ignore_ascii_case("NULL") => Ok(SqlExpr::Null),
+            Some(Token::Word(w)) if !is_keyword(&w) && self.parse_symbol("(") => {
+                let distinct = self.parse_keyword("DISTINCT");
+                let args = if self.parse_symbol(")") {
+                    vec![]
+                } else {
+                    let args = self.parse_comma_separated(Self::parse_expr)?;
+                    self.expect_symbol(")")?;
+                    args
+                };
+                Ok(SqlExpr::Function {
+                    name: w.to_lowercase(),
+                    distinct,
+                    args,
+                })
+            }
+            Some(Token::Word(w)) if !is_keyword(&w) => self.parse_column(w),
+            Some(Token::QuotedIdent(w)) => self.parse_column(w),
+            token => Err(sql_err(format!("unexpected {:?}", token))),
+        }
+    }
+
+    /// Parse a column that may be qualified with its table, e.g. `t.a` or `t.*`.
+    fn parse_column(&mut self, name: String) -> Result<SqlExpr> {
+        if !self.parse_symbol(".") {
+            return Ok(SqlExpr::Column { table: None, name });
+        }
+        if self.parse_symbol("*") {
+            return Ok(SqlExpr::Wildcard);
+        }
+        Ok(SqlExpr::Column {
+            table: Some(name),
+            name: self.parse_identifier()?,
+        })
+    }
+}
+
+fn is_keyword(word: &str) -> bool {
+    KEYWORDS.iter().any(|kw| kw.eq_ignore_ascii_case(word))
+}
+
+fn binary(left: SqlExpr, op: Operator, right: SqlExpr) -> SqlExpr {
+    SqlExpr::Binary {
+        left: Box::new(left),
+        op,
+        right: Box::new(right),
+    }
+}
+
+pub(super) fn parse_query(sql: &str) -> Result<Query> {
+    let tokens = tokenize(sql)?;
+    Parser { tokens, pos: 0 }.parse_query()
+}
@@ -6,6 +6,28 @@ use polars_core::prelude::*;
 use std::collections::HashSet;
 use std::sync::Arc;
 
+/// Expand a glob pattern to the matching paths in alphabetical order. A path without glob
+/// characters is returned as is.
+pub(crate) fn expand_paths(path: &str) -> Result<Vec<String>> {
+    if !path.contains(|c| c == '*' || c == '?' || c == '[') {
+        return Ok(vec![path.to_string()]);
+    }
+    let paths = glob::glob(path)
+        .map_err(|e| {
+            PolarsError::ValueError(format!("invalid glob pattern {}: {}", path, e).into())
+        })?
+        .map(|entry| {
+            entry
+                .map(|path| path.to_string_lossy().into_owned())
+                .map_err(|e| PolarsError::Io(e.into_error()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if paths.is_empty() {
+        return Err(PolarsError::NotFound(format!("no files match {}", path)));
+    }
+    Ok(paths)
+}
+
 pub(crate) fn has_aexpr(current_node: Node, arena: &Arena<AExpr>, matching_expr: &AExpr) -> bool {
     arena.iter(current_node).any(|(_node, e)| match e {
         AExpr::Agg(_) => false,
@@ -43,7 +65,7 @@ pub(crate) fn output_name(expr: &Expr) -> Result<Arc<String>> {
 }
 
 pub(crate) fn rename_field(field: &Field, name: &str) -> Field {
-    Field::new(name, field.data_type().clone())
+    Field::new(name, field.data_type().clone()).with_metadata(field.metadata().clone())
 }
 
 /// This should gradually replace expr_to_root_column as this will get all names in the tree.
@@ -95,8 +117,9 @@ pub(crate) fn rename_aexpr_root_name(
         1 => {
             let node = roots[0];
             arena.replace_with(node, |ae| match ae {
-                AExpr::Column(_) => AExpr::Column(new_name),
-                _ => panic!("should be only a column"),
+                AExpr::Column(_) | AExpr::Wildcard => AExpr::Column(new_name),
+                // the roots are columns or wildcards
+                _ => unreachable!(),
             });
             Ok(())
         }
@@ -104,6 +127,29 @@ pub(crate) fn rename_aexpr_root_name(
     }
 }
 
+/// Rename the root columns of the expression that are in `existing` to the name at the same
+/// position in `new`. All columns are renamed at once, so names may be swapped.
+pub(crate) fn rename_aexpr_root_names(
+    node: Node,
+    arena: &mut Arena<AExpr>,
+    existing: &[String],
+    new: &[String],
+) {
+    let mut roots = aexpr_to_root_nodes(node, arena);
+    // a root may be shared and should only be renamed once
+    roots.sort_by_key(|node| node.0);
+    roots.dedup();
+    for root in roots {
+        arena.replace_with(root, |ae| match ae {
+            AExpr::Column(name) => match existing.iter().position(|e| e == &*name) {
+                Some(idx) => AExpr::Column(Arc::new(new[idx].clone())),
+                None => AExpr::Column(name),
+            },
+            ae => ae,
+        });
+    }
+}
+
 /// Get all root column expressions in the expression tree.
 pub(crate) fn expr_to_root_column_exprs(expr: &Expr) -> Vec<Expr> {
     let mut out = vec![];
@@ -123,13 +169,16 @@ pub(crate) fn rename_expr_root_name(expr: &Expr, new_name: Arc<String>) -> Resul
     Ok(node_to_exp(root, &arena))
 }
 
-pub(crate) fn expressions_to_schema(expr: &[Expr], schema: &Schema, ctxt: Context) -> Schema {
+pub(crate) fn expressions_to_schema(
+    expr: &[Expr],
+    schema: &Schema,
+    ctxt: Context,
+) -> Result<Schema> {
     let fields = expr
         .iter()
         .map(|expr| expr.to_field(schema, ctxt))
-        .collect::<Result<Vec<_>>>()
-        .unwrap();
-    Schema::new(fields)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Schema::new(fields))
 }
 
 /// Get a set of the data source paths in this LogicalPlan
@@ -147,7 +196,9 @@ pub(crate) fn agg_source_paths(
         Selection { input, .. } => {
             agg_source_paths(*input, paths, lp_arena);
         }
-        Cache { input } => {
+        Cache { input, id } => {
+            // branches that share a cache have a common source
+            paths.insert(format!("cache_{}", id));
             agg_source_paths(*input, paths, lp_arena);
         }
         CsvScan { path, .. } => {
@@ -157,6 +208,10 @@ pub(crate) fn agg_source_paths(
         ParquetScan { path, .. } => {
             paths.insert(path.clone());
         }
+        #[cfg(feature = "ipc")]
+        IpcScan { path, .. } => {
+            paths.insert(path.clone());
+        }
         DataFrameScan { .. } => (),
         Projection { input, .. } => {
             agg_source_paths(*input, paths, lp_arena);
@@ -170,6 +225,9 @@ pub(crate) fn agg_source_paths(
         Explode { input, .. } => {
             agg_source_paths(*input, paths, lp_arena);
         }
+        Rename { input, .. } => {
+            agg_source_paths(*input, paths, lp_arena);
+        }
         Distinct { input, .. } => {
             agg_source_paths(*input, paths, lp_arena);
         }
@@ -184,6 +242,24 @@ pub(crate) fn agg_source_paths(
             agg_source_paths(*input_left, paths, lp_arena);
             agg_source_paths(*input_right, paths, lp_arena);
         }
+        SetOp {
+            input_left,
+            input_right,
+            ..
+        }
+        | JoinWhere {
+            input_left,
+            input_right,
+            ..
+        } => {
+            agg_source_paths(*input_left, paths, lp_arena);
+            agg_source_paths(*input_right, paths, lp_arena);
+        }
+        Union { inputs } => {
+            for input in inputs {
+                agg_source_paths(*input, paths, lp_arena);
+            }
+        }
         HStack { input, .. } => {
             agg_source_paths(*input, paths, lp_arena);
         }
@@ -193,6 +269,9 @@ pub(crate) fn agg_source_paths(
         Udf { input, .. } => {
             agg_source_paths(*input, paths, lp_arena);
         }
+        Sink { input, .. } => {
+            agg_source_paths(*input, paths, lp_arena);
+        }
     }
 }
 pub(crate) fn aexpr_to_root_names(node: Node, arena: &Arena<AExpr>) -> Vec<Arc<String>> {
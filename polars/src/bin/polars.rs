@@ -0,0 +1,335 @@
+//! Command line tool to inspect and query CSV and parquet files.
+//!
+//! ```text
+//! polars sales.csv --filter "price > 10" --groupby region --agg sum:price,count:id --sort price:desc
+//! ```
+use polars::prelude::*;
+use std::fs::File;
+use std::path::Path;
+
+const USAGE: &str = "\
+Query a CSV or parquet file and print the result.
+
+USAGE:
+    polars [OPTIONS] <FILE>
+
+OPTIONS:
+    -s, --select <COLUMNS>     Comma separated columns to keep
+    -f, --filter <PREDICATE>   Keep the rows where `<column> <op> <value>` holds, with op one of
+                               == != < <= > >=. Can be repeated, all predicates must hold
+    -g, --groupby <COLUMNS>    Comma separated columns to group by
+    -a, --agg <AGGREGATIONS>   Comma separated `<function>:<column>` aggregations of the groups,
+                               with function one of sum min max mean median first last count
+                               n_unique
+        --sort <COLUMN>        Sort by a column, append `:desc` to sort in descending order
+    -n, --head <N>             Only keep the first N rows
+    -o, --output <FILE>        Write the result to a .csv or .parquet file instead of printing it
+        --schema               Print the column names and data types of the file
+        --plan                 Print the optimized query plan instead of running the query
+    -h, --help                 Print this message
+";
+
+/// The operations of a query, in the order in which they are applied.
+#[derive(Debug, Default, PartialEq)]
+struct Query {
+    input: String,
+    select: Vec<String>,
+    filters: Vec<String>,
+    groupby: Vec<String>,
+    aggs: Vec<String>,
+    sort: Option<String>,
+    head: Option<usize>,
+    output: Option<String>,
+    schema: bool,
+    plan: bool,
+}
+
+fn invalid(msg: String) -> PolarsError {
+    PolarsError::InvalidOperation(msg.into())
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Parse the command line arguments, without the program name. Returns `None` if the usage
+/// should be printed.
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Option<Query>> {
+    let mut query = Query::default();
+    let mut input = None;
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| invalid(format!("missing value of {}", name)))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-s" | "--select" => query.select = split_list(&value(&arg)?),
+            "-f" | "--filter" => query.filters.push(value(&arg)?),
+            "-g" | "--groupby" => query.groupby = split_list(&value(&arg)?),
+            "-a" | "--agg" => query.aggs = split_list(&value(&arg)?),
+            "--sort" => query.sort = Some(value(&arg)?),
+            "-n" | "--head" => {
+                let n = value(&arg)?;
+                let n = n
+                    .parse()
+                    .map_err(|_| invalid(format!("{} is not a number of rows", n)))?;
+                query.head = Some(n)
+            }
+            "-o" | "--output" => query.output = Some(value(&arg)?),
+            "--schema" => query.schema = true,
+            "--plan" => query.plan = true,
+            _ if arg.starts_with('-') => return Err(invalid(format!("unknown option {}", arg))),
+            _ => {
+                if input.replace(arg).is_some() {
+                    return Err(invalid("only a single input file is supported".into()));
+                }
+            }
+        }
+    }
+    match input {
+        Some(input) => {
+            query.input = input;
+            Ok(Some(query))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Parse a literal of a predicate. Numbers are parsed as integers or floats, other values are
+/// strings, optionally in single or double quotes.
+fn parse_literal(value: &str) -> Expr {
+    if let Ok(v) = value.parse::<i64>() {
+        lit(v)
+    } else if let Ok(v) = value.parse::<f64>() {
+        lit(v)
+    } else {
+        let unquoted = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+            .unwrap_or(value);
+        lit(unquoted)
+    }
+}
+
+/// Parse a predicate `<column> <op> <value>`.
+fn parse_predicate(predicate: &str) -> Result<Expr> {
+    // two character operators first, so that `<=` isn't parsed as `<`
+    const OPS: [&str; 6] = ["==", "!=", "<=", ">=", "<", ">"];
+    let (idx, op) = OPS
+        .iter()
+        .filter_map(|op| predicate.find(op).map(|idx| (idx, *op)))
+        .min_by_key(|(idx, op)| (*idx, std::cmp::Reverse(op.len())))
+        .ok_or_else(|| invalid(format!("no comparison operator in predicate {}", predicate)))?;
+    let column = predicate[..idx].trim();
+    let value = predicate[idx + op.len()..].trim();
+    if column.is_empty() || value.is_empty() {
+        return Err(invalid(format!("cannot parse predicate {}", predicate)));
+    }
+    let (left, right) = (col(column), parse_literal(value));
+    Ok(match op {
+        "==" => left.eq(right),
+        "!=" => left.neq(right),
+        "<=" => left.lt_eq(right),
+        ">=" => left.gt_eq(right),
+        "<" => left.lt(right),
+        _ => left.gt(right),
+    })
+}
+
+/// Parse an aggregation `<function>:<column>`.
+fn parse_agg(agg: &str) -> Result<Expr> {
+    let mut parts = agg.splitn(2, ':');
+    let (function, column) = match (parts.next(), parts.next()) {
+        (Some(function), Some(column)) => (function, col(column)),
+        _ => return Err(invalid(format!("cannot parse aggregation {}", agg))),
+    };
+    Ok(match function {
+        "sum" => column.sum(),
+        "min" => column.min(),
+        "max" => column.max(),
+        "mean" => column.mean(),
+        "median" => column.median(),
+        "first" => column.first(),
+        "last" => column.last(),
+        "count" => column.count(),
+        "n_unique" => column.n_unique(),
+        _ => return Err(invalid(format!("unknown aggregation {}", function))),
+    })
+}
+
+fn extension(path: &str) -> Option<&str> {
+    Path::new(path).extension().and_then(|ext| ext.to_str())
+}
+
+fn scan(path: &str) -> Result<LazyFrame> {
+    match extension(path) {
+        Some("parquet") => Ok(LazyFrame::new_from_parquet(path.into(), None, false)),
+        Some("csv") | Some("tsv") | Some("txt") => {
+            let delimiter = if extension(path) == Some("tsv") {
+                b'\t'
+            } else {
+                b','
+            };
+            Ok(LazyCsvReader::new(path.into())
+                .with_delimiter(delimiter)
+                .with_cache(false)
+                .finish())
+        }
+        _ => Err(invalid(format!(
+            "cannot read {}, only csv and parquet files are supported",
+            path
+        ))),
+    }
+}
+
+/// Build the lazy query of `query` on the file `query.input`.
+fn build(query: &Query) -> Result<LazyFrame> {
+    let mut lf = scan(&query.input)?;
+    for predicate in &query.filters {
+        lf = lf.filter(parse_predicate(predicate)?);
+    }
+    if !query.groupby.is_empty() {
+        let aggs = query
+            .aggs
+            .iter()
+            .map(|agg| parse_agg(agg))
+            .collect::<Result<Vec<_>>>()?;
+        if aggs.is_empty() {
+            return Err(invalid("a groupby needs at least one aggregation".into()));
+        }
+        let keys = query.groupby.iter().map(|name| col(name)).collect();
+        lf = lf.groupby(keys).agg(aggs);
+    } else if !query.aggs.is_empty() {
+        return Err(invalid("aggregations need a groupby".into()));
+    }
+    if !query.select.is_empty() {
+        lf = lf.select(
+            query
+                .select
+                .iter()
+                .map(|name| col(name))
+                .collect::<Vec<_>>(),
+        );
+    }
+    if let Some(sort) = &query.sort {
+        lf = match sort.strip_suffix(":desc") {
+            Some(column) => lf.sort(column, true),
+            None => lf.sort(sort.strip_suffix(":asc").unwrap_or(sort), false),
+        };
+    }
+    if let Some(n) = query.head {
+        lf = lf.limit(n);
+    }
+    Ok(lf)
+}
+
+fn write(df: &mut DataFrame, path: &str) -> Result<()> {
+    let mut file = File::create(path)?;
+    match extension(path) {
+        Some("parquet") => ParquetWriter::new(file).finish(df),
+        Some("csv") => CsvWriter::new(&mut file).finish(df),
+        _ => Err(invalid(format!(
+            "cannot write {}, only csv and parquet files are supported",
+            path
+        ))),
+    }
+}
+
+fn run(query: &Query) -> Result<()> {
+    if query.schema {
+        let df = scan(&query.input)?.limit(0).collect()?;
+        for field in df.schema().fields() {
+            println!("{}: {:?}", field.name(), field.data_type());
+        }
+        return Ok(());
+    }
+    let lf = build(query)?;
+    if query.plan {
+        println!("{}", lf.describe_optimized_plan()?);
+        return Ok(());
+    }
+    let mut df = lf.collect()?;
+    match &query.output {
+        Some(path) => write(&mut df, path),
+        None => {
+            println!("{}", df);
+            Ok(())
+        }
+    }
+}
+
+fn main() {
+    let result = parse_args(std::env::args().skip(1)).and_then(|query| match query {
+        Some(query) => run(&query),
+        None => {
+            print!("{}", USAGE);
+            Ok(())
+        }
+    });
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn test_parse_args() -> Result<()> {
+        let query = parse_args(args(&[
+            "data.csv", "-f", "a >= 1", "-g", "b, c", "-a", "sum:a", "-n", "5",
+        ]))?
+        .unwrap();
+        assert_eq!(
+            query,
+            Query {
+                input: "data.csv".into(),
+                filters: vec!["a >= 1".into()],
+                groupby: vec!["b".into(), "c".into()],
+                aggs: vec!["sum:a".into()],
+                head: Some(5),
+                ..Default::default()
+            }
+        );
+        assert_eq!(parse_args(args(&["--help", "data.csv"]))?, None);
+        assert!(parse_args(args(&["data.csv", "--head"])).is_err());
+        assert!(parse_args(args(&["a.csv", "b.csv"])).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_predicate() -> Result<()> {
+        let df = df![
+            "a" => &[1, 2, 3],
+            "b" => &["x", "y", "z"]
+        ]?;
+        let filter = |predicate: &str| -> Result<DataFrame> {
+            df.clone()
+                .lazy()
+                .filter(parse_predicate(predicate)?)
+                .collect()
+        };
+        assert_eq!(filter("a <= 2")?.height(), 2);
+        assert_eq!(filter("a<2")?.height(), 1);
+        assert_eq!(filter("b != \"y\"")?.height(), 2);
+        assert_eq!(filter("b == z")?.height(), 1);
+        assert!(parse_predicate("a").is_err());
+        assert!(parse_agg("mode:a").is_err());
+        Ok(())
+    }
+}
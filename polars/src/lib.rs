@@ -216,7 +216,7 @@
 //! * `json`
 //!     - Json serialization
 //! * `ipc`
-//!     - Arrow's IPC format serialization
+//!     - Arrow's IPC format serialization and lazy scans of IPC (Feather v2) files
 //! * `random`
 //!     - Generate array's with randomly sampled values
 //! * `ndarray`
@@ -230,6 +230,12 @@
 //! * `object`
 //!     - Support for generic ChunkedArray's called `ObjectChunked<T>` (generic over `T`).
 //!       These will downcastable from Series through the [Any](https://doc.rust-lang.org/std/any/index.html) trait.
+//! * `bigidx`
+//!     - Use `u64` instead of `u32` for the row indices of joins and groupbys.
+//!       Needed for DataFrames with more than 2^32 rows.
+//! * `cli`
+//!     - The `polars` binary to query csv and parquet files from the command line,
+//!       e.g. `polars data.csv --filter "a > 1" --groupby b --agg sum:a`.
 //!
 //!
 pub mod docs;
@@ -241,6 +247,8 @@ pub use polars_core::{
 
 pub use polars_core::apply_method_all_arrow_series;
 pub use polars_core::df;
+#[cfg(feature = "random")]
+pub use polars_core::rand;
 
 pub use polars_io as io;
 #[cfg(feature = "lazy")]
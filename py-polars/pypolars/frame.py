@@ -897,6 +897,9 @@ class DataFrame:
         right_on: "Optional[Union[str, List[str]]]" = None,
         on: "Optional[Union[str, List[str]]]" = None,
         how="inner",
+        suffix: str = "_right",
+        validate: str = "m:m",
+        indicator: bool = False,
     ) -> "DataFrame":
         """
         SQL like joins
@@ -916,6 +919,17 @@ class DataFrame:
                 - "inner"
                 - "left"
                 - "outer"
+        suffix
+            Suffix to append to columns of the right DataFrame that are also in the left DataFrame.
+        validate
+            Check that the join keys are unique, raises if they are not
+                - "m:m": no check
+                - "1:m": unique keys in the left DataFrame
+                - "m:1": unique keys in the right DataFrame
+                - "1:1": unique keys in both DataFrames
+        indicator
+            Add a Categorical "_merge" column that records whether a row was found in
+            "left_only", "right_only" or "both" DataFrames.
 
         Example
         ---
@@ -978,7 +992,9 @@ class DataFrame:
         if left_on is None or right_on is None:
             raise ValueError("you should pass the column to join on as an argument")
 
-        out = self._df.join(df._df, left_on, right_on, how)
+        out = self._df.join(
+            df._df, left_on, right_on, how, suffix, validate, indicator
+        )
 
         return wrap_df(out)
 
@@ -1116,6 +1132,7 @@ class DataFrame:
     def explode(self, columns: "Union[str, List[str]]") -> "DataFrame":
         """
         Explode `DataFrame` to long format by exploding a column with Lists.
+        The exploded rows keep the order of the input rows.
 
         Parameters
         ----------
@@ -1131,7 +1148,10 @@ class DataFrame:
         return wrap_df(self._df.explode(columns))
 
     def melt(
-        self, id_vars: "Union[List[str], str]", value_vars: "Union[List[str], str]"
+        self,
+        id_vars: "Union[List[str], str]",
+        value_vars: "Union[List[str], str]",
+        maintain_order: bool = False,
     ) -> "DataFrame":
         """
         Unpivot DataFrame to long format.
@@ -1144,6 +1164,10 @@ class DataFrame:
         value_vars
             Values to use as identifier variables
 
+        maintain_order
+            Keep the order of the rows: every row is followed by its values of the next
+            value variable. Otherwise the rows of the value variables are stacked.
+
         Returns
         -------
 
@@ -1152,7 +1176,7 @@ class DataFrame:
             value_vars = [value_vars]
         if isinstance(id_vars, str):
             id_vars = [id_vars]
-        return wrap_df(self._df.melt(id_vars, value_vars))
+        return wrap_df(self._df.melt(id_vars, value_vars, maintain_order))
 
     def shift(self, periods: int) -> "DataFrame":
         """
@@ -1628,6 +1652,16 @@ class GBSelection:
             return wrap_df(self._df.downsample(self.by, self.rule, self.n, "n_unique"))
         return wrap_df(self._df.groupby(self.by, self.selection, "n_unique"))
 
+    def null_count(self) -> DataFrame:
+        """
+        Count the null values per group.
+        """
+        if self.downsample:
+            return wrap_df(
+                self._df.downsample(self.by, self.rule, self.n, "null_count")
+            )
+        return wrap_df(self._df.groupby(self.by, self.selection, "null_count"))
+
     def quantile(self, quantile: float) -> DataFrame:
         """
         Compute the quantile per group
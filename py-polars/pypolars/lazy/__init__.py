@@ -301,6 +301,9 @@ class LazyFrame:
         how="inner",
         allow_parallel: bool = True,
         force_parallel: bool = False,
+        suffix: str = "_right",
+        validate: str = "m:m",
+        indicator: bool = False,
     ) -> "LazyFrame":
         """
         Add a join operation to the Logical Plan.
@@ -324,6 +327,17 @@ class LazyFrame:
             Allow the physical plan to optionally evaluate the computation of both DataFrames up to the join in parallel.
         force_parallel
             Force the physical plan evaluate the computation of both DataFrames up to the join in parallel.
+        suffix
+            Suffix to append to columns of the right DataFrame that are also in the left DataFrame.
+        validate
+            Check that the join keys are unique when the join is executed, one of:
+                "m:m": no check
+                "1:m": unique keys in the left DataFrame
+                "m:1": unique keys in the right DataFrame
+                "1:1": unique keys in both DataFrames
+        indicator
+            Add a Categorical "_merge" column that records whether a row was found in
+            "left_only", "right_only" or "both" DataFrames.
         """
         if isinstance(left_on, str):
             left_on = [left_on]
@@ -350,7 +364,15 @@ class LazyFrame:
             new_right_on.append(column._pyexpr)
 
         out = self._ldf.join(
-            ldf._ldf, new_left_on, new_right_on, allow_parallel, force_parallel, how
+            ldf._ldf,
+            new_left_on,
+            new_right_on,
+            allow_parallel,
+            force_parallel,
+            how,
+            suffix,
+            validate,
+            indicator,
         )
 
         return wrap_ldf(out)
@@ -504,7 +526,12 @@ class LazyFrame:
 
     def explode(self, columns: "Union[str, List[str]]") -> "LazyFrame":
         """
-        Explode lists to long format
+        Explode lists to long format. The exploded rows keep the order of the input rows.
+
+        Parameters
+        ----------
+        columns
+            Columns to explode
         """
         if isinstance(columns, str):
             columns = [columns]
@@ -534,8 +561,11 @@ class LazyFrame:
         return wrap_ldf(self._ldf.drop_nulls(subset))
 
     def melt(
-        self, id_vars: "Union[List[str], str]", value_vars: "Union[List[str], str]"
-    ) -> "DataFrame":
+        self,
+        id_vars: "Union[List[str], str]",
+        value_vars: "Union[List[str], str]",
+        maintain_order: bool = False,
+    ) -> "LazyFrame":
         """
         Unpivot DataFrame to long format.
 
@@ -546,12 +576,16 @@ class LazyFrame:
 
         value_vars
             Values to use as identifier variables
+
+        maintain_order
+            Keep the order of the rows: every row is followed by its values of the next
+            value variable. Otherwise the rows of the value variables are stacked.
         """
         if isinstance(value_vars, str):
             value_vars = [value_vars]
         if isinstance(id_vars, str):
             id_vars = [id_vars]
-        return wrap_ldf(self._ldf.melt(id_vars, value_vars))
+        return wrap_ldf(self._ldf.melt(id_vars, value_vars, maintain_order))
 
     def map(
         self,
@@ -797,6 +831,10 @@ class Expr:
         """Count unique values"""
         return wrap_expr(self._pyexpr.n_unique())
 
+    def null_count(self) -> "Expr":
+        """Count null values"""
+        return wrap_expr(self._pyexpr.null_count())
+
     def first(self) -> "Expr":
         """
         Get first value
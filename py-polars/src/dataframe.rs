@@ -4,7 +4,7 @@ use pyo3::{exceptions::PyRuntimeError, prelude::*};
 use crate::datatypes::PyDataType;
 use crate::file::FileLike;
 use crate::lazy::dataframe::PyLazyFrame;
-use crate::utils::str_to_polarstype;
+use crate::utils::{str_to_join_type, str_to_join_validation, str_to_polarstype};
 use crate::{
     arrow_interop,
     error::PyPolarsEr,
@@ -261,23 +261,27 @@ impl PyDataFrame {
         Ok(PyDataFrame::new(df))
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn join(
         &self,
         other: &PyDataFrame,
         left_on: Vec<&str>,
         right_on: Vec<&str>,
         how: &str,
+        suffix: String,
+        validate: &str,
+        indicator: bool,
     ) -> PyResult<Self> {
-        let how = match how {
-            "left" => JoinType::Left,
-            "inner" => JoinType::Inner,
-            "outer" => JoinType::Outer,
-            _ => panic!("not supported"),
-        };
+        let how = str_to_join_type(how)?;
+        let validate = str_to_join_validation(validate)?;
 
+        let args = JoinArgs::new(how)
+            .with_suffix(Some(suffix))
+            .with_validation(validate)
+            .with_indicator(indicator);
         let df = self
             .df
-            .join(&other.df, left_on, right_on, how)
+            .join(&other.df, left_on, right_on, args)
             .map_err(PyPolarsEr::from)?;
         Ok(PyDataFrame::new(df))
     }
@@ -399,14 +403,17 @@ impl PyDataFrame {
         }
     }
 
-    pub fn take(&self, indices: Vec<usize>) -> Self {
-        let df = self.df.take_iter(indices.iter().copied());
-        PyDataFrame::new(df)
+    pub fn take(&self, indices: Vec<usize>) -> PyResult<Self> {
+        let df = self
+            .df
+            .take_iter_checked(indices.iter().copied())
+            .map_err(PyPolarsEr::from)?;
+        Ok(PyDataFrame::new(df))
     }
 
     pub fn take_with_series(&self, indices: &PySeries) -> PyResult<Self> {
-        let idx = indices.series.u32().map_err(PyPolarsEr::from)?;
-        let df = self.df.take(&idx);
+        let idx = indices.series.idx().map_err(PyPolarsEr::from)?;
+        let df = self.df.take_checked(&idx).map_err(PyPolarsEr::from)?;
         Ok(PyDataFrame::new(df))
     }
 
@@ -616,10 +623,15 @@ impl PyDataFrame {
         Ok(PyDataFrame::new(df))
     }
 
-    pub fn melt(&self, id_vars: Vec<&str>, value_vars: Vec<&str>) -> PyResult<Self> {
+    pub fn melt(
+        &self,
+        id_vars: Vec<&str>,
+        value_vars: Vec<&str>,
+        maintain_order: bool,
+    ) -> PyResult<Self> {
         let df = self
             .df
-            .melt(id_vars, value_vars)
+            .melt(id_vars, value_vars, maintain_order)
             .map_err(PyPolarsEr::from)?;
         Ok(PyDataFrame::new(df))
     }
@@ -677,7 +689,7 @@ impl PyDataFrame {
     }
 
     pub fn to_dummies(&self) -> PyResult<Self> {
-        let df = self.df.to_dummies().map_err(PyPolarsEr::from)?;
+        let df = self.df.to_dummies(None, "_").map_err(PyPolarsEr::from)?;
         Ok(df.into())
     }
 
@@ -697,6 +709,7 @@ fn finish_groupby(gb: GroupBy, agg: &str) -> PyResult<PyDataFrame> {
         "sum" => gb.sum(),
         "count" => gb.count(),
         "n_unique" => gb.n_unique(),
+        "null_count" => gb.null_count(),
         "median" => gb.median(),
         "agg_list" => gb.agg_list(),
         "groups" => gb.groups(),
@@ -48,7 +48,7 @@ impl From<&DataType> for PyDataType {
             DataType::Duration(TimeUnit::Nanosecond) => DurationNanosecond,
             DataType::Duration(TimeUnit::Millisecond) => DurationMillisecond,
             DataType::Object => Object,
-            DataType::Categorical => Categorical,
+            DataType::Categorical(_) => Categorical,
             dt => panic!("datatype: {:?} not supported", dt),
         }
     }
@@ -1,12 +1,12 @@
 use crate::dataframe::PyDataFrame;
 use crate::error::PyPolarsEr;
 use crate::lazy::{dsl::PyExpr, utils::py_exprs_to_exprs};
-use crate::utils::str_to_polarstype;
+use crate::utils::{str_to_join_type, str_to_join_validation, str_to_polarstype};
 use polars::lazy::frame::{
     AllowedOptimizations, JoinOptions, LazyCsvReader, LazyFrame, LazyGroupBy,
 };
 use polars::lazy::prelude::col;
-use polars::prelude::{DataFrame, Field, JoinType, Schema};
+use polars::prelude::{DataFrame, Field, Schema};
 use pyo3::prelude::*;
 
 #[pyclass]
@@ -195,6 +195,7 @@ impl PyLazyFrame {
         PyLazyGroupBy { lgb: Some(lazy_gb) }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn join(
         &mut self,
         other: PyLazyFrame,
@@ -203,25 +204,28 @@ impl PyLazyFrame {
         allow_parallel: bool,
         force_parallel: bool,
         how: &str,
-    ) -> PyLazyFrame {
-        let how = match how {
-            "left" => JoinType::Left,
-            "inner" => JoinType::Inner,
-            "outer" => JoinType::Outer,
-            _ => panic!("not supported"),
-        };
+        suffix: String,
+        validate: &str,
+        indicator: bool,
+    ) -> PyResult<PyLazyFrame> {
+        let how = str_to_join_type(how)?;
 
         let ldf = self.ldf.clone();
         let other = other.ldf;
         let options = JoinOptions {
             allow_parallel,
             force_parallel,
+            suffix: Some(suffix),
+            validate: str_to_join_validation(validate)?,
+            indicator,
+            ..Default::default()
         };
         let left_on = left_on.into_iter().map(|pyexpr| pyexpr.inner).collect();
         let right_on = right_on.into_iter().map(|pyexpr| pyexpr.inner).collect();
 
-        ldf.join(other, left_on, right_on, Some(options), how)
-            .into()
+        Ok(ldf
+            .join(other, left_on, right_on, Some(options), how)
+            .into())
     }
 
     pub fn with_column(&mut self, expr: PyExpr) -> PyLazyFrame {
@@ -316,9 +320,14 @@ impl PyLazyFrame {
         ldf.slice(offset, len).into()
     }
 
-    pub fn melt(&self, id_vars: Vec<String>, value_vars: Vec<String>) -> Self {
+    pub fn melt(
+        &self,
+        id_vars: Vec<String>,
+        value_vars: Vec<String>,
+        maintain_order: bool,
+    ) -> Self {
         let ldf = self.ldf.clone();
-        ldf.melt(id_vars, value_vars).into()
+        ldf.melt(id_vars, value_vars, maintain_order).into()
     }
 
     pub fn map(&self, lambda: PyObject, predicate_pd: bool, projection_pd: bool) -> Self {
@@ -111,6 +111,9 @@ impl PyExpr {
     pub fn n_unique(&self) -> PyExpr {
         self.clone().inner.n_unique().into()
     }
+    pub fn null_count(&self) -> PyExpr {
+        self.clone().inner.null_count().into()
+    }
     pub fn first(&self) -> PyExpr {
         self.clone().inner.first().into()
     }
@@ -366,7 +366,7 @@ impl PySeries {
         PySeries::new(self.series.sort(reverse))
     }
 
-    pub fn argsort(&self, reverse: bool) -> Py<PyArray1<u32>> {
+    pub fn argsort(&self, reverse: bool) -> Py<PyArray1<IdxSize>> {
         let gil = pyo3::Python::acquire_gil();
         let pyarray = PyArray1::from_iter(
             gil.python(),
@@ -385,21 +385,24 @@ impl PySeries {
         Ok(df.into())
     }
 
-    pub fn arg_unique(&self) -> PyResult<Py<PyArray1<u32>>> {
+    pub fn arg_unique(&self) -> PyResult<Py<PyArray1<IdxSize>>> {
         let gil = pyo3::Python::acquire_gil();
         let arg_unique = self.series.arg_unique().map_err(PyPolarsEr::from)?;
         let pyarray = PyArray1::from_vec(gil.python(), arg_unique);
         Ok(pyarray.to_owned())
     }
 
-    pub fn take(&self, indices: Vec<usize>) -> Self {
-        let take = self.series.take_iter(&mut indices.iter().copied());
-        PySeries::new(take)
+    pub fn take(&self, indices: Vec<usize>) -> PyResult<Self> {
+        let take = self
+            .series
+            .take_iter_checked(&mut indices.iter().copied())
+            .map_err(PyPolarsEr::from)?;
+        Ok(PySeries::new(take))
     }
 
     pub fn take_with_series(&self, indices: &PySeries) -> PyResult<Self> {
-        let idx = indices.series.u32().map_err(PyPolarsEr::from)?;
-        let take = self.series.take(&idx);
+        let idx = indices.series.idx().map_err(PyPolarsEr::from)?;
+        let take = self.series.take_checked(&idx).map_err(PyPolarsEr::from)?;
         Ok(PySeries::new(take))
     }
 
@@ -1,4 +1,6 @@
 use polars::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::PyResult;
 
 pub fn str_to_polarstype(s: &str) -> DataType {
     match s {
@@ -17,7 +19,35 @@ pub fn str_to_polarstype(s: &str) -> DataType {
         "<class 'pypolars.datatypes.Date32'>" => DataType::Date32,
         "<class 'pypolars.datatypes.Date64'>" => DataType::Date64,
         "<class 'pypolars.datatypes.List'>" => DataType::List(ArrowDataType::Null),
-        "<class 'pypolars.datatypes.Categorical'>" => DataType::Categorical,
+        "<class 'pypolars.datatypes.Categorical'>" => {
+            DataType::Categorical(CategoricalOrdering::Physical)
+        }
         tp => panic!("Type {} not implemented in str_to_polarstype", tp),
     }
 }
+
+pub fn str_to_join_type(s: &str) -> PyResult<JoinType> {
+    match s {
+        "left" => Ok(JoinType::Left),
+        "inner" => Ok(JoinType::Inner),
+        "outer" => Ok(JoinType::Outer),
+        "cross" => Ok(JoinType::Cross),
+        v => Err(PyValueError::new_err(format!(
+            "join type {} not supported, use one of inner, left, outer, cross",
+            v
+        ))),
+    }
+}
+
+pub fn str_to_join_validation(s: &str) -> PyResult<JoinValidation> {
+    match s {
+        "m:m" => Ok(JoinValidation::ManyToMany),
+        "1:m" => Ok(JoinValidation::OneToMany),
+        "m:1" => Ok(JoinValidation::ManyToOne),
+        "1:1" => Ok(JoinValidation::OneToOne),
+        v => Err(PyValueError::new_err(format!(
+            "join validation {} not supported, use one of 1:1, 1:m, m:1, m:m",
+            v
+        ))),
+    }
+}
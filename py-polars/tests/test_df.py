@@ -227,6 +227,30 @@ def test_join():
     assert lazy_join.shape == eager_join.shape
 
 
+def test_join_indicator():
+    df_a = DataFrame({"a": [1, 2, 3], "b": ["x", "y", "z"]})
+    df_b = DataFrame({"a": [2, 3, 4], "c": [0.5, 1.5, 2.5]})
+
+    eager_join = df_a.join(df_b, on="a", how="outer", indicator=True).sort("a")
+    assert eager_join.columns == ["a", "b", "c", "_merge"]
+    expected = ["left_only", "both", "both", "right_only"]
+    assert list(eager_join["_merge"].cast(Utf8)) == expected
+
+    lazy_join = (
+        df_a.lazy()
+        .join(df_b.lazy(), on="a", how="outer", indicator=True)
+        .collect()
+        .sort("a")
+    )
+    assert lazy_join.columns == eager_join.columns
+    assert list(lazy_join["_merge"].cast(Utf8)) == expected
+
+    with pytest.raises(ValueError):
+        df_a.join(df_b, on="a", how="sideways")
+    with pytest.raises(ValueError):
+        df_a.join(df_b, on="a", validate="2:2")
+
+
 def test_hstack():
     df = DataFrame({"a": [2, 1, 3], "b": ["a", "b", "c"]})
     df.hstack([Series("stacked", [-1, -1, -1])], in_place=True)
@@ -270,6 +294,24 @@ def test_melt():
     melted = df.melt(id_vars="A", value_vars=["B", "C"])
     assert melted["value"] == [1, 3, 4, 2, 4, 6]
 
+    melted = df.melt(id_vars="A", value_vars=["B", "C"], maintain_order=True)
+    assert list(melted["value"]) == [1, 2, 3, 4, 5, 6]
+    melted = (
+        df.lazy()
+        .melt(id_vars="A", value_vars=["B", "C"], maintain_order=True)
+        .collect()
+    )
+    assert list(melted["value"]) == [1, 2, 3, 4, 5, 6]
+
+
+def test_explode():
+    df = DataFrame({"A": ["ab", "c"], "B": [1, 2]})
+    exploded = df.explode("A")
+    assert list(exploded["A"]) == ["a", "b", "c"]
+    assert list(exploded["B"]) == [1, 1, 2]
+    exploded = df.lazy().explode("A").collect()
+    assert list(exploded["B"]) == [1, 1, 2]
+
 
 def test_shift():
     df = DataFrame({"A": ["a", "b", "c"], "B": [1, 3, 5]})